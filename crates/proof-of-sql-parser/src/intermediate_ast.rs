@@ -4,8 +4,11 @@
 * https://docs.rs/vervolg/latest/vervolg/ast/enum.Statement.html
 ***/
 
-use crate::{posql_time::PoSQLTimestamp, Identifier};
-use alloc::{boxed::Box, string::String, vec::Vec};
+use crate::{
+    posql_time::{PoSQLDateTimeField, PoSQLInterval, PoSQLTimestamp},
+    Identifier, SelectStatement,
+};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use bigdecimal::BigDecimal;
 use core::{
     fmt,
@@ -15,7 +18,7 @@ use core::{
 use serde::{Deserialize, Serialize};
 
 /// Representation of a `SetExpression`, a collection of rows, each having one or more columns.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum SetExpression {
     /// Query result as `SetExpression`
     Query {
@@ -27,11 +30,60 @@ pub enum SetExpression {
         /// If None, no filter is applied
         where_expr: Option<Box<Expression>>,
         /// Group by expressions e.g. `a` in `SELECT a, COUNT(*) FROM table GROUP BY a`
-        group_by: Vec<Identifier>,
+        /// or `a + b` in `SELECT a + b AS s, COUNT(*) FROM table GROUP BY a + b`
+        group_by: Vec<Expression>,
+        /// Filter expression on the groups e.g. `SUM(b) > 10` in `SELECT a, SUM(b) FROM table GROUP BY a HAVING SUM(b) > 10`
+        /// If None, no filter is applied
+        having: Option<Box<Expression>>,
+    },
+    /// Concatenation of the results of queries e.g. `SELECT a FROM t1 UNION ALL SELECT a FROM t2`
+    UnionAll {
+        /// The queries whose results are concatenated, in order
+        inputs: Vec<SetExpression>,
+    },
+    /// The unique rows of the result of a query e.g. `SELECT DISTINCT a FROM t`,
+    /// or `SELECT a FROM t1 UNION SELECT a FROM t2` as the unique rows of a `UNION ALL`
+    Distinct {
+        /// The query whose duplicate rows are removed
+        input: Box<SetExpression>,
     },
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+impl SetExpression {
+    /// Creates `left UNION ALL right`, appending `right` to the inputs of `left` if it is a `UNION ALL` itself
+    #[must_use]
+    pub fn union_all(left: Self, right: Self) -> Box<Self> {
+        Box::new(match left {
+            SetExpression::UnionAll { mut inputs } => {
+                inputs.push(right);
+                SetExpression::UnionAll { inputs }
+            }
+            left => SetExpression::UnionAll {
+                inputs: vec![left, right],
+            },
+        })
+    }
+
+    /// Creates `left UNION right`, i.e. the unique rows of `left UNION ALL right`
+    ///
+    /// If `left` is a `UNION` itself, `right` is appended to the inputs of its `UNION ALL`.
+    #[must_use]
+    pub fn union(left: Self, right: Self) -> Box<Self> {
+        let left = match left {
+            SetExpression::Distinct { input }
+                if matches!(*input, SetExpression::UnionAll { .. }) =>
+            {
+                *input
+            }
+            left => left,
+        };
+        Box::new(SetExpression::Distinct {
+            input: Self::union_all(left, right),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// What to select in a query
 pub enum SelectResultExpr {
     /// All columns in a table e.g. `SELECT * FROM table`
@@ -40,7 +92,7 @@ pub enum SelectResultExpr {
     AliasedResultExpr(AliasedResultExpr),
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// An expression with an alias e.g. `a + 1 AS b`
 pub struct AliasedResultExpr {
     /// The expression e.g. `a + 1`, `COUNT(*)`, etc.
@@ -71,7 +123,7 @@ impl AliasedResultExpr {
 }
 
 /// Representations of base queries
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named {
@@ -80,6 +132,67 @@ pub enum TableExpression {
         /// Namespace / schema for the table
         schema: Option<Identifier>,
     },
+    /// The row set produced by joining two table expressions
+    /// e.g. `a INNER JOIN b ON a.x = b.y`
+    Join {
+        /// The left side of the join
+        left: Box<TableExpression>,
+        /// The right side of the join
+        right: Box<TableExpression>,
+        /// The kind of join
+        join_type: JoinType,
+        /// The equality conditions of the join e.g. `a.x = b.y` and `a.z = b.w`
+        /// in `ON a.x = b.y AND a.z = b.w`
+        on: Vec<JoinCondition>,
+    },
+    /// The result of a query that is queried like a table, i.e. a derived table
+    /// e.g. `(SELECT a, SUM(b) AS s FROM t GROUP BY a) AS sums`
+    Subquery {
+        /// The query whose result is the table
+        query: Box<SelectStatement>,
+        /// The name that the result of the query is referred to by
+        alias: Identifier,
+    },
+}
+
+/// A common table expression, i.e. a named query of a `WITH` clause
+/// e.g. `sums AS (SELECT a, SUM(b) AS s FROM t GROUP BY a)`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+pub struct CommonTableExpression {
+    /// The name that the result of the query is referred to by
+    pub alias: Identifier,
+    /// The query whose result is the table
+    pub query: Box<SelectStatement>,
+}
+
+/// The kind of join between two table expressions
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum JoinType {
+    /// Inner join, i.e. only rows with a match on both sides are returned
+    Inner,
+    /// Left outer join, i.e. the rows of the left side without a match are returned as well
+    Left,
+    /// Right outer join, i.e. the rows of the right side without a match are returned as well
+    Right,
+}
+
+/// A column reference that is optionally qualified by a table e.g. `a.x`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct QualifiedColumn {
+    /// The table the column belongs to, if specified
+    pub table: Option<Identifier>,
+    /// The column name
+    pub column: Identifier,
+}
+
+/// An equality condition between two columns in the `ON` clause of a join
+/// e.g. `a.x = b.y`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct JoinCondition {
+    /// The left hand side of the equality
+    pub left: QualifiedColumn,
+    /// The right hand side of the equality
+    pub right: QualifiedColumn,
 }
 
 /// Binary operators for simple expressions
@@ -97,6 +210,9 @@ pub enum BinaryOperator {
     /// Numeric division
     Division,
 
+    /// Numeric remainder
+    Modulo,
+
     /// Logical And
     And,
 
@@ -132,6 +248,10 @@ pub enum AggregationOperator {
     Sum,
     /// Count
     Count,
+    /// Count of the distinct values
+    CountDistinct,
+    /// Average
+    Avg,
     /// Return the first value
     First,
 }
@@ -143,11 +263,53 @@ impl Display for AggregationOperator {
             AggregationOperator::Min => write!(f, "min"),
             AggregationOperator::Sum => write!(f, "sum"),
             AggregationOperator::Count => write!(f, "count"),
+            AggregationOperator::CountDistinct => write!(f, "count_distinct"),
+            AggregationOperator::Avg => write!(f, "avg"),
             AggregationOperator::First => write!(f, "first"),
         }
     }
 }
 
+/// Functions that are evaluated over the rows of a window
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+pub enum WindowFunction {
+    /// The position of the row in its partition, starting at 1
+    RowNumber,
+    /// The position of the first row of the peers of the row in its partition, starting at 1
+    Rank,
+    /// The value of the expression at the previous row of the partition
+    Lag(Box<Expression>),
+    /// The value of the expression at the next row of the partition
+    Lead(Box<Expression>),
+    /// The sum of the expression over the rows of the partition up to the last peer of the row
+    Sum(Box<Expression>),
+}
+
+impl WindowFunction {
+    /// The expression that the function is evaluated on, if it takes one
+    #[must_use]
+    pub fn argument(&self) -> Option<&Expression> {
+        match self {
+            WindowFunction::RowNumber | WindowFunction::Rank => None,
+            WindowFunction::Lag(expr) | WindowFunction::Lead(expr) | WindowFunction::Sum(expr) => {
+                Some(expr)
+            }
+        }
+    }
+}
+
+impl Display for WindowFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunction::RowNumber => write!(f, "row_number"),
+            WindowFunction::Rank => write!(f, "rank"),
+            WindowFunction::Lag(_) => write!(f, "lag"),
+            WindowFunction::Lead(_) => write!(f, "lead"),
+            WindowFunction::Sum(_) => write!(f, "sum"),
+        }
+    }
+}
+
 /// Boolean Expressions
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Expression {
@@ -157,6 +319,14 @@ pub enum Expression {
     /// Column
     Column(Identifier),
 
+    /// Column that is qualified by the table it belongs to e.g. `a.x`
+    QualifiedColumn {
+        /// The table the column belongs to
+        table: Identifier,
+        /// The column name
+        column: Identifier,
+    },
+
     /// Unary operation
     Unary {
         /// The unary operator
@@ -184,6 +354,100 @@ pub enum Expression {
         op: AggregationOperator,
         /// The expression to aggregate
         expr: Box<Expression>,
+        /// The condition of the `FILTER (WHERE ...)` clause, which restricts the aggregated rows
+        filter: Option<Box<Expression>>,
+    },
+
+    /// `IS NULL` predicate
+    IsNull(Box<Expression>),
+
+    /// `IS NOT NULL` predicate
+    IsNotNull(Box<Expression>),
+
+    /// `IN` predicate over a list of candidate expressions
+    InList {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The candidates the expression is compared against
+        list: Vec<Expression>,
+    },
+
+    /// `BETWEEN` predicate with inclusive bounds
+    Between {
+        /// The expression to check
+        expr: Box<Expression>,
+        /// The lower bound
+        low: Box<Expression>,
+        /// The upper bound
+        high: Box<Expression>,
+    },
+
+    /// `LIKE` predicate, where `%` in the pattern matches any sequence of characters
+    /// and `_` matches any single character
+    Like {
+        /// The string to match
+        expr: Box<Expression>,
+        /// The pattern the string is matched against
+        pattern: String,
+    },
+
+    /// `CASE WHEN ... THEN ... ELSE ... END` expression
+    Case {
+        /// The conditions with their results, in the order they are checked
+        when_then: Vec<(Expression, Expression)>,
+        /// The result if none of the conditions hold, which is NULL if missing
+        else_expr: Option<Box<Expression>>,
+    },
+
+    /// `IN` predicate over the single column of the result of a query
+    /// e.g. `a IN (SELECT b FROM t)`
+    InSubquery {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The query whose result the expression is looked up in
+        subquery: Box<SelectStatement>,
+    },
+
+    /// The value of a query whose result is a single row with a single column
+    /// e.g. `(SELECT MAX(b) FROM t)` in `a > (SELECT MAX(b) FROM t)`
+    ScalarSubquery(Box<SelectStatement>),
+
+    /// Window function
+    /// e.g. `ROW_NUMBER() OVER (PARTITION BY a ORDER BY b DESC)`
+    Window {
+        /// The function that is evaluated over the window
+        function: WindowFunction,
+        /// The columns whose values partition the rows
+        partition_by: Vec<Identifier>,
+        /// The order of the rows within a partition
+        order_by: Vec<OrderBy>,
+    },
+
+    /// `EXTRACT(<field> FROM <expr>)`, the field of a timestamp in its time zone
+    /// e.g. `EXTRACT(HOUR FROM ts)`
+    Extract {
+        /// The field to extract
+        field: PoSQLDateTimeField,
+        /// The timestamp to extract the field from
+        expr: Box<Expression>,
+    },
+
+    /// `DATE_TRUNC('<field>', <expr>)`, a timestamp truncated to the start of its field in its time zone
+    /// e.g. `DATE_TRUNC('day', ts)`
+    DateTrunc {
+        /// The field to truncate to
+        field: PoSQLDateTimeField,
+        /// The timestamp to truncate
+        expr: Box<Expression>,
+    },
+
+    /// `<expr> + INTERVAL '<interval>'`, where subtracting an interval adds its negation
+    /// e.g. `ts - INTERVAL '1 day'`
+    AddInterval {
+        /// The timestamp to shift
+        expr: Box<Expression>,
+        /// The interval to add
+        interval: PoSQLInterval,
     },
 }
 
@@ -194,6 +458,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Sum,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -203,6 +468,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Max,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -212,6 +478,7 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Min,
             expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -221,6 +488,27 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::Count,
             expr: Box::new(self),
+            filter: None,
+        })
+    }
+
+    /// Create a new `COUNT(DISTINCT)`
+    #[must_use]
+    pub fn count_distinct(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            expr: Box::new(self),
+            filter: None,
+        })
+    }
+
+    /// Create a new `AVG()`
+    #[must_use]
+    pub fn avg(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::Avg,
+            expr: Box::new(self),
+            filter: None,
         })
     }
 
@@ -230,8 +518,62 @@ impl Expression {
         Box::new(Expression::Aggregation {
             op: AggregationOperator::First,
             expr: Box::new(self),
+            filter: None,
         })
     }
+
+    /// Create a new `NULLIF()`, which is `expr` unless `expr = value` is true, in which case it is NULL
+    #[must_use]
+    pub fn nullif(expr: Box<Self>, value: Box<Self>) -> Box<Self> {
+        let equal = Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: expr.clone(),
+            right: value,
+        };
+        Box::new(Expression::Case {
+            when_then: vec![(
+                Expression::Binary {
+                    op: BinaryOperator::Or,
+                    left: Box::new(Expression::Unary {
+                        op: UnaryOperator::Not,
+                        expr: Box::new(equal.clone()),
+                    }),
+                    right: Box::new(Expression::IsNull(Box::new(equal))),
+                },
+                *expr,
+            )],
+            else_expr: None,
+        })
+    }
+
+    /// Rewrite an `IN` list as `=` comparisons joined by `OR`
+    /// and a `BETWEEN` as `>=` and `<=` comparisons joined by `AND`.
+    ///
+    /// Returns `None` for any other expression.
+    #[must_use]
+    pub fn to_comparisons(&self) -> Option<Expression> {
+        let not = |expr| Expression::Unary {
+            op: UnaryOperator::Not,
+            expr: Box::new(expr),
+        };
+        let binary = |op, left: &Expression, right: Expression| Expression::Binary {
+            op,
+            left: Box::new(left.clone()),
+            right: Box::new(right),
+        };
+        match self {
+            Expression::InList { expr, list } => list
+                .iter()
+                .map(|candidate| binary(BinaryOperator::Equal, expr, candidate.clone()))
+                .reduce(|left, right| binary(BinaryOperator::Or, &left, right)),
+            Expression::Between { expr, low, high } => Some(binary(
+                BinaryOperator::And,
+                &not(binary(BinaryOperator::LessThan, expr, (**low).clone())),
+                not(binary(BinaryOperator::GreaterThan, expr, (**high).clone())),
+            )),
+            _ => None,
+        }
+    }
     /// Create an `AliasedResultExpr` from an `Expression` using the provided alias.
     /// # Panics
     ///
@@ -278,6 +620,17 @@ impl core::ops::Div<Box<Expression>> for Box<Expression> {
         })
     }
 }
+impl core::ops::Rem<Box<Expression>> for Box<Expression> {
+    type Output = Box<Expression>;
+
+    fn rem(self, rhs: Box<Expression>) -> Box<Expression> {
+        Box::new(Expression::Binary {
+            op: BinaryOperator::Modulo,
+            left: self,
+            right: rhs,
+        })
+    }
+}
 impl core::ops::Sub<Box<Expression>> for Box<Expression> {
     type Output = Box<Expression>;
 
@@ -291,16 +644,18 @@ impl core::ops::Sub<Box<Expression>> for Box<Expression> {
 }
 
 /// `OrderBy`
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct OrderBy {
     /// which column to order by
     pub expr: Identifier,
+    /// the table that qualifies the column, if any e.g. `a` in `ORDER BY a.x`
+    pub table: Option<Identifier>,
     /// in which direction to order
     pub direction: OrderByDirection,
 }
 
 /// `OrderByDirection` values
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum OrderByDirection {
     /// Ascending
     Asc,
@@ -319,7 +674,7 @@ impl Display for OrderByDirection {
 }
 
 /// Limits for a limit clause
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Slice {
     /// number of rows to return
     ///
@@ -1,5 +1,10 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        JoinType,
+        OrderByDirection::{Asc, Desc},
+        WindowFunction,
+    },
+    posql_time::PoSQLDateTimeField,
     sql::*,
     utility::*,
     SelectStatement,
@@ -616,6 +621,159 @@ fn we_can_parse_logical_not_with_less_precedence_priority_than_equal_operator()
     assert_eq!(parsed_ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_is_null_and_is_not_null_predicates() {
+    let ast = "select a from sxt_tab where b is null or not c IS NOT NULL and d + 1 = e is null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(
+                is_null(col("b")),
+                and(
+                    not(is_not_null(col("c"))),
+                    is_null(equal(col("d") + lit(1), col("e"))),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_in_list_and_between_predicates() {
+    let ast = "select a from sxt_tab where b in (1, c + 2) and d NOT IN ('x') or e between 1 + f and 3 and not g not between 0 and 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(
+                and(
+                    in_list(col("b"), vec![lit(1), col("c") + lit(2)]),
+                    not(in_list(col("d"), vec![lit("x")])),
+                ),
+                and(
+                    between(col("e"), lit(1) + col("f"), lit(3)),
+                    not(not(between(col("g"), lit(0), lit(1)))),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_in_list_and_between_with_more_precedence_than_comparisons() {
+    let ast = "select a from sxt_tab where b between 1 and 2 = c in (true)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where (b between 1 and 2) = (c in (true))"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_like_predicates() {
+    let ast = "select a from sxt_tab where b LIKE 'ab%' and c not like '%_z' = d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                like(col("b"), "ab%"),
+                equal(not(like(col("c"), "%_z")), col("d")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_like_predicate_without_a_string_pattern() {
+    assert!("select a from sxt_tab where b like c"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b like 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_case_expressions() {
+    let ast = "select a from sxt_tab where case when b = 1 then c when not d then e + 1 else k + 2 end > iif(f, g, 3) and case when h then true end"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                gt(
+                    case_when(
+                        vec![
+                            (equal(col("b"), lit(1)), col("c")),
+                            (not(col("d")), col("e") + lit(1)),
+                        ],
+                        Some(col("k") + lit(2)),
+                    ),
+                    case_when(vec![(col("f"), col("g"))], Some(lit(3))),
+                ),
+                case_when(vec![(col("h"), lit(true))], None),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_nullif_as_a_case_expression() {
+    let ast = "select a from sxt_tab where NULLIF(b, c) = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(
+                case_when(
+                    vec![(
+                        or(
+                            not(equal(col("b"), col("c"))),
+                            is_null(equal(col("b"), col("c"))),
+                        ),
+                        col("b"),
+                    )],
+                    None,
+                ),
+                lit(1),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_logical_and_with_more_precedence_priority_than_logical_or() {
     let ast = "select a from sxt_tab where a = -1 or c = -3 and a = 3"
@@ -842,15 +1000,29 @@ fn we_cannot_parse_a_query_with_select_tablename_followed_by_star() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
-    assert!("select tab.a from tab".parse::<SelectStatement>().is_err());
-    assert!("select tab.a from eth.tab;"
+fn we_can_parse_a_query_with_columns_qualified_by_table_names() {
+    let ast = "select tab.a from eth.tab where tab.b = 3;"
         .parse::<SelectStatement>()
-        .is_err());
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("tab.a"), "a")],
+            tab(Some("eth"), "tab"),
+            equal(col("tab.b"), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
     assert!("select eth.tab.a from eth.tab"
         .parse::<SelectStatement>()
         .is_err());
-    assert!("select a from eth.tab where tab.b = 3;"
+    assert!("select a from eth.tab where eth.tab.b = 3;"
         .parse::<SelectStatement>()
         .is_err());
 }
@@ -1032,23 +1204,22 @@ fn we_can_parse_a_query_with_filter_gt() {
     assert_eq!(ast, expected_ast);
 }
 
+// Case when
 #[test]
-fn we_cannot_parse_a_query_with_inner_join_keyword() {
+fn we_cannot_parse_a_query_with_an_invalid_case_expression() {
     assert!(
-        "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4;"
+        "select case when a == 2 then 3 else 5 from tab where b <= 4;"
             .parse::<SelectStatement>()
             .is_err()
     );
-}
-
-// Case when
-#[test]
-fn we_cannot_parse_a_query_with_case_when_keyword() {
     assert!(
-        "select case when a == 2 then 3 else 5 from tab where b <= 4;"
+        "select case when a = 2 then 3 else 5 from tab where b <= 4;"
             .parse::<SelectStatement>()
             .is_err()
     );
+    assert!("select case else 5 end from tab where b <= 4;"
+        .parse::<SelectStatement>()
+        .is_err());
 }
 
 //////////////////////
@@ -1196,6 +1367,28 @@ fn we_can_parse_a_group_by_clause_containing_multiple_aggregations_where_clause_
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_averages_with_and_without_aliases() {
+    let ast = "select a, avg(b), AVG(b + c) as avg_bc, count(*) from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                avg_res(col("b"), "__avg__"),
+                avg_res(add(col("b"), col("c")), "avg_bc"),
+                count_all_res("__count__"),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_cannot_parse_a_group_by_clause_after_order_by() {
     assert!("select a from tab order by a group by a"
@@ -1210,6 +1403,60 @@ fn we_cannot_parse_a_group_by_clause_before_where_expr() {
         .is_err());
 }
 
+#[test]
+fn we_can_parse_a_group_by_clause_with_expressions() {
+    let ast = "select a + b as s, case when c > 0 then 1 else 0 end as positive, count(*) from tab group by a + b, case when c > 0 then 1 else 0 end, d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let positive = case_when(vec![(gt(col("c"), lit(0)), lit(1))], Some(lit(0)));
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(add(col("a"), col("b")), "s"),
+                col_res(positive.clone(), "positive"),
+                count_all_res("__count__"),
+            ],
+            tab(None, "tab"),
+            vec![*add(col("a"), col("b")), *positive, *col("d")],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_having_clause() {
+    let ast = "select a, sum(b) as s, count(*) from tab where c = 1 group by a having sum(b) > 10 and count(*) >= 2 order by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query(
+                vec![
+                    col_res(col("a"), "a"),
+                    sum_res(col("b"), "s"),
+                    count_all_res("__count__"),
+                ],
+                tab(None, "tab"),
+                equal(col("c"), lit(1)),
+                group_by(&["a"]),
+            ),
+            and(gt(sum(col("b")), lit(10)), ge(count_all(), lit(2))),
+        ),
+        order("a", Asc),
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_having_clause_before_group_by() {
+    assert!("select a, count(*) from tab having count(*) > 1 group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
     let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
@@ -1235,6 +1482,7 @@ fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_inco
 
 #[test]
 fn we_cannot_parse_a_non_count_aggregations_with_wildcard() {
+    assert!("select avg(*) from tab".parse::<SelectStatement>().is_err());
     assert!("select min(*) from tab".parse::<SelectStatement>().is_err());
     assert!("select max(*) from tab".parse::<SelectStatement>().is_err());
     assert!("select sum(*) from tab".parse::<SelectStatement>().is_err());
@@ -1283,6 +1531,28 @@ fn mul_and_div_operators_have_the_same_precedence_and_left_expressions_are_alway
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn modulo_has_the_same_precedence_as_mul_and_div() {
+    let ast = "select a % b, a * b % c, a % b / c, a + b % c from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a") % col("b"), "__expr__"),
+                col_res(col("a") * col("b") % col("c"), "__expr__"),
+                col_res(col("a") % col("b") / col("c"), "__expr__"),
+                col_res(col("a") + modulo(col("b"), col("c")), "__expr__"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_single_literal_in_the_result_expr() {
     let ast = "select -123 from tab".parse::<SelectStatement>().unwrap();
@@ -1439,3 +1709,754 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+////////////////////////////////
+/// Tests for joins
+////////////////////////////////
+#[test]
+fn we_can_parse_an_inner_join() {
+    let ast = "select a, b from tab inner join eth.details on tab.id = details.id"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a", "b"]),
+            inner_join(
+                tab(None, "tab"),
+                tab(Some("eth"), "details"),
+                &[("tab.id", "details.id")],
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_join_without_the_inner_keyword_and_with_unqualified_columns() {
+    let ast = "SELECT A FROM TAB JOIN DETAILS ON ID = TAB_ID"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            inner_join(tab(None, "tab"), tab(None, "details"), &[("id", "tab_id")]),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_join_on_multiple_columns_with_where_group_by_order_by_and_limit() {
+    let ast = "select a, count(*) as c from tab inner join details on tab.x = details.x and tab.y = details.z where b = 3 group by a order by a desc limit 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("a"), "a"), count_all_res("c")],
+            inner_join(
+                tab(None, "tab"),
+                tab(None, "details"),
+                &[("tab.x", "details.x"), ("tab.y", "details.z")],
+            ),
+            equal(col("b"), lit(3)),
+            group_by(&["a"]),
+        ),
+        order("a", Desc),
+        slice(2, 0),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_qualified_columns_outside_of_the_on_clause_of_a_join() {
+    let ast = "select tab.a, details.a as b from tab join details on tab.x = details.x where details.b > 4 group by tab.a, details.a order by tab.a desc"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(col("tab.a"), "a"), col_res(col("details.a"), "b")],
+            inner_join(
+                tab(None, "tab"),
+                tab(None, "details"),
+                &[("tab.x", "details.x")],
+            ),
+            gt(col("details.b"), lit(4)),
+            vec![*col("tab.a"), *col("details.a")],
+        ),
+        order("tab.a", Desc),
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_left_and_right_outer_joins() {
+    let left_ast = "select a from tab left outer join details on tab.id = details.id"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_left_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            join(
+                tab(None, "tab"),
+                tab(None, "details"),
+                JoinType::Left,
+                &[("tab.id", "details.id")],
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(left_ast, expected_left_ast);
+    assert_eq!(
+        "SELECT A FROM TAB LEFT JOIN DETAILS ON TAB.ID = DETAILS.ID"
+            .parse::<SelectStatement>()
+            .unwrap(),
+        expected_left_ast
+    );
+
+    let right_ast = "select a from tab right join details on tab.id = details.id"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_right_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            join(
+                tab(None, "tab"),
+                tab(None, "details"),
+                JoinType::Right,
+                &[("tab.id", "details.id")],
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(right_ast, expected_right_ast);
+    assert_eq!(
+        "select a from tab right outer join details on tab.id = details.id"
+            .parse::<SelectStatement>()
+            .unwrap(),
+        expected_right_ast
+    );
+}
+
+#[test]
+fn we_cannot_parse_a_join_without_an_on_clause_or_with_a_non_equality_condition() {
+    assert!("select a from tab inner join details"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from tab inner join details on tab.id > details.id"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+    assert!("select a from tab inner join details on tab.id = 3"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from tab outer join details on tab.id = details.id"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_a_union_all_of_queries() {
+    let ast = "select a from tab_1 where b = 1 union all select a from tab_2 union all select c as a from tab_3 order by a desc limit 5"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        union_all(vec![
+            query(
+                cols_res(&["a"]),
+                tab(None, "tab_1"),
+                equal(col("b"), lit(1)),
+                vec![],
+            ),
+            query_all(cols_res(&["a"]), tab(None, "tab_2"), vec![]),
+            query_all(vec![col_res(col("c"), "a")], tab(None, "tab_3"), vec![]),
+        ]),
+        order("a", Desc),
+        slice(5, 0),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_union_with_ordered_inputs() {
+    assert!(
+        "select a from tab_1 order by a union all select a from tab_2"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+    assert!("select a from tab_1 limit 1 union select a from tab_2"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_select_distinct() {
+    let ast = "SELECT DISTINCT a, b FROM tab WHERE c = 1 ORDER BY a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(query(
+            cols_res(&["a", "b"]),
+            tab(None, "tab"),
+            equal(col("c"), lit(1)),
+            vec![],
+        )),
+        order("a", Asc),
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_union_of_queries_as_the_distinct_rows_of_a_union_all() {
+    let ast = "select a from tab_1 union select a from tab_2 union select c as a from tab_3"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(union_all(vec![
+            query_all(cols_res(&["a"]), tab(None, "tab_1"), vec![]),
+            query_all(cols_res(&["a"]), tab(None, "tab_2"), vec![]),
+            query_all(vec![col_res(col("c"), "a")], tab(None, "tab_3"), vec![]),
+        ])),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_union_all_after_a_union() {
+    let ast =
+        "select a from tab_1 union select a from tab_2 union all select distinct a from tab_3"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        union_all(vec![
+            distinct(union_all(vec![
+                query_all(cols_res(&["a"]), tab(None, "tab_1"), vec![]),
+                query_all(cols_res(&["a"]), tab(None, "tab_2"), vec![]),
+            ])),
+            distinct(query_all(cols_res(&["a"]), tab(None, "tab_3"), vec![])),
+        ]),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_count_of_distinct_values() {
+    let ast = "select a, count(DISTINCT b), count(distinct c) as c from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(count_distinct(col("b")), "__count_distinct__"),
+                col_res(count_distinct(col("c")), "c"),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_aggregations_with_a_filter_clause() {
+    let ast = "select a, sum(b) FILTER (WHERE c > 1) as s, count(*) filter (where not c = 2) as n, count(b) as m from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(filtered(sum(col("b")), gt(col("c"), lit(1))), "s"),
+                col_res(filtered(count_all(), not(equal(col("c"), lit(2)))), "n"),
+                col_res(count(col("b")), "m"),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_filter_clause_without_an_aggregation() {
+    assert!("select a filter (where a > 1) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(a) filter (a > 1) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+////////////////////////////////
+/// Tests for derived tables
+////////////////////////////////
+#[test]
+fn we_can_parse_a_query_over_a_derived_table() {
+    let ast = "select a, s from (select a, sum(b) as s from tab group by a) as sums where s > 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a", "s"]),
+            derived_tab(
+                select(
+                    query_all(
+                        vec![col_res(col("a"), "a"), sum_res(col("b"), "s")],
+                        tab(None, "tab"),
+                        group_by(&["a"]),
+                    ),
+                    vec![],
+                    None,
+                ),
+                "sums",
+            ),
+            gt(col("s"), lit(4)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_nested_derived_tables_with_order_by_and_limit_and_without_as() {
+    let ast = "select b from (select b from (select b from eth.tab) t order by b desc limit 2) u"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["b"]),
+            derived_tab(
+                select(
+                    query_all(
+                        cols_res(&["b"]),
+                        derived_tab(
+                            select(
+                                query_all(cols_res(&["b"]), tab(Some("eth"), "tab"), vec![]),
+                                vec![],
+                                None,
+                            ),
+                            "t",
+                        ),
+                        vec![],
+                    ),
+                    order("b", Desc),
+                    slice(2, 0),
+                ),
+                "u",
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_derived_table_without_an_alias() {
+    assert!("select a from (select a from tab)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from (select a from tab;) as t"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+////////////////////////////////
+/// Tests for subqueries
+////////////////////////////////
+#[test]
+fn we_can_parse_in_and_scalar_subqueries() {
+    let ast = "select a from tab where a in (select b from eth.allowed where c = 1) and d > (select max(d) as m from tab_2)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                in_subquery(
+                    col("a"),
+                    select(
+                        query(
+                            cols_res(&["b"]),
+                            tab(Some("eth"), "allowed"),
+                            equal(col("c"), lit(1)),
+                            vec![],
+                        ),
+                        vec![],
+                        None,
+                    ),
+                ),
+                gt(
+                    col("d"),
+                    scalar_subquery(select(
+                        query_all(vec![max_res(col("d"), "m")], tab(None, "tab_2"), vec![]),
+                        vec![],
+                        None,
+                    )),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_negated_in_subquery_with_the_precedence_of_in() {
+    let ast = "select a from tab where a + 1 not in (select b from allowed) = true"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            equal(
+                not(in_subquery(
+                    col("a") + lit(1),
+                    select(
+                        query_all(cols_res(&["b"]), tab(None, "allowed"), vec![]),
+                        vec![],
+                        None,
+                    ),
+                )),
+                lit(true),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_subquery_that_ends_with_a_semicolon() {
+    assert!("select a from tab where a in (select b from allowed;)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab where a = (select b from allowed;)"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+////////////////////////////////
+/// Tests for window functions
+////////////////////////////////
+#[test]
+fn we_can_parse_window_functions() {
+    let ast = "select a, ROW_NUMBER() OVER (PARTITION BY a, b ORDER BY c DESC) as rn, rank() over (order by c) as r from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(
+                    window(WindowFunction::RowNumber, &["a", "b"], order("c", Desc)),
+                    "rn",
+                ),
+                col_res(window(WindowFunction::Rank, &[], order("c", Asc)), "r"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_window_functions_with_arguments_inside_expressions() {
+    let ast = "select c - lag(c) over (partition by a order by b) as delta, lead(c + 1) over () as next, sum(c) over (partition by a) as total from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(
+                    sub(
+                        col("c"),
+                        window(WindowFunction::Lag(col("c")), &["a"], order("b", Asc)),
+                    ),
+                    "delta",
+                ),
+                col_res(
+                    window(WindowFunction::Lead(add(col("c"), lit(1))), &[], vec![]),
+                    "next",
+                ),
+                col_res(
+                    window(WindowFunction::Sum(col("c")), &["a"], vec![]),
+                    "total",
+                ),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_sum_without_over_as_an_aggregation() {
+    let ast = "select sum(c) as total from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(vec![sum_res(col("c"), "total")], tab(None, "tab"), vec![]),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_window_functions_without_an_over_clause() {
+    assert!("select row_number() as rn from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select lag(c) as prev from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select rank() over (order by c limit 1) as r from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_columns_named_like_words_that_are_only_keywords_in_some_positions() {
+    let ast = "SELECT rank, end, left FROM t"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(cols_res(&["rank", "end", "left"]), tab(None, "t"), vec![]),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast =
+        "select sum(a) over, count(b) filter from t where partition = lag and interval > outer"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query(
+            vec![sum_res(col("a"), "over"), count_res(col("b"), "filter")],
+            tab(None, "t"),
+            and(
+                equal(col("partition"), col("lag")),
+                gt(col("interval"), col("outer")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_calls_of_unsupported_functions() {
+    assert!("select lead() over () as l from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select rank(a) over () as r from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab where nvl(a, b) = 1"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab where date_trunc(b, a) = a"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+////////////////////////////////
+/// Tests for common table expressions
+////////////////////////////////
+#[test]
+fn we_can_parse_a_query_with_a_common_table_expression() {
+    let ast = "with sums as (select a, sum(b) as s from tab group by a) select a, s from sums where s > 4 order by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = with(
+        vec![cte(
+            "sums",
+            select(
+                query_all(
+                    vec![col_res(col("a"), "a"), sum_res(col("b"), "s")],
+                    tab(None, "tab"),
+                    group_by(&["a"]),
+                ),
+                vec![],
+                None,
+            ),
+        )],
+        select(
+            query(
+                cols_res(&["a", "s"]),
+                tab(None, "sums"),
+                gt(col("s"), lit(4)),
+                vec![],
+            ),
+            order("a", Asc),
+            None,
+        ),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_common_table_expressions_that_refer_to_each_other_and_nested_ones() {
+    let ast = "WITH t AS (SELECT b FROM eth.tab), u AS (SELECT b FROM t) SELECT b FROM (WITH v AS (SELECT b FROM u) SELECT b FROM v) AS w WHERE b IN (SELECT b FROM t)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = with(
+        vec![
+            cte(
+                "t",
+                select(
+                    query_all(cols_res(&["b"]), tab(Some("eth"), "tab"), vec![]),
+                    vec![],
+                    None,
+                ),
+            ),
+            cte(
+                "u",
+                select(
+                    query_all(cols_res(&["b"]), tab(None, "t"), vec![]),
+                    vec![],
+                    None,
+                ),
+            ),
+        ],
+        select(
+            query(
+                cols_res(&["b"]),
+                derived_tab(
+                    with(
+                        vec![cte(
+                            "v",
+                            select(
+                                query_all(cols_res(&["b"]), tab(None, "u"), vec![]),
+                                vec![],
+                                None,
+                            ),
+                        )],
+                        select(
+                            query_all(cols_res(&["b"]), tab(None, "v"), vec![]),
+                            vec![],
+                            None,
+                        ),
+                    ),
+                    "w",
+                ),
+                in_subquery(
+                    col("b"),
+                    select(
+                        query_all(cols_res(&["b"]), tab(None, "t"), vec![]),
+                        vec![],
+                        None,
+                    ),
+                ),
+                vec![],
+            ),
+            vec![],
+            None,
+        ),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_common_table_expressions() {
+    assert!("with t as select a from tab select a from t"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with t (select a from tab) select a from t"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with eth.t as (select a from tab) select a from t"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with t as (select a from tab)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with t as (select a from tab;) select a from t"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_date_time_functions() {
+    let ast = "select extract(YEAR from a) as y, DATE_TRUNC('Day', a) as d from sxt_tab where a + interval '2 days' > b - INTERVAL '1 hour'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(extract(PoSQLDateTimeField::Year, col("a")), "y"),
+                col_res(date_trunc(PoSQLDateTimeField::Day, col("a")), "d"),
+            ],
+            tab(None, "sxt_tab"),
+            gt(
+                add_interval(col("a"), 2, PoSQLDateTimeField::Day),
+                add_interval(col("b"), -1, PoSQLDateTimeField::Hour),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_date_time_functions() {
+    assert!("select extract(week from a) as w from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select date_trunc('fortnight', a) as d from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select date_trunc(day, a) as d from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a + interval '1.5 days' as b from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a + interval 1 as b from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
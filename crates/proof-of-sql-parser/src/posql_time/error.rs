@@ -52,6 +52,20 @@ pub enum PoSQLTimestampError {
         /// The underlying error
         error: String,
     },
+
+    /// The field of a timestamp is not one of `year`, `month`, `day`, `hour`, `minute` and `second`
+    #[snafu(display("Unsupported date/time field: {field}"))]
+    UnsupportedDateTimeField {
+        /// The unsupported field
+        field: String,
+    },
+
+    /// The interval is not a whole number of a single date/time field, e.g. `3 days`
+    #[snafu(display("Invalid interval: {interval}"))]
+    InvalidInterval {
+        /// The invalid interval
+        interval: String,
+    },
 }
 
 // This exists because TryFrom<DataType> for ColumnType error is String
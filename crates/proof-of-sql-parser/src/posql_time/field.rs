@@ -0,0 +1,119 @@
+use super::PoSQLTimestampError;
+use alloc::string::ToString;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A field of a timestamp, as used by `EXTRACT`, `DATE_TRUNC` and intervals
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PoSQLDateTimeField {
+    /// The year
+    Year,
+    /// The month of the year, from 1 to 12
+    Month,
+    /// The day of the month, from 1 to 31
+    Day,
+    /// The hour of the day, from 0 to 23
+    Hour,
+    /// The minute of the hour, from 0 to 59
+    Minute,
+    /// The second of the minute, from 0 to 59
+    Second,
+}
+
+impl PoSQLDateTimeField {
+    /// The length of the field in seconds, or `None` for months and years, whose length varies
+    #[must_use]
+    pub const fn seconds(self) -> Option<i64> {
+        match self {
+            PoSQLDateTimeField::Year | PoSQLDateTimeField::Month => None,
+            PoSQLDateTimeField::Day => Some(86_400),
+            PoSQLDateTimeField::Hour => Some(3_600),
+            PoSQLDateTimeField::Minute => Some(60),
+            PoSQLDateTimeField::Second => Some(1),
+        }
+    }
+}
+
+impl TryFrom<&str> for PoSQLDateTimeField {
+    type Error = PoSQLTimestampError;
+    fn try_from(value: &str) -> Result<Self, PoSQLTimestampError> {
+        match value.to_lowercase().as_str() {
+            "year" => Ok(PoSQLDateTimeField::Year),
+            "month" => Ok(PoSQLDateTimeField::Month),
+            "day" => Ok(PoSQLDateTimeField::Day),
+            "hour" => Ok(PoSQLDateTimeField::Hour),
+            "minute" => Ok(PoSQLDateTimeField::Minute),
+            "second" => Ok(PoSQLDateTimeField::Second),
+            _ => Err(PoSQLTimestampError::UnsupportedDateTimeField {
+                field: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for PoSQLDateTimeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoSQLDateTimeField::Year => write!(f, "year"),
+            PoSQLDateTimeField::Month => write!(f, "month"),
+            PoSQLDateTimeField::Day => write!(f, "day"),
+            PoSQLDateTimeField::Hour => write!(f, "hour"),
+            PoSQLDateTimeField::Minute => write!(f, "minute"),
+            PoSQLDateTimeField::Second => write!(f, "second"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_parse_date_time_fields_case_insensitively() {
+        assert_eq!(
+            PoSQLDateTimeField::try_from("YEAR"),
+            Ok(PoSQLDateTimeField::Year)
+        );
+        assert_eq!(
+            PoSQLDateTimeField::try_from("Month"),
+            Ok(PoSQLDateTimeField::Month)
+        );
+        assert_eq!(
+            PoSQLDateTimeField::try_from("day"),
+            Ok(PoSQLDateTimeField::Day)
+        );
+        assert_eq!(
+            PoSQLDateTimeField::try_from("hour"),
+            Ok(PoSQLDateTimeField::Hour)
+        );
+        assert_eq!(
+            PoSQLDateTimeField::try_from("minute"),
+            Ok(PoSQLDateTimeField::Minute)
+        );
+        assert_eq!(
+            PoSQLDateTimeField::try_from("second"),
+            Ok(PoSQLDateTimeField::Second)
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_unsupported_date_time_fields() {
+        for value in ["week", "quarter", "epoch", "days", ""] {
+            assert!(matches!(
+                PoSQLDateTimeField::try_from(value),
+                Err(PoSQLTimestampError::UnsupportedDateTimeField { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn only_fields_up_to_days_have_a_fixed_length() {
+        assert_eq!(PoSQLDateTimeField::Year.seconds(), None);
+        assert_eq!(PoSQLDateTimeField::Month.seconds(), None);
+        assert_eq!(PoSQLDateTimeField::Day.seconds(), Some(86_400));
+        assert_eq!(PoSQLDateTimeField::Hour.seconds(), Some(3_600));
+        assert_eq!(PoSQLDateTimeField::Minute.seconds(), Some(60));
+        assert_eq!(PoSQLDateTimeField::Second.seconds(), Some(1));
+    }
+}
@@ -0,0 +1,149 @@
+use super::{PoSQLDateTimeField, PoSQLTimestampError};
+use alloc::string::ToString;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// An interval of a whole number of a single date/time field, e.g. `INTERVAL '3 days'`
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoSQLInterval {
+    count: i64,
+    field: PoSQLDateTimeField,
+}
+
+impl PoSQLInterval {
+    /// Create an interval of `count` times the given field
+    #[must_use]
+    pub const fn new(count: i64, field: PoSQLDateTimeField) -> Self {
+        PoSQLInterval { count, field }
+    }
+
+    /// The number of fields in the interval
+    #[must_use]
+    pub const fn count(self) -> i64 {
+        self.count
+    }
+
+    /// The field the interval is counted in
+    #[must_use]
+    pub const fn field(self) -> PoSQLDateTimeField {
+        self.field
+    }
+
+    /// The length of the interval in seconds, or `None` if it is counted in months or years
+    /// or does not fit into an `i64`
+    #[must_use]
+    pub fn seconds(self) -> Option<i64> {
+        self.count.checked_mul(self.field.seconds()?)
+    }
+
+    /// The interval of the opposite direction, or `None` on overflow
+    #[must_use]
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(PoSQLInterval::new(self.count.checked_neg()?, self.field))
+    }
+}
+
+impl TryFrom<&str> for PoSQLInterval {
+    type Error = PoSQLTimestampError;
+
+    /// Parses intervals such as `1 day` or `-12 hours`
+    fn try_from(value: &str) -> Result<Self, PoSQLTimestampError> {
+        let invalid_interval = || PoSQLTimestampError::InvalidInterval {
+            interval: value.to_string(),
+        };
+        let mut parts = value.split_whitespace();
+        let (Some(count), Some(field), None) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid_interval());
+        };
+        let count = count.parse::<i64>().map_err(|_| invalid_interval())?;
+        // Both `1 day` and `2 days` are valid
+        let field = PoSQLDateTimeField::try_from(field)
+            .or_else(|_| PoSQLDateTimeField::try_from(field.strip_suffix(['s', 'S']).unwrap_or("")))
+            .map_err(|_| invalid_interval())?;
+        Ok(PoSQLInterval::new(count, field))
+    }
+}
+
+impl fmt::Display for PoSQLInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.count, self.field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_parse_intervals() {
+        assert_eq!(
+            PoSQLInterval::try_from("1 day"),
+            Ok(PoSQLInterval::new(1, PoSQLDateTimeField::Day))
+        );
+        assert_eq!(
+            PoSQLInterval::try_from(" 12  Hours "),
+            Ok(PoSQLInterval::new(12, PoSQLDateTimeField::Hour))
+        );
+        assert_eq!(
+            PoSQLInterval::try_from("-30 minutes"),
+            Ok(PoSQLInterval::new(-30, PoSQLDateTimeField::Minute))
+        );
+        assert_eq!(
+            PoSQLInterval::try_from("2 MONTHS"),
+            Ok(PoSQLInterval::new(2, PoSQLDateTimeField::Month))
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_invalid_intervals() {
+        for value in [
+            "",
+            "day",
+            "1",
+            "1.5 days",
+            "1 day 2 hours",
+            "1 fortnight",
+            "1 s",
+        ] {
+            assert!(matches!(
+                PoSQLInterval::try_from(value),
+                Err(PoSQLTimestampError::InvalidInterval { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_length_of_intervals_with_a_fixed_length() {
+        assert_eq!(
+            PoSQLInterval::new(2, PoSQLDateTimeField::Day).seconds(),
+            Some(172_800)
+        );
+        assert_eq!(
+            PoSQLInterval::new(-3, PoSQLDateTimeField::Second).seconds(),
+            Some(-3)
+        );
+        assert_eq!(
+            PoSQLInterval::new(1, PoSQLDateTimeField::Year).seconds(),
+            None
+        );
+        assert_eq!(
+            PoSQLInterval::new(i64::MAX, PoSQLDateTimeField::Minute).seconds(),
+            None
+        );
+    }
+
+    #[test]
+    fn we_can_display_and_negate_intervals() {
+        let interval = PoSQLInterval::new(3, PoSQLDateTimeField::Hour);
+        assert_eq!(interval.to_string(), "3 hour");
+        assert_eq!(
+            interval.checked_neg(),
+            Some(PoSQLInterval::new(-3, PoSQLDateTimeField::Hour))
+        );
+        assert_eq!(
+            PoSQLInterval::new(i64::MIN, PoSQLDateTimeField::Hour).checked_neg(),
+            None
+        );
+    }
+}
@@ -10,3 +10,9 @@ pub use timezone::PoSQLTimeZone;
 mod unit;
 /// Defines the precision of the timestamp
 pub use unit::PoSQLTimeUnit;
+mod field;
+/// Defines the fields of a timestamp, e.g. its year or hour
+pub use field::PoSQLDateTimeField;
+mod interval;
+/// Defines an interval of a whole number of a single date/time field
+pub use interval::PoSQLInterval;
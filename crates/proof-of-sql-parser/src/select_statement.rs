@@ -1,12 +1,18 @@
-use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
+use super::intermediate_ast::{
+    CommonTableExpression, Expression, OrderBy, SelectResultExpr, SetExpression, Slice,
+    TableExpression,
+};
 use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use core::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 /// Representation of a select statement, that is, the only type of queries allowed.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
 pub struct SelectStatement {
+    /// the common table expressions of the `WITH` clause, which the query expression may refer to by name
+    pub with: Vec<CommonTableExpression>,
+
     /// the query expression
     pub expr: Box<SetExpression>,
 
@@ -22,8 +28,8 @@ impl fmt::Debug for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "SelectStatement \n[{:#?},\n{:#?},\n{:#?}\n]",
-            self.expr, self.order_by, self.slice
+            "SelectStatement \n[{:#?},\n{:#?},\n{:#?},\n{:#?}\n]",
+            self.with, self.expr, self.order_by, self.slice
         )
     }
 }
@@ -40,19 +46,148 @@ impl SelectStatement {
     /// as this would imply the caller to always know beforehand the referenced
     /// schemas.
     ///
+    /// The names of common table expressions are not tables, so the tables referenced by
+    /// their queries are returned instead, once per common table expression.
+    ///
     /// Return:
     /// - The vector with all tables referenced by the intermediate ast, encoded as resource ids.
     #[must_use]
     pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
-        let set_expression: &SetExpression = &(self.expr);
-
-        match set_expression {
-            SetExpression::Query {
-                result_exprs: _,
-                from,
-                where_expr: _,
-                group_by: _,
-            } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
+        let mut tables = Vec::new();
+        push_select_resource_ids(&mut tables, self, default_schema, &[]);
+        tables
+    }
+}
+
+/// Pushes the tables referenced by a `SelectStatement` to `tables`
+///
+/// `ctes` are the names of the common table expressions in scope, which the statement may refer to.
+fn push_select_resource_ids(
+    tables: &mut Vec<ResourceId>,
+    select: &SelectStatement,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    let mut ctes = ctes.to_vec();
+    for cte in &select.with {
+        push_select_resource_ids(tables, &cte.query, default_schema, &ctes);
+        ctes.push(cte.alias);
+    }
+    push_set_expr_resource_ids(tables, &select.expr, default_schema, &ctes);
+}
+
+/// Pushes the tables referenced by a `SetExpression` to `tables`
+fn push_set_expr_resource_ids(
+    tables: &mut Vec<ResourceId>,
+    set_expression: &SetExpression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match set_expression {
+        SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+        } => {
+            tables.extend(convert_table_expr_to_resource_id_vector(
+                &from[..],
+                default_schema,
+                ctes,
+            ));
+            let result_exprs = result_exprs
+                .iter()
+                .filter_map(|result_expr| match result_expr {
+                    SelectResultExpr::ALL => None,
+                    SelectResultExpr::AliasedResultExpr(aliased_expr) => Some(&*aliased_expr.expr),
+                });
+            for expr in result_exprs
+                .chain(where_expr.as_deref())
+                .chain(group_by)
+                .chain(having.as_deref())
+            {
+                push_expr_resource_ids(tables, expr, default_schema, ctes);
+            }
+        }
+        SetExpression::UnionAll { inputs } => {
+            for input in inputs {
+                push_set_expr_resource_ids(tables, input, default_schema, ctes);
+            }
+        }
+        SetExpression::Distinct { input } => {
+            push_set_expr_resource_ids(tables, input, default_schema, ctes);
+        }
+    }
+}
+
+/// Pushes the tables referenced by the subqueries of an `Expression` to `tables`
+fn push_expr_resource_ids(
+    tables: &mut Vec<ResourceId>,
+    expr: &Expression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match expr {
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Wildcard => {}
+        Expression::Unary { expr, .. }
+        | Expression::Aggregation {
+            expr, filter: None, ..
+        }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Like { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::AddInterval { expr, .. } => {
+            push_expr_resource_ids(tables, expr, default_schema, ctes);
+        }
+        Expression::Binary { left, right, .. }
+        | Expression::Aggregation {
+            expr: left,
+            filter: Some(right),
+            ..
+        } => {
+            push_expr_resource_ids(tables, left, default_schema, ctes);
+            push_expr_resource_ids(tables, right, default_schema, ctes);
+        }
+        Expression::InList { expr, list } => {
+            push_expr_resource_ids(tables, expr, default_schema, ctes);
+            for candidate in list {
+                push_expr_resource_ids(tables, candidate, default_schema, ctes);
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            for expr in [expr, low, high] {
+                push_expr_resource_ids(tables, expr, default_schema, ctes);
+            }
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            for (condition, then) in when_then {
+                push_expr_resource_ids(tables, condition, default_schema, ctes);
+                push_expr_resource_ids(tables, then, default_schema, ctes);
+            }
+            if let Some(else_expr) = else_expr {
+                push_expr_resource_ids(tables, else_expr, default_schema, ctes);
+            }
+        }
+        Expression::InSubquery { expr, subquery } => {
+            push_expr_resource_ids(tables, expr, default_schema, ctes);
+            push_select_resource_ids(tables, subquery, default_schema, ctes);
+        }
+        Expression::ScalarSubquery(query) => {
+            push_select_resource_ids(tables, query, default_schema, ctes);
+        }
+        Expression::Window { function, .. } => {
+            if let Some(expr) = function.argument() {
+                push_expr_resource_ids(tables, expr, default_schema, ctes);
+            }
         }
     }
 }
@@ -77,25 +212,48 @@ impl FromStr for SelectStatement {
 fn convert_table_expr_to_resource_id_vector(
     table_expressions: &[Box<TableExpression>],
     default_schema: Identifier,
+    ctes: &[Identifier],
 ) -> Vec<ResourceId> {
     let mut tables = Vec::new();
 
     for table_expression in table_expressions {
-        let table_ref: &TableExpression = table_expression;
+        push_table_expr_resource_ids(&mut tables, table_expression, default_schema, ctes);
+    }
 
-        match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema.as_ref().map_or_else(
-                    || default_schema.name(),
-                    super::identifier::Identifier::as_str,
-                );
+    tables
+}
 
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
-            }
+/// # Panics
+///
+/// This function will panic if `ResourceId::try_new` fails to create a valid `ResourceId`.
+fn push_table_expr_resource_ids(
+    tables: &mut Vec<ResourceId>,
+    table_expression: &TableExpression,
+    default_schema: Identifier,
+    ctes: &[Identifier],
+) {
+    match table_expression {
+        // A table without a schema may refer to a common table expression instead
+        TableExpression::Named {
+            table,
+            schema: None,
+        } if ctes.contains(table) => {}
+        TableExpression::Named { table, schema } => {
+            let schema = schema.as_ref().map_or_else(
+                || default_schema.name(),
+                super::identifier::Identifier::as_str,
+            );
+
+            tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+        }
+        TableExpression::Join { left, right, .. } => {
+            push_table_expr_resource_ids(tables, left, default_schema, ctes);
+            push_table_expr_resource_ids(tables, right, default_schema, ctes);
+        }
+        TableExpression::Subquery { query, .. } => {
+            push_select_resource_ids(tables, query, default_schema, ctes);
         }
     }
-
-    tables
 }
 
 #[cfg(test)]
@@ -138,4 +296,109 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_union_all() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB UNION ALL SELECT A FROM SCHEMA.TAB_2 UNION ALL SELECT A FROM TAB")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "tab_2").unwrap(),
+                ResourceId::try_new("eth", "tab").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_join() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB INNER JOIN SCHEMA.DETAILS ON TAB.ID = DETAILS.ID")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "details").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_subqueries() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB WHERE A IN (SELECT B FROM SCHEMA.ALLOWED) AND C > (SELECT MAX(C) FROM TAB_2)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "allowed").unwrap(),
+                ResourceId::try_new("eth", "tab_2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_derived_table() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM (SELECT A FROM SCHEMA.TAB UNION ALL SELECT A FROM TAB_2) AS T WHERE A > 3")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("schema", "tab").unwrap(),
+                ResourceId::try_new("eth", "tab_2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_common_table_expressions() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("WITH T AS (SELECT A FROM SCHEMA.TAB), U AS (SELECT A FROM T WHERE A IN (SELECT A FROM TAB_2)) SELECT A FROM U WHERE A > (SELECT MAX(A) FROM T)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("schema", "tab").unwrap(),
+                ResourceId::try_new("eth", "tab_2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_table_references_of_tables_that_are_named_like_common_table_expressions() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM (WITH T AS (SELECT A FROM T) SELECT A FROM T) AS U WHERE A IN (SELECT A FROM ETH.U) AND A IN (SELECT A FROM T)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "t").unwrap(),
+                ResourceId::try_new("eth", "u").unwrap(),
+                ResourceId::try_new("eth", "t").unwrap()
+            ]
+        );
+    }
 }
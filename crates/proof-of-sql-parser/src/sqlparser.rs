@@ -1,18 +1,22 @@
 //! This module exists to adapt the current parser to `sqlparser`.
 use crate::{
     intermediate_ast::{
-        AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator, Expression, Literal,
-        OrderBy as PoSqlOrderBy, OrderByDirection, SelectResultExpr, SetExpression,
-        TableExpression, UnaryOperator as PoSqlUnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator,
+        CommonTableExpression, Expression, JoinCondition, JoinType, Literal,
+        OrderBy as PoSqlOrderBy, OrderByDirection, QualifiedColumn, SelectResultExpr,
+        SetExpression, TableExpression, UnaryOperator as PoSqlUnaryOperator, WindowFunction,
     },
+    posql_time::{PoSQLDateTimeField, PoSQLInterval},
     Identifier, ResourceId, SelectStatement,
 };
 use alloc::{boxed::Box, string::ToString, vec};
 use core::fmt::Display;
 use sqlparser::ast::{
-    BinaryOperator, DataType, Expr, Function, FunctionArg, FunctionArgExpr, GroupByExpr, Ident,
-    ObjectName, Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, TableFactor,
-    TableWithJoins, TimezoneInfo, UnaryOperator, Value, WildcardAdditionalOptions,
+    BinaryOperator, Cte, DataType, DateTimeField, Distinct, Expr, Function, FunctionArg,
+    FunctionArgExpr, GroupByExpr, Ident, Interval, Join, JoinConstraint, JoinOperator, ObjectName,
+    Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator,
+    SetQuantifier, TableAlias, TableFactor, TableWithJoins, TimezoneInfo, UnaryOperator, Value,
+    WildcardAdditionalOptions, WindowSpec, WindowType, With,
 };
 
 /// Convert a number into a [`Expr`].
@@ -58,6 +62,75 @@ impl From<TableExpression> for TableFactor {
                     partitions: vec![],
                 }
             }
+            TableExpression::Join { .. } => TableFactor::NestedJoin {
+                table_with_joins: Box::new(table.into()),
+                alias: None,
+            },
+            TableExpression::Subquery { query, alias } => TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new((*query).into()),
+                alias: Some(TableAlias {
+                    name: alias.into(),
+                    columns: vec![],
+                }),
+            },
+        }
+    }
+}
+
+impl From<QualifiedColumn> for Expr {
+    fn from(column: QualifiedColumn) -> Self {
+        match column.table {
+            Some(table) => Expr::CompoundIdentifier(vec![table.into(), column.column.into()]),
+            None => id(column.column),
+        }
+    }
+}
+
+impl From<JoinCondition> for Expr {
+    fn from(condition: JoinCondition) -> Self {
+        Expr::BinaryOp {
+            left: Box::new(condition.left.into()),
+            op: BinaryOperator::Eq,
+            right: Box::new(condition.right.into()),
+        }
+    }
+}
+
+impl From<TableExpression> for TableWithJoins {
+    fn from(table: TableExpression) -> Self {
+        match table {
+            TableExpression::Named { .. } | TableExpression::Subquery { .. } => TableWithJoins {
+                relation: table.into(),
+                joins: vec![],
+            },
+            TableExpression::Join {
+                left,
+                right,
+                join_type,
+                on,
+            } => {
+                let constraint = on
+                    .into_iter()
+                    .map(Expr::from)
+                    .reduce(|left, right| Expr::BinaryOp {
+                        left: Box::new(left),
+                        op: BinaryOperator::And,
+                        right: Box::new(right),
+                    })
+                    .map_or(JoinConstraint::None, JoinConstraint::On);
+                let join_operator = match join_type {
+                    JoinType::Inner => JoinOperator::Inner(constraint),
+                    JoinType::Left => JoinOperator::LeftOuter(constraint),
+                    JoinType::Right => JoinOperator::RightOuter(constraint),
+                };
+                let mut table_with_joins = TableWithJoins::from(*left);
+                table_with_joins.joins.push(Join {
+                    relation: (*right).into(),
+                    join_operator,
+                });
+                table_with_joins
+            }
         }
     }
 }
@@ -96,6 +169,7 @@ impl From<PoSqlBinaryOperator> for BinaryOperator {
             PoSqlBinaryOperator::Subtract => BinaryOperator::Minus,
             PoSqlBinaryOperator::Multiply => BinaryOperator::Multiply,
             PoSqlBinaryOperator::Division => BinaryOperator::Divide,
+            PoSqlBinaryOperator::Modulo => BinaryOperator::Modulo,
         }
     }
 }
@@ -108,6 +182,31 @@ impl From<PoSqlUnaryOperator> for UnaryOperator {
     }
 }
 
+impl From<PoSQLDateTimeField> for DateTimeField {
+    fn from(field: PoSQLDateTimeField) -> Self {
+        match field {
+            PoSQLDateTimeField::Year => DateTimeField::Year,
+            PoSQLDateTimeField::Month => DateTimeField::Month,
+            PoSQLDateTimeField::Day => DateTimeField::Day,
+            PoSQLDateTimeField::Hour => DateTimeField::Hour,
+            PoSQLDateTimeField::Minute => DateTimeField::Minute,
+            PoSQLDateTimeField::Second => DateTimeField::Second,
+        }
+    }
+}
+
+impl From<PoSQLInterval> for Expr {
+    fn from(interval: PoSQLInterval) -> Self {
+        Expr::Interval(Interval {
+            value: Box::new(number(interval.count())),
+            leading_field: Some(interval.field().into()),
+            leading_precision: None,
+            last_field: None,
+            fractional_seconds_precision: None,
+        })
+    }
+}
+
 impl From<PoSqlOrderBy> for OrderByExpr {
     fn from(order_by: PoSqlOrderBy) -> Self {
         let asc = match order_by.direction {
@@ -115,7 +214,11 @@ impl From<PoSqlOrderBy> for OrderByExpr {
             OrderByDirection::Desc => Some(false),
         };
         OrderByExpr {
-            expr: id(order_by.expr),
+            expr: QualifiedColumn {
+                table: order_by.table,
+                column: order_by.expr,
+            }
+            .into(),
             asc,
             nulls_first: None,
         }
@@ -127,6 +230,9 @@ impl From<Expression> for Expr {
         match expr {
             Expression::Literal(literal) => literal.into(),
             Expression::Column(identifier) => id(identifier),
+            Expression::QualifiedColumn { table, column } => {
+                Expr::CompoundIdentifier(vec![table.into(), column.into()])
+            }
             Expression::Unary { op, expr } => Expr::UnaryOp {
                 op: op.into(),
                 expr: Box::new((*expr).into()),
@@ -137,10 +243,112 @@ impl From<Expression> for Expr {
                 right: Box::new((*right).into()),
             },
             Expression::Wildcard => Expr::Wildcard,
-            Expression::Aggregation { op, expr } => Expr::Function(Function {
+            Expression::IsNull(expr) => Expr::IsNull(Box::new((*expr).into())),
+            Expression::IsNotNull(expr) => Expr::IsNotNull(Box::new((*expr).into())),
+            Expression::InList { expr, list } => Expr::InList {
+                expr: Box::new((*expr).into()),
+                list: list.into_iter().map(Into::into).collect(),
+                negated: false,
+            },
+            Expression::Between { expr, low, high } => Expr::Between {
+                expr: Box::new((*expr).into()),
+                negated: false,
+                low: Box::new((*low).into()),
+                high: Box::new((*high).into()),
+            },
+            Expression::Like { expr, pattern } => Expr::Like {
+                negated: false,
+                expr: Box::new((*expr).into()),
+                pattern: Box::new(Expr::Value(Value::SingleQuotedString(pattern))),
+                escape_char: None,
+            },
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                let (conditions, results) = when_then
+                    .into_iter()
+                    .map(|(condition, then)| (condition.into(), then.into()))
+                    .unzip();
+                Expr::Case {
+                    operand: None,
+                    conditions,
+                    results,
+                    else_result: else_expr.map(|else_expr| Box::new((*else_expr).into())),
+                }
+            }
+            Expression::InSubquery { expr, subquery } => Expr::InSubquery {
+                expr: Box::new((*expr).into()),
+                subquery: Box::new((*subquery).into()),
+                negated: false,
+            },
+            Expression::ScalarSubquery(query) => Expr::Subquery(Box::new((*query).into())),
+            Expression::Extract { field, expr } => Expr::Extract {
+                field: field.into(),
+                expr: Box::new((*expr).into()),
+            },
+            Expression::DateTrunc { field, expr } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new("date_trunc")]),
+                args: vec![
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                        Value::SingleQuotedString(field.to_string()),
+                    ))),
+                    FunctionArg::Unnamed((*expr).into()),
+                ],
+                filter: None,
+                null_treatment: None,
+                over: None,
+                distinct: false,
+                special: false,
+                order_by: vec![],
+            }),
+            Expression::AddInterval { expr, interval } => Expr::BinaryOp {
+                left: Box::new((*expr).into()),
+                op: BinaryOperator::Plus,
+                right: Box::new(interval.into()),
+            },
+            Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(function.to_string())]),
+                args: match function {
+                    WindowFunction::RowNumber | WindowFunction::Rank => vec![],
+                    WindowFunction::Lag(expr)
+                    | WindowFunction::Lead(expr)
+                    | WindowFunction::Sum(expr) => vec![FunctionArg::Unnamed((*expr).into())],
+                },
+                filter: None,
+                null_treatment: None,
+                over: Some(WindowType::WindowSpec(WindowSpec {
+                    partition_by: partition_by.into_iter().map(id).collect(),
+                    order_by: order_by.into_iter().map(Into::into).collect(),
+                    window_frame: None,
+                })),
+                distinct: false,
+                special: false,
+                order_by: vec![],
+            }),
+            // `COUNT(DISTINCT expr)` is a `COUNT` of the distinct values of `expr`
+            Expression::Aggregation {
+                op: AggregationOperator::CountDistinct,
+                expr,
+                filter,
+            } => Expr::Function(Function {
+                name: ObjectName(vec![Ident::new(AggregationOperator::Count.to_string())]),
+                args: vec![FunctionArg::Unnamed((*expr).into())],
+                filter: filter.map(|filter| Box::new((*filter).into())),
+                null_treatment: None,
+                over: None,
+                distinct: true,
+                special: false,
+                order_by: vec![],
+            }),
+            Expression::Aggregation { op, expr, filter } => Expr::Function(Function {
                 name: ObjectName(vec![Ident::new(op.to_string())]),
                 args: vec![FunctionArg::Unnamed((*expr).into())],
-                filter: None,
+                filter: filter.map(|filter| Box::new((*filter).into())),
                 null_treatment: None,
                 over: None,
                 distinct: false,
@@ -180,37 +388,75 @@ impl From<SelectResultExpr> for SelectItem {
     }
 }
 
-impl From<SetExpression> for Select {
+impl From<SetExpression> for SetExpr {
     fn from(select: SetExpression) -> Self {
-        match select {
-            SetExpression::Query {
-                result_exprs,
-                from,
-                where_expr,
-                group_by,
-            } => Select {
-                distinct: None,
-                top: None,
-                projection: result_exprs.into_iter().map(SelectItem::from).collect(),
-                into: None,
-                from: from
-                    .into_iter()
-                    .map(|table_expression| TableWithJoins {
-                        relation: (*table_expression).into(),
-                        joins: vec![],
-                    })
-                    .collect(),
-                lateral_views: vec![],
-                selection: where_expr.map(|expr| (*expr).into()),
-                group_by: GroupByExpr::Expressions(group_by.into_iter().map(id).collect()),
-                cluster_by: vec![],
-                distribute_by: vec![],
-                sort_by: vec![],
-                having: None,
-                named_window: vec![],
-                qualify: None,
-                value_table_mode: None,
+        set_expr_with_quantifier(select, SetQuantifier::All)
+    }
+}
+
+/// Converts a `SetExpression` into a [`SetExpr`] whose unions have the given quantifier.
+///
+/// `SELECT DISTINCT` and `UNION` are the unique rows of a query and of a `UNION ALL` respectively.
+fn set_expr_with_quantifier(select: SetExpression, set_quantifier: SetQuantifier) -> SetExpr {
+    match select {
+        SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+        } => SetExpr::Select(Box::new(Select {
+            distinct: None,
+            top: None,
+            projection: result_exprs.into_iter().map(SelectItem::from).collect(),
+            into: None,
+            from: from
+                .into_iter()
+                .map(|table_expression| (*table_expression).into())
+                .collect(),
+            lateral_views: vec![],
+            selection: where_expr.map(|expr| (*expr).into()),
+            group_by: GroupByExpr::Expressions(group_by.into_iter().map(Expr::from).collect()),
+            cluster_by: vec![],
+            distribute_by: vec![],
+            sort_by: vec![],
+            having: having.map(|expr| (*expr).into()),
+            named_window: vec![],
+            qualify: None,
+            value_table_mode: None,
+        })),
+        SetExpression::UnionAll { inputs } => inputs
+            .into_iter()
+            .map(SetExpr::from)
+            .reduce(|left, right| SetExpr::SetOperation {
+                op: SetOperator::Union,
+                set_quantifier,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+            .expect("A UNION ALL has at least one input"),
+        SetExpression::Distinct { input } => {
+            match set_expr_with_quantifier(*input, SetQuantifier::None) {
+                SetExpr::Select(mut select) => {
+                    select.distinct = Some(Distinct::Distinct);
+                    SetExpr::Select(select)
+                }
+                set_expr => set_expr,
+            }
+        }
+    }
+}
+
+impl From<CommonTableExpression> for Cte {
+    fn from(cte: CommonTableExpression) -> Self {
+        Cte {
+            alias: TableAlias {
+                name: cte.alias.into(),
+                columns: vec![],
             },
+            query: Box::new((*cte.query).into()),
+            from: None,
+            materialized: None,
         }
     }
 }
@@ -218,8 +464,11 @@ impl From<SetExpression> for Select {
 impl From<SelectStatement> for Query {
     fn from(select: SelectStatement) -> Self {
         Query {
-            with: None,
-            body: Box::new(SetExpr::Select(Box::new((*select.expr).into()))),
+            with: (!select.with.is_empty()).then(|| With {
+                recursive: false,
+                cte_tables: select.with.into_iter().map(Cte::from).collect(),
+            }),
+            body: Box::new((*select.expr).into()),
             order_by: select.order_by.into_iter().map(OrderByExpr::from).collect(),
             limit: select.slice.clone().map(|slice| number(slice.number_rows)),
             limit_by: vec![],
@@ -299,5 +548,132 @@ mod test {
         check_posql_intermediate_ast_to_sqlparser_equality(
             "select cat as cat, sum(a) as s, count(*) as rows from tab where d = 'Space and Time' group by cat;",
         );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select cat as cat, avg(a) as a_avg, count(*) as rows from tab group by cat;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select cat as cat, sum(a) as s, count(*) as rows from tab group by cat having sum(a) > 10;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a + b as ab, cat as cat, count(*) as rows from tab group by a + b, cat;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b like 'ab%' and c = 1;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_joins_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, b as b from namespace.t inner join namespace.u on t.id = u.id;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, b as b from t inner join u on t.id = u.tid and t.x = y where a = 4;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select t.a as a, u.a as b from t inner join u on t.id = u.tid where u.x = 4 order by t.a desc;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a, b from t join u on id = tid;",
+            "select a as a, b as b from t inner join u on id = tid;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, b as b from t left join u on t.id = u.id;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a, b from t right outer join u on t.id = u.id;",
+            "select a as a, b as b from t right join u on t.id = u.id;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_union_all_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from namespace.t union all select b as a from namespace.u;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from t where a > 1 union all select a as a from u union all select a as a from v order by a desc limit 3 offset 0;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_distinct_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select distinct a as a, b as b from namespace.t where c = 4;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from t union select b as a from u union select c as a from v order by a desc;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, count(distinct b) as b from t group by a;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_a_derived_table_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, s as s from (select a as a, sum(b) as s from t group by a) as sums where s > 4;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select b from (select b from t order by b desc limit 2) s;",
+            "select b as b from (select b as b from t order by b desc limit 2 offset 0) as s;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_common_table_expressions_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "with sums as (select a as a, sum(b) as s from t group by a), big as (select a as a from sums where s > 4) select a as a from big;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a from (with u as (select a from t) select a from u) as v;",
+            "select a as a from (with u as (select a as a from t) select a as a from u) as v;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_window_functions_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select row_number() over (partition by a, b order by c desc) as rn, rank() over (order by c asc) as r from t;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select c - lag(c) over (partition by a order by b desc) as delta, lead(c) over () as next from t;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select sum(c) over (partition by a order by b) as running from t;",
+            "select sum(c) over (partition by a order by b asc) as running from t;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_filtered_aggregations_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, sum(b) filter (where c > 1) as s, count(*) filter (where c = 2) as n from t group by a;",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_subqueries_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from namespace.t where a in (select b as b from namespace.u);",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from t where a > (select max(b) as m from u where c = 1);",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a from t where a not in (select b from u);",
+            "select a as a from t where not a in (select b as b from u);",
+        );
+    }
+
+    #[test]
+    fn we_can_convert_posql_intermediate_ast_with_date_time_functions_to_sqlparser() {
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select extract(year from a) as y, date_trunc('day', a) as d from t;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a + interval '2 days' as b from t where a - interval '1 hour' > c;",
+            "select a + interval 2 day as b from t where a + interval -1 hour > c;",
+        );
     }
 }
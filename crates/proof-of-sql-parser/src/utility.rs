@@ -1,11 +1,13 @@
 use crate::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        OrderByDirection, SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, CommonTableExpression, Expression,
+        JoinCondition, JoinType, Literal, OrderBy, OrderByDirection, QualifiedColumn,
+        SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator, WindowFunction,
     },
+    posql_time::{PoSQLDateTimeField, PoSQLInterval},
     Identifier, SelectStatement,
 };
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 
 ///
 /// # Panics
@@ -76,6 +78,120 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` P IS NULL
+#[must_use]
+pub fn is_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::IsNull(expr))
+}
+
+/// Construct a new boxed `Expression` P IS NOT NULL
+#[must_use]
+pub fn is_not_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::IsNotNull(expr))
+}
+
+/// Construct a new boxed `Expression` A IN (B, C, ...)
+#[must_use]
+pub fn in_list(expr: Box<Expression>, list: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().map(|candidate| *candidate).collect(),
+    })
+}
+
+/// Construct a new boxed `Expression` A IN (SELECT ...)
+#[must_use]
+pub fn in_subquery(expr: Box<Expression>, subquery: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::InSubquery {
+        expr,
+        subquery: Box::new(subquery),
+    })
+}
+
+/// Construct a new boxed `Expression` (SELECT ...) for a query with a single value
+#[must_use]
+pub fn scalar_subquery(query: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::ScalarSubquery(Box::new(query)))
+}
+
+/// Construct a new boxed `Expression` for a window function i.e. F OVER (PARTITION BY ... ORDER BY ...)
+///
+/// # Panics
+///
+/// This function will panic if any of the `partition_by` ids cannot be parsed
+/// into an identifier.
+#[must_use]
+pub fn window(
+    function: WindowFunction,
+    partition_by: &[&str],
+    order_by: Vec<OrderBy>,
+) -> Box<Expression> {
+    Box::new(Expression::Window {
+        function,
+        partition_by: partition_by.iter().map(|id| id.parse().unwrap()).collect(),
+        order_by,
+    })
+}
+
+/// Construct a new boxed `Expression` A BETWEEN B AND C
+#[must_use]
+pub fn between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between { expr, low, high })
+}
+
+/// Construct a new boxed `Expression` A LIKE 'pattern'
+#[must_use]
+pub fn like(expr: Box<Expression>, pattern: &str) -> Box<Expression> {
+    Box::new(Expression::Like {
+        expr,
+        pattern: pattern.to_string(),
+    })
+}
+
+/// Construct a new boxed `Expression` EXTRACT(FIELD FROM A)
+#[must_use]
+pub fn extract(field: PoSQLDateTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Extract { field, expr })
+}
+
+/// Construct a new boxed `Expression` `DATE_TRUNC('field', A)`
+#[must_use]
+pub fn date_trunc(field: PoSQLDateTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::DateTrunc { field, expr })
+}
+
+/// Construct a new boxed `Expression` A + INTERVAL 'count field'
+#[must_use]
+pub fn add_interval(
+    expr: Box<Expression>,
+    count: i64,
+    field: PoSQLDateTimeField,
+) -> Box<Expression> {
+    Box::new(Expression::AddInterval {
+        expr,
+        interval: PoSQLInterval::new(count, field),
+    })
+}
+
+/// Construct a new boxed `Expression` CASE WHEN P THEN A ... ELSE B END
+#[must_use]
+pub fn case_when(
+    when_then: Vec<(Box<Expression>, Box<Expression>)>,
+    else_expr: Option<Box<Expression>>,
+) -> Box<Expression> {
+    Box::new(Expression::Case {
+        when_then: when_then
+            .into_iter()
+            .map(|(condition, then)| (*condition, *then))
+            .collect(),
+        else_expr,
+    })
+}
+
 /// Construct a new boxed `Expression` P AND Q
 #[must_use]
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
@@ -136,6 +252,16 @@ pub fn div(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A % B
+#[must_use]
+pub fn modulo(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::Modulo,
+        left,
+        right,
+    })
+}
+
 /// Get table from schema and name.
 ///
 /// If the schema is `None`, the table is assumed to be in the default schema.
@@ -150,14 +276,99 @@ pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     })
 }
 
-/// Get column from name
+/// Get the result of a query as a table which is referred to by `alias` i.e. `(SELECT ...) AS ALIAS`
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn derived_tab(query: SelectStatement, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Subquery {
+        query: Box::new(query),
+        alias: alias.parse().unwrap(),
+    })
+}
+
+/// Get a column which is optionally qualified by a table i.e. `COL` or `TAB.COL`
+///
+/// # Panics
+///
+/// This function will panic if the table or column name cannot be parsed as valid [Identifier]s.
+#[must_use]
+pub fn qualified_col(name: &str) -> QualifiedColumn {
+    match name.split_once('.') {
+        Some((table, column)) => QualifiedColumn {
+            table: Some(table.parse().unwrap()),
+            column: column.parse().unwrap(),
+        },
+        None => QualifiedColumn {
+            table: None,
+            column: name.parse().unwrap(),
+        },
+    }
+}
+
+/// Join two table expressions i.e. `LEFT <join_type> JOIN RIGHT ON L0 = R0 AND L1 = R1 ...`
+///
+/// Each pair in `on` is a pair of optionally qualified column names e.g. `("tab.id", "details.id")`.
+///
+/// # Panics
+///
+/// This function will panic if any of the column names cannot be parsed.
+#[must_use]
+pub fn join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    join_type: JoinType,
+    on: &[(&str, &str)],
+) -> Box<TableExpression> {
+    Box::new(TableExpression::Join {
+        left,
+        right,
+        join_type,
+        on: on
+            .iter()
+            .map(|(left, right)| JoinCondition {
+                left: qualified_col(left),
+                right: qualified_col(right),
+            })
+            .collect(),
+    })
+}
+
+/// Inner join two table expressions i.e. `LEFT INNER JOIN RIGHT ON L0 = R0 AND L1 = R1 ...`
+///
+/// See [`join`] for the format of `on`.
+///
+/// # Panics
+///
+/// This function will panic if any of the column names cannot be parsed.
+#[must_use]
+pub fn inner_join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    on: &[(&str, &str)],
+) -> Box<TableExpression> {
+    join(left, right, JoinType::Inner, on)
+}
+
+/// Get column from name, which is optionally qualified by a table i.e. `COL` or `TAB.COL`
 ///
 /// # Panics
 ///
 /// This function will panic if the `name` cannot be parsed into a valid column expression as valid [Identifier]s.
 #[must_use]
 pub fn col(name: &str) -> Box<Expression> {
-    Box::new(Expression::Column(name.parse().unwrap()))
+    Box::new(match qualified_col(name) {
+        QualifiedColumn {
+            table: Some(table),
+            column,
+        } => Expression::QualifiedColumn { table, column },
+        QualifiedColumn {
+            table: None,
+            column,
+        } => Expression::Column(column),
+    })
 }
 
 /// Get literal from value
@@ -171,6 +382,7 @@ pub fn sum(expr: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Aggregation {
         op: AggregationOperator::Sum,
         expr,
+        filter: None,
     })
 }
 
@@ -180,6 +392,7 @@ pub fn min(expr: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Aggregation {
         op: AggregationOperator::Min,
         expr,
+        filter: None,
     })
 }
 
@@ -189,6 +402,17 @@ pub fn max(expr: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Aggregation {
         op: AggregationOperator::Max,
         expr,
+        filter: None,
+    })
+}
+
+/// Compute the average of an expression
+#[must_use]
+pub fn avg(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::Avg,
+        expr,
+        filter: None,
     })
 }
 
@@ -198,9 +422,35 @@ pub fn count(expr: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Aggregation {
         op: AggregationOperator::Count,
         expr,
+        filter: None,
+    })
+}
+
+/// Count the amount of distinct non-null entries of expression
+#[must_use]
+pub fn count_distinct(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::CountDistinct,
+        expr,
+        filter: None,
     })
 }
 
+/// Restrict the rows of an aggregation to those where a condition holds,
+/// i.e. `<aggregation> FILTER (WHERE <condition>)`
+///
+/// # Panics
+///
+/// This function will panic if `aggregation` is not an aggregation.
+#[must_use]
+pub fn filtered(mut aggregation: Box<Expression>, condition: Box<Expression>) -> Box<Expression> {
+    let Expression::Aggregation { filter, .. } = aggregation.as_mut() else {
+        panic!("only aggregations can be filtered")
+    };
+    *filter = Some(condition);
+    aggregation
+}
+
 /// Count the rows
 #[must_use]
 pub fn count_all() -> Box<Expression> {
@@ -284,6 +534,19 @@ pub fn sum_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
     })
 }
 
+/// Compute the average of an expression and give it an alias i.e. SELECT AVG(EXPR) AS ALIAS
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed.
+#[must_use]
+pub fn avg_res(expr: Box<Expression>, alias: &str) -> SelectResultExpr {
+    SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+        expr: avg(expr),
+        alias: alias.parse().unwrap(),
+    })
+}
+
 /// Count the amount of non-null entries of expression and give it an alias i.e. SELECT COUNT(EXPR) AS ALIAS
 ///
 /// # Panics
@@ -308,6 +571,7 @@ pub fn count_all_res(alias: &str) -> SelectResultExpr {
         expr: Box::new(Expression::Aggregation {
             op: AggregationOperator::Count,
             expr: Box::new(Expression::Wildcard),
+            filter: None,
         }),
         alias: alias.parse().unwrap(),
     })
@@ -319,13 +583,14 @@ pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
     where_expr: Box<Expression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: Some(where_expr),
         group_by,
+        having: None,
     })
 }
 
@@ -336,16 +601,41 @@ pub fn query(
 pub fn query_all(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: None,
         group_by,
+        having: None,
+    })
+}
+
+/// Generate a `SetExpression` of the kind QUERY1 UNION ALL QUERY2 UNION ALL ...
+#[must_use]
+pub fn union_all(inputs: Vec<Box<SetExpression>>) -> Box<SetExpression> {
+    Box::new(SetExpression::UnionAll {
+        inputs: inputs.into_iter().map(|input| *input).collect(),
     })
 }
 
+/// Generate a `SetExpression` of the kind SELECT DISTINCT ... from a `SetExpression` generated by `query` or `query_all`,
+/// or of the kind QUERY1 UNION QUERY2 UNION ... from one generated by `union_all`
+#[must_use]
+pub fn distinct(input: Box<SetExpression>) -> Box<SetExpression> {
+    Box::new(SetExpression::Distinct { input })
+}
+
+/// Add a HAVING clause to a `SetExpression` generated by `query` or `query_all`
+#[must_use]
+pub fn having(mut query: Box<SetExpression>, having_expr: Box<Expression>) -> Box<SetExpression> {
+    if let SetExpression::Query { having, .. } = &mut *query {
+        *having = Some(having_expr);
+    }
+    query
+}
+
 /// Generate a query of the kind SELECT ... ORDER BY ... [LIMIT ... OFFSET ...]
 ///
 /// Note that `expr` is a boxed `SetExpression`
@@ -356,27 +646,49 @@ pub fn select(
     slice: Option<Slice>,
 ) -> SelectStatement {
     SelectStatement {
+        with: vec![],
         expr,
         order_by,
         slice,
     }
 }
 
-/// Order by one column i.e. ORDER BY ID [ASC|DESC]
+/// Name the result of a query for a `WITH` clause i.e. `ALIAS AS (SELECT ...)`
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn cte(alias: &str, query: SelectStatement) -> CommonTableExpression {
+    CommonTableExpression {
+        alias: alias.parse().unwrap(),
+        query: Box::new(query),
+    }
+}
+
+/// Add a `WITH` clause to a query i.e. `WITH ... SELECT ...`
+#[must_use]
+pub fn with(ctes: Vec<CommonTableExpression>, query: SelectStatement) -> SelectStatement {
+    SelectStatement {
+        with: ctes,
+        ..query
+    }
+}
+
+/// Order by one column, which is optionally qualified by a table i.e. ORDER BY [TAB.]ID [ASC|DESC]
 ///
 /// # Panics
 ///
 /// This function will panic if the `id` cannot be parsed into an identifier.
 #[must_use]
 pub fn order(id: &str, direction: OrderByDirection) -> Vec<OrderBy> {
-    vec![OrderBy {
-        expr: id.parse().unwrap(),
-        direction,
-    }]
+    orders(&[id], &[direction])
 }
 
 /// Order by multiple columns i.e. ORDER BY ID0 [ASC|DESC], ID1 [ASC|DESC], ...
 ///
+/// Each column is optionally qualified by a table, see [`qualified_col`].
+///
 /// # Panics
 ///
 /// This function will panic if any of the `ids` cannot be parsed
@@ -385,9 +697,13 @@ pub fn order(id: &str, direction: OrderByDirection) -> Vec<OrderBy> {
 pub fn orders(ids: &[&str], directions: &[OrderByDirection]) -> Vec<OrderBy> {
     ids.iter()
         .zip(directions.iter())
-        .map(|(id, dir)| OrderBy {
-            expr: id.parse().unwrap(),
-            direction: *dir,
+        .map(|(id, dir)| {
+            let QualifiedColumn { table, column } = qualified_col(id);
+            OrderBy {
+                expr: column,
+                table,
+                direction: *dir,
+            }
         })
         .collect::<Vec<_>>()
 }
@@ -408,6 +724,8 @@ pub fn slice(number_rows: u64, offset_value: i64) -> Option<Slice> {
 /// This function will panic if any of the `ids` cannot be parsed
 /// into an identifier.
 #[must_use]
-pub fn group_by(ids: &[&str]) -> Vec<Identifier> {
-    ids.iter().map(|id| id.parse().unwrap()).collect()
+pub fn group_by(ids: &[&str]) -> Vec<Expression> {
+    ids.iter()
+        .map(|id| Expression::Column(id.parse().unwrap()))
+        .collect()
 }
@@ -14,7 +14,7 @@
 //! However, the actual arrow backing `i128` is the correct value.
 use super::scalar_and_i256_conversions::{convert_i256_to_scalar, convert_scalar_to_i256};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable, OwnedTableError},
+    database::{NullableOwnedColumn, OwnedColumn, OwnedTable, OwnedTableError},
     map::IndexMap,
     math::decimal::Precision,
     scalar::Scalar,
@@ -22,10 +22,11 @@ use crate::base::{
 use alloc::sync::Arc;
 use arrow::{
     array::{
-        ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Decimal256Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
+        make_array, Array, ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Decimal256Array,
+        Int16Array, Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
         TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt8Array,
     },
+    buffer::NullBuffer,
     datatypes::{i256, DataType, Schema, SchemaRef, TimeUnit as ArrowTimeUnit},
     error::ArrowError,
     record_batch::RecordBatch,
@@ -80,6 +81,8 @@ pub enum OwnedArrowConversionError {
 /// Will panic if setting precision and scale fails when converting `OwnedColumn::Int128`.
 /// Will panic if setting precision and scale fails when converting `OwnedColumn::Decimal75`.
 /// Will panic if trying to convert `OwnedColumn::Scalar`, as this conversion is not implemented
+///
+/// The NULL rows of a nullable column are nulls of the array.
 impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
     fn from(value: OwnedColumn<S>) -> Self {
         match value {
@@ -114,6 +117,14 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
                 PoSQLTimeUnit::Microsecond => Arc::new(TimestampMicrosecondArray::from(col)),
                 PoSQLTimeUnit::Nanosecond => Arc::new(TimestampNanosecondArray::from(col)),
             },
+            OwnedColumn::Nullable(values, presence) => make_array(
+                ArrayRef::from(*values)
+                    .to_data()
+                    .into_builder()
+                    .nulls(Some(NullBuffer::from(presence)))
+                    .build()
+                    .expect("the presence has the length of the values"),
+            ),
         }
     }
 }
@@ -145,179 +156,194 @@ impl<S: Scalar> TryFrom<ArrayRef> for OwnedColumn<S> {
 impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
     type Error = OwnedArrowConversionError;
 
-    #[allow(clippy::too_many_lines)]
+    /// Arrays with nulls are converted to nullable columns.
+    fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
+        NullableOwnedColumn::try_from(value).map(Self::from)
+    }
+}
+
+impl<S: Scalar> TryFrom<&ArrayRef> for NullableOwnedColumn<S> {
+    type Error = OwnedArrowConversionError;
+
     /// # Panics
     ///
-    /// Will panic if downcasting fails for the following types:
-    /// - `BooleanArray` when converting from `DataType::Boolean`.
-    /// - `Int16Array` when converting from `DataType::Int16`.
-    /// - `Int32Array` when converting from `DataType::Int32`.
-    /// - `Int64Array` when converting from `DataType::Int64`.
-    /// - `Decimal128Array` when converting from `DataType::Decimal128(38, 0)`.
-    /// - `Decimal256Array` when converting from `DataType::Decimal256` if precision is less than or equal to 75.
-    /// - `StringArray` when converting from `DataType::Utf8`.
+    /// Will panic if the conversion of the values of the array panics.
     fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
-        match &value.data_type() {
-            // Arrow uses a bit-packed representation for booleans.
-            // Hence we need to unpack the bits to get the actual boolean values.
-            DataType::Boolean => Ok(Self::Boolean(
-                value
-                    .as_any()
-                    .downcast_ref::<BooleanArray>()
-                    .unwrap()
-                    .iter()
-                    .collect::<Option<Vec<bool>>>()
-                    .ok_or(OwnedArrowConversionError::NullNotSupportedYet)?,
-            )),
-            DataType::UInt8 => Ok(Self::Uint8(
-                value
-                    .as_any()
-                    .downcast_ref::<UInt8Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int8 => Ok(Self::TinyInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int8Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int16 => Ok(Self::SmallInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int16Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int32 => Ok(Self::Int(
-                value
-                    .as_any()
-                    .downcast_ref::<Int32Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int64 => Ok(Self::BigInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int64Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Decimal128(38, 0) => Ok(Self::Int128(
-                value
+        let presence = (value.null_count() != 0)
+            .then(|| (0..value.len()).map(|i| value.is_valid(i)).collect());
+        let values = owned_column_from_array_values(value)?;
+        Ok(NullableOwnedColumn::try_new(values, presence)
+            .expect("the presence column has the length of the array"))
+    }
+}
+
+/// Converts the values of an array, whatever they are at its nulls.
+///
+/// # Panics
+///
+/// Will panic if downcasting fails for the following types:
+/// - `BooleanArray` when converting from `DataType::Boolean`.
+/// - `Int16Array` when converting from `DataType::Int16`.
+/// - `Int32Array` when converting from `DataType::Int32`.
+/// - `Int64Array` when converting from `DataType::Int64`.
+/// - `Decimal128Array` when converting from `DataType::Decimal128(38, 0)`.
+/// - `Decimal256Array` when converting from `DataType::Decimal256` if precision is less than or equal to 75.
+/// - `StringArray` when converting from `DataType::Utf8`.
+#[allow(clippy::too_many_lines)]
+fn owned_column_from_array_values<S: Scalar>(
+    value: &ArrayRef,
+) -> Result<OwnedColumn<S>, OwnedArrowConversionError> {
+    match &value.data_type() {
+        // Arrow uses a bit-packed representation for booleans.
+        // Hence we need to unpack the bits to get the actual boolean values.
+        DataType::Boolean => Ok(OwnedColumn::Boolean(
+            value
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::UInt8 => Ok(OwnedColumn::Uint8(
+            value
+                .as_any()
+                .downcast_ref::<UInt8Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Int8 => Ok(OwnedColumn::TinyInt(
+            value
+                .as_any()
+                .downcast_ref::<Int8Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Int16 => Ok(OwnedColumn::SmallInt(
+            value
+                .as_any()
+                .downcast_ref::<Int16Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Int32 => Ok(OwnedColumn::Int(
+            value
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Int64 => Ok(OwnedColumn::BigInt(
+            value
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Decimal128(38, 0) => Ok(OwnedColumn::Int128(
+            value
+                .as_any()
+                .downcast_ref::<Decimal128Array>()
+                .unwrap()
+                .values()
+                .to_vec(),
+        )),
+        DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(OwnedColumn::Decimal75(
+            Precision::new(*precision).expect("precision is less than 76"),
+            *scale,
+            value
+                .as_any()
+                .downcast_ref::<Decimal256Array>()
+                .unwrap()
+                .iter()
+                .map(|value| value.map_or(Some(S::ZERO), |value| convert_i256_to_scalar(&value)))
+                .map(Option::unwrap)
+                .collect(),
+        )),
+        DataType::Utf8 => Ok(OwnedColumn::VarChar(
+            value
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .iter()
+                .map(|s| s.unwrap_or_default().to_string())
+                .collect(),
+        )),
+        DataType::Binary => Ok(OwnedColumn::VarBinary(
+            value
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .unwrap()
+                .iter()
+                .map(|s| s.map(<[u8]>::to_vec).unwrap_or_default())
+                .collect(),
+        )),
+        DataType::Timestamp(time_unit, timezone) => match time_unit {
+            ArrowTimeUnit::Second => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<Decimal128Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(Self::Decimal75(
-                Precision::new(*precision).expect("precision is less than 76"),
-                *scale,
-                value
+                    .downcast_ref::<TimestampSecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Second,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Millisecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<Decimal256Array>()
-                    .unwrap()
-                    .values()
-                    .iter()
-                    .map(convert_i256_to_scalar)
-                    .map(Option::unwrap)
-                    .collect(),
-            )),
-            DataType::Utf8 => Ok(Self::VarChar(
-                value
+                    .downcast_ref::<TimestampMillisecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Millisecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Microsecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<StringArray>()
-                    .unwrap()
-                    .iter()
-                    .map(|s| s.unwrap().to_string())
-                    .collect(),
-            )),
-            DataType::Binary => Ok(Self::VarBinary(
-                value
+                    .downcast_ref::<TimestampMicrosecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Microsecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Nanosecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<BinaryArray>()
-                    .unwrap()
-                    .iter()
-                    .map(|s| s.map(<[u8]>::to_vec).unwrap())
-                    .collect(),
-            )),
-            DataType::Timestamp(time_unit, timezone) => match time_unit {
-                ArrowTimeUnit::Second => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampSecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Second,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Millisecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampMillisecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Millisecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Microsecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampMicrosecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Microsecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Nanosecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampNanosecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Nanosecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-            },
-            &data_type => Err(OwnedArrowConversionError::UnsupportedType {
-                datatype: data_type.clone(),
-            }),
-        }
+                    .downcast_ref::<TimestampNanosecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Nanosecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+        },
+        &data_type => Err(OwnedArrowConversionError::UnsupportedType {
+            datatype: data_type.clone(),
+        }),
     }
 }
 
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
+    /// Arrays with nulls are converted to nullable columns.
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
-        let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
+        let columns: Vec<(Ident, OwnedColumn<S>)> = value
             .schema()
             .fields()
             .iter()
@@ -327,8 +353,9 @@ impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
                 let identifier = Ident::new(field.name());
                 Ok((identifier, owned_column))
             })
-            .collect();
-        let owned_table = Self::try_new(table?)?;
+            .collect::<Result<Vec<_>, Self::Error>>()?;
+        let num_columns = columns.len();
+        let owned_table = Self::try_new(columns.into_iter().collect())?;
         if num_columns == owned_table.num_columns() {
             Ok(owned_table)
         } else {
@@ -1,10 +1,10 @@
 use super::owned_and_arrow_conversions::OwnedArrowConversionError;
 use crate::base::{
-    database::{owned_table_utility::*, OwnedColumn, OwnedTable},
+    database::{owned_table_utility::*, NullableOwnedColumn, OwnedColumn, OwnedTable},
     map::IndexMap,
     scalar::test_scalar::TestScalar,
 };
-use alloc::sync::Arc;
+use alloc::{boxed::Box, sync::Arc};
 use arrow::{
     array::{
         ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Float32Array, Int64Array, StringArray,
@@ -197,3 +197,57 @@ fn we_panic_when_converting_an_owned_table_with_a_scalar_column() {
     let owned_table = owned_table::<TestScalar>([scalar("a", [0; 0])]);
     let _ = RecordBatch::try_from(owned_table);
 }
+
+#[test]
+fn we_can_convert_arrays_with_nulls_to_nullable_owned_columns() {
+    let array_ref: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+    assert_eq!(
+        OwnedColumn::<TestScalar>::try_from(&array_ref).unwrap(),
+        OwnedColumn::Nullable(
+            Box::new(OwnedColumn::BigInt(vec![1, 0, 3])),
+            vec![true, false, true]
+        )
+    );
+    let nullable_column = NullableOwnedColumn::<TestScalar>::try_from(&array_ref).unwrap();
+    assert_eq!(
+        nullable_column.values(),
+        &OwnedColumn::BigInt(vec![1, 0, 3])
+    );
+    assert_eq!(nullable_column.presence(), Some(&[true, false, true][..]));
+
+    let array_ref: ArrayRef = Arc::new(StringArray::from(vec![None, Some("b")]));
+    let nullable_column = NullableOwnedColumn::<TestScalar>::try_from(&array_ref).unwrap();
+    assert_eq!(
+        nullable_column.values(),
+        &OwnedColumn::VarChar(vec![String::new(), "b".to_string()])
+    );
+    assert_eq!(nullable_column.presence(), Some(&[false, true][..]));
+
+    let array_ref: ArrayRef = Arc::new(BooleanArray::from(vec![true, false]));
+    let nullable_column = NullableOwnedColumn::<TestScalar>::try_from(&array_ref).unwrap();
+    assert!(!nullable_column.is_nullable());
+}
+
+#[test]
+fn we_can_convert_between_a_record_batch_with_nulls_and_an_owned_table_with_nullable_columns() {
+    let record_batch = RecordBatch::try_from_iter(vec![
+        (
+            "a",
+            Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef,
+        ),
+        (
+            "b",
+            Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let table = OwnedTable::<TestScalar>::try_from(record_batch.clone()).unwrap();
+    assert_eq!(
+        table,
+        owned_table([
+            nullable(bigint("a", [1, 0]), [true, false]),
+            boolean("b", [true, false]),
+        ])
+    );
+    assert_eq!(RecordBatch::try_from(table).unwrap(), record_batch);
+}
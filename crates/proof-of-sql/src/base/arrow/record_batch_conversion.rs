@@ -1,5 +1,6 @@
 use super::{
-    arrow_array_to_column_conversion::ArrayRefExt,
+    arrow_array_to_column_conversion::{ArrayRefExt, ArrowArrayToColumnConversionError},
+    owned_and_arrow_conversions::OwnedArrowConversionError,
     record_batch_errors::{AppendRecordBatchTableCommitmentError, RecordBatchToColumnsError},
 };
 use crate::base::{
@@ -7,7 +8,7 @@ use crate::base::{
         AppendColumnCommitmentsError, AppendTableCommitmentError, Commitment, TableCommitment,
         TableCommitmentFromColumnsError,
     },
-    database::Column,
+    database::{Column, NullableOwnedColumn, OwnedColumn},
     scalar::Scalar,
 };
 use arrow::record_batch::RecordBatch;
@@ -17,21 +18,82 @@ use sqlparser::ast::Ident;
 /// This function will return an error if:
 /// - The field name cannot be parsed into an [`Identifier`].
 /// - The conversion of an Arrow array to a [`Column`] fails.
+///
+/// Arrays with nulls are converted to nullable columns.
 pub fn batch_to_columns<'a, S: Scalar + 'a>(
     batch: &'a RecordBatch,
     alloc: &'a Bump,
 ) -> Result<Vec<(Ident, Column<'a, S>)>, RecordBatchToColumnsError> {
-    batch
-        .schema()
-        .fields()
-        .into_iter()
-        .zip(batch.columns())
-        .map(|(field, array)| {
-            let identifier: Ident = field.name().as_str().into();
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (field, array) in batch.schema().fields().into_iter().zip(batch.columns()) {
+        let identifier: Ident = field.name().as_str().into();
+        if array.null_count() == 0 {
             let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
-            Ok((identifier, column))
-        })
-        .collect()
+            columns.push((identifier, column));
+        } else {
+            let nullable_column =
+                NullableOwnedColumn::<S>::try_from(array).map_err(|err| match err {
+                    OwnedArrowConversionError::TimestampConversionError { source } => {
+                        ArrowArrayToColumnConversionError::TimestampConversionError { source }
+                    }
+                    _ => ArrowArrayToColumnConversionError::UnsupportedType {
+                        datatype: array.data_type().clone(),
+                    },
+                })?;
+            let column = OwnedColumn::from(nullable_column);
+            columns.push((identifier, copy_owned_column(&column, alloc)));
+        }
+    }
+    Ok(columns)
+}
+
+/// Copies an [`OwnedColumn`] into a [`Column`] that is allocated in `alloc`.
+fn copy_owned_column<'a, S: Scalar>(column: &OwnedColumn<S>, alloc: &'a Bump) -> Column<'a, S> {
+    match column {
+        OwnedColumn::Boolean(col) => Column::Boolean(alloc.alloc_slice_copy(col)),
+        OwnedColumn::Uint8(col) => Column::Uint8(alloc.alloc_slice_copy(col)),
+        OwnedColumn::TinyInt(col) => Column::TinyInt(alloc.alloc_slice_copy(col)),
+        OwnedColumn::SmallInt(col) => Column::SmallInt(alloc.alloc_slice_copy(col)),
+        OwnedColumn::Int(col) => Column::Int(alloc.alloc_slice_copy(col)),
+        OwnedColumn::BigInt(col) => Column::BigInt(alloc.alloc_slice_copy(col)),
+        OwnedColumn::Int128(col) => Column::Int128(alloc.alloc_slice_copy(col)),
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            Column::Decimal75(*precision, *scale, alloc.alloc_slice_copy(col))
+        }
+        OwnedColumn::Scalar(col) => Column::Scalar(alloc.alloc_slice_copy(col)),
+        OwnedColumn::VarChar(col) => {
+            let scalars = col.iter().map(S::from).collect::<Vec<_>>();
+            let strs = col
+                .iter()
+                .map(|s| alloc.alloc_str(s) as &str)
+                .collect::<Vec<_>>();
+            Column::VarChar((
+                alloc.alloc_slice_copy(strs.as_slice()),
+                alloc.alloc_slice_copy(scalars.as_slice()),
+            ))
+        }
+        OwnedColumn::VarBinary(col) => {
+            let scalars = col
+                .iter()
+                .map(|b| S::from_byte_slice_via_hash(b))
+                .collect::<Vec<_>>();
+            let bytes = col
+                .iter()
+                .map(|b| alloc.alloc_slice_copy(b) as &[u8])
+                .collect::<Vec<_>>();
+            Column::VarBinary((
+                alloc.alloc_slice_copy(bytes.as_slice()),
+                alloc.alloc_slice_copy(scalars.as_slice()),
+            ))
+        }
+        OwnedColumn::TimestampTZ(tu, tz, col) => {
+            Column::TimestampTZ(*tu, *tz, alloc.alloc_slice_copy(col))
+        }
+        OwnedColumn::Nullable(values, presence) => Column::Nullable(
+            alloc.alloc(copy_owned_column(values, alloc)),
+            alloc.alloc_slice_copy(presence),
+        ),
+    }
 }
 
 impl<C: Commitment> TableCommitment<C> {
@@ -137,6 +137,48 @@ where
         }
     }
 
+    /// Combine [`Bounds`]s as if some rows of self's source collection, whose bounds are
+    /// `removed`, are being replaced by rows whose bounds are `added`.
+    ///
+    /// Taking the difference and then the union would almost always give [`Bounds::Bounded`].
+    /// Instead, each end of the result is known to be exact if the rows of self's source collection
+    /// that attain it are not removed, or if the added rows reach past it.
+    fn replace(self, removed: Bounds<T>, added: Bounds<T>) -> Self {
+        let (bounds, is_sharp) = match self {
+            Bounds::Empty => return added,
+            Bounds::Bounded(bounds) => (bounds, false),
+            Bounds::Sharp(bounds) => (bounds, true),
+        };
+        // whether the minimum and maximum of self's source collection are still attained
+        let (min_is_kept, max_is_kept) = match &removed {
+            _ if !is_sharp => (false, false),
+            Bounds::Empty => (true, true),
+            Bounds::Bounded(removed) | Bounds::Sharp(removed) => {
+                (bounds.min() < removed.min(), removed.max() < bounds.max())
+            }
+        };
+        let (min_is_sharp, max_is_sharp) = match &added {
+            Bounds::Empty => (min_is_kept, max_is_kept),
+            Bounds::Bounded(added) => (
+                min_is_kept && bounds.min() <= added.min(),
+                max_is_kept && added.max() <= bounds.max(),
+            ),
+            Bounds::Sharp(added) => (
+                min_is_kept || added.min() <= bounds.min(),
+                max_is_kept || bounds.max() <= added.max(),
+            ),
+        };
+        let bounds = match added {
+            Bounds::Empty => bounds,
+            Bounds::Bounded(added) | Bounds::Sharp(added) => bounds.union(added),
+        };
+        if min_is_sharp && max_is_sharp {
+            Bounds::Sharp(bounds)
+        } else {
+            Bounds::Bounded(bounds)
+        }
+    }
+
     /// Returns true if the value is within these bounds.
     ///
     /// This doesn't necessarily mean that the source collection contains this value.
@@ -147,6 +189,19 @@ where
             Bounds::Bounded(inner) | Bounds::Sharp(inner) => inner.surrounds(value),
         }
     }
+
+    /// Returns true if the source collections of these bounds may have values in common.
+    ///
+    /// A `false` result implies that the source collections cannot have values in common.
+    pub fn overlaps(&self, other: &Bounds<T>) -> bool {
+        match (self, other) {
+            (Bounds::Empty, _) | (_, Bounds::Empty) => false,
+            (
+                Bounds::Bounded(inner_a) | Bounds::Sharp(inner_a),
+                Bounds::Bounded(inner_b) | Bounds::Sharp(inner_b),
+            ) => inner_a.min() <= inner_b.max() && inner_b.min() <= inner_a.max(),
+        }
+    }
 }
 
 impl<'a, T> FromIterator<&'a T> for Bounds<T>
@@ -235,6 +290,7 @@ impl ColumnBounds {
             CommittableColumn::TimestampTZ(_, _, times) => {
                 ColumnBounds::TimestampTZ(Bounds::from_iter(*times))
             }
+            CommittableColumn::Nullable(values, _) => ColumnBounds::from_column(values),
             CommittableColumn::Boolean(_)
             | CommittableColumn::Decimal75(_, _, _)
             | CommittableColumn::Scalar(_)
@@ -311,6 +367,92 @@ impl ColumnBounds {
             }),
         }
     }
+
+    /// Combine [`ColumnBounds`] as if some rows of self's source collection, whose bounds are
+    /// `removed`, are being replaced by rows whose bounds are `added`.
+    ///
+    /// Can error if the three values do not share the same [`ColumnBounds`] variant.
+    pub fn try_replace(self, removed: Self, added: Self) -> Result<Self, ColumnBoundsMismatch> {
+        match (self, removed, added) {
+            (ColumnBounds::NoOrder, ColumnBounds::NoOrder, ColumnBounds::NoOrder) => Ok(self),
+            (
+                ColumnBounds::Uint8(bounds),
+                ColumnBounds::Uint8(removed),
+                ColumnBounds::Uint8(added),
+            ) => Ok(ColumnBounds::Uint8(bounds.replace(removed, added))),
+            (
+                ColumnBounds::TinyInt(bounds),
+                ColumnBounds::TinyInt(removed),
+                ColumnBounds::TinyInt(added),
+            ) => Ok(ColumnBounds::TinyInt(bounds.replace(removed, added))),
+            (
+                ColumnBounds::SmallInt(bounds),
+                ColumnBounds::SmallInt(removed),
+                ColumnBounds::SmallInt(added),
+            ) => Ok(ColumnBounds::SmallInt(bounds.replace(removed, added))),
+            (ColumnBounds::Int(bounds), ColumnBounds::Int(removed), ColumnBounds::Int(added)) => {
+                Ok(ColumnBounds::Int(bounds.replace(removed, added)))
+            }
+            (
+                ColumnBounds::BigInt(bounds),
+                ColumnBounds::BigInt(removed),
+                ColumnBounds::BigInt(added),
+            ) => Ok(ColumnBounds::BigInt(bounds.replace(removed, added))),
+            (
+                ColumnBounds::Int128(bounds),
+                ColumnBounds::Int128(removed),
+                ColumnBounds::Int128(added),
+            ) => Ok(ColumnBounds::Int128(bounds.replace(removed, added))),
+            (
+                ColumnBounds::TimestampTZ(bounds),
+                ColumnBounds::TimestampTZ(removed),
+                ColumnBounds::TimestampTZ(added),
+            ) => Ok(ColumnBounds::TimestampTZ(bounds.replace(removed, added))),
+            (_, _, _) => Err(ColumnBoundsMismatch {
+                bounds_a: Box::new(self),
+                bounds_b: Box::new(
+                    if core::mem::discriminant(&self) == core::mem::discriminant(&removed) {
+                        added
+                    } else {
+                        removed
+                    },
+                ),
+            }),
+        }
+    }
+
+    /// Returns true if the source collections of two [`ColumnBounds`] may have values in common.
+    ///
+    /// This is always the case for [`ColumnBounds::NoOrder`], since such columns are not bounded.
+    /// Can error if the two values do not share the same [`ColumnBounds`] variant.
+    pub fn try_overlaps(&self, other: &Self) -> Result<bool, ColumnBoundsMismatch> {
+        match (self, other) {
+            (ColumnBounds::NoOrder, ColumnBounds::NoOrder) => Ok(true),
+            (ColumnBounds::Uint8(bounds_a), ColumnBounds::Uint8(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (ColumnBounds::TinyInt(bounds_a), ColumnBounds::TinyInt(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (ColumnBounds::SmallInt(bounds_a), ColumnBounds::SmallInt(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (ColumnBounds::Int(bounds_a), ColumnBounds::Int(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (ColumnBounds::BigInt(bounds_a), ColumnBounds::BigInt(bounds_b))
+            | (ColumnBounds::TimestampTZ(bounds_a), ColumnBounds::TimestampTZ(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (ColumnBounds::Int128(bounds_a), ColumnBounds::Int128(bounds_b)) => {
+                Ok(bounds_a.overlaps(bounds_b))
+            }
+            (_, _) => Err(ColumnBoundsMismatch {
+                bounds_a: Box::new(*self),
+                bounds_b: Box::new(*other),
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -506,6 +648,108 @@ mod tests {
         assert_eq!(sharp_a.difference(Bounds::Bounded(bounds_b)), bounded_a);
     }
 
+    #[test]
+    fn replacing_rows_that_do_not_attain_the_bounds_keeps_them_sharp() {
+        let sharp = Bounds::Sharp(BoundsInner { min: 0, max: 10 });
+
+        assert_eq!(sharp.replace(Bounds::Empty, Bounds::Empty), sharp);
+        assert_eq!(
+            sharp.replace(Bounds::sharp(3, 6).unwrap(), Bounds::sharp(1, 9).unwrap()),
+            sharp
+        );
+        assert_eq!(
+            sharp.replace(Bounds::bounded(3, 6).unwrap(), Bounds::Empty),
+            sharp
+        );
+        assert_eq!(
+            sharp.replace(Bounds::sharp(3, 6).unwrap(), Bounds::sharp(-5, 5).unwrap()),
+            Bounds::Sharp(BoundsInner { min: -5, max: 10 })
+        );
+        assert_eq!(
+            sharp.replace(Bounds::sharp(3, 6).unwrap(), Bounds::bounded(1, 9).unwrap()),
+            sharp
+        );
+        assert_eq!(
+            sharp.replace(
+                Bounds::sharp(3, 6).unwrap(),
+                Bounds::bounded(1, 11).unwrap()
+            ),
+            Bounds::Bounded(BoundsInner { min: 0, max: 11 })
+        );
+    }
+
+    #[test]
+    fn replacing_rows_with_rows_that_reach_past_the_bounds_keeps_them_sharp() {
+        let sharp = Bounds::Sharp(BoundsInner { min: 0, max: 10 });
+        let bounded = Bounds::Bounded(BoundsInner { min: 0, max: 10 });
+
+        assert_eq!(
+            sharp.replace(
+                Bounds::sharp(0, 10).unwrap(),
+                Bounds::sharp(-1, 11).unwrap()
+            ),
+            Bounds::Sharp(BoundsInner { min: -1, max: 11 })
+        );
+        assert_eq!(
+            bounded.replace(Bounds::sharp(0, 10).unwrap(), Bounds::sharp(0, 10).unwrap()),
+            sharp
+        );
+        assert_eq!(
+            sharp.replace(Bounds::sharp(0, 5).unwrap(), Bounds::sharp(-3, 2).unwrap()),
+            Bounds::Sharp(BoundsInner { min: -3, max: 10 })
+        );
+        assert_eq!(
+            Bounds::Empty.replace(Bounds::Empty, Bounds::sharp(1, 2).unwrap()),
+            Bounds::Sharp(BoundsInner { min: 1, max: 2 })
+        );
+    }
+
+    #[test]
+    fn replacing_rows_that_may_attain_the_bounds_is_bounded() {
+        let sharp = Bounds::Sharp(BoundsInner { min: 0, max: 10 });
+        let bounded = Bounds::Bounded(BoundsInner { min: 0, max: 10 });
+
+        assert_eq!(
+            sharp.replace(Bounds::sharp(0, 5).unwrap(), Bounds::Empty),
+            bounded
+        );
+        assert_eq!(
+            sharp.replace(
+                Bounds::bounded(5, 10).unwrap(),
+                Bounds::sharp(3, 4).unwrap()
+            ),
+            bounded
+        );
+        assert_eq!(
+            sharp.replace(
+                Bounds::sharp(0, 5).unwrap(),
+                Bounds::bounded(-3, 2).unwrap()
+            ),
+            Bounds::Bounded(BoundsInner { min: -3, max: 10 })
+        );
+        assert_eq!(
+            bounded.replace(Bounds::Empty, Bounds::sharp(3, 4).unwrap()),
+            bounded
+        );
+        assert_eq!(
+            bounded.replace(Bounds::sharp(3, 4).unwrap(), Bounds::sharp(-1, 4).unwrap()),
+            Bounds::Bounded(BoundsInner { min: -1, max: 10 })
+        );
+    }
+
+    #[test]
+    fn we_can_check_if_bounds_overlap() {
+        let bounds = Bounds::<i32>::sharp(-5, 10).unwrap();
+        assert!(bounds.overlaps(&Bounds::sharp(10, 20).unwrap()));
+        assert!(bounds.overlaps(&Bounds::bounded(-10, -5).unwrap()));
+        assert!(bounds.overlaps(&Bounds::sharp(0, 1).unwrap()));
+        assert!(Bounds::bounded(0, 1).unwrap().overlaps(&bounds));
+        assert!(!bounds.overlaps(&Bounds::sharp(11, 20).unwrap()));
+        assert!(!bounds.overlaps(&Bounds::bounded(-10, -6).unwrap()));
+        assert!(!bounds.overlaps(&Bounds::Empty));
+        assert!(!Bounds::Empty.overlaps(&bounds));
+    }
+
     #[test]
     fn we_can_construct_column_bounds_from_column() {
         let varchar_column = OwnedColumn::<TestScalar>::VarChar(
@@ -707,6 +951,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_check_if_column_bounds_overlap() {
+        let bounds = ColumnBounds::BigInt(Bounds::sharp(1, 5).unwrap());
+        assert!(bounds
+            .try_overlaps(&ColumnBounds::BigInt(Bounds::sharp(5, 9).unwrap()))
+            .unwrap());
+        assert!(!bounds
+            .try_overlaps(&ColumnBounds::BigInt(Bounds::sharp(6, 9).unwrap()))
+            .unwrap());
+        assert!(!bounds
+            .try_overlaps(&ColumnBounds::BigInt(Bounds::Empty))
+            .unwrap());
+        assert!(ColumnBounds::NoOrder
+            .try_overlaps(&ColumnBounds::NoOrder)
+            .unwrap());
+        assert!(bounds
+            .try_overlaps(&ColumnBounds::Int(Bounds::sharp(1, 5).unwrap()))
+            .is_err());
+        assert!(bounds.try_overlaps(&ColumnBounds::NoOrder).is_err());
+    }
+
     #[test]
     fn we_cannot_difference_mismatched_column_bounds() {
         let no_order = ColumnBounds::NoOrder;
@@ -731,4 +996,41 @@ mod tests {
         assert!(smallint.try_difference(timestamp).is_err());
         assert!(timestamp.try_difference(smallint).is_err());
     }
+
+    #[test]
+    fn we_can_replace_column_bounds_with_matching_variant() {
+        let no_order = ColumnBounds::NoOrder;
+        assert_eq!(no_order.try_replace(no_order, no_order).unwrap(), no_order);
+
+        let bigint = ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 1, max: 9 }));
+        let bigint_removed = ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 2, max: 3 }));
+        let bigint_added = ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 4, max: 12 }));
+        assert_eq!(
+            bigint.try_replace(bigint_removed, bigint_added).unwrap(),
+            ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 1, max: 12 }))
+        );
+
+        let timestamp = ColumnBounds::TimestampTZ(Bounds::Sharp(BoundsInner { min: 1, max: 9 }));
+        let timestamp_removed =
+            ColumnBounds::TimestampTZ(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+        let timestamp_added = ColumnBounds::TimestampTZ(Bounds::Empty);
+        assert_eq!(
+            timestamp
+                .try_replace(timestamp_removed, timestamp_added)
+                .unwrap(),
+            ColumnBounds::TimestampTZ(Bounds::Bounded(BoundsInner { min: 1, max: 9 }))
+        );
+    }
+
+    #[test]
+    fn we_cannot_replace_mismatched_column_bounds() {
+        let no_order = ColumnBounds::NoOrder;
+        let int = ColumnBounds::Int(Bounds::Sharp(BoundsInner { min: -10, max: 10 }));
+        let bigint = ColumnBounds::BigInt(Bounds::Sharp(BoundsInner { min: 1, max: 3 }));
+
+        assert!(int.try_replace(int, bigint).is_err());
+        assert!(int.try_replace(bigint, int).is_err());
+        assert!(bigint.try_replace(int, int).is_err());
+        assert!(no_order.try_replace(no_order, int).is_err());
+    }
 }
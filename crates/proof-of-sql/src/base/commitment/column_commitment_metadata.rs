@@ -176,6 +176,36 @@ impl ColumnCommitmentMetadata {
             column_type: self.column_type,
         })
     }
+
+    /// Combine [`ColumnCommitmentMetadata`] as if some rows of self's source collection, with
+    /// metadata `removed`, are being replaced by rows with metadata `added`.
+    ///
+    /// Can error if the metadatas are mismatched.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn try_replace(
+        self,
+        removed: ColumnCommitmentMetadata,
+        added: ColumnCommitmentMetadata,
+    ) -> Result<ColumnCommitmentMetadata, ColumnCommitmentMetadataMismatch> {
+        for other in [removed, added] {
+            if self.column_type != other.column_type {
+                return Err(ColumnCommitmentMetadataMismatch {
+                    datatype_a: self.column_type,
+                    datatype_b: other.column_type,
+                });
+            }
+        }
+
+        let bounds = self
+            .bounds
+            .try_replace(removed.bounds, added.bounds)
+            .expect(EXPECT_BOUNDS_MATCH_MESSAGE);
+
+        Ok(ColumnCommitmentMetadata {
+            bounds,
+            column_type: self.column_type,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -790,6 +820,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_replace_bigint_matching_metadata() {
+        let ints = [1, 2, 3, 1, 0];
+        let bigint_metadata =
+            ColumnCommitmentMetadata::from_column(&CommittableColumn::BigInt(&ints));
+
+        // The replaced rows 1 and 2 do not attain the bounds of the column
+        let removed_metadata =
+            ColumnCommitmentMetadata::from_column(&CommittableColumn::BigInt(&ints[..2]));
+        let added_metadata =
+            ColumnCommitmentMetadata::from_column(&CommittableColumn::BigInt(&[-1, 2]));
+        let replaced = bigint_metadata
+            .try_replace(removed_metadata, added_metadata)
+            .unwrap();
+        assert_eq!(replaced.column_type, ColumnType::BigInt);
+        assert_eq!(
+            replaced.bounds,
+            ColumnBounds::BigInt(Bounds::sharp(-1, 3).unwrap())
+        );
+
+        // The replaced rows 3 and 1 attain the maximum of the column
+        let removed_metadata =
+            ColumnCommitmentMetadata::from_column(&CommittableColumn::BigInt(&ints[2..4]));
+        let replaced = bigint_metadata
+            .try_replace(removed_metadata, added_metadata)
+            .unwrap();
+        assert_eq!(
+            replaced.bounds,
+            ColumnBounds::BigInt(Bounds::bounded(-1, 3).unwrap())
+        );
+    }
+
+    #[test]
+    fn we_cannot_replace_mismatched_metadata() {
+        let bigint_metadata = ColumnCommitmentMetadata {
+            column_type: ColumnType::BigInt,
+            bounds: ColumnBounds::BigInt(Bounds::Empty),
+        };
+        let timestamp_metadata = ColumnCommitmentMetadata {
+            column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+        };
+
+        assert!(bigint_metadata
+            .try_replace(bigint_metadata, timestamp_metadata)
+            .is_err());
+        assert!(bigint_metadata
+            .try_replace(timestamp_metadata, bigint_metadata)
+            .is_err());
+        assert!(timestamp_metadata
+            .try_replace(bigint_metadata, bigint_metadata)
+            .is_err());
+    }
+
     #[allow(clippy::too_many_lines)]
     #[test]
     fn we_cannot_perform_arithmetic_on_mismatched_metadata() {
@@ -32,6 +32,20 @@ pub enum ColumnCommitmentsMismatch {
         /// The second column ident
         id_b: String,
     },
+    /// Nullable columns cannot operate with columns that are not nullable.
+    #[snafu(display("column with ident {id} is nullable in only one of the commitments"))]
+    Nullability {
+        /// The column ident
+        id: String,
+    },
+    /// Columns can only operate with each other if the same character components of them are committed to.
+    #[snafu(display(
+        "column with ident {id} has different character components in each of the commitments"
+    ))]
+    CharacterComponents {
+        /// The column ident
+        id: String,
+    },
 }
 
 /// Extension trait intended for [`ColumnCommitmentMetadataMap`].
@@ -56,6 +70,12 @@ pub trait ColumnCommitmentMetadataMapExt {
     fn try_difference(self, other: Self) -> Result<Self, ColumnCommitmentsMismatch>
     where
         Self: Sized;
+
+    /// Combine metadata maps as if rows of the source table commitment, with metadata `removed`,
+    /// are being replaced by rows with metadata `added`.
+    fn try_replace(self, removed: Self, added: Self) -> Result<Self, ColumnCommitmentsMismatch>
+    where
+        Self: Sized;
 }
 
 impl ColumnCommitmentMetadataMapExt for ColumnCommitmentMetadataMap {
@@ -133,6 +153,40 @@ impl ColumnCommitmentMetadataMapExt for ColumnCommitmentMetadataMap {
             })
             .collect()
     }
+
+    fn try_replace(self, removed: Self, added: Self) -> Result<Self, ColumnCommitmentsMismatch>
+    where
+        Self: Sized,
+    {
+        if self.len() != removed.len() || self.len() != added.len() {
+            return Err(ColumnCommitmentsMismatch::NumColumns);
+        }
+
+        self.into_iter()
+            .zip(removed)
+            .zip(added)
+            .map(
+                |(
+                    ((identifier, metadata), (identifier_removed, metadata_removed)),
+                    (identifier_added, metadata_added),
+                )| {
+                    for other_identifier in [identifier_removed, identifier_added] {
+                        if identifier != other_identifier {
+                            Err(ColumnCommitmentsMismatch::Ident {
+                                id_a: identifier.to_string(),
+                                id_b: other_identifier.to_string(),
+                            })?;
+                        }
+                    }
+
+                    Ok((
+                        identifier,
+                        metadata.try_replace(metadata_removed, metadata_added)?,
+                    ))
+                },
+            )
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -290,6 +344,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_replace_rows_of_matching_metadata_maps() {
+        let table = owned_table([
+            bigint("bigint_column", [1, 5, -5, 0, 10]),
+            varchar("varchar_column", ["Lorem", "ipsum", "dolor", "sit", "amet"]),
+        ]);
+        let metadata = metadata_map_from_owned_table(&table);
+        let removed_table = owned_table([
+            bigint("bigint_column", [5, -5]),
+            varchar("varchar_column", ["ipsum", "dolor"]),
+        ]);
+        let removed_metadata = metadata_map_from_owned_table(&removed_table);
+        let added_table = owned_table([
+            bigint("bigint_column", [-10, 3]),
+            varchar("varchar_column", ["consectetur", "adipiscing"]),
+        ]);
+        let added_metadata = metadata_map_from_owned_table(&added_table);
+
+        let replaced = metadata
+            .clone()
+            .try_replace(removed_metadata.clone(), added_metadata.clone())
+            .unwrap();
+        assert_eq!(replaced.len(), 2);
+
+        // The minimum is replaced by a smaller value, and the maximum is kept
+        let (index_0, metadata_0) = replaced.get_index(0).unwrap();
+        assert_eq!(index_0.value.as_str(), "bigint_column");
+        assert_eq!(
+            metadata_0.bounds(),
+            &ColumnBounds::BigInt(Bounds::sharp(-10, 10).unwrap())
+        );
+        assert_eq!(replaced.get_index(1), metadata.get_index(1));
+
+        assert!(matches!(
+            metadata.clone().try_replace(
+                removed_metadata.clone(),
+                metadata_map_from_owned_table(&owned_table([bigint("bigint_column", [1])]))
+            ),
+            Err(ColumnCommitmentsMismatch::NumColumns)
+        ));
+        let renamed_metadata = metadata_map_from_owned_table(&owned_table([
+            bigint("bigint_column", [-10, 3]),
+            varchar("other_column", ["consectetur", "adipiscing"]),
+        ]));
+        assert!(matches!(
+            metadata
+                .clone()
+                .try_replace(removed_metadata.clone(), renamed_metadata.clone()),
+            Err(ColumnCommitmentsMismatch::Ident { .. })
+        ));
+        assert!(matches!(
+            metadata.try_replace(renamed_metadata, added_metadata),
+            Err(ColumnCommitmentsMismatch::Ident { .. })
+        ));
+    }
+
     #[test]
     fn we_cannot_perform_arithmetic_on_metadata_maps_with_different_column_counts() {
         let table_a = owned_table([
@@ -3,15 +3,19 @@ use super::{
     ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, Commitment, VecCommitmentExt,
 };
 use crate::base::{
-    database::{ColumnField, ColumnRef, CommitmentAccessor, TableRef},
-    map::IndexSet,
+    database::{ColumnComponent, ColumnField, CommitmentAccessor, TableRef},
+    map::{IndexMap, IndexSet},
 };
 use alloc::{
     string::{String, ToString},
     vec,
     vec::Vec,
 };
-use core::{iter, slice};
+use core::{
+    iter,
+    ops::{AddAssign, SubAssign},
+    slice,
+};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use sqlparser::ast::Ident;
@@ -43,10 +47,16 @@ pub enum AppendColumnCommitmentsError {
 /// Commitments for a collection of columns with some metadata.
 ///
 /// These columns do not need to belong to the same table, and can have differing lengths.
+/// The commitments are to the values of the columns,
+/// and the presence of every nullable column is committed to separately.
+/// The character components of `VarChar` columns can be committed to as well,
+/// which only an accessor can provide, see [`Self::from_accessor_with_max_bounds`].
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ColumnCommitments<C> {
     commitments: Vec<C>,
     column_metadata: ColumnCommitmentMetadataMap,
+    presence_commitments: IndexMap<Ident, C>,
+    character_commitments: IndexMap<Ident, Vec<(ColumnComponent, C)>>,
 }
 
 impl<C: Commitment> ColumnCommitments<C> {
@@ -56,17 +66,42 @@ impl<C: Commitment> ColumnCommitments<C> {
         columns: &[ColumnField],
         accessor: &impl CommitmentAccessor<C>,
     ) -> Self {
+        let fields_of = |component: fn(ColumnComponent) -> bool| {
+            columns
+                .iter()
+                .filter(move |c| component(c.component()))
+                .cloned()
+        };
+        let value_fields: Vec<_> = fields_of(|c| c == ColumnComponent::Values).collect();
+        let presence_fields: Vec<_> = fields_of(|c| c == ColumnComponent::Presence).collect();
         let column_metadata =
-            ColumnCommitmentMetadataMap::from_column_fields_with_max_bounds(columns);
-        let commitments = columns
+            ColumnCommitmentMetadataMap::from_column_fields_with_max_bounds(&value_fields);
+        let commitments = value_fields
+            .iter()
+            .map(|c| accessor.get_commitment(c.column_ref(table.clone())))
+            .collect();
+        let presence_commitments = presence_fields
             .iter()
             .map(|c| {
-                accessor.get_commitment(ColumnRef::new(table.clone(), c.name(), c.data_type()))
+                (
+                    c.name(),
+                    accessor.get_commitment(c.column_ref(table.clone())),
+                )
             })
             .collect();
+        let mut character_commitments: IndexMap<Ident, Vec<_>> = IndexMap::default();
+        for c in fields_of(ColumnComponent::is_character) {
+            let commitment = accessor.get_commitment(c.column_ref(table.clone()));
+            character_commitments
+                .entry(c.name())
+                .or_default()
+                .push((c.component(), commitment));
+        }
         ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
+            character_commitments,
         }
     }
 
@@ -107,6 +142,51 @@ impl<C: Commitment> ColumnCommitments<C> {
             .map(|index| self.commitments[index].clone())
     }
 
+    /// Returns the commitment to the presence of the nullable column with the given ident.
+    #[must_use]
+    pub fn get_presence_commitment(&self, identifier: &Ident) -> Option<C> {
+        self.presence_commitments.get(identifier).cloned()
+    }
+
+    /// Returns true if the column with the given ident is nullable.
+    #[must_use]
+    pub fn is_nullable(&self, identifier: &Ident) -> bool {
+        self.presence_commitments.contains_key(identifier)
+    }
+
+    /// Returns the commitment to the character component `component` of the column with the given ident.
+    #[must_use]
+    pub fn get_character_commitment(
+        &self,
+        identifier: &Ident,
+        component: ColumnComponent,
+    ) -> Option<C> {
+        self.character_commitments
+            .get(identifier)?
+            .iter()
+            .find(|(c, _)| *c == component)
+            .map(|(_, commitment)| commitment.clone())
+    }
+
+    /// Returns the number of first and last characters of the column with the given ident
+    /// whose components are all committed to along with its length.
+    #[must_use]
+    pub fn character_count(&self, identifier: &Ident) -> usize {
+        let has_component = |component| {
+            self.get_character_commitment(identifier, component)
+                .is_some()
+        };
+        if !has_component(ColumnComponent::Length) {
+            return 0;
+        }
+        (0..)
+            .take_while(|&index| {
+                has_component(ColumnComponent::Character(index))
+                    && has_component(ColumnComponent::ReversedCharacter(index))
+            })
+            .count()
+    }
+
     /// Returns the metadata for the commitment with the given ident.
     #[must_use]
     pub fn get_metadata(&self, identifier: &Ident) -> Option<&ColumnCommitmentMetadata> {
@@ -151,6 +231,9 @@ impl<C: Commitment> ColumnCommitments<C> {
                 })
                 .unzip();
 
+        let presence_commitments =
+            presence_commitments_with_offset(&identifiers, &committable_columns, offset, setup);
+
         let column_metadata = ColumnCommitmentMetadataMap::from_columns(
             identifiers.into_iter().zip(committable_columns.iter()),
         );
@@ -160,6 +243,8 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
+            character_commitments: IndexMap::default(),
         })
     }
 
@@ -204,11 +289,24 @@ impl<C: Commitment> ColumnCommitments<C> {
                 })
                 .unzip();
 
+        let presence_commitments =
+            presence_commitments_with_offset(&identifiers, &committable_columns, offset, setup);
+
         let column_metadata = ColumnCommitmentMetadataMap::from_columns(
             identifiers.into_iter().zip(committable_columns.iter()),
         );
 
-        self.column_metadata = self.column_metadata.clone().try_union(column_metadata)?;
+        let column_metadata = self.column_metadata.clone().try_union(column_metadata)?;
+        self.presence_commitments = try_combine_presence_commitments(
+            self.presence_commitments.clone(),
+            presence_commitments,
+            AddAssign::add_assign,
+        )?;
+        // The character components of the new rows can not be committed to from the columns
+        if let Some(id) = self.character_commitments.keys().next() {
+            return Err(ColumnCommitmentsMismatch::CharacterComponents { id: id.to_string() })?;
+        }
+        self.column_metadata = column_metadata;
 
         self.commitments
             .try_append_rows_with_offset(committable_columns, offset, setup)
@@ -254,6 +352,10 @@ impl<C: Commitment> ColumnCommitments<C> {
         self.commitments.extend(new_column_commitments.commitments);
         self.column_metadata
             .extend(new_column_commitments.column_metadata);
+        self.presence_commitments
+            .extend(new_column_commitments.presence_commitments);
+        self.character_commitments
+            .extend(new_column_commitments.character_commitments);
 
         Ok(())
     }
@@ -268,6 +370,16 @@ impl<C: Commitment> ColumnCommitments<C> {
         Self: Sized,
     {
         let column_metadata = self.column_metadata.try_union(other.column_metadata)?;
+        let presence_commitments = try_combine_presence_commitments(
+            self.presence_commitments,
+            other.presence_commitments,
+            AddAssign::add_assign,
+        )?;
+        let character_commitments = try_combine_character_commitments(
+            self.character_commitments,
+            other.character_commitments,
+            AddAssign::add_assign,
+        )?;
         let commitments = self
             .commitments
             .try_add(other.commitments)
@@ -276,6 +388,8 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
+            character_commitments,
         })
     }
 
@@ -289,6 +403,16 @@ impl<C: Commitment> ColumnCommitments<C> {
         Self: Sized,
     {
         let column_metadata = self.column_metadata.try_difference(other.column_metadata)?;
+        let presence_commitments = try_combine_presence_commitments(
+            self.presence_commitments,
+            other.presence_commitments,
+            SubAssign::sub_assign,
+        )?;
+        let character_commitments = try_combine_character_commitments(
+            self.character_commitments,
+            other.character_commitments,
+            SubAssign::sub_assign,
+        )?;
         let commitments = self
             .commitments
             .try_sub(other.commitments)
@@ -297,10 +421,160 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
+            character_commitments,
+        })
+    }
+
+    /// Replace rows of these [`ColumnCommitments`], whose commitments are `removed`, with rows
+    /// whose commitments are `added`.
+    ///
+    /// The removed and added rows must have been committed to with the same offset.
+    ///
+    /// Will error on a variety of mismatches.
+    /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn try_replace(self, removed: Self, added: Self) -> Result<Self, ColumnCommitmentsMismatch>
+    where
+        Self: Sized,
+    {
+        let column_metadata = self
+            .column_metadata
+            .try_replace(removed.column_metadata, added.column_metadata)?;
+        let presence_commitments = try_combine_presence_commitments(
+            self.presence_commitments,
+            removed.presence_commitments,
+            SubAssign::sub_assign,
+        )
+        .and_then(|presence_commitments| {
+            try_combine_presence_commitments(
+                presence_commitments,
+                added.presence_commitments,
+                AddAssign::add_assign,
+            )
+        })?;
+        let character_commitments = try_combine_character_commitments(
+            self.character_commitments,
+            removed.character_commitments,
+            SubAssign::sub_assign,
+        )
+        .and_then(|character_commitments| {
+            try_combine_character_commitments(
+                character_commitments,
+                added.character_commitments,
+                AddAssign::add_assign,
+            )
+        })?;
+        let commitments = self
+            .commitments
+            .try_sub(removed.commitments)
+            .and_then(|commitments| commitments.try_add(added.commitments))
+            .expect("we've already checked that self, removed and added have equal column counts");
+
+        Ok(ColumnCommitments {
+            commitments,
+            column_metadata,
+            presence_commitments,
+            character_commitments,
         })
     }
 }
 
+/// Commits to the presence of the nullable columns among `committable_columns`,
+/// keyed by their idents.
+fn presence_commitments_with_offset<C: Commitment>(
+    identifiers: &[&Ident],
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+) -> IndexMap<Ident, C> {
+    let (identifiers, presences): (Vec<Ident>, Vec<CommittableColumn>) = identifiers
+        .iter()
+        .zip(committable_columns)
+        .filter_map(|(identifier, column)| {
+            Some((
+                (*identifier).clone(),
+                CommittableColumn::Boolean(column.presence()?),
+            ))
+        })
+        .unzip();
+    identifiers
+        .into_iter()
+        .zip(Vec::<C>::from_commitable_columns_with_offset(
+            &presences, offset, setup,
+        ))
+        .collect()
+}
+
+/// Combines the presence commitments of two [`ColumnCommitments`] of the same columns.
+///
+/// Errors if a column is nullable in only one of them.
+fn try_combine_presence_commitments<C: Commitment>(
+    presence_commitments: IndexMap<Ident, C>,
+    mut other: IndexMap<Ident, C>,
+    combine: impl Fn(&mut C, C),
+) -> Result<IndexMap<Ident, C>, ColumnCommitmentsMismatch> {
+    if let Some(id) = presence_commitments
+        .keys()
+        .find(|id| !other.contains_key(*id))
+        .or_else(|| {
+            other
+                .keys()
+                .find(|id| !presence_commitments.contains_key(*id))
+        })
+    {
+        return Err(ColumnCommitmentsMismatch::Nullability { id: id.to_string() });
+    }
+    Ok(presence_commitments
+        .into_iter()
+        .map(|(id, mut commitment)| {
+            combine(&mut commitment, other.swap_remove(&id).unwrap_or_default());
+            (id, commitment)
+        })
+        .collect())
+}
+
+/// Combines the character commitments of two [`ColumnCommitments`] of the same columns.
+///
+/// Errors if they do not commit to the same character components of each column.
+fn try_combine_character_commitments<C: Commitment>(
+    character_commitments: IndexMap<Ident, Vec<(ColumnComponent, C)>>,
+    mut other: IndexMap<Ident, Vec<(ColumnComponent, C)>>,
+    combine: impl Fn(&mut C, C),
+) -> Result<IndexMap<Ident, Vec<(ColumnComponent, C)>>, ColumnCommitmentsMismatch> {
+    let mismatch =
+        |id: &Ident| ColumnCommitmentsMismatch::CharacterComponents { id: id.to_string() };
+    if let Some(id) = other
+        .keys()
+        .find(|id| !character_commitments.contains_key(*id))
+    {
+        return Err(mismatch(id));
+    }
+    character_commitments
+        .into_iter()
+        .map(|(id, commitments)| {
+            let other_commitments = other.swap_remove(&id).unwrap_or_default();
+            if commitments.len() != other_commitments.len()
+                || commitments
+                    .iter()
+                    .zip(&other_commitments)
+                    .any(|((component, _), (other_component, _))| component != other_component)
+            {
+                return Err(mismatch(&id));
+            }
+            let commitments = commitments
+                .into_iter()
+                .zip(other_commitments)
+                .map(|((component, mut commitment), (_, other_commitment))| {
+                    combine(&mut commitment, other_commitment);
+                    (component, commitment)
+                })
+                .collect();
+            Ok((id, commitments))
+        })
+        .collect()
+}
+
 /// Owning iterator for [`ColumnCommitments`].
 pub type IntoIter<C> = iter::Map<
     iter::Zip<<ColumnCommitmentMetadataMap as IntoIterator>::IntoIter, vec::IntoIter<C>>,
@@ -347,6 +621,8 @@ impl<C> FromIterator<(Ident, ColumnCommitmentMetadata, C)> for ColumnCommitments
         ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments: IndexMap::default(),
+            character_commitments: IndexMap::default(),
         }
     }
 }
@@ -642,6 +918,73 @@ mod tests {
         assert_eq!(column_commitments, expected_column_commitments);
     }
 
+    #[test]
+    fn we_can_append_rows_to_nullable_column_commitments() {
+        let bigint_id: Ident = "bigint_column".into();
+        let bigint_data = [1i64, 5, 0, 0, 10];
+        let bigint_presence = [true, true, false, false, true];
+
+        let mut column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                owned_table::<TestScalar>([nullable(
+                    bigint(bigint_id.value.as_str(), bigint_data[..2].to_vec()),
+                    bigint_presence[..2].to_vec(),
+                )])
+                .inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+        assert!(column_commitments.is_nullable(&bigint_id));
+
+        column_commitments
+            .try_append_rows_with_offset(
+                owned_table::<TestScalar>([nullable(
+                    bigint(bigint_id.value.as_str(), bigint_data[2..].to_vec()),
+                    bigint_presence[2..].to_vec(),
+                )])
+                .inner_table(),
+                2,
+                &(),
+            )
+            .unwrap();
+
+        let expected_column_commitments = ColumnCommitments::try_from_columns_with_offset(
+            owned_table::<TestScalar>([nullable(
+                bigint(bigint_id.value.as_str(), bigint_data),
+                bigint_presence,
+            )])
+            .inner_table(),
+            0,
+            &(),
+        )
+        .unwrap();
+        assert_eq!(column_commitments, expected_column_commitments);
+        assert_eq!(
+            column_commitments.get_presence_commitment(&bigint_id),
+            Some(
+                NaiveCommitment::compute_commitments(
+                    &[CommittableColumn::Boolean(&bigint_presence)],
+                    0,
+                    &()
+                )[0]
+                .clone()
+            )
+        );
+
+        // a column cannot be nullable in only some of the rows
+        assert!(matches!(
+            column_commitments.try_append_rows_with_offset(
+                owned_table::<TestScalar>([bigint(bigint_id.value.as_str(), [1])]).inner_table(),
+                5,
+                &()
+            ),
+            Err(AppendColumnCommitmentsError::Mismatch {
+                source: ColumnCommitmentsMismatch::Nullability { .. }
+            })
+        ));
+    }
+
     #[test]
     fn we_cannot_append_rows_to_mismatched_column_commitments() {
         let base_table: OwnedTable<TestScalar> = owned_table([
@@ -954,4 +1297,116 @@ mod tests {
             Err(ColumnCommitmentsMismatch::NumColumns)
         ));
     }
+
+    #[test]
+    fn we_can_replace_rows_of_column_commitments() {
+        let bigint_id: Ident = "bigint_column".into();
+        let varchar_id: Ident = "varchar_column".into();
+
+        let columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [1, 5, -5, 0, 10]),
+            varchar(
+                varchar_id.value.as_str(),
+                ["Lorem", "ipsum", "dolor", "sit", "amet"],
+            ),
+        ]);
+        let column_commitments =
+            ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+                columns.inner_table(),
+                0,
+                &(),
+            )
+            .unwrap();
+
+        let removed_columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [-5, 0]),
+            varchar(varchar_id.value.as_str(), ["dolor", "sit"]),
+        ]);
+        let removed_commitments =
+            ColumnCommitments::try_from_columns_with_offset(removed_columns.inner_table(), 2, &())
+                .unwrap();
+        let added_columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [2, 3]),
+            varchar(varchar_id.value.as_str(), ["consectetur", "adipiscing"]),
+        ]);
+        let added_commitments =
+            ColumnCommitments::try_from_columns_with_offset(added_columns.inner_table(), 2, &())
+                .unwrap();
+
+        let actual_replaced = column_commitments
+            .try_replace(removed_commitments, added_commitments)
+            .unwrap();
+
+        let expected_columns: OwnedTable<TestScalar> = owned_table([
+            bigint(bigint_id.value.as_str(), [1, 5, 2, 3, 10]),
+            varchar(
+                varchar_id.value.as_str(),
+                ["Lorem", "ipsum", "consectetur", "adipiscing", "amet"],
+            ),
+        ]);
+        let expected_replaced =
+            ColumnCommitments::try_from_columns_with_offset(expected_columns.inner_table(), 0, &())
+                .unwrap();
+
+        assert_eq!(
+            actual_replaced.commitments(),
+            expected_replaced.commitments()
+        );
+
+        // The removed rows attain the minimum of the bigint column
+        let bigint_metadata = actual_replaced.get_metadata(&bigint_id).unwrap();
+        assert_eq!(bigint_metadata.column_type(), &ColumnType::BigInt);
+        assert_eq!(
+            bigint_metadata.bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-5, 10).unwrap())
+        );
+
+        let varchar_metadata = actual_replaced.get_metadata(&varchar_id).unwrap();
+        assert_eq!(varchar_metadata.column_type(), &ColumnType::VarChar);
+        assert_eq!(varchar_metadata.bounds(), &ColumnBounds::NoOrder);
+    }
+
+    #[test]
+    fn we_cannot_replace_rows_of_mismatched_column_commitments() {
+        let table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2, 3, 4]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+        ]);
+        let commitments = ColumnCommitments::<NaiveCommitment>::try_from_columns_with_offset(
+            table.inner_table(),
+            0,
+            &(),
+        )
+        .unwrap();
+        let removed_table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 2]),
+            varchar("column_b", ["Lorem", "ipsum"]),
+        ]);
+        let removed_commitments =
+            ColumnCommitments::try_from_columns_with_offset(removed_table.inner_table(), 0, &())
+                .unwrap();
+
+        let table_diff_type: OwnedTable<TestScalar> = owned_table([
+            varchar("column_a", ["1", "2"]),
+            varchar("column_b", ["Lorem", "ipsum"]),
+        ]);
+        let commitments_diff_type =
+            ColumnCommitments::try_from_columns_with_offset(table_diff_type.inner_table(), 0, &())
+                .unwrap();
+        assert!(matches!(
+            commitments
+                .clone()
+                .try_replace(removed_commitments.clone(), commitments_diff_type),
+            Err(ColumnCommitmentsMismatch::ColumnCommitmentMetadata { .. })
+        ));
+
+        let table_diff_len: OwnedTable<TestScalar> = owned_table([bigint("column_a", [1, 2])]);
+        let commitments_diff_len =
+            ColumnCommitments::try_from_columns_with_offset(table_diff_len.inner_table(), 0, &())
+                .unwrap();
+        assert!(matches!(
+            commitments.try_replace(commitments_diff_len, removed_commitments),
+            Err(ColumnCommitmentsMismatch::NumColumns)
+        ));
+    }
 }
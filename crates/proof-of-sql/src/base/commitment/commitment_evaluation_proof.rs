@@ -2,6 +2,7 @@ use super::Commitment;
 use crate::base::{proof::Transcript, scalar::Scalar};
 #[cfg(feature = "blitzar")]
 use crate::base::{scalar::MontScalar, slice_ops};
+use alloc::vec::Vec;
 #[cfg(feature = "blitzar")]
 use blitzar::proof::{InnerProductProof, ProofError};
 #[cfg(feature = "blitzar")]
@@ -76,6 +77,65 @@ pub trait CommitmentEvaluationProof {
         table_length: usize,
         setup: &Self::VerifierPublicSetup<'_>,
     ) -> Result<(), Self::Error>;
+    /// Verify many batch proofs, returning the outcome of each of them.
+    ///
+    /// Schemes whose final check can be folded together with random linear combinations override
+    /// this, so that the expensive part of the check is only done once when all proofs are valid.
+    /// By default, each proof is verified individually.
+    ///
+    /// The Dory schemes and `HyperKZGCommitmentEvaluationProof` fold their pairing checks,
+    /// while `InnerProductProof` and `NaiveEvaluationProof` verify each proof individually.
+    ///
+    /// Note: each transcript ends in the same state as if its proof was verified individually.
+    fn verify_batched_proofs<T: Transcript + Clone>(
+        verifications: Vec<BatchedProofVerification<'_, Self, T>>,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Vec<Result<(), Self::Error>>
+    where
+        Self: Sized,
+    {
+        verifications
+            .into_iter()
+            .map(|verification| verification.verify(setup))
+            .collect()
+    }
+}
+
+/// The arguments of a single [`CommitmentEvaluationProof::verify_batched_proof`] call,
+/// so that many of them can be verified at once with [`CommitmentEvaluationProof::verify_batched_proofs`].
+pub struct BatchedProofVerification<'a, CP: CommitmentEvaluationProof, T> {
+    /// The proof to verify.
+    pub proof: &'a CP,
+    /// The transcript of the proof.
+    pub transcript: &'a mut T,
+    /// The commitments that are folded together.
+    pub commit_batch: &'a [CP::Commitment],
+    /// The factors that the commitments and evaluations are folded with.
+    pub batching_factors: &'a [CP::Scalar],
+    /// The evaluations of the committed vectors.
+    pub evaluations: &'a [CP::Scalar],
+    /// The point the committed vectors are evaluated at.
+    pub b_point: &'a [CP::Scalar],
+    /// The offset of the generators used by the commitments.
+    pub generators_offset: u64,
+    /// The length of the committed vectors.
+    pub table_length: usize,
+}
+
+impl<CP: CommitmentEvaluationProof, T: Transcript> BatchedProofVerification<'_, CP, T> {
+    /// Verify the proof individually.
+    pub fn verify(self, setup: &CP::VerifierPublicSetup<'_>) -> Result<(), CP::Error> {
+        self.proof.verify_batched_proof(
+            self.transcript,
+            self.commit_batch,
+            self.batching_factors,
+            self.evaluations,
+            self.b_point,
+            self.generators_offset,
+            self.table_length,
+            setup,
+        )
+    }
 }
 
 #[cfg(feature = "blitzar")]
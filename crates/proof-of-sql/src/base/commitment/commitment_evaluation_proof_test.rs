@@ -1,4 +1,4 @@
-use super::CommitmentEvaluationProof;
+use super::{BatchedProofVerification, CommitmentEvaluationProof};
 use crate::base::{commitment::vec_commitment_ext::VecCommitmentExt, database::Column};
 use ark_std::UniformRand;
 #[cfg(feature = "blitzar")]
@@ -137,6 +137,101 @@ pub fn test_random_commitment_evaluation_proof<CP: CommitmentEvaluationProof>(
     assert!(r.is_err(), "verification improperly succeeded");
 }
 
+pub fn test_batched_commitment_evaluation_proofs<CP: CommitmentEvaluationProof>(
+    prover_setup: &CP::ProverPublicSetup<'_>,
+    verifier_setup: &CP::VerifierPublicSetup<'_>,
+) {
+    let mut rng = ark_std::test_rng();
+    let cases: Vec<_> = [5, 16, 2]
+        .into_iter()
+        .map(|table_length: usize| {
+            let nu = table_length.next_power_of_two().trailing_zeros() as usize;
+            let a = core::iter::repeat_with(|| CP::Scalar::rand(&mut rng))
+                .take(table_length)
+                .collect::<Vec<_>>();
+            let b_point = core::iter::repeat_with(|| CP::Scalar::rand(&mut rng))
+                .take(nu)
+                .collect::<Vec<_>>();
+            let mut transcript = Transcript::new(b"evaluation_proof");
+            let proof = CP::new(&mut transcript, &a, &b_point, 0, prover_setup);
+            let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, prover_setup);
+            let mut b = vec![CP::Scalar::zero(); a.len()];
+            crate::base::polynomial::compute_evaluation_vector(&mut b, &b_point);
+            let product: CP::Scalar = a.iter().zip(b.iter()).map(|(a, b)| *a * *b).sum();
+            (proof, commits, product, b_point, table_length)
+        })
+        .collect();
+    let verify_cases = |products: &[CP::Scalar]| {
+        let mut transcripts = vec![Transcript::new(b"evaluation_proof"); cases.len()];
+        let results = CP::verify_batched_proofs(
+            cases
+                .iter()
+                .zip(products)
+                .zip(&mut transcripts)
+                .map(
+                    |(((proof, commits, _, b_point, table_length), product), transcript)| {
+                        BatchedProofVerification {
+                            proof,
+                            transcript,
+                            commit_batch: commits,
+                            batching_factors: &[CP::Scalar::one()],
+                            evaluations: core::slice::from_ref(product),
+                            b_point,
+                            generators_offset: 0,
+                            table_length: *table_length,
+                        }
+                    },
+                )
+                .collect(),
+            verifier_setup,
+        );
+        (results, transcripts)
+    };
+
+    let products: Vec<_> = cases.iter().map(|(_, _, product, _, _)| *product).collect();
+    let (results, mut transcripts) = verify_cases(&products);
+    assert!(results.iter().all(Result::is_ok));
+    // The transcripts end as if the proofs were verified individually
+    for ((proof, commits, product, b_point, table_length), batched_transcript) in
+        cases.iter().zip(&mut transcripts)
+    {
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        assert!(proof
+            .verify_proof(
+                &mut transcript,
+                &commits[0],
+                product,
+                b_point,
+                0,
+                *table_length,
+                verifier_setup,
+            )
+            .is_ok());
+        let mut challenge = [0; 32];
+        let mut batched_challenge = [0; 32];
+        transcript.challenge_bytes(b"test", &mut challenge);
+        batched_transcript.challenge_bytes(b"test", &mut batched_challenge);
+        assert_eq!(challenge, batched_challenge);
+    }
+
+    // Only the invalid proof is rejected
+    let mut invalid_products = products.clone();
+    invalid_products[1] += CP::Scalar::one();
+    let (results, _) = verify_cases(&invalid_products);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+
+    let (results, _) = verify_cases(&[]);
+    assert!(results.is_empty());
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn test_batched_ipa() {
+    test_batched_commitment_evaluation_proofs::<InnerProductProof>(&(), &());
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn test_simple_ipa() {
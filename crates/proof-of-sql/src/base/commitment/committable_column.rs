@@ -4,7 +4,7 @@ use crate::base::{
     ref_into::RefInto,
     scalar::{Scalar, ScalarExt},
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 #[cfg(feature = "blitzar")]
 use blitzar::sequence::Sequence;
 use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
@@ -47,9 +47,13 @@ pub enum CommittableColumn<'a> {
     VarBinary(Vec<[u64; 4]>),
     /// Borrowed Timestamp column with Timezone, mapped to `i64`.
     TimestampTZ(PoSQLTimeUnit, PoSQLTimeZone, &'a [i64]),
+    /// Nullable column, committed to as its values.
+    ///
+    /// Its borrowed presence, mapped to `bool`, is committed to as a column of its own.
+    Nullable(Box<CommittableColumn<'a>>, &'a [bool]),
 }
 
-impl CommittableColumn<'_> {
+impl<'a> CommittableColumn<'a> {
     /// Returns the length of the column.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -65,6 +69,25 @@ impl CommittableColumn<'_> {
             | CommittableColumn::VarChar(col)
             | CommittableColumn::VarBinary(col) => col.len(),
             CommittableColumn::Boolean(col) => col.len(),
+            CommittableColumn::Nullable(_, presence) => presence.len(),
+        }
+    }
+
+    /// Returns the values of the column, which is the column itself if it is not nullable.
+    #[must_use]
+    pub fn values(&self) -> &Self {
+        match self {
+            CommittableColumn::Nullable(values, _) => values.values(),
+            _ => self,
+        }
+    }
+
+    /// Returns the presence of the column if it is nullable.
+    #[must_use]
+    pub fn presence(&self) -> Option<&'a [bool]> {
+        match self {
+            CommittableColumn::Nullable(_, presence) => Some(presence),
+            _ => None,
         }
     }
 
@@ -98,6 +121,7 @@ impl<'a> From<&CommittableColumn<'a>> for ColumnType {
             CommittableColumn::VarBinary(_) => ColumnType::VarBinary,
             CommittableColumn::Boolean(_) => ColumnType::Boolean,
             CommittableColumn::TimestampTZ(tu, tz, _) => ColumnType::TimestampTZ(*tu, *tz),
+            CommittableColumn::Nullable(values, _) => values.as_ref().into(),
         }
     }
 }
@@ -126,6 +150,9 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for CommittableColumn<'a> {
                 CommittableColumn::VarBinary(as_limbs)
             }
             Column::TimestampTZ(tu, tz, times) => CommittableColumn::TimestampTZ(*tu, *tz, times),
+            Column::Nullable(values, presence) => {
+                CommittableColumn::Nullable(Box::new((*values).into()), presence)
+            }
         }
     }
 }
@@ -173,6 +200,9 @@ impl<'a, S: Scalar> From<&'a OwnedColumn<S>> for CommittableColumn<'a> {
             OwnedColumn::TimestampTZ(tu, tz, times) => {
                 CommittableColumn::TimestampTZ(*tu, *tz, times as &[_])
             }
+            OwnedColumn::Nullable(values, presence) => {
+                CommittableColumn::Nullable(Box::new(values.as_ref().into()), presence)
+            }
         }
     }
 }
@@ -236,6 +266,7 @@ impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
             | CommittableColumn::VarBinary(limbs) => Sequence::from(limbs),
             CommittableColumn::Boolean(bools) => Sequence::from(*bools),
             CommittableColumn::TimestampTZ(_, _, times) => Sequence::from(*times),
+            CommittableColumn::Nullable(values, _) => Sequence::from(values.as_ref()),
         }
     }
 }
@@ -705,6 +736,29 @@ mod tests {
         assert_eq!(from_owned_column, CommittableColumn::BigInt(&[12, 34, 56]));
     }
 
+    #[test]
+    fn we_can_convert_from_owned_nullable_column() {
+        let owned_column = OwnedColumn::<TestScalar>::Nullable(
+            Box::new(OwnedColumn::BigInt(vec![12, 0, 56])),
+            vec![true, false, true],
+        );
+        let from_owned_column = CommittableColumn::from(&owned_column);
+        assert_eq!(
+            from_owned_column,
+            CommittableColumn::Nullable(
+                Box::new(CommittableColumn::BigInt(&[12, 0, 56])),
+                &[true, false, true]
+            )
+        );
+        assert_eq!(from_owned_column.len(), 3);
+        assert_eq!(from_owned_column.column_type(), ColumnType::BigInt);
+        assert_eq!(
+            from_owned_column.values(),
+            &CommittableColumn::BigInt(&[12, 0, 56])
+        );
+        assert_eq!(from_owned_column.presence(), Some(&[true, false, true][..]));
+    }
+
     #[test]
     fn we_can_convert_from_owned_tinyint_column() {
         // empty case
@@ -17,7 +17,7 @@ pub use vec_commitment_ext::{NumColumnsMismatch, VecCommitmentExt};
 
 mod column_bounds;
 use super::scalar::Curve25519Scalar;
-pub use column_bounds::{Bounds, ColumnBounds, NegativeBounds};
+pub use column_bounds::{Bounds, ColumnBounds, ColumnBoundsMismatch, NegativeBounds};
 
 mod column_commitment_metadata;
 pub use column_commitment_metadata::ColumnCommitmentMetadata;
@@ -32,8 +32,14 @@ pub use column_commitments::{AppendColumnCommitmentsError, ColumnCommitments, Du
 
 mod table_commitment;
 pub use table_commitment::{
-    AppendTableCommitmentError, MixedLengthColumns, NegativeRange, TableCommitment,
-    TableCommitmentArithmeticError, TableCommitmentFromColumnsError,
+    AppendTableCommitmentError, MixedLengthColumns, NegativeRange, ReplaceTableCommitmentRowsError,
+    RowReplacement, TableCommitment, TableCommitmentArithmeticError,
+    TableCommitmentFromColumnsError,
+};
+
+mod partitioned_table_commitment;
+pub use partitioned_table_commitment::{
+    PartitionedTableCommitment, PartitionedTableCommitmentError,
 };
 
 mod query_commitments;
@@ -137,7 +143,7 @@ impl Commitment for RistrettoPoint {
 }
 
 mod commitment_evaluation_proof;
-pub use commitment_evaluation_proof::CommitmentEvaluationProof;
+pub use commitment_evaluation_proof::{BatchedProofVerification, CommitmentEvaluationProof};
 #[cfg(test)]
 pub(crate) mod commitment_evaluation_proof_test;
 
@@ -154,6 +154,14 @@ impl Commitment for NaiveCommitment {
                     CommittableColumn::TimestampTZ(_, _, i64_vec) => {
                         i64_vec.iter().map(core::convert::Into::into).collect()
                     }
+                    CommittableColumn::Nullable(values, _) => {
+                        return NaiveCommitment::compute_commitments(
+                            &[values.as_ref().clone()],
+                            offset,
+                            &(),
+                        )
+                        .remove(0);
+                    }
                 };
                 vectors.append(&mut existing_scalars);
                 NaiveCommitment(vectors)
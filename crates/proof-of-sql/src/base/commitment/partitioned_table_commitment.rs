@@ -0,0 +1,381 @@
+use super::{
+    ColumnBounds, ColumnBoundsMismatch, Commitment, TableCommitment, TableCommitmentArithmeticError,
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use sqlparser::ast::Ident;
+
+/// Errors that can occur when creating or combining the partitions of a
+/// [`PartitionedTableCommitment`].
+#[derive(Debug, Snafu)]
+pub enum PartitionedTableCommitmentError {
+    /// Partitions must be contiguous, with each partition beginning where the previous one ends.
+    #[snafu(display("partitions of a table commitment must be contiguous"))]
+    NonContiguousPartitions,
+    /// Cannot combine an empty range of partitions.
+    #[snafu(display("cannot combine an empty range of partitions"))]
+    NoPartitions,
+    /// The range of partitions extends past the last partition.
+    #[snafu(display("partitions {partitions:?} are out of bounds"))]
+    PartitionsOutOfBounds {
+        /// The range of partitions
+        partitions: Range<usize>,
+    },
+    /// The column is not part of the table commitment.
+    #[snafu(display("column {column_id} is not part of the table commitment"))]
+    ColumnNotFound {
+        /// The identifier of the column
+        column_id: Ident,
+    },
+    /// Encountered error when adding partitions together.
+    #[snafu(transparent)]
+    TableCommitmentArithmetic {
+        /// The underlying source error
+        source: TableCommitmentArithmeticError,
+    },
+    /// The bounds have a different variant than those of the column.
+    #[snafu(transparent)]
+    ColumnBoundsMismatch {
+        /// The underlying source error
+        source: ColumnBoundsMismatch,
+    },
+}
+
+/// Commitment for an entire table that is split into partitions of contiguous rows.
+///
+/// Each partition is a [`TableCommitment`] of its own. A query that only targets some rows of the
+/// table can be verified against the partitions that contain those rows, combined with
+/// [`TableCommitment::try_add`], so that the cost of verification scales with the number of rows
+/// queried rather than with the size of the table.
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionedTableCommitment<C>
+where
+    C: Commitment,
+{
+    partitions: Vec<TableCommitment<C>>,
+}
+
+impl<C: Commitment> PartitionedTableCommitment<C> {
+    /// Construct a new [`PartitionedTableCommitment`] from its partitions, in order.
+    ///
+    /// Will error if the partitions are not contiguous.
+    pub fn try_new(
+        partitions: Vec<TableCommitment<C>>,
+    ) -> Result<Self, PartitionedTableCommitmentError> {
+        if partitions
+            .windows(2)
+            .any(|pair| pair[0].range().end != pair[1].range().start)
+        {
+            Err(PartitionedTableCommitmentError::NonContiguousPartitions)?;
+        }
+        Ok(Self { partitions })
+    }
+
+    /// Returns the partitions of the table commitment, in order.
+    #[must_use]
+    pub fn partitions(&self) -> &[TableCommitment<C>] {
+        &self.partitions
+    }
+
+    /// Returns the range of rows that the partitions commit to.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        match (self.partitions.first(), self.partitions.last()) {
+            (Some(first), Some(last)) => first.range().start..last.range().end,
+            _ => 0..0,
+        }
+    }
+
+    /// Append a partition to the end of the table commitment.
+    ///
+    /// The partition must begin where the last partition ends.
+    /// Mismatches between the columns of the partitions are only detected when they are combined.
+    pub fn try_append_partition(
+        &mut self,
+        partition: TableCommitment<C>,
+    ) -> Result<(), PartitionedTableCommitmentError> {
+        if let Some(last) = self.partitions.last() {
+            if last.range().end != partition.range().start {
+                Err(PartitionedTableCommitmentError::NonContiguousPartitions)?;
+            }
+        }
+        self.partitions.push(partition);
+        Ok(())
+    }
+
+    /// Returns the range of partitions that contain any of the given rows.
+    #[must_use]
+    pub fn partitions_overlapping_rows(&self, rows: &Range<usize>) -> Range<usize> {
+        let start = self
+            .partitions
+            .partition_point(|partition| partition.range().end <= rows.start);
+        if rows.is_empty() {
+            return start..start;
+        }
+        let end = self
+            .partitions
+            .partition_point(|partition| partition.range().start < rows.end);
+        start..end.max(start)
+    }
+
+    /// Returns the smallest range of partitions that contains every partition whose values of the
+    /// column may lie within `bounds`.
+    ///
+    /// For example, this finds the partitions that a `WHERE block_number BETWEEN x AND y` clause
+    /// can select rows from. The range is empty if no partition can contain such values.
+    ///
+    /// Will error if the column is missing from a partition, or if its bounds have a different
+    /// variant than `bounds`.
+    pub fn try_partitions_overlapping_bounds(
+        &self,
+        column_id: &Ident,
+        bounds: &ColumnBounds,
+    ) -> Result<Range<usize>, PartitionedTableCommitmentError> {
+        let mut overlapping = None;
+        for (index, partition) in self.partitions.iter().enumerate() {
+            let metadata = partition
+                .column_commitments()
+                .get_metadata(column_id)
+                .ok_or_else(|| PartitionedTableCommitmentError::ColumnNotFound {
+                    column_id: column_id.clone(),
+                })?;
+            if metadata.bounds().try_overlaps(bounds)? {
+                let start = overlapping.map_or(index, |range: Range<usize>| range.start);
+                overlapping = Some(start..index + 1);
+            }
+        }
+        Ok(overlapping.unwrap_or(0..0))
+    }
+
+    /// Combine a range of partitions into a single [`TableCommitment`] using
+    /// [`TableCommitment::try_add`].
+    ///
+    /// Will error if the range is empty or out of bounds, or if the partitions have mismatched
+    /// columns.
+    pub fn try_combine_partitions(
+        &self,
+        partitions: Range<usize>,
+    ) -> Result<TableCommitment<C>, PartitionedTableCommitmentError> {
+        if partitions.end > self.partitions.len() {
+            Err(PartitionedTableCommitmentError::PartitionsOutOfBounds {
+                partitions: partitions.clone(),
+            })?;
+        }
+        let (first, rest) = self
+            .partitions
+            .get(partitions)
+            .and_then(<[_]>::split_first)
+            .ok_or(PartitionedTableCommitmentError::NoPartitions)?;
+        Ok(rest.iter().try_fold(first.clone(), |combined, partition| {
+            combined.try_add(partition.clone())
+        })?)
+    }
+
+    /// Returns the [`TableCommitment`] to the partitions that contain any of the given rows.
+    ///
+    /// Note: unless the rows begin and end at partition boundaries, the range of the resulting
+    /// commitment is larger than `rows`. Queries should be proven against the rows in this range.
+    pub fn try_commitment_to_rows(
+        &self,
+        rows: &Range<usize>,
+    ) -> Result<TableCommitment<C>, PartitionedTableCommitmentError> {
+        self.try_combine_partitions(self.partitions_overlapping_rows(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::{naive_commitment::NaiveCommitment, Bounds},
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::test_scalar::TestScalar,
+    };
+    use alloc::vec;
+
+    fn table() -> OwnedTable<TestScalar> {
+        owned_table([
+            bigint("block_number", [1, 1, 2, 3, 3, 3, 4, 5, 6, 6]),
+            varchar("name", ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]),
+        ])
+    }
+
+    fn commitment_to_rows(rows: Range<usize>) -> TableCommitment<NaiveCommitment> {
+        let rows_of_table = OwnedTable::try_from_iter(
+            table()
+                .inner_table()
+                .iter()
+                .map(|(id, column)| (id.clone(), column.slice(rows.start - 2, rows.end - 2))),
+        )
+        .unwrap();
+        TableCommitment::from_owned_table_with_offset(&rows_of_table, rows.start, &())
+    }
+
+    /// The table at an offset of 2, in partitions of 3 rows
+    fn partitioned_commitment() -> PartitionedTableCommitment<NaiveCommitment> {
+        PartitionedTableCommitment::try_new(vec![
+            commitment_to_rows(2..5),
+            commitment_to_rows(5..8),
+            commitment_to_rows(8..11),
+            commitment_to_rows(11..12),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn we_can_construct_and_append_to_partitioned_table_commitments() {
+        let mut partitioned = PartitionedTableCommitment::default();
+        assert_eq!(partitioned.range(), 0..0);
+        partitioned
+            .try_append_partition(commitment_to_rows(2..5))
+            .unwrap();
+        partitioned
+            .try_append_partition(commitment_to_rows(5..8))
+            .unwrap();
+        partitioned
+            .try_append_partition(commitment_to_rows(8..11))
+            .unwrap();
+        partitioned
+            .try_append_partition(commitment_to_rows(11..12))
+            .unwrap();
+        assert_eq!(partitioned, partitioned_commitment());
+        assert_eq!(partitioned.range(), 2..12);
+        assert_eq!(partitioned.partitions().len(), 4);
+    }
+
+    #[test]
+    fn we_cannot_construct_partitioned_table_commitments_from_noncontiguous_partitions() {
+        assert!(matches!(
+            PartitionedTableCommitment::try_new(vec![
+                commitment_to_rows(2..5),
+                commitment_to_rows(6..8),
+            ]),
+            Err(PartitionedTableCommitmentError::NonContiguousPartitions)
+        ));
+
+        let mut partitioned = partitioned_commitment();
+        assert!(matches!(
+            partitioned.try_append_partition(commitment_to_rows(8..11)),
+            Err(PartitionedTableCommitmentError::NonContiguousPartitions)
+        ));
+        assert_eq!(partitioned, partitioned_commitment());
+    }
+
+    #[test]
+    fn we_can_find_the_partitions_overlapping_rows() {
+        let partitioned = partitioned_commitment();
+        assert_eq!(partitioned.partitions_overlapping_rows(&(2..12)), 0..4);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(5..8)), 1..2);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(4..6)), 0..2);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(7..9)), 1..3);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(11..20)), 3..4);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(0..2)), 0..0);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(12..20)), 4..4);
+        assert_eq!(partitioned.partitions_overlapping_rows(&(6..6)), 1..1);
+    }
+
+    #[test]
+    fn we_can_find_the_partitions_overlapping_bounds() {
+        let partitioned = partitioned_commitment();
+        let block_number: Ident = "block_number".into();
+        let between = |min, max| ColumnBounds::BigInt(Bounds::sharp(min, max).unwrap());
+        assert_eq!(
+            partitioned
+                .try_partitions_overlapping_bounds(&block_number, &between(3, 3))
+                .unwrap(),
+            1..2
+        );
+        assert_eq!(
+            partitioned
+                .try_partitions_overlapping_bounds(&block_number, &between(2, 4))
+                .unwrap(),
+            0..3
+        );
+        assert_eq!(
+            partitioned
+                .try_partitions_overlapping_bounds(&block_number, &between(6, 100))
+                .unwrap(),
+            2..4
+        );
+        assert_eq!(
+            partitioned
+                .try_partitions_overlapping_bounds(&block_number, &between(7, 100))
+                .unwrap(),
+            0..0
+        );
+        assert!(matches!(
+            partitioned.try_partitions_overlapping_bounds(&"missing".into(), &between(3, 3)),
+            Err(PartitionedTableCommitmentError::ColumnNotFound { .. })
+        ));
+        assert!(matches!(
+            partitioned.try_partitions_overlapping_bounds(
+                &block_number,
+                &ColumnBounds::Int(Bounds::sharp(3, 3).unwrap())
+            ),
+            Err(PartitionedTableCommitmentError::ColumnBoundsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_combine_partitions() {
+        let partitioned = partitioned_commitment();
+        assert_eq!(
+            partitioned.try_combine_partitions(1..3).unwrap(),
+            commitment_to_rows(5..11)
+        );
+        assert_eq!(
+            partitioned.try_combine_partitions(0..4).unwrap(),
+            commitment_to_rows(2..12)
+        );
+        assert_eq!(
+            partitioned.try_combine_partitions(3..4).unwrap(),
+            commitment_to_rows(11..12)
+        );
+        assert_eq!(
+            partitioned.try_commitment_to_rows(&(6..9)).unwrap(),
+            commitment_to_rows(5..11)
+        );
+    }
+
+    #[test]
+    fn we_cannot_combine_empty_or_out_of_bounds_partitions() {
+        let partitioned = partitioned_commitment();
+        assert!(matches!(
+            partitioned.try_combine_partitions(2..2),
+            Err(PartitionedTableCommitmentError::NoPartitions)
+        ));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..2;
+        assert!(matches!(
+            partitioned.try_combine_partitions(reversed),
+            Err(PartitionedTableCommitmentError::NoPartitions)
+        ));
+        assert!(matches!(
+            partitioned.try_combine_partitions(3..5),
+            Err(PartitionedTableCommitmentError::PartitionsOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            partitioned.try_commitment_to_rows(&(20..30)),
+            Err(PartitionedTableCommitmentError::NoPartitions)
+        ));
+    }
+
+    #[test]
+    fn we_cannot_combine_mismatched_partitions() {
+        let mismatched = TableCommitment::try_from_columns_with_offset(
+            owned_table::<TestScalar>([bigint("block_number", [7, 8])]).inner_table(),
+            12,
+            &(),
+        )
+        .unwrap();
+        let mut partitioned = partitioned_commitment();
+        partitioned.try_append_partition(mismatched).unwrap();
+        assert!(partitioned.try_combine_partitions(0..4).is_ok());
+        assert!(matches!(
+            partitioned.try_combine_partitions(3..5),
+            Err(PartitionedTableCommitmentError::TableCommitmentArithmetic { .. })
+        ));
+    }
+}
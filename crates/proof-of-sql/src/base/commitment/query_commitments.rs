@@ -1,8 +1,8 @@
 use super::{Commitment, TableCommitment};
 use crate::base::{
     database::{
-        ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor,
-        TableRef,
+        ColumnComponent, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor,
+        SchemaAccessor, TableRef,
     },
     map::IndexMap,
 };
@@ -39,10 +39,12 @@ impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
             .fold(
                 IndexMap::<_, Vec<_>>::default(),
                 |mut table_columns, column| {
+                    let column_field =
+                        ColumnField::new(column.column_id(), *column.values().column_type());
                     table_columns
                         .entry(column.table_ref())
                         .or_default()
-                        .push(ColumnField::new(column.column_id(), *column.column_type()));
+                        .push(column_field.with_component(column.component()));
                     table_columns
                 },
             )
@@ -51,11 +53,15 @@ impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
                 let selected_column_fields = accessor
                     .lookup_schema(table_ref.clone())
                     .into_iter()
-                    .filter_map(|(ident, _)| {
-                        column_fields
+                    .flat_map(|(ident, _)| {
+                        let mut fields = column_fields
                             .iter()
-                            .find(|column_field| column_field.name() == ident)
+                            .filter(|column_field| column_field.name() == ident)
                             .cloned()
+                            .collect::<Vec<_>>();
+                        fields.sort_by_key(ColumnField::component);
+                        fields.dedup();
+                        fields
                     })
                     .collect::<Vec<_>>();
                 let table_commitment = TableCommitment::from_accessor_with_max_bounds(
@@ -86,12 +92,18 @@ impl<C: Commitment> MetadataAccessor for QueryCommitments<C> {
 /// Panics if the commitment for the table or column cannot be found.
 impl<C: Commitment> CommitmentAccessor<C> for QueryCommitments<C> {
     fn get_commitment(&self, column: ColumnRef) -> C {
-        let table_commitment = self.get(&column.table_ref()).unwrap();
-
-        table_commitment
-            .column_commitments()
-            .get_commitment(&column.column_id())
-            .unwrap()
+        let column_commitments = self.get(&column.table_ref()).unwrap().column_commitments();
+
+        match column.component() {
+            ColumnComponent::Values => column_commitments.get_commitment(&column.column_id()),
+            ColumnComponent::Presence => {
+                column_commitments.get_presence_commitment(&column.column_id())
+            }
+            component => {
+                column_commitments.get_character_commitment(&column.column_id(), component)
+            }
+        }
+        .unwrap()
     }
 }
 
@@ -127,6 +139,22 @@ impl<C: Commitment> SchemaAccessor for QueryCommitments<C> {
             })
             .collect()
     }
+
+    fn is_nullable(&self, table_ref: TableRef, column_id: Ident) -> bool {
+        self.get(&table_ref).is_some_and(|table_commitment| {
+            table_commitment
+                .column_commitments()
+                .is_nullable(&column_id)
+        })
+    }
+
+    fn character_count(&self, table_ref: TableRef, column_id: Ident) -> usize {
+        self.get(&table_ref).map_or(0, |table_commitment| {
+            table_commitment
+                .column_commitments()
+                .character_count(&column_id)
+        })
+    }
 }
 
 #[cfg(all(test, feature = "blitzar"))]
@@ -398,4 +426,53 @@ mod tests {
         );
         assert_eq!(query_commitments, expected_query_commitments);
     }
+
+    #[test]
+    fn we_can_get_the_character_components_of_a_column_from_query_commitments() {
+        let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+        let prover_setup = ProverSetup::from(&public_parameters);
+        let setup = DoryProverPublicSetup::new(&prover_setup, 3);
+
+        let table_ref = TableRef::new("table", "a");
+        let column_id: Ident = "column_a".into();
+        let column = ColumnRef::new(table_ref.clone(), column_id.clone(), ColumnType::VarChar);
+        let mut accessor =
+            OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+        accessor.add_table(
+            table_ref.clone(),
+            owned_table([varchar(column_id.value.as_str(), ["ab", "", "xyz"])]),
+            0,
+        );
+
+        // The second reversed character is missing, so only the first characters are complete
+        let components = [
+            ColumnComponent::Length,
+            ColumnComponent::Character(0),
+            ColumnComponent::ReversedCharacter(0),
+            ColumnComponent::Character(1),
+        ];
+        let query_commitments = QueryCommitments::<DoryCommitment>::from_accessor_with_max_bounds(
+            core::iter::once(column.clone())
+                .chain(components.map(|component| column.with_component(component))),
+            &accessor,
+        );
+        assert_eq!(
+            query_commitments.character_count(table_ref.clone(), column_id.clone()),
+            1
+        );
+        assert_eq!(
+            query_commitments.lookup_schema(table_ref.clone()),
+            vec![(column_id.clone(), ColumnType::VarChar)]
+        );
+        for component in components {
+            assert_eq!(
+                query_commitments.get_commitment(column.with_component(component)),
+                accessor.get_commitment(column.with_component(component))
+            );
+        }
+        assert_eq!(
+            query_commitments.character_count(table_ref, "column_b".into()),
+            0
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use super::{
-    committable_column::CommittableColumn, AppendColumnCommitmentsError, ColumnCommitments,
-    ColumnCommitmentsMismatch, Commitment, DuplicateIdents,
+    committable_column::CommittableColumn, AppendColumnCommitmentsError,
+    ColumnCommitmentMetadataMapExt, ColumnCommitments, ColumnCommitmentsMismatch, Commitment,
+    DuplicateIdents,
 };
 use crate::base::{
     database::{ColumnField, CommitmentAccessor, OwnedTable, TableRef},
@@ -78,6 +79,128 @@ pub enum TableCommitmentArithmeticError {
     NonContiguous,
 }
 
+/// Errors that can occur when replacing rows of a [`TableCommitment`].
+#[derive(Debug, Snafu)]
+pub enum ReplaceTableCommitmentRowsError {
+    /// Cannot commit to the replaced or replacing rows.
+    #[snafu(transparent)]
+    TableCommitmentFromColumns {
+        /// The underlying source error
+        source: TableCommitmentFromColumnsError,
+    },
+    /// Cannot replace rows with rows of mismatched metadata.
+    #[snafu(transparent)]
+    ColumnMismatch {
+        /// The underlying source error
+        source: ColumnCommitmentsMismatch,
+    },
+    /// Cannot replace rows with rows that start at a different row number.
+    #[snafu(display(
+        "cannot replace rows starting at {removed_start} with rows starting at {added_start}"
+    ))]
+    MisalignedRows {
+        /// The first replaced row
+        removed_start: usize,
+        /// The first replacing row
+        added_start: usize,
+    },
+    /// Cannot replace rows that are not committed to.
+    #[snafu(display(
+        "cannot replace rows {rows:?} that are not within the committed rows {range:?}"
+    ))]
+    RowsOutOfRange {
+        /// The replaced rows
+        rows: Range<usize>,
+        /// The committed rows
+        range: Range<usize>,
+    },
+    /// Cannot change the number of rows unless the last rows of the table are replaced.
+    #[snafu(display(
+        "cannot change the number of rows unless the last rows of the table are replaced"
+    ))]
+    NumRowsChanged,
+}
+
+/// The change to a [`TableCommitment`] from replacing a range of its rows with new rows.
+///
+/// This consists of the commitments to the replaced rows and to the replacing rows, both starting
+/// at the first replaced row. Since commitments are additive, the change can be applied to a
+/// [`TableCommitment`] with [`TableCommitment::try_replace_rows`] without committing to its other
+/// rows again.
+///
+/// The number of replacing rows only needs to match the number of replaced rows if rows after them
+/// are committed to. So, the last rows of a table can be deleted, e.g. during a reorg, by replacing
+/// them with no rows, or replaced with any number of rows.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowReplacement<C>
+where
+    C: Commitment,
+{
+    removed: TableCommitment<C>,
+    added: TableCommitment<C>,
+}
+
+impl<C: Commitment> RowReplacement<C> {
+    /// Construct a new [`RowReplacement`] from commitments to the replaced and replacing rows.
+    ///
+    /// Will error if the replaced and replacing rows do not start at the same row number,
+    /// or on a variety of mismatches between their columns.
+    pub fn try_new(
+        removed: TableCommitment<C>,
+        added: TableCommitment<C>,
+    ) -> Result<Self, ReplaceTableCommitmentRowsError> {
+        if removed.range.start != added.range.start {
+            return Err(ReplaceTableCommitmentRowsError::MisalignedRows {
+                removed_start: removed.range.start,
+                added_start: added.range.start,
+            });
+        }
+
+        // Checking the columns now guarantees that applying the replacement can only fail on
+        // mismatches with the commitment it is applied to.
+        removed
+            .column_commitments
+            .column_metadata()
+            .clone()
+            .try_union(added.column_commitments.column_metadata().clone())?;
+
+        Ok(RowReplacement { removed, added })
+    }
+
+    /// Returns a [`RowReplacement`] of the rows starting at row number `offset`, which have the
+    /// values of `removed_columns`, with the values of `added_columns`.
+    ///
+    /// Provided columns must have the same idents and types, and there must be no duplicate
+    /// idents. Columns of the same collection must have the same length.
+    pub fn try_from_columns_with_offset<'a, COL, NEWCOL>(
+        removed_columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        added_columns: impl IntoIterator<Item = (&'a Ident, NEWCOL)>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, ReplaceTableCommitmentRowsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+        NEWCOL: Into<CommittableColumn<'a>>,
+    {
+        Self::try_new(
+            TableCommitment::try_from_columns_with_offset(removed_columns, offset, setup)?,
+            TableCommitment::try_from_columns_with_offset(added_columns, offset, setup)?,
+        )
+    }
+
+    /// Returns the commitment to the replaced rows.
+    #[must_use]
+    pub fn removed(&self) -> &TableCommitment<C> {
+        &self.removed
+    }
+
+    /// Returns the commitment to the replacing rows.
+    #[must_use]
+    pub fn added(&self) -> &TableCommitment<C> {
+        &self.added
+    }
+}
+
 /// Commitment for an entire table, with column and table metadata.
 ///
 /// Unlike [`ColumnCommitments`], all columns in this commitment must have the same length.
@@ -354,6 +477,40 @@ impl<C: Commitment> TableCommitment<C> {
             range,
         })
     }
+
+    /// Replace rows of the committed table, correcting its commitments and column bounds.
+    ///
+    /// The replaced rows must be committed to by this [`TableCommitment`], and the number of rows
+    /// can only change if they are the last rows of the table.
+    /// Unlike [`TableCommitment::try_sub`], column bounds stay sharp whenever it can be determined
+    /// that the rows attaining them are kept or that the replacing rows reach past them.
+    ///
+    /// This will also error on a variety of mismatches.
+    /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
+    pub fn try_replace_rows(
+        &mut self,
+        replacement: RowReplacement<C>,
+    ) -> Result<(), ReplaceTableCommitmentRowsError> {
+        let RowReplacement { removed, added } = replacement;
+
+        if removed.range.start < self.range.start || self.range.end < removed.range.end {
+            return Err(ReplaceTableCommitmentRowsError::RowsOutOfRange {
+                rows: removed.range,
+                range: self.range.clone(),
+            });
+        }
+        if removed.range.end != added.range.end && removed.range.end != self.range.end {
+            return Err(ReplaceTableCommitmentRowsError::NumRowsChanged);
+        }
+
+        self.column_commitments = self
+            .column_commitments
+            .clone()
+            .try_replace(removed.column_commitments, added.column_commitments)?;
+        self.range.end = self.range.end - removed.range.len() + added.range.len();
+
+        Ok(())
+    }
 }
 
 /// Return the number of rows for the provided columns, erroring if they have mixed length.
@@ -378,7 +535,7 @@ fn num_rows_of_columns<'a>(
 mod tests {
     use super::*;
     use crate::base::{
-        commitment::naive_commitment::NaiveCommitment,
+        commitment::{naive_commitment::NaiveCommitment, Bounds, ColumnBounds},
         database::{owned_table_utility::*, Column, OwnedColumn},
         map::IndexMap,
         scalar::test_scalar::TestScalar,
@@ -1151,6 +1308,242 @@ mod tests {
         ));
     }
 
+    fn replacement_test_table(
+        bigint_data: &[i64],
+        varchar_data: &[&str],
+    ) -> OwnedTable<TestScalar> {
+        owned_table([
+            bigint("bigint_column", bigint_data.to_vec()),
+            varchar("varchar_column", varchar_data.to_vec()),
+        ])
+    }
+
+    #[test]
+    fn we_can_replace_rows_of_table_commitment() {
+        let table = replacement_test_table(
+            &[1, 5, -5, 0, 10],
+            &["Lorem", "ipsum", "dolor", "sit", "amet"],
+        );
+        let mut table_commitment =
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(&table, 2, &());
+
+        // replace a row that attains neither bound of the bigint column
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            replacement_test_table(&[5], &["ipsum"]).inner_table(),
+            replacement_test_table(&[7], &["consectetur"]).inner_table(),
+            3,
+            &(),
+        )
+        .unwrap();
+        table_commitment.try_replace_rows(replacement).unwrap();
+        let expected_table = replacement_test_table(
+            &[1, 7, -5, 0, 10],
+            &["Lorem", "consectetur", "dolor", "sit", "amet"],
+        );
+        assert_eq!(
+            table_commitment,
+            TableCommitment::from_owned_table_with_offset(&expected_table, 2, &())
+        );
+
+        // replace rows including the one attaining the minimum
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            replacement_test_table(&[-5, 0], &["dolor", "sit"]).inner_table(),
+            replacement_test_table(&[2, 3], &["adipiscing", "elit"]).inner_table(),
+            4,
+            &(),
+        )
+        .unwrap();
+        table_commitment.try_replace_rows(replacement).unwrap();
+        let expected_table = replacement_test_table(
+            &[1, 7, 2, 3, 10],
+            &["Lorem", "consectetur", "adipiscing", "elit", "amet"],
+        );
+        let expected_commitment = TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+            &expected_table,
+            2,
+            &(),
+        );
+        assert_eq!(table_commitment.range(), &(2..7));
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            expected_commitment.column_commitments().commitments()
+        );
+        assert_eq!(
+            table_commitment
+                .column_commitments()
+                .get_metadata(&"bigint_column".into())
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-5, 10).unwrap())
+        );
+    }
+
+    #[test]
+    fn we_can_delete_and_replace_the_last_rows_of_table_commitment() {
+        let table = replacement_test_table(
+            &[1, 5, -5, 0, 10],
+            &["Lorem", "ipsum", "dolor", "sit", "amet"],
+        );
+        let mut table_commitment =
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(&table, 2, &());
+
+        // delete the last two rows
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            replacement_test_table(&[0, 10], &["sit", "amet"]).inner_table(),
+            replacement_test_table(&[], &[]).inner_table(),
+            5,
+            &(),
+        )
+        .unwrap();
+        table_commitment.try_replace_rows(replacement).unwrap();
+        let expected_table = replacement_test_table(&[1, 5, -5], &["Lorem", "ipsum", "dolor"]);
+        let expected_commitment = TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+            &expected_table,
+            2,
+            &(),
+        );
+        assert_eq!(table_commitment.range(), &(2..5));
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            expected_commitment.column_commitments().commitments()
+        );
+        assert_eq!(
+            table_commitment
+                .column_commitments()
+                .get_metadata(&"bigint_column".into())
+                .unwrap()
+                .bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-5, 10).unwrap())
+        );
+
+        // replace the last row with more rows that reach past the bounds
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            replacement_test_table(&[-5], &["dolor"]).inner_table(),
+            replacement_test_table(&[-6, 8, 11], &["sit", "amet", "elit"]).inner_table(),
+            4,
+            &(),
+        )
+        .unwrap();
+        table_commitment.try_replace_rows(replacement).unwrap();
+        let expected_table = replacement_test_table(
+            &[1, 5, -6, 8, 11],
+            &["Lorem", "ipsum", "sit", "amet", "elit"],
+        );
+        assert_eq!(
+            table_commitment,
+            TableCommitment::from_owned_table_with_offset(&expected_table, 2, &())
+        );
+    }
+
+    #[test]
+    fn we_cannot_replace_rows_of_table_commitment_with_mismatched_rows() {
+        let table = replacement_test_table(
+            &[1, 5, -5, 0, 10],
+            &["Lorem", "ipsum", "dolor", "sit", "amet"],
+        );
+        let table_commitment =
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(&table, 2, &());
+        let two_rows = replacement_test_table(&[1, 5], &["Lorem", "ipsum"]);
+        let one_row = replacement_test_table(&[1], &["Lorem"]);
+
+        let try_replace = |replacement| table_commitment.clone().try_replace_rows(replacement);
+
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            two_rows.inner_table(),
+            two_rows.inner_table(),
+            1,
+            &(),
+        )
+        .unwrap();
+        assert!(matches!(
+            try_replace(replacement),
+            Err(ReplaceTableCommitmentRowsError::RowsOutOfRange { .. })
+        ));
+
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            two_rows.inner_table(),
+            two_rows.inner_table(),
+            6,
+            &(),
+        )
+        .unwrap();
+        assert!(matches!(
+            try_replace(replacement),
+            Err(ReplaceTableCommitmentRowsError::RowsOutOfRange { .. })
+        ));
+
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            two_rows.inner_table(),
+            one_row.inner_table(),
+            2,
+            &(),
+        )
+        .unwrap();
+        assert!(matches!(
+            try_replace(replacement),
+            Err(ReplaceTableCommitmentRowsError::NumRowsChanged)
+        ));
+
+        let other_columns = owned_table::<TestScalar>([bigint("bigint_column", [1, 5])]);
+        let replacement = RowReplacement::try_from_columns_with_offset(
+            other_columns.inner_table(),
+            other_columns.inner_table(),
+            2,
+            &(),
+        )
+        .unwrap();
+        assert!(matches!(
+            try_replace(replacement),
+            Err(ReplaceTableCommitmentRowsError::ColumnMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_construct_mismatched_row_replacements() {
+        let two_rows = replacement_test_table(&[1, 5], &["Lorem", "ipsum"]);
+        let removed =
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(&two_rows, 2, &());
+
+        let added = TableCommitment::from_owned_table_with_offset(&two_rows, 3, &());
+        assert!(matches!(
+            RowReplacement::try_new(removed.clone(), added),
+            Err(ReplaceTableCommitmentRowsError::MisalignedRows {
+                removed_start: 2,
+                added_start: 3
+            })
+        ));
+
+        let other_columns = owned_table::<TestScalar>([
+            bigint("bigint_column", [1, 5]),
+            bigint("varchar_column", [1, 5]),
+        ]);
+        let added = TableCommitment::from_owned_table_with_offset(&other_columns, 2, &());
+        assert!(matches!(
+            RowReplacement::try_new(removed, added),
+            Err(ReplaceTableCommitmentRowsError::ColumnMismatch { .. })
+        ));
+
+        let mixed_length_columns = [
+            (
+                &"bigint_column".into(),
+                &OwnedColumn::<TestScalar>::BigInt(vec![1, 5]),
+            ),
+            (
+                &"varchar_column".into(),
+                &OwnedColumn::<TestScalar>::VarChar(vec!["Lorem".into()]),
+            ),
+        ];
+        assert!(matches!(
+            RowReplacement::<NaiveCommitment>::try_from_columns_with_offset(
+                two_rows.inner_table(),
+                mixed_length_columns,
+                2,
+                &()
+            ),
+            Err(ReplaceTableCommitmentRowsError::TableCommitmentFromColumns { .. })
+        ));
+    }
+
     #[test]
     fn we_can_create_and_append_table_commitments_with_record_batches() {
         let schema = Arc::new(Schema::new(vec![
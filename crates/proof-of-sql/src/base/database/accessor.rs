@@ -1,10 +1,11 @@
 use crate::base::{
     commitment::Commitment,
-    database::{Column, ColumnRef, ColumnType, Table, TableOptions, TableRef},
+    database::{Column, ColumnRef, ColumnType, OwnedColumn, Table, TableOptions, TableRef},
     map::{IndexMap, IndexSet},
     scalar::Scalar,
 };
 use alloc::vec::Vec;
+use core::ops::Range;
 use sqlparser::ast::Ident;
 
 /// Access metadata of a table span in a database.
@@ -51,6 +52,9 @@ pub trait MetadataAccessor {
 /// will only be accessing information about columns that exist in the database.
 pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
     /// Return the full table column commitment
+    ///
+    /// This is the commitment to the component of the column that `column` refers to,
+    /// i.e. to the values of the column or to the presence of a nullable column.
     fn get_commitment(&self, column: ColumnRef) -> C;
 }
 
@@ -85,12 +89,19 @@ pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
 /// will only be accessing information about columns that exist in the database.
 pub trait DataAccessor<S: Scalar>: MetadataAccessor {
     /// Return the data span in the table (not the full-table data)
+    ///
+    /// A reference to the values of a nullable column returns the whole [`Column::Nullable`],
+    /// whose values are committed to as the column, and a reference to its presence
+    /// returns the presence as a [`Column::Boolean`].
+    /// A reference to a character component of a `VarChar` column returns it computed from the strings,
+    /// see [`Column::component`].
     fn get_column(&self, column: ColumnRef) -> Column<S>;
 
     /// Creates a new [`Table`] from a [`TableRef`] and [`ColumnRef`]s.
     ///
     /// Columns are retrieved from the [`DataAccessor`] using the provided [`TableRef`] and [`ColumnRef`]s.
     /// The only reason why [`table_ref`] is needed is because [`column_refs`] can be empty.
+    /// A nullable column is part of the table along with its presence if any of its components is referenced.
     /// # Panics
     /// Column length mismatches can occur in theory. In practice, this should not happen.
     fn get_table(&self, table_ref: TableRef, column_refs: &IndexSet<ColumnRef>) -> Table<S> {
@@ -101,6 +112,8 @@ pub trait DataAccessor<S: Scalar>: MetadataAccessor {
                 TableOptions::new(Some(input_length)),
             )
         } else {
+            let column_refs: IndexSet<ColumnRef> =
+                column_refs.iter().map(ColumnRef::values).collect();
             Table::<S>::try_from_iter(column_refs.into_iter().map(|column_ref| {
                 let column = self.get_column(column_ref.clone());
                 (column_ref.column_id(), column)
@@ -110,6 +123,30 @@ pub trait DataAccessor<S: Scalar>: MetadataAccessor {
     }
 }
 
+/// Access database columns of a table span one chunk of rows at a time.
+///
+/// Prover uses this information to process a query over a table span that is too large to hold
+/// in memory at once. Unlike a [`DataAccessor`], only the requested rows need to be materialized,
+/// e.g. by reading them from disk.
+///
+/// Note: `range` is relative to the start of the data span and always lies within the span.
+pub trait ChunkedDataAccessor<S: Scalar>: MetadataAccessor {
+    /// Return the rows of the data span in `range`
+    fn get_column_chunk(&self, column: ColumnRef, range: Range<usize>) -> OwnedColumn<S>;
+}
+
+/// Access commitments of chunks of database columns.
+///
+/// Verifier uses this information to process a query over only some of the rows of a table span,
+/// see [`RowRangeAccessor`](super::RowRangeAccessor).
+///
+/// Note: `range` is relative to the start of the data span and always lies within the span.
+pub trait ChunkedCommitmentAccessor<C: Commitment>: MetadataAccessor {
+    /// Return the commitment to the rows of the data span in `range`,
+    /// i.e. a commitment with an offset of `range.start` more than the data span's offset
+    fn get_chunk_commitment(&self, column: ColumnRef, range: Range<usize>) -> C;
+}
+
 /// Access tables and their schemas in a database.
 ///
 /// This accessor should be implemented by both the prover and verifier
@@ -134,4 +171,24 @@ pub trait SchemaAccessor {
     /// Precondition 1: the table must exist and be tamperproof.
     /// Precondition 2: `table_name` must be lowercase.
     fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Ident, ColumnType)>;
+
+    /// Lookup whether the column in the specified table is nullable,
+    /// i.e. whether it has a presence that is committed to along with its values
+    ///
+    /// Precondition 1: the table must exist and be tamperproof.
+    /// Precondition 2: `table_ref` and `column_id` must always be lowercase.
+    fn is_nullable(&self, _table_ref: TableRef, _column_id: Ident) -> bool {
+        false
+    }
+
+    /// Lookup the number of characters of the `VarChar` column in the specified table that are committed to,
+    /// i.e. up to which index its [`ColumnComponent::Character`](super::ColumnComponent::Character)
+    /// and [`ColumnComponent::ReversedCharacter`](super::ColumnComponent::ReversedCharacter) components,
+    /// along with its [`ColumnComponent::Length`](super::ColumnComponent::Length), can be referenced
+    ///
+    /// Precondition 1: the table must exist and be tamperproof.
+    /// Precondition 2: `table_ref` and `column_id` must always be lowercase.
+    fn character_count(&self, _table_ref: TableRef, _column_id: Ident) -> usize {
+        0
+    }
 }
@@ -1,10 +1,10 @@
-use super::{LiteralValue, OwnedColumn, TableRef};
+use super::{code_points, string_lengths, LiteralValue, OwnedColumn, TableRef};
 use crate::base::{
     math::decimal::Precision,
     scalar::{Scalar, ScalarExt},
     slice_ops::slice_cast_with,
 };
-use alloc::vec::Vec;
+use alloc::{format, vec, vec::Vec};
 use bumpalo::Bump;
 use core::{
     fmt,
@@ -54,6 +54,10 @@ pub enum Column<'a, S: Scalar> {
     TimestampTZ(PoSQLTimeUnit, PoSQLTimeZone, &'a [i64]),
     /// Variable length binary columns
     VarBinary((&'a [&'a [u8]], &'a [S])),
+    /// Nullable columns
+    /// - the first element maps to the values of the column, where NULL rows hold the default value of the type
+    /// - the second element maps to the presence of the column, which is `true` for the rows that are not NULL
+    Nullable(&'a Column<'a, S>, &'a [bool]),
 }
 
 impl<'a, S: Scalar> Column<'a, S> {
@@ -75,6 +79,7 @@ impl<'a, S: Scalar> Column<'a, S> {
                 ColumnType::TimestampTZ(*time_unit, *timezone)
             }
             Self::VarBinary(..) => ColumnType::VarBinary,
+            Self::Nullable(values, _) => values.column_type(),
         }
     }
     /// Returns the length of the column.
@@ -99,6 +104,10 @@ impl<'a, S: Scalar> Column<'a, S> {
             }
             Self::Int128(col) => col.len(),
             Self::Scalar(col) | Self::Decimal75(_, _, col) => col.len(),
+            Self::Nullable(values, presence) => {
+                assert_eq!(values.len(), presence.len());
+                presence.len()
+            }
         }
     }
     /// Returns `true` if the column has no elements.
@@ -107,6 +116,138 @@ impl<'a, S: Scalar> Column<'a, S> {
         self.len() == 0
     }
 
+    /// Returns the rows of the column from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// # Panics
+    /// Panics if the rows are out of bounds.
+    #[must_use]
+    pub fn slice(&self, start: usize, end: usize, alloc: &'a Bump) -> Self {
+        match self {
+            Self::Boolean(col) => Self::Boolean(&col[start..end]),
+            Self::Uint8(col) => Self::Uint8(&col[start..end]),
+            Self::TinyInt(col) => Self::TinyInt(&col[start..end]),
+            Self::SmallInt(col) => Self::SmallInt(&col[start..end]),
+            Self::Int(col) => Self::Int(&col[start..end]),
+            Self::BigInt(col) => Self::BigInt(&col[start..end]),
+            Self::Int128(col) => Self::Int128(&col[start..end]),
+            Self::Decimal75(precision, scale, col) => {
+                Self::Decimal75(*precision, *scale, &col[start..end])
+            }
+            Self::Scalar(col) => Self::Scalar(&col[start..end]),
+            Self::VarChar((col, scals)) => Self::VarChar((&col[start..end], &scals[start..end])),
+            Self::TimestampTZ(tu, tz, col) => Self::TimestampTZ(*tu, *tz, &col[start..end]),
+            Self::VarBinary((col, scals)) => {
+                Self::VarBinary((&col[start..end], &scals[start..end]))
+            }
+            Self::Nullable(values, presence) => Self::Nullable(
+                alloc.alloc(values.slice(start, end, alloc)),
+                &presence[start..end],
+            ),
+        }
+    }
+
+    /// Returns the values of the column, which for a nullable column hold the default value of the type in its NULL rows.
+    #[must_use]
+    pub fn values(&self) -> Self {
+        match self {
+            Self::Nullable(values, _) => **values,
+            _ => *self,
+        }
+    }
+
+    /// Returns the presence of the column if it is nullable, which is `true` for the rows that are not NULL.
+    #[must_use]
+    pub fn presence(&self) -> Option<&'a [bool]> {
+        match self {
+            Self::Nullable(_, presence) => Some(presence),
+            _ => None,
+        }
+    }
+
+    /// Returns the given component of the column.
+    ///
+    /// The character components of a `VarChar` column are computed from its strings in `alloc`.
+    ///
+    /// # Panics
+    /// Panics if the presence of a column that is not nullable,
+    /// or a character component of a column that is not `VarChar`, is requested.
+    #[must_use]
+    pub fn component(&self, alloc: &'a Bump, component: ColumnComponent) -> Self {
+        let strings = || self.as_varchar().expect("Column is not VarChar").0;
+        match component {
+            ColumnComponent::Values => self.values(),
+            ColumnComponent::Presence => {
+                Self::Boolean(self.presence().expect("Column is not nullable"))
+            }
+            ColumnComponent::Length => {
+                Self::BigInt(alloc.alloc_slice_copy(&string_lengths(strings())))
+            }
+            ColumnComponent::Character(index) => {
+                Self::Int(alloc.alloc_slice_copy(&code_points(strings(), index, false)))
+            }
+            ColumnComponent::ReversedCharacter(index) => {
+                Self::Int(alloc.alloc_slice_copy(&code_points(strings(), index, true)))
+            }
+        }
+    }
+
+    /// Returns the components of the column that are committed to on their own,
+    /// namely its values followed by its presence if it is nullable.
+    #[must_use]
+    pub fn components(&self) -> Vec<Self> {
+        match self {
+            Self::Nullable(values, presence) => vec![**values, Self::Boolean(presence)],
+            _ => vec![*self],
+        }
+    }
+
+    /// Returns the values of the column with the default value of the type in the rows where `presence` is `false`.
+    ///
+    /// # Panics
+    /// Panics if `presence` does not have the length of the column.
+    #[must_use]
+    pub fn mask(&self, alloc: &'a Bump, presence: &[bool]) -> Self {
+        fn mask_slice<'a, T: Copy + Default>(
+            alloc: &'a Bump,
+            values: &[T],
+            presence: &[bool],
+        ) -> &'a [T] {
+            assert_eq!(values.len(), presence.len());
+            alloc.alloc_slice_fill_with(values.len(), |i| {
+                if presence[i] {
+                    values[i]
+                } else {
+                    T::default()
+                }
+            })
+        }
+        match self.values() {
+            Self::Boolean(col) => Self::Boolean(mask_slice(alloc, col, presence)),
+            Self::Uint8(col) => Self::Uint8(mask_slice(alloc, col, presence)),
+            Self::TinyInt(col) => Self::TinyInt(mask_slice(alloc, col, presence)),
+            Self::SmallInt(col) => Self::SmallInt(mask_slice(alloc, col, presence)),
+            Self::Int(col) => Self::Int(mask_slice(alloc, col, presence)),
+            Self::BigInt(col) => Self::BigInt(mask_slice(alloc, col, presence)),
+            Self::Int128(col) => Self::Int128(mask_slice(alloc, col, presence)),
+            Self::Decimal75(precision, scale, col) => {
+                Self::Decimal75(precision, scale, mask_slice(alloc, col, presence))
+            }
+            Self::Scalar(col) => Self::Scalar(mask_slice(alloc, col, presence)),
+            Self::VarChar((col, scals)) => Self::VarChar((
+                mask_slice(alloc, col, presence),
+                mask_slice(alloc, scals, presence),
+            )),
+            Self::TimestampTZ(tu, tz, col) => {
+                Self::TimestampTZ(tu, tz, mask_slice(alloc, col, presence))
+            }
+            Self::VarBinary((col, scals)) => Self::VarBinary((
+                mask_slice(alloc, col, presence),
+                mask_slice(alloc, scals, presence),
+            )),
+            Self::Nullable(..) => unreachable!("the values of a column are not nullable"),
+        }
+    }
+
     /// Generate a constant column from a literal value with a given length
     pub fn from_literal_with_length(
         literal: &LiteralValue,
@@ -195,12 +336,18 @@ impl<'a, S: Scalar> Column<'a, S> {
                 ))
             }
             OwnedColumn::TimestampTZ(tu, tz, col) => Column::TimestampTZ(*tu, *tz, col.as_slice()),
+            OwnedColumn::Nullable(values, presence) => Column::Nullable(
+                alloc.alloc(Column::from_owned_column(values, alloc)),
+                presence.as_slice(),
+            ),
         }
     }
 
     /// Returns the column as a slice of booleans if it is a boolean column. Otherwise, returns None.
+    ///
+    /// For a nullable column, this and the accessors below return its values.
     pub(crate) fn as_boolean(&self) -> Option<&'a [bool]> {
-        match self {
+        match self.values() {
             Self::Boolean(col) => Some(col),
             _ => None,
         }
@@ -208,7 +355,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of u8 if it is a uint8 column. Otherwise, returns None.
     pub(crate) fn as_uint8(&self) -> Option<&'a [u8]> {
-        match self {
+        match self.values() {
             Self::Uint8(col) => Some(col),
             _ => None,
         }
@@ -216,7 +363,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i8 if it is a tinyint column. Otherwise, returns None.
     pub(crate) fn as_tinyint(&self) -> Option<&'a [i8]> {
-        match self {
+        match self.values() {
             Self::TinyInt(col) => Some(col),
             _ => None,
         }
@@ -224,7 +371,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i16 if it is a smallint column. Otherwise, returns None.
     pub(crate) fn as_smallint(&self) -> Option<&'a [i16]> {
-        match self {
+        match self.values() {
             Self::SmallInt(col) => Some(col),
             _ => None,
         }
@@ -232,7 +379,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i32 if it is an int column. Otherwise, returns None.
     pub(crate) fn as_int(&self) -> Option<&'a [i32]> {
-        match self {
+        match self.values() {
             Self::Int(col) => Some(col),
             _ => None,
         }
@@ -240,7 +387,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i64 if it is a bigint column. Otherwise, returns None.
     pub(crate) fn as_bigint(&self) -> Option<&'a [i64]> {
-        match self {
+        match self.values() {
             Self::BigInt(col) => Some(col),
             _ => None,
         }
@@ -248,7 +395,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i128 if it is an int128 column. Otherwise, returns None.
     pub(crate) fn as_int128(&self) -> Option<&'a [i128]> {
-        match self {
+        match self.values() {
             Self::Int128(col) => Some(col),
             _ => None,
         }
@@ -256,7 +403,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of scalars if it is a scalar column. Otherwise, returns None.
     pub(crate) fn as_scalar(&self) -> Option<&'a [S]> {
-        match self {
+        match self.values() {
             Self::Scalar(col) => Some(col),
             _ => None,
         }
@@ -264,7 +411,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of scalars if it is a decimal75 column. Otherwise, returns None.
     pub(crate) fn as_decimal75(&self) -> Option<&'a [S]> {
-        match self {
+        match self.values() {
             Self::Decimal75(_, _, col) => Some(col),
             _ => None,
         }
@@ -272,7 +419,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of strings and a slice of scalars if it is a varchar column. Otherwise, returns None.
     pub(crate) fn as_varchar(&self) -> Option<(&'a [&'a str], &'a [S])> {
-        match self {
+        match self.values() {
             Self::VarChar((col, scals)) => Some((col, scals)),
             _ => None,
         }
@@ -280,7 +427,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of strings and a slice of scalars if it is a varchar column. Otherwise, returns None.
     pub(crate) fn as_varbinary(&self) -> Option<(&'a [&'a [u8]], &'a [S])> {
-        match self {
+        match self.values() {
             Self::VarBinary((col, scals)) => Some((col, scals)),
             _ => None,
         }
@@ -288,7 +435,7 @@ impl<'a, S: Scalar> Column<'a, S> {
 
     /// Returns the column as a slice of i64 if it is a timestamp column. Otherwise, returns None.
     pub(crate) fn as_timestamptz(&self) -> Option<&'a [i64]> {
-        match self {
+        match self.values() {
             Self::TimestampTZ(_, _, col) => Some(col),
             _ => None,
         }
@@ -308,6 +455,7 @@ impl<'a, S: Scalar> Column<'a, S> {
             Self::Int128(col) => S::from(col[index]),
             Self::Scalar(col) | Self::Decimal75(_, _, col) => col[index],
             Self::VarChar((_, scals)) | Self::VarBinary((_, scals)) => scals[index],
+            Self::Nullable(values, _) => values.scalar_at(index)?,
         })
     }
 
@@ -328,6 +476,7 @@ impl<'a, S: Scalar> Column<'a, S> {
             Self::Int128(col) => slice_cast_with(col, |i| S::from(i) * scale_factor),
             Self::Scalar(col) => slice_cast_with(col, |i| S::from(i) * scale_factor),
             Self::TimestampTZ(_, _, col) => slice_cast_with(col, |i| S::from(i) * scale_factor),
+            Self::Nullable(values, _) => values.to_scalar_with_scaling(scale),
         }
     }
 }
@@ -586,12 +735,65 @@ impl Display for ColumnType {
     }
 }
 
+/// A component of a column that is committed to on its own.
+///
+/// Every column has values, and a nullable column also has a presence.
+/// The characters of the strings of a `VarChar` column, which `LIKE` is proven with,
+/// can be committed to as components of it as well.
+#[derive(
+    Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize,
+)]
+pub enum ColumnComponent {
+    /// The values of the column
+    #[default]
+    Values,
+    /// The presence of a nullable column, which is `true` for the rows that are not NULL
+    Presence,
+    /// The number of characters of each string of a `VarChar` column
+    Length,
+    /// The code point of the character at the given index of each string of a `VarChar` column,
+    /// or zero if the string is too short
+    Character(usize),
+    /// The code point of the character at the given index from the end of each string of a `VarChar` column,
+    /// or zero if the string is too short
+    ReversedCharacter(usize),
+}
+
+impl ColumnComponent {
+    /// Returns the type of the component of a column of type `column_type`
+    #[must_use]
+    pub fn component_type(self, column_type: &ColumnType) -> &ColumnType {
+        match self {
+            ColumnComponent::Values => column_type,
+            ColumnComponent::Presence => &ColumnType::Boolean,
+            ColumnComponent::Length => &ColumnType::BigInt,
+            ColumnComponent::Character(_) | ColumnComponent::ReversedCharacter(_) => {
+                &ColumnType::Int
+            }
+        }
+    }
+
+    /// Whether this is one of the character components of a `VarChar` column
+    #[must_use]
+    pub fn is_character(self) -> bool {
+        matches!(
+            self,
+            ColumnComponent::Length
+                | ColumnComponent::Character(_)
+                | ColumnComponent::ReversedCharacter(_)
+        )
+    }
+}
+
 /// Reference of a SQL column
+///
+/// It refers to one component of the column, which is its values unless stated otherwise.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct ColumnRef {
     column_id: Ident,
     table_ref: TableRef,
     column_type: ColumnType,
+    component: ColumnComponent,
 }
 
 impl ColumnRef {
@@ -602,9 +804,37 @@ impl ColumnRef {
             column_id,
             table_ref,
             column_type,
+            component: ColumnComponent::Values,
         }
     }
 
+    /// Returns the reference to the values of this column
+    #[must_use]
+    pub fn values(&self) -> Self {
+        self.with_component(ColumnComponent::Values)
+    }
+
+    /// Returns the reference to the presence of this column, which has to be nullable
+    #[must_use]
+    pub fn presence(&self) -> Self {
+        self.with_component(ColumnComponent::Presence)
+    }
+
+    /// Returns the reference to the given component of this column
+    #[must_use]
+    pub fn with_component(&self, component: ColumnComponent) -> Self {
+        Self {
+            component,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the component of the column that this refers to
+    #[must_use]
+    pub fn component(&self) -> ColumnComponent {
+        self.component
+    }
+
     /// Returns the table reference of this column
     #[must_use]
     pub fn table_ref(&self) -> TableRef {
@@ -617,10 +847,11 @@ impl ColumnRef {
         self.column_id.clone()
     }
 
-    /// Returns the column type of this column
+    /// Returns the column type of the component of this column,
+    /// which is `Boolean` for its presence
     #[must_use]
     pub fn column_type(&self) -> &ColumnType {
-        &self.column_type
+        self.component.component_type(&self.column_type)
     }
 }
 
@@ -628,17 +859,39 @@ impl ColumnRef {
 /// of a column in a table. Namely: it's name and type.
 ///
 /// This is the analog of a `Field` in Apache Arrow.
+/// Like a [`ColumnRef`], it describes one component of the column,
+/// so the presence of a nullable column has a field of its own.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct ColumnField {
     name: Ident,
     data_type: ColumnType,
+    component: ColumnComponent,
 }
 
 impl ColumnField {
     /// Create a new `ColumnField` from a name and a type
     #[must_use]
     pub fn new(name: Ident, data_type: ColumnType) -> ColumnField {
-        ColumnField { name, data_type }
+        ColumnField {
+            name,
+            data_type,
+            component: ColumnComponent::Values,
+        }
+    }
+
+    /// Returns the field of the presence of this column, which has to be nullable
+    #[must_use]
+    pub fn presence(&self) -> ColumnField {
+        self.with_component(ColumnComponent::Presence)
+    }
+
+    /// Returns the field of the given component of this column
+    #[must_use]
+    pub fn with_component(&self, component: ColumnComponent) -> ColumnField {
+        ColumnField {
+            component,
+            ..self.clone()
+        }
     }
 
     /// Returns the name of the column
@@ -647,10 +900,23 @@ impl ColumnField {
         self.name.clone()
     }
 
-    /// Returns the type of the column
+    /// Returns the type of the component of the column,
+    /// which is `Boolean` for its presence
     #[must_use]
     pub fn data_type(&self) -> ColumnType {
-        self.data_type
+        *self.component.component_type(&self.data_type)
+    }
+
+    /// Returns the component of the column that this describes
+    #[must_use]
+    pub fn component(&self) -> ColumnComponent {
+        self.component
+    }
+
+    /// Returns the reference to the component of the column that this describes in the table `table_ref`
+    #[must_use]
+    pub fn column_ref(&self, table_ref: TableRef) -> ColumnRef {
+        ColumnRef::new(table_ref, self.name(), self.data_type).with_component(self.component)
     }
 }
 
@@ -964,6 +1230,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_slice_a_column() {
+        let alloc = Bump::new();
+        let scalar_values = [
+            TestScalar::from(1),
+            TestScalar::from(2),
+            TestScalar::from(3),
+        ];
+
+        let column = Column::<TestScalar>::BigInt(&[1, 2, 3]);
+        assert_eq!(column.slice(1, 3, &alloc), Column::BigInt(&[2, 3]));
+        assert_eq!(column.slice(3, 3, &alloc), Column::BigInt(&[]));
+
+        let column = Column::VarChar((&["a", "b", "c"], &scalar_values));
+        assert_eq!(
+            column.slice(0, 2, &alloc),
+            Column::VarChar((&["a", "b"], &scalar_values[..2]))
+        );
+
+        let column = Column::<TestScalar>::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            &[1, 2, 3],
+        );
+        assert_eq!(
+            column.slice(2, 3, &alloc),
+            Column::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), &[3])
+        );
+
+        let values = Column::<TestScalar>::BigInt(&[1, 0, 3]);
+        let column = Column::Nullable(&values, &[true, false, true]);
+        assert_eq!(
+            column.slice(1, 3, &alloc),
+            Column::Nullable(&Column::BigInt(&[0, 3]), &[false, true])
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_components_of_a_nullable_column() {
+        let values = Column::<TestScalar>::BigInt(&[1, 0, 3]);
+        let column = Column::Nullable(&values, &[true, false, true]);
+        assert_eq!(column.column_type(), ColumnType::BigInt);
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.values(), values);
+        assert_eq!(column.presence(), Some(&[true, false, true][..]));
+        let alloc = Bump::new();
+        assert_eq!(
+            column.component(&alloc, ColumnComponent::Presence),
+            Column::Boolean(&[true, false, true])
+        );
+        assert_eq!(column.component(&alloc, ColumnComponent::Values), values);
+        assert_eq!(values.presence(), None);
+        assert_eq!(values.values(), values);
+    }
+
+    #[test]
+    fn we_can_get_the_character_components_of_a_varchar_column() {
+        let alloc = Bump::new();
+        let strings = ["ab", "", "xyz"];
+        let scalars = strings.map(TestScalar::from);
+        let column = Column::VarChar((&strings, &scalars));
+        assert_eq!(
+            column.component(&alloc, ColumnComponent::Length),
+            Column::BigInt(&[2, 0, 3])
+        );
+        assert_eq!(
+            column.component(&alloc, ColumnComponent::Character(1)),
+            Column::Int(&['b' as i32, 0, 'y' as i32])
+        );
+        assert_eq!(
+            column.component(&alloc, ColumnComponent::ReversedCharacter(0)),
+            Column::Int(&['b' as i32, 0, 'z' as i32])
+        );
+        assert_eq!(
+            ColumnComponent::Length.component_type(&ColumnType::VarChar),
+            &ColumnType::BigInt
+        );
+        assert_eq!(
+            ColumnComponent::Character(0).component_type(&ColumnType::VarChar),
+            &ColumnType::Int
+        );
+    }
+
     #[test]
     fn we_can_get_the_len_of_a_column() {
         let precision = 10;
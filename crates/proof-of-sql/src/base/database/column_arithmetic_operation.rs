@@ -6,8 +6,8 @@ use crate::base::{
             try_subtract_decimal_columns,
         },
         slice_operation::{
-            try_add, try_div, try_mul, try_slice_binary_op, try_slice_binary_op_left_upcast,
-            try_slice_binary_op_right_upcast, try_sub,
+            try_add, try_div, try_mul, try_rem, try_slice_binary_op,
+            try_slice_binary_op_left_upcast, try_slice_binary_op_right_upcast, try_sub,
         },
         ColumnType, OwnedColumn,
     },
@@ -17,12 +17,12 @@ use crate::base::{
 use alloc::{string::ToString, vec::Vec};
 use core::fmt::Debug;
 use num_bigint::BigInt;
-use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
 
 pub trait ArithmeticOp {
     fn op<T>(l: &T, r: &T) -> ColumnOperationResult<T>
     where
-        T: Debug + CheckedDiv + CheckedMul + CheckedAdd + CheckedSub;
+        T: Debug + CheckedDiv + CheckedMul + CheckedAdd + CheckedSub + CheckedRem<Output = T>;
     fn decimal_op<S, T0, T1>(
         lhs: &[T0],
         rhs: &[T1],
@@ -323,3 +323,31 @@ impl ArithmeticOp for DivOp {
         try_divide_decimal_columns(lhs, rhs, left_column_type, right_column_type)
     }
 }
+
+pub struct ModOp {}
+impl ArithmeticOp for ModOp {
+    fn op<T>(l: &T, r: &T) -> ColumnOperationResult<T>
+    where
+        T: CheckedRem<Output = T> + Debug,
+    {
+        try_rem(l, r)
+    }
+
+    fn decimal_op<S, T0, T1>(
+        _lhs: &[T0],
+        _rhs: &[T1],
+        left_column_type: ColumnType,
+        right_column_type: ColumnType,
+    ) -> ColumnOperationResult<(Precision, i8, Vec<S>)>
+    where
+        S: Scalar + From<T0> + From<T1>,
+        T0: Copy + Debug + Into<BigInt>,
+        T1: Copy + Debug + Into<BigInt>,
+    {
+        Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "%".to_string(),
+            left_type: left_column_type,
+            right_type: right_column_type,
+        })
+    }
+}
@@ -16,6 +16,12 @@ pub(crate) fn apply_column_to_indexes<'a, S>(
 where
     S: Scalar,
 {
+    if let Column::Nullable(values, presence) = column {
+        return Ok(Column::Nullable(
+            alloc.alloc(apply_column_to_indexes(values, alloc, indexes)?),
+            alloc.alloc_slice_copy(&apply_slice_to_indexes(presence, indexes)?),
+        ));
+    }
     match column.column_type() {
         ColumnType::Boolean => {
             let raw_values = apply_slice_to_indexes(
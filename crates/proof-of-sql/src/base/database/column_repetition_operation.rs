@@ -16,6 +16,13 @@ pub trait RepetitionOp {
     where
         S: Scalar,
     {
+        if let Column::Nullable(values, presence) = column {
+            let Column::Boolean(presence) = Self::column_op(&Column::Boolean(*presence), alloc, n)
+            else {
+                unreachable!("the repetition of a boolean column is boolean")
+            };
+            return Column::Nullable(alloc.alloc(Self::column_op(values, alloc, n)), presence);
+        }
         let len = n * column.len();
         match column.column_type() {
             ColumnType::Boolean => {
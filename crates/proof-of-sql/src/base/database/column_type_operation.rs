@@ -169,6 +169,143 @@ pub fn try_divide_column_types(
     Ok(ColumnType::Decimal75(precision, scale))
 }
 
+/// Determine the output type of a modulo operation if it is possible
+/// to take the remainder of the two input types. If the types are not compatible, return
+/// an error.
+///
+/// Only integer types are supported. The result has the larger of the two types.
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` is an integer, and `lhs.max_integer_type(&rhs)` returns `None`.
+pub fn try_modulo_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if !lhs.is_integer() || !rhs.is_integer() {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "%".to_string(),
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    // We can unwrap here because we know that both types are integers
+    Ok(lhs.max_integer_type(&rhs).unwrap())
+}
+
+/// Determine the output type of a conditional expression whose branches have the two input types
+/// if they are compatible. If they are not, return an error.
+///
+/// Both branches have to be booleans or numeric. The result can hold the values of either branch, that is
+///   - integers result in the larger of the two types
+///   - decimals result in `new_scale = max(lhs_scale, rhs_scale)` and
+///     `new_precision = new_scale + max(lhs_precision - lhs_scale, rhs_precision - rhs_scale)`
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
+/// - Panics if `lhs` or `rhs` is an integer, and `lhs.max_integer_type(&rhs)` returns `None`.
+pub fn try_case_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if lhs == rhs && (lhs == ColumnType::Boolean || lhs.is_numeric()) {
+        return Ok(lhs);
+    }
+    if !lhs.is_numeric() || !rhs.is_numeric() {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "CASE".to_string(),
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    match (lhs, rhs) {
+        // A `TINYINT` can not hold every `UINT8`
+        (ColumnType::Uint8, ColumnType::TinyInt) | (ColumnType::TinyInt, ColumnType::Uint8) => {
+            return Ok(ColumnType::SmallInt);
+        }
+        // We can unwrap here because we know that both types are integers
+        _ if lhs.is_integer() && rhs.is_integer() => {
+            return Ok(lhs.max_integer_type(&rhs).unwrap())
+        }
+        (ColumnType::Scalar, _) | (_, ColumnType::Scalar) => return Ok(ColumnType::Scalar),
+        _ => (),
+    }
+    let left_precision_value =
+        i16::from(lhs.precision_value().expect("Numeric types have precision"));
+    let right_precision_value =
+        i16::from(rhs.precision_value().expect("Numeric types have precision"));
+    let left_scale = lhs.scale().expect("Numeric types have scale");
+    let right_scale = rhs.scale().expect("Numeric types have scale");
+    let scale = left_scale.max(right_scale);
+    let precision_value: i16 = i16::from(scale)
+        + (left_precision_value - i16::from(left_scale))
+            .max(right_precision_value - i16::from(right_scale));
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of an average of the given type if it is possible
+/// to average it. If the type is not numeric, return an error.
+///
+/// The average is always a decimal which keeps at least 6 digits after the decimal point.
+/// Since an average can not be larger in absolute value than the values it is taken over,
+/// the number of digits before the decimal point is the same as that of the input type. That is,
+///   - `new_scale = max(6, scale)`
+///   - `new_precision = precision - scale + new_scale`
+///
+/// # Panics
+///
+/// - Panics if `column_type` does not have a precision or scale when it is expected to be a numeric type.
+pub fn try_average_column_type(column_type: ColumnType) -> ColumnOperationResult<ColumnType> {
+    if !column_type.is_numeric() || column_type == ColumnType::Scalar {
+        return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: "AVG".to_string(),
+            operand_type: column_type,
+        });
+    }
+    let precision_value = i16::from(
+        column_type
+            .precision_value()
+            .expect("Numeric types have precision"),
+    );
+    let scale = i16::from(column_type.scale().expect("Numeric types have scale"));
+    let raw_scale = scale.max(6_i16);
+    let new_precision_value = precision_value - scale + raw_scale;
+    let new_scale =
+        i8::try_from(raw_scale).map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidScale {
+                scale: raw_scale.to_string(),
+            },
+        })?;
+    let precision = u8::try_from(new_precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: new_precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, new_scale))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -750,4 +887,147 @@ mod test {
             })
         ));
     }
+
+    #[test]
+    fn we_can_modulo_integer_types() {
+        assert_eq!(
+            try_modulo_column_types(ColumnType::TinyInt, ColumnType::TinyInt).unwrap(),
+            ColumnType::TinyInt
+        );
+        assert_eq!(
+            try_modulo_column_types(ColumnType::SmallInt, ColumnType::BigInt).unwrap(),
+            ColumnType::BigInt
+        );
+        assert_eq!(
+            try_modulo_column_types(ColumnType::Int128, ColumnType::Int).unwrap(),
+            ColumnType::Int128
+        );
+    }
+
+    #[test]
+    fn we_cannot_modulo_non_integer_types() {
+        for (lhs, rhs) in [
+            (ColumnType::BigInt, ColumnType::VarChar),
+            (ColumnType::Boolean, ColumnType::Int),
+            (ColumnType::Scalar, ColumnType::BigInt),
+            (
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 0),
+                ColumnType::BigInt,
+            ),
+            (
+                ColumnType::Int,
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            ),
+        ] {
+            assert!(matches!(
+                try_modulo_column_types(lhs, rhs),
+                Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_average_numeric_types() {
+        assert_eq!(
+            try_average_column_type(ColumnType::TinyInt).unwrap(),
+            ColumnType::Decimal75(Precision::new(9).unwrap(), 6)
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::BigInt).unwrap(),
+            ColumnType::Decimal75(Precision::new(25).unwrap(), 6)
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Int128).unwrap(),
+            ColumnType::Decimal75(Precision::new(45).unwrap(), 6)
+        );
+        // The scale is kept if it is at least 6
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(20).unwrap(), 8)).unwrap(),
+            ColumnType::Decimal75(Precision::new(20).unwrap(), 8)
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), 2)).unwrap(),
+            ColumnType::Decimal75(Precision::new(14).unwrap(), 6)
+        );
+        assert_eq!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(10).unwrap(), -2))
+                .unwrap(),
+            ColumnType::Decimal75(Precision::new(18).unwrap(), 6)
+        );
+    }
+
+    #[test]
+    fn we_cannot_average_non_numeric_or_scalar_types_or_too_precise_decimals() {
+        for column_type in [ColumnType::Boolean, ColumnType::VarChar, ColumnType::Scalar] {
+            assert!(matches!(
+                try_average_column_type(column_type),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_average_column_type(ColumnType::Decimal75(Precision::new(72).unwrap(), 0)),
+            Err(ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn we_can_determine_the_type_of_case_branches() {
+        assert_eq!(
+            try_case_column_types(ColumnType::Boolean, ColumnType::Boolean).unwrap(),
+            ColumnType::Boolean
+        );
+        assert_eq!(
+            try_case_column_types(ColumnType::SmallInt, ColumnType::BigInt).unwrap(),
+            ColumnType::BigInt
+        );
+        assert_eq!(
+            try_case_column_types(ColumnType::Uint8, ColumnType::TinyInt).unwrap(),
+            ColumnType::SmallInt
+        );
+        assert_eq!(
+            try_case_column_types(ColumnType::Int, ColumnType::Scalar).unwrap(),
+            ColumnType::Scalar
+        );
+        assert_eq!(
+            try_case_column_types(
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+                ColumnType::Decimal75(Precision::new(5).unwrap(), 4)
+            )
+            .unwrap(),
+            ColumnType::Decimal75(Precision::new(12).unwrap(), 4)
+        );
+        assert_eq!(
+            try_case_column_types(
+                ColumnType::Int,
+                ColumnType::Decimal75(Precision::new(3).unwrap(), 1)
+            )
+            .unwrap(),
+            ColumnType::Decimal75(Precision::new(11).unwrap(), 1)
+        );
+    }
+
+    #[test]
+    fn we_cannot_determine_the_type_of_incompatible_case_branches() {
+        for (lhs, rhs) in [
+            (ColumnType::Boolean, ColumnType::BigInt),
+            (ColumnType::VarChar, ColumnType::VarChar),
+            (ColumnType::Int, ColumnType::VarChar),
+        ] {
+            assert!(matches!(
+                try_case_column_types(lhs, rhs),
+                Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_case_column_types(
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 0),
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 1)
+            ),
+            Err(ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision { .. }
+            })
+        ));
+    }
 }
@@ -1,40 +1,195 @@
 use super::{ExpressionEvaluationError, ExpressionEvaluationResult};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable},
+    database::{ColumnOperationError, LikePattern, NullableOwnedColumn, OwnedColumn, OwnedTable},
     math::{
         decimal::{try_convert_intermediate_decimal_to_scalar, DecimalError, Precision},
         BigDecimalExt,
     },
     scalar::Scalar,
 };
-use alloc::{format, string::ToString, vec};
-use proof_of_sql_parser::intermediate_ast::{Expression, Literal};
+use alloc::{format, string::ToString, vec, vec::Vec};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone,
+    Timelike, Utc,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{Expression, Literal},
+    posql_time::{PoSQLDateTimeField, PoSQLTimeUnit},
+};
 use sqlparser::ast::{BinaryOperator, Ident, UnaryOperator};
 
 impl<S: Scalar> OwnedTable<S> {
     /// Evaluate an expression on the table.
+    ///
+    /// NULL entries of the result hold the default value of the type.
+    /// In particular, a predicate is only `true` where it is neither `false` nor NULL.
     pub fn evaluate(&self, expr: &Expression) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        Ok(self.evaluate_nullable(expr)?.into_values())
+    }
+
+    /// Evaluate an expression on the table, keeping track of NULLs.
+    ///
+    /// A column is nullable if the table has a presence column for it.
+    /// Predicates use three-valued logic and other operations are NULL if any operand is NULL.
+    pub fn evaluate_nullable(
+        &self,
+        expr: &Expression,
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
         match expr {
-            Expression::Column(identifier) => self.evaluate_column(&Ident::from(*identifier)),
-            Expression::Literal(lit) => self.evaluate_literal(lit),
+            Expression::Column(identifier)
+            | Expression::QualifiedColumn {
+                column: identifier, ..
+            } => self.evaluate_column(&Ident::from(*identifier)),
+            Expression::Literal(lit) => Ok(self.evaluate_literal(lit)?.into()),
             Expression::Binary { op, left, right } => {
                 self.evaluate_binary_expr(&(*op).into(), left, right)
             }
             Expression::Unary { op, expr } => self.evaluate_unary_expr((*op).into(), expr),
+            Expression::IsNull(expr) => Ok(self.evaluate_nullable(expr)?.element_wise_is_null()),
+            Expression::IsNotNull(expr) => {
+                Ok(self.evaluate_nullable(expr)?.element_wise_is_not_null())
+            }
+            Expression::InList { .. } | Expression::Between { .. } => {
+                self.evaluate_comparisons(expr)
+            }
+            Expression::Like { expr, pattern } => self.evaluate_like(expr, pattern),
+            Expression::Extract {
+                expr: timestamps, ..
+            }
+            | Expression::DateTrunc {
+                expr: timestamps, ..
+            }
+            | Expression::AddInterval {
+                expr: timestamps, ..
+            } => self.evaluate_date_time(expr, timestamps),
             _ => Err(ExpressionEvaluationError::Unsupported {
                 expression: format!("Expression {expr:?} is not supported yet"),
             }),
         }
     }
 
-    fn evaluate_column(&self, identifier: &Ident) -> ExpressionEvaluationResult<OwnedColumn<S>> {
-        Ok(self
+    /// Evaluates an `IN` or `BETWEEN` predicate through the comparisons it stands for.
+    fn evaluate_comparisons(
+        &self,
+        expr: &Expression,
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let comparisons =
+            expr.to_comparisons()
+                .ok_or_else(|| ExpressionEvaluationError::Unsupported {
+                    expression: format!("Expression {expr:?} is not a membership predicate"),
+                })?;
+        self.evaluate_nullable(&comparisons)
+    }
+
+    /// Evaluates a `LIKE` predicate, which is NULL where the string is NULL.
+    fn evaluate_like(
+        &self,
+        expr: &Expression,
+        pattern: &str,
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let column = self.evaluate_nullable(expr)?;
+        let OwnedColumn::VarChar(values) = column.values() else {
+            return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+                operator: "LIKE".to_string(),
+                operand_type: column.column_type(),
+            })?;
+        };
+        let pattern = LikePattern::new(pattern);
+        Ok(NullableOwnedColumn::try_new(
+            OwnedColumn::Boolean(values.iter().map(|value| pattern.matches(value)).collect()),
+            column.presence().map(<[bool]>::to_vec),
+        )?)
+    }
+
+    /// Evaluates a date/time function in the time zone of its timestamps,
+    /// which is NULL where the timestamp is.
+    fn evaluate_date_time(
+        &self,
+        expr: &Expression,
+        timestamps: &Expression,
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let column = self.evaluate_nullable(timestamps)?;
+        let OwnedColumn::TimestampTZ(unit, timezone, values) = column.values() else {
+            return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+                operator: "date/time function".to_string(),
+                operand_type: column.column_type(),
+            })?;
+        };
+        let out_of_range = || ColumnOperationError::IntegerOverflow {
+            error: format!("Expression {expr:?} results in a timestamp out of range"),
+        };
+        let offset = FixedOffset::east_opt(timezone.offset()).ok_or_else(out_of_range)?;
+        let local_times = values
+            .iter()
+            .map(|&value| Some(from_timestamp(value, *unit)?.with_timezone(&offset)))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(out_of_range)?;
+        let values = match expr {
+            Expression::Extract { field, .. } => OwnedColumn::BigInt(
+                local_times
+                    .iter()
+                    .map(|time| extract_field(time, *field))
+                    .collect(),
+            ),
+            Expression::DateTrunc { field, .. } => OwnedColumn::TimestampTZ(
+                *unit,
+                *timezone,
+                local_times
+                    .iter()
+                    .map(|time| to_timestamp(&truncate(time, *field)?, *unit))
+                    .collect::<Option<_>>()
+                    .ok_or_else(out_of_range)?,
+            ),
+            Expression::AddInterval { interval, .. } => OwnedColumn::TimestampTZ(
+                *unit,
+                *timezone,
+                local_times
+                    .iter()
+                    .map(|time| {
+                        let time = match interval.field() {
+                            PoSQLDateTimeField::Year | PoSQLDateTimeField::Month => {
+                                let months = if interval.field() == PoSQLDateTimeField::Year {
+                                    interval.count().checked_mul(12)?
+                                } else {
+                                    interval.count()
+                                };
+                                let abs_months = Months::new(u32::try_from(months.abs()).ok()?);
+                                if months < 0 {
+                                    time.checked_sub_months(abs_months)?
+                                } else {
+                                    time.checked_add_months(abs_months)?
+                                }
+                            }
+                            _ => time
+                                .checked_add_signed(TimeDelta::try_seconds(interval.seconds()?)?)?,
+                        };
+                        to_timestamp(&time, *unit)
+                    })
+                    .collect::<Option<_>>()
+                    .ok_or_else(out_of_range)?,
+            ),
+            _ => Err(ExpressionEvaluationError::Unsupported {
+                expression: format!("Expression {expr:?} is not a date/time function"),
+            })?,
+        };
+        Ok(NullableOwnedColumn::try_new(
+            values,
+            column.presence().map(<[bool]>::to_vec),
+        )?)
+    }
+
+    fn evaluate_column(
+        &self,
+        identifier: &Ident,
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let column = self
             .inner_table()
             .get(identifier)
             .ok_or(ExpressionEvaluationError::ColumnNotFound {
                 error: identifier.to_string(),
             })?
-            .clone())
+            .clone();
+        Ok(NullableOwnedColumn::from(column))
     }
 
     fn evaluate_literal(&self, lit: &Literal) -> ExpressionEvaluationResult<OwnedColumn<S>> {
@@ -67,8 +222,8 @@ impl<S: Scalar> OwnedTable<S> {
         &self,
         op: UnaryOperator,
         expr: &Expression,
-    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
-        let column = self.evaluate(expr)?;
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let column = self.evaluate_nullable(expr)?;
         match op {
             UnaryOperator::Not => Ok(column.element_wise_not()?),
             // Handle unsupported unary operators
@@ -83,22 +238,81 @@ impl<S: Scalar> OwnedTable<S> {
         op: &BinaryOperator,
         left: &Expression,
         right: &Expression,
-    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
-        let left = self.evaluate(left)?;
-        let right = self.evaluate(right)?;
+    ) -> ExpressionEvaluationResult<NullableOwnedColumn<S>> {
+        let left = self.evaluate_nullable(left)?;
+        let right = self.evaluate_nullable(right)?;
         match op {
             BinaryOperator::And => Ok(left.element_wise_and(&right)?),
             BinaryOperator::Or => Ok(left.element_wise_or(&right)?),
-            BinaryOperator::Eq => Ok(left.element_wise_eq(&right)?),
-            BinaryOperator::Gt => Ok(left.element_wise_gt(&right)?),
-            BinaryOperator::Lt => Ok(left.element_wise_lt(&right)?),
-            BinaryOperator::Plus => Ok(left.element_wise_add(&right)?),
-            BinaryOperator::Minus => Ok(left.element_wise_sub(&right)?),
-            BinaryOperator::Multiply => Ok(left.element_wise_mul(&right)?),
-            BinaryOperator::Divide => Ok(left.element_wise_div(&right)?),
+            BinaryOperator::Eq => Ok(left.try_binary_op(&right, OwnedColumn::element_wise_eq)?),
+            BinaryOperator::Gt => Ok(left.try_binary_op(&right, OwnedColumn::element_wise_gt)?),
+            BinaryOperator::Lt => Ok(left.try_binary_op(&right, OwnedColumn::element_wise_lt)?),
+            BinaryOperator::Plus => Ok(left.try_binary_op(&right, OwnedColumn::element_wise_add)?),
+            BinaryOperator::Minus => Ok(left.try_binary_op(&right, OwnedColumn::element_wise_sub)?),
+            BinaryOperator::Multiply => {
+                Ok(left.try_binary_op(&right, OwnedColumn::element_wise_mul)?)
+            }
+            BinaryOperator::Divide => {
+                Ok(left.try_binary_op(&right, OwnedColumn::element_wise_div)?)
+            }
+            BinaryOperator::Modulo => {
+                Ok(left.try_binary_op(&right, OwnedColumn::element_wise_mod)?)
+            }
             _ => Err(ExpressionEvaluationError::Unsupported {
                 expression: format!("Binary operator '{op}' is not supported."),
             }),
         }
     }
 }
+
+/// Converts a timestamp counted in `unit` since the Unix epoch into a `DateTime`
+fn from_timestamp(value: i64, unit: PoSQLTimeUnit) -> Option<DateTime<Utc>> {
+    match unit {
+        PoSQLTimeUnit::Second => DateTime::from_timestamp(value, 0),
+        PoSQLTimeUnit::Millisecond => DateTime::from_timestamp_millis(value),
+        PoSQLTimeUnit::Microsecond => DateTime::from_timestamp_micros(value),
+        PoSQLTimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(value)),
+    }
+}
+
+/// Converts a `DateTime` into a timestamp counted in `unit` since the Unix epoch
+fn to_timestamp(time: &DateTime<FixedOffset>, unit: PoSQLTimeUnit) -> Option<i64> {
+    match unit {
+        PoSQLTimeUnit::Second => Some(time.timestamp()),
+        PoSQLTimeUnit::Millisecond => Some(time.timestamp_millis()),
+        PoSQLTimeUnit::Microsecond => Some(time.timestamp_micros()),
+        PoSQLTimeUnit::Nanosecond => time.timestamp_nanos_opt(),
+    }
+}
+
+/// The value of a field of a local time
+fn extract_field(time: &DateTime<FixedOffset>, field: PoSQLDateTimeField) -> i64 {
+    match field {
+        PoSQLDateTimeField::Year => i64::from(time.year()),
+        PoSQLDateTimeField::Month => i64::from(time.month()),
+        PoSQLDateTimeField::Day => i64::from(time.day()),
+        PoSQLDateTimeField::Hour => i64::from(time.hour()),
+        PoSQLDateTimeField::Minute => i64::from(time.minute()),
+        PoSQLDateTimeField::Second => i64::from(time.second()),
+    }
+}
+
+/// The start of the field a local time lies in
+fn truncate(
+    time: &DateTime<FixedOffset>,
+    field: PoSQLDateTimeField,
+) -> Option<DateTime<FixedOffset>> {
+    let date = match field {
+        PoSQLDateTimeField::Year => NaiveDate::from_ymd_opt(time.year(), 1, 1)?,
+        PoSQLDateTimeField::Month => NaiveDate::from_ymd_opt(time.year(), time.month(), 1)?,
+        _ => time.date_naive(),
+    };
+    let (hour, minute, second) = match field {
+        PoSQLDateTimeField::Year | PoSQLDateTimeField::Month | PoSQLDateTimeField::Day => (0, 0, 0),
+        PoSQLDateTimeField::Hour => (time.hour(), 0, 0),
+        PoSQLDateTimeField::Minute => (time.hour(), time.minute(), 0),
+        PoSQLDateTimeField::Second => (time.hour(), time.minute(), time.second()),
+    };
+    let local: NaiveDateTime = date.and_hms_opt(hour, minute, second)?;
+    time.timezone().from_local_datetime(&local).single()
+}
@@ -1,7 +1,7 @@
 use crate::base::{
     database::{
-        owned_table_utility::*, ColumnOperationError, ExpressionEvaluationError, OwnedColumn,
-        OwnedTable,
+        owned_table_utility::*, ColumnOperationError, ExpressionEvaluationError,
+        NullableOwnedColumn, OwnedColumn, OwnedTable,
     },
     math::decimal::Precision,
     scalar::test_scalar::TestScalar,
@@ -9,7 +9,7 @@ use crate::base::{
 use bigdecimal::BigDecimal;
 use proof_of_sql_parser::{
     intermediate_ast::Literal,
-    posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestamp},
+    posql_time::{PoSQLDateTimeField, PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestamp},
     utility::*,
 };
 
@@ -187,6 +187,12 @@ fn we_can_evaluate_an_arithmetic_expression() {
         .collect();
     let expected_column = OwnedColumn::Decimal75(Precision::new(46).unwrap(), 6, expected_scalars);
     assert_eq!(actual_column, expected_column);
+
+    // Bigints modulo 3 plus smallints
+    let expr = add(modulo(col("bigints"), lit(3)), col("smallints"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![-4, -2, 0, 2, 4]);
+    assert_eq!(actual_column, expected_column);
 }
 
 #[test]
@@ -241,4 +247,191 @@ fn we_cannot_evaluate_expressions_if_column_operation_errors_out() {
             source: ColumnOperationError::DivisionByZero
         })
     ));
+
+    // We can't take a remainder modulo zero either
+    let expr = modulo(col("bigints"), lit(0));
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::ColumnOperationError {
+            source: ColumnOperationError::DivisionByZero
+        })
+    ));
+}
+
+#[test]
+fn we_can_evaluate_in_list_and_between_predicates() {
+    let table: OwnedTable<TestScalar> = owned_table([
+        nullable(bigint("a", [1_i64, 0, 3, 5]), [true, false, true, true]),
+        bigint("b", [1_i64, 2, 3, 4]),
+    ]);
+
+    let expr = in_list(col("b"), vec![lit(2), col("a") + lit(1), lit(7)]);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = between(col("b"), lit(2), lit(3));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, true, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // A NULL candidate only leaves the result unknown if no other candidate matches
+    let expr = in_list(col("b"), vec![col("a"), lit(2)]);
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::Boolean(vec![true, true, true, false]),
+        Some(vec![true, true, true, true]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+
+    // A NULL bound only leaves the result unknown if the other bound holds
+    let expr = between(col("b"), col("a"), lit(3));
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::Boolean(vec![true, false, true, false]),
+        Some(vec![true, false, true, true]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+}
+
+#[test]
+fn we_can_evaluate_like_predicates() {
+    let table: OwnedTable<TestScalar> = owned_table([
+        nullable(
+            varchar("a", ["abc", "", "xbcz", "ab"]),
+            [true, false, true, true],
+        ),
+        bigint("b", [1_i64, 2, 3, 4]),
+    ]);
+
+    let expr = like(col("a"), "_bc%");
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::Boolean(vec![true, false, true, false]),
+        Some(vec![true, false, true, true]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+
+    let expr = not(like(col("a"), "%b%"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = like(col("b"), "1%");
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::ColumnOperationError {
+            source: ColumnOperationError::UnaryOperationInvalidColumnType { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_evaluate_date_time_functions_in_the_time_zone_of_the_timestamps() {
+    let timezone = PoSQLTimeZone::new(-18_000);
+    let table: OwnedTable<TestScalar> = owned_table([
+        nullable(
+            timestamptz(
+                "ts",
+                PoSQLTimeUnit::Millisecond,
+                timezone,
+                [1_709_251_199_500_i64, 0, -1],
+            ),
+            [true, true, false],
+        ),
+        bigint("b", [1_i64, 2, 3]),
+    ]);
+
+    let expr = extract(PoSQLDateTimeField::Day, col("ts"));
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::BigInt(vec![29, 31, 31]),
+        Some(vec![true, true, false]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+
+    // Months and years are truncated and added on the local calendar
+    let expr = date_trunc(PoSQLDateTimeField::Month, col("ts"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::TimestampTZ(
+        PoSQLTimeUnit::Millisecond,
+        timezone,
+        vec![1_706_763_600_000, -2_660_400_000, -2_660_400_000],
+    );
+    assert_eq!(actual_column, expected_column);
+
+    let expr = add_interval(col("ts"), 1, PoSQLDateTimeField::Month);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::TimestampTZ(
+        PoSQLTimeUnit::Millisecond,
+        timezone,
+        vec![1_711_756_799_500, 2_678_400_000, 2_678_399_999],
+    );
+    assert_eq!(actual_column, expected_column);
+
+    let expr = add_interval(col("ts"), -2, PoSQLDateTimeField::Hour);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::TimestampTZ(
+        PoSQLTimeUnit::Millisecond,
+        timezone,
+        vec![1_709_243_999_500, -7_200_000, -7_200_001],
+    );
+    assert_eq!(actual_column, expected_column);
+
+    let expr = extract(PoSQLDateTimeField::Year, col("b"));
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::ColumnOperationError {
+            source: ColumnOperationError::UnaryOperationInvalidColumnType { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_evaluate_expressions_on_nullable_columns() {
+    // NULLs are stored as zeros along with the presence of the column
+    let table: OwnedTable<TestScalar> = owned_table([
+        nullable(bigint("a", [1_i64, 0, 3, 0]), [true, false, true, false]),
+        nullable(bigint("b", [1_i64, 2, 0, 0]), [true, true, false, false]),
+    ]);
+
+    let expr = is_null(col("a"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, false, true]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = is_not_null(col("b"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Dividing by NULL yields NULL rather than a division by zero
+    let expr = div(col("b"), col("a"));
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::BigInt(vec![1, 0, 0, 0]),
+        Some(vec![true, false, false, false]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+
+    // NULL = 1 is NULL, so NULL = 1 OR b = 2 is only true where b = 2
+    let expr = or(equal(col("a"), lit(1)), equal(col("b"), lit(2)));
+    let actual_column = table.evaluate_nullable(&expr).unwrap();
+    let expected_column = NullableOwnedColumn::try_new(
+        OwnedColumn::Boolean(vec![true, true, false, false]),
+        Some(vec![true, true, false, false]),
+    )
+    .unwrap();
+    assert_eq!(actual_column, expected_column);
+
+    // NOT (a = 3) is only true where a is not NULL
+    let expr = not(equal(col("a"), lit(3)));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, false, false, false]);
+    assert_eq!(actual_column, expected_column);
 }
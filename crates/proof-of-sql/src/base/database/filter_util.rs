@@ -82,5 +82,9 @@ pub fn filter_column_by_index<'a, S: Scalar>(
             *tz,
             alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| col[i])),
         ),
+        Column::Nullable(values, presence) => Column::Nullable(
+            alloc.alloc(filter_column_by_index(alloc, values, indexes)),
+            alloc.alloc_slice_fill_iter(indexes.iter().map(|&i| presence[i])),
+        ),
     }
 }
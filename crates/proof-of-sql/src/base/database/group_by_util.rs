@@ -164,6 +164,10 @@ pub(crate) fn sum_aggregate_column_by_index_counts<'a, S: Scalar>(
             sum_aggregate_slice_by_index_counts(alloc, col, counts, indexes)
         }
         Column::Scalar(col) => sum_aggregate_slice_by_index_counts(alloc, col, counts, indexes),
+        // NULLs hold zero and therefore do not change a sum
+        Column::Nullable(values, _) => {
+            sum_aggregate_column_by_index_counts(alloc, values, counts, indexes)
+        }
         // The following should never be reached because the `SUM` function can only be applied to numeric types.
         Column::VarChar(_)
         | Column::TimestampTZ(_, _, _)
@@ -205,6 +209,8 @@ pub(crate) fn max_aggregate_column_by_index_counts<'a, S: Scalar>(
         Column::VarChar(_) | Column::VarBinary(_) => {
             unreachable!("MAX can not be applied to varchar")
         }
+        // NULLs are replaced by the lowest value before their maximum is taken
+        Column::Nullable(..) => unreachable!("MAX can not be applied to nullable columns"),
     }
 }
 
@@ -240,6 +246,8 @@ pub(crate) fn min_aggregate_column_by_index_counts<'a, S: Scalar>(
         Column::VarChar(_) => {
             unreachable!("MIN can not be applied to varchar")
         }
+        // NULLs are replaced by the highest value before their minimum is taken
+        Column::Nullable(..) => unreachable!("MIN can not be applied to nullable columns"),
     }
 }
 
@@ -2,11 +2,11 @@ use super::{
     apply_column_to_indexes,
     order_by_util::{compare_indexes_by_columns, compare_single_row_of_tables},
     union_util::column_union,
-    Column, ColumnOperationResult, ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp, Table,
-    TableOperationError, TableOperationResult, TableOptions,
+    Column, ColumnOperationResult, ColumnRepeatOp, ColumnType, ElementwiseRepeatOp, RepetitionOp,
+    Table, TableOperationError, TableOperationResult, TableOptions,
 };
-use crate::base::scalar::Scalar;
-use alloc::vec::Vec;
+use crate::base::scalar::{Scalar, ScalarExt};
+use alloc::{vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
@@ -51,6 +51,83 @@ pub(crate) fn ordered_set_union<'a, S: Scalar>(
     Ok(result)
 }
 
+/// The most bits that a join key on multiple columns may have, see [`join_key_weights`].
+///
+/// This leaves room for the differences of consecutive keys to be told apart from negative numbers.
+const MAX_JOIN_KEY_BITS: u32 = 248;
+
+/// The number of bits of each column of a join key on multiple columns, see [`join_key_weights`].
+///
+/// Returns `None` if a column is not a boolean, an integer or a timestamp,
+/// or if the key does not fit in [`MAX_JOIN_KEY_BITS`] bits.
+fn join_key_bits(column_types: &[ColumnType]) -> Option<Vec<u32>> {
+    let bits = column_types
+        .iter()
+        .map(|column_type| match column_type {
+            ColumnType::Boolean => Some(1),
+            ColumnType::Uint8
+            | ColumnType::TinyInt
+            | ColumnType::SmallInt
+            | ColumnType::Int
+            | ColumnType::BigInt
+            | ColumnType::Int128
+            | ColumnType::TimestampTZ(_, _) => Some(column_type.bit_size()),
+            _ => None,
+        })
+        .collect::<Option<Vec<u32>>>()?;
+    (bits.iter().sum::<u32>() <= MAX_JOIN_KEY_BITS).then_some(bits)
+}
+
+/// Whether the columns of a join key can be combined into a single column, see [`join_key_weights`].
+pub(crate) fn is_combinable_join_key(column_types: &[ColumnType]) -> bool {
+    column_types.len() == 1 || join_key_bits(column_types).is_some()
+}
+
+/// Get the weights and the offset that combine the columns of a join key into the single column
+/// `offset + sum_i weights[i] * key[i]`, which is strictly increasing if the rows of the key are.
+///
+/// A key on a single column is the column itself. Otherwise, each column is shifted to be nonnegative
+/// and the columns are concatenated as bits, with the first column as the most significant bits.
+/// Returns `None` if the key can not be combined, see [`is_combinable_join_key`].
+pub(crate) fn join_key_weights<S: Scalar>(column_types: &[ColumnType]) -> Option<(Vec<S>, S)> {
+    if let [_] = column_types {
+        return Some((vec![S::ONE], S::ZERO));
+    }
+    let bits = join_key_bits(column_types)?;
+    let power_of_two =
+        |exponent: u32| -> S { itertools::repeat_n(S::TWO, exponent as usize).product() };
+    let mut weight = S::ONE;
+    let mut offset = S::ZERO;
+    let mut weights = Vec::with_capacity(bits.len());
+    for (column_type, &column_bits) in column_types.iter().zip(&bits).rev() {
+        if column_type.is_signed() {
+            offset += weight * power_of_two(column_bits - 1);
+        }
+        weights.push(weight);
+        weight *= power_of_two(column_bits);
+    }
+    weights.reverse();
+    Some((weights, offset))
+}
+
+/// Combines the columns of a join key into a single column
+/// with the weights and the offset given by [`join_key_weights`].
+pub(crate) fn combine_join_key<'a, S: Scalar>(
+    key: &[Column<'a, S>],
+    weights: &[S],
+    offset: S,
+    alloc: &'a Bump,
+) -> &'a [S] {
+    let num_rows = key.first().map_or(0, Column::len);
+    let combined = alloc.alloc_slice_fill_copy(num_rows, offset);
+    for (column, &weight) in key.iter().zip_eq(weights) {
+        for (combined_value, value) in combined.iter_mut().zip(column.to_scalar_with_scaling(0)) {
+            *combined_value += weight * value;
+        }
+    }
+    combined
+}
+
 /// Get multiplicities of rows of `data` in `unique`.
 ///
 /// `data` consists of rows possibly present in `unique` and `unique` has only
@@ -281,12 +358,126 @@ pub fn apply_sort_merge_join_indexes<'a, S: Scalar>(
         .collect::<ColumnOperationResult<Vec<_>>>()
 }
 
+/// Get the scalar of the default value of a column type
+///
+/// The default values are `false`, zero, the empty string and the empty byte string.
+/// They are used to pad the unmatched rows of outer joins.
+pub(crate) fn get_default_scalar<S: Scalar>(column_type: ColumnType) -> S {
+    match column_type {
+        ColumnType::VarChar => S::from(""),
+        ColumnType::VarBinary => S::from_byte_slice_via_hash(&[]),
+        _ => S::ZERO,
+    }
+}
+
+/// Get a column of the given type and length which only contains the default value of the type
+///
+/// See [`get_default_scalar`] for the default values.
+pub(crate) fn get_default_column<S: Scalar>(
+    column_type: ColumnType,
+    length: usize,
+    alloc: &Bump,
+) -> Column<'_, S> {
+    match column_type {
+        ColumnType::Boolean => Column::Boolean(alloc.alloc_slice_fill_copy(length, false)),
+        ColumnType::Uint8 => Column::Uint8(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::TinyInt => Column::TinyInt(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::SmallInt => Column::SmallInt(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::Int => Column::Int(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::BigInt => Column::BigInt(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::Int128 => Column::Int128(alloc.alloc_slice_fill_copy(length, 0)),
+        ColumnType::Scalar => Column::Scalar(alloc.alloc_slice_fill_copy(length, S::ZERO)),
+        ColumnType::Decimal75(precision, scale) => Column::Decimal75(
+            precision,
+            scale,
+            alloc.alloc_slice_fill_copy(length, S::ZERO),
+        ),
+        ColumnType::TimestampTZ(time_unit, timezone) => {
+            Column::TimestampTZ(time_unit, timezone, alloc.alloc_slice_fill_copy(length, 0))
+        }
+        ColumnType::VarChar => Column::VarChar((
+            alloc.alloc_slice_fill_copy(length, ""),
+            alloc.alloc_slice_fill_copy(length, get_default_scalar(column_type)),
+        )),
+        ColumnType::VarBinary => Column::VarBinary((
+            alloc.alloc_slice_fill_copy(length, &[] as &[u8]),
+            alloc.alloc_slice_fill_copy(length, get_default_scalar(column_type)),
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::base::{database::Column, scalar::test_scalar::TestScalar};
+    use crate::base::{
+        database::Column, math::decimal::Precision, scalar::test_scalar::TestScalar,
+    };
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
     use sqlparser::ast::Ident;
 
+    #[test]
+    fn we_can_combine_a_join_key_on_a_single_column_into_itself() {
+        let bump = Bump::new();
+        let (weights, offset) = join_key_weights::<TestScalar>(&[ColumnType::VarChar]).unwrap();
+        assert_eq!(weights, vec![TestScalar::ONE]);
+        assert_eq!(offset, TestScalar::ZERO);
+        let key = [Column::BigInt(&[-3_i64, 2, 5])];
+        assert_eq!(
+            combine_join_key(&key, &weights, offset, &bump),
+            &[-3, 2, 5].map(TestScalar::from)
+        );
+    }
+
+    #[test]
+    fn we_can_combine_a_join_key_on_multiple_columns_in_the_order_of_its_rows() {
+        let bump = Bump::new();
+        let (weights, offset) = join_key_weights::<TestScalar>(&[
+            ColumnType::TinyInt,
+            ColumnType::Boolean,
+            ColumnType::Uint8,
+        ])
+        .unwrap();
+        assert_eq!(
+            weights,
+            vec![
+                TestScalar::from(512),
+                TestScalar::from(256),
+                TestScalar::ONE
+            ]
+        );
+        assert_eq!(offset, TestScalar::from(128 * 512));
+        let key = [
+            Column::TinyInt(&[-128_i8, -1, -1, 0, 0, 127]),
+            Column::Boolean(&[true, false, true, false, false, false]),
+            Column::Uint8(&[255_u8, 7, 0, 0, 1, 0]),
+        ];
+        assert_eq!(
+            combine_join_key(&key, &weights, offset, &bump),
+            &[
+                256 + 255,
+                127 * 512 + 7,
+                127 * 512 + 256,
+                128 * 512,
+                128 * 512 + 1,
+                255 * 512
+            ]
+            .map(TestScalar::from)
+        );
+    }
+
+    #[test]
+    fn we_cannot_combine_a_join_key_on_multiple_columns_that_are_not_integers_or_too_wide() {
+        assert!(
+            join_key_weights::<TestScalar>(&[ColumnType::BigInt, ColumnType::VarChar]).is_none()
+        );
+        assert!(
+            join_key_weights::<TestScalar>(&[ColumnType::Int128, ColumnType::Int128]).is_none()
+        );
+        assert!(
+            join_key_weights::<TestScalar>(&[ColumnType::Int128, ColumnType::BigInt]).is_some()
+        );
+    }
+
     #[test]
     fn we_can_do_cross_joins() {
         let bump = Bump::new();
@@ -944,4 +1135,46 @@ mod tests {
         assert_eq!(result[1], Column::SmallInt(&[0_i16; 0]));
         assert_eq!(result[2], Column::BigInt(&[0_i64; 0]));
     }
+
+    #[test]
+    fn we_can_get_default_columns() {
+        let bump = Bump::new();
+        assert_eq!(
+            get_default_column::<TestScalar>(ColumnType::Boolean, 2, &bump),
+            Column::Boolean(&[false, false])
+        );
+        assert_eq!(
+            get_default_column::<TestScalar>(ColumnType::BigInt, 3, &bump),
+            Column::BigInt(&[0_i64, 0, 0])
+        );
+        assert_eq!(
+            get_default_column::<TestScalar>(ColumnType::VarChar, 2, &bump),
+            Column::VarChar((&["", ""], &[TestScalar::from(""), TestScalar::from("")]))
+        );
+        assert_eq!(
+            get_default_column::<TestScalar>(ColumnType::Int128, 0, &bump),
+            Column::Int128(&[0_i128; 0])
+        );
+        for column_type in [
+            ColumnType::Boolean,
+            ColumnType::Uint8,
+            ColumnType::TinyInt,
+            ColumnType::SmallInt,
+            ColumnType::Int,
+            ColumnType::BigInt,
+            ColumnType::Int128,
+            ColumnType::Scalar,
+            ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            ColumnType::VarChar,
+            ColumnType::VarBinary,
+        ] {
+            let column = get_default_column::<TestScalar>(column_type, 1, &bump);
+            assert_eq!(column.column_type(), column_type);
+            assert_eq!(
+                column.scalar_at(0),
+                Some(get_default_scalar::<TestScalar>(column_type))
+            );
+        }
+    }
 }
@@ -0,0 +1,261 @@
+//! `LIKE` patterns and the character components that make them provable.
+//!
+//! A `VarChar` column is committed to as the hashes of its strings, which can only be compared for equality.
+//! To match it against a pattern, the characters of its strings can be committed to as components of it:
+//! the length of each string in characters, see [`ColumnComponent::Length`],
+//! the code points of its first characters, see [`ColumnComponent::Character`],
+//! and the code points of its last characters, see [`ColumnComponent::ReversedCharacter`].
+//! Positions past the end of a string hold zero.
+//! How many characters of a column are committed to is given by [`SchemaAccessor::character_count`](super::SchemaAccessor::character_count).
+use super::ColumnComponent;
+use alloc::vec::Vec;
+
+/// Returns the number of characters of each of the strings `values`, i.e. their [`ColumnComponent::Length`].
+///
+/// # Panics
+/// Panics if a string has more than `i64::MAX` characters, which can not happen in practice.
+pub(crate) fn string_lengths(values: &[impl AsRef<str>]) -> Vec<i64> {
+    values
+        .iter()
+        .map(|value| {
+            i64::try_from(value.as_ref().chars().count()).expect("string lengths fit in an i64")
+        })
+        .collect()
+}
+
+/// Returns the code points of the character at `index` of each of the strings `values`,
+/// counting from the end if `reversed` is true, with zero for the strings that are too short.
+///
+/// These are the [`ColumnComponent::Character`] and [`ColumnComponent::ReversedCharacter`] components.
+///
+/// # Panics
+/// Panics if a code point does not fit in an `i32`, which can not happen since code points are at most `0x10FFFF`.
+pub(crate) fn code_points(values: &[impl AsRef<str>], index: usize, reversed: bool) -> Vec<i32> {
+    values
+        .iter()
+        .map(|value| {
+            let character = if reversed {
+                value.as_ref().chars().rev().nth(index)
+            } else {
+                value.as_ref().chars().nth(index)
+            };
+            character.map_or(0, |character| {
+                i32::try_from(u32::from(character)).expect("code points fit in an i32")
+            })
+        })
+        .collect()
+}
+
+/// A `LIKE` pattern, where `%` matches any sequence of characters and `_` matches any single character.
+///
+/// The pattern is kept as the segments between its `%` wildcards,
+/// with `None` standing for a `_` wildcard.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LikePattern {
+    segments: Vec<Vec<Option<char>>>,
+}
+
+impl LikePattern {
+    /// Parses a `LIKE` pattern. Every character other than `%` and `_` matches itself.
+    #[must_use]
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            segments: pattern
+                .split('%')
+                .map(|segment| {
+                    segment
+                        .chars()
+                        .map(|character| (character != '_').then_some(character))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the pattern has no `%` wildcard, so that it only matches strings of its own length.
+    #[must_use]
+    pub fn is_exact(&self) -> bool {
+        self.segments.len() == 1
+    }
+
+    /// The number of characters a string needs at least to match the pattern.
+    #[must_use]
+    pub fn min_length(&self) -> usize {
+        self.segments.iter().map(Vec::len).sum()
+    }
+
+    /// Whether the pattern has characters between its first and last `%`,
+    /// which can not be matched with character components.
+    #[must_use]
+    pub fn has_infix(&self) -> bool {
+        self.segments.len() > 2
+            && self.segments[1..self.segments.len() - 1]
+                .iter()
+                .any(|segment| !segment.is_empty())
+    }
+
+    /// The characters of the pattern that can be checked with the character components of a column,
+    /// together with those components.
+    ///
+    /// These are the characters before the first `%` and after the last `%`.
+    #[must_use]
+    pub fn character_constraints(&self) -> Vec<(ColumnComponent, char)> {
+        let prefix = &self.segments[0];
+        let suffix: &[_] = if self.is_exact() {
+            &[]
+        } else {
+            &self.segments[self.segments.len() - 1]
+        };
+        let forward = prefix.iter().enumerate().filter_map(|(index, character)| {
+            character.map(|character| (ColumnComponent::Character(index), character))
+        });
+        let reversed = suffix
+            .iter()
+            .rev()
+            .enumerate()
+            .filter_map(|(index, character)| {
+                character.map(|character| (ColumnComponent::ReversedCharacter(index), character))
+            });
+        forward.chain(reversed).collect()
+    }
+
+    /// The number of first and last characters of a column whose components are needed
+    /// to check the [`Self::character_constraints`] of the pattern.
+    #[must_use]
+    pub fn character_count(&self) -> usize {
+        self.character_constraints()
+            .into_iter()
+            .map(|(component, _)| match component {
+                ColumnComponent::Character(index) | ColumnComponent::ReversedCharacter(index) => {
+                    index + 1
+                }
+                _ => 0,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Whether `value` matches the pattern.
+    #[must_use]
+    pub fn matches(&self, value: &str) -> bool {
+        let value: Vec<char> = value.chars().collect();
+        let segment_matches = |segment: &[Option<char>], start: usize| {
+            segment
+                .iter()
+                .zip(&value[start..start + segment.len()])
+                .all(|(expected, actual)| expected.map_or(true, |expected| expected == *actual))
+        };
+        let Some((prefix, rest)) = self.segments.split_first() else {
+            return false;
+        };
+        let Some((suffix, infixes)) = rest.split_last() else {
+            return value.len() == prefix.len() && segment_matches(prefix, 0);
+        };
+        if value.len() < prefix.len() + suffix.len()
+            || !segment_matches(prefix, 0)
+            || !segment_matches(suffix, value.len() - suffix.len())
+        {
+            return false;
+        }
+        // Matching each infix as early as possible leaves the most room for the following ones
+        let end = value.len() - suffix.len();
+        let mut start = prefix.len();
+        infixes.iter().all(|infix| {
+            let found = (start..=end)
+                .take_while(|&position| position + infix.len() <= end)
+                .find(|&position| segment_matches(infix, position));
+            found
+                .map(|position| start = position + infix.len())
+                .is_some()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn we_can_get_the_character_components_of_strings() {
+        let values = ["ab", "", "xyz"];
+        assert_eq!(string_lengths(&values), vec![2, 0, 3]);
+        assert_eq!(
+            code_points(&values, 0, false),
+            vec!['a' as i32, 0, 'x' as i32]
+        );
+        assert_eq!(
+            code_points(&values, 1, false),
+            vec!['b' as i32, 0, 'y' as i32]
+        );
+        assert_eq!(
+            code_points(&values, 0, true),
+            vec!['b' as i32, 0, 'z' as i32]
+        );
+        assert_eq!(code_points(&values, 2, true), vec![0, 0, 'x' as i32]);
+        assert_eq!(string_lengths(&["café"]), vec![4]);
+        assert_eq!(code_points(&["café"], 0, true), vec!['é' as i32]);
+    }
+
+    #[test]
+    fn we_can_match_strings_against_patterns() {
+        let cases = [
+            ("abc", "abc", true),
+            ("abc", "abcd", false),
+            ("a_c", "abc", true),
+            ("a_c", "ac", false),
+            ("ab%", "ab", true),
+            ("ab%", "abxyz", true),
+            ("ab%", "xab", false),
+            ("%yz", "xyz", true),
+            ("%yz", "yzx", false),
+            ("a%z", "az", true),
+            ("a%z", "a-z", true),
+            ("a%z", "z", false),
+            ("%", "", true),
+            ("%b%", "abc", true),
+            ("%b%", "ac", false),
+            ("a%b%b%c", "abbc", true),
+            ("a%b%b%c", "abc", false),
+            ("%é_", "café", false),
+            ("%é", "café", true),
+        ];
+        for (pattern, value, expected) in cases {
+            assert_eq!(
+                LikePattern::new(pattern).matches(value),
+                expected,
+                "{value} LIKE {pattern}"
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_character_constraints_of_a_pattern() {
+        let pattern = LikePattern::new("a_c%_z");
+        assert!(!pattern.is_exact());
+        assert!(!pattern.has_infix());
+        assert_eq!(pattern.min_length(), 5);
+        assert_eq!(
+            pattern.character_constraints(),
+            vec![
+                (ColumnComponent::Character(0), 'a'),
+                (ColumnComponent::Character(2), 'c'),
+                (ColumnComponent::ReversedCharacter(0), 'z'),
+            ]
+        );
+        assert_eq!(pattern.character_count(), 3);
+
+        let pattern = LikePattern::new("x_");
+        assert!(pattern.is_exact());
+        assert_eq!(pattern.min_length(), 2);
+        assert_eq!(
+            pattern.character_constraints(),
+            vec![(ColumnComponent::Character(0), 'x')]
+        );
+        assert_eq!(pattern.character_count(), 1);
+        assert_eq!(LikePattern::new("%").character_count(), 0);
+
+        assert!(!LikePattern::new("%%a").has_infix());
+        assert!(LikePattern::new("%a%").has_infix());
+    }
+}
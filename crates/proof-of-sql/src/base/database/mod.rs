@@ -2,29 +2,34 @@
 //! accessor traits and the `OwnedTable` type along with some utility functions to convert
 //! between Arrow and `OwnedTable`.
 mod accessor;
-pub use accessor::{CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor};
+pub use accessor::{
+    ChunkedCommitmentAccessor, ChunkedDataAccessor, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, SchemaAccessor,
+};
 
 mod column;
-pub use column::{Column, ColumnField, ColumnRef, ColumnType};
+pub use column::{Column, ColumnComponent, ColumnField, ColumnRef, ColumnType};
 
 #[allow(dead_code)]
 pub(crate) mod slice_operation;
 
 mod slice_decimal_operation;
+pub(crate) use slice_decimal_operation::try_average_decimal_column;
 
 mod column_type_operation;
 pub use column_type_operation::{
-    try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+    try_add_subtract_column_types, try_average_column_type, try_case_column_types,
+    try_divide_column_types, try_modulo_column_types, try_multiply_column_types,
 };
 
 mod column_arithmetic_operation;
-pub(super) use column_arithmetic_operation::{AddOp, ArithmeticOp, DivOp, MulOp, SubOp};
+pub(super) use column_arithmetic_operation::{AddOp, ArithmeticOp, DivOp, ModOp, MulOp, SubOp};
 
 mod column_comparison_operation;
 pub(super) use column_comparison_operation::{ComparisonOp, EqualOp, GreaterThanOp, LessThanOp};
 
 mod column_index_operation;
-pub(super) use column_index_operation::apply_column_to_indexes;
+pub(crate) use column_index_operation::apply_column_to_indexes;
 
 mod column_repetition_operation;
 pub(super) use column_repetition_operation::{ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp};
@@ -65,6 +70,13 @@ pub use owned_column_error::{OwnedColumnError, OwnedColumnResult};
 /// TODO: add docs
 pub(crate) mod owned_column_operation;
 
+mod nullable_column;
+pub use nullable_column::NullableOwnedColumn;
+
+mod like_pattern;
+pub use like_pattern::LikePattern;
+pub(crate) use like_pattern::{code_points, string_lengths};
+
 mod owned_table;
 pub use owned_table::OwnedTable;
 pub(crate) use owned_table::{OwnedTableError, TableCoercionError};
@@ -90,6 +102,11 @@ mod expression_evaluation_error;
 mod expression_evaluation_test;
 pub use expression_evaluation_error::{ExpressionEvaluationError, ExpressionEvaluationResult};
 
+mod row_range_accessor;
+pub use row_range_accessor::RowRangeAccessor;
+#[cfg(all(test, feature = "blitzar"))]
+mod row_range_accessor_test;
+
 mod test_accessor;
 pub use test_accessor::TestAccessor;
 
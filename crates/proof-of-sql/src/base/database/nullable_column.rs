@@ -0,0 +1,621 @@
+//! Columns that may contain NULL values.
+//!
+//! A nullable column is represented by its values together with its presence,
+//! which is `true` for the rows that are not NULL. NULL rows hold the default value of the type,
+//! so that the values of a nullable column can be committed to and proven like any other column.
+//! In a table a nullable column is stored as an [`OwnedColumn::Nullable`],
+//! and its presence is committed to as a component of its own.
+use super::{ColumnOperationError, ColumnOperationResult, ColumnType, OwnedColumn};
+use crate::base::scalar::Scalar;
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+/// A column of values with an optional presence column.
+///
+/// A column without a presence column contains no NULLs.
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct NullableOwnedColumn<S: Scalar> {
+    values: OwnedColumn<S>,
+    presence: Option<Vec<bool>>,
+}
+
+impl<S: Scalar> From<OwnedColumn<S>> for NullableOwnedColumn<S> {
+    fn from(column: OwnedColumn<S>) -> Self {
+        match column {
+            OwnedColumn::Nullable(values, presence) => Self {
+                values: *values,
+                presence: Some(presence),
+            },
+            values => Self {
+                values,
+                presence: None,
+            },
+        }
+    }
+}
+
+impl<S: Scalar> From<NullableOwnedColumn<S>> for OwnedColumn<S> {
+    fn from(column: NullableOwnedColumn<S>) -> Self {
+        match column.presence {
+            Some(presence) => OwnedColumn::Nullable(Box::new(column.values), presence),
+            None => column.values,
+        }
+    }
+}
+
+impl<S: Scalar> NullableOwnedColumn<S> {
+    /// Creates a nullable column from its values and its presence column.
+    ///
+    /// The values of the NULL rows are replaced by the default value of the type.
+    /// If the values are nullable themselves, a row is NULL if either presence says so.
+    pub fn try_new(
+        values: OwnedColumn<S>,
+        presence: Option<Vec<bool>>,
+    ) -> ColumnOperationResult<Self> {
+        let Self {
+            values,
+            presence: values_presence,
+        } = Self::from(values);
+        let presence = match (presence, values_presence) {
+            (Some(presence), Some(values_presence)) if presence.len() == values_presence.len() => {
+                Some(
+                    presence
+                        .iter()
+                        .zip(values_presence)
+                        .map(|(&lhs, rhs)| lhs && rhs)
+                        .collect(),
+                )
+            }
+            (presence, values_presence) => presence.or(values_presence),
+        };
+        match presence {
+            Some(presence) if presence.len() != values.len() => {
+                Err(ColumnOperationError::DifferentColumnLength {
+                    len_a: values.len(),
+                    len_b: presence.len(),
+                })
+            }
+            Some(presence) => Ok(Self {
+                values: replace_nulls(&values, &presence, NullFill::Default),
+                presence: Some(presence),
+            }),
+            None => Ok(Self::from(values)),
+        }
+    }
+
+    /// Returns the values of the column. NULL rows hold the default value of the type.
+    #[must_use]
+    pub fn values(&self) -> &OwnedColumn<S> {
+        &self.values
+    }
+
+    /// Returns the values of the column, dropping the presence column.
+    #[must_use]
+    pub fn into_values(self) -> OwnedColumn<S> {
+        self.values
+    }
+
+    /// Returns the presence column, if any.
+    #[must_use]
+    pub fn presence(&self) -> Option<&[bool]> {
+        self.presence.as_deref()
+    }
+
+    /// Returns the length of the column.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the column is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the type of the column.
+    #[must_use]
+    pub fn column_type(&self) -> ColumnType {
+        self.values.column_type()
+    }
+
+    /// Returns true if the column has a presence column.
+    #[must_use]
+    pub fn is_nullable(&self) -> bool {
+        self.presence.is_some()
+    }
+
+    /// Returns true if the entry at `index` is NULL.
+    #[must_use]
+    pub fn is_null(&self, index: usize) -> bool {
+        self.presence
+            .as_ref()
+            .is_some_and(|presence| !presence[index])
+    }
+
+    /// Returns the number of entries that are not NULL.
+    #[must_use]
+    pub fn non_null_count(&self) -> usize {
+        self.presence.as_ref().map_or(self.len(), |presence| {
+            presence.iter().filter(|&&present| present).count()
+        })
+    }
+
+    /// Returns the entries that are not NULL.
+    ///
+    /// Aggregates skip NULLs, so they are computed on these values.
+    #[must_use]
+    pub fn non_null_values(&self) -> OwnedColumn<S> {
+        match &self.presence {
+            Some(presence) => filter_column(&self.values, presence),
+            None => self.values.clone(),
+        }
+    }
+
+    /// Returns the values of the column with the NULL rows replaced by the lowest value of the type.
+    ///
+    /// The maximum of these values is the maximum of the non-NULL values, if there are any.
+    pub(crate) fn values_with_nulls_as_lowest(&self) -> OwnedColumn<S> {
+        match &self.presence {
+            Some(presence) => replace_nulls(&self.values, presence, NullFill::Lowest),
+            None => self.values.clone(),
+        }
+    }
+
+    /// Returns the values of the column with the NULL rows replaced by the highest value of the type.
+    ///
+    /// The minimum of these values is the minimum of the non-NULL values, if there are any.
+    pub(crate) fn values_with_nulls_as_highest(&self) -> OwnedColumn<S> {
+        match &self.presence {
+            Some(presence) => replace_nulls(&self.values, presence, NullFill::Highest),
+            None => self.values.clone(),
+        }
+    }
+
+    /// Returns the `IS NULL` predicate of the column, which is never NULL itself.
+    #[must_use]
+    pub fn element_wise_is_null(&self) -> Self {
+        Self::from(OwnedColumn::Boolean(self.presence.as_ref().map_or_else(
+            || vec![false; self.len()],
+            |presence| presence.iter().map(|present| !present).collect(),
+        )))
+    }
+
+    /// Returns the `IS NOT NULL` predicate of the column, which is never NULL itself.
+    #[must_use]
+    pub fn element_wise_is_not_null(&self) -> Self {
+        Self::from(OwnedColumn::Boolean(
+            self.presence
+                .clone()
+                .unwrap_or_else(|| vec![true; self.len()]),
+        ))
+    }
+
+    /// Element-wise NOT operation for a column. The negation of NULL is NULL.
+    pub fn element_wise_not(&self) -> ColumnOperationResult<Self> {
+        Self::try_new(self.values.element_wise_not()?, self.presence.clone())
+    }
+
+    /// Element-wise AND for two columns, using three-valued logic.
+    ///
+    /// The result is `false` if either side is `false`, and NULL if it is not `false`
+    /// and either side is NULL.
+    pub fn element_wise_and(&self, rhs: &Self) -> ColumnOperationResult<Self> {
+        let values = self.values.element_wise_and(&rhs.values)?;
+        let presence = self.kleene_presence(rhs, false)?;
+        Self::try_new(values, presence)
+    }
+
+    /// Element-wise OR for two columns, using three-valued logic.
+    ///
+    /// The result is `true` if either side is `true`, and NULL if it is not `true`
+    /// and either side is NULL.
+    pub fn element_wise_or(&self, rhs: &Self) -> ColumnOperationResult<Self> {
+        let values = self.values.element_wise_or(&rhs.values)?;
+        let presence = self.kleene_presence(rhs, true)?;
+        Self::try_new(values, presence)
+    }
+
+    /// Applies a binary operation to the rows where neither side is NULL.
+    ///
+    /// The result is NULL wherever either side is NULL.
+    pub fn try_binary_op(
+        &self,
+        rhs: &Self,
+        op: impl FnOnce(&OwnedColumn<S>, &OwnedColumn<S>) -> ColumnOperationResult<OwnedColumn<S>>,
+    ) -> ColumnOperationResult<Self> {
+        if self.len() != rhs.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: self.len(),
+                len_b: rhs.len(),
+            });
+        }
+        let presence = match (&self.presence, &rhs.presence) {
+            (None, None) => return Ok(Self::from(op(&self.values, &rhs.values)?)),
+            (Some(presence), None) | (None, Some(presence)) => presence.clone(),
+            (Some(lhs_presence), Some(rhs_presence)) => lhs_presence
+                .iter()
+                .zip(rhs_presence)
+                .map(|(&lhs, &rhs)| lhs && rhs)
+                .collect(),
+        };
+        // NULL rows are left out so that they can not cause errors such as a division by zero
+        let values = op(
+            &filter_column(&self.values, &presence),
+            &filter_column(&rhs.values, &presence),
+        )?;
+        Ok(Self {
+            values: expand_column(&values, &presence),
+            presence: Some(presence),
+        })
+    }
+
+    /// Computes the presence column of the three-valued AND (`dominant == false`)
+    /// or OR (`dominant == true`) of two boolean columns.
+    ///
+    /// The result is known wherever both sides are known or either side is known to be `dominant`.
+    fn kleene_presence(
+        &self,
+        rhs: &Self,
+        dominant: bool,
+    ) -> ColumnOperationResult<Option<Vec<bool>>> {
+        if self.presence.is_none() && rhs.presence.is_none() {
+            return Ok(None);
+        }
+        let (OwnedColumn::Boolean(lhs_values), OwnedColumn::Boolean(rhs_values)) =
+            (&self.values, &rhs.values)
+        else {
+            return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+                operator: if dominant { "OR" } else { "AND" }.into(),
+                left_type: self.column_type(),
+                right_type: rhs.column_type(),
+            });
+        };
+        Ok(Some(
+            (0..self.len())
+                .map(|i| {
+                    let lhs_known = !self.is_null(i);
+                    let rhs_known = !rhs.is_null(i);
+                    (lhs_known && (rhs_known || lhs_values[i] == dominant))
+                        || (rhs_known && rhs_values[i] == dominant)
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// The value that NULL rows are replaced with.
+#[derive(Clone, Copy)]
+enum NullFill {
+    Default,
+    Lowest,
+    Highest,
+}
+
+impl NullFill {
+    fn pick<T>(self, default: T, lowest: T, highest: T) -> T {
+        match self {
+            NullFill::Default => default,
+            NullFill::Lowest => lowest,
+            NullFill::Highest => highest,
+        }
+    }
+}
+
+fn replace_slice_nulls<T: Clone>(values: &[T], presence: &[bool], fill: T) -> Vec<T> {
+    values
+        .iter()
+        .zip(presence)
+        .map(|(value, &present)| if present { value.clone() } else { fill.clone() })
+        .collect()
+}
+
+/// Replaces the entries of the NULL rows.
+///
+/// Types without a lowest or highest value, such as strings, use the default value instead.
+fn replace_nulls<S: Scalar>(
+    column: &OwnedColumn<S>,
+    presence: &[bool],
+    fill: NullFill,
+) -> OwnedColumn<S> {
+    match column {
+        OwnedColumn::Boolean(col) => OwnedColumn::Boolean(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(false, false, true),
+        )),
+        OwnedColumn::Uint8(col) => OwnedColumn::Uint8(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, u8::MIN, u8::MAX),
+        )),
+        OwnedColumn::TinyInt(col) => OwnedColumn::TinyInt(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, i8::MIN, i8::MAX),
+        )),
+        OwnedColumn::SmallInt(col) => OwnedColumn::SmallInt(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, i16::MIN, i16::MAX),
+        )),
+        OwnedColumn::Int(col) => OwnedColumn::Int(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, i32::MIN, i32::MAX),
+        )),
+        OwnedColumn::BigInt(col) => OwnedColumn::BigInt(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, i64::MIN, i64::MAX),
+        )),
+        OwnedColumn::Int128(col) => OwnedColumn::Int128(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(0, i128::MIN, i128::MAX),
+        )),
+        OwnedColumn::VarChar(col) => {
+            OwnedColumn::VarChar(replace_slice_nulls(col, presence, String::new()))
+        }
+        OwnedColumn::VarBinary(col) => {
+            OwnedColumn::VarBinary(replace_slice_nulls(col, presence, Vec::new()))
+        }
+        OwnedColumn::Decimal75(precision, scale, col) => OwnedColumn::Decimal75(
+            *precision,
+            *scale,
+            replace_slice_nulls(
+                col,
+                presence,
+                fill.pick(S::ZERO, -S::MAX_SIGNED, S::MAX_SIGNED),
+            ),
+        ),
+        OwnedColumn::Scalar(col) => OwnedColumn::Scalar(replace_slice_nulls(
+            col,
+            presence,
+            fill.pick(S::ZERO, -S::MAX_SIGNED, S::MAX_SIGNED),
+        )),
+        OwnedColumn::TimestampTZ(tu, tz, col) => OwnedColumn::TimestampTZ(
+            *tu,
+            *tz,
+            replace_slice_nulls(col, presence, fill.pick(0, i64::MIN, i64::MAX)),
+        ),
+        OwnedColumn::Nullable(values, _) => replace_nulls(values, presence, fill),
+    }
+}
+
+fn filter_slice<T: Clone>(values: &[T], selection: &[bool]) -> Vec<T> {
+    values
+        .iter()
+        .zip(selection)
+        .filter(|(_, &selected)| selected)
+        .map(|(value, _)| value.clone())
+        .collect()
+}
+
+/// Returns the entries of the selected rows.
+fn filter_column<S: Scalar>(column: &OwnedColumn<S>, selection: &[bool]) -> OwnedColumn<S> {
+    match column {
+        OwnedColumn::Boolean(col) => OwnedColumn::Boolean(filter_slice(col, selection)),
+        OwnedColumn::Uint8(col) => OwnedColumn::Uint8(filter_slice(col, selection)),
+        OwnedColumn::TinyInt(col) => OwnedColumn::TinyInt(filter_slice(col, selection)),
+        OwnedColumn::SmallInt(col) => OwnedColumn::SmallInt(filter_slice(col, selection)),
+        OwnedColumn::Int(col) => OwnedColumn::Int(filter_slice(col, selection)),
+        OwnedColumn::BigInt(col) => OwnedColumn::BigInt(filter_slice(col, selection)),
+        OwnedColumn::Int128(col) => OwnedColumn::Int128(filter_slice(col, selection)),
+        OwnedColumn::VarChar(col) => OwnedColumn::VarChar(filter_slice(col, selection)),
+        OwnedColumn::VarBinary(col) => OwnedColumn::VarBinary(filter_slice(col, selection)),
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            OwnedColumn::Decimal75(*precision, *scale, filter_slice(col, selection))
+        }
+        OwnedColumn::Scalar(col) => OwnedColumn::Scalar(filter_slice(col, selection)),
+        OwnedColumn::TimestampTZ(tu, tz, col) => {
+            OwnedColumn::TimestampTZ(*tu, *tz, filter_slice(col, selection))
+        }
+        OwnedColumn::Nullable(values, presence) => OwnedColumn::Nullable(
+            Box::new(filter_column(values, selection)),
+            filter_slice(presence, selection),
+        ),
+    }
+}
+
+fn expand_slice<T: Clone + Default>(values: &[T], selection: &[bool]) -> Vec<T> {
+    let mut values = values.iter();
+    selection
+        .iter()
+        .map(|&selected| {
+            if selected {
+                values.next().cloned().unwrap_or_default()
+            } else {
+                T::default()
+            }
+        })
+        .collect()
+}
+
+/// Places the entries at the selected rows, which is the inverse of [`filter_column`].
+///
+/// The other rows hold the default value of the type.
+fn expand_column<S: Scalar>(column: &OwnedColumn<S>, selection: &[bool]) -> OwnedColumn<S> {
+    match column {
+        OwnedColumn::Boolean(col) => OwnedColumn::Boolean(expand_slice(col, selection)),
+        OwnedColumn::Uint8(col) => OwnedColumn::Uint8(expand_slice(col, selection)),
+        OwnedColumn::TinyInt(col) => OwnedColumn::TinyInt(expand_slice(col, selection)),
+        OwnedColumn::SmallInt(col) => OwnedColumn::SmallInt(expand_slice(col, selection)),
+        OwnedColumn::Int(col) => OwnedColumn::Int(expand_slice(col, selection)),
+        OwnedColumn::BigInt(col) => OwnedColumn::BigInt(expand_slice(col, selection)),
+        OwnedColumn::Int128(col) => OwnedColumn::Int128(expand_slice(col, selection)),
+        OwnedColumn::VarChar(col) => OwnedColumn::VarChar(expand_slice(col, selection)),
+        OwnedColumn::VarBinary(col) => OwnedColumn::VarBinary(expand_slice(col, selection)),
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            OwnedColumn::Decimal75(*precision, *scale, expand_slice(col, selection))
+        }
+        OwnedColumn::Scalar(col) => OwnedColumn::Scalar(expand_slice(col, selection)),
+        OwnedColumn::TimestampTZ(tu, tz, col) => {
+            OwnedColumn::TimestampTZ(*tu, *tz, expand_slice(col, selection))
+        }
+        OwnedColumn::Nullable(values, presence) => OwnedColumn::Nullable(
+            Box::new(expand_column(values, selection)),
+            expand_slice(presence, selection),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::scalar::test_scalar::TestScalar;
+    use alloc::string::ToString;
+
+    fn nullable_bool(values: &[Option<bool>]) -> NullableOwnedColumn<TestScalar> {
+        NullableOwnedColumn::try_new(
+            OwnedColumn::Boolean(values.iter().map(|v| v.unwrap_or_default()).collect()),
+            Some(values.iter().map(Option::is_some).collect()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn we_can_create_nullable_columns_which_zero_out_null_rows() {
+        let column = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::BigInt(vec![1, 2, 3]),
+            Some(vec![true, false, true]),
+        )
+        .unwrap();
+        assert_eq!(column.values(), &OwnedColumn::BigInt(vec![1, 0, 3]));
+        assert_eq!(column.presence(), Some(&[true, false, true][..]));
+        assert!(column.is_nullable());
+        assert!(column.is_null(1));
+        assert!(!column.is_null(2));
+        assert_eq!(column.non_null_count(), 2);
+        assert_eq!(column.non_null_values(), OwnedColumn::BigInt(vec![1, 3]));
+        assert_eq!(
+            column.values_with_nulls_as_lowest(),
+            OwnedColumn::BigInt(vec![1, i64::MIN, 3])
+        );
+        assert_eq!(
+            column.values_with_nulls_as_highest(),
+            OwnedColumn::BigInt(vec![1, i64::MAX, 3])
+        );
+
+        let column =
+            NullableOwnedColumn::<TestScalar>::from(OwnedColumn::VarChar(vec!["a".to_string()]));
+        assert!(!column.is_nullable());
+        assert!(!column.is_null(0));
+        assert_eq!(column.non_null_count(), 1);
+    }
+
+    #[test]
+    fn we_cannot_create_nullable_columns_with_a_presence_column_of_the_wrong_length() {
+        assert_eq!(
+            NullableOwnedColumn::<TestScalar>::try_new(
+                OwnedColumn::BigInt(vec![1, 2, 3]),
+                Some(vec![true, false]),
+            ),
+            Err(ColumnOperationError::DifferentColumnLength { len_a: 3, len_b: 2 })
+        );
+    }
+
+    #[test]
+    fn we_can_check_nullable_columns_for_nulls() {
+        let column = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::Int(vec![1, 2]),
+            Some(vec![false, true]),
+        )
+        .unwrap();
+        assert_eq!(
+            column.element_wise_is_null(),
+            OwnedColumn::Boolean(vec![true, false]).into()
+        );
+        assert_eq!(
+            column.element_wise_is_not_null(),
+            OwnedColumn::Boolean(vec![false, true]).into()
+        );
+        let column = NullableOwnedColumn::<TestScalar>::from(OwnedColumn::Int(vec![1, 2]));
+        assert_eq!(
+            column.element_wise_is_null(),
+            OwnedColumn::Boolean(vec![false, false]).into()
+        );
+    }
+
+    #[test]
+    fn we_can_use_three_valued_logic_on_nullable_columns() {
+        let t = Some(true);
+        let f = Some(false);
+        let lhs = nullable_bool(&[t, t, t, f, f, f, None, None, None]);
+        let rhs = nullable_bool(&[t, f, None, t, f, None, t, f, None]);
+        assert_eq!(
+            lhs.element_wise_and(&rhs).unwrap(),
+            nullable_bool(&[t, f, None, f, f, f, None, f, None])
+        );
+        assert_eq!(
+            lhs.element_wise_or(&rhs).unwrap(),
+            nullable_bool(&[t, t, t, t, f, None, t, None, None])
+        );
+        assert_eq!(
+            lhs.element_wise_not().unwrap(),
+            nullable_bool(&[f, f, f, t, t, t, None, None, None])
+        );
+    }
+
+    #[test]
+    fn binary_operations_on_nullable_columns_skip_null_rows() {
+        let lhs = NullableOwnedColumn::<TestScalar>::from(OwnedColumn::BigInt(vec![6, 7, 8]));
+        let rhs = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::BigInt(vec![2, 0, 4]),
+            Some(vec![true, false, true]),
+        )
+        .unwrap();
+        let expected = NullableOwnedColumn::try_new(
+            OwnedColumn::BigInt(vec![3, 0, 2]),
+            Some(vec![true, false, true]),
+        )
+        .unwrap();
+        assert_eq!(
+            lhs.try_binary_op(&rhs, OwnedColumn::element_wise_div)
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            lhs.try_binary_op(&lhs, OwnedColumn::element_wise_add)
+                .unwrap(),
+            OwnedColumn::BigInt(vec![12, 14, 16]).into()
+        );
+    }
+
+    #[test]
+    fn we_can_convert_nullable_columns_to_and_from_owned_columns() {
+        let column = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::SmallInt(vec![1, 2]),
+            Some(vec![true, false]),
+        )
+        .unwrap();
+        let owned_column = OwnedColumn::from(column.clone());
+        assert_eq!(
+            owned_column,
+            OwnedColumn::Nullable(
+                Box::new(OwnedColumn::SmallInt(vec![1, 0])),
+                vec![true, false]
+            )
+        );
+        assert_eq!(NullableOwnedColumn::from(owned_column), column);
+        assert_eq!(
+            OwnedColumn::from(NullableOwnedColumn::<TestScalar>::from(OwnedColumn::Int(
+                vec![1]
+            ))),
+            OwnedColumn::Int(vec![1])
+        );
+    }
+
+    #[test]
+    fn we_can_add_a_presence_to_a_nullable_column() {
+        let column = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::Nullable(
+                Box::new(OwnedColumn::Int(vec![1, 2, 3])),
+                vec![true, false, true],
+            ),
+            Some(vec![true, true, false]),
+        )
+        .unwrap();
+        assert_eq!(column.values(), &OwnedColumn::Int(vec![1, 0, 0]));
+        assert_eq!(column.presence(), Some(&[true, false, false][..]));
+    }
+}
@@ -32,6 +32,10 @@ pub(crate) fn compare_indexes_by_columns<S: Scalar>(
             Column::Scalar(col) => col[i].cmp(&col[j]),
             Column::VarChar((col, _)) => col[i].cmp(col[j]),
             Column::VarBinary((col, _)) => col[i].cmp(col[j]),
+            // NULLs come first
+            Column::Nullable(values, presence) => presence[i]
+                .cmp(&presence[j])
+                .then_with(|| compare_indexes_by_columns(core::slice::from_ref(*values), i, j)),
         })
         .find(|&ord| ord != Ordering::Equal)
         .unwrap_or(Ordering::Equal)
@@ -135,21 +139,7 @@ pub(crate) fn compare_indexes_by_owned_columns_with_direction<S: Scalar>(
     order_by_pairs
         .iter()
         .map(|(col, is_asc)| {
-            let ordering = match col {
-                OwnedColumn::Boolean(col) => col[i].cmp(&col[j]),
-                OwnedColumn::Uint8(col) => col[i].cmp(&col[j]),
-                OwnedColumn::TinyInt(col) => col[i].cmp(&col[j]),
-                OwnedColumn::SmallInt(col) => col[i].cmp(&col[j]),
-                OwnedColumn::Int(col) => col[i].cmp(&col[j]),
-                OwnedColumn::BigInt(col) | OwnedColumn::TimestampTZ(_, _, col) => {
-                    col[i].cmp(&col[j])
-                }
-                OwnedColumn::Int128(col) => col[i].cmp(&col[j]),
-                OwnedColumn::Decimal75(_, _, col) => col[i].signed_cmp(&col[j]),
-                OwnedColumn::Scalar(col) => col[i].cmp(&col[j]),
-                OwnedColumn::VarChar(col) => col[i].cmp(&col[j]),
-                OwnedColumn::VarBinary(col) => col[i].cmp(&col[j]),
-            };
+            let ordering = compare_indexes_by_owned_column(col, i, j);
             match is_asc {
                 true => ordering,
                 false => ordering.reverse(),
@@ -158,3 +148,27 @@ pub(crate) fn compare_indexes_by_owned_columns_with_direction<S: Scalar>(
         .find(|&ord| ord != Ordering::Equal)
         .unwrap_or(Ordering::Equal)
 }
+
+/// Compares `col[i]` and `col[j]`, where NULLs come first.
+fn compare_indexes_by_owned_column<S: Scalar>(
+    col: &OwnedColumn<S>,
+    i: usize,
+    j: usize,
+) -> Ordering {
+    match col {
+        OwnedColumn::Boolean(col) => col[i].cmp(&col[j]),
+        OwnedColumn::Uint8(col) => col[i].cmp(&col[j]),
+        OwnedColumn::TinyInt(col) => col[i].cmp(&col[j]),
+        OwnedColumn::SmallInt(col) => col[i].cmp(&col[j]),
+        OwnedColumn::Int(col) => col[i].cmp(&col[j]),
+        OwnedColumn::BigInt(col) | OwnedColumn::TimestampTZ(_, _, col) => col[i].cmp(&col[j]),
+        OwnedColumn::Int128(col) => col[i].cmp(&col[j]),
+        OwnedColumn::Decimal75(_, _, col) => col[i].signed_cmp(&col[j]),
+        OwnedColumn::Scalar(col) => col[i].cmp(&col[j]),
+        OwnedColumn::VarChar(col) => col[i].cmp(&col[j]),
+        OwnedColumn::VarBinary(col) => col[i].cmp(&col[j]),
+        OwnedColumn::Nullable(values, presence) => presence[i]
+            .cmp(&presence[j])
+            .then_with(|| compare_indexes_by_owned_column(values, i, j)),
+    }
+}
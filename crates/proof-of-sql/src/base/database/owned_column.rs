@@ -2,7 +2,10 @@
 /// This is primarily used as an internal result that is used before
 /// converting to the final result in either Arrow format or JSON.
 /// This is the analog of an arrow Array.
-use super::{Column, ColumnCoercionError, ColumnType, OwnedColumnError, OwnedColumnResult};
+use super::{
+    code_points, string_lengths, Column, ColumnCoercionError, ColumnComponent, ColumnType,
+    NullableOwnedColumn, OwnedColumnError, OwnedColumnResult,
+};
 use crate::base::{
     math::{
         decimal::Precision,
@@ -12,6 +15,7 @@ use crate::base::{
     slice_ops::{inner_product_ref_cast, inner_product_with_bytes},
 };
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
@@ -47,6 +51,10 @@ pub enum OwnedColumn<S: Scalar> {
     Scalar(Vec<S>),
     /// Timestamp columns
     TimestampTZ(PoSQLTimeUnit, PoSQLTimeZone, Vec<i64>),
+    /// Nullable columns
+    /// - the first element maps to the values of the column, where NULL rows hold the default value of the type
+    /// - the second element maps to the presence of the column, which is `true` for the rows that are not NULL
+    Nullable(Box<OwnedColumn<S>>, Vec<bool>),
 }
 
 impl<S: Scalar> OwnedColumn<S> {
@@ -67,6 +75,7 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => {
                 inner_product_ref_cast(col, vec)
             }
+            OwnedColumn::Nullable(values, _) => values.inner_product(vec),
         }
     }
 
@@ -84,6 +93,7 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::VarBinary(col) => col.len(),
             OwnedColumn::Int128(col) => col.len(),
             OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => col.len(),
+            OwnedColumn::Nullable(_, presence) => presence.len(),
         }
     }
 
@@ -106,6 +116,10 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::TimestampTZ(tu, tz, col) => {
                 OwnedColumn::TimestampTZ(*tu, *tz, permutation.try_apply(col)?)
             }
+            OwnedColumn::Nullable(values, presence) => OwnedColumn::Nullable(
+                Box::new(values.try_permute(permutation)?),
+                permutation.try_apply(presence)?,
+            ),
         })
     }
 
@@ -129,9 +143,65 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::TimestampTZ(tu, tz, col) => {
                 OwnedColumn::TimestampTZ(*tu, *tz, col[start..end].to_vec())
             }
+            OwnedColumn::Nullable(values, presence) => OwnedColumn::Nullable(
+                Box::new(values.slice(start, end)),
+                presence[start..end].to_vec(),
+            ),
         }
     }
 
+    /// Appends the rows of `other`, which must have the same type, to the column.
+    pub(crate) fn try_append(&mut self, other: Self) -> OwnedColumnResult<()> {
+        match (self, other) {
+            (OwnedColumn::Boolean(col), OwnedColumn::Boolean(other)) => col.extend(other),
+            (OwnedColumn::Uint8(col), OwnedColumn::Uint8(other)) => col.extend(other),
+            (OwnedColumn::TinyInt(col), OwnedColumn::TinyInt(other)) => col.extend(other),
+            (OwnedColumn::SmallInt(col), OwnedColumn::SmallInt(other)) => col.extend(other),
+            (OwnedColumn::Int(col), OwnedColumn::Int(other)) => col.extend(other),
+            (OwnedColumn::BigInt(col), OwnedColumn::BigInt(other)) => col.extend(other),
+            (OwnedColumn::VarChar(col), OwnedColumn::VarChar(other)) => col.extend(other),
+            (OwnedColumn::VarBinary(col), OwnedColumn::VarBinary(other)) => col.extend(other),
+            (OwnedColumn::Int128(col), OwnedColumn::Int128(other)) => col.extend(other),
+            (OwnedColumn::Scalar(col), OwnedColumn::Scalar(other)) => col.extend(other),
+            (
+                OwnedColumn::Decimal75(precision, scale, col),
+                OwnedColumn::Decimal75(other_precision, other_scale, other),
+            ) if *precision == other_precision && *scale == other_scale => col.extend(other),
+            (
+                OwnedColumn::TimestampTZ(tu, tz, col),
+                OwnedColumn::TimestampTZ(other_tu, other_tz, other),
+            ) if *tu == other_tu && *tz == other_tz => col.extend(other),
+            (OwnedColumn::Nullable(values, presence), other) => {
+                let other_len = other.len();
+                match other {
+                    OwnedColumn::Nullable(other_values, other_presence) => {
+                        values.try_append(*other_values)?;
+                        presence.extend(other_presence);
+                    }
+                    other => {
+                        values.try_append(other)?;
+                        presence.extend(core::iter::repeat(true).take(other_len));
+                    }
+                }
+            }
+            (column, OwnedColumn::Nullable(other_values, other_presence)) => {
+                let len = column.len();
+                let mut values = core::mem::replace(column, OwnedColumn::Boolean(Vec::new()));
+                values.try_append(*other_values)?;
+                let presence = core::iter::repeat(true)
+                    .take(len)
+                    .chain(other_presence)
+                    .collect();
+                *column = OwnedColumn::Nullable(Box::new(values), presence);
+            }
+            (column, other) => Err(OwnedColumnError::TypeCastError {
+                from_type: other.column_type(),
+                to_type: column.column_type(),
+            })?,
+        }
+        Ok(())
+    }
+
     /// Returns true if the column is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -146,6 +216,7 @@ impl<S: Scalar> OwnedColumn<S> {
             OwnedColumn::VarBinary(col) => col.is_empty(),
             OwnedColumn::Int128(col) => col.is_empty(),
             OwnedColumn::Scalar(col) | OwnedColumn::Decimal75(_, _, col) => col.is_empty(),
+            OwnedColumn::Nullable(_, presence) => presence.is_empty(),
         }
     }
     /// Returns the type of the column.
@@ -166,6 +237,66 @@ impl<S: Scalar> OwnedColumn<S> {
                 ColumnType::Decimal75(*precision, *scale)
             }
             OwnedColumn::TimestampTZ(tu, tz, _) => ColumnType::TimestampTZ(*tu, *tz),
+            OwnedColumn::Nullable(values, _) => values.column_type(),
+        }
+    }
+
+    /// Creates a nullable column from its values and its presence,
+    /// which is `true` for the rows that are not NULL.
+    ///
+    /// The values of the NULL rows are replaced by the default value of the type,
+    /// and a column without a presence is returned as is.
+    pub fn try_new_nullable(self, presence: Option<Vec<bool>>) -> OwnedColumnResult<Self> {
+        NullableOwnedColumn::try_new(self, presence)
+            .map(OwnedColumn::from)
+            .map_err(|_| OwnedColumnError::Unsupported {
+                error: "the presence of a column must have the length of its values".to_string(),
+            })
+    }
+
+    /// Returns the values of the column, which for a nullable column hold the default value of the type in its NULL rows.
+    #[must_use]
+    pub fn values(&self) -> &Self {
+        match self {
+            OwnedColumn::Nullable(values, _) => values,
+            _ => self,
+        }
+    }
+
+    /// Returns the presence of the column if it is nullable, which is `true` for the rows that are not NULL.
+    #[must_use]
+    pub fn presence(&self) -> Option<&[bool]> {
+        match self {
+            OwnedColumn::Nullable(_, presence) => Some(presence),
+            _ => None,
+        }
+    }
+
+    /// Returns a copy of the given component of the column.
+    ///
+    /// The character components of a `VarChar` column are computed from its strings.
+    ///
+    /// # Panics
+    /// Panics if the presence of a column that is not nullable,
+    /// or a character component of a column that is not `VarChar`, is requested.
+    #[must_use]
+    pub fn component(&self, component: ColumnComponent) -> Self {
+        let strings = || match self.values() {
+            OwnedColumn::VarChar(strings) => strings,
+            _ => panic!("Column is not VarChar"),
+        };
+        match component {
+            ColumnComponent::Values => self.values().clone(),
+            ColumnComponent::Presence => {
+                OwnedColumn::Boolean(self.presence().expect("Column is not nullable").to_vec())
+            }
+            ColumnComponent::Length => OwnedColumn::BigInt(string_lengths(strings())),
+            ColumnComponent::Character(index) => {
+                OwnedColumn::Int(code_points(strings(), index, false))
+            }
+            ColumnComponent::ReversedCharacter(index) => {
+                OwnedColumn::Int(code_points(strings(), index, true))
+            }
         }
     }
 
@@ -262,14 +393,19 @@ impl<S: Scalar> OwnedColumn<S> {
         option_scalars: &[Option<S>],
         column_type: ColumnType,
     ) -> OwnedColumnResult<Self> {
-        let scalars = option_scalars
-            .iter()
-            .copied()
-            .collect::<Option<Vec<_>>>()
-            .ok_or(OwnedColumnError::Unsupported {
-                error: "NULL is not supported yet".to_string(),
-            })?;
-        Self::try_from_scalars(&scalars, column_type)
+        let scalars = option_scalars.iter().copied().collect::<Option<Vec<_>>>();
+        match scalars {
+            Some(scalars) => Self::try_from_scalars(&scalars, column_type),
+            // NULLs hold the default value of the type, which is zero
+            None => Self::try_from_scalars(
+                &option_scalars
+                    .iter()
+                    .map(|scalar| scalar.unwrap_or(S::ZERO))
+                    .collect::<Vec<_>>(),
+                column_type,
+            )?
+            .try_new_nullable(Some(option_scalars.iter().map(Option::is_some).collect())),
+        }
     }
     #[cfg(test)]
     /// Returns an iterator over the raw data of the column
@@ -375,6 +511,9 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for OwnedColumn<S> {
             }
             Column::Scalar(col) => OwnedColumn::Scalar(col.to_vec()),
             Column::TimestampTZ(tu, tz, col) => OwnedColumn::TimestampTZ(*tu, *tz, col.to_vec()),
+            Column::Nullable(values, presence) => {
+                OwnedColumn::Nullable(Box::new((*values).into()), presence.to_vec())
+            }
         }
     }
 }
@@ -406,6 +545,11 @@ impl<S: Scalar> OwnedColumn<S> {
     ) -> Result<Self, ColumnCoercionError> {
         if self.column_type() == to_type {
             Ok(self)
+        } else if let OwnedColumn::Nullable(values, presence) = self {
+            Ok(OwnedColumn::Nullable(
+                Box::new(values.try_coerce_scalar_to_numeric(to_type)?),
+                presence,
+            ))
         } else if let OwnedColumn::Scalar(vec) = self {
             match to_type {
                 ColumnType::Uint8 => vec
@@ -471,6 +615,32 @@ mod test {
         assert_eq!(col.slice(1, 4), OwnedColumn::Int128(vec![2, 3, 4]));
     }
 
+    #[test]
+    fn we_can_append_a_column_of_the_same_type() {
+        let mut col: OwnedColumn<TestScalar> = OwnedColumn::Int128(vec![1, 2, 3]);
+        col.try_append(OwnedColumn::Int128(vec![4, 5])).unwrap();
+        assert_eq!(col, OwnedColumn::Int128(vec![1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn we_cannot_append_a_column_of_a_different_type() {
+        let mut col: OwnedColumn<TestScalar> = OwnedColumn::Int128(vec![1, 2, 3]);
+        assert!(matches!(
+            col.try_append(OwnedColumn::BigInt(vec![4, 5])),
+            Err(OwnedColumnError::TypeCastError { .. })
+        ));
+        let mut col: OwnedColumn<TestScalar> =
+            OwnedColumn::Decimal75(Precision::new(10).unwrap(), 2, vec![TestScalar::ONE]);
+        assert!(matches!(
+            col.try_append(OwnedColumn::Decimal75(
+                Precision::new(10).unwrap(),
+                3,
+                vec![TestScalar::ONE]
+            )),
+            Err(OwnedColumnError::TypeCastError { .. })
+        ));
+    }
+
     #[test]
     fn we_can_permute_a_column() {
         let col: OwnedColumn<TestScalar> = OwnedColumn::Int128(vec![1, 2, 3, 4, 5]);
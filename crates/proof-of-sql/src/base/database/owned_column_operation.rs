@@ -1,9 +1,10 @@
 use super::{
     AddOp, ArithmeticOp, ColumnOperationError, ColumnOperationResult, ComparisonOp, DivOp, EqualOp,
-    GreaterThanOp, LessThanOp, MulOp, SubOp,
+    GreaterThanOp, LessThanOp, ModOp, MulOp, SubOp,
 };
 use crate::base::{
     database::{
+        slice_decimal_operation::try_average_decimal_column,
         slice_operation::{slice_and, slice_not, slice_or},
         OwnedColumn,
     },
@@ -93,6 +94,40 @@ impl<S: Scalar> OwnedColumn<S> {
     pub fn element_wise_div(&self, rhs: &OwnedColumn<S>) -> ColumnOperationResult<OwnedColumn<S>> {
         DivOp::owned_column_element_wise_arithmetic(self, rhs)
     }
+
+    /// Element-wise modulo for two columns
+    pub fn element_wise_mod(&self, rhs: &OwnedColumn<S>) -> ColumnOperationResult<OwnedColumn<S>> {
+        ModOp::owned_column_element_wise_arithmetic(self, rhs)
+    }
+
+    /// Element-wise average of a column of sums given the number of values each sum was taken over
+    ///
+    /// The result is a decimal whose type is given by [`try_average_column_type`](super::try_average_column_type).
+    pub fn try_average(&self, counts: &[i64]) -> ColumnOperationResult<OwnedColumn<S>> {
+        if self.len() != counts.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: self.len(),
+                len_b: counts.len(),
+            });
+        }
+        let column_type = self.column_type();
+        let (precision, scale, values) = match self {
+            OwnedColumn::Uint8(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::TinyInt(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::SmallInt(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::Int(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::BigInt(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::Int128(sums) => try_average_decimal_column(sums, counts, column_type),
+            OwnedColumn::Decimal75(_, _, sums) => {
+                try_average_decimal_column(sums, counts, column_type)
+            }
+            _ => Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+                operator: "AVG".to_string(),
+                operand_type: column_type,
+            }),
+        }?;
+        Ok(OwnedColumn::Decimal75(precision, scale, values))
+    }
 }
 
 #[cfg(test)]
@@ -820,6 +855,84 @@ mod test {
         );
     }
 
+    #[test]
+    fn we_can_modulo_integer_columns() {
+        let lhs = OwnedColumn::<TestScalar>::TinyInt(vec![4_i8, 5, -7]);
+        let rhs = OwnedColumn::<TestScalar>::TinyInt(vec![3_i8, -2, 3]);
+        let result = lhs.element_wise_mod(&rhs).unwrap();
+        assert_eq!(
+            result,
+            OwnedColumn::<TestScalar>::TinyInt(vec![1_i8, 1, -1])
+        );
+
+        let lhs = OwnedColumn::<TestScalar>::Int(vec![3_i32, 2, 13]);
+        let rhs = OwnedColumn::<TestScalar>::Int128(vec![1_i128, 3, 5]);
+        let result = lhs.element_wise_mod(&rhs).unwrap();
+        assert_eq!(
+            result,
+            OwnedColumn::<TestScalar>::Int128(vec![0_i128, 2, 3])
+        );
+
+        let rhs = OwnedColumn::<TestScalar>::Int128(vec![1_i128, 0, 5]);
+        assert!(matches!(
+            lhs.element_wise_mod(&rhs),
+            Err(ColumnOperationError::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn we_cannot_modulo_decimal_columns() {
+        let lhs_scalars = [4, 5, 3].iter().map(TestScalar::from).collect();
+        let lhs = OwnedColumn::<TestScalar>::Decimal75(Precision::new(5).unwrap(), 2, lhs_scalars);
+        let rhs = OwnedColumn::<TestScalar>::Int(vec![1_i32, 2, 3]);
+        assert!(matches!(
+            lhs.element_wise_mod(&rhs),
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_try_average_columns_of_sums() {
+        let sums = OwnedColumn::<TestScalar>::BigInt(vec![7, -7, 9]);
+        let result = sums.try_average(&[2, 3, 3]).unwrap();
+        let expected_scalars = [3_500_000, -2_333_333, 3_000_000]
+            .iter()
+            .map(TestScalar::from)
+            .collect();
+        assert_eq!(
+            result,
+            OwnedColumn::<TestScalar>::Decimal75(Precision::new(25).unwrap(), 6, expected_scalars)
+        );
+
+        let sum_scalars = [15, 1].iter().map(TestScalar::from).collect();
+        let sums =
+            OwnedColumn::<TestScalar>::Decimal75(Precision::new(10).unwrap(), 2, sum_scalars);
+        let result = sums.try_average(&[4, 3]).unwrap();
+        let expected_scalars = [37_500, 3_333].iter().map(TestScalar::from).collect();
+        assert_eq!(
+            result,
+            OwnedColumn::<TestScalar>::Decimal75(Precision::new(14).unwrap(), 6, expected_scalars)
+        );
+    }
+
+    #[test]
+    fn we_cannot_try_average_invalid_columns_of_sums() {
+        let sums = OwnedColumn::<TestScalar>::BigInt(vec![1, 2]);
+        assert!(matches!(
+            sums.try_average(&[1]),
+            Err(ColumnOperationError::DifferentColumnLength { .. })
+        ));
+        assert!(matches!(
+            sums.try_average(&[1, 0]),
+            Err(ColumnOperationError::DivisionByZero)
+        ));
+        let sums = OwnedColumn::<TestScalar>::VarChar(vec!["a".to_string()]);
+        assert!(matches!(
+            sums.try_average(&[1]),
+            Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+        ));
+    }
+
     #[test]
     fn we_can_try_divide_decimal_columns() {
         // lhs and rhs are both decimals
@@ -1,7 +1,10 @@
 use super::{ColumnField, OwnedColumn, Table};
 use crate::base::{
-    database::ColumnCoercionError, map::IndexMap, polynomial::compute_evaluation_vector,
+    database::{ColumnCoercionError, ColumnComponent},
+    map::IndexMap,
+    polynomial::compute_evaluation_vector,
     scalar::Scalar,
+    slice_ops::inner_product_ref_cast,
 };
 use alloc::{vec, vec::Vec};
 use itertools::{EitherOrBoth, Itertools};
@@ -79,9 +82,14 @@ impl<S: Scalar> OwnedTable<S> {
         self,
         fields: T,
     ) -> Result<Self, TableCoercionError> {
+        // The presence of a nullable column is part of the column
         self.into_inner()
             .into_iter()
-            .zip_longest(fields)
+            .zip_longest(
+                fields
+                    .into_iter()
+                    .filter(|field| field.component() == ColumnComponent::Values),
+            )
             .map(|p| match p {
                 EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => {
                     Err(TableCoercionError::ColumnCountMismatch)
@@ -139,9 +147,16 @@ impl<S: Scalar> OwnedTable<S> {
     pub(crate) fn mle_evaluations(&self, evaluation_point: &[S]) -> Vec<S> {
         let mut evaluation_vector = vec![S::ZERO; self.num_rows()];
         compute_evaluation_vector(&mut evaluation_vector, evaluation_point);
+        // The presence of a nullable column is evaluated after its values
         self.table
             .values()
-            .map(|column| column.inner_product(&evaluation_vector))
+            .flat_map(|column| {
+                let presence_evaluation = column
+                    .presence()
+                    .map(|presence| inner_product_ref_cast(presence, &evaluation_vector));
+                core::iter::once(column.inner_product(&evaluation_vector))
+                    .chain(presence_evaluation)
+            })
             .collect()
     }
 }
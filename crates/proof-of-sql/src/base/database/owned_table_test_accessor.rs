@@ -1,6 +1,7 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    ChunkedCommitmentAccessor, ChunkedDataAccessor, Column, ColumnComponent, ColumnRef, ColumnType,
+    CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor,
+    TableRef, TestAccessor,
 };
 use crate::base::{
     commitment::{CommitmentEvaluationProof, VecCommitmentExt},
@@ -9,11 +10,13 @@ use crate::base::{
 };
 use alloc::{string::String, vec::Vec};
 use bumpalo::Bump;
+use core::ops::Range;
 use sqlparser::ast::Ident;
 /// A test accessor that uses [`OwnedTable`] as the underlying table type.
 /// Note: this is intended for testing and examples. It is not optimized for performance, so should not be used for benchmarks or production use-cases.
 pub struct OwnedTableTestAccessor<'a, CP: CommitmentEvaluationProof> {
     tables: IndexMap<TableRef, (OwnedTable<CP::Scalar>, usize)>,
+    character_counts: IndexMap<(TableRef, Ident), usize>,
     alloc: Bump,
     setup: Option<CP::ProverPublicSetup<'a>>,
 }
@@ -22,6 +25,7 @@ impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<'_, CP> {
     fn default() -> Self {
         Self {
             tables: IndexMap::default(),
+            character_counts: IndexMap::default(),
             alloc: Bump::new(),
             setup: None,
         }
@@ -32,6 +36,7 @@ impl<CP: CommitmentEvaluationProof> Clone for OwnedTableTestAccessor<'_, CP> {
     fn clone(&self) -> Self {
         Self {
             tables: self.tables.clone(),
+            character_counts: self.character_counts.clone(),
             setup: self.setup,
             ..Default::default()
         }
@@ -82,15 +87,29 @@ impl<CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment>
 /// indicating that an invalid column reference was provided.
 impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestAccessor<'_, CP> {
     fn get_column(&self, column: ColumnRef) -> Column<CP::Scalar> {
-        match self
+        let owned_column = self
             .tables
             .get(&column.table_ref())
             .unwrap()
             .0
             .inner_table()
             .get(&column.column_id())
-            .unwrap()
-        {
+            .unwrap();
+        let borrowed_column = self.column_from_owned(owned_column);
+        match column.component() {
+            ColumnComponent::Values => borrowed_column,
+            component => borrowed_column.component(&self.alloc, component),
+        }
+    }
+}
+
+impl<CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'_, CP> {
+    /// Borrows an owned column of the accessor as a column
+    fn column_from_owned<'b>(
+        &'b self,
+        owned_column: &'b OwnedColumn<CP::Scalar>,
+    ) -> Column<'b, CP::Scalar> {
+        match owned_column {
             OwnedColumn::Boolean(col) => Column::Boolean(col),
             OwnedColumn::TinyInt(col) => Column::TinyInt(col),
             OwnedColumn::Uint8(col) => Column::Uint8(col),
@@ -127,6 +146,9 @@ impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestA
                 Column::VarBinary((col_as_slices, scals))
             }
             OwnedColumn::TimestampTZ(tu, tz, col) => Column::TimestampTZ(*tu, *tz, col),
+            OwnedColumn::Nullable(values, presence) => {
+                Column::Nullable(self.alloc.alloc(self.column_from_owned(values)), presence)
+            }
         }
     }
 }
@@ -140,15 +162,70 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
 {
     fn get_commitment(&self, column: ColumnRef) -> CP::Commitment {
         let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
-        let owned_column = table.inner_table().get(&column.column_id()).unwrap();
+        let owned_column = table
+            .inner_table()
+            .get(&column.column_id())
+            .unwrap()
+            .component(column.component());
         Vec::<CP::Commitment>::from_columns_with_offset(
-            [owned_column],
+            [&owned_column],
             *offset,
             self.setup.as_ref().unwrap(),
         )[0]
         .clone()
     }
 }
+///
+/// # Panics
+///
+/// Will panic if the `column.table_ref()` is not found in `self.tables`, or if
+/// the `column.column_id()` is not found in the inner table for that reference,
+/// indicating that an invalid column reference was provided.
+impl<CP: CommitmentEvaluationProof> ChunkedDataAccessor<CP::Scalar>
+    for OwnedTableTestAccessor<'_, CP>
+{
+    fn get_column_chunk(&self, column: ColumnRef, range: Range<usize>) -> OwnedColumn<CP::Scalar> {
+        let owned_column = self
+            .tables
+            .get(&column.table_ref())
+            .unwrap()
+            .0
+            .inner_table()
+            .get(&column.column_id())
+            .unwrap()
+            .slice(range.start, range.end);
+        match column.component() {
+            ColumnComponent::Values => owned_column,
+            component => owned_column.component(component),
+        }
+    }
+}
+
+///
+/// # Panics
+///
+/// Will panic if the `column.table_ref()` is not found in `self.tables`, or if
+/// the `column.column_id()` is not found in the inner table for that reference,
+/// indicating that an invalid column reference was provided.
+impl<CP: CommitmentEvaluationProof> ChunkedCommitmentAccessor<CP::Commitment>
+    for OwnedTableTestAccessor<'_, CP>
+{
+    fn get_chunk_commitment(&self, column: ColumnRef, range: Range<usize>) -> CP::Commitment {
+        let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
+        let owned_column = table
+            .inner_table()
+            .get(&column.column_id())
+            .unwrap()
+            .component(column.component())
+            .slice(range.start, range.end);
+        Vec::<CP::Commitment>::from_columns_with_offset(
+            [&owned_column],
+            offset + range.start,
+            self.setup.as_ref().unwrap(),
+        )[0]
+        .clone()
+    }
+}
 impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<'_, CP> {
     ///
     /// # Panics
@@ -190,6 +267,20 @@ impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<'_
             .map(|(id, col)| (id.clone(), col.column_type()))
             .collect()
     }
+
+    fn is_nullable(&self, table_ref: TableRef, column_id: Ident) -> bool {
+        self.tables
+            .get(&table_ref)
+            .and_then(|(table, _)| table.inner_table().get(&column_id))
+            .is_some_and(|column| column.presence().is_some())
+    }
+
+    fn character_count(&self, table_ref: TableRef, column_id: Ident) -> usize {
+        self.character_counts
+            .get(&(table_ref, column_id))
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 impl<'a, CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'a, CP> {
@@ -211,4 +302,10 @@ impl<'a, CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'a, CP> {
         res.add_table(table_ref, owned_table, offset);
         res
     }
+
+    /// Commits to the first and last `count` characters of the `VarChar` column `column_id` of the table `table_ref`,
+    /// so that `LIKE` can be proven on it.
+    pub fn set_character_count(&mut self, table_ref: TableRef, column_id: Ident, count: usize) {
+        self.character_counts.insert((table_ref, column_id), count);
+    }
 }
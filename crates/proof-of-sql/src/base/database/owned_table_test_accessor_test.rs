@@ -1,6 +1,7 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
-    OwnedTableTestAccessor, SchemaAccessor, TestAccessor,
+    ChunkedCommitmentAccessor, ChunkedDataAccessor, Column, ColumnRef, ColumnType,
+    CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn, OwnedTableTestAccessor,
+    SchemaAccessor, TestAccessor,
 };
 use crate::base::{
     commitment::{
@@ -167,6 +168,48 @@ fn we_can_access_the_commitments_of_table_columns() {
     );
 }
 
+#[test]
+fn we_can_access_chunks_of_table_columns_and_their_commitments() {
+    let table_ref = TableRef::new("sxt", "test");
+    let accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_from_table(
+        table_ref.clone(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            varchar("b", ["a", "b", "c", "d", "e"]),
+        ]),
+        3_usize,
+        (),
+    );
+
+    let column = ColumnRef::new(table_ref.clone(), "a".into(), ColumnType::BigInt);
+    assert_eq!(
+        accessor.get_column_chunk(column.clone(), 1..4),
+        OwnedColumn::BigInt(vec![2, 3, 4])
+    );
+    assert_eq!(
+        accessor.get_chunk_commitment(column.clone(), 1..4),
+        NaiveCommitment::compute_commitments(
+            &[CommittableColumn::from(&[2i64, 3, 4][..])],
+            4_usize,
+            &()
+        )[0]
+    );
+    assert_eq!(
+        accessor.get_chunk_commitment(column.clone(), 0..5),
+        accessor.get_commitment(column)
+    );
+
+    let column = ColumnRef::new(table_ref, "b".into(), ColumnType::VarChar);
+    assert_eq!(
+        accessor.get_column_chunk(column.clone(), 3..5),
+        OwnedColumn::VarChar(vec!["d".into(), "e".into()])
+    );
+    assert_eq!(
+        accessor.get_column_chunk(column, 5..5),
+        OwnedColumn::VarChar(vec![])
+    );
+}
+
 #[test]
 fn we_can_access_the_type_of_table_columns() {
     let mut accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
@@ -296,3 +296,27 @@ pub fn timestamptz<S: Scalar>(
         OwnedColumn::TimestampTZ(time_unit, timezone, data.into_iter().collect()),
     )
 }
+
+/// Makes a `(Ident, OwnedColumn)` pair nullable, where `presence` is `true` for the rows that are not NULL.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     nullable(bigint("a", [1, 0, 3]), [true, false, true]),
+/// ]);
+/// ```
+///
+/// # Panics
+/// - Panics if `presence` does not have the length of the column.
+pub fn nullable<S: Scalar>(
+    (name, column): (Ident, OwnedColumn<S>),
+    presence: impl IntoIterator<Item = bool>,
+) -> (Ident, OwnedColumn<S>) {
+    (
+        name,
+        column
+            .try_new_nullable(Some(presence.into_iter().collect()))
+            .unwrap(),
+    )
+}
@@ -0,0 +1,108 @@
+use super::{
+    ChunkedCommitmentAccessor, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, SchemaAccessor, TableRef,
+};
+use crate::base::{commitment::Commitment, map::IndexMap, scalar::Scalar};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use core::ops::Range;
+use sqlparser::ast::Ident;
+
+/// An accessor that restricts the data spans of some tables of another accessor to a range of rows.
+///
+/// This allows a query to be proven and verified against only the rows it targets, e.g. the
+/// committed partitions of an append-only table that can contain the rows that a
+/// `WHERE block_number BETWEEN x AND y` clause selects. Tables without a range are not restricted.
+///
+/// Note: ranges are in absolute row numbers, i.e. in the same units as
+/// [`MetadataAccessor::get_offset`], and must lie within the data spans of the inner accessor.
+pub struct RowRangeAccessor<'a, A> {
+    accessor: &'a A,
+    ranges: IndexMap<TableRef, Range<usize>>,
+    alloc: Bump,
+}
+
+impl<'a, A: MetadataAccessor> RowRangeAccessor<'a, A> {
+    /// Create a new [`RowRangeAccessor`] restricting each table to its range of rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range does not lie within the data span of its table.
+    pub fn new(
+        accessor: &'a A,
+        ranges: impl IntoIterator<Item = (TableRef, Range<usize>)>,
+    ) -> Self {
+        let ranges: IndexMap<_, _> = ranges.into_iter().collect();
+        for (table_ref, range) in &ranges {
+            let offset = accessor.get_offset(table_ref);
+            let length = accessor.get_length(table_ref);
+            assert!(
+                offset <= range.start && range.start <= range.end && range.end <= offset + length,
+                "rows {range:?} are not within the data span of {table_ref}"
+            );
+        }
+        Self {
+            accessor,
+            ranges,
+            alloc: Bump::new(),
+        }
+    }
+
+    /// Returns the rows of the table's data span in the inner accessor that are accessed,
+    /// relative to the start of that data span.
+    fn relative_range(&self, table_ref: &TableRef) -> Range<usize> {
+        match self.ranges.get(table_ref) {
+            Some(range) => {
+                let offset = self.accessor.get_offset(table_ref);
+                range.start - offset..range.end - offset
+            }
+            None => 0..self.accessor.get_length(table_ref),
+        }
+    }
+}
+
+impl<A: MetadataAccessor> MetadataAccessor for RowRangeAccessor<'_, A> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.relative_range(table_ref).len()
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.accessor.get_offset(table_ref) + self.relative_range(table_ref).start
+    }
+}
+
+impl<S: Scalar, A: DataAccessor<S>> DataAccessor<S> for RowRangeAccessor<'_, A> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        let range = self.relative_range(&column.table_ref());
+        self.accessor
+            .get_column(column)
+            .slice(range.start, range.end, &self.alloc)
+    }
+}
+
+impl<C: Commitment, A: ChunkedCommitmentAccessor<C>> CommitmentAccessor<C>
+    for RowRangeAccessor<'_, A>
+{
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        let range = self.relative_range(&column.table_ref());
+        self.accessor.get_chunk_commitment(column, range)
+    }
+}
+
+impl<A: SchemaAccessor> SchemaAccessor for RowRangeAccessor<'_, A> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Ident) -> Option<ColumnType> {
+        self.accessor.lookup_column(table_ref, column_id)
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Ident, ColumnType)> {
+        self.accessor.lookup_schema(table_ref)
+    }
+
+    fn is_nullable(&self, table_ref: TableRef, column_id: Ident) -> bool {
+        self.accessor.is_nullable(table_ref, column_id)
+    }
+
+    fn character_count(&self, table_ref: TableRef, column_id: Ident) -> usize {
+        self.accessor.character_count(table_ref, column_id)
+    }
+}
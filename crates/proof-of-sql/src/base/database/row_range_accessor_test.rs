@@ -0,0 +1,102 @@
+use super::{
+    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
+    OwnedTableTestAccessor, RowRangeAccessor, SchemaAccessor, TestAccessor,
+};
+use crate::base::{
+    commitment::{
+        naive_commitment::NaiveCommitment, naive_evaluation_proof::NaiveEvaluationProof,
+        Commitment, CommittableColumn,
+    },
+    database::{owned_table_utility::*, TableRef},
+};
+
+fn accessor_with_two_tables() -> OwnedTableTestAccessor<'static, NaiveEvaluationProof> {
+    let mut accessor = OwnedTableTestAccessor::<NaiveEvaluationProof>::new_empty_with_setup(());
+    accessor.add_table(
+        TableRef::new("sxt", "t"),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            varchar("b", ["a", "b", "c", "d", "e"]),
+        ]),
+        10,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "u"),
+        owned_table([bigint("c", [6, 7])]),
+        0,
+    );
+    accessor
+}
+
+#[test]
+fn we_can_restrict_the_data_span_of_a_table_to_a_range_of_rows() {
+    let inner = accessor_with_two_tables();
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    let accessor = RowRangeAccessor::new(&inner, [(t.clone(), 11..14)]);
+
+    assert_eq!(accessor.get_offset(&t), 11);
+    assert_eq!(accessor.get_length(&t), 3);
+    assert_eq!(accessor.get_offset(&u), 0);
+    assert_eq!(accessor.get_length(&u), 2);
+
+    let column = ColumnRef::new(t.clone(), "a".into(), ColumnType::BigInt);
+    assert_eq!(
+        accessor.get_column(column.clone()),
+        Column::BigInt(&[2, 3, 4])
+    );
+    assert_eq!(
+        accessor.get_commitment(column),
+        NaiveCommitment::compute_commitments(
+            &[CommittableColumn::from(&[2i64, 3, 4][..])],
+            11,
+            &()
+        )[0]
+    );
+    let column = ColumnRef::new(t.clone(), "b".into(), ColumnType::VarChar);
+    let Column::VarChar((strings, _)) = accessor.get_column(column) else {
+        panic!("expected a varchar column");
+    };
+    assert_eq!(strings, &["b", "c", "d"]);
+
+    let column = ColumnRef::new(u.clone(), "c".into(), ColumnType::BigInt);
+    assert_eq!(accessor.get_column(column.clone()), Column::BigInt(&[6, 7]));
+    assert_eq!(
+        accessor.get_commitment(column.clone()),
+        inner.get_commitment(column)
+    );
+
+    assert_eq!(
+        accessor.lookup_column(t.clone(), "a".into()),
+        Some(ColumnType::BigInt)
+    );
+    assert_eq!(
+        accessor.lookup_schema(u),
+        inner.lookup_schema(TableRef::new("sxt", "u"))
+    );
+}
+
+#[test]
+fn we_can_restrict_a_table_to_no_rows() {
+    let inner = accessor_with_two_tables();
+    let t = TableRef::new("sxt", "t");
+    let accessor = RowRangeAccessor::new(&inner, [(t.clone(), 15..15)]);
+    assert_eq!(accessor.get_offset(&t), 15);
+    assert_eq!(accessor.get_length(&t), 0);
+    let column = ColumnRef::new(t, "a".into(), ColumnType::BigInt);
+    assert_eq!(accessor.get_column(column), Column::BigInt(&[]));
+}
+
+#[test]
+#[should_panic(expected = "are not within the data span")]
+fn we_cannot_restrict_a_table_to_rows_before_its_data_span() {
+    let inner = accessor_with_two_tables();
+    let _ = RowRangeAccessor::new(&inner, [(TableRef::new("sxt", "t"), 9..12)]);
+}
+
+#[test]
+#[should_panic(expected = "are not within the data span")]
+fn we_cannot_restrict_a_table_to_rows_after_its_data_span() {
+    let inner = accessor_with_two_tables();
+    let _ = RowRangeAccessor::new(&inner, [(TableRef::new("sxt", "t"), 12..16)]);
+}
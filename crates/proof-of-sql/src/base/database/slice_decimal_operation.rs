@@ -2,7 +2,8 @@ use super::{ColumnOperationError, ColumnOperationResult};
 use crate::base::{
     database::{
         column_type_operation::{
-            try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+            try_add_subtract_column_types, try_average_column_type, try_divide_column_types,
+            try_multiply_column_types,
         },
         ColumnType,
     },
@@ -12,7 +13,7 @@ use crate::base::{
 use alloc::vec::Vec;
 use core::{cmp::Ordering, fmt::Debug};
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::{Signed, Zero};
 /// Check whether a numerical slice is equal to a decimal one.
 ///
 /// Note that we do not check for length equality here.
@@ -463,6 +464,57 @@ where
     ))
 }
 
+/// Average a column of sums given the number of values each sum was taken over.
+///
+/// Notes:
+/// 1. We do not check for length equality here.
+/// 2. We round half away from zero so that the result is exact up to the new scale.
+/// 3. If a count is not positive, we return an error.
+/// 4. Precision and scale are given by [`try_average_column_type`].
+#[allow(clippy::missing_panics_doc)]
+pub(crate) fn try_average_decimal_column<S, T>(
+    sums: &[T],
+    counts: &[i64],
+    column_type: ColumnType,
+) -> ColumnOperationResult<(Precision, i8, Vec<S>)>
+where
+    S: Scalar,
+    T: Copy + Debug + Into<BigInt>,
+{
+    let new_column_type = try_average_column_type(column_type)?;
+    let new_precision_value = new_column_type
+        .precision_value()
+        .expect("numeric columns have precision");
+    let new_scale = new_column_type.scale().expect("numeric columns have scale");
+    let scale = column_type.scale().expect("numeric columns have scale");
+    let applied_scale_factor = BigInt::from(10).pow(u32::from((new_scale - scale).unsigned_abs()));
+    let result: Vec<S> = sums
+        .iter()
+        .zip(counts)
+        .map(|(sum, &count)| -> ColumnOperationResult<S> {
+            if count <= 0 {
+                return Err(ColumnOperationError::DivisionByZero);
+            }
+            let count_bigint = BigInt::from(count);
+            let scaled_sum = Into::<BigInt>::into(*sum) * &applied_scale_factor;
+            // Truncating division followed by rounding the remainder away from zero
+            let quotient = &scaled_sum / &count_bigint;
+            let remainder = &scaled_sum % &count_bigint;
+            let new_bigint = if remainder.magnitude() * 2_u8 >= *count_bigint.magnitude() {
+                quotient + remainder.signum()
+            } else {
+                quotient
+            };
+            Ok(S::try_from(new_bigint).expect("Average should fit into scalar"))
+        })
+        .collect::<ColumnOperationResult<Vec<_>>>()?;
+    Ok((
+        Precision::new(new_precision_value).expect("Precision value is valid"),
+        new_scale,
+        result,
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1310,4 +1362,76 @@ mod test {
         let expected = (Precision::new(9).unwrap(), 6, expected_scalars);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn we_can_try_average_decimal_columns() {
+        // Integer sums are rounded half away from zero at scale 6
+        let sums = [2_i64, -2, 1, -5, 0];
+        let counts = [3_i64, 3, 8_000_000, 2, 7];
+        let actual: (Precision, i8, Vec<TestScalar>) =
+            try_average_decimal_column(&sums, &counts, ColumnType::BigInt).unwrap();
+        let expected_scalars = vec![
+            TestScalar::from(666_667_i64),
+            TestScalar::from(-666_667_i64),
+            TestScalar::from(0_i64),
+            TestScalar::from(-2_500_000_i64),
+            TestScalar::from(0_i64),
+        ];
+        let expected = (Precision::new(25).unwrap(), 6, expected_scalars);
+        assert_eq!(expected, actual);
+
+        // Ties are rounded away from zero
+        let sums = [1_i64, -1];
+        let counts = [2_000_000_i64, 2_000_000];
+        let actual: (Precision, i8, Vec<TestScalar>) =
+            try_average_decimal_column(&sums, &counts, ColumnType::BigInt).unwrap();
+        let expected_scalars = vec![TestScalar::from(1_i64), TestScalar::from(-1_i64)];
+        assert_eq!(expected_scalars, actual.2);
+
+        // Decimal sums with a large scale keep their scale
+        let sums = [
+            TestScalar::from(1_000_000_001_i64),
+            -TestScalar::from(7_i64),
+        ];
+        let counts = [2_i64, 2];
+        let column_type = ColumnType::Decimal75(Precision::new(20).unwrap(), 9);
+        let actual: (Precision, i8, Vec<TestScalar>) =
+            try_average_decimal_column(&sums, &counts, column_type).unwrap();
+        let expected_scalars = vec![TestScalar::from(500_000_001_i64), -TestScalar::from(4_i64)];
+        let expected = (Precision::new(20).unwrap(), 9, expected_scalars);
+        assert_eq!(expected, actual);
+
+        // Decimal sums with a negative scale are scaled up
+        let sums = [TestScalar::from(3_i64)];
+        let counts = [4_i64];
+        let column_type = ColumnType::Decimal75(Precision::new(5).unwrap(), -1);
+        let actual: (Precision, i8, Vec<TestScalar>) =
+            try_average_decimal_column(&sums, &counts, column_type).unwrap();
+        let expected = (
+            Precision::new(12).unwrap(),
+            6,
+            vec![TestScalar::from(7_500_000_i64)],
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_cannot_try_average_decimal_columns_with_non_positive_counts_or_invalid_types() {
+        let sums = [1_i64, 2];
+        let counts = [1_i64, 0];
+        assert!(matches!(
+            try_average_decimal_column::<TestScalar, _>(&sums, &counts, ColumnType::BigInt),
+            Err(ColumnOperationError::DivisionByZero)
+        ));
+        let counts = [1_i64, -1];
+        assert!(matches!(
+            try_average_decimal_column::<TestScalar, _>(&sums, &counts, ColumnType::BigInt),
+            Err(ColumnOperationError::DivisionByZero)
+        ));
+        let counts = [1_i64, 1];
+        assert!(matches!(
+            try_average_decimal_column::<TestScalar, _>(&sums, &counts, ColumnType::Boolean),
+            Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+        ));
+    }
 }
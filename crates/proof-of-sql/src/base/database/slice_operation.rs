@@ -1,7 +1,7 @@
 use super::{ColumnOperationError, ColumnOperationResult};
 use alloc::{format, vec::Vec};
 use core::fmt::Debug;
-use num_traits::ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use num_traits::ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
 
 /// Reverse a binary operator. That is, $a *_{op} b = b * a$.
 ///
@@ -56,6 +56,14 @@ where
     l.checked_div(r).ok_or(ColumnOperationError::DivisionByZero)
 }
 
+/// Function for checked remainder with division by zero error handling
+pub(super) fn try_rem<T>(l: &T, r: &T) -> ColumnOperationResult<T>
+where
+    T: CheckedRem<Output = T> + Debug,
+{
+    l.checked_rem(r).ok_or(ColumnOperationError::DivisionByZero)
+}
+
 // Generic binary operations on slice and a single value
 
 /// Apply a binary operator to a slice and a single value.
@@ -631,6 +639,41 @@ mod test {
         ));
     }
 
+    // %
+    #[test]
+    fn we_can_try_modulo_slices() {
+        let lhs = [5_i16, -5, -7, 9];
+        let rhs = [-3_i16, 3, -4, 5];
+        let actual = try_slice_binary_op(&lhs, &rhs, try_rem).unwrap();
+        let expected = vec![2_i16, -2, -3, 4];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_cannot_try_modulo_slices_if_divide_by_zero() {
+        let lhs = [1_i32, 2, 3];
+        let rhs = [0_i32, -5, 6];
+        assert!(matches!(
+            try_slice_binary_op(&lhs, &rhs, try_rem),
+            Err(ColumnOperationError::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn we_can_try_modulo_slices_with_upcasts() {
+        let lhs = [5_i16, -4, -9, 9];
+        let rhs = [-3_i32, 3, -4, 5];
+        let actual = try_slice_binary_op_left_upcast(&lhs, &rhs, try_rem).unwrap();
+        let expected = vec![2_i32, -1, -1, 4];
+        assert_eq!(expected, actual);
+
+        let lhs = [15_i128, -82, -7, 9];
+        let rhs = [-4_i32, 3, -4, 5];
+        let actual = try_slice_binary_op_right_upcast(&lhs, &rhs, try_rem).unwrap();
+        let expected = vec![3_i128, -1, -3, 4];
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn we_can_repeat_a_slice() {
         // We can repeat a slice
@@ -1,4 +1,4 @@
-use super::{Column, ColumnField};
+use super::{Column, ColumnComponent, ColumnField};
 use crate::base::{map::IndexMap, scalar::Scalar};
 use alloc::vec::Vec;
 use bumpalo::Bump;
@@ -93,6 +93,40 @@ impl<'a, S: Scalar> Table<'a, S> {
         Self::try_new_with_options(IndexMap::from_iter(iter), options)
     }
 
+    /// Creates a new [`Table`] with the schema `fields` from the components of its columns with [`TableOptions`],
+    /// which is the inverse of [`Self::schema`] and [`Self::column_components`].
+    ///
+    /// The presence of a nullable column follows its values, and is a boolean column.
+    /// Character components are skipped, since they are computed from the strings of their column.
+    ///
+    /// # Panics
+    /// Panics if a presence is not a boolean column that follows the values of its column.
+    pub fn try_from_components_with_options<T: IntoIterator<Item = Column<'a, S>>>(
+        fields: &[ColumnField],
+        components: T,
+        options: TableOptions,
+        alloc: &'a Bump,
+    ) -> Result<Self, TableError> {
+        let mut table: IndexMap<Ident, Column<'a, S>> = IndexMap::default();
+        for (field, component) in fields.iter().zip(components) {
+            match field.component() {
+                ColumnComponent::Values => {
+                    table.insert(field.name(), component);
+                }
+                ColumnComponent::Presence => {
+                    let values = table
+                        .get_mut(&field.name())
+                        .expect("presence has to follow the values of its column");
+                    let presence = component.as_boolean().expect("presence is not boolean");
+                    *values = Column::Nullable(alloc.alloc(*values), presence);
+                }
+                // The character components of a column are computed from its strings
+                _ => {}
+            }
+        }
+        Self::try_new_with_options(table, options)
+    }
+
     /// Number of columns in the table.
     #[must_use]
     pub fn num_columns(&self) -> usize {
@@ -119,11 +153,17 @@ impl<'a, S: Scalar> Table<'a, S> {
         &self.table
     }
     /// Return the schema of this table as a `Vec` of `ColumnField`s
+    ///
+    /// A nullable column is described by the field of its values followed by the field of its presence.
     #[must_use]
     pub fn schema(&self) -> Vec<ColumnField> {
         self.table
             .iter()
-            .map(|(name, column)| ColumnField::new(name.clone(), column.column_type()))
+            .flat_map(|(name, column)| {
+                let field = ColumnField::new(name.clone(), column.column_type());
+                let presence_field = column.presence().map(|_| field.presence());
+                core::iter::once(field).chain(presence_field)
+            })
             .collect()
     }
     /// Returns the columns of this table as an Iterator
@@ -134,6 +174,11 @@ impl<'a, S: Scalar> Table<'a, S> {
     pub fn columns(&self) -> impl Iterator<Item = &Column<'a, S>> {
         self.table.values()
     }
+    /// Returns the components of the columns of this table, which are described by [`Self::schema`]
+    #[must_use]
+    pub fn column_components(&self) -> Vec<Column<'a, S>> {
+        self.table.values().flat_map(Column::components).collect()
+    }
     /// Returns the column with the given position.
     #[must_use]
     pub fn column(&self, index: usize) -> Option<&Column<'a, S>> {
@@ -1,18 +1,21 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
-    SchemaAccessor, Table, TableRef, TestAccessor,
+    Column, ColumnComponent, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, SchemaAccessor, Table, TableRef, TestAccessor,
 };
 use crate::base::{
     commitment::{CommitmentEvaluationProof, VecCommitmentExt},
     map::IndexMap,
 };
 use alloc::vec::Vec;
+use bumpalo::Bump;
 use sqlparser::ast::Ident;
 
 /// A test accessor that uses [`Table`] as the underlying table type.
 /// Note: this is intended for testing and examples. It is not optimized for performance, so should not be used for benchmarks or production use-cases.
 pub struct TableTestAccessor<'a, CP: CommitmentEvaluationProof> {
     tables: IndexMap<TableRef, (Table<'a, CP::Scalar>, usize)>,
+    character_counts: IndexMap<(TableRef, Ident), usize>,
+    alloc: Bump,
     setup: Option<CP::ProverPublicSetup<'a>>,
 }
 
@@ -20,6 +23,8 @@ impl<CP: CommitmentEvaluationProof> Default for TableTestAccessor<'_, CP> {
     fn default() -> Self {
         Self {
             tables: IndexMap::default(),
+            character_counts: IndexMap::default(),
+            alloc: Bump::new(),
             setup: None,
         }
     }
@@ -29,7 +34,9 @@ impl<CP: CommitmentEvaluationProof> Clone for TableTestAccessor<'_, CP> {
     fn clone(&self) -> Self {
         Self {
             tables: self.tables.clone(),
+            character_counts: self.character_counts.clone(),
             setup: self.setup,
+            ..Default::default()
         }
     }
 }
@@ -75,15 +82,19 @@ impl<'a, CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment> for TableTe
 /// the `column.column_id()` is not found in the inner table for that reference,
 /// indicating that an invalid column reference was provided.
 impl<'a, CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for TableTestAccessor<'a, CP> {
-    fn get_column(&self, column: ColumnRef) -> Column<'a, CP::Scalar> {
-        *self
+    fn get_column(&self, column: ColumnRef) -> Column<CP::Scalar> {
+        let table_column = *self
             .tables
             .get(&column.table_ref())
             .unwrap()
             .0
             .inner_table()
             .get(&column.column_id())
-            .unwrap()
+            .unwrap();
+        match column.component() {
+            ColumnComponent::Values => table_column,
+            component => table_column.component(&self.alloc, component),
+        }
     }
 }
 
@@ -96,9 +107,13 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
 {
     fn get_commitment(&self, column: ColumnRef) -> CP::Commitment {
         let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
-        let borrowed_column = table.inner_table().get(&column.column_id()).unwrap();
+        let borrowed_column = table
+            .inner_table()
+            .get(&column.column_id())
+            .unwrap()
+            .component(&self.alloc, column.component());
         Vec::<CP::Commitment>::from_columns_with_offset(
-            [borrowed_column],
+            [&borrowed_column],
             *offset,
             self.setup.as_ref().unwrap(),
         )[0]
@@ -146,6 +161,20 @@ impl<CP: CommitmentEvaluationProof> SchemaAccessor for TableTestAccessor<'_, CP>
             .map(|(id, col)| (id.clone(), col.column_type()))
             .collect()
     }
+
+    fn is_nullable(&self, table_ref: TableRef, column_id: Ident) -> bool {
+        self.tables
+            .get(&table_ref)
+            .and_then(|(table, _)| table.inner_table().get(&column_id))
+            .is_some_and(|column| column.presence().is_some())
+    }
+
+    fn character_count(&self, table_ref: TableRef, column_id: Ident) -> usize {
+        self.character_counts
+            .get(&(table_ref, column_id))
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 impl<'a, CP: CommitmentEvaluationProof> TableTestAccessor<'a, CP> {
@@ -167,4 +196,10 @@ impl<'a, CP: CommitmentEvaluationProof> TableTestAccessor<'a, CP> {
         res.add_table(table_ref, table, offset);
         res
     }
+
+    /// Commits to the first and last `count` characters of the `VarChar` column `column_id` of the table `table_ref`,
+    /// so that `LIKE` can be proven on it.
+    pub fn set_character_count(&mut self, table_ref: TableRef, column_id: Ident, count: usize) {
+        self.character_counts.insert((table_ref, column_id), count);
+    }
 }
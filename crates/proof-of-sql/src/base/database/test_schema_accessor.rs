@@ -1,15 +1,40 @@
 use super::{ColumnType, SchemaAccessor, TableRef};
-use crate::base::map::IndexMap;
+use crate::base::map::{IndexMap, IndexSet};
 use sqlparser::ast::Ident;
 /// A simple in-memory `SchemaAccessor` for testing intermediate AST -> Provable AST conversion.
 pub struct TestSchemaAccessor {
     schemas: IndexMap<TableRef, IndexMap<Ident, ColumnType>>,
+    nullable_columns: IndexSet<(TableRef, Ident)>,
+    character_counts: IndexMap<(TableRef, Ident), usize>,
 }
 
 impl TestSchemaAccessor {
     /// Create a new `TestSchemaAccessor` with the given schema.
     pub fn new(schemas: IndexMap<TableRef, IndexMap<Ident, ColumnType>>) -> Self {
-        Self { schemas }
+        Self {
+            schemas,
+            nullable_columns: IndexSet::default(),
+            character_counts: IndexMap::default(),
+        }
+    }
+
+    /// Marks the column `column_id` of the table `table_ref` as nullable.
+    #[must_use]
+    pub fn with_nullable_column(mut self, table_ref: TableRef, column_id: Ident) -> Self {
+        self.nullable_columns.insert((table_ref, column_id));
+        self
+    }
+
+    /// Marks the first and last `count` characters of the column `column_id` of the table `table_ref` as committed to.
+    #[must_use]
+    pub fn with_character_count(
+        mut self,
+        table_ref: TableRef,
+        column_id: Ident,
+        count: usize,
+    ) -> Self {
+        self.character_counts.insert((table_ref, column_id), count);
+        self
     }
 }
 
@@ -26,6 +51,17 @@ impl SchemaAccessor for TestSchemaAccessor {
             .map(|(id, col)| (id.clone(), *col))
             .collect()
     }
+
+    fn is_nullable(&self, table_ref: TableRef, column_id: Ident) -> bool {
+        self.nullable_columns.contains(&(table_ref, column_id))
+    }
+
+    fn character_count(&self, table_ref: TableRef, column_id: Ident) -> usize {
+        self.character_counts
+            .get(&(table_ref, column_id))
+            .copied()
+            .unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
@@ -103,4 +139,14 @@ mod tests {
         );
         assert_eq!(accessor.lookup_schema(not_a_table), vec![]);
     }
+
+    #[test]
+    fn test_is_nullable() {
+        let table1 = TableRef::new("schema", "table1");
+        let accessor =
+            sample_test_schema_accessor().with_nullable_column(table1.clone(), "col2".into());
+        assert!(!accessor.is_nullable(table1.clone(), "col1".into()));
+        assert!(accessor.is_nullable(table1, "col2".into()));
+        assert!(!accessor.is_nullable(TableRef::new("schema", "table2"), "col2".into()));
+    }
 }
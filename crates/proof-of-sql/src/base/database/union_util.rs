@@ -1,6 +1,6 @@
 use super::{
-    Column, ColumnField, ColumnOperationError, ColumnOperationResult, ColumnType, Table,
-    TableOperationError, TableOperationResult, TableOptions,
+    Column, ColumnComponent, ColumnField, ColumnOperationError, ColumnOperationResult, ColumnType,
+    Table, TableOperationError, TableOperationResult, TableOptions,
 };
 use crate::base::scalar::Scalar;
 use alloc::vec::Vec;
@@ -37,6 +37,15 @@ pub fn column_union<'a, S: Scalar>(
             correct_type: column_type,
         });
     }
+    // The union of nullable columns is nullable, where the rows of the other columns are not NULL
+    if columns.iter().any(|col| col.presence().is_some()) {
+        let values: Vec<_> = columns.iter().map(|col| col.values()).collect();
+        let values = column_union(&values.iter().collect::<Vec<_>>(), alloc, column_type)?;
+        let presence = alloc.alloc_slice_fill_iter(columns.iter().flat_map(|col| {
+            (0..col.len()).map(|i| col.presence().is_none_or(|presence| presence[i]))
+        }));
+        return Ok(Column::Nullable(alloc.alloc(values), presence));
+    }
     // First, calculate the total length of the combined columns
     let len: usize = columns.iter().map(|col| col.len()).sum();
 
@@ -224,17 +233,23 @@ pub fn table_union<'a, S: Scalar>(
     // Union the columns
     // Make sure to consider the case where the tables have no columns
     let num_rows = tables.iter().map(Table::num_rows).sum();
+    // The presence of a nullable column is part of the column
     let result = Table::<'a, S>::try_from_iter_with_options(
-        schema.iter().enumerate().map(|(i, field)| {
-            let columns: Vec<_> = tables
-                .iter()
-                .map(|table| table.column(i).expect("Schemas should be compatible"))
-                .collect();
-            (
-                field.name(),
-                column_union(&columns, alloc, field.data_type()).expect("Failed to union columns"),
-            )
-        }),
+        schema
+            .iter()
+            .filter(|field| field.component() == ColumnComponent::Values)
+            .enumerate()
+            .map(|(i, field)| {
+                let columns: Vec<_> = tables
+                    .iter()
+                    .map(|table| table.column(i).expect("Schemas should be compatible"))
+                    .collect();
+                (
+                    field.name(),
+                    column_union(&columns, alloc, field.data_type())
+                        .expect("Failed to union columns"),
+                )
+            }),
         TableOptions::new(Some(num_rows)),
     )
     .expect("Failed to create table from iterator");
@@ -95,6 +95,7 @@ where
     slice_like_mle_impl!();
 }
 
+/// The multilinear extension of a nullable column is the one of its values.
 impl<S: Scalar> MultilinearExtension<S> for &Column<'_, S> {
     fn inner_product(&self, evaluation_vec: &[S]) -> S {
         match self {
@@ -109,6 +110,7 @@ impl<S: Scalar> MultilinearExtension<S> for &Column<'_, S> {
             Column::Int(c) => c.inner_product(evaluation_vec),
             Column::BigInt(c) | Column::TimestampTZ(_, _, c) => c.inner_product(evaluation_vec),
             Column::Int128(c) => c.inner_product(evaluation_vec),
+            Column::Nullable(values, _) => values.inner_product(evaluation_vec),
         }
     }
 
@@ -127,6 +129,7 @@ impl<S: Scalar> MultilinearExtension<S> for &Column<'_, S> {
             Column::Int(c) => c.mul_add(res, multiplier),
             Column::BigInt(c) | Column::TimestampTZ(_, _, c) => c.mul_add(res, multiplier),
             Column::Int128(c) => c.mul_add(res, multiplier),
+            Column::Nullable(values, _) => values.mul_add(res, multiplier),
         }
     }
 
@@ -143,6 +146,7 @@ impl<S: Scalar> MultilinearExtension<S> for &Column<'_, S> {
             Column::Int(c) => c.to_sumcheck_term(num_vars),
             Column::BigInt(c) | Column::TimestampTZ(_, _, c) => c.to_sumcheck_term(num_vars),
             Column::Int128(c) => c.to_sumcheck_term(num_vars),
+            Column::Nullable(values, _) => values.to_sumcheck_term(num_vars),
         }
     }
 
@@ -159,6 +163,7 @@ impl<S: Scalar> MultilinearExtension<S> for &Column<'_, S> {
             Column::Int(c) => MultilinearExtension::<S>::id(c),
             Column::BigInt(c) | Column::TimestampTZ(_, _, c) => MultilinearExtension::<S>::id(c),
             Column::Int128(c) => MultilinearExtension::<S>::id(c),
+            Column::Nullable(values, _) => MultilinearExtension::<S>::id(*values),
         }
     }
 }
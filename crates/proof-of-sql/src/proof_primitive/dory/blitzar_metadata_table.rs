@@ -136,6 +136,9 @@ fn copy_column_data_to_slice(
         | CommittableColumn::VarBinary(column) => {
             scalar_row_slice[start..end].copy_from_slice(&column[index].offset_to_bytes());
         }
+        CommittableColumn::Nullable(values, _) => {
+            copy_column_data_to_slice(values, scalar_row_slice, start, end, index);
+        }
     }
 }
 
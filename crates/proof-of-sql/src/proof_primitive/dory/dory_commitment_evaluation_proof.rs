@@ -1,14 +1,19 @@
 use super::{
     build_vmv_prover_state, build_vmv_verifier_state, compute_T_vec_prime, compute_nu,
-    eval_vmv_re_prove, eval_vmv_re_verify, extended_dory_inner_product_prove,
-    extended_dory_inner_product_verify,
+    eval_vmv_re_prove, eval_vmv_re_verify, extended_dory_inner_product_pairing_check,
+    extended_dory_inner_product_prove,
     extended_dory_reduce_helper::extended_dory_reduce_verify_fold_s_vecs, DeferredGT,
-    DoryCommitment, DoryMessages, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup, F,
+    DoryCommitment, DoryMessages, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup,
+    PairingCheck, F,
 };
 use crate::{
-    base::{commitment::CommitmentEvaluationProof, proof::Transcript},
+    base::{
+        commitment::{BatchedProofVerification, CommitmentEvaluationProof},
+        proof::Transcript,
+    },
     utils::log,
 };
+use alloc::vec::Vec;
 use snafu::Snafu;
 
 /// The `CommitmentEvaluationProof` for the Dory PCS.
@@ -83,51 +88,94 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         evaluations: &[Self::Scalar],
         b_point: &[Self::Scalar],
         generators_offset: u64,
-        _table_length: usize,
+        table_length: usize,
         setup: &Self::VerifierPublicSetup<'_>,
     ) -> Result<(), Self::Error> {
-        log::log_memory_usage("Start");
-
-        let a_commit = DeferredGT::new(
-            commit_batch.iter().map(|c| c.0),
-            batching_factors.iter().map(|f| f.0),
-        );
-        let product: Self::Scalar = evaluations
-            .iter()
-            .zip(batching_factors)
-            .map(|(&e, &f)| e * f)
-            .sum();
-        // Dory PCS Logic
-        if generators_offset != 0 {
-            return Err(DoryError::InvalidGeneratorsOffset {
-                offset: generators_offset,
-            });
-        }
-        let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
-        let verifier_setup = setup.verifier_setup();
-        let mut messages = self.clone();
-        let nu = compute_nu(b_point.len(), setup.sigma());
-        if nu > verifier_setup.max_nu {
-            return Err(DoryError::SmallSetup {
-                actual: verifier_setup.max_nu,
-                required: nu,
-            });
-        }
-        let state = build_vmv_verifier_state(product.0, b_point, a_commit, setup.sigma(), nu);
-        let extended_state = eval_vmv_re_verify(&mut messages, transcript, state, verifier_setup)
-            .ok_or(DoryError::VerificationError)?;
-        if !extended_dory_inner_product_verify(
-            &mut messages,
+        let mut verification = BatchedProofVerification {
+            proof: self,
             transcript,
-            extended_state,
-            verifier_setup,
-            extended_dory_reduce_verify_fold_s_vecs,
-        ) {
+            commit_batch,
+            batching_factors,
+            evaluations,
+            b_point,
+            generators_offset,
+            table_length,
+        };
+        if !pairing_check(&mut verification, setup)?.verify() {
             Err(DoryError::VerificationError)?;
         }
+        Ok(())
+    }
 
-        log::log_memory_usage("End");
+    #[tracing::instrument(
+        name = "DoryEvaluationProof::verify_batched_proofs",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proofs<T: Transcript + Clone>(
+        verifications: Vec<BatchedProofVerification<'_, Self, T>>,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Vec<Result<(), Self::Error>> {
+        let (checks, transcript_challenges): (Vec<_>, Vec<_>) = verifications
+            .into_iter()
+            .map(|mut verification| {
+                let check = pairing_check(&mut verification, setup);
+                (check, verification.transcript.clone().challenge_as_le())
+            })
+            .unzip();
+        PairingCheck::verify_many(checks, &transcript_challenges, || {
+            DoryError::VerificationError
+        })
+    }
+}
 
-        Ok(())
+/// Verifies a proof up to its final pairing check, which is returned.
+fn pairing_check(
+    verification: &mut BatchedProofVerification<'_, DoryEvaluationProof, impl Transcript>,
+    setup: &DoryVerifierPublicSetup<'_>,
+) -> Result<PairingCheck, DoryError> {
+    log::log_memory_usage("Start");
+
+    let a_commit = DeferredGT::new(
+        verification.commit_batch.iter().map(|c| c.0),
+        verification.batching_factors.iter().map(|f| f.0),
+    );
+    let product: DoryScalar = verification
+        .evaluations
+        .iter()
+        .zip(verification.batching_factors)
+        .map(|(&e, &f)| e * f)
+        .sum();
+    // Dory PCS Logic
+    if verification.generators_offset != 0 {
+        return Err(DoryError::InvalidGeneratorsOffset {
+            offset: verification.generators_offset,
+        });
+    }
+    let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(verification.b_point);
+    let verifier_setup = setup.verifier_setup();
+    let mut messages = verification.proof.clone();
+    let nu = compute_nu(b_point.len(), setup.sigma());
+    if nu > verifier_setup.max_nu {
+        return Err(DoryError::SmallSetup {
+            actual: verifier_setup.max_nu,
+            required: nu,
+        });
     }
+    let transcript = &mut *verification.transcript;
+    let state = build_vmv_verifier_state(product.0, b_point, a_commit, setup.sigma(), nu);
+    let extended_state = eval_vmv_re_verify(&mut messages, transcript, state, verifier_setup)
+        .ok_or(DoryError::VerificationError)?;
+    let check = extended_dory_inner_product_pairing_check(
+        &mut messages,
+        transcript,
+        extended_state,
+        verifier_setup,
+        extended_dory_reduce_verify_fold_s_vecs,
+    )
+    .ok_or(DoryError::VerificationError)?;
+
+    log::log_memory_usage("End");
+
+    Ok(check)
 }
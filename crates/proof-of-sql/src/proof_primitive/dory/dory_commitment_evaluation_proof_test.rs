@@ -69,6 +69,17 @@ fn test_random_ipa_with_various_lengths() {
     }
 }
 
+#[test]
+fn we_can_verify_many_dory_evaluation_proofs_at_once() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    test_batched_commitment_evaluation_proofs::<DoryEvaluationProof>(
+        &DoryProverPublicSetup::new(&prover_setup, 3),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 3),
+    );
+}
+
 #[test]
 fn we_can_serialize_and_deserialize_dory_evaluation_proofs() {
     let mut rng = test_rng();
@@ -1,14 +1,18 @@
 use super::{
     dynamic_build_vmv_state::{build_dynamic_vmv_prover_state, build_dynamic_vmv_verifier_state},
     dynamic_dory_helper::{compute_dynamic_T_vec_prime, compute_dynamic_nu, fold_dynamic_tensors},
-    eval_vmv_re_prove, eval_vmv_re_verify, extended_dory_inner_product_prove,
-    extended_dory_inner_product_verify, DeferredGT, DoryMessages, DoryScalar,
-    DynamicDoryCommitment, ProverSetup, VerifierSetup, F,
+    eval_vmv_re_prove, eval_vmv_re_verify, extended_dory_inner_product_pairing_check,
+    extended_dory_inner_product_prove, DeferredGT, DoryMessages, DoryScalar, DynamicDoryCommitment,
+    PairingCheck, ProverSetup, VerifierSetup, F,
 };
 use crate::{
-    base::{commitment::CommitmentEvaluationProof, proof::Transcript},
+    base::{
+        commitment::{BatchedProofVerification, CommitmentEvaluationProof},
+        proof::Transcript,
+    },
     utils::log,
 };
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 
@@ -84,50 +88,93 @@ impl CommitmentEvaluationProof for DynamicDoryEvaluationProof {
         evaluations: &[Self::Scalar],
         b_point: &[Self::Scalar],
         generators_offset: u64,
-        _table_length: usize,
+        table_length: usize,
         setup: &Self::VerifierPublicSetup<'_>,
     ) -> Result<(), Self::Error> {
-        log::log_memory_usage("Start");
-
-        let a_commit = DeferredGT::new(
-            commit_batch.iter().map(|c| c.0),
-            batching_factors.iter().map(|f| f.0),
-        );
-        let product: Self::Scalar = evaluations
-            .iter()
-            .zip(batching_factors)
-            .map(|(&e, &f)| e * f)
-            .sum();
-        // Dory PCS Logic
-        if generators_offset != 0 {
-            return Err(DoryError::InvalidGeneratorsOffset {
-                offset: generators_offset,
-            });
-        }
-        let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
-        let mut messages = self.0.clone();
-        let nu = compute_dynamic_nu(b_point.len());
-        if nu > setup.max_nu {
-            return Err(DoryError::SmallSetup {
-                actual: setup.max_nu,
-                required: nu,
-            });
-        }
-        let state = build_dynamic_vmv_verifier_state(product.0, b_point, a_commit, nu);
-        let extended_state = eval_vmv_re_verify(&mut messages, transcript, state, setup)
-            .ok_or(DoryError::VerificationError)?;
-        if !extended_dory_inner_product_verify(
-            &mut messages,
+        let mut verification = BatchedProofVerification {
+            proof: self,
             transcript,
-            extended_state,
-            setup,
-            fold_dynamic_tensors,
-        ) {
+            commit_batch,
+            batching_factors,
+            evaluations,
+            b_point,
+            generators_offset,
+            table_length,
+        };
+        if !pairing_check(&mut verification, setup)?.verify() {
             Err(DoryError::VerificationError)?;
         }
+        Ok(())
+    }
 
-        log::log_memory_usage("End");
+    #[tracing::instrument(
+        name = "DoryEvaluationProof::verify_batched_proofs",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proofs<T: Transcript + Clone>(
+        verifications: Vec<BatchedProofVerification<'_, Self, T>>,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Vec<Result<(), Self::Error>> {
+        let (checks, transcript_challenges): (Vec<_>, Vec<_>) = verifications
+            .into_iter()
+            .map(|mut verification| {
+                let check = pairing_check(&mut verification, setup);
+                (check, verification.transcript.clone().challenge_as_le())
+            })
+            .unzip();
+        PairingCheck::verify_many(checks, &transcript_challenges, || {
+            DoryError::VerificationError
+        })
+    }
+}
 
-        Ok(())
+/// Verifies a proof up to its final pairing check, which is returned.
+fn pairing_check(
+    verification: &mut BatchedProofVerification<'_, DynamicDoryEvaluationProof, impl Transcript>,
+    setup: &VerifierSetup,
+) -> Result<PairingCheck, DoryError> {
+    log::log_memory_usage("Start");
+
+    let a_commit = DeferredGT::new(
+        verification.commit_batch.iter().map(|c| c.0),
+        verification.batching_factors.iter().map(|f| f.0),
+    );
+    let product: DoryScalar = verification
+        .evaluations
+        .iter()
+        .zip(verification.batching_factors)
+        .map(|(&e, &f)| e * f)
+        .sum();
+    // Dory PCS Logic
+    if verification.generators_offset != 0 {
+        return Err(DoryError::InvalidGeneratorsOffset {
+            offset: verification.generators_offset,
+        });
     }
+    let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(verification.b_point);
+    let mut messages = verification.proof.0.clone();
+    let nu = compute_dynamic_nu(b_point.len());
+    if nu > setup.max_nu {
+        return Err(DoryError::SmallSetup {
+            actual: setup.max_nu,
+            required: nu,
+        });
+    }
+    let transcript = &mut *verification.transcript;
+    let state = build_dynamic_vmv_verifier_state(product.0, b_point, a_commit, nu);
+    let extended_state = eval_vmv_re_verify(&mut messages, transcript, state, setup)
+        .ok_or(DoryError::VerificationError)?;
+    let check = extended_dory_inner_product_pairing_check(
+        &mut messages,
+        transcript,
+        extended_state,
+        setup,
+        fold_dynamic_tensors,
+    )
+    .ok_or(DoryError::VerificationError)?;
+
+    log::log_memory_usage("End");
+
+    Ok(check)
 }
@@ -27,6 +27,17 @@ fn test_random_ipa_with_length_1() {
     );
 }
 
+#[test]
+fn we_can_verify_many_dory_evaluation_proofs_at_once() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    test_batched_commitment_evaluation_proofs::<DynamicDoryEvaluationProof>(
+        &&prover_setup,
+        &&verifier_setup,
+    );
+}
+
 #[test]
 #[should_panic = "verification improperly failed"]
 fn test_random_ipa_fails_with_too_small_of_verifier_setup() {
@@ -90,6 +90,7 @@ fn compute_dory_commitment(
         CommittableColumn::TimestampTZ(_, _, column) => {
             compute_dory_commitment_impl(column, offset, setup)
         }
+        CommittableColumn::Nullable(values, _) => compute_dory_commitment(values, offset, setup),
     }
 }
 
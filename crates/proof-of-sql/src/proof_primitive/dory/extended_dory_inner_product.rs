@@ -1,6 +1,6 @@
 use super::{
-    scalar_product_prove, scalar_product_verify, DoryMessages, ExtendedProverState,
-    ExtendedVerifierState, ProverSetup, VerifierSetup, F,
+    scalar_product_pairing_check, scalar_product_prove, DoryMessages, ExtendedProverState,
+    ExtendedVerifierState, PairingCheck, ProverSetup, VerifierSetup, F,
 };
 use crate::{
     base::proof::Transcript,
@@ -35,28 +35,49 @@ pub fn extended_dory_inner_product_prove(
 
 /// This is the verifier side of the extended Dory-Innerproduct algorithm in section 4.3 of https://eprint.iacr.org/2020/1274.pdf.
 /// This function consumes/dequeues from `messages`, appends to `transcript`, and consumes `state`.
+#[cfg(test)]
 #[tracing::instrument(level = "debug", skip_all)]
 pub fn extended_dory_inner_product_verify(
     messages: &mut DoryMessages,
     transcript: &mut impl Transcript,
-    mut state: ExtendedVerifierState,
+    state: ExtendedVerifierState,
     setup: &VerifierSetup,
     fold_s_tensors_verify: impl Fn(&ExtendedVerifierState) -> (F, F),
 ) -> bool {
+    extended_dory_inner_product_pairing_check(
+        messages,
+        transcript,
+        state,
+        setup,
+        fold_s_tensors_verify,
+    )
+    .is_some_and(PairingCheck::verify)
+}
+
+/// This is the verifier side of the extended Dory-Innerproduct algorithm, except that the final pairing check is returned
+/// rather than done, so that the checks of many proofs can be folded together.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn extended_dory_inner_product_pairing_check(
+    messages: &mut DoryMessages,
+    transcript: &mut impl Transcript,
+    mut state: ExtendedVerifierState,
+    setup: &VerifierSetup,
+    fold_s_tensors_verify: impl Fn(&ExtendedVerifierState) -> (F, F),
+) -> Option<PairingCheck> {
     log::log_memory_usage("Start");
 
     let nu = state.base_state.nu;
     assert!(setup.max_nu >= nu);
     for _ in 0..nu {
         if !extended_dory_reduce_verify(messages, transcript, &mut state, setup) {
-            return false;
+            return None;
         }
     }
     let base_state =
         fold_scalars_0_verify(messages, transcript, state, setup, fold_s_tensors_verify);
-    let res = scalar_product_verify(messages, transcript, base_state, setup);
+    let check = scalar_product_pairing_check(messages, transcript, base_state, setup);
 
     log::log_memory_usage("End");
 
-    res
+    check
 }
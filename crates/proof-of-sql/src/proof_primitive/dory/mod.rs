@@ -52,7 +52,9 @@ mod scalar_product;
 
 #[cfg(test)]
 use dory_reduce::{dory_reduce_prove, dory_reduce_verify};
-use scalar_product::{scalar_product_prove, scalar_product_verify};
+#[cfg(test)]
+use scalar_product::scalar_product_verify;
+use scalar_product::{scalar_product_pairing_check, scalar_product_prove, PairingCheck};
 
 #[cfg(test)]
 mod dory_inner_product;
@@ -78,8 +80,10 @@ pub(crate) use fold_scalars::{fold_scalars_0_prove, fold_scalars_0_verify};
 mod fold_scalars_test;
 
 mod extended_dory_inner_product;
+#[cfg(test)]
+pub(crate) use extended_dory_inner_product::extended_dory_inner_product_verify;
 pub(crate) use extended_dory_inner_product::{
-    extended_dory_inner_product_prove, extended_dory_inner_product_verify,
+    extended_dory_inner_product_pairing_check, extended_dory_inner_product_prove,
 };
 
 #[cfg(test)]
@@ -363,10 +363,11 @@ pub fn bit_table_and_scalars_for_packed_msm(
     );
 
     // For each committable column, pack the data into the packed_scalar array.
+    // Nullable columns are committed to as their values.
     committable_columns
         .iter()
         .enumerate()
-        .for_each(|(i, column)| match column {
+        .for_each(|(i, column)| match column.values() {
             CommittableColumn::Uint8(column) => {
                 pack_bit(
                     column,
@@ -458,6 +459,9 @@ pub fn bit_table_and_scalars_for_packed_msm(
                     num_matrix_commitment_columns,
                 );
             }
+            CommittableColumn::Nullable(..) => {
+                unreachable!("the values of a column are never nullable")
+            }
         });
 
     log::log_memory_usage("End");
@@ -1,6 +1,13 @@
 #![allow(unused_variables)]
-use super::{pairings, DoryMessages, ProverState, VerifierSetup, VerifierState};
-use crate::{base::proof::Transcript, utils::log};
+use super::{
+    pairings, DeferredGT, DoryMessages, DoryScalar, G1Projective, G2Projective, ProverState,
+    VerifierSetup, VerifierState, F, GT,
+};
+use crate::{
+    base::proof::{Keccak256Transcript, Transcript},
+    utils::log,
+};
+use alloc::{vec, vec::Vec};
 
 /// This is the prover side of the Scalar-Product algorithm in section 3.1 of <https://eprint.iacr.org/2020/1274.pdf>.
 #[allow(clippy::missing_panics_doc)]
@@ -37,6 +44,7 @@ pub fn scalar_product_prove(
 }
 
 /// This is the verifier side of the Scalar-Product algorithm in section 3.1 of https://eprint.iacr.org/2020/1274.pdf.
+#[cfg(test)]
 #[tracing::instrument(level = "debug", skip_all)]
 pub fn scalar_product_verify(
     messages: &mut DoryMessages,
@@ -61,6 +69,19 @@ pub fn scalar_product_verify(
     // * `Gamma_1_0` is the Γ_1 used in Scalar-Product algorithm.
     // * `Gamma_2_0` is the Γ_2 used in Scalar-Product algorithm.
 
+    scalar_product_pairing_check(messages, transcript, state, setup)
+        .is_some_and(PairingCheck::verify)
+}
+
+/// This is the verifier side of the Scalar-Product algorithm, except that the final pairing check is returned
+/// rather than done, so that the checks of many proofs can be folded together.
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn scalar_product_pairing_check(
+    messages: &mut DoryMessages,
+    transcript: &mut impl Transcript,
+    state: VerifierState,
+    setup: &VerifierSetup,
+) -> Option<PairingCheck> {
     log::log_memory_usage("Start");
 
     assert_eq!(state.nu, 0);
@@ -68,15 +89,100 @@ pub fn scalar_product_verify(
         || messages.G2_messages.len() != 1
         || !messages.GT_messages.is_empty()
     {
-        return false;
+        return None;
     }
     let E_1 = messages.prover_recieve_G1_message(transcript);
     let E_2 = messages.prover_recieve_G2_message(transcript);
     let (d, d_inv) = messages.verifier_F_message(transcript);
-    let res = pairings::pairing(E_1 + setup.Gamma_1_0 * d, E_2 + setup.Gamma_2_0 * d_inv)
-        == (state.C + setup.chi[0] + state.D_2 * d + state.D_1 * d_inv).compute();
+    let check = PairingCheck {
+        lhs_1: (E_1 + setup.Gamma_1_0 * d).into(),
+        lhs_2: (E_2 + setup.Gamma_2_0 * d_inv).into(),
+        rhs: state.C + setup.chi[0] + state.D_2 * d + state.D_1 * d_inv,
+    };
 
     log::log_memory_usage("End");
 
-    res
+    Some(check)
+}
+
+/// The final check `e(lhs_1, lhs_2) == rhs` of the Scalar-Product algorithm.
+pub struct PairingCheck {
+    lhs_1: G1Projective,
+    lhs_2: G2Projective,
+    rhs: DeferredGT,
+}
+
+impl PairingCheck {
+    /// Performs the check.
+    pub fn verify(self) -> bool {
+        let lhs: GT = pairings::pairing(self.lhs_1, self.lhs_2);
+        lhs == self.rhs.compute()
+    }
+
+    /// Performs the checks of many proofs at once, where the proofs that failed before their check keep their error.
+    ///
+    /// The checks are folded into a single multi-pairing and MSM with random multipliers, which are drawn from
+    /// the `transcript_challenges`. These must be drawn from the transcripts of the proofs after all of their
+    /// messages, so that the multipliers are chosen after the checks are fixed.
+    /// Only if the folded check fails, the checks are done individually to find the invalid proofs.
+    #[tracing::instrument(name = "PairingCheck::verify_many", level = "debug", skip_all)]
+    pub fn verify_many<E>(
+        checks: Vec<Result<Self, E>>,
+        transcript_challenges: &[[u8; 32]],
+        verification_error: impl Fn() -> E,
+    ) -> Vec<Result<(), E>> {
+        let mut transcript = Keccak256Transcript::new();
+        transcript.extend_as_le_from_refs(transcript_challenges);
+        let multipliers: Vec<F> = checks
+            .iter()
+            .filter(|check| check.is_ok())
+            .map(|_| transcript.scalar_challenge_as_be::<DoryScalar>().0)
+            .collect();
+        let (passed_checks, mut results): (Vec<_>, Vec<_>) = checks
+            .into_iter()
+            .map(|check| match check {
+                Ok(check) => (Some(check), None),
+                Err(e) => (None, Some(Err(e))),
+            })
+            .unzip();
+        let mut passed =
+            Self::verify_folded(passed_checks.into_iter().flatten().collect(), &multipliers)
+                .into_iter();
+        for result in &mut results {
+            if result.is_none() {
+                *result = Some(if passed.next() == Some(true) {
+                    Ok(())
+                } else {
+                    Err(verification_error())
+                });
+            }
+        }
+        results.into_iter().flatten().collect()
+    }
+
+    /// Performs many checks at once, returning whether each of them passed.
+    ///
+    /// The checks are folded with the `multipliers`, which must be random and chosen after the checks are fixed.
+    fn verify_folded(checks: Vec<Self>, multipliers: &[F]) -> Vec<bool> {
+        assert_eq!(checks.len(), multipliers.len());
+        let Some(folded_rhs) = checks
+            .iter()
+            .zip(multipliers)
+            .map(|(check, &multiplier)| check.rhs.clone() * multiplier)
+            .reduce(|a, b| a + b)
+        else {
+            return Vec::new();
+        };
+        let (lhs_1, lhs_2): (Vec<_>, Vec<_>) = checks
+            .iter()
+            .zip(multipliers)
+            .map(|(check, multiplier)| (check.lhs_1 * multiplier, check.lhs_2))
+            .unzip();
+        let folded_lhs: GT = pairings::multi_pairing(lhs_1, lhs_2);
+        if folded_lhs == folded_rhs.compute() {
+            vec![true; checks.len()]
+        } else {
+            checks.into_iter().map(Self::verify).collect()
+        }
+    }
 }
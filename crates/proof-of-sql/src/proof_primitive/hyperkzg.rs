@@ -1,5 +1,7 @@
 use crate::base::{
-    commitment::{Commitment, CommitmentEvaluationProof, CommittableColumn},
+    commitment::{
+        BatchedProofVerification, Commitment, CommitmentEvaluationProof, CommittableColumn,
+    },
     proof::{Keccak256Transcript, Transcript},
     scalar::{MontScalar, Scalar},
     slice_ops,
@@ -15,13 +17,14 @@ use nova_snark::{
         hyperkzg::{
             CommitmentEngine, CommitmentKey, EvaluationArgument, EvaluationEngine, VerifierKey,
         },
+        traits::{DlogGroup, PairingGroup},
     },
     traits::{
         commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait, Engine,
         TranscriptEngineTrait, TranscriptReprTrait,
     },
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tracing::{span, Level};
 
 /// The scalar used in the `HyperKZG` PCS. This is the BN254 scalar.
@@ -137,6 +140,9 @@ impl Commitment for HyperKZGCommitment {
                 | CommittableColumn::VarBinary(vals) => {
                     compute_commitments_impl(setup, offset, vals)
                 }
+                CommittableColumn::Nullable(values, _) => {
+                    Self::compute_commitments(&[values.as_ref().clone()], offset, setup).remove(0)
+                }
             })
             .collect()
     }
@@ -237,42 +243,282 @@ impl CommitmentEvaluationProof for HyperKZGCommitmentEvaluationProof {
         if generators_offset != 0 {
             Err(NovaError::InvalidPCS)?;
         }
-        let nova_commit = commit_batch
-            .iter()
-            .zip(batching_factors)
-            .map(|(c, m)| c.commitment * NovaScalar::from(m))
-            .fold(NovaCommitment::default(), Add::add);
-        let nova_eval = evaluations
-            .iter()
-            .zip(batching_factors)
-            .map(|(&e, &f)| e * f)
-            .sum::<Self::Scalar>();
-        let mut nova_point = slice_ops::slice_cast(b_point);
-        nova_point.reverse();
-        if nova_point.is_empty() {
-            nova_point.push(NovaScalar::ZERO);
-        }
+        let (nova_commit, nova_eval, nova_point) =
+            fold_batch(commit_batch, batching_factors, evaluations, b_point);
         transcript.wrap_transcript(|keccak_transcript| {
             EvaluationEngine::verify(
                 setup,
                 keccak_transcript,
                 &nova_commit,
                 &nova_point,
-                &nova_eval.into(),
+                &nova_eval,
                 self,
             )
         })
     }
+
+    #[tracing::instrument(
+        name = "HyperKZGCommitmentEvaluationProof::verify_batched_proofs",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proofs<T: Transcript + Clone>(
+        mut verifications: Vec<BatchedProofVerification<'_, Self, T>>,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Vec<Result<(), Self::Error>> {
+        let Some(verifier_key) = reinterpret::<_, VerifierKeyMessages>(*setup) else {
+            return verifications
+                .into_iter()
+                .map(|verification| verification.verify(setup))
+                .collect();
+        };
+        let (checks, initial_transcripts): (Vec<_>, Vec<_>) = verifications
+            .iter_mut()
+            .map(|verification| {
+                let initial_transcript = verification.transcript.clone();
+                (
+                    pairing_check(verification, &verifier_key),
+                    initial_transcript,
+                )
+            })
+            .unzip();
+        let mut transcript = <Keccak256Transcript as Transcript>::new();
+        transcript.extend_as_le_from_refs(
+            &verifications
+                .iter()
+                .map(|verification| verification.transcript.clone().challenge_as_le())
+                .collect::<Vec<_>>(),
+        );
+        let multipliers: Vec<NovaScalar> = checks
+            .iter()
+            .map(|_| transcript.scalar_challenge_as_be::<BNScalar>().into())
+            .collect();
+        let passed = PairingCheck::verify_many(&checks, &multipliers, &verifier_key);
+        // Whatever did not pass the replayed check is left to `nova_snark`,
+        // starting from the transcript it would have started from.
+        verifications
+            .into_iter()
+            .zip(initial_transcripts)
+            .zip(passed)
+            .map(|((verification, initial_transcript), passed)| {
+                if passed {
+                    Ok(())
+                } else {
+                    *verification.transcript = initial_transcript;
+                    verification.verify(setup)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Folds the commitments and evaluations of a batch with the batching factors
+/// and converts the point to `nova_snark`'s big-endian order.
+fn fold_batch(
+    commit_batch: &[HyperKZGCommitment],
+    batching_factors: &[BNScalar],
+    evaluations: &[BNScalar],
+    b_point: &[BNScalar],
+) -> (NovaCommitment, NovaScalar, Vec<NovaScalar>) {
+    let nova_commit = commit_batch
+        .iter()
+        .zip(batching_factors)
+        .map(|(c, m)| c.commitment * NovaScalar::from(m))
+        .fold(NovaCommitment::default(), Add::add);
+    let nova_eval = evaluations
+        .iter()
+        .zip(batching_factors)
+        .map(|(&e, &f)| e * f)
+        .sum::<BNScalar>();
+    let mut nova_point = slice_ops::slice_cast(b_point);
+    nova_point.reverse();
+    if nova_point.is_empty() {
+        nova_point.push(NovaScalar::ZERO);
+    }
+    (nova_commit, nova_eval.into(), nova_point)
+}
+
+type G1 = <HyperKZGEngine as Engine>::GE;
+type G2 = <G1 as PairingGroup>::G2;
+type G1Affine = <G1 as DlogGroup>::AffineGroupElement;
+type G2Affine = <G2 as DlogGroup>::AffineGroupElement;
+
+/// The messages of a [`HyperKZGCommitmentEvaluationProof`], which `nova_snark` keeps private.
+/// The fields are in the same order as the fields of `EvaluationArgument`.
+#[derive(Deserialize)]
+struct EvaluationArgumentMessages {
+    com: Vec<G1Affine>,
+    w: Vec<G1Affine>,
+    v: Vec<Vec<NovaScalar>>,
+}
+
+/// The parts of a `HyperKZG` [`VerifierKey`], which `nova_snark` keeps private.
+/// The fields are in the same order as the fields of `VerifierKey`.
+#[derive(Deserialize)]
+struct VerifierKeyMessages {
+    g: G1Affine,
+    h: G2Affine,
+    tau_h: G2Affine,
+}
+
+/// Reads a `nova_snark` type as a type with the same serialized layout.
+fn reinterpret<T: Serialize, M: DeserializeOwned>(value: &T) -> Option<M> {
+    let bytes = bincode::serde::encode_to_vec(value, bincode::config::legacy()).ok()?;
+    bincode::serde::decode_from_slice(&bytes, bincode::config::legacy())
+        .ok()
+        .map(|(messages, _)| messages)
+}
+
+/// The final check of a `HyperKZG` proof, which passes if `e(lhs, H) = e(rhs, tau_H)`.
+struct PairingCheck {
+    lhs: G1,
+    rhs: G1,
+}
+
+impl PairingCheck {
+    /// Performs the check.
+    fn verify(&self, verifier_key: &VerifierKeyMessages) -> bool {
+        G1::pairing(&self.lhs, &G2::group(&verifier_key.h))
+            == G1::pairing(&self.rhs, &G2::group(&verifier_key.tau_h))
+    }
+
+    /// Performs many checks at once, returning whether each of them passed.
+    /// A missing check does not pass.
+    ///
+    /// The checks are folded with the `multipliers`, which must be random and chosen after the checks are fixed.
+    /// Only if the folded check fails, the checks are done individually to find the invalid proofs.
+    fn verify_many(
+        checks: &[Option<Self>],
+        multipliers: &[NovaScalar],
+        verifier_key: &VerifierKeyMessages,
+    ) -> Vec<bool> {
+        assert_eq!(checks.len(), multipliers.len());
+        let folded = checks
+            .iter()
+            .zip(multipliers)
+            .filter_map(|(check, &multiplier)| {
+                check.as_ref().map(|check| Self {
+                    lhs: check.lhs * multiplier,
+                    rhs: check.rhs * multiplier,
+                })
+            })
+            .reduce(|a, b| Self {
+                lhs: a.lhs + b.lhs,
+                rhs: a.rhs + b.rhs,
+            });
+        if folded.is_some_and(|folded| folded.verify(verifier_key)) {
+            checks.iter().map(Option::is_some).collect()
+        } else {
+            checks
+                .iter()
+                .map(|check| {
+                    check
+                        .as_ref()
+                        .is_some_and(|check| check.verify(verifier_key))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Replays `nova_snark`'s verification of a proof up to its final pairing check, which is returned.
+///
+/// This returns `None` whenever the replay cannot decide the proof,
+/// in which case it must be verified by `nova_snark` instead.
+fn pairing_check(
+    verification: &mut BatchedProofVerification<
+        '_,
+        HyperKZGCommitmentEvaluationProof,
+        impl Transcript,
+    >,
+    verifier_key: &VerifierKeyMessages,
+) -> Option<PairingCheck> {
+    if verification.generators_offset != 0 {
+        return None;
+    }
+    let EvaluationArgumentMessages { com, w, v } = reinterpret(verification.proof)?;
+    let (nova_commit, nova_eval, point) = fold_batch(
+        verification.commit_batch,
+        verification.batching_factors,
+        verification.evaluations,
+        verification.b_point,
+    );
+    let commitment: G1 = reinterpret(&nova_commit)?;
+    let ell = point.len();
+    if com.len() + 1 != ell || w.len() != 3 || v.len() != 3 || v.iter().any(|v_j| v_j.len() != ell)
+    {
+        return None;
+    }
+    verification
+        .transcript
+        .wrap_transcript(|transcript: &mut Keccak256Transcript| {
+            TranscriptEngineTrait::<HyperKZGEngine>::absorb(transcript, b"c", &com.as_slice());
+            let r = TranscriptEngineTrait::<HyperKZGEngine>::squeeze(transcript, b"c").ok()?;
+            if r == NovaScalar::ZERO || commitment == G1::zero() {
+                return None;
+            }
+            // Check the consistency of v with the point and the evaluation
+            let two = NovaScalar::from(2_u64);
+            let consistent = (0..ell).all(|i| {
+                let y = v[2].get(i + 1).copied().unwrap_or(nova_eval);
+                let x_i = point[ell - i - 1];
+                two * r * y
+                    == r * (NovaScalar::ONE - x_i) * (v[0][i] + v[1][i]) + x_i * (v[0][i] - v[1][i])
+            });
+            if !consistent {
+                return None;
+            }
+            let flattened_v: Vec<_> = v.iter().flatten().copied().collect();
+            TranscriptEngineTrait::<HyperKZGEngine>::absorb(
+                transcript,
+                b"v",
+                &flattened_v.as_slice(),
+            );
+            let q = TranscriptEngineTrait::<HyperKZGEngine>::squeeze(transcript, b"r").ok()?;
+            TranscriptEngineTrait::<HyperKZGEngine>::absorb(transcript, b"W", &w.as_slice());
+            let d_0 = TranscriptEngineTrait::<HyperKZGEngine>::squeeze(transcript, b"d").ok()?;
+            let d_1 = d_0 * d_0;
+
+            let q_powers: Vec<_> =
+                core::iter::successors(Some(NovaScalar::ONE), |&power| Some(power * q))
+                    .take(ell)
+                    .collect();
+            let [b_0, b_1, b_2] = [0, 1, 2].map(|j| {
+                q_powers
+                    .iter()
+                    .zip(&v[j])
+                    .map(|(&power, &v_ji)| power * v_ji)
+                    .sum::<NovaScalar>()
+            });
+            let [w_0, w_1, w_2] = [0, 1, 2].map(|j| G1::group(&w[j]));
+            let lhs = core::iter::once(commitment)
+                .chain(com.iter().map(G1::group))
+                .zip(&q_powers)
+                .map(|(c, &power)| c * (power * (NovaScalar::ONE + d_0 + d_1)))
+                .fold(G1::zero(), Add::add)
+                + w_0 * r
+                + w_1 * (-r * d_0)
+                + w_2 * (r * r * d_1)
+                - G1::group(&verifier_key.g) * (b_0 + d_0 * b_1 + d_1 * b_2);
+            let rhs = w_0 + w_1 * d_0 + w_2 * d_1;
+            Some(PairingCheck { lhs, rhs })
+        })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::base::{
-        commitment::commitment_evaluation_proof_test::{
-            test_commitment_evaluation_proof_with_length_1,
-            test_random_commitment_evaluation_proof, test_simple_commitment_evaluation_proof,
+        commitment::{
+            commitment_evaluation_proof_test::{
+                test_batched_commitment_evaluation_proofs,
+                test_commitment_evaluation_proof_with_length_1,
+                test_random_commitment_evaluation_proof, test_simple_commitment_evaluation_proof,
+            },
+            VecCommitmentExt,
         },
+        database::Column,
+        polynomial::compute_evaluation_vector,
         scalar::test_scalar_constants,
     };
     use ark_std::UniformRand;
@@ -333,6 +579,143 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_verify_many_hyperkzg_evaluation_proofs_at_once() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+        let (_, vk) = EvaluationEngine::setup(&ck);
+        test_batched_commitment_evaluation_proofs::<HyperKZGCommitmentEvaluationProof>(&&ck, &&vk);
+    }
+
+    /// Proves the evaluation of a random vector of the given length at a random point,
+    /// returning the proof, the vector, its evaluation and the point.
+    fn random_proof(
+        length: usize,
+        ck: &CommitmentKey<HyperKZGEngine>,
+    ) -> (
+        HyperKZGCommitmentEvaluationProof,
+        Vec<BNScalar>,
+        BNScalar,
+        Vec<BNScalar>,
+    ) {
+        let mut rng = ark_std::test_rng();
+        let nu = length.next_power_of_two().trailing_zeros() as usize;
+        let a: Vec<_> = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+            .take(length)
+            .collect();
+        let b_point: Vec<_> = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+            .take(nu)
+            .collect();
+        let mut transcript = <Keccak256Transcript as Transcript>::new();
+        let proof = HyperKZGCommitmentEvaluationProof::new(&mut transcript, &a, &b_point, 0, &ck);
+        let mut b = vec![BNScalar::ZERO; length];
+        compute_evaluation_vector(&mut b, &b_point);
+        let evaluation = a.iter().zip(&b).map(|(&a, &b)| a * b).sum();
+        (proof, a, evaluation, b_point)
+    }
+
+    #[test]
+    fn we_can_replay_the_pairing_check_of_hyperkzg_evaluation_proofs() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+        let (_, vk) = EvaluationEngine::setup(&ck);
+        let verifier_key: VerifierKeyMessages = reinterpret(&vk).unwrap();
+        for length in [1, 2, 5, 16] {
+            let (proof, a, evaluation, b_point) = random_proof(length, &ck);
+            let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, &&ck);
+            let mut transcript = <Keccak256Transcript as Transcript>::new();
+            let check = pairing_check(
+                &mut BatchedProofVerification {
+                    proof: &proof,
+                    transcript: &mut transcript,
+                    commit_batch: &commits,
+                    batching_factors: &[BNScalar::ONE],
+                    evaluations: &[evaluation],
+                    b_point: &b_point,
+                    generators_offset: 0,
+                    table_length: length,
+                },
+                &verifier_key,
+            )
+            .unwrap();
+            assert!(check.verify(&verifier_key));
+
+            // The transcript ends as if the proof was verified by nova
+            let mut nova_transcript = <Keccak256Transcript as Transcript>::new();
+            assert!(proof
+                .verify_proof(
+                    &mut nova_transcript,
+                    &commits[0],
+                    &evaluation,
+                    &b_point,
+                    0,
+                    length,
+                    &&vk,
+                )
+                .is_ok());
+            assert_eq!(
+                transcript.challenge_as_le(),
+                nova_transcript.challenge_as_le()
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_isolate_an_invalid_hyperkzg_evaluation_proof_in_a_batch() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+        let (_, vk) = EvaluationEngine::setup(&ck);
+        let verifier_key: VerifierKeyMessages = reinterpret(&vk).unwrap();
+        let cases: Vec<_> = [5, 16, 2]
+            .into_iter()
+            .map(|length| {
+                let (proof, mut a, evaluation, b_point) = random_proof(length, &ck);
+                if length == 16 {
+                    // The proof is checked against the commitment to a different vector,
+                    // which is only caught by the pairing check.
+                    a[3] += BNScalar::ONE;
+                }
+                let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, &&ck);
+                (proof, commits, evaluation, b_point, length)
+            })
+            .collect();
+        let mut transcripts = vec![<Keccak256Transcript as Transcript>::new(); cases.len()];
+        let mut verifications: Vec<_> = cases
+            .iter()
+            .zip(&mut transcripts)
+            .map(
+                |((proof, commits, evaluation, b_point, length), transcript)| {
+                    BatchedProofVerification {
+                        proof,
+                        transcript,
+                        commit_batch: commits,
+                        batching_factors: &[BNScalar::ONE],
+                        evaluations: core::slice::from_ref(evaluation),
+                        b_point,
+                        generators_offset: 0,
+                        table_length: *length,
+                    }
+                },
+            )
+            .collect();
+
+        let mut checks: Vec<_> = verifications
+            .iter_mut()
+            .map(|verification| pairing_check(verification, &verifier_key))
+            .collect();
+        assert!(checks.iter().all(Option::is_some));
+        let multipliers = [2_u64, 3, 5].map(NovaScalar::from);
+        assert_eq!(
+            PairingCheck::verify_many(&checks, &multipliers, &verifier_key),
+            [true, false, true]
+        );
+        assert_eq!(
+            PairingCheck::verify_many(
+                &[checks[0].take(), None, checks[2].take()],
+                &multipliers,
+                &verifier_key
+            ),
+            [true, false, true]
+        );
+    }
+
     #[test]
     fn we_can_create_hyperkzg_evaluation_proofs_with_various_lengths() {
         let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 128);
@@ -47,6 +47,10 @@ impl FilterExec {
         table_refs: &IndexSet<TableRef>,
         column_refs: &IndexSet<ColumnRef>,
     ) -> Result<Self, Error> {
+        // Filters over the result of another plan are not supported yet
+        if plan.table.input.is_some() {
+            return Err(Error::NotSupported);
+        }
         Ok(Self {
             table_number: table_refs
                 .get_index_of(&plan.table.table_ref)
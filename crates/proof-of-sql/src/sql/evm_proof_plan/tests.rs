@@ -38,7 +38,7 @@ fn we_can_generate_serialized_proof_plan_for_simple_filter() {
             expr: DynProofExpr::Column(ColumnExpr::new(column_ref_b)),
             alias: identifier_alias,
         }],
-        TableExpr { table_ref },
+        TableExpr::new(table_ref),
         DynProofExpr::Equals(EqualsExpr::new(
             Box::new(DynProofExpr::Column(ColumnExpr::new(column_ref_a))),
             Box::new(DynProofExpr::Literal(LiteralExpr::new(
@@ -51,6 +51,8 @@ pub enum BinaryOperator {
     Multiply,
     /// Divide
     Divide,
+    /// Modulo
+    Modulo,
 }
 
 impl TryFrom<ast::BinaryOperator> for BinaryOperator {
@@ -70,6 +72,7 @@ impl TryFrom<ast::BinaryOperator> for BinaryOperator {
             ast::BinaryOperator::Minus => Ok(BinaryOperator::Minus),
             ast::BinaryOperator::Multiply => Ok(BinaryOperator::Multiply),
             ast::BinaryOperator::Divide => Ok(BinaryOperator::Divide),
+            ast::BinaryOperator::Modulo => Ok(BinaryOperator::Modulo),
             _ => Err(LogicalPlanError::UnsupportedBinaryOperator { op }),
         }
     }
@@ -96,6 +99,7 @@ mod tests {
             (ast::BinaryOperator::Minus, BinaryOperator::Minus),
             (ast::BinaryOperator::Multiply, BinaryOperator::Multiply),
             (ast::BinaryOperator::Divide, BinaryOperator::Divide),
+            (ast::BinaryOperator::Modulo, BinaryOperator::Modulo),
         ];
 
         for (sql_op, expected) in test_cases {
@@ -1,8 +1,8 @@
 use super::ConversionError;
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue},
-        map::IndexMap,
+        database::{ColumnComponent, ColumnRef, ColumnType, LikePattern, LiteralValue},
+        map::{IndexMap, IndexSet},
         math::{
             decimal::{DecimalError, Precision},
             i256::I256,
@@ -12,15 +12,22 @@ use crate::{
     sql::{
         parse::{
             dyn_proof_expr_builder::DecimalError::{InvalidPrecision, InvalidScale},
+            query_context::subquery_table_ref,
             ConversionError::DecimalConversionError,
         },
-        proof_exprs::{ColumnExpr, DynProofExpr, ProofExpr},
+        proof_exprs::{
+            AliasedDynProofExpr, ColumnExpr, DateTimeFunction, DynProofExpr, ProofExpr, TableExpr,
+        },
+        proof_plans::{DynProofPlan, ProjectionExec},
     },
 };
-use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString, vec, vec::Vec};
 use proof_of_sql_parser::{
-    intermediate_ast::{AggregationOperator, Expression, Literal},
+    intermediate_ast::{
+        self, AggregationOperator, Expression, Literal, SelectResultExpr, SetExpression,
+    },
     posql_time::{PoSQLTimeUnit, PoSQLTimestampError},
+    SelectStatement,
 };
 use sqlparser::ast::{BinaryOperator, Ident, UnaryOperator};
 
@@ -28,6 +35,9 @@ use sqlparser::ast::{BinaryOperator, Ident, UnaryOperator};
 /// a `proof_of_sql_parser::intermediate_ast::Expression`.
 pub struct DynProofExprBuilder<'a> {
     column_mapping: &'a IndexMap<Ident, ColumnRef>,
+    nullable_columns: Option<&'a IndexSet<ColumnRef>>,
+    character_counts: Option<&'a IndexMap<ColumnRef, usize>>,
+    subquery_plans: Option<&'a IndexMap<SelectStatement, DynProofPlan>>,
     in_agg_scope: bool,
 }
 
@@ -36,6 +46,9 @@ impl<'a> DynProofExprBuilder<'a> {
     pub fn new(column_mapping: &'a IndexMap<Ident, ColumnRef>) -> Self {
         Self {
             column_mapping,
+            nullable_columns: None,
+            character_counts: None,
+            subquery_plans: None,
             in_agg_scope: false,
         }
     }
@@ -43,33 +56,302 @@ impl<'a> DynProofExprBuilder<'a> {
     pub(crate) fn new_agg(column_mapping: &'a IndexMap<Ident, ColumnRef>) -> Self {
         Self {
             column_mapping,
+            nullable_columns: None,
+            character_counts: None,
+            subquery_plans: None,
             in_agg_scope: true,
         }
     }
+    /// Sets the columns of the mapping that are nullable, which no column is by default.
+    #[must_use]
+    pub(crate) fn with_nullable_columns(
+        mut self,
+        nullable_columns: &'a IndexSet<ColumnRef>,
+    ) -> Self {
+        self.nullable_columns = Some(nullable_columns);
+        self
+    }
+    /// Sets the number of first and last characters of the columns of the mapping
+    /// whose character components are committed to, which is zero for any column by default.
+    #[must_use]
+    pub(crate) fn with_character_counts(
+        mut self,
+        character_counts: &'a IndexMap<ColumnRef, usize>,
+    ) -> Self {
+        self.character_counts = Some(character_counts);
+        self
+    }
+    /// Sets the plans of the subqueries that the expressions may contain.
+    #[must_use]
+    pub(crate) fn with_subquery_plans(
+        mut self,
+        subquery_plans: &'a IndexMap<SelectStatement, DynProofPlan>,
+    ) -> Self {
+        self.subquery_plans = Some(subquery_plans);
+        self
+    }
     /// Builds a `proofs::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression`
     pub fn build(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
         self.visit_expr(expr)
     }
+    /// Builds the expression of a column of the mapping, which is nullable if the column is.
+    pub(crate) fn build_column(&self, column_ref: &ColumnRef) -> DynProofExpr {
+        let column = DynProofExpr::new_column(column_ref.clone());
+        if self.is_nullable_column(column_ref) {
+            DynProofExpr::try_new_nullable(column, DynProofExpr::new_column(column_ref.presence()))
+                .expect("the presence of a column is boolean")
+        } else {
+            column
+        }
+    }
+    /// Whether `expr` can be NULL, which is the case if it uses a nullable column.
+    pub(crate) fn is_nullable(&self, expr: &Expression) -> bool {
+        if let Some(column_id) = self.column_ident(expr) {
+            return self
+                .column_mapping
+                .get(&column_id)
+                .is_some_and(|column_ref| self.is_nullable_column(column_ref));
+        }
+        match expr {
+            Expression::Binary { left, right, .. } => {
+                self.is_nullable(left) || self.is_nullable(right)
+            }
+            Expression::Unary { expr, .. }
+            | Expression::Like { expr, .. }
+            | Expression::Extract { expr, .. }
+            | Expression::DateTrunc { expr, .. }
+            | Expression::AddInterval { expr, .. } => self.is_nullable(expr),
+            Expression::InList { expr, list } => {
+                self.is_nullable(expr) || list.iter().any(|candidate| self.is_nullable(candidate))
+            }
+            Expression::Between { expr, low, high } => {
+                self.is_nullable(expr) || self.is_nullable(low) || self.is_nullable(high)
+            }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                when_then.iter().any(|(_, then)| self.is_nullable(then))
+                    || else_expr
+                        .as_ref()
+                        .map_or(true, |else_expr| self.is_nullable(else_expr))
+            }
+            // Only a count is a row even if it counts no rows
+            Expression::ScalarSubquery(subquery) => !is_count(subquery),
+            _ => false,
+        }
+    }
 }
 
 #[allow(clippy::match_wildcard_for_single_variants)]
 // Private interface
 impl DynProofExprBuilder<'_> {
+    /// Builds `expr`, whose values are the default value of its type where it is NULL.
     fn visit_expr(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
+        match expr {
+            // Logical connectives follow three-valued logic on their own
+            Expression::Unary { .. }
+            | Expression::Binary {
+                op: intermediate_ast::BinaryOperator::And | intermediate_ast::BinaryOperator::Or,
+                ..
+            } => self.visit_value(expr),
+            Expression::IsNull(inner) => {
+                Ok(DynProofExpr::new_is_null(self.visit_expr(inner)?, false))
+            }
+            Expression::IsNotNull(inner) => {
+                Ok(DynProofExpr::new_is_null(self.visit_expr(inner)?, true))
+            }
+            _ if !self.is_nullable(expr) => self.visit_value(expr),
+            _ => {
+                // `IN` and `BETWEEN` are NULL exactly where the comparisons they stand for are
+                if let Some(comparisons) = expr.to_comparisons() {
+                    return self.visit_expr(&comparisons);
+                }
+                let value = self.visit_value(expr)?;
+                match self.visit_presence(expr)? {
+                    Some(presence) => DynProofExpr::try_new_nullable(value, presence),
+                    None => Ok(value),
+                }
+            }
+        }
+    }
+
+    /// Builds `expr` regardless of where it is NULL, see [`Self::visit_expr`].
+    fn visit_value(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
         match expr {
             Expression::Column(identifier) => self.visit_column((*identifier).into()),
+            Expression::QualifiedColumn { .. } => {
+                self.visit_column(self.column_ident(expr).ok_or_else(|| {
+                    ConversionError::InvalidExpression {
+                        expression: format!("column {expr:?} is not part of the query"),
+                    }
+                })?)
+            }
             Expression::Literal(lit) => self.visit_literal(lit),
             Expression::Binary { op, left, right } => {
                 self.visit_binary_expr(&(*op).into(), left, right)
             }
             Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
-            Expression::Aggregation { op, expr } => self.visit_aggregate_expr(*op, expr),
+            Expression::Aggregation { op, expr, filter } => {
+                self.visit_aggregate_expr(*op, expr, filter.as_deref())
+            }
+            Expression::IsNull(_) | Expression::IsNotNull(_) => self.visit_expr(expr),
+            Expression::InList { expr, list } => self.visit_in_list(expr, list),
+            Expression::Between { expr, low, high } => DynProofExpr::try_new_between(
+                self.visit_value(expr)?,
+                self.visit_value(low)?,
+                self.visit_value(high)?,
+            ),
+            Expression::Like { expr, pattern } => self.visit_like(expr, pattern),
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => self.visit_case(when_then, else_expr.as_deref()),
+            Expression::InSubquery { expr, subquery } => self.visit_in_subquery(expr, subquery),
+            Expression::ScalarSubquery(subquery) => {
+                DynProofExpr::try_new_scalar_subquery(self.subquery_plan(subquery)?)
+            }
+            Expression::Extract { field, expr } => DynProofExpr::try_new_date_time(
+                self.visit_value(expr)?,
+                DateTimeFunction::Extract(*field),
+            ),
+            Expression::DateTrunc { field, expr } => DynProofExpr::try_new_date_time(
+                self.visit_value(expr)?,
+                DateTimeFunction::Truncate(*field),
+            ),
+            Expression::AddInterval { expr, interval } => DynProofExpr::try_new_date_time(
+                self.visit_value(expr)?,
+                DateTimeFunction::AddInterval(*interval),
+            ),
             _ => Err(ConversionError::Unprovable {
                 error: format!("Expression {expr:?} is not supported yet"),
             }),
         }
     }
 
+    /// Builds the presence of `expr`, which is `true` where it is not NULL,
+    /// or `None` if `expr` is never NULL.
+    fn visit_presence(&self, expr: &Expression) -> Result<Option<DynProofExpr>, ConversionError> {
+        match expr {
+            _ if !self.is_nullable(expr) => Ok(None),
+            Expression::Column(_) | Expression::QualifiedColumn { .. } => {
+                let DynProofExpr::Column(column) = self.visit_value(expr)? else {
+                    unreachable!("columns are built as column expressions")
+                };
+                Ok(Some(DynProofExpr::new_column(
+                    column.get_column_reference().presence(),
+                )))
+            }
+            // A scalar subquery is NULL where its result is empty, i.e. it has no row to project `true` from
+            Expression::ScalarSubquery(subquery) => {
+                let table_ref = subquery_table_ref();
+                let presence = DynProofPlan::Projection(ProjectionExec::new(
+                    vec![AliasedDynProofExpr {
+                        expr: DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+                        alias: Ident::new("__present__"),
+                    }],
+                    TableExpr::new_with_input(table_ref, self.subquery_plan(subquery)?),
+                ));
+                Ok(Some(DynProofExpr::try_new_scalar_subquery(presence)?))
+            }
+            // A `LIKE` predicate or a date/time function is NULL exactly where its argument is
+            Expression::Like { expr, .. }
+            | Expression::Extract { expr, .. }
+            | Expression::DateTrunc { expr, .. }
+            | Expression::AddInterval { expr, .. } => self.visit_presence(expr),
+            // A logical connective proves where it is NULL itself
+            Expression::Unary { .. }
+            | Expression::Binary {
+                op: intermediate_ast::BinaryOperator::And | intermediate_ast::BinaryOperator::Or,
+                ..
+            }
+            | Expression::InList { .. }
+            | Expression::Between { .. } => Ok(Some(DynProofExpr::new_is_null(
+                self.visit_expr(expr)?,
+                true,
+            ))),
+            Expression::Binary { left, right, .. } => {
+                and_conditions(self.visit_presence(left)?, self.visit_presence(right)?)
+            }
+            // A `CASE` expression is present where the selected branch is
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                let branch_presence =
+                    |branch: &Expression| {
+                        Ok::<_, ConversionError>(self.visit_presence(branch)?.unwrap_or_else(
+                            || DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+                        ))
+                    };
+                let else_presence = match else_expr {
+                    Some(else_expr) => branch_presence(else_expr)?,
+                    None => DynProofExpr::new_literal(LiteralValue::Boolean(false)),
+                };
+                when_then
+                    .iter()
+                    .rev()
+                    .try_fold(else_presence, |else_presence, (condition, then)| {
+                        DynProofExpr::try_new_case(
+                            self.visit_expr(condition)?,
+                            branch_presence(then)?,
+                            else_presence,
+                        )
+                    })
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Whether the column that `column_ref` refers to is nullable
+    fn is_nullable_column(&self, column_ref: &ColumnRef) -> bool {
+        self.nullable_columns
+            .is_some_and(|nullable_columns| nullable_columns.contains(column_ref))
+    }
+
+    /// The plan of a subquery, which the builder has to be given
+    fn subquery_plan(&self, subquery: &SelectStatement) -> Result<DynProofPlan, ConversionError> {
+        self.subquery_plans
+            .and_then(|subquery_plans| subquery_plans.get(subquery))
+            .cloned()
+            .ok_or_else(|| ConversionError::Unprovable {
+                error: "subqueries are not supported at this location".to_string(),
+            })
+    }
+
+    /// Builds an `IN` subquery predicate, whose expression can not be NULL
+    fn visit_in_subquery(
+        &self,
+        expr: &Expression,
+        subquery: &SelectStatement,
+    ) -> Result<DynProofExpr, ConversionError> {
+        if self.is_nullable(expr) {
+            return Err(ConversionError::Unprovable {
+                error: "IN subqueries can not be proven on nullable expressions".to_string(),
+            });
+        }
+        DynProofExpr::try_new_in_subquery(self.visit_value(expr)?, self.subquery_plan(subquery)?)
+    }
+
+    /// The identifier of the column that `expr` refers to under the column mapping, if it is a column
+    ///
+    /// A qualified column is the column of the mapping with its table and name.
+    fn column_ident(&self, expr: &Expression) -> Option<Ident> {
+        match expr {
+            Expression::Column(identifier) => Some((*identifier).into()),
+            Expression::QualifiedColumn { table, column } => self
+                .column_mapping
+                .iter()
+                .find(|(_, column_ref)| {
+                    *column_ref.table_ref().table_id() == Ident::from(*table)
+                        && column_ref.column_id() == Ident::from(*column)
+                })
+                .map(|(ident, _)| ident.clone()),
+            _ => None,
+        }
+    }
+
     fn visit_column(&self, identifier: Ident) -> Result<DynProofExpr, ConversionError> {
         Ok(DynProofExpr::Column(ColumnExpr::new(
             self.column_mapping
@@ -164,38 +446,45 @@ impl DynProofExprBuilder<'_> {
                 DynProofExpr::try_new_or(left?, right?)
             }
             BinaryOperator::Eq => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_equals(left?, right?)
             }
             BinaryOperator::Gt => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_inequality(left?, right?, false)
             }
             BinaryOperator::Lt => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_inequality(left?, right?, true)
             }
             BinaryOperator::Plus => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_add(left?, right?)
             }
             BinaryOperator::Minus => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_subtract(left?, right?)
             }
             BinaryOperator::Multiply => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
                 DynProofExpr::try_new_multiply(left?, right?)
             }
-            BinaryOperator::Divide => Err(ConversionError::Unprovable {
-                error: format!("Binary operator {op:?} is not supported at this location"),
-            }),
+            BinaryOperator::Divide => {
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
+                DynProofExpr::try_new_divide(left?, right?)
+            }
+            BinaryOperator::Modulo => {
+                let left = self.visit_value(left);
+                let right = self.visit_value(right);
+                DynProofExpr::try_new_modulo(left?, right?)
+            }
             _ => {
                 // Handle unsupported binary operations
                 Err(ConversionError::UnsupportedOperation {
@@ -205,29 +494,260 @@ impl DynProofExprBuilder<'_> {
         }
     }
 
+    fn visit_in_list(
+        &self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> Result<DynProofExpr, ConversionError> {
+        let expr = self.visit_value(expr)?;
+        let list = list
+            .iter()
+            .map(|candidate| self.visit_value(candidate))
+            .collect::<Result<_, _>>()?;
+        DynProofExpr::try_new_in_list(expr, list)
+    }
+
+    /// Builds a `LIKE` predicate from the character components of its string,
+    /// which has to be a column.
+    ///
+    /// Only the characters before the first `%` and after the last `%` of the pattern can be checked,
+    /// and each of them needs its character component to be committed to.
+    fn visit_like(
+        &self,
+        expr: &Expression,
+        pattern: &str,
+    ) -> Result<DynProofExpr, ConversionError> {
+        let Some(column_id) = self.column_ident(expr) else {
+            return Err(ConversionError::Unprovable {
+                error: format!("LIKE can only be proven on columns, not on {expr:?}"),
+            });
+        };
+        let column_ref = self
+            .column_mapping
+            .get(&column_id)
+            .cloned()
+            .ok_or_else(|| ConversionError::MissingColumnWithoutTable {
+                identifier: Box::new(column_id.clone()),
+            })?;
+        let data_type = *column_ref.column_type();
+        if data_type != ColumnType::VarChar {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::VarChar,
+                actual: data_type,
+            });
+        }
+        let like_pattern = LikePattern::new(pattern);
+        if like_pattern.has_infix() {
+            return Err(ConversionError::Unprovable {
+                error: format!(
+                    "LIKE pattern '{pattern}' has characters between its first and last '%'"
+                ),
+            });
+        }
+        let character_count = self
+            .character_counts
+            .and_then(|character_counts| character_counts.get(&column_ref))
+            .copied()
+            .unwrap_or_default();
+        if like_pattern.character_count() > character_count {
+            return Err(ConversionError::Unprovable {
+                error: format!(
+                    "LIKE pattern '{pattern}' on column '{column_id}' needs more characters than the {character_count} committed to"
+                ),
+            });
+        }
+        let length = DynProofExpr::new_column(column_ref.with_component(ColumnComponent::Length));
+        let min_length = DynProofExpr::new_literal(LiteralValue::BigInt(
+            like_pattern.min_length().try_into().map_err(|_| {
+                ConversionError::InvalidExpression {
+                    expression: format!("LIKE pattern '{pattern}' is too long"),
+                }
+            })?,
+        ));
+        let length_condition = if like_pattern.is_exact() {
+            DynProofExpr::try_new_equals(length, min_length)?
+        } else {
+            DynProofExpr::try_new_not(DynProofExpr::try_new_inequality(length, min_length, true)?)?
+        };
+        let characters = like_pattern
+            .character_constraints()
+            .into_iter()
+            .map(|(component, character)| (column_ref.with_component(component), character))
+            .collect();
+        DynProofExpr::try_new_like(length_condition, characters)
+    }
+
+    /// Builds a `CASE` expression, nesting each further condition in the `ELSE` branch.
+    ///
+    /// A condition only selects its branch where it is true, and a missing `ELSE` branch is NULL.
+    fn visit_case(
+        &self,
+        when_then: &[(Expression, Expression)],
+        else_expr: Option<&Expression>,
+    ) -> Result<DynProofExpr, ConversionError> {
+        let branches = when_then
+            .iter()
+            .map(|(condition, then)| Ok((self.visit_expr(condition)?, self.visit_value(then)?)))
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        let else_expr = match (else_expr, branches.first()) {
+            (Some(else_expr), _) => self.visit_value(else_expr)?,
+            (None, Some((_, then))) => {
+                let then_type = then.data_type();
+                DynProofExpr::new_literal(zero_literal(then_type).ok_or_else(|| {
+                    ConversionError::Unprovable {
+                        error: format!("CASE without ELSE can not be proven for type {then_type}"),
+                    }
+                })?)
+            }
+            (None, None) => {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "CASE expressions need at least one condition".to_string(),
+                })
+            }
+        };
+        branches
+            .into_iter()
+            .rev()
+            .try_fold(else_expr, |else_expr, (condition, then)| {
+                DynProofExpr::try_new_case(condition, then, else_expr)
+            })
+    }
+
     fn visit_aggregate_expr(
         &self,
         op: AggregationOperator,
         expr: &Expression,
+        filter: Option<&Expression>,
     ) -> Result<DynProofExpr, ConversionError> {
         if self.in_agg_scope {
             return Err(ConversionError::InvalidExpression {
                 expression: "nested aggregations are invalid".to_string(),
             });
         }
-        let expr = DynProofExprBuilder::new_agg(self.column_mapping).visit_expr(expr)?;
+        let agg_builder = DynProofExprBuilder {
+            in_agg_scope: true,
+            subquery_plans: None,
+            ..*self
+        };
+        // The rows where the condition of the filter is NULL are not aggregated
+        let filter = filter
+            .map(|filter| agg_builder.visit_expr(filter))
+            .transpose()?;
+        match (op, filter) {
+            // A count is a sum of ones over the rows where its expression is not NULL,
+            // i.e. `COUNT(expr)` is `COUNT(1) FILTER (WHERE expr IS NOT NULL)`
+            (AggregationOperator::Count, filter) => {
+                let presence = agg_builder.visit_presence(expr)?;
+                let one = DynProofExpr::new_literal(LiteralValue::BigInt(1));
+                return match and_conditions(presence, filter)? {
+                    Some(condition) => DynProofExpr::try_new_filtered_aggregate(op, one, condition),
+                    None => Ok(DynProofExpr::new_aggregate(op, one)),
+                };
+            }
+            // NULLs are zero and therefore do not change a sum
+            (AggregationOperator::Sum, filter) => {
+                let expr = agg_builder.visit_expr(expr)?;
+                if !expr.data_type().is_numeric() {
+                    return Err(ConversionError::InvalidExpression {
+                        expression: format!(
+                            "Aggregation operator {op:?} doesn't work with non-numeric types"
+                        ),
+                    });
+                }
+                return match filter {
+                    Some(condition) => {
+                        DynProofExpr::try_new_filtered_aggregate(op, expr, condition)
+                    }
+                    None => Ok(DynProofExpr::new_aggregate(op, expr)),
+                };
+            }
+            (_, Some(_)) => {
+                return Err(ConversionError::Unprovable {
+                    error: format!("Aggregation operator {op:?} can not be proven with a filter"),
+                })
+            }
+            (_, None) => {}
+        }
+        // Extrema would take the zeros of NULLs into account, so they skip NULLs in postprocessing instead
+        if self.is_nullable(expr) {
+            return Err(ConversionError::Unprovable {
+                error: format!(
+                    "Aggregation operator {op:?} can not be proven on nullable expressions"
+                ),
+            });
+        }
+        let expr = agg_builder.visit_expr(expr)?;
         match (op, expr.data_type().is_numeric()) {
-            (AggregationOperator::Count, _) | (AggregationOperator::Sum, true) => {
+            // Extrema can only be proven for types whose order is the order of their scalars
+            (AggregationOperator::Max | AggregationOperator::Min, _)
+                if matches!(
+                    expr.data_type(),
+                    ColumnType::Uint8
+                        | ColumnType::TinyInt
+                        | ColumnType::SmallInt
+                        | ColumnType::Int
+                        | ColumnType::BigInt
+                        | ColumnType::Int128
+                        | ColumnType::Decimal75(_, _)
+                        | ColumnType::TimestampTZ(_, _)
+                ) =>
+            {
                 Ok(DynProofExpr::new_aggregate(op, expr))
             }
-            (AggregationOperator::Sum, false) => Err(ConversionError::InvalidExpression {
-                expression: format!(
-                    "Aggregation operator {op:?} doesn't work with non-numeric types"
-                ),
-            }),
             _ => Err(ConversionError::Unprovable {
                 error: format!("Aggregation operator {op:?} is not supported at this location"),
             }),
         }
     }
 }
+
+/// The conjunction of two optional conditions, or the one that is given
+fn and_conditions(
+    left: Option<DynProofExpr>,
+    right: Option<DynProofExpr>,
+) -> Result<Option<DynProofExpr>, ConversionError> {
+    Ok(match (left, right) {
+        (Some(left), Some(right)) => Some(DynProofExpr::try_new_and(left, right)?),
+        (left, right) => left.or(right),
+    })
+}
+
+/// The value that NULLs of the given type hold, if it can be written as a literal
+fn zero_literal(column_type: ColumnType) -> Option<LiteralValue> {
+    match column_type {
+        ColumnType::Boolean => Some(LiteralValue::Boolean(false)),
+        ColumnType::Uint8 => Some(LiteralValue::Uint8(0)),
+        ColumnType::TinyInt => Some(LiteralValue::TinyInt(0)),
+        ColumnType::SmallInt => Some(LiteralValue::SmallInt(0)),
+        ColumnType::Int => Some(LiteralValue::Int(0)),
+        ColumnType::BigInt => Some(LiteralValue::BigInt(0)),
+        ColumnType::Int128 => Some(LiteralValue::Int128(0)),
+        ColumnType::Decimal75(precision, scale) => {
+            Some(LiteralValue::Decimal75(precision, scale, I256::from(0)))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a subquery is a count without `GROUP BY`, whose result has a row even if it counts no rows
+fn is_count(subquery: &SelectStatement) -> bool {
+    let SetExpression::Query {
+        result_exprs,
+        group_by,
+        ..
+    } = &*subquery.expr
+    else {
+        return false;
+    };
+    matches!(
+        (result_exprs.as_slice(), group_by.is_empty()),
+        ([SelectResultExpr::AliasedResultExpr(aliased_expr)], true)
+            if matches!(
+                *aliased_expr.expr,
+                Expression::Aggregation {
+                    op: AggregationOperator::Count,
+                    ..
+                }
+            )
+    )
+}
@@ -1,6 +1,9 @@
 use super::DynProofExprBuilder;
 use crate::{
-    base::{database::ColumnRef, map::IndexMap},
+    base::{
+        database::ColumnRef,
+        map::{IndexMap, IndexSet},
+    },
     sql::proof_exprs::DynProofExpr,
 };
 use alloc::boxed::Box;
@@ -24,11 +27,20 @@ impl EnrichedExpr {
     /// If the expression is not provable, the `dyn_proof_expr` will be `None`.
     /// Otherwise the `dyn_proof_expr` will contain the provable expression plan
     /// and the `residue_expression` will contain the remaining expression.
-    pub fn new(expression: AliasedResultExpr, column_mapping: &IndexMap<Ident, ColumnRef>) -> Self {
+    /// The columns of `nullable_columns` are nullable,
+    /// and `character_counts` are the committed characters of the columns, see [`DynProofExprBuilder::with_character_counts`].
+    pub fn new(
+        expression: AliasedResultExpr,
+        column_mapping: &IndexMap<Ident, ColumnRef>,
+        nullable_columns: &IndexSet<ColumnRef>,
+        character_counts: &IndexMap<ColumnRef, usize>,
+    ) -> Self {
         // TODO: Using new_agg (ironically) disables aggregations in `QueryExpr` for now.
         // Re-enable aggregations when we add `GroupByExec` generalizations.
-        let res_dyn_proof_expr =
-            DynProofExprBuilder::new_agg(column_mapping).build(&expression.expr);
+        let res_dyn_proof_expr = DynProofExprBuilder::new_agg(column_mapping)
+            .with_nullable_columns(nullable_columns)
+            .with_character_counts(character_counts)
+            .build(&expression.expr);
         match res_dyn_proof_expr {
             Ok(dyn_proof_expr) => {
                 let alias = expression.alias;
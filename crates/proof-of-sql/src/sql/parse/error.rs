@@ -6,6 +6,7 @@ use alloc::{
     boxed::Box,
     format,
     string::{String, ToString},
+    vec::Vec,
 };
 use core::result::Result;
 use proof_of_sql_parser::posql_time::PoSQLTimestampError;
@@ -38,6 +39,13 @@ pub enum ConversionError {
         identifier: Box<Ident>,
     },
 
+    #[snafu(display("Column '{identifier}' is ambiguous since it exists in more than one table"))]
+    /// The column exists in more than one of the tables referenced by the query
+    AmbiguousColumn {
+        /// The ambiguous column identifier
+        identifier: Box<Ident>,
+    },
+
     #[snafu(display("Expected '{expected}' but found '{actual}'"))]
     /// Invalid data type received
     InvalidDataType {
@@ -148,6 +156,17 @@ pub enum ConversionError {
         error: String,
     },
 
+    #[snafu(display(
+        "The inputs of a UNION ALL have the incompatible column types {left_types:?} and {right_types:?}"
+    ))]
+    /// The inputs of a `UNION ALL` do not have the same column types
+    UnionSchemaMismatch {
+        /// The column types of the first input
+        left_types: Vec<ColumnType>,
+        /// The column types of the input that differ from them
+        right_types: Vec<ColumnType>,
+    },
+
     #[snafu(display("Unsupported operator: {message}"))]
     /// Unsupported operation
     UnsupportedOperation {
@@ -1,23 +1,28 @@
-use super::{where_expr_builder::WhereExprBuilder, ConversionError, EnrichedExpr};
+use super::{
+    where_expr_builder::WhereExprBuilder, ConversionError, DynProofExprBuilder, EnrichedExpr,
+};
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue, TableRef},
-        map::IndexMap,
+        database::{ColumnRef, LiteralValue},
+        map::{IndexMap, IndexSet},
     },
     sql::{
         proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
-        proof_plans::FilterExec,
+        proof_plans::{DynProofPlan, FilterExec},
     },
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use itertools::Itertools;
-use proof_of_sql_parser::intermediate_ast::Expression;
+use proof_of_sql_parser::{intermediate_ast::Expression, SelectStatement};
 use sqlparser::ast::Ident;
 pub struct FilterExecBuilder {
     table_expr: Option<TableExpr>,
     where_expr: Option<DynProofExpr>,
     filter_result_expr_list: Vec<AliasedDynProofExpr>,
     column_mapping: IndexMap<Ident, ColumnRef>,
+    nullable_columns: IndexSet<ColumnRef>,
+    character_counts: IndexMap<ColumnRef, usize>,
+    subquery_plans: IndexMap<SelectStatement, DynProofPlan>,
 }
 
 // Public interface
@@ -28,11 +33,35 @@ impl FilterExecBuilder {
             where_expr: None,
             filter_result_expr_list: vec![],
             column_mapping,
+            nullable_columns: IndexSet::default(),
+            character_counts: IndexMap::default(),
+            subquery_plans: IndexMap::default(),
         }
     }
 
-    pub fn add_table_expr(mut self, table_ref: TableRef) -> Self {
-        self.table_expr = Some(TableExpr { table_ref });
+    pub fn add_table_expr(mut self, table_expr: TableExpr) -> Self {
+        self.table_expr = Some(table_expr);
+        self
+    }
+
+    /// Adds the columns of the column mapping that are nullable
+    pub fn add_nullable_columns(mut self, nullable_columns: IndexSet<ColumnRef>) -> Self {
+        self.nullable_columns = nullable_columns;
+        self
+    }
+
+    /// Adds the committed characters of the columns of the column mapping
+    pub fn add_character_counts(mut self, character_counts: IndexMap<ColumnRef, usize>) -> Self {
+        self.character_counts = character_counts;
+        self
+    }
+
+    /// Adds the plans of the subqueries of the where clause, which has to be added afterwards
+    pub fn add_subquery_plans(
+        mut self,
+        subquery_plans: IndexMap<SelectStatement, DynProofPlan>,
+    ) -> Self {
+        self.subquery_plans = subquery_plans;
         self
     }
 
@@ -40,7 +69,11 @@ impl FilterExecBuilder {
         mut self,
         where_expr: Option<Box<Expression>>,
     ) -> Result<Self, ConversionError> {
-        self.where_expr = WhereExprBuilder::new(&self.column_mapping).build(where_expr)?;
+        self.where_expr = WhereExprBuilder::new(&self.column_mapping)
+            .with_nullable_columns(&self.nullable_columns)
+            .with_character_counts(&self.character_counts)
+            .with_subquery_plans(&self.subquery_plans)
+            .build(where_expr)?;
         Ok(self)
     }
 
@@ -64,11 +97,13 @@ impl FilterExecBuilder {
         }
 
         if has_nonprovable_column {
+            let builder = DynProofExprBuilder::new(&self.column_mapping)
+                .with_nullable_columns(&self.nullable_columns);
             // Has to keep them sorted to have deterministic order for tests
             for alias in self.column_mapping.keys().sorted() {
                 let column_ref = self.column_mapping.get(alias).unwrap();
                 self.filter_result_expr_list.push(AliasedDynProofExpr {
-                    expr: DynProofExpr::new_column(column_ref.clone()),
+                    expr: builder.build_column(column_ref),
                     alias: alias.clone(),
                 });
             }
@@ -16,6 +16,9 @@ pub use query_expr::QueryExpr;
 mod filter_exec_builder;
 pub(crate) use filter_exec_builder::FilterExecBuilder;
 
+mod sort_merge_join_exec_builder;
+pub(crate) use sort_merge_join_exec_builder::SortMergeJoinExecBuilder;
+
 /// TODO: add docs
 pub(crate) mod query_context;
 pub(crate) use query_context::QueryContext;
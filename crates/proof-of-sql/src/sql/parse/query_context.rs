@@ -5,13 +5,16 @@ use crate::{
     },
     sql::{
         parse::{ConversionError, ConversionResult, DynProofExprBuilder, WhereExprBuilder},
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
-        proof_plans::GroupByExec,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{DynProofPlan, GroupByExec},
     },
 };
-use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
-use proof_of_sql_parser::intermediate_ast::{
-    AggregationOperator, AliasedResultExpr, Expression, Slice,
+use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, Expression, JoinType, Slice, WindowFunction,
+    },
+    Identifier, SelectStatement,
 };
 use sqlparser::ast::Ident;
 
@@ -22,15 +25,24 @@ pub struct QueryContext {
     slice_expr: Option<Slice>,
     col_ref_counter: usize,
     table: Option<TableRef>,
+    table_input: Option<DynProofPlan>,
+    join_table: Option<TableRef>,
+    join_conditions: Vec<(ColumnRef, ColumnRef)>,
+    join_type: Option<JoinType>,
     in_result_scope: bool,
     has_visited_group_by: bool,
     order_by_exprs: OrderIndexDirectionPairs,
     group_by_exprs: Vec<Ident>,
+    group_by_key_exprs: IndexMap<Ident, Expression>,
     where_expr: Option<Box<Expression>>,
+    having_expr: Option<Box<Expression>>,
     result_column_set: IndexSet<Ident>,
     res_aliased_exprs: Vec<AliasedResultExpr>,
     column_mapping: IndexMap<Ident, ColumnRef>,
+    nullable_columns: IndexSet<ColumnRef>,
+    character_counts: IndexMap<ColumnRef, usize>,
     first_result_col_out_agg_scope: Option<Ident>,
+    subquery_plans: IndexMap<SelectStatement, DynProofPlan>,
 }
 
 impl QueryContext {
@@ -47,6 +59,107 @@ impl QueryContext {
             .expect("Table should already have been set")
     }
 
+    /// Sets the plan whose result is the table of the query, i.e. the query of a derived table.
+    pub fn set_table_input(&mut self, input: DynProofPlan) {
+        self.table_input = Some(input);
+    }
+
+    /// The plan whose result is the table of the query, if it is a derived table
+    pub fn get_table_input(&self) -> Option<&DynProofPlan> {
+        self.table_input.as_ref()
+    }
+
+    /// The expression for the table of the query, which is the result of its input plan for a derived table
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_table_expr(&self) -> TableExpr {
+        let table_ref = self.get_table_ref().clone();
+        match &self.table_input {
+            Some(input) => TableExpr::new_with_input(table_ref, input.clone()),
+            None => TableExpr::new(table_ref),
+        }
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_join_table_ref(&mut self, join_table: TableRef) {
+        assert!(self.join_table.is_none());
+        self.join_table = Some(join_table);
+    }
+
+    /// The table on the right side of a join, if the query has one.
+    ///
+    /// The left side of the join is the table returned by [`Self::get_table_ref`].
+    /// For outer joins, the left side is always the side whose rows are preserved.
+    pub fn get_join_table_ref(&self) -> Option<&TableRef> {
+        self.join_table.as_ref()
+    }
+
+    /// All the tables that columns of the query can be resolved against
+    pub fn get_table_refs(&self) -> impl Iterator<Item = &TableRef> {
+        self.table.iter().chain(self.join_table.iter())
+    }
+
+    /// Add a join condition, given as a pair of (left column, right column)
+    pub fn push_join_condition(&mut self, left: ColumnRef, right: ColumnRef) {
+        self.join_conditions.push((left, right));
+    }
+
+    pub fn get_join_conditions(&self) -> &[(ColumnRef, ColumnRef)] {
+        &self.join_conditions
+    }
+
+    /// Sets the kind of join between the table returned by [`Self::get_table_ref`]
+    /// and the one returned by [`Self::get_join_table_ref`].
+    ///
+    /// For a right join the former is the right table of the query, so that it is
+    /// always the table whose rows are preserved by an outer join.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_join_type(&mut self, join_type: JoinType) {
+        assert!(self.join_type.is_none());
+        self.join_type = Some(join_type);
+    }
+
+    pub fn is_outer_join(&self) -> bool {
+        matches!(self.join_type, Some(JoinType::Left | JoinType::Right))
+    }
+
+    /// Adds the plan of a subquery of the `WHERE` clause
+    pub fn push_subquery_plan(&mut self, subquery: SelectStatement, plan: DynProofPlan) {
+        self.subquery_plans.insert(subquery, plan);
+    }
+
+    /// The plans of the subqueries of the `WHERE` clause
+    pub fn get_subquery_plans(&self) -> &IndexMap<SelectStatement, DynProofPlan> {
+        &self.subquery_plans
+    }
+
+    /// Marks a column that the query uses as nullable
+    pub fn push_nullable_column(&mut self, column_ref: ColumnRef) {
+        self.nullable_columns.insert(column_ref);
+    }
+
+    /// The columns that the query uses that are nullable
+    pub fn get_nullable_columns(&self) -> &IndexSet<ColumnRef> {
+        &self.nullable_columns
+    }
+
+    /// Records the number of first and last characters of a `VarChar` column that the query uses
+    /// whose components are committed to
+    pub fn push_character_count(&mut self, column_ref: ColumnRef, count: usize) {
+        self.character_counts.insert(column_ref, count);
+    }
+
+    /// The number of committed characters of the `VarChar` columns that the query uses in a `LIKE` predicate
+    pub fn get_character_counts(&self) -> &IndexMap<ColumnRef, usize> {
+        &self.character_counts
+    }
+
+    /// A builder of the expressions of the query outside of its `WHERE` clause
+    pub(crate) fn expr_builder(&self) -> DynProofExprBuilder<'_> {
+        DynProofExprBuilder::new(&self.column_mapping)
+            .with_nullable_columns(&self.nullable_columns)
+            .with_character_counts(&self.character_counts)
+    }
+
     pub fn set_where_expr(&mut self, where_expr: Option<Box<Expression>>) {
         self.where_expr = where_expr;
     }
@@ -56,6 +169,15 @@ impl QueryContext {
         &self.where_expr
     }
 
+    pub fn set_having_expr(&mut self, having_expr: Option<Box<Expression>>) {
+        self.having_expr = having_expr;
+    }
+
+    #[allow(clippy::ref_option)]
+    pub fn get_having_expr(&self) -> &Option<Box<Expression>> {
+        &self.having_expr
+    }
+
     pub fn set_slice_expr(&mut self, slice_expr: Option<Slice>) {
         self.slice_expr = slice_expr;
     }
@@ -96,9 +218,9 @@ impl QueryContext {
         self.in_agg_scope
     }
 
-    /// TODO: add docs
+    /// Whether the query aggregates, i.e. it has aggregations, a `GROUP BY` or a `HAVING` clause
     pub(crate) fn has_agg(&self) -> bool {
-        self.agg_counter > 0 || !self.group_by_exprs.is_empty()
+        self.agg_counter > 0 || !self.group_by_exprs.is_empty() || self.having_expr.is_some()
     }
 
     pub fn push_column_ref(&mut self, column: Ident, column_ref: ColumnRef) {
@@ -131,12 +253,19 @@ impl QueryContext {
         // Add the group by columns to the result column set
         // to ensure their integrity in the filter expression.
         for group_column in &self.group_by_exprs {
-            self.result_column_set.insert(group_column.clone());
+            if !self.group_by_key_exprs.contains_key(group_column) {
+                self.result_column_set.insert(group_column.clone());
+            }
         }
 
         self.has_visited_group_by = true;
     }
 
+    /// Sets the group by expressions that are not columns, by the identifiers they are grouped by under
+    pub fn set_group_by_key_exprs(&mut self, exprs: IndexMap<Ident, Expression>) {
+        self.group_by_key_exprs = exprs;
+    }
+
     pub fn set_order_by_exprs(&mut self, order_by_exprs: OrderIndexDirectionPairs) {
         self.order_by_exprs = order_by_exprs;
     }
@@ -209,6 +338,12 @@ impl QueryContext {
         &self.group_by_exprs
     }
 
+    /// The group by expressions that are not columns, by the identifiers they are grouped by under,
+    /// i.e. the aliases of their result columns
+    pub fn get_group_by_key_exprs(&self) -> &IndexMap<Ident, Expression> {
+        &self.group_by_key_exprs
+    }
+
     pub fn get_result_column_set(&self) -> IndexSet<Ident> {
         self.result_column_set.clone()
     }
@@ -216,6 +351,189 @@ impl QueryContext {
     pub fn get_column_mapping(&self) -> IndexMap<Ident, ColumnRef> {
         self.column_mapping.clone()
     }
+
+    /// The aliases of the columns that hold the aggregations of the `HAVING` clause
+    /// in a provable aggregation query, together with the aggregations that need additional columns.
+    ///
+    /// Counts are held by the count column and other aggregations by the result column
+    /// with the same aggregation, if there is one. The remaining ones are computed
+    /// as additional aggregate columns after the result ones.
+    /// Returns `None` if the `HAVING` clause has an aggregation that can not be proven.
+    pub fn get_having_aggregations(
+        &self,
+    ) -> Option<(IndexMap<Expression, Identifier>, Vec<AliasedResultExpr>)> {
+        let mut aliases = IndexMap::default();
+        let mut additional_aggregations = Vec::new();
+        let Some(having_expr) = &self.having_expr else {
+            return Some((aliases, additional_aggregations));
+        };
+        let count_alias = self.res_aliased_exprs.last()?.alias;
+        let builder = self.expr_builder();
+        try_rewrite_expression(having_expr, &mut |expr| {
+            let Expression::Aggregation {
+                op,
+                expr: operand,
+                filter,
+            } = expr
+            else {
+                return Ok(None);
+            };
+            if aliases.contains_key(expr) {
+                return Ok(Some(expr.clone()));
+            }
+            let alias = match op {
+                // The count of all rows only differs from the count of an expression for NULLs
+                AggregationOperator::Count if filter.is_none() && !builder.is_nullable(operand) => {
+                    count_alias
+                }
+                AggregationOperator::Sum
+                | AggregationOperator::Max
+                | AggregationOperator::Min
+                | AggregationOperator::Count => self
+                    .res_aliased_exprs
+                    .iter()
+                    .find(|res| *res.expr == *expr)
+                    .map_or_else(
+                        || {
+                            let aggregation = expr
+                                .clone()
+                                .alias(&format!("__having_{}__", additional_aggregations.len()));
+                            let alias = aggregation.alias;
+                            additional_aggregations.push(aggregation);
+                            alias
+                        },
+                        |res| res.alias,
+                    ),
+                _ => {
+                    return Err(ConversionError::Unprovable {
+                        error: format!("{op} in a HAVING clause"),
+                    })
+                }
+            };
+            aliases.insert(expr.clone(), alias);
+            Ok(Some(expr.clone()))
+        })
+        .ok()?;
+        Some((aliases, additional_aggregations))
+    }
+}
+
+/// The table that the columns of the result of a subquery are referred to as columns of
+pub(crate) fn subquery_table_ref() -> TableRef {
+    TableRef::from_idents(None, Ident::new("__subquery__"))
+}
+
+/// Rewrites an expression top-down.
+///
+/// Subexpressions for which `rewrite` returns a replacement are replaced by it
+/// and the others are rebuilt from their rewritten subexpressions.
+pub(crate) fn try_rewrite_expression(
+    expr: &Expression,
+    rewrite: &mut impl FnMut(&Expression) -> ConversionResult<Option<Expression>>,
+) -> ConversionResult<Expression> {
+    if let Some(replacement) = rewrite(expr)? {
+        return Ok(replacement);
+    }
+    Ok(match expr {
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Wildcard
+        | Expression::ScalarSubquery(_) => expr.clone(),
+        Expression::Unary { op, expr } => Expression::Unary {
+            op: *op,
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+        },
+        Expression::Binary { op, left, right } => Expression::Binary {
+            op: *op,
+            left: Box::new(try_rewrite_expression(left, rewrite)?),
+            right: Box::new(try_rewrite_expression(right, rewrite)?),
+        },
+        Expression::Aggregation { op, expr, filter } => Expression::Aggregation {
+            op: *op,
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            filter: filter
+                .as_deref()
+                .map(|filter| try_rewrite_expression(filter, rewrite).map(Box::new))
+                .transpose()?,
+        },
+        Expression::IsNull(expr) => {
+            Expression::IsNull(Box::new(try_rewrite_expression(expr, rewrite)?))
+        }
+        Expression::IsNotNull(expr) => {
+            Expression::IsNotNull(Box::new(try_rewrite_expression(expr, rewrite)?))
+        }
+        Expression::InList { expr, list } => Expression::InList {
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            list: list
+                .iter()
+                .map(|candidate| try_rewrite_expression(candidate, rewrite))
+                .collect::<ConversionResult<_>>()?,
+        },
+        Expression::InSubquery { expr, subquery } => Expression::InSubquery {
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            subquery: subquery.clone(),
+        },
+        Expression::Between { expr, low, high } => Expression::Between {
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            low: Box::new(try_rewrite_expression(low, rewrite)?),
+            high: Box::new(try_rewrite_expression(high, rewrite)?),
+        },
+        Expression::Like { expr, pattern } => Expression::Like {
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            pattern: pattern.clone(),
+        },
+        Expression::Extract { field, expr } => Expression::Extract {
+            field: *field,
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+        },
+        Expression::DateTrunc { field, expr } => Expression::DateTrunc {
+            field: *field,
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+        },
+        Expression::AddInterval { expr, interval } => Expression::AddInterval {
+            expr: Box::new(try_rewrite_expression(expr, rewrite)?),
+            interval: *interval,
+        },
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => Expression::Case {
+            when_then: when_then
+                .iter()
+                .map(|(condition, then)| {
+                    Ok((
+                        try_rewrite_expression(condition, rewrite)?,
+                        try_rewrite_expression(then, rewrite)?,
+                    ))
+                })
+                .collect::<ConversionResult<_>>()?,
+            else_expr: else_expr
+                .as_deref()
+                .map(|else_expr| try_rewrite_expression(else_expr, rewrite).map(Box::new))
+                .transpose()?,
+        },
+        Expression::Window {
+            function,
+            partition_by,
+            order_by,
+        } => Expression::Window {
+            function: match function {
+                WindowFunction::RowNumber | WindowFunction::Rank => function.clone(),
+                WindowFunction::Lag(expr) => {
+                    WindowFunction::Lag(Box::new(try_rewrite_expression(expr, rewrite)?))
+                }
+                WindowFunction::Lead(expr) => {
+                    WindowFunction::Lead(Box::new(try_rewrite_expression(expr, rewrite)?))
+                }
+                WindowFunction::Sum(expr) => {
+                    WindowFunction::Sum(Box::new(try_rewrite_expression(expr, rewrite)?))
+                }
+            },
+            partition_by: partition_by.clone(),
+            order_by: order_by.clone(),
+        },
+    })
 }
 
 /// Converts a `QueryContext` into an `Option<GroupByExec>`.
@@ -225,70 +543,124 @@ impl QueryContext {
 impl TryFrom<&QueryContext> for Option<GroupByExec> {
     type Error = ConversionError;
 
+    #[allow(clippy::too_many_lines)]
     fn try_from(value: &QueryContext) -> Result<Option<GroupByExec>, Self::Error> {
+        // `GroupByExec` only supports a single table as its input
+        if value.join_table.is_some() {
+            return Ok(None);
+        }
         let where_clause = WhereExprBuilder::new(&value.column_mapping)
+            .with_nullable_columns(&value.nullable_columns)
+            .with_character_counts(&value.character_counts)
+            .with_subquery_plans(&value.subquery_plans)
             .build(value.where_expr.clone())?
             .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
-        let table = value
-            .table
-            .as_ref()
-            .map(|table_ref| TableExpr {
-                table_ref: table_ref.clone(),
-            })
-            .ok_or(ConversionError::InvalidExpression {
+        let table = value.table.as_ref().map(|_| value.get_table_expr()).ok_or(
+            ConversionError::InvalidExpression {
                 expression: "QueryContext has no table_ref".to_owned(),
-            })?;
-
-        let group_by_exprs = value
+            },
+        )?;
+
+        // NULLs are zero, so grouping by a nullable column would merge them with the zeros
+        let builder = value.expr_builder();
+        if value.group_by_exprs.iter().any(|id| {
+            value
+                .column_mapping
+                .get(id)
+                .is_some_and(|column_ref| value.nullable_columns.contains(column_ref))
+                || value
+                    .group_by_key_exprs
+                    .get(id)
+                    .is_some_and(|expr| builder.is_nullable(expr))
+        }) {
+            return Ok(None);
+        }
+        let Some(group_by_exprs) = value
             .group_by_exprs
             .iter()
-            .map(|expr| -> Result<ColumnExpr, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or_else(|| ConversionError::MissingColumn {
-                        identifier: Box::new(expr.clone()),
-                        table_ref: table.table_ref.clone(),
-                    })
-                    .map(|column_ref| ColumnExpr::new(column_ref.clone()))
-            })
-            .collect::<Result<Vec<ColumnExpr>, ConversionError>>()?;
+            .map(
+                |id| -> Result<Option<AliasedDynProofExpr>, ConversionError> {
+                    // Group by expressions that are not columns are grouped by under the alias of their result column
+                    if let Some(expr) = value.group_by_key_exprs.get(id) {
+                        return Ok(builder.build(expr).ok().map(|expr| AliasedDynProofExpr {
+                            alias: id.clone(),
+                            expr,
+                        }));
+                    }
+                    value
+                        .column_mapping
+                        .get(id)
+                        .ok_or_else(|| ConversionError::MissingColumn {
+                            identifier: Box::new(id.clone()),
+                            table_ref: table.table_ref.clone(),
+                        })
+                        .map(|column_ref| {
+                            Some(AliasedDynProofExpr {
+                                alias: column_ref.column_id(),
+                                expr: DynProofExpr::new_column(column_ref.clone()),
+                            })
+                        })
+                },
+            )
+            .collect::<Result<Option<Vec<_>>, ConversionError>>()?
+        else {
+            return Ok(None);
+        };
         // For a query to be provable the result columns must be of one of three kinds below:
-        // 1. Group by columns (it is mandatory to have all of them in the correct order)
-        // 2. Sum(expr) expressions (it is optional to have any)
-        // 3. count(*) with an alias (it is mandatory to have one and only one)
+        // 1. Group by expressions (it is mandatory to have all of them in the correct order)
+        // 2. Sum(expr), Count(expr), Avg(expr), Max(expr) or Min(expr) expressions (it is optional to have any),
+        //    where sums and counts may have a `FILTER` clause
+        // 3. count(*) with an alias and without a `FILTER` clause (it is mandatory to have one as the last column)
+        // Averages are proven as sums and derived from them and the count in postprocessing.
         let num_group_by_columns = group_by_exprs.len();
         let num_result_columns = value.res_aliased_exprs.len();
         if num_result_columns < num_group_by_columns + 1 {
             return Ok(None);
         }
         let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns].to_vec();
-        let sum_expr_columns =
+        let aggregate_expr_columns =
             &value.res_aliased_exprs[num_group_by_columns..num_result_columns - 1].to_vec();
         // Check group by columns
         let group_by_compliance = value
             .group_by_exprs
             .iter()
             .zip(res_group_by_columns.iter())
-            .all(|(ident, res)| {
-                if let Expression::Column(res_ident) = *res.expr {
-                    Ident::from(res_ident) == *ident
-                } else {
-                    false
-                }
+            .all(|(ident, res)| match value.group_by_key_exprs.get(ident) {
+                Some(expr) => Ident::from(res.alias) == *ident && *res.expr == *expr,
+                None => matches!(
+                    *res.expr,
+                    Expression::Column(res_ident) if Ident::from(res_ident) == *ident
+                ),
             });
 
-        // Check sums
-        let sum_expr = sum_expr_columns
+        // Check sums, counts, averages and extrema
+        let aggregate_exprs = aggregate_expr_columns
             .iter()
             .map(|res| {
                 if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
-                    ..
+                    op:
+                        op @ (AggregationOperator::Sum
+                        | AggregationOperator::Count
+                        | AggregationOperator::Avg
+                        | AggregationOperator::Max
+                        | AggregationOperator::Min),
+                    expr,
+                    filter,
                 } = (*res.expr).clone()
                 {
+                    // An average is divided by the count of all rows, which must be the count of its rows
+                    if op == AggregationOperator::Avg
+                        && (filter.is_some() || builder.is_nullable(&expr))
+                    {
+                        return None;
+                    }
+                    let op = if op == AggregationOperator::Avg {
+                        AggregationOperator::Sum
+                    } else {
+                        op
+                    };
                     let res_dyn_proof_expr =
-                        DynProofExprBuilder::new(&value.column_mapping).build(&res.expr);
+                        builder.build(&Expression::Aggregation { op, expr, filter });
                     res_dyn_proof_expr
                         .ok()
                         .map(|dyn_proof_expr| AliasedDynProofExpr {
@@ -303,23 +675,54 @@ impl TryFrom<&QueryContext> for Option<GroupByExec> {
 
         // Check count(*)
         let count_column = &value.res_aliased_exprs[num_result_columns - 1];
-        let count_column_compliant = matches!(
-            *count_column.expr,
+        // `GroupByExec` counts all rows, which only differs from the count of an expression for NULLs
+        let count_column_compliant = match &*count_column.expr {
             Expression::Aggregation {
                 op: AggregationOperator::Count,
-                ..
-            }
-        );
+                expr,
+                filter: None,
+            } => !builder.is_nullable(expr),
+            _ => false,
+        };
+
+        // Aggregations of the `HAVING` clause that are not result columns are computed after them
+        let having_aggregate_exprs =
+            value
+                .get_having_aggregations()
+                .and_then(|(_, additional_aggregations)| {
+                    additional_aggregations
+                        .iter()
+                        .map(|res| {
+                            builder.build(&res.expr).ok().map(|dyn_proof_expr| {
+                                AliasedDynProofExpr {
+                                    alias: res.alias.into(),
+                                    expr: dyn_proof_expr,
+                                }
+                            })
+                        })
+                        .collect::<Option<Vec<AliasedDynProofExpr>>>()
+                });
 
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
+        let (true, Some(mut aggregate_exprs), true, Some(having_aggregate_exprs)) = (
+            group_by_compliance,
+            aggregate_exprs,
+            count_column_compliant,
+            having_aggregate_exprs,
+        ) else {
             return Ok(None);
-        }
-        Ok(Some(GroupByExec::new(
+        };
+        aggregate_exprs.extend(having_aggregate_exprs);
+        let group_by_exec = GroupByExec::new(
             group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
+            aggregate_exprs,
             count_column.alias.into(),
             table,
             where_clause,
-        )))
+        );
+        // A `HAVING` clause is proven as a filter on the groups, which requires them to be proven distinct
+        if value.having_expr.is_some() && !group_by_exec.proves_distinct_groups() {
+            return Ok(None);
+        }
+        Ok(Some(group_by_exec))
     }
 }
@@ -1,27 +1,38 @@
-use super::{ConversionError, ConversionResult, QueryContext};
-use crate::base::{
-    database::{
-        try_add_subtract_column_types, try_multiply_column_types, ColumnRef, ColumnType,
-        SchemaAccessor, TableRef,
-    },
-    map::IndexSet,
-    math::{
-        decimal::{DecimalError, Precision},
-        BigDecimalExt,
+use super::{
+    query_context::try_rewrite_expression, ConversionError, ConversionResult, QueryContext,
+    QueryExpr,
+};
+use crate::{
+    base::{
+        database::{
+            try_add_subtract_column_types, try_average_column_type, try_case_column_types,
+            try_modulo_column_types, try_multiply_column_types, ColumnComponent, ColumnRef,
+            ColumnType, SchemaAccessor, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::{
+            decimal::{DecimalError, Precision},
+            BigDecimalExt,
+        },
     },
+    sql::{proof::ProofPlan, proof_plans::DynProofPlan},
 };
 use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use proof_of_sql_parser::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, Expression, Literal, SelectResultExpr, Slice,
-        TableExpression,
+        AggregationOperator, AliasedResultExpr, Expression, JoinCondition, JoinType, Literal,
+        QualifiedColumn, SelectResultExpr, Slice, TableExpression,
     },
-    Identifier,
+    Identifier, SelectStatement,
 };
 use sqlparser::ast::{BinaryOperator, Expr, Ident, OrderByExpr, UnaryOperator};
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
+    default_schema: Option<Ident>,
+    in_where_scope: bool,
+    /// The result aliases of the qualified columns that can not be referred to by their name alone
+    qualified_column_aliases: IndexMap<ColumnRef, Identifier>,
 }
 
 // Public interface
@@ -30,6 +41,9 @@ impl<'a> QueryContextBuilder<'a> {
         Self {
             context: QueryContext::default(),
             schema_accessor,
+            default_schema: None,
+            in_where_scope: false,
+            qualified_column_aliases: IndexMap::default(),
         }
     }
 
@@ -38,40 +52,157 @@ impl<'a> QueryContextBuilder<'a> {
         mut self,
         table_expr: &[Box<TableExpression>],
         default_schema: Ident,
-    ) -> Self {
+    ) -> ConversionResult<Self> {
         assert_eq!(table_expr.len(), 1);
+        self.default_schema = Some(default_schema.clone());
 
-        match *table_expr[0] {
+        match &*table_expr[0] {
             TableExpression::Named { table, schema } => {
-                let actual_schema = schema.map_or(default_schema, Ident::from);
-                let table_ref = TableRef::from_idents(Some(actual_schema), Ident::from(table));
+                let table_ref = named_table_ref(*table, *schema, default_schema);
                 self.context.set_table_ref(table_ref);
             }
+            // The columns of a derived table are referred to as columns of a table named after its alias
+            TableExpression::Subquery { query, alias } => {
+                let table_ref = TableRef::from_idents(None, Ident::from(*alias));
+                let input = QueryExpr::try_new_input_plan(
+                    (**query).clone(),
+                    &table_ref,
+                    default_schema,
+                    self.schema_accessor,
+                )?;
+                self.context.set_table_ref(table_ref);
+                self.context.set_table_input(input);
+            }
+            TableExpression::Join {
+                left,
+                right,
+                join_type,
+                on,
+            } => {
+                let (
+                    TableExpression::Named {
+                        table: left_table,
+                        schema: left_schema,
+                    },
+                    TableExpression::Named {
+                        table: right_table,
+                        schema: right_schema,
+                    },
+                ) = (&**left, &**right)
+                else {
+                    return Err(ConversionError::UnsupportedOperation {
+                        message: "Joins of more than two tables are not supported yet".to_string(),
+                    });
+                };
+                // A right join is planned as a left join with the sides swapped
+                let ((preserved_table, preserved_schema), (other_table, other_schema)) =
+                    match join_type {
+                        JoinType::Inner | JoinType::Left => {
+                            ((left_table, left_schema), (right_table, right_schema))
+                        }
+                        JoinType::Right => ((right_table, right_schema), (left_table, left_schema)),
+                    };
+                self.context.set_table_ref(named_table_ref(
+                    *preserved_table,
+                    *preserved_schema,
+                    default_schema.clone(),
+                ));
+                self.context.set_join_table_ref(named_table_ref(
+                    *other_table,
+                    *other_schema,
+                    default_schema,
+                ));
+                for condition in on {
+                    self.visit_join_condition(condition)?;
+                }
+                self.context.set_join_type(*join_type);
+            }
         }
 
+        Ok(self)
+    }
+
+    /// Visits a derived table that is given by its plan,
+    /// whose columns are referred to as columns of `table_ref`.
+    pub fn visit_table_input(
+        mut self,
+        table_ref: TableRef,
+        input: DynProofPlan,
+        default_schema: Ident,
+    ) -> Self {
+        self.default_schema = Some(default_schema);
+        self.context.set_table_ref(table_ref);
+        self.context.set_table_input(input);
         self
     }
 
     pub fn visit_where_expr(
         mut self,
-        mut where_expr: Option<Box<Expression>>,
+        where_expr: Option<Box<Expression>>,
     ) -> ConversionResult<Self> {
+        let mut where_expr = where_expr
+            .map(|expr| self.resolve_qualified_columns(&expr).map(Box::new))
+            .transpose()?;
         if let Some(expr) = where_expr.as_deref_mut() {
+            self.in_where_scope = true;
             self.visit_expr(expr)?;
+            self.in_where_scope = false;
         }
         self.context.set_where_expr(where_expr);
         Ok(self)
     }
 
+    /// Visits the `HAVING` clause, which must be a predicate whose columns
+    /// are group by columns unless they are aggregated.
+    pub fn visit_having_expr(
+        mut self,
+        having_expr: Option<Box<Expression>>,
+    ) -> ConversionResult<Self> {
+        let having_expr = having_expr
+            .map(|expr| self.resolve_qualified_columns(&expr).map(Box::new))
+            .transpose()?;
+        if let Some(expr) = having_expr.as_deref() {
+            let dtype = self.visit_expr(expr)?;
+            if dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: dtype,
+                });
+            }
+            let group_by_exprs = self.context.get_group_by_exprs();
+            let group_by_key_exprs = self.context.get_group_by_key_exprs();
+            try_rewrite_expression(expr, &mut |expr| match expr {
+                Expression::Aggregation { .. } => Ok(Some(expr.clone())),
+                _ if group_by_key_exprs.values().any(|key_expr| key_expr == expr) => {
+                    Ok(Some(expr.clone()))
+                }
+                Expression::Column(identifier)
+                    if !group_by_exprs.contains(&Ident::from(*identifier)) =>
+                {
+                    Err(ConversionError::InvalidGroupByColumnRef {
+                        column: identifier.to_string(),
+                    })
+                }
+                _ => Ok(None),
+            })?;
+        }
+        self.context.set_having_expr(having_expr);
+        Ok(self)
+    }
+
     pub fn visit_result_exprs(
         mut self,
         result_exprs: Vec<SelectResultExpr>,
     ) -> ConversionResult<Self> {
+        self.visit_qualified_result_columns(&result_exprs)?;
         self.context.toggle_result_scope();
         for column in result_exprs {
             match column {
                 SelectResultExpr::ALL => self.visit_select_all_expr()?,
-                SelectResultExpr::AliasedResultExpr(expr) => self.visit_aliased_expr(expr)?,
+                SelectResultExpr::AliasedResultExpr(expr) => {
+                    let aliased_expr = self.resolve_qualified_result_expr(expr)?;
+                    self.visit_aliased_expr(aliased_expr)?;
+                }
             }
         }
         self.context.toggle_result_scope();
@@ -83,43 +214,54 @@ impl<'a> QueryContextBuilder<'a> {
         mut self,
         order_by_exprs: Vec<OrderByExpr>,
     ) -> ConversionResult<Self> {
-        let ident_direction_pairs: Vec<(Ident, bool)> = order_by_exprs
-            .into_iter()
-            .map(|order_by_expr| -> ConversionResult<(Ident, bool)> {
-                let ident_direction_pair = match order_by_expr {
-                    OrderByExpr {
-                        expr: Expr::Identifier(id),
-                        asc: Some(true) | None,
-                        ..
-                    } => Ok((id, true)),
-                    OrderByExpr {
-                        expr: Expr::Identifier(id),
-                        asc: Some(false),
-                        ..
-                    } => Ok((id, false)),
-                    _ => Err(ConversionError::UnsupportedOperation {
-                        message: "Order by columns other than result columns not supported yet"
-                            .to_string(),
-                    }),
-                }?;
-                Ok(ident_direction_pair)
-            })
-            .collect::<ConversionResult<Vec<_>>>()?;
+        let result_exprs = self.context.get_aliased_result_exprs()?;
         // Collect all the result aliases
-        let result_aliases = self
-            .context
-            .get_aliased_result_exprs()?
+        let result_aliases = result_exprs
             .iter()
             .map(|aliased_expr| aliased_expr.alias.into())
             .collect::<IndexSet<Ident>>();
-        let index_direction_pairs: Vec<(usize, bool)> = ident_direction_pairs
+        let index_direction_pairs: Vec<(usize, bool)> = order_by_exprs
             .into_iter()
-            .map(|(ident, direction)| -> ConversionResult<(usize, bool)> {
-                let index = result_aliases.get_index_of(&ident).ok_or(
-                    ConversionError::MissingColumnWithoutTable {
-                        identifier: Box::new(ident),
-                    },
-                )?;
+            .map(|order_by_expr| -> ConversionResult<(usize, bool)> {
+                let direction = order_by_expr.asc != Some(false);
+                let index = match order_by_expr.expr {
+                    Expr::Identifier(ident) => result_aliases.get_index_of(&ident).ok_or(
+                        ConversionError::MissingColumnWithoutTable {
+                            identifier: Box::new(ident),
+                        },
+                    )?,
+                    // A qualified column has to be a result column
+                    Expr::CompoundIdentifier(idents) => {
+                        let [table, column] = idents
+                            .into_iter()
+                            .map(identifier_from_ident)
+                            .collect::<ConversionResult<Vec<_>>>()?[..]
+                        else {
+                            return Err(ConversionError::UnsupportedOperation {
+                                message:
+                                    "Order by columns other than result columns not supported yet"
+                                        .to_string(),
+                            });
+                        };
+                        let expr =
+                            self.resolve_qualified_columns(&Expression::QualifiedColumn {
+                                table,
+                                column,
+                            })?;
+                        result_exprs
+                            .iter()
+                            .position(|aliased_expr| *aliased_expr.expr == expr)
+                            .ok_or_else(|| ConversionError::InvalidOrderBy {
+                                alias: format!("{table}.{column}"),
+                            })?
+                    }
+                    _ => {
+                        return Err(ConversionError::UnsupportedOperation {
+                            message: "Order by columns other than result columns not supported yet"
+                                .to_string(),
+                        })
+                    }
+                };
                 Ok((index, direction))
             })
             .collect::<ConversionResult<Vec<_>>>()?;
@@ -132,11 +274,69 @@ impl<'a> QueryContextBuilder<'a> {
         self
     }
 
-    pub fn visit_group_by_exprs(mut self, group_by_exprs: Vec<Ident>) -> ConversionResult<Self> {
-        for id in &group_by_exprs {
-            self.visit_column_identifier(id)?;
+    /// Visits the `GROUP BY` clause.
+    ///
+    /// A group by expression that is not a column is referred to by the alias of the first result
+    /// expression that is equal to it, since grouping by it is only supported when it is proven,
+    /// which requires the group by expressions to be result columns.
+    pub fn visit_group_by_exprs(
+        mut self,
+        group_by_exprs: Vec<Expression>,
+        result_exprs: &[SelectResultExpr],
+    ) -> ConversionResult<Self> {
+        let mut group_by_idents = Vec::with_capacity(group_by_exprs.len());
+        let mut group_by_key_exprs = IndexMap::default();
+        self.visit_qualified_result_columns(result_exprs)?;
+        let result_exprs = result_exprs
+            .iter()
+            .filter_map(|result_expr| match result_expr {
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => Some(aliased_expr),
+                SelectResultExpr::ALL => None,
+            })
+            .map(|aliased_expr| self.resolve_qualified_result_expr(aliased_expr.clone()))
+            .collect::<ConversionResult<Vec<_>>>()?;
+        for expr in group_by_exprs {
+            let expr = self.resolve_qualified_columns(&expr)?;
+            if let Expression::Column(identifier) = expr {
+                let id = Ident::from(identifier);
+                self.visit_column_identifier(&id)?;
+                group_by_idents.push(id);
+                continue;
+            }
+            let mut is_scalar = true;
+            // The rewrite only visits the expression
+            let _ = try_rewrite_expression(&expr, &mut |expr| {
+                is_scalar &= !matches!(
+                    expr,
+                    Expression::Aggregation { .. }
+                        | Expression::Wildcard
+                        | Expression::InSubquery { .. }
+                        | Expression::ScalarSubquery(_)
+                        | Expression::Window { .. }
+                );
+                Ok(None)
+            });
+            if !is_scalar {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "GROUP BY expressions can only be scalar expressions of columns"
+                        .to_string(),
+                });
+            }
+            let alias = result_exprs
+                .iter()
+                .find(|aliased_expr| *aliased_expr.expr == expr)
+                .map(|aliased_expr| Ident::from(aliased_expr.alias))
+                .ok_or_else(|| ConversionError::Unprovable {
+                    error:
+                        "a GROUP BY expression that is not a column has to be a result expression"
+                            .to_string(),
+                })?;
+            self.visit_expr(&expr)?;
+            group_by_key_exprs.insert(alias.clone(), expr);
+            group_by_idents.push(alias);
         }
-        self.context.set_group_by_exprs(group_by_exprs);
+        self.context.set_group_by_key_exprs(group_by_key_exprs);
+        self.context.set_group_by_exprs(group_by_idents);
         Ok(self)
     }
 
@@ -153,19 +353,22 @@ impl QueryContextBuilder<'_> {
         reason = "The assertion ensures there is at least one column, and this is a fundamental requirement for schema retrieval."
     )]
     fn lookup_schema(&self) -> Vec<(Ident, ColumnType)> {
-        let table_ref = self.context.get_table_ref();
-        let columns = self.schema_accessor.lookup_schema(table_ref.clone());
+        let mut columns: Vec<(Ident, ColumnType)> = Vec::new();
+        for table_ref in self.context.get_table_refs() {
+            for (column_name, column_type) in self.lookup_table_schema(table_ref) {
+                // Columns joined on under the same name are only returned once
+                if !columns.iter().any(|(name, _)| *name == column_name) {
+                    columns.push((column_name, column_type));
+                }
+            }
+        }
         assert!(!columns.is_empty(), "At least one column must exist");
         columns
     }
 
     fn visit_select_all_expr(&mut self) -> ConversionResult<()> {
         for (column_name, _) in self.lookup_schema() {
-            let column_identifier = Identifier::try_from(column_name).map_err(|e| {
-                ConversionError::IdentifierConversionError {
-                    error: format!("Failed to convert Ident to Identifier: {e}"),
-                }
-            })?;
+            let column_identifier = identifier_from_ident(column_name)?;
             let col_expr = Expression::Column(column_identifier);
             self.visit_aliased_expr(AliasedResultExpr::new(col_expr, column_identifier))?;
         }
@@ -184,11 +387,155 @@ impl QueryContextBuilder<'_> {
             Expression::Wildcard => Ok(ColumnType::BigInt), // Since COUNT(*) = COUNT(1)
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::Column(_) => self.visit_column_expr(expr),
+            Expression::QualifiedColumn { .. } => {
+                let expr = self.resolve_qualified_columns(expr)?;
+                self.visit_expr(&expr)
+            }
             Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
             Expression::Binary { op, left, right } => {
                 self.visit_binary_expr(&(*op).into(), left, right)
             }
-            Expression::Aggregation { op, expr } => self.visit_agg_expr(*op, expr),
+            Expression::Aggregation { op, expr, filter } => {
+                self.visit_agg_expr(*op, expr, filter.as_deref())
+            }
+            Expression::IsNull(expr) | Expression::IsNotNull(expr) => {
+                self.visit_expr(expr)?;
+                Ok(ColumnType::Boolean)
+            }
+            Expression::InList { expr, list } => {
+                let dtype = self.visit_expr(expr)?;
+                for candidate in list {
+                    let candidate_dtype = self.visit_expr(candidate)?;
+                    check_dtypes(dtype, candidate_dtype, &BinaryOperator::Eq)?;
+                }
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Between { expr, low, high } => {
+                let dtype = self.visit_expr(expr)?;
+                for bound in [low, high] {
+                    let bound_dtype = self.visit_expr(bound)?;
+                    check_dtypes(dtype, bound_dtype, &BinaryOperator::Lt)?;
+                }
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Like { expr, .. } => self.visit_like_expr(expr),
+            Expression::Extract { expr, .. } => {
+                self.visit_timestamp_expr(expr)?;
+                Ok(ColumnType::BigInt)
+            }
+            Expression::DateTrunc { expr, .. } | Expression::AddInterval { expr, .. } => {
+                self.visit_timestamp_expr(expr)
+            }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => self.visit_case_expr(when_then, else_expr.as_deref()),
+            Expression::InSubquery { expr, subquery } => {
+                let dtype = self.visit_expr(expr)?;
+                let subquery_dtype = self.visit_subquery(subquery)?;
+                check_dtypes(dtype, subquery_dtype, &BinaryOperator::Eq)?;
+                Ok(ColumnType::Boolean)
+            }
+            Expression::ScalarSubquery(subquery) => self.visit_subquery(subquery),
+            // Window functions are planned before the query that uses them, see `QueryExpr::try_new_window`
+            Expression::Window { .. } => Err(ConversionError::Unprovable {
+                error: "window functions are only supported in the SELECT list of a query \
+                    without GROUP BY"
+                    .to_string(),
+            }),
+        }
+    }
+
+    /// Plans a subquery of the `WHERE` clause and returns the type of its single result column.
+    fn visit_subquery(&mut self, subquery: &SelectStatement) -> ConversionResult<ColumnType> {
+        let (true, Some(default_schema)) = (self.in_where_scope, self.default_schema.clone())
+        else {
+            return Err(ConversionError::Unprovable {
+                error: "subqueries are only supported in WHERE clauses".to_string(),
+            });
+        };
+        let plan = QueryExpr::try_new_subquery_plan(
+            subquery.clone(),
+            default_schema,
+            self.schema_accessor,
+        )?;
+        let dtype = plan.get_column_result_fields()[0].data_type();
+        self.context.push_subquery_plan(subquery.clone(), plan);
+        Ok(dtype)
+    }
+
+    /// Visits the string of a `LIKE` predicate.
+    ///
+    /// If the string is a column, the number of its characters whose components are committed to is recorded,
+    /// since the pattern can only be checked against those.
+    fn visit_like_expr(&mut self, expr: &Expression) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        if dtype != ColumnType::VarChar {
+            return Err(ConversionError::InvalidDataType {
+                expected: ColumnType::VarChar,
+                actual: dtype,
+            });
+        }
+        if let Expression::Column(identifier) = expr {
+            let column = self.lookup_column_ref(&Ident::from(*identifier))?;
+            let character_count = self.character_count(&column);
+            self.context.push_character_count(column, character_count);
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    /// Visits the argument of a date/time function, which has to be a timestamp, and returns its type.
+    fn visit_timestamp_expr(&mut self, expr: &Expression) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        if !matches!(dtype, ColumnType::TimestampTZ(_, _)) {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!("date/time functions can not be applied to {dtype}"),
+            });
+        }
+        Ok(dtype)
+    }
+
+    /// Visits the conditions and branches of a `CASE` expression and returns the type of its result.
+    fn visit_case_expr(
+        &mut self,
+        when_then: &[(Expression, Expression)],
+        else_expr: Option<&Expression>,
+    ) -> ConversionResult<ColumnType> {
+        let mut dtype = None;
+        for (condition, then) in when_then {
+            let condition_dtype = self.visit_expr(condition)?;
+            if condition_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: condition_dtype,
+                });
+            }
+            dtype = Some(self.visit_case_branch(dtype, then)?);
+        }
+        let dtype = match else_expr {
+            Some(else_expr) => Some(self.visit_case_branch(dtype, else_expr)?),
+            None => dtype,
+        };
+        dtype.ok_or_else(|| ConversionError::InvalidExpression {
+            expression: "CASE expressions need at least one condition".to_string(),
+        })
+    }
+
+    /// Visits a branch of a `CASE` expression and returns the type that holds both it and the previous branches.
+    fn visit_case_branch(
+        &mut self,
+        dtype: Option<ColumnType>,
+        branch: &Expression,
+    ) -> ConversionResult<ColumnType> {
+        let branch_dtype = self.visit_expr(branch)?;
+        match dtype {
+            Some(dtype) => try_case_column_types(dtype, branch_dtype).map_err(|_| {
+                ConversionError::DataTypeMismatch {
+                    left_type: dtype.to_string(),
+                    right_type: branch_dtype.to_string(),
+                }
+            }),
+            None => Ok(branch_dtype),
         }
     }
 
@@ -220,6 +567,7 @@ impl QueryContextBuilder<'_> {
             | BinaryOperator::Lt => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Divide
+            | BinaryOperator::Modulo
             | BinaryOperator::Minus
             | BinaryOperator::Plus => Ok(left_dtype),
             _ => {
@@ -258,13 +606,29 @@ impl QueryContextBuilder<'_> {
         &mut self,
         op: AggregationOperator,
         expr: &Expression,
+        filter: Option<&Expression>,
     ) -> ConversionResult<ColumnType> {
         self.context.set_in_agg_scope(true)?;
 
         let expr_dtype = self.visit_expr(expr)?;
 
+        // The rows of the aggregation are the rows where the condition of its filter holds
+        if let Some(filter) = filter {
+            let filter_dtype = self.visit_expr(filter)?;
+            if filter_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: filter_dtype,
+                });
+            }
+        }
+
         // We only support sum/max/min aggregations on numeric columns.
-        if op != AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
+        if !matches!(
+            op,
+            AggregationOperator::Count | AggregationOperator::CountDistinct
+        ) && expr_dtype == ColumnType::VarChar
+        {
             return Err(ConversionError::non_numeric_expr_in_agg(
                 expr_dtype.to_string(),
                 op.to_string(),
@@ -273,11 +637,13 @@ impl QueryContextBuilder<'_> {
 
         self.context.set_in_agg_scope(false)?;
 
-        // Count aggregation always results in an integer type
-        if op == AggregationOperator::Count {
-            Ok(ColumnType::BigInt)
-        } else {
-            Ok(expr_dtype)
+        match op {
+            // Count aggregation always results in an integer type
+            AggregationOperator::Count | AggregationOperator::CountDistinct => {
+                Ok(ColumnType::BigInt)
+            }
+            AggregationOperator::Avg => Ok(try_average_column_type(expr_dtype)?),
+            _ => Ok(expr_dtype),
         }
     }
 
@@ -303,22 +669,313 @@ impl QueryContextBuilder<'_> {
     }
 
     fn visit_column_identifier(&mut self, column_name: &Ident) -> ConversionResult<ColumnType> {
-        let table_ref = self.context.get_table_ref();
-        let column_type = self
-            .schema_accessor
-            .lookup_column(table_ref.clone(), column_name.clone());
-
-        let column_type = column_type.ok_or_else(|| ConversionError::MissingColumn {
-            identifier: Box::new(column_name.clone()),
-            table_ref: table_ref.clone(),
-        })?;
+        let column = self.lookup_column_ref(column_name)?;
+        self.push_column(column_name, column)
+    }
 
-        let column = ColumnRef::new(table_ref.clone(), column_name.clone(), column_type);
+    /// Pushes `column` under the name `column_name`, marking it as nullable if it is, and returns its type
+    fn push_column(
+        &mut self,
+        column_name: &Ident,
+        column: ColumnRef,
+    ) -> ConversionResult<ColumnType> {
+        let column_type = *column.column_type();
 
+        if self.is_nullable_column(&column) {
+            self.context.push_nullable_column(column.clone());
+        }
         self.context.push_column_ref(column_name.clone(), column);
 
         Ok(column_type)
     }
+
+    /// The columns of a table of the query.
+    ///
+    /// The columns of a derived table are the result columns of its query,
+    /// and those of any other table are looked up in the schema of the database.
+    fn lookup_table_schema(&self, table_ref: &TableRef) -> Vec<(Ident, ColumnType)> {
+        match self.context.get_table_input() {
+            Some(input) if self.context.get_table_ref() == table_ref => input
+                .get_column_result_fields()
+                .iter()
+                .filter(|field| field.component() == ColumnComponent::Values)
+                .map(|field| (field.name(), field.data_type()))
+                .collect(),
+            _ => self.schema_accessor.lookup_schema(table_ref.clone()),
+        }
+    }
+
+    /// Whether a column of a table of the query is nullable, see [`Self::lookup_table_schema`]
+    fn is_nullable_column(&self, column: &ColumnRef) -> bool {
+        match self.context.get_table_input() {
+            Some(input) if *self.context.get_table_ref() == column.table_ref() => {
+                input.get_column_result_fields().iter().any(|field| {
+                    field.name() == column.column_id()
+                        && field.component() == ColumnComponent::Presence
+                })
+            }
+            _ => self
+                .schema_accessor
+                .is_nullable(column.table_ref(), column.column_id()),
+        }
+    }
+
+    /// The number of committed characters of a column of a table of the query, see [`Self::lookup_table_schema`].
+    ///
+    /// The characters of the result columns of a derived table are not committed to.
+    fn character_count(&self, column: &ColumnRef) -> usize {
+        match self.context.get_table_input() {
+            Some(_) if *self.context.get_table_ref() == column.table_ref() => 0,
+            _ => self
+                .schema_accessor
+                .character_count(column.table_ref(), column.column_id()),
+        }
+    }
+
+    /// The type of a column of a table of the query, if the table has it, see [`Self::lookup_table_schema`]
+    fn lookup_column_type(&self, table_ref: &TableRef, column_name: &Ident) -> Option<ColumnType> {
+        match self.context.get_table_input() {
+            Some(_) if self.context.get_table_ref() == table_ref => self
+                .lookup_table_schema(table_ref)
+                .into_iter()
+                .find(|(name, _)| name == column_name)
+                .map(|(_, column_type)| column_type),
+            _ => self
+                .schema_accessor
+                .lookup_column(table_ref.clone(), column_name.clone()),
+        }
+    }
+
+    /// Resolves an unqualified column name, which is either the result alias of a qualified column,
+    /// see [`Self::visit_qualified_result_columns`], or a column of the tables of the query,
+    /// see [`Self::lookup_table_column_ref`].
+    fn lookup_column_ref(&self, column_name: &Ident) -> ConversionResult<ColumnRef> {
+        match self
+            .qualified_column_aliases
+            .iter()
+            .find(|(_, alias)| Ident::from(**alias) == *column_name)
+        {
+            Some((column_ref, _)) => Ok(column_ref.clone()),
+            None => self.lookup_table_column_ref(column_name),
+        }
+    }
+
+    /// Resolves an unqualified column name against the tables of the query.
+    ///
+    /// In a join, a column name that exists in both tables is only allowed
+    /// if it names the join column on both sides, in which case the left column is used.
+    /// A right join column with a distinct name also resolves to the left join column,
+    /// since the two are equal in every row of an inner join.
+    /// In an outer join such a column is rejected, since unmatched rows have no value for it.
+    fn lookup_table_column_ref(&self, column_name: &Ident) -> ConversionResult<ColumnRef> {
+        let columns = self
+            .context
+            .get_table_refs()
+            .filter_map(|table_ref| {
+                self.lookup_column_type(table_ref, column_name)
+                    .map(|column_type| {
+                        ColumnRef::new(table_ref.clone(), column_name.clone(), column_type)
+                    })
+            })
+            .collect::<Vec<_>>();
+        let join_conditions = self.context.get_join_conditions();
+        match columns.as_slice() {
+            [] => Err(ConversionError::MissingColumn {
+                identifier: Box::new(column_name.clone()),
+                table_ref: self.context.get_table_ref().clone(),
+            }),
+            [column] => self.resolve_right_join_column(&column_name.value, column),
+            [left_column, right_column]
+                if join_conditions
+                    .iter()
+                    .any(|(left, right)| left == left_column && right == right_column) =>
+            {
+                Ok(left_column.clone())
+            }
+            _ => Err(ConversionError::AmbiguousColumn {
+                identifier: Box::new(column_name.clone()),
+            }),
+        }
+    }
+
+    /// Resolves `column`, which is referred to as `column_name`, to the left join column if it is
+    /// the right join column, since the two are equal in every row of an inner join.
+    ///
+    /// In an outer join such a column is rejected, since unmatched rows have no value for it.
+    fn resolve_right_join_column(
+        &self,
+        column_name: &str,
+        column: &ColumnRef,
+    ) -> ConversionResult<ColumnRef> {
+        match self
+            .context
+            .get_join_conditions()
+            .iter()
+            .find(|(_, right)| right == column)
+        {
+            Some(_) if self.context.is_outer_join() => Err(ConversionError::Unprovable {
+                error: format!(
+                    "column '{column_name}' is joined on from the side of an outer join whose rows are not preserved"
+                ),
+            }),
+            Some((left, _)) => Ok(left.clone()),
+            None => Ok(column.clone()),
+        }
+    }
+
+    /// Resolves the column `table.column` outside of the join conditions, see [`Self::lookup_column_ref`]
+    fn lookup_qualified_column_ref(
+        &self,
+        table: Identifier,
+        column: Identifier,
+    ) -> ConversionResult<ColumnRef> {
+        let column_ref = self.lookup_join_column_ref(&QualifiedColumn {
+            table: Some(table),
+            column,
+        })?;
+        self.resolve_right_join_column(&format!("{table}.{column}"), &column_ref)
+    }
+
+    /// Registers the result aliases of the qualified result columns whose name is ambiguous,
+    /// e.g. `bonus_name` in `SELECT employees.name, bonuses.name AS bonus_name FROM employees JOIN bonuses ...`,
+    /// under which they are referred to throughout the query.
+    ///
+    /// An alias that is the name of a column of the tables of the query can only refer to that column.
+    fn visit_qualified_result_columns(
+        &mut self,
+        result_exprs: &[SelectResultExpr],
+    ) -> ConversionResult<()> {
+        for result_expr in result_exprs {
+            let SelectResultExpr::AliasedResultExpr(AliasedResultExpr { expr, alias }) =
+                result_expr
+            else {
+                continue;
+            };
+            let Expression::QualifiedColumn { table, column } = **expr else {
+                continue;
+            };
+            let column_ref = self.lookup_qualified_column_ref(table, column)?;
+            if self.lookup_table_column_ref(&Ident::from(column)).ok() == Some(column_ref.clone())
+                || self.qualified_column_aliases.contains_key(&column_ref)
+            {
+                continue;
+            }
+            // The alias can only be the name of the column itself or of no other column
+            let is_other_column = match self.lookup_column_ref(&Ident::from(*alias)) {
+                Ok(alias_column_ref) => alias_column_ref != column_ref,
+                Err(ConversionError::MissingColumn { .. }) => false,
+                Err(_) => *alias != column,
+            };
+            if is_other_column {
+                return Err(ConversionError::AmbiguousColumn {
+                    identifier: Box::new(Ident::from(*alias)),
+                });
+            }
+            self.qualified_column_aliases.insert(column_ref, *alias);
+        }
+        Ok(())
+    }
+
+    /// Rewrites the qualified columns of `expr` into columns, which are referred to by their name
+    /// or, if their name is ambiguous, by their result alias, see [`Self::visit_qualified_result_columns`].
+    fn resolve_qualified_columns(&self, expr: &Expression) -> ConversionResult<Expression> {
+        try_rewrite_expression(expr, &mut |expr| match expr {
+            Expression::QualifiedColumn { table, column } => {
+                let column_ref = self.lookup_qualified_column_ref(*table, *column)?;
+                if self.lookup_table_column_ref(&Ident::from(*column)).ok()
+                    == Some(column_ref.clone())
+                {
+                    return Ok(Some(Expression::Column(*column)));
+                }
+                self.qualified_column_aliases
+                    .get(&column_ref)
+                    .map(|alias| Some(Expression::Column(*alias)))
+                    .ok_or_else(|| ConversionError::Unprovable {
+                        error: format!(
+                            "column '{table}.{column}' has an ambiguous name, \
+                            so it is only supported if it is selected as a result column"
+                        ),
+                    })
+            }
+            _ => Ok(None),
+        })
+    }
+
+    /// Resolves the qualified columns of a result expression, see [`Self::resolve_qualified_columns`]
+    fn resolve_qualified_result_expr(
+        &self,
+        aliased_expr: AliasedResultExpr,
+    ) -> ConversionResult<AliasedResultExpr> {
+        Ok(AliasedResultExpr {
+            expr: Box::new(self.resolve_qualified_columns(&aliased_expr.expr)?),
+            alias: aliased_expr.alias,
+        })
+    }
+
+    /// Resolves a column of a join condition, which may be qualified by the name of its table
+    fn lookup_join_column_ref(&self, column: &QualifiedColumn) -> ConversionResult<ColumnRef> {
+        let column_name = Ident::from(column.column);
+        let Some(table) = column.table else {
+            return self.lookup_column_ref(&column_name);
+        };
+        let table_id = Ident::from(table);
+        let table_ref = self
+            .context
+            .get_table_refs()
+            .find(|table_ref| *table_ref.table_id() == table_id)
+            .ok_or_else(|| ConversionError::InvalidExpression {
+                expression: format!("table '{table_id}' is not part of the query"),
+            })?;
+        let column_type = self
+            .lookup_column_type(table_ref, &column_name)
+            .ok_or_else(|| ConversionError::MissingColumn {
+                identifier: Box::new(column_name.clone()),
+                table_ref: table_ref.clone(),
+            })?;
+        Ok(ColumnRef::new(table_ref.clone(), column_name, column_type))
+    }
+
+    fn visit_join_condition(&mut self, condition: &JoinCondition) -> ConversionResult<()> {
+        let first = self.lookup_join_column_ref(&condition.left)?;
+        let second = self.lookup_join_column_ref(&condition.right)?;
+        let left_table_ref = self.context.get_table_ref();
+        let (left, right) = match (
+            first.table_ref() == *left_table_ref,
+            second.table_ref() == *left_table_ref,
+        ) {
+            (true, false) => (first, second),
+            (false, true) => (second, first),
+            _ => {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "a join condition must compare a column from each side of the join"
+                        .to_string(),
+                })
+            }
+        };
+        if left.column_type() != right.column_type() {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: left.column_type().to_string(),
+                right_type: right.column_type().to_string(),
+            });
+        }
+        self.context.push_join_condition(left, right);
+        Ok(())
+    }
+}
+
+fn identifier_from_ident(ident: Ident) -> ConversionResult<Identifier> {
+    Identifier::try_from(ident).map_err(|e| ConversionError::IdentifierConversionError {
+        error: format!("Failed to convert Ident to Identifier: {e}"),
+    })
+}
+
+fn named_table_ref(
+    table: Identifier,
+    schema: Option<Identifier>,
+    default_schema: Ident,
+) -> TableRef {
+    let actual_schema = schema.map_or(default_schema, Ident::from);
+    TableRef::from_idents(Some(actual_schema), Ident::from(table))
 }
 
 /// Checks if the binary operation between the left and right data types is valid.
@@ -381,6 +1038,7 @@ pub(crate) fn type_check_binary_operation(
         }
         BinaryOperator::Multiply => try_multiply_column_types(left_dtype, right_dtype).is_ok(),
         BinaryOperator::Divide => left_dtype.is_numeric() && right_dtype.is_numeric(),
+        BinaryOperator::Modulo => try_modulo_column_types(left_dtype, right_dtype).is_ok(),
         _ => {
             // Handle unsupported binary operations
             false
@@ -1,17 +1,37 @@
-use super::{EnrichedExpr, FilterExecBuilder, QueryContextBuilder};
+use super::{
+    query_context::{subquery_table_ref, try_rewrite_expression},
+    DynProofExprBuilder, EnrichedExpr, FilterExecBuilder, QueryContext, QueryContextBuilder,
+    SortMergeJoinExecBuilder, WhereExprBuilder,
+};
 use crate::{
-    base::database::SchemaAccessor,
+    base::{
+        database::{
+            ColumnComponent, ColumnField, ColumnRef, LiteralValue, SchemaAccessor, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+    },
     sql::{
-        parse::ConversionResult,
+        parse::{ConversionError, ConversionResult},
         postprocessing::{
-            GroupByPostprocessing, OrderByPostprocessing, OwnedTablePostprocessing,
-            SelectPostprocessing, SlicePostprocessing,
+            AveragePostprocessing, GroupByPostprocessing, OrderByPostprocessing,
+            OwnedTablePostprocessing, SelectPostprocessing, SlicePostprocessing,
+        },
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{
+            AliasedWindowFunction, DistinctExec, DynProofPlan, FilterExec, GroupByExec,
+            ProjectionExec, SliceExec, SortExec, UnionExec, WindowExec, WindowFunction,
         },
-        proof_plans::{DynProofPlan, GroupByExec},
     },
 };
-use alloc::{fmt, vec, vec::Vec};
-use proof_of_sql_parser::{intermediate_ast::SetExpression, SelectStatement};
+use alloc::{boxed::Box, fmt, format, string::ToString, vec, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        self, AggregationOperator, AliasedResultExpr, Expression, OrderBy, OrderByDirection,
+        SelectResultExpr, SetExpression, Slice, TableExpression,
+    },
+    Identifier, SelectStatement,
+};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
@@ -51,43 +71,103 @@ impl QueryExpr {
         default_schema: Ident,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
+        let ast = if ast.with.is_empty() {
+            ast
+        } else {
+            inline_common_table_expressions(&ast, &IndexMap::default())?
+        };
         let context = match *ast.expr {
             SetExpression::Query {
                 result_exprs,
                 from,
                 where_expr,
                 group_by,
+                having,
+            } if result_exprs.iter().any(has_window_function) => {
+                if !group_by.is_empty() || having.is_some() {
+                    return Err(ConversionError::Unprovable {
+                        error: "window functions are not supported in queries with GROUP BY"
+                            .to_string(),
+                    });
+                }
+                return Self::try_new_window(
+                    result_exprs,
+                    from,
+                    where_expr,
+                    ast.order_by,
+                    ast.slice,
+                    default_schema,
+                    schema_accessor,
+                );
+            }
+            SetExpression::Query {
+                result_exprs,
+                from,
+                where_expr,
+                group_by,
+                having,
             } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(&from, default_schema)
-                .visit_group_by_exprs(group_by.into_iter().map(Ident::from).collect())?
+                .visit_table_expr(&from, default_schema)?
+                .visit_group_by_exprs(group_by, &result_exprs)?
                 .visit_result_exprs(result_exprs)?
                 .visit_where_expr(where_expr)?
+                .visit_having_expr(having)?
                 .visit_order_by_exprs(ast.order_by.into_iter().map(Into::into).collect())?
                 .visit_slice_expr(ast.slice)
                 .build()?,
+            SetExpression::UnionAll { inputs } => {
+                return Self::try_new_union_all(
+                    inputs,
+                    &ast.order_by,
+                    ast.slice.as_ref(),
+                    &default_schema,
+                    schema_accessor,
+                );
+            }
+            SetExpression::Distinct { input } => {
+                return Self::try_new_distinct(
+                    *input,
+                    &ast.order_by,
+                    ast.slice.as_ref(),
+                    &default_schema,
+                    schema_accessor,
+                );
+            }
         };
+        Self::try_new_with_context(&context)
+    }
+
+    /// Plans a query of a single table, or of a join of two tables, given its context.
+    #[allow(clippy::too_many_lines)]
+    fn try_new_with_context(context: &QueryContext) -> ConversionResult<Self> {
         let result_aliased_exprs = context.get_aliased_result_exprs()?.to_vec();
         let group_by = context.get_group_by_exprs();
         // Figure out the basic postprocessing steps.
-        let mut postprocessing = vec![];
-        let order_bys = context.get_order_by_exprs();
-        if !order_bys.is_empty() {
-            postprocessing.push(OwnedTablePostprocessing::new_order_by(
-                OrderByPostprocessing::new(order_bys.to_vec()),
-            ));
-        }
-        if let Some(slice) = context.get_slice_expr() {
-            postprocessing.push(OwnedTablePostprocessing::new_slice(
-                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
-            ));
-        }
-        if context.has_agg() {
-            if let Some(group_by_expr) = Option::<GroupByExec>::try_from(&context)? {
+        let mut postprocessing = order_by_and_slice_postprocessing(
+            context.get_order_by_exprs(),
+            context.get_slice_expr().as_ref(),
+        );
+        let query_expr = if context.get_join_table_ref().is_some() {
+            Self::try_new_join(context, result_aliased_exprs, postprocessing)
+        } else if context.has_agg() {
+            if let Some(group_by_expr) = build_count_distinct(context)? {
                 Ok(Self {
                     proof_expr: DynProofPlan::GroupBy(group_by_expr),
                     postprocessing,
                 })
+            } else if let Some(group_by_expr) = Option::<GroupByExec>::try_from(context)? {
+                add_average_postprocessing(&mut postprocessing, &result_aliased_exprs);
+                let proof_expr = if context.get_having_expr().is_some() {
+                    DynProofPlan::Filter(build_having_filter(context, group_by_expr)?)
+                } else {
+                    DynProofPlan::GroupBy(group_by_expr)
+                };
+                Ok(Self {
+                    proof_expr,
+                    postprocessing,
+                })
             } else {
+                reject_group_by_key_exprs(context)?;
                 let raw_enriched_exprs = result_aliased_exprs
                     .iter()
                     .map(|aliased_expr| EnrichedExpr {
@@ -96,31 +176,20 @@ impl QueryExpr {
                     })
                     .collect::<Vec<_>>();
                 let filter = FilterExecBuilder::new(context.get_column_mapping())
-                    .add_table_expr(context.get_table_ref().clone())
+                    .add_nullable_columns(context.get_nullable_columns().clone())
+                    .add_character_counts(context.get_character_counts().clone())
+                    .add_table_expr(context.get_table_expr())
+                    .add_subquery_plans(context.get_subquery_plans().clone())
                     .add_where_expr(context.get_where_expr().clone())?
                     .add_result_columns(&raw_enriched_exprs)
                     .build();
 
-                let group_by_postprocessing =
-                    GroupByPostprocessing::try_new(group_by.to_vec(), result_aliased_exprs)?;
-                postprocessing.insert(
-                    0,
-                    OwnedTablePostprocessing::new_group_by(group_by_postprocessing.clone()),
-                );
-                let remainder_exprs = group_by_postprocessing.remainder_exprs();
-                // Check whether we need to do select postprocessing.
-                // That is, if any of them is not simply a column reference.
-                if remainder_exprs
-                    .iter()
-                    .any(|expr| expr.try_as_identifier().is_none())
-                {
-                    postprocessing.insert(
-                        1,
-                        OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
-                            remainder_exprs.to_vec(),
-                        )),
-                    );
-                }
+                add_group_by_postprocessing(
+                    &mut postprocessing,
+                    group_by.to_vec(),
+                    result_aliased_exprs,
+                    context.get_having_expr().as_deref().cloned(),
+                )?;
                 Ok(Self {
                     proof_expr: DynProofPlan::Filter(filter),
                     postprocessing,
@@ -131,14 +200,24 @@ impl QueryExpr {
             let column_mapping = context.get_column_mapping();
             let enriched_exprs = result_aliased_exprs
                 .iter()
-                .map(|aliased_expr| EnrichedExpr::new(aliased_expr.clone(), &column_mapping))
+                .map(|aliased_expr| {
+                    EnrichedExpr::new(
+                        aliased_expr.clone(),
+                        &column_mapping,
+                        context.get_nullable_columns(),
+                        context.get_character_counts(),
+                    )
+                })
                 .collect::<Vec<_>>();
             let select_exprs = enriched_exprs
                 .iter()
                 .map(|enriched_expr| enriched_expr.residue_expression.clone())
                 .collect::<Vec<_>>();
             let filter = FilterExecBuilder::new(context.get_column_mapping())
-                .add_table_expr(context.get_table_ref().clone())
+                .add_nullable_columns(context.get_nullable_columns().clone())
+                .add_character_counts(context.get_character_counts().clone())
+                .add_table_expr(context.get_table_expr())
+                .add_subquery_plans(context.get_subquery_plans().clone())
                 .add_where_expr(context.get_where_expr().clone())?
                 .add_result_columns(&enriched_exprs)
                 .build();
@@ -156,9 +235,528 @@ impl QueryExpr {
                 proof_expr: DynProofPlan::Filter(filter),
                 postprocessing,
             })
+        }?;
+        Ok(query_expr.with_provable_order_by_and_slice(
+            context.get_order_by_exprs(),
+            context.get_slice_expr().as_ref(),
+        ))
+    }
+
+    /// Plans a `UNION ALL` of queries, each of which has to be provable without postprocessing.
+    ///
+    /// The `ORDER BY` and `LIMIT` are applied to the combined result,
+    /// whose columns are named after the columns of the first query.
+    fn try_new_union_all(
+        inputs: Vec<SetExpression>,
+        order_by: &[OrderBy],
+        slice: Option<&Slice>,
+        default_schema: &Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let input_plans = inputs
+            .into_iter()
+            .map(|input| {
+                let query_expr = Self::try_new(
+                    SelectStatement {
+                        with: vec![],
+                        expr: Box::new(input),
+                        order_by: vec![],
+                        slice: None,
+                    },
+                    default_schema.clone(),
+                    schema_accessor,
+                )?;
+                if !query_expr.postprocessing.is_empty()
+                    || !proves_result_without_it(&query_expr.proof_expr)
+                {
+                    return Err(ConversionError::Unprovable {
+                        error:
+                            "every input of a UNION ALL has to be provable without postprocessing"
+                                .to_string(),
+                    });
+                }
+                Ok(query_expr.proof_expr)
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        let schema = input_plans
+            .first()
+            .ok_or_else(|| ConversionError::Unprovable {
+                error: "a UNION ALL needs at least one input".to_string(),
+            })?
+            .get_column_result_fields();
+        let left_types = schema
+            .iter()
+            .map(ColumnField::data_type)
+            .collect::<Vec<_>>();
+        let left_components = schema
+            .iter()
+            .map(ColumnField::component)
+            .collect::<Vec<_>>();
+        for input_plan in &input_plans[1..] {
+            let right_fields = input_plan.get_column_result_fields();
+            let right_types = right_fields
+                .iter()
+                .map(ColumnField::data_type)
+                .collect::<Vec<_>>();
+            // A nullable column has a presence field, so the columns of the inputs have to agree on being nullable
+            let right_components = right_fields
+                .iter()
+                .map(ColumnField::component)
+                .collect::<Vec<_>>();
+            if right_types != left_types || right_components != left_components {
+                return Err(ConversionError::UnionSchemaMismatch {
+                    left_types,
+                    right_types,
+                });
+            }
+        }
+        let index_direction_pairs = order_by_index_direction_pairs(order_by, &schema)?;
+        let postprocessing = order_by_and_slice_postprocessing(&index_direction_pairs, slice);
+        let query_expr = Self {
+            proof_expr: DynProofPlan::Union(UnionExec::new(input_plans, schema)),
+            postprocessing,
+        };
+        Ok(query_expr.with_provable_order_by_and_slice(&index_direction_pairs, slice))
+    }
+
+    /// Plans the distinct rows of a query, which has to be provable without postprocessing.
+    ///
+    /// This covers both `SELECT DISTINCT` and `UNION`, which is the distinct rows of a `UNION ALL`.
+    /// The `ORDER BY` and `LIMIT` are applied to the distinct rows.
+    fn try_new_distinct(
+        input: SetExpression,
+        order_by: &[OrderBy],
+        slice: Option<&Slice>,
+        default_schema: &Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let input_query_expr = Self::try_new(
+            SelectStatement {
+                with: vec![],
+                expr: Box::new(input),
+                order_by: vec![],
+                slice: None,
+            },
+            default_schema.clone(),
+            schema_accessor,
+        )?;
+        if !input_query_expr.postprocessing.is_empty()
+            || !proves_result_without_it(&input_query_expr.proof_expr)
+        {
+            return Err(ConversionError::Unprovable {
+                error: "the input of a DISTINCT has to be provable without postprocessing"
+                    .to_string(),
+            });
+        }
+        if !DistinctExec::is_provable(&input_query_expr.proof_expr) {
+            return Err(ConversionError::Unprovable {
+                error: "DISTINCT is only supported on few enough integer, boolean and timestamp \
+                    columns"
+                    .to_string(),
+            });
+        }
+        let schema = input_query_expr.proof_expr.get_column_result_fields();
+        let index_direction_pairs = order_by_index_direction_pairs(order_by, &schema)?;
+        let postprocessing = order_by_and_slice_postprocessing(&index_direction_pairs, slice);
+        let query_expr = Self {
+            proof_expr: DynProofPlan::Distinct(DistinctExec::new(Box::new(
+                input_query_expr.proof_expr,
+            ))),
+            postprocessing,
+        };
+        Ok(query_expr.with_provable_order_by_and_slice(&index_direction_pairs, slice))
+    }
+
+    /// Plans a query whose result expressions use window functions, i.e. of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+    /// ```
+    /// where the result expressions may contain `<function> OVER (PARTITION BY ... ORDER BY ...)`.
+    ///
+    /// The rows of the table that satisfy the `WHERE` clause are the input of a [`WindowExec`]
+    /// per distinct window, whose functions are queried as the columns of a derived table.
+    /// NULLs are zero, so the query may not reference nullable columns.
+    #[allow(clippy::too_many_lines)]
+    fn try_new_window(
+        result_exprs: Vec<SelectResultExpr>,
+        from: Vec<Box<TableExpression>>,
+        where_expr: Option<Box<Expression>>,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+        default_schema: Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        // 1. The window functions are replaced by the columns that hold them
+        let mut windows = Vec::new();
+        let mut columns = IndexSet::<Identifier>::default();
+        let result_exprs = result_exprs
+            .into_iter()
+            .map(|result_expr| match result_expr {
+                SelectResultExpr::ALL => Ok(SelectResultExpr::ALL),
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                    let expr = try_rewrite_expression(&aliased_expr.expr, &mut |expr| {
+                        Ok(match expr {
+                            Expression::Column(identifier) => {
+                                columns.insert(*identifier);
+                                None
+                            }
+                            Expression::QualifiedColumn { .. } => {
+                                return Err(unqualified_window_columns_error());
+                            }
+                            Expression::Window {
+                                function,
+                                partition_by,
+                                order_by,
+                            } => {
+                                let identifier = internal_identifier("window", windows.len());
+                                windows.push((
+                                    function.clone(),
+                                    partition_by.clone(),
+                                    order_by.clone(),
+                                ));
+                                Some(Expression::Column(identifier))
+                            }
+                            _ => None,
+                        })
+                    })?;
+                    Ok(SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+                        expr,
+                        aliased_expr.alias,
+                    )))
+                }
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        // 2. The input has the columns that the query uses,
+        // followed by the arguments of the window functions that are not columns
+        let mut argument_exprs = Vec::new();
+        let mut window_arguments = Vec::new();
+        for (i, (function, partition_by, order_by)) in windows.iter().enumerate() {
+            if order_by.iter().any(|order_by| order_by.table.is_some()) {
+                return Err(unqualified_window_columns_error());
+            }
+            columns.extend(partition_by.iter().copied());
+            columns.extend(order_by.iter().map(|order_by| order_by.expr));
+            if matches!(
+                function.argument(),
+                Some(Expression::QualifiedColumn { .. })
+            ) {
+                return Err(unqualified_window_columns_error());
+            }
+            window_arguments.push(function.argument().map(|argument| match argument {
+                Expression::Column(identifier) => {
+                    columns.insert(*identifier);
+                    *identifier
+                }
+                _ => {
+                    let identifier = internal_identifier("window_argument", i);
+                    argument_exprs.push(SelectResultExpr::AliasedResultExpr(
+                        AliasedResultExpr::new(argument.clone(), identifier),
+                    ));
+                    identifier
+                }
+            }));
+        }
+        let selects_all = result_exprs
+            .iter()
+            .any(|result_expr| matches!(result_expr, SelectResultExpr::ALL));
+        let input_exprs = if selects_all || columns.is_empty() {
+            vec![SelectResultExpr::ALL]
+        } else {
+            columns
+                .iter()
+                .map(|&identifier| {
+                    SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+                        Expression::Column(identifier),
+                        identifier,
+                    ))
+                })
+                .collect()
+        };
+        let num_arguments = argument_exprs.len();
+        let table_ref = TableRef::from_idents(None, Ident::new("__window__"));
+        let input = Self::try_new_input_plan(
+            SelectStatement {
+                with: vec![],
+                expr: Box::new(SetExpression::Query {
+                    result_exprs: input_exprs.into_iter().chain(argument_exprs).collect(),
+                    from,
+                    where_expr,
+                    group_by: vec![],
+                    having: None,
+                }),
+                order_by: vec![],
+                slice: None,
+            },
+            &table_ref,
+            default_schema.clone(),
+            schema_accessor,
+        )?;
+        if references_nullable_columns(&input, schema_accessor) {
+            return Err(ConversionError::Unprovable {
+                error: "window functions on nullable columns are not supported".to_string(),
+            });
+        }
+        let input_fields = input.get_column_result_fields();
+        let index_of = |identifier: Identifier| {
+            let ident = Ident::from(identifier);
+            input_fields
+                .iter()
+                .position(|field| field.name() == ident)
+                .ok_or(ConversionError::MissingColumnWithoutTable {
+                    identifier: Box::new(ident),
+                })
+        };
+        // 3. The functions over the same window are evaluated by the same `WindowExec`
+        let mut functions_by_window =
+            IndexMap::<(Vec<usize>, Vec<(usize, bool)>), Vec<AliasedWindowFunction>>::default();
+        for (i, ((function, partition_by, order_by), argument)) in
+            windows.iter().zip(window_arguments).enumerate()
+        {
+            let partition_by = partition_by
+                .iter()
+                .map(|&identifier| index_of(identifier))
+                .collect::<ConversionResult<Vec<_>>>()?;
+            let order_by = order_by
+                .iter()
+                .map(|order_by| {
+                    Ok((
+                        index_of(order_by.expr)?,
+                        order_by.direction == OrderByDirection::Asc,
+                    ))
+                })
+                .collect::<ConversionResult<Vec<_>>>()?;
+            // Only the functions with an argument use its index
+            let argument_index = argument.map(&index_of).transpose()?.unwrap_or_default();
+            let function = match function {
+                intermediate_ast::WindowFunction::RowNumber => WindowFunction::RowNumber,
+                intermediate_ast::WindowFunction::Rank => WindowFunction::Rank,
+                intermediate_ast::WindowFunction::Lag(_) => WindowFunction::Lag(argument_index),
+                intermediate_ast::WindowFunction::Lead(_) => WindowFunction::Lead(argument_index),
+                intermediate_ast::WindowFunction::Sum(_) => WindowFunction::Sum(argument_index),
+            };
+            functions_by_window
+                .entry((partition_by, order_by))
+                .or_default()
+                .push(AliasedWindowFunction {
+                    function,
+                    alias: internal_identifier("window", i).into(),
+                });
+        }
+        // The columns of the input come first in the result of a `WindowExec`,
+        // so their indexes are the same for all windows
+        let mut plan = input;
+        for ((partition_by, order_by), functions) in functions_by_window {
+            let window_functions = functions
+                .iter()
+                .map(|aliased_function| aliased_function.function)
+                .collect::<Vec<_>>();
+            if !WindowExec::is_provable(&plan, &partition_by, &order_by, &window_functions) {
+                return Err(ConversionError::Unprovable {
+                    error: "window functions are only supported with PARTITION BY and ORDER BY \
+                        on few enough integer, boolean and timestamp columns, \
+                        and SUM on numeric columns"
+                        .to_string(),
+                });
+            }
+            plan = DynProofPlan::Window(WindowExec::new(
+                Box::new(plan),
+                partition_by,
+                order_by,
+                functions,
+            ));
+        }
+        // 4. The query of the windows, where `*` stands for the columns of the table
+        let mut outer_result_exprs = Vec::new();
+        for result_expr in result_exprs {
+            if let SelectResultExpr::ALL = result_expr {
+                for field in &input_fields[..input_fields.len() - num_arguments] {
+                    let identifier = Identifier::try_from(field.name()).map_err(|e| {
+                        ConversionError::IdentifierConversionError {
+                            error: format!("Failed to convert Ident to Identifier: {e}"),
+                        }
+                    })?;
+                    outer_result_exprs.push(SelectResultExpr::AliasedResultExpr(
+                        AliasedResultExpr::new(Expression::Column(identifier), identifier),
+                    ));
+                }
+            } else {
+                outer_result_exprs.push(result_expr);
+            }
+        }
+        let context = QueryContextBuilder::new(schema_accessor)
+            .visit_table_input(table_ref, plan, default_schema)
+            .visit_result_exprs(outer_result_exprs)?
+            .visit_order_by_exprs(order_by.into_iter().map(Into::into).collect())?
+            .visit_slice_expr(slice)
+            .build()?;
+        Self::try_new_with_context(&context)
+    }
+
+    /// Plans the query of a derived table, whose columns are referred to as columns of `table_ref`.
+    ///
+    /// The query has to be provable without postprocessing, so that its result can be the input of another plan.
+    /// Since the rows of a table have no order, an `ORDER BY` without a `LIMIT` or `OFFSET` is dropped.
+    pub(super) fn try_new_input_plan(
+        mut ast: SelectStatement,
+        table_ref: &TableRef,
+        default_schema: Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<DynProofPlan> {
+        if ast.slice.is_none() {
+            ast.order_by.clear();
+        }
+        let query_expr = Self::try_new(ast, default_schema, schema_accessor)?;
+        match (query_expr.proof_expr, query_expr.postprocessing.as_slice()) {
+            (proof_expr, []) if proves_result_without_it(&proof_expr) => Ok(proof_expr),
+            (join @ DynProofPlan::SortMergeJoin(_), [OwnedTablePostprocessing::Select(select)]) => {
+                build_join_projection(join, select, table_ref)
+            }
+            _ => Err(ConversionError::Unprovable {
+                error: "the query of a derived table has to be provable without postprocessing"
+                    .to_string(),
+            }),
         }
     }
 
+    /// Plans a subquery of a `WHERE` clause, which has to have a single result column
+    /// and be provable without postprocessing.
+    ///
+    /// An aggregation without `GROUP BY`, such as `SELECT MAX(a) FROM t`, is proven by a [`GroupByExec`]
+    /// without group by columns that also counts the rows, so its aggregation is projected from its result.
+    /// NULLs are zero, so subqueries may not reference nullable columns.
+    pub(super) fn try_new_subquery_plan(
+        mut ast: SelectStatement,
+        default_schema: Ident,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<DynProofPlan> {
+        if ast.slice.is_none() {
+            ast.order_by.clear();
+        }
+        let is_counted = add_subquery_count(&mut ast);
+        let query_expr = Self::try_new(ast, default_schema, schema_accessor)?;
+        if !query_expr.postprocessing.is_empty()
+            || !proves_result_without_it(&query_expr.proof_expr)
+        {
+            return Err(ConversionError::Unprovable {
+                error: "subqueries have to be provable without postprocessing".to_string(),
+            });
+        }
+        let plan = if is_counted {
+            let table_ref = subquery_table_ref();
+            let field = query_expr.proof_expr.get_column_result_fields()[0].clone();
+            DynProofPlan::Projection(ProjectionExec::new(
+                vec![AliasedDynProofExpr {
+                    expr: DynProofExpr::new_column(ColumnRef::new(
+                        table_ref.clone(),
+                        field.name(),
+                        field.data_type(),
+                    )),
+                    alias: field.name(),
+                }],
+                TableExpr::new_with_input(table_ref, query_expr.proof_expr),
+            ))
+        } else {
+            query_expr.proof_expr
+        };
+        if references_nullable_columns(&plan, schema_accessor) {
+            return Err(ConversionError::Unprovable {
+                error: "subqueries on nullable columns are not supported".to_string(),
+            });
+        }
+        let num_columns = plan.get_column_result_fields().len();
+        if num_columns != 1 {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!(
+                    "subqueries must have a single result column, but this one has {num_columns}"
+                ),
+            });
+        }
+        Ok(plan)
+    }
+
+    /// Proves the `ORDER BY` and `LIMIT` of the query with a [`SortExec`] and a [`SliceExec`]
+    /// if they are the only postprocessing steps that are left.
+    ///
+    /// Otherwise, e.g. if the sort key is not provable or the offset counts from the end,
+    /// the query is returned unchanged.
+    fn with_provable_order_by_and_slice(
+        self,
+        order_by: &[(usize, bool)],
+        slice: Option<&Slice>,
+    ) -> Self {
+        let (
+            Some(slice),
+            [OwnedTablePostprocessing::OrderBy(_), OwnedTablePostprocessing::Slice(_)],
+        ) = (slice, self.postprocessing.as_slice())
+        else {
+            return self;
+        };
+        let Ok(skip) = usize::try_from(slice.offset_value) else {
+            return self;
+        };
+        // The indexes of the columns are the indexes of the fields of their values
+        let value_field_indexes = self
+            .proof_expr
+            .get_column_result_fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.component() == ColumnComponent::Values)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let Some(order_by) = order_by
+            .iter()
+            .map(|&(index, direction)| Some((*value_field_indexes.get(index)?, direction)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return self;
+        };
+        if !proves_result_without_it(&self.proof_expr)
+            || !SortExec::is_provable(&self.proof_expr, &order_by)
+        {
+            return self;
+        }
+        let fetch = (slice.number_rows != u64::MAX)
+            .then(|| usize::try_from(slice.number_rows).ok())
+            .flatten();
+        let sort = DynProofPlan::Sort(SortExec::new(Box::new(self.proof_expr), order_by));
+        Self {
+            proof_expr: DynProofPlan::Slice(SliceExec::new(Box::new(sort), skip, fetch)),
+            postprocessing: vec![],
+        }
+    }
+
+    /// Plans a query over a join of two tables.
+    ///
+    /// The join returns the referenced columns, so the result expressions
+    /// are evaluated in postprocessing.
+    fn try_new_join(
+        context: &QueryContext,
+        result_aliased_exprs: Vec<AliasedResultExpr>,
+        mut postprocessing: Vec<OwnedTablePostprocessing>,
+    ) -> ConversionResult<Self> {
+        let join = SortMergeJoinExecBuilder::new(context).build()?;
+        if context.has_agg() {
+            reject_group_by_key_exprs(context)?;
+            add_group_by_postprocessing(
+                &mut postprocessing,
+                context.get_group_by_exprs().to_vec(),
+                result_aliased_exprs,
+                context.get_having_expr().as_deref().cloned(),
+            )?;
+        } else {
+            postprocessing.insert(
+                0,
+                OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
+                    result_aliased_exprs,
+                )),
+            );
+        }
+        Ok(Self {
+            proof_expr: join,
+            postprocessing,
+        })
+    }
+
     /// Immutable access to this query's provable filter expression.
     #[must_use]
     pub fn proof_expr(&self) -> &DynProofPlan {
@@ -171,3 +769,558 @@ impl QueryExpr {
         &self.postprocessing
     }
 }
+
+/// The postprocessing steps that order and slice the result of a query
+fn order_by_and_slice_postprocessing(
+    order_by: &[(usize, bool)],
+    slice: Option<&Slice>,
+) -> Vec<OwnedTablePostprocessing> {
+    let mut postprocessing = vec![];
+    if !order_by.is_empty() {
+        postprocessing.push(OwnedTablePostprocessing::new_order_by(
+            OrderByPostprocessing::new(order_by.to_vec()),
+        ));
+    }
+    if let Some(slice) = slice {
+        postprocessing.push(OwnedTablePostprocessing::new_slice(
+            SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
+        ));
+    }
+    postprocessing
+}
+
+/// Proves the result expressions of an inner join, which are otherwise evaluated by `select` in postprocessing,
+/// as a [`ProjectionExec`] of the result of the join, whose columns are referred to as columns of `table_ref`.
+fn build_join_projection(
+    join: DynProofPlan,
+    select: &SelectPostprocessing,
+    table_ref: &TableRef,
+) -> ConversionResult<DynProofPlan> {
+    let column_mapping = join
+        .get_column_result_fields()
+        .into_iter()
+        .map(|field| {
+            (
+                field.name(),
+                ColumnRef::new(table_ref.clone(), field.name(), field.data_type()),
+            )
+        })
+        .collect::<IndexMap<_, _>>();
+    let builder = DynProofExprBuilder::new(&column_mapping);
+    let aliased_results = select
+        .aliased_result_exprs()
+        .iter()
+        .map(|aliased_expr| {
+            Ok(AliasedDynProofExpr {
+                expr: builder.build(&aliased_expr.expr)?,
+                alias: aliased_expr.alias.into(),
+            })
+        })
+        .collect::<ConversionResult<Vec<_>>>()?;
+    Ok(DynProofPlan::Projection(ProjectionExec::new(
+        aliased_results,
+        TableExpr::new_with_input(table_ref.clone(), join),
+    )))
+}
+
+/// The error for a query with window functions that qualifies a column by its table
+fn unqualified_window_columns_error() -> ConversionError {
+    ConversionError::UnsupportedOperation {
+        message: "queries with window functions can only refer to columns by their names"
+            .to_string(),
+    }
+}
+
+/// Resolves the `ORDER BY` of a query whose result has the fields `schema`
+/// into the indexes of the columns and whether they are ordered ascendingly
+///
+/// The presence fields of nullable columns are not columns of their own, so they are skipped.
+fn order_by_index_direction_pairs(
+    order_by: &[OrderBy],
+    schema: &[ColumnField],
+) -> ConversionResult<Vec<(usize, bool)>> {
+    order_by
+        .iter()
+        .map(|order_by| {
+            if let Some(table) = order_by.table {
+                return Err(ConversionError::InvalidOrderBy {
+                    alias: format!("{table}.{}", order_by.expr),
+                });
+            }
+            let ident = Ident::from(order_by.expr);
+            let index = schema
+                .iter()
+                .filter(|field| field.component() == ColumnComponent::Values)
+                .position(|field| field.name() == ident)
+                .ok_or(ConversionError::MissingColumnWithoutTable {
+                    identifier: Box::new(ident),
+                })?;
+            Ok((index, order_by.direction == OrderByDirection::Asc))
+        })
+        .collect()
+}
+
+/// Adds a `COUNT(*)` to a subquery whose only result column is an aggregation without `GROUP BY`
+/// other than a count, so that it is planned as a [`GroupByExec`], and returns whether it did.
+fn add_subquery_count(ast: &mut SelectStatement) -> bool {
+    let SetExpression::Query {
+        result_exprs,
+        group_by,
+        ..
+    } = &mut *ast.expr
+    else {
+        return false;
+    };
+    let [SelectResultExpr::AliasedResultExpr(aliased_expr)] = result_exprs.as_slice() else {
+        return false;
+    };
+    if !group_by.is_empty()
+        || !matches!(
+            *aliased_expr.expr,
+            Expression::Aggregation {
+                op: AggregationOperator::Sum | AggregationOperator::Max | AggregationOperator::Min,
+                ..
+            }
+        )
+    {
+        return false;
+    }
+    result_exprs.push(SelectResultExpr::AliasedResultExpr(
+        Expression::Aggregation {
+            op: AggregationOperator::Count,
+            expr: Box::new(Expression::Wildcard),
+            filter: None,
+        }
+        .alias("__count__"),
+    ));
+    true
+}
+
+/// Replaces the references to common table expressions by derived tables of their queries,
+/// so that each of them is planned as the input of a table named after the common table expression.
+///
+/// `scope` maps the names of the common table expressions in scope to their already inlined queries.
+/// The common table expressions of `ast` are added to it in order, so each of them may refer to the previous ones.
+/// Tables with a schema, e.g. `sxt.t`, always are real tables.
+fn inline_common_table_expressions(
+    ast: &SelectStatement,
+    scope: &IndexMap<Identifier, SelectStatement>,
+) -> ConversionResult<SelectStatement> {
+    let mut scope = scope.clone();
+    for cte in &ast.with {
+        let query = inline_common_table_expressions(&cte.query, &scope)?;
+        scope.insert(cte.alias, query);
+    }
+    Ok(SelectStatement {
+        with: vec![],
+        expr: Box::new(inline_set_expr_ctes(&ast.expr, &scope)?),
+        order_by: ast.order_by.clone(),
+        slice: ast.slice.clone(),
+    })
+}
+
+/// Replaces the references to common table expressions of a `SetExpression`
+fn inline_set_expr_ctes(
+    set_expr: &SetExpression,
+    scope: &IndexMap<Identifier, SelectStatement>,
+) -> ConversionResult<SetExpression> {
+    Ok(match set_expr {
+        SetExpression::Query {
+            result_exprs,
+            from,
+            where_expr,
+            group_by,
+            having,
+        } => SetExpression::Query {
+            result_exprs: result_exprs
+                .iter()
+                .map(|result_expr| {
+                    Ok(match result_expr {
+                        SelectResultExpr::ALL => SelectResultExpr::ALL,
+                        SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                            SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+                                inline_expr_ctes(&aliased_expr.expr, scope)?,
+                                aliased_expr.alias,
+                            ))
+                        }
+                    })
+                })
+                .collect::<ConversionResult<_>>()?,
+            from: from
+                .iter()
+                .map(|table_expr| inline_table_expr_ctes(table_expr, scope).map(Box::new))
+                .collect::<ConversionResult<_>>()?,
+            where_expr: where_expr
+                .as_deref()
+                .map(|expr| inline_expr_ctes(expr, scope).map(Box::new))
+                .transpose()?,
+            group_by: group_by
+                .iter()
+                .map(|expr| inline_expr_ctes(expr, scope))
+                .collect::<ConversionResult<_>>()?,
+            having: having
+                .as_deref()
+                .map(|expr| inline_expr_ctes(expr, scope).map(Box::new))
+                .transpose()?,
+        },
+        SetExpression::UnionAll { inputs } => SetExpression::UnionAll {
+            inputs: inputs
+                .iter()
+                .map(|input| inline_set_expr_ctes(input, scope))
+                .collect::<ConversionResult<_>>()?,
+        },
+        SetExpression::Distinct { input } => SetExpression::Distinct {
+            input: Box::new(inline_set_expr_ctes(input, scope)?),
+        },
+    })
+}
+
+/// Replaces the references to common table expressions of a `TableExpression`
+fn inline_table_expr_ctes(
+    table_expr: &TableExpression,
+    scope: &IndexMap<Identifier, SelectStatement>,
+) -> ConversionResult<TableExpression> {
+    Ok(match table_expr {
+        TableExpression::Named {
+            table,
+            schema: None,
+        } if scope.contains_key(table) => TableExpression::Subquery {
+            query: Box::new(scope[table].clone()),
+            alias: *table,
+        },
+        TableExpression::Named { .. } => table_expr.clone(),
+        TableExpression::Join {
+            left,
+            right,
+            join_type,
+            on,
+        } => TableExpression::Join {
+            left: Box::new(inline_table_expr_ctes(left, scope)?),
+            right: Box::new(inline_table_expr_ctes(right, scope)?),
+            join_type: *join_type,
+            on: on.clone(),
+        },
+        TableExpression::Subquery { query, alias } => TableExpression::Subquery {
+            query: Box::new(inline_common_table_expressions(query, scope)?),
+            alias: *alias,
+        },
+    })
+}
+
+/// Replaces the references to common table expressions of the subqueries of an `Expression`
+fn inline_expr_ctes(
+    expr: &Expression,
+    scope: &IndexMap<Identifier, SelectStatement>,
+) -> ConversionResult<Expression> {
+    try_rewrite_expression(expr, &mut |expr| {
+        Ok(match expr {
+            Expression::InSubquery { expr, subquery } => Some(Expression::InSubquery {
+                expr: Box::new(inline_expr_ctes(expr, scope)?),
+                subquery: Box::new(inline_common_table_expressions(subquery, scope)?),
+            }),
+            Expression::ScalarSubquery(query) => Some(Expression::ScalarSubquery(Box::new(
+                inline_common_table_expressions(query, scope)?,
+            ))),
+            _ => None,
+        })
+    })
+}
+
+/// Whether a plan references columns that can be NULL, i.e. that have a presence
+fn references_nullable_columns(plan: &DynProofPlan, schema_accessor: &dyn SchemaAccessor) -> bool {
+    plan.get_column_references().iter().any(|column_ref| {
+        column_ref.component() == ColumnComponent::Presence
+            || schema_accessor.is_nullable(column_ref.table_ref(), column_ref.column_id())
+    })
+}
+
+/// Whether a result expression uses a window function
+fn has_window_function(result_expr: &SelectResultExpr) -> bool {
+    let SelectResultExpr::AliasedResultExpr(aliased_expr) = result_expr else {
+        return false;
+    };
+    let mut has_window_function = false;
+    // The rewrite only visits the expression
+    let _ = try_rewrite_expression(&aliased_expr.expr, &mut |expr| {
+        has_window_function |= matches!(expr, Expression::Window { .. });
+        Ok(None)
+    });
+    has_window_function
+}
+
+/// The identifier of the `index`th internal column of the given kind, e.g. `__window_0__`,
+/// which can not clash with the columns of a table
+fn internal_identifier(kind: &str, index: usize) -> Identifier {
+    Identifier::try_new(format!("__{kind}_{index}__"))
+        .expect("internal column names are valid identifiers")
+}
+
+/// Whether a plan proves its result without being given it,
+/// so that it can be the input of another plan.
+fn proves_result_without_it(plan: &DynProofPlan) -> bool {
+    match plan {
+        DynProofPlan::GroupBy(group_by_exec) => group_by_exec.proves_distinct_groups(),
+        _ => true,
+    }
+}
+
+/// Prepends the postprocessing steps that evaluate an aggregation query
+/// on the columns returned by the proof plan.
+fn add_group_by_postprocessing(
+    postprocessing: &mut Vec<OwnedTablePostprocessing>,
+    group_by: Vec<Ident>,
+    result_aliased_exprs: Vec<AliasedResultExpr>,
+    having_expr: Option<Expression>,
+) -> ConversionResult<()> {
+    let group_by_postprocessing =
+        GroupByPostprocessing::try_new_with_having(group_by, result_aliased_exprs, having_expr)?;
+    postprocessing.insert(
+        0,
+        OwnedTablePostprocessing::new_group_by(group_by_postprocessing.clone()),
+    );
+    let remainder_exprs = group_by_postprocessing.remainder_exprs();
+    // Check whether we need to do select postprocessing.
+    // That is, if any of them is not simply a column reference.
+    if remainder_exprs
+        .iter()
+        .any(|expr| expr.try_as_identifier().is_none())
+    {
+        postprocessing.insert(
+            1,
+            OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
+                remainder_exprs.to_vec(),
+            )),
+        );
+    }
+    Ok(())
+}
+
+/// Plans the `HAVING` clause of a provable aggregation query as a filter on the groups of `group_by_exec`.
+///
+/// The groups are referred to as the queried table. Their aggregations are looked up
+/// by the aliases of the columns that hold them, and the columns that are only needed
+/// by the `HAVING` clause are not returned by the filter.
+///
+/// # Panics
+/// Panics if the aggregations of the `HAVING` clause can not be proven, which `GroupByExec` planning rules out.
+fn build_having_filter(
+    context: &QueryContext,
+    group_by_exec: GroupByExec,
+) -> ConversionResult<FilterExec> {
+    let table_ref = context.get_table_ref().clone();
+    let (aggregation_aliases, additional_aggregations) = context
+        .get_having_aggregations()
+        .expect("the aggregations of the HAVING clause of a provable query are provable");
+    let fields = group_by_exec.get_column_result_fields();
+    let mut group_column_mapping = fields
+        .iter()
+        .map(|field| {
+            (
+                field.name(),
+                ColumnRef::new(table_ref.clone(), field.name(), field.data_type()),
+            )
+        })
+        .collect::<IndexMap<_, _>>();
+    // The group by columns of the groups are named after the columns of the table
+    let column_mapping = context.get_column_mapping();
+    for ident in context.get_group_by_exprs() {
+        if let Some(column_ref) = column_mapping
+            .get(ident)
+            .and_then(|column_ref| group_column_mapping.get(&column_ref.column_id()))
+        {
+            group_column_mapping.insert(ident.clone(), column_ref.clone());
+        }
+    }
+    // The group by expressions that are not columns are named after their result columns
+    let key_aliases = context
+        .get_group_by_key_exprs()
+        .iter()
+        .map(|(alias, expr)| {
+            Identifier::try_from(alias.clone())
+                .map(|alias| (expr, alias))
+                .map_err(|e| ConversionError::IdentifierConversionError {
+                    error: format!("Failed to convert Ident to Identifier: {e}"),
+                })
+        })
+        .collect::<ConversionResult<IndexMap<_, _>>>()?;
+    let having_expr = context
+        .get_having_expr()
+        .as_deref()
+        .map(|having_expr| {
+            try_rewrite_expression(having_expr, &mut |expr| {
+                Ok(aggregation_aliases
+                    .get(expr)
+                    .or_else(|| key_aliases.get(expr))
+                    .map(|alias| Expression::Column(*alias)))
+            })
+            .map(Box::new)
+        })
+        .transpose()?;
+    let additional_aliases = additional_aggregations
+        .iter()
+        .map(|aggregation| Ident::from(aggregation.alias))
+        .collect::<IndexSet<_>>();
+    let result_exprs = fields
+        .iter()
+        .filter(|field| !additional_aliases.contains(&field.name()))
+        .map(|field| AliasedDynProofExpr {
+            alias: field.name(),
+            expr: DynProofExpr::new_column(group_column_mapping[&field.name()].clone()),
+        })
+        .collect();
+    let where_expr = WhereExprBuilder::new(&group_column_mapping)
+        .build(having_expr)?
+        .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+    Ok(FilterExec::new(
+        result_exprs,
+        TableExpr::new_with_input(table_ref, DynProofPlan::GroupBy(group_by_exec)),
+        where_expr,
+    ))
+}
+
+/// Errors if the query groups by an expression that is not a column,
+/// as such groups can only be formed by a [`GroupByExec`].
+fn reject_group_by_key_exprs(context: &QueryContext) -> ConversionResult<()> {
+    if context.get_group_by_key_exprs().is_empty() {
+        Ok(())
+    } else {
+        Err(ConversionError::Unprovable {
+            error: "grouping by an expression that is not a column is only supported \
+                when the aggregation is provable"
+                .to_string(),
+        })
+    }
+}
+
+/// Plans an aggregation query whose last result column is a `COUNT(DISTINCT <column>)`, i.e. of the form
+/// ```ignore
+///     SELECT <group by columns>, COUNT(DISTINCT <column>) FROM <table> WHERE <where clause> GROUP BY <group by columns>
+/// ```
+/// as a [`GroupByExec`] that counts the rows of a [`DistinctExec`] of the group by columns and the counted column.
+///
+/// Returns `None` if the last result column is not a `COUNT(DISTINCT)`,
+/// and errors if the query is not of the form above or its distinct rows can not be proven.
+fn build_count_distinct(context: &QueryContext) -> ConversionResult<Option<GroupByExec>> {
+    let result_aliased_exprs = context.get_aliased_result_exprs()?;
+    let Some((count_expr, group_by_result_exprs)) = result_aliased_exprs.split_last() else {
+        return Ok(None);
+    };
+    let Expression::Aggregation {
+        op: AggregationOperator::CountDistinct,
+        expr,
+        filter: None,
+    } = &*count_expr.expr
+    else {
+        return Ok(None);
+    };
+    let unprovable = || ConversionError::Unprovable {
+        error: "COUNT(DISTINCT) is only supported on a column that is not grouped by, \
+            as the only aggregation of a query without a HAVING clause"
+            .to_string(),
+    };
+    let group_by = context.get_group_by_exprs();
+    let column_mapping = context.get_column_mapping();
+    let (Expression::Column(counted_column), None, true) = (
+        &**expr,
+        context.get_having_expr(),
+        group_by_result_exprs.len() == group_by.len(),
+    ) else {
+        return Err(unprovable());
+    };
+    let counted_column = Ident::from(*counted_column);
+    if group_by.contains(&counted_column)
+        || !group_by
+            .iter()
+            .zip(group_by_result_exprs)
+            .all(|(ident, res)| {
+                if let Expression::Column(res_ident) = *res.expr {
+                    Ident::from(res_ident) == *ident
+                } else {
+                    false
+                }
+            })
+    {
+        return Err(unprovable());
+    }
+    // NULLs are zero, so they would be counted as a distinct value
+    let distinct_column_refs = group_by
+        .iter()
+        .chain([&counted_column])
+        .map(|ident| match column_mapping.get(ident) {
+            Some(column_ref) if !context.get_nullable_columns().contains(column_ref) => {
+                Ok(column_ref.clone())
+            }
+            _ => Err(unprovable()),
+        })
+        .collect::<ConversionResult<Vec<_>>>()?;
+    let table_ref = context.get_table_ref().clone();
+    let where_expr = WhereExprBuilder::new(&column_mapping)
+        .with_nullable_columns(context.get_nullable_columns())
+        .with_character_counts(context.get_character_counts())
+        .with_subquery_plans(context.get_subquery_plans())
+        .build(context.get_where_expr().clone())?
+        .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+    let distinct_input = DynProofPlan::Filter(FilterExec::new(
+        distinct_column_refs
+            .iter()
+            .map(|column_ref| AliasedDynProofExpr {
+                alias: column_ref.column_id(),
+                expr: DynProofExpr::new_column(column_ref.clone()),
+            })
+            .collect(),
+        context.get_table_expr(),
+        where_expr,
+    ));
+    if !DistinctExec::is_provable(&distinct_input) {
+        return Err(unprovable());
+    }
+    // The distinct rows are referred to as the queried table
+    let group_by_exprs = distinct_column_refs[..group_by.len()]
+        .iter()
+        .map(|column_ref| AliasedDynProofExpr {
+            alias: column_ref.column_id(),
+            expr: DynProofExpr::new_column(column_ref.clone()),
+        })
+        .collect();
+    Ok(Some(GroupByExec::new(
+        group_by_exprs,
+        vec![],
+        count_expr.alias.into(),
+        TableExpr::new_with_input(
+            table_ref,
+            DynProofPlan::Distinct(DistinctExec::new(Box::new(distinct_input))),
+        ),
+        DynProofExpr::new_literal(LiteralValue::Boolean(true)),
+    )))
+}
+
+/// Prepends the postprocessing step that derives the averages of a provable aggregation query
+/// from the sums and the count returned by the proof plan, if the query has any averages.
+fn add_average_postprocessing(
+    postprocessing: &mut Vec<OwnedTablePostprocessing>,
+    result_aliased_exprs: &[AliasedResultExpr],
+) {
+    let avg_identifiers = result_aliased_exprs
+        .iter()
+        .filter(|aliased_expr| {
+            matches!(
+                *aliased_expr.expr,
+                Expression::Aggregation {
+                    op: AggregationOperator::Avg,
+                    ..
+                }
+            )
+        })
+        .map(|aliased_expr| aliased_expr.alias.into())
+        .collect::<Vec<Ident>>();
+    // The count is always the last result column of a provable aggregation query
+    if let (false, Some(count_expr)) = (avg_identifiers.is_empty(), result_aliased_exprs.last()) {
+        postprocessing.insert(
+            0,
+            OwnedTablePostprocessing::new_average(AveragePostprocessing::new(
+                avg_identifiers,
+                count_expr.alias.into(),
+            )),
+        );
+    }
+}
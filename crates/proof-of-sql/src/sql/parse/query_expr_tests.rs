@@ -7,16 +7,21 @@ use crate::{
     sql::{
         parse::QueryExpr,
         postprocessing::{test_utility::*, PostprocessingError},
-        proof_exprs::test_utility::*,
-        proof_plans::{test_utility::*, DynProofPlan},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan, WindowFunction},
     },
 };
 use itertools::Itertools;
 use proof_of_sql_parser::{
+    posql_time::{
+        PoSQLDateTimeField::{Day, Hour, Month, Year},
+        PoSQLTimeUnit, PoSQLTimeZone,
+    },
     sql::SelectStatementParser,
     utility::{
-        add as padd, aliased_expr, col, count, count_all, lit, max, min, mul as pmul, sub as psub,
-        sum,
+        add as padd, add_interval as padd_interval, aliased_expr, avg, col, cols_res, count,
+        count_all, date_trunc as pdate_trunc, gt as pgt, inner_join, lit, max, min, mul as pmul,
+        query_all, select, sub as psub, sum, tab as ptab,
     },
 };
 use sqlparser::ast::Ident;
@@ -539,44 +544,89 @@ fn we_can_convert_an_ast_using_an_aliased_column() {
 }
 
 #[test]
-fn we_cannot_convert_an_ast_with_a_nonexistent_column() {
+fn we_can_convert_an_ast_with_an_is_null_predicate_on_a_nullable_column() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "a".into() => ColumnType::BigInt,
             "b".into() => ColumnType::BigInt,
         },
+    )
+    .with_nullable_column(t.clone(), "a".into());
+    let ast = query_to_provable_ast(
+        &t,
+        "select b from sxt_tab where a is null or b >= +4",
+        &accessor,
     );
-    invalid_query_to_provable_ast(&t, "select * from sxt_tab where a = 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["b"], &accessor),
+            tab(&t),
+            or(
+                is_null(nullable_column(&t, "a", &accessor)),
+                gte(column(&t, "b", &accessor), const_bigint(4)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_convert_an_ast_with_a_column_type_different_than_equal_literal() {
+fn we_can_convert_an_ast_with_a_comparison_on_a_nullable_column() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "b".into() => ColumnType::VarChar,
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
         },
+    )
+    .with_nullable_column(t.clone(), "a".into());
+    let ast = query_to_provable_ast(&t, "select b from sxt_tab where a = 4", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["b"], &accessor),
+            tab(&t),
+            nullable_expr(
+                equal(column(&t, "a", &accessor), const_bigint(4)),
+                presence(&t, "a", &accessor),
+            ),
+        ),
+        vec![],
     );
-    invalid_query_to_provable_ast(&t, "select * from sxt_tab where b = 123", &accessor);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_convert_an_ast_with_a_schema() {
-    let t = TableRef::new("eth", "sxt_tab");
+fn we_can_convert_an_ast_that_selects_a_nullable_column() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
         },
-    );
-    let ast = query_to_provable_ast(&t, "select a from eth.sxt_tab where a = 3", &accessor);
+    )
+    .with_nullable_column(t.clone(), "a".into());
+    let ast = query_to_provable_ast(&t, "select a, b from sxt_tab where a > 1", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["a"], &accessor),
+            vec![
+                aliased_plan(nullable_column(&t, "a", &accessor), "a"),
+                col_expr_plan(&t, "b", &accessor),
+            ],
             tab(&t),
-            equal(column(&t, "a", &accessor), const_bigint(3)),
+            nullable_expr(
+                DynProofExpr::try_new_inequality(
+                    column(&t, "a", &accessor),
+                    const_bigint(1),
+                    false,
+                )
+                .unwrap(),
+                presence(&t, "a", &accessor),
+            ),
         ),
         vec![],
     );
@@ -584,643 +634,807 @@ fn we_can_convert_an_ast_with_a_schema() {
 }
 
 #[test]
-fn we_can_convert_an_ast_without_any_filter() {
-    let t = TableRef::new("eth", "sxt_tab");
+fn we_can_convert_an_ast_with_in_list_and_between_predicates() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+            "c".into() => ColumnType::BigInt,
         },
     );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a from sxt_tab where b in ('x', 'y') and a not between 1 and c",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            vec![
-                col_expr_plan(&t, "a", &accessor),
-                aliased_plan(const_bigint(3), "b"),
-            ],
+            cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
-            const_bool(true),
+            and(
+                in_list(
+                    column(&t, "b", &accessor),
+                    vec![const_varchar("x"), const_varchar("y")],
+                ),
+                not(and(
+                    gte(column(&t, "a", &accessor), const_bigint(1)),
+                    lte(column(&t, "a", &accessor), column(&t, "c", &accessor)),
+                )),
+            ),
         ),
         vec![],
     );
-    let queries = [
-        "select *, 3 as b from eth.sxt_tab",
-        "select a, 3 as b from eth.sxt_tab",
-    ];
-    for query in queries {
-        let ast = query_to_provable_ast(&t, query, &accessor);
-        assert_eq!(ast, expected_ast);
-    }
+    assert_eq!(ast, expected_ast);
+
+    let ast = query_to_provable_ast(
+        &t,
+        "select a from sxt_tab where a between -1 and 10",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            between(
+                column(&t, "a", &accessor),
+                const_bigint(-1),
+                const_bigint(10),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
-/////////////////////////
-/// `OrderBy`
-/////////////////////////
 #[test]
-fn we_can_parse_order_by_with_a_single_column() {
+fn we_cannot_convert_an_ast_with_an_in_list_of_incomparable_types() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(&t, "select a from sxt_tab where a in (1, 'x')", &accessor);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_case_expressions() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "b".into() => ColumnType::BigInt,
             "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select * from sxt_tab where a = 3 order by b",
+        "select case when b = 'x' then a when b = 'y' then 1 else a * 2 end as c from sxt_tab where iif(a <= 2, true, false)",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["b", "a"], &accessor),
+            vec![aliased_plan(
+                case_when(
+                    equal(column(&t, "b", &accessor), const_varchar("x")),
+                    column(&t, "a", &accessor),
+                    case_when(
+                        equal(column(&t, "b", &accessor), const_varchar("y")),
+                        const_bigint(1),
+                        multiply(column(&t, "a", &accessor), const_bigint(2)),
+                    ),
+                ),
+                "c",
+            )],
             tab(&t),
-            equal(column(&t, "a", &accessor), const_bigint(3)),
+            case_when(
+                lte(column(&t, "a", &accessor), const_bigint(2)),
+                const_bool(true),
+                const_bool(false),
+            ),
         ),
-        vec![orders(&[0_usize], &[true])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_order_by_with_multiple_columns() {
+fn we_can_do_provable_group_by_with_a_sum_of_a_case_expression() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "b".into() => ColumnType::BigInt,
             "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+            "d".into() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select a, b from sxt_tab where a = b + 3 order by b desc, a asc",
+        "select d, sum(case when b = 'x' then a else 0 end) as s, count(*) as n from sxt_tab group by d",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&t, &["d"], &accessor),
+            vec![sum_expr(
+                case_when(
+                    equal(column(&t, "b", &accessor), const_varchar("x")),
+                    column(&t, "a", &accessor),
+                    const_bigint(0),
+                ),
+                "s",
+            )],
+            "n",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_nullif_on_a_nullable_column() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+        },
+    )
+    .with_nullable_column(t.clone(), "a".into());
+    let ast = query_to_provable_ast(
+        &t,
+        "select b from sxt_tab where nullif(a, 0) = 4",
         &accessor,
     );
+    // `a` is selected where `a = 0` is false or NULL
+    let a_is_zero = nullable_expr(
+        equal(column(&t, "a", &accessor), const_bigint(0)),
+        presence(&t, "a", &accessor),
+    );
+    let condition = or(not(a_is_zero.clone()), is_null(a_is_zero));
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["a", "b"], &accessor),
+            cols_expr_plan(&t, &["b"], &accessor),
             tab(&t),
-            equal(
-                column(&t, "a", &accessor),
-                add(column(&t, "b", &accessor), const_bigint(3)),
+            nullable_expr(
+                equal(
+                    case_when(
+                        condition.clone(),
+                        column(&t, "a", &accessor),
+                        const_bigint(0),
+                    ),
+                    const_bigint(4),
+                ),
+                case_when(condition, presence(&t, "a", &accessor), const_bool(false)),
             ),
         ),
-        vec![orders(&[1_usize, 0], &[false, true])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_order_by_referencing_an_alias_associated_with_column_b_but_with_name_equals_column_a_also_renamed(
-) {
+fn we_can_convert_an_ast_with_like_predicates() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "a".into() => ColumnType::VarChar,
+            "b".into() => ColumnType::BigInt,
         },
-    );
+    )
+    .with_character_count(t.clone(), "a".into(), 2);
     let ast = query_to_provable_ast(
         &t,
-        "select salary as s, name as salary from sxt_tab where salary = 5 order by salary desc",
+        "select b, a like '_b' as is_xb from sxt_tab where a like 'a%' and a not like '%.z'",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
             vec![
-                aliased_col_expr_plan(&t, "salary", "s", &accessor),
-                aliased_col_expr_plan(&t, "name", "salary", &accessor),
+                col_expr_plan(&t, "b", &accessor),
+                aliased_plan(like(&t, "a", "_b", &accessor), "is_xb"),
             ],
             tab(&t),
-            equal(column(&t, "salary", &accessor), const_bigint(5)),
+            and(
+                like(&t, "a", "a%", &accessor),
+                not(like(&t, "a", "%.z", &accessor)),
+            ),
         ),
-        vec![orders(&[1_usize], &[false])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_parse_order_by_referencing_a_column_name_instead_of_an_alias() {
+fn we_can_convert_an_ast_with_a_like_predicate_on_a_nullable_column() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::VarChar,
+            "b".into() => ColumnType::BigInt,
         },
+    )
+    .with_nullable_column(t.clone(), "a".into())
+    .with_character_count(t.clone(), "a".into(), 1);
+    let ast = query_to_provable_ast(&t, "select b from sxt_tab where not a like 'x%'", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["b"], &accessor),
+            tab(&t),
+            not(nullable_expr(
+                like(&t, "a", "x%", &accessor),
+                presence(&t, "a", &accessor),
+            )),
+        ),
+        vec![],
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select salary as s from sxt_tab order by salary",
-        &accessor,
-    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_parse_order_by_referencing_invalid_aliased_expressions() {
+fn we_cannot_convert_an_ast_with_unprovable_like_predicates() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "a".into() => ColumnType::VarChar,
             "b".into() => ColumnType::BigInt,
-            "a".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
         },
-    );
-    // Note: While this operation is acceptable with PostgreSQL, we do not currently support it.
-    invalid_query_to_provable_ast(&t, "select a from sxt_tab order by b desc", &accessor);
-    invalid_query_to_provable_ast(&t, "select a as b from sxt_tab order by a desc", &accessor);
-    invalid_query_to_provable_ast(&t, "select sum(a) from sxt_tab order by a desc", &accessor);
-    invalid_query_to_provable_ast(&t, "select 2 * a from sxt_tab order by a desc", &accessor);
+    )
+    .with_character_count(t.clone(), "a".into(), 1);
+    // Characters between the first and last wildcard
+    invalid_query_to_provable_ast(&t, "select b from sxt_tab where a like '%x%'", &accessor);
+    // More characters than are committed to
+    invalid_query_to_provable_ast(&t, "select b from sxt_tab where a like 'xy%'", &accessor);
+    // No committed characters
+    invalid_query_to_provable_ast(&t, "select b from sxt_tab where c like 'x%'", &accessor);
+    // Not a string
+    invalid_query_to_provable_ast(&t, "select b from sxt_tab where b like 'x%'", &accessor);
 }
 
 #[test]
-fn we_cannot_parse_order_by_referencing_an_alias_name_associated_with_two_different_columns() {
+fn we_can_use_columns_named_like_character_components_as_ordinary_columns() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "a".into() => ColumnType::VarChar,
+            "a__length".into() => ColumnType::VarChar,
+            "a__char_0".into() => ColumnType::BigInt,
         },
-    );
-    invalid_query_to_provable_ast(
+    )
+    .with_character_count(t.clone(), "a".into(), 1);
+    let ast = query_to_provable_ast(
         &t,
-        "select salary as s, name as s from sxt_tab order by s desc",
+        "select a__length, a__char_0 as b__rchar_0 from sxt_tab where a like 'x%' and a__length like '%'",
         &accessor,
     );
-    invalid_query_to_provable_ast(
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                col_expr_plan(&t, "a__length", &accessor),
+                aliased_plan(column(&t, "a__char_0", &accessor), "b__rchar_0"),
+            ],
+            tab(&t),
+            and(
+                like(&t, "a", "x%", &accessor),
+                like(&t, "a__length", "%", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_invalid_case_expressions() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
-        "select salary as name, name from sxt_tab order by name desc",
-        &accessor,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+        },
     );
-    // Note: While this is not ambiguous with PostgreSQL,
-    // it currently is with our code because there is
-    // no way to differentiate between the two columns
-    // in the record batch since they share the same name.
     invalid_query_to_provable_ast(
         &t,
-        "select salary as name, name from sxt_tab order by salary desc",
+        "select a from sxt_tab where case when a then true else false end",
         &accessor,
     );
-    // Note: This is not ambiguous with PostgreSQL either,
-    // but it is with our code for the reasons mentioned above.
     invalid_query_to_provable_ast(
         &t,
-        "select salary as s, name as s from sxt_tab order by salary desc",
+        "select case when a = 1 then a else b end as c from sxt_tab",
         &accessor,
     );
 }
 
 #[test]
-fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_once_and_with_different_alias_name(
-) {
+fn we_cannot_convert_an_ast_with_a_nonexistent_column() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "b".into() => ColumnType::BigInt,
         },
     );
-    for (index, order_by) in [(0_usize, "s"), (2_usize, "d")] {
-        let ast = query_to_provable_ast(
-            &t,
-            &("select salary as s, name, salary as d from sxt_tab order by ".to_owned() + order_by),
-            &accessor,
-        );
-        let expected_ast = QueryExpr::new(
-            filter(
-                vec![
-                    aliased_col_expr_plan(&t, "salary", "s", &accessor),
-                    col_expr_plan(&t, "name", &accessor),
-                    aliased_col_expr_plan(&t, "salary", "d", &accessor),
-                ],
-                tab(&t),
-                const_bool(true),
-            ),
-            vec![orders(&[index], &[true])],
-        );
-        assert_eq!(ast, expected_ast);
-    }
+    invalid_query_to_provable_ast(&t, "select * from sxt_tab where a = 3", &accessor);
 }
 
-/////////////////////////
-// Slice
-/////////////////////////
-
 #[test]
-fn we_can_parse_a_query_having_a_simple_limit_clause() {
+fn we_cannot_convert_an_ast_with_a_column_type_different_than_equal_literal() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
         },
     );
-    let ast = query_to_provable_ast(&t, "select a from sxt_tab limit 3", &accessor);
-    let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["a"], &accessor),
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![slice(Some(3), Some(0))],
-    );
-    assert_eq!(ast, expected_ast);
+    invalid_query_to_provable_ast(&t, "select * from sxt_tab where b = 123", &accessor);
 }
 
 #[test]
-fn slice_is_still_applied_when_limit_is_u64_max_and_offset_is_zero() {
-    let t = TableRef::new("sxt", "sxt_tab");
+fn we_can_convert_an_ast_with_a_schema() {
+    let t = TableRef::new("eth", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset 0", &accessor);
+    let ast = query_to_provable_ast(&t, "select a from eth.sxt_tab where a = 3", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
             cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
-            const_bool(true),
+            equal(column(&t, "a", &accessor), const_bigint(3)),
         ),
-        vec![slice(Some(u64::MAX), Some(0))],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_having_a_simple_positive_offset_clause() {
-    let t = TableRef::new("sxt", "sxt_tab");
+fn we_can_convert_an_ast_without_any_filter() {
+    let t = TableRef::new("eth", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset 7", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["a"], &accessor),
+            vec![
+                col_expr_plan(&t, "a", &accessor),
+                aliased_plan(const_bigint(3), "b"),
+            ],
             tab(&t),
             const_bool(true),
         ),
-        vec![slice(Some(u64::MAX), Some(7))],
+        vec![],
     );
-    assert_eq!(ast, expected_ast);
+    let queries = [
+        "select *, 3 as b from eth.sxt_tab",
+        "select a, 3 as b from eth.sxt_tab",
+    ];
+    for query in queries {
+        let ast = query_to_provable_ast(&t, query, &accessor);
+        assert_eq!(ast, expected_ast);
+    }
 }
 
+/////////////////////////
+/// `OrderBy`
+/////////////////////////
 #[test]
-fn we_can_parse_a_query_having_a_negative_offset_clause() {
+fn we_can_parse_order_by_with_a_single_column() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "b".into() => ColumnType::BigInt,
             "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset -7", &accessor);
+    let ast = query_to_provable_ast(
+        &t,
+        "select * from sxt_tab where a = 3 order by b",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["a"], &accessor),
+            cols_expr_plan(&t, &["b", "a"], &accessor),
             tab(&t),
-            const_bool(true),
+            equal(column(&t, "a", &accessor), const_bigint(3)),
         ),
-        vec![slice(Some(u64::MAX), Some(-7))],
+        vec![orders(&[0_usize], &[true])],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause() {
+fn we_can_parse_order_by_with_multiple_columns() {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "b".into() => ColumnType::BigInt,
             "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(&t, "select a from sxt_tab limit 55 offset 3", &accessor);
+    let ast = query_to_provable_ast(
+        &t,
+        "select a, b from sxt_tab where a = b + 3 order by b desc, a asc",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["a"], &accessor),
+            cols_expr_plan(&t, &["a", "b"], &accessor),
             tab(&t),
-            const_bool(true),
+            equal(
+                column(&t, "a", &accessor),
+                add(column(&t, "b", &accessor), const_bigint(3)),
+            ),
         ),
-        vec![slice(Some(55), Some(3))],
+        vec![orders(&[1_usize, 0], &[false, true])],
     );
     assert_eq!(ast, expected_ast);
 }
 
-///////////////////////////
-// Composition Expressions
-///////////////////////////
 #[test]
-fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_where_expr_and_order_by(
+fn we_can_parse_order_by_referencing_an_alias_associated_with_column_b_but_with_name_equals_column_a_also_renamed(
 ) {
     let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "a".into() => ColumnType::BigInt,
-            "boolean".into() => ColumnType::Boolean,
+            "salary".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select a, boolean and a >= 4 as res from sxt_tab where a = -3 order by a desc limit 55 offset 3",
+        "select salary as s, name as salary from sxt_tab where salary = 5 order by salary desc",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
             vec![
-                col_expr_plan(&t, "a", &accessor),
-                aliased_plan(
-                    and(
-                        column(&t, "boolean", &accessor),
-                        gte(column(&t, "a", &accessor), const_bigint(4)),
-                    ),
-                    "res",
-                ),
+                aliased_col_expr_plan(&t, "salary", "s", &accessor),
+                aliased_col_expr_plan(&t, "name", "salary", &accessor),
             ],
             tab(&t),
-            equal(column(&t, "a", &accessor), const_bigint(-3)),
+            equal(column(&t, "salary", &accessor), const_bigint(5)),
         ),
-        vec![orders(&[0_usize], &[false]), slice(Some(55), Some(3))],
+        vec![orders(&[1_usize], &[false])],
     );
     assert_eq!(ast, expected_ast);
 }
 
-///////////////////////////
-// Group By Expressions - Prover
-///////////////////////////
 #[test]
-fn we_can_do_provable_group_by() {
-    let t = TableRef::new("sxt", "employees");
+fn we_cannot_parse_order_by_referencing_a_column_name_instead_of_an_alias() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(
+    invalid_query_to_provable_ast(
         &t,
-        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department",
+        "select salary as s from sxt_tab order by salary",
         &accessor,
     );
-    let expected_ast = QueryExpr::new(
-        group_by(
-            cols_expr(&t, &["department"], &accessor),
-            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
-            "num_employee",
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![],
+}
+
+#[test]
+fn we_cannot_parse_order_by_referencing_invalid_aliased_expressions() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "b".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
+        },
     );
-    assert_eq!(ast, expected_ast);
+    // Note: While this operation is acceptable with PostgreSQL, we do not currently support it.
+    invalid_query_to_provable_ast(&t, "select a from sxt_tab order by b desc", &accessor);
+    invalid_query_to_provable_ast(&t, "select a as b from sxt_tab order by a desc", &accessor);
+    invalid_query_to_provable_ast(&t, "select sum(a) from sxt_tab order by a desc", &accessor);
+    invalid_query_to_provable_ast(&t, "select 2 * a from sxt_tab order by a desc", &accessor);
 }
 
 #[test]
-fn we_can_do_provable_group_by_without_sum() {
-    let t = TableRef::new("sxt", "employees");
+fn we_cannot_parse_order_by_referencing_an_alias_name_associated_with_two_different_columns() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
         },
     );
-    let ast = query_to_provable_ast(
+    invalid_query_to_provable_ast(
         &t,
-        "select department, count(*) as num_employee from employees group by department",
+        "select salary as s, name as s from sxt_tab order by s desc",
         &accessor,
     );
-    let expected_ast = QueryExpr::new(
-        group_by(
-            cols_expr(&t, &["department"], &accessor),
-            vec![],
-            "num_employee",
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![],
+    invalid_query_to_provable_ast(
+        &t,
+        "select salary as name, name from sxt_tab order by name desc",
+        &accessor,
+    );
+    // Note: While this is not ambiguous with PostgreSQL,
+    // it currently is with our code because there is
+    // no way to differentiate between the two columns
+    // in the record batch since they share the same name.
+    invalid_query_to_provable_ast(
+        &t,
+        "select salary as name, name from sxt_tab order by salary desc",
+        &accessor,
+    );
+    // Note: This is not ambiguous with PostgreSQL either,
+    // but it is with our code for the reasons mentioned above.
+    invalid_query_to_provable_ast(
+        &t,
+        "select salary as s, name as s from sxt_tab order by salary desc",
+        &accessor,
     );
-    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_do_provable_group_by_with_two_group_by_columns() {
-    let t = TableRef::new("sxt", "employees");
+fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_once_and_with_different_alias_name(
+) {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "state".into() => ColumnType::VarChar,
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
         },
     );
-    let ast = query_to_provable_ast(
+    for (index, order_by) in [(0_usize, "s"), (2_usize, "d")] {
+        let ast = query_to_provable_ast(
+            &t,
+            &("select salary as s, name, salary as d from sxt_tab order by ".to_owned() + order_by),
+            &accessor,
+        );
+        let expected_ast = QueryExpr::new(
+            filter(
+                vec![
+                    aliased_col_expr_plan(&t, "salary", "s", &accessor),
+                    col_expr_plan(&t, "name", &accessor),
+                    aliased_col_expr_plan(&t, "salary", "d", &accessor),
+                ],
+                tab(&t),
+                const_bool(true),
+            ),
+            vec![orders(&[index], &[true])],
+        );
+        assert_eq!(ast, expected_ast);
+    }
+}
+
+/////////////////////////
+// Slice
+/////////////////////////
+
+#[test]
+fn we_can_parse_a_query_having_a_simple_limit_clause() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
-        "select state, department, sum(salary) as total_salary, count(*) as num_employee from employees group by state, department",
-        &accessor,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+        },
     );
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab limit 3", &accessor);
     let expected_ast = QueryExpr::new(
-        group_by(
-            cols_expr(&t, &["state", "department"], &accessor),
-            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
-            "num_employee",
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
             const_bool(true),
         ),
-        vec![],
+        vec![slice(Some(3), Some(0))],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_do_provable_group_by_with_two_sums_and_filter() {
-    let t = TableRef::new("sxt", "employees");
+fn slice_is_still_applied_when_limit_is_u64_max_and_offset_is_zero() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "tax".into() => ColumnType::BigInt,
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(
-        &t,
-        "select department, sum(salary) as total_salary, sum(tax) as total_tax, count(*) as num_employee from employees where tax <= 1 group by department",
-        &accessor,
-    );
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset 0", &accessor);
     let expected_ast = QueryExpr::new(
-        group_by(
-            cols_expr(&t, &["department"], &accessor),
-            vec![
-                sum_expr(column(&t, "salary", &accessor), "total_salary"),
-                sum_expr(column(&t, "tax", &accessor), "total_tax"),
-            ],
-            "num_employee",
-            tab(&t),
-            lte(column(&t, "tax", &accessor), const_bigint(1)),
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            const_bool(true),
         ),
-        vec![],
+        vec![slice(Some(u64::MAX), Some(0))],
     );
     assert_eq!(ast, expected_ast);
 }
 
-///////////////////////////
-// Group By Expressions - Postprocessing
-///////////////////////////
 #[test]
-fn we_can_group_by_without_using_aggregate_functions() {
-    let t = TableRef::new("sxt", "employees");
+fn we_can_parse_a_query_having_a_simple_positive_offset_clause() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
         },
     );
-    let ast = query_to_provable_ast(
-        &t,
-        "select department, true as is_remote from employees group by department",
-        &accessor,
-    );
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset 7", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
-            vec![col_expr_plan(&t, "department", &accessor)],
+            cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
             const_bool(true),
         ),
-        vec![
-            group_by_postprocessing(
-                &["department"],
-                &[
-                    aliased_expr(col("department"), "department"),
-                    aliased_expr(lit(true), "is_remote"),
-                ],
-            ),
-            select_expr(&[
-                aliased_expr(col("department"), "department"),
-                aliased_expr(lit(true), "is_remote"),
-            ]),
-        ],
+        vec![slice(Some(u64::MAX), Some(7))],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
-    let query_text =
-        "select max(salary) max_sal, department_budget d, count(department_budget) from sxt.employees group by department_budget, tax order by max_sal";
-
-    let t = TableRef::new("sxt", "employees");
+fn we_can_parse_a_query_having_a_negative_offset_clause() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "department_budget".into() => ColumnType::BigInt,
-            "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
         },
     );
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab offset -7", &accessor);
+    let expected_ast = QueryExpr::new(
         filter(
-            vec![
-                col_expr_plan(&t, "department_budget", &accessor),
-                col_expr_plan(&t, "salary", &accessor),
-                col_expr_plan(&t, "tax", &accessor),
-            ],
+            cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
             const_bool(true),
         ),
-        vec![
-            group_by_postprocessing(
-                &["department_budget", "tax"],
-                &[
-                    aliased_expr(max(col("salary")), "max_sal"),
-                    aliased_expr(col("department_budget"), "d"),
-                    aliased_expr(count(col("department_budget")), "__count__"),
-                ],
-            ),
-            orders(&[0_usize], &[true]),
-        ],
+        vec![slice(Some(u64::MAX), Some(-7))],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
-    let t = TableRef::new("sxt", "employees");
+fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select department, salary from sxt.employees group by department",
-        &accessor,
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab limit 55 offset 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![slice(Some(55), Some(3))],
     );
+    assert_eq!(ast, expected_ast);
 }
 
+///////////////////////////
+// Composition Expressions
+///////////////////////////
 #[test]
-fn alias_references_are_not_allowed_in_the_group_by() {
-    let t = TableRef::new("sxt", "employees");
+fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_where_expr_and_order_by(
+) {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
+            "boolean".into() => ColumnType::Boolean,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select department, min(salary) as min_salary from employees group by min_salary",
+        "select a, boolean and a >= 4 as res from sxt_tab where a = -3 order by a desc limit 55 offset 3",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select salary as min_salary from employees group by min_salary",
-        &accessor,
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                filter(
+                    vec![
+                        col_expr_plan(&t, "a", &accessor),
+                        aliased_plan(
+                            and(
+                                column(&t, "boolean", &accessor),
+                                gte(column(&t, "a", &accessor), const_bigint(4)),
+                            ),
+                            "res",
+                        ),
+                    ],
+                    tab(&t),
+                    equal(column(&t, "a", &accessor), const_bigint(-3)),
+                ),
+                vec![(0, false)],
+            ),
+            3,
+            Some(55),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn order_by_cannot_reference_an_invalid_group_by_column() {
-    let t = TableRef::new("sxt", "employees");
+fn we_can_prove_an_order_by_with_a_multi_column_key_followed_by_a_limit() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
+            "c".into() => ColumnType::VarChar,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select department as d from sxt.employees group by department order by department",
+        "select c, a, b from sxt_tab order by b desc, a limit 3",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select department, min(salary) from sxt.employees group by department order by salary",
-        &accessor,
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                filter(
+                    cols_expr_plan(&t, &["c", "a", "b"], &accessor),
+                    tab(&t),
+                    const_bool(true),
+                ),
+                vec![(2, false), (1, true)],
+            ),
+            0,
+            Some(3),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
+
+    // An offset without a limit slices all remaining rows
+    let ast = query_to_provable_ast(&t, "select a from sxt_tab order by a offset 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                filter(
+                    cols_expr_plan(&t, &["a"], &accessor),
+                    tab(&t),
+                    const_bool(true),
+                ),
+                vec![(0, true)],
+            ),
+            2,
+            None,
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_column_cannot_be_a_column_result_alias() {
+fn we_can_prove_an_order_by_of_the_groups_of_a_group_by_followed_by_a_limit() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
@@ -1229,254 +1443,329 @@ fn group_by_column_cannot_be_a_column_result_alias() {
             "department".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select min(salary) as min_sal from sxt.employees group by min_sal",
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department order by total_salary desc limit 10",
         &accessor,
     );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                group_by(
+                    cols_expr_plan(&t, &["department"], &accessor),
+                    vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+                    "num_employee",
+                    tab(&t),
+                    const_bool(true),
+                ),
+                vec![(1, false)],
+            ),
+            0,
+            Some(10),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_have_aggregate_functions_without_a_group_by_clause() {
-    let query_text = "select count(name) from sxt.employees";
-    let t = TableRef::new("sxt", "employees");
+fn we_sort_in_postprocessing_if_the_order_by_with_a_limit_is_not_provable() {
+    let t = TableRef::new("sxt", "sxt_tab");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
+            "a".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
         },
     );
+    // The sort key is not provable
+    let ast = query_to_provable_ast(&t, "select a, c from sxt_tab order by c limit 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a", "c"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![orders(&[1_usize], &[true]), slice(Some(3), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
 
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
+    // The offset counts from the end
+    let ast = query_to_provable_ast(
+        &t,
+        "select a, c from sxt_tab order by a limit 3 offset -2",
+        &accessor,
+    );
     let expected_ast = QueryExpr::new(
-        group_by(vec![], vec![], "__count__", tab(&t), const_bool(true)),
-        vec![],
+        filter(
+            cols_expr_plan(&t, &["a", "c"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![orders(&[0_usize], &[true]), slice(Some(3), Some(-2))],
     );
     assert_eq!(ast, expected_ast);
 }
 
+///////////////////////////
+// Group By Expressions - Prover
+///////////////////////////
 #[test]
-fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
+fn we_can_do_provable_group_by() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select count(bonus) department from sxt.employees group by department",
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["bonus", "department"], &accessor),
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count(col("bonus")), "department")],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
+fn we_can_do_provable_group_by_without_sum() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
+        "select department, count(*) as num_employee from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["bonus", "department"], &accessor),
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[
-                aliased_expr(col("department"), "department"),
-                aliased_expr(count(col("bonus")), "__count__"),
-                aliased_expr(count(col("department")), "dep"),
-            ],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
+fn we_can_do_provable_group_by_with_two_group_by_columns() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "state".into() => ColumnType::VarChar,
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select count(*) from sxt.employees where salary = 4 group by department",
+        "select state, department, sum(salary) as total_salary, count(*) as num_employee from employees group by state, department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["department", "salary"], &accessor),
+        group_by(
+            cols_expr_plan(&t, &["state", "department"], &accessor),
+            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+            "num_employee",
             tab(&t),
-            equal(column(&t, "salary", &accessor), const_bigint(4)),
+            const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count_all(), "__count__")],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn aggregate_result_columns_cannot_reference_invalid_columns() {
+fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "tax".into() => ColumnType::BigInt,
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select department, max(non_existent) from sxt.employees group by department",
+        "select department, sum(salary) as total_salary, sum(tax) as total_tax, count(*) as num_employee from employees where tax <= 1 group by department",
         &accessor,
     );
-}
-
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![
+                sum_expr(column(&t, "salary", &accessor), "total_salary"),
+                sum_expr(column(&t, "tax", &accessor), "total_tax"),
+            ],
+            "num_employee",
+            tab(&t),
+            lte(column(&t, "tax", &accessor), const_bigint(1)),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
-fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
+fn we_can_do_provable_group_by_with_averages_derived_from_sums() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "tax".into() => ColumnType::BigInt,
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "SELECT department as d1, department as d2 from employees group by department",
+        "select department, avg(salary) as avg_salary, sum(tax) as total_tax, avg(salary + tax) as avg_total, count(*) as num_employee from employees group by department order by avg_salary desc limit 3",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
+        group_by(
             cols_expr_plan(&t, &["department"], &accessor),
+            vec![
+                sum_expr(column(&t, "salary", &accessor), "avg_salary"),
+                sum_expr(column(&t, "tax", &accessor), "total_tax"),
+                sum_expr(
+                    add(
+                        column(&t, "salary", &accessor),
+                        column(&t, "tax", &accessor),
+                    ),
+                    "avg_total",
+                ),
+            ],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[
-                aliased_expr(col("department"), "d1"),
-                aliased_expr(col("department"), "d2"),
-            ],
-        )],
+        vec![
+            average(&["avg_salary", "avg_total"], "num_employee"),
+            orders(&[1_usize], &[false]),
+            slice(Some(3), Some(0)),
+        ],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
+fn we_can_do_provable_group_by_with_filtered_sums_and_counts_of_nullable_columns() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "tax".into() => ColumnType::BigInt,
             "bonus".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
             "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
+    )
+    .with_nullable_column(t.clone(), "bonus".into());
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, sum(salary) filter (where tax >= 2) as taxed_salary, count(bonus) as num_bonus, count(bonus) filter (where tax >= 2) as num_taxed_bonus, count(*) as num_employee from employees group by department",
+        &accessor,
     );
-    let query_text = "select salary d1, max(tax), salary d2, sum(bonus) sum_bonus, count(name) count_s from sxt.employees group by salary, bonus, salary";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["bonus", "name", "salary", "tax"], &accessor),
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![
+                filtered_sum_expr(
+                    column(&t, "salary", &accessor),
+                    gte(column(&t, "tax", &accessor), const_bigint(2)),
+                    "taxed_salary",
+                ),
+                count_expr(Some(presence(&t, "bonus", &accessor)), "num_bonus"),
+                count_expr(
+                    Some(and(
+                        presence(&t, "bonus", &accessor),
+                        gte(column(&t, "tax", &accessor), const_bigint(2)),
+                    )),
+                    "num_taxed_bonus",
+                ),
+            ],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["salary", "bonus", "salary"],
-            &[
-                aliased_expr(col("salary"), "d1"),
-                aliased_expr(max(col("tax")), "__max__"),
-                aliased_expr(col("salary"), "d2"),
-                aliased_expr(sum(col("bonus")), "sum_bonus"),
-                aliased_expr(count(col("name")), "count_s"),
-            ],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
+fn we_can_do_provable_group_by_with_sums_of_arithmetic_on_nullable_columns() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "a".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::Int128,
-            "b".into() => ColumnType::BigInt,
-            "h".into() => ColumnType::Int128,
+            "tax".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
-    );
-    // TODO: add `a / b as a_div_b` result expr once polars properly
-    // supports decimal division without panicking in production
+    )
+    .with_nullable_column(t.clone(), "bonus".into());
     let ast = query_to_provable_ast(
         &t,
-        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        "select department, sum(bonus + 1) as a, sum(bonus + salary) as b, sum(bonus - 5) filter (where tax >= 2) as c, sum(bonus) as d, count(*) as num_employee from employees group by department",
         &accessor,
     );
+    // The sums are zero where the bonus is NULL, so they skip those rows
     let expected_ast = QueryExpr::new(
-        filter(
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
             vec![
-                aliased_plan(
-                    add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
-                    "__expr__",
+                sum_expr(
+                    nullable_expr(
+                        add(column(&t, "bonus", &accessor), const_bigint(1)),
+                        presence(&t, "bonus", &accessor),
+                    ),
+                    "a",
                 ),
-                aliased_plan(multiply(const_bigint(2), column(&t, "f", &accessor)), "f2"),
-                aliased_plan(
-                    subtract(const_bigint(-77), column(&t, "h", &accessor)),
-                    "col",
+                sum_expr(
+                    nullable_expr(
+                        add(
+                            column(&t, "bonus", &accessor),
+                            column(&t, "salary", &accessor),
+                        ),
+                        presence(&t, "bonus", &accessor),
+                    ),
+                    "b",
                 ),
-                aliased_plan(
-                    add(column(&t, "a", &accessor), column(&t, "f", &accessor)),
-                    "af",
+                filtered_sum_expr(
+                    nullable_expr(
+                        subtract(column(&t, "bonus", &accessor), const_bigint(5)),
+                        presence(&t, "bonus", &accessor),
+                    ),
+                    gte(column(&t, "tax", &accessor), const_bigint(2)),
+                    "c",
                 ),
+                sum_expr(nullable_column(&t, "bonus", &accessor), "d"),
             ],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
@@ -1486,56 +1775,81 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
 }
 
 #[test]
-fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
-) {
+fn we_cannot_convert_an_ast_with_an_unsupported_filter_clause() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "c".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::BigInt,
-            "g".into() => ColumnType::BigInt,
-            "h".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // The filter must be a boolean expression
+    assert!(matches!(
+        try_new("select department, sum(salary) filter (where tax) as s, count(*) as n from employees group by department"),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    // Only sums and counts can be proven with a filter
+    assert!(matches!(
+        try_new("select department, max(salary) filter (where tax >= 2) as m, count(*) as n from employees group by department"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnsupportedAggregation { .. }
+        })
+    ));
+    assert!(matches!(
+        try_new("select department, avg(salary) filter (where tax >= 2) as a, count(*) as n from employees group by department"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnsupportedAggregation { .. }
+        })
+    ));
+    // The last result column must be the count of all rows
+    assert!(matches!(
+        try_new("select department, count(*) filter (where tax >= 2) as n from employees group by department"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnsupportedAggregation { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_do_provable_group_by_with_extrema() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "tax".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        "select department, min(salary) as min_salary, sum(tax) as total_tax, max(salary - tax) as max_net, count(*) as num_employee from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
             vec![
-                aliased_plan(
-                    multiply(
-                        add(const_bigint(2), column(&t, "f", &accessor)),
-                        add(
-                            add(column(&t, "c", &accessor), column(&t, "g", &accessor)),
-                            multiply(const_bigint(2), column(&t, "h", &accessor)),
-                        ),
-                    ),
-                    "__expr__",
-                ),
-                aliased_plan(
-                    multiply(
-                        add(
-                            add(
-                                multiply(
-                                    subtract(
-                                        column(&t, "h", &accessor),
-                                        column(&t, "g", &accessor),
-                                    ),
-                                    const_bigint(2),
-                                ),
-                                column(&t, "c", &accessor),
-                            ),
-                            column(&t, "g", &accessor),
-                        ),
-                        add(column(&t, "f", &accessor), const_bigint(2)),
+                min_expr(column(&t, "salary", &accessor), "min_salary"),
+                sum_expr(column(&t, "tax", &accessor), "total_tax"),
+                max_expr(
+                    subtract(
+                        column(&t, "salary", &accessor),
+                        column(&t, "tax", &accessor),
                     ),
-                    "d",
+                    "max_net",
                 ),
             ],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
@@ -1545,540 +1859,1522 @@ fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_preceden
 }
 
 #[test]
-fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+fn we_can_do_provable_group_by_with_a_having_clause() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "c".into() => ColumnType::BigInt,
-            "f".into() => ColumnType::BigInt,
-            "g".into() => ColumnType::BigInt,
-            "k".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department having sum(salary) >= 100 and max(tax) <= 5 and count(*) >= 2 order by total_salary desc",
         &accessor,
     );
+    // The HAVING clause refers to the columns of the groups
+    let groups_accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "department".into() => ColumnType::BigInt,
+            "total_salary".into() => ColumnType::BigInt,
+            "__having_0__".into() => ColumnType::BigInt,
+            "num_employee".into() => ColumnType::BigInt,
+        },
+    );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["c", "f"], &accessor),
+            cols_expr_plan(
+                &t,
+                &["department", "total_salary", "num_employee"],
+                &groups_accessor,
+            ),
+            tab_with_input(
+                &t,
+                group_by(
+                    cols_expr_plan(&t, &["department"], &accessor),
+                    vec![
+                        sum_expr(column(&t, "salary", &accessor), "total_salary"),
+                        max_expr(column(&t, "tax", &accessor), "__having_0__"),
+                    ],
+                    "num_employee",
+                    tab(&t),
+                    const_bool(true),
+                ),
+            ),
+            and(
+                and(
+                    gte(
+                        column(&t, "total_salary", &groups_accessor),
+                        const_bigint(100),
+                    ),
+                    lte(
+                        column(&t, "__having_0__", &groups_accessor),
+                        const_bigint(5),
+                    ),
+                ),
+                gte(
+                    column(&t, "num_employee", &groups_accessor),
+                    const_bigint(2),
+                ),
+            ),
+        ),
+        vec![orders(&[1_usize], &[false])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_filter_groups_in_postprocessing_if_the_having_clause_is_not_provable() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::VarChar,
+            "team".into() => ColumnType::BigInt,
+        },
+    );
+    // Groups of VarChar columns are not proven to be distinct
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department having count(*) > 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["department", "salary"], &accessor),
             tab(&t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["c"],
+        vec![group_by_postprocessing_with_having(
+            &["department"],
             &[
-                aliased_expr(col("c"), "c"),
-                aliased_expr(
-                    sum(psub(padd(pmul(lit(2), col("f")), col("c")), lit(-7))),
-                    "d",
-                ),
+                aliased_expr(col("department"), "department"),
+                aliased_expr(sum(col("salary")), "total_salary"),
+                aliased_expr(count_all(), "num_employee"),
+            ],
+            &pgt(count_all(), lit(1)),
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Averages are derived from sums in postprocessing, so they can not be filtered on by the proof
+    let ast = query_to_provable_ast(
+        &t,
+        "select team, sum(salary) as total_salary, count(*) as num_employee from employees group by team having avg(salary) > 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["salary", "team"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing_with_having(
+            &["team"],
+            &[
+                aliased_expr(col("team"), "team"),
+                aliased_expr(sum(col("salary")), "total_salary"),
+                aliased_expr(count_all(), "num_employee"),
             ],
+            &pgt(avg(col("salary")), lit(1)),
         )],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_use_non_grouped_columns_outside_agg() {
+fn we_cannot_have_invalid_having_clauses() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let identifier_not_in_agg_queries = vec![
-        "select salary from sxt.employees group by name",
-        "select sum(salary), salary from sxt.employees group by name",
-        "select min(salary) + salary from sxt.employees group by name",
-        "select 2 * salary, min(salary) from sxt.employees group by name",
-    ];
-
-    for query_text in &identifier_not_in_agg_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-        assert!(matches!(
-            result,
-            Err(ConversionError::PostprocessingError {
-                source: PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. }
-            })
-        ));
-    }
-
-    let invalid_group_by_queries = vec![
-        "select 2 * salary, min(salary) from sxt.employees",
-        "select sum(salary), salary from sxt.employees",
-        "select max(salary) + 2 * salary from sxt.employees",
-    ];
-
-    for query_text in &invalid_group_by_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-        assert!(matches!(
-            result,
-            Err(ConversionError::InvalidGroupByColumnRef { .. })
-        ));
-    }
-}
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select department, count(*) as num_employee from employees group by department having salary > 1")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor),
+        Err(ConversionError::InvalidGroupByColumnRef { .. })
+    ));
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select department, count(*) as num_employee from employees group by department having sum(salary)")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+}
 
 #[test]
-fn varchar_column_is_not_compatible_with_integer_column() {
+fn we_can_do_provable_group_by_with_an_expression_key_and_a_having_clause() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "bonus".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
+    let ast = query_to_provable_ast(
+        &t,
+        "select salary + bonus as pay, department, count(*) as num_employee from employees group by salary + bonus, department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![
+                aliased_plan(
+                    add(
+                        column(&t, "salary", &accessor),
+                        column(&t, "bonus", &accessor),
+                    ),
+                    "pay",
+                ),
+                col_expr_plan(&t, "department", &accessor),
+            ],
+            vec![],
+            "num_employee",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 
-    let bigint_to_varchar_queries = vec![
-        "select -123 * name from sxt.employees",
-        "select salary - name from sxt.employees",
-    ];
-
-    let varchar_to_bigint_queries = vec![
-        "select name from sxt.employees where 'abc' = salary",
-        "select name from sxt.employees where 'abc' != salary",
-    ];
-
-    for query_text in &bigint_to_varchar_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::DataTypeMismatch {
-                left_type: ColumnType::BigInt.to_string(),
-                right_type: ColumnType::VarChar.to_string(),
-            })
-        );
-    }
-
-    for query_text in &varchar_to_bigint_queries {
-        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-        let result =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::DataTypeMismatch {
-                left_type: ColumnType::VarChar.to_string(),
-                right_type: ColumnType::BigInt.to_string(),
-            })
-        );
-    }
-}
-
-#[test]
-fn arithmetic_operations_are_not_allowed_with_varchar_column() {
-    let t = TableRef::new("sxt", "employees");
-    let accessor = schema_accessor_from_table_ref_with_schema(
+    // The HAVING clause refers to the expression key by the alias of its result column
+    let ast = query_to_provable_ast(
+        &t,
+        "select salary + bonus as pay, count(*) as num_employee from employees group by salary + bonus having salary + bonus >= 100",
+        &accessor,
+    );
+    let groups_accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
-            "position".into() => ColumnType::VarChar,
+            "pay".into() => ColumnType::BigInt,
+            "num_employee".into() => ColumnType::BigInt,
         },
     );
-
-    let query_text = "select name - position from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-    assert_eq!(
-        result,
-        Err(ConversionError::DataTypeMismatch {
-            left_type: ColumnType::VarChar.to_string(),
-            right_type: ColumnType::VarChar.to_string(),
-        })
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["pay", "num_employee"], &groups_accessor),
+            tab_with_input(
+                &t,
+                group_by(
+                    vec![aliased_plan(
+                        add(
+                            column(&t, "salary", &accessor),
+                            column(&t, "bonus", &accessor),
+                        ),
+                        "pay",
+                    )],
+                    vec![],
+                    "num_employee",
+                    tab(&t),
+                    const_bool(true),
+                ),
+            ),
+            gte(column(&t, "pay", &groups_accessor), const_bigint(100)),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn varchar_column_is_not_allowed_within_numeric_aggregations() {
+fn we_cannot_group_by_expressions_that_are_not_provable_result_expressions() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::BigInt,
+            "is_remote".into() => ColumnType::Boolean,
         },
     );
-    let sum_query = "select sum(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(sum_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select count(*) as num_employee from employees group by salary + bonus")
+        .unwrap();
     assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'sum'"
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor),
+        Err(ConversionError::Unprovable { .. })
     ));
-
-    let max_query = "select max(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(max_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
+    let intermediate_ast = SelectStatementParser::new()
+        .parse(
+            "select sum(salary) as s, count(*) as num_employee from employees group by sum(salary)",
+        )
+        .unwrap();
     assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'max'"
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor),
+        Err(ConversionError::InvalidExpression { .. })
     ));
-
-    let min_query = "select min(name) from sxt.employees";
-    let intermediate_ast = SelectStatementParser::new().parse(min_query).unwrap();
-    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
+    // Extrema of boolean columns are computed in postprocessing, which can not group by expressions
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select salary + bonus as pay, max(is_remote) as any_remote, count(*) as num_employee from employees group by salary + bonus")
+        .unwrap();
     assert!(matches!(
-        result,
-        Err(ConversionError::InvalidExpression { expression })
-            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'min'"
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor),
+        Err(ConversionError::Unprovable { .. })
     ));
 }
 
 #[test]
-fn group_by_with_bigint_column_is_valid() {
+fn we_compute_extrema_of_boolean_columns_in_postprocessing() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::BigInt,
+            "is_remote".into() => ColumnType::Boolean,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let query_text = "select salary from sxt.employees group by salary";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, max(is_remote) as any_remote, count(*) as num_employee from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["salary"], &accessor),
+            cols_expr_plan(&t, &["department", "is_remote"], &accessor),
             tab(&t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["salary"],
-            &[aliased_expr(col("salary"), "salary")],
+            &["department"],
+            &[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(max(col("is_remote")), "any_remote"),
+                aliased_expr(count_all(), "num_employee"),
+            ],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_decimal_column_is_valid() {
+fn we_can_compute_averages_in_postprocessing_if_the_group_by_is_not_provable() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "salary".into() => ColumnType::Int128,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let query_text = "select salary from sxt.employees group by salary";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, avg(salary) as avg_salary from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["salary"], &accessor),
+            cols_expr_plan(&t, &["department", "salary"], &accessor),
             tab(&t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["salary"],
-            &[aliased_expr(col("salary"), "salary")],
+            &["department"],
+            &[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(avg(col("salary")), "avg_salary"),
+            ],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_varchar_column_is_valid() {
+fn we_cannot_average_non_numeric_columns() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "name".into() => ColumnType::VarChar,
+            "is_remote".into() => ColumnType::Boolean,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let query_text = "select name from sxt.employees group by name";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["name"], &accessor),
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![group_by_postprocessing(
-            &["name"],
-            &[aliased_expr(col("name"), "name")],
-        )],
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, avg(name) as n, count(*) as c from employees group by department",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, avg(is_remote) as r, count(*) as c from employees group by department",
+        &accessor,
     );
-    assert_eq!(query, expected_query);
 }
 
+///////////////////////////
+// Group By Expressions - Postprocessing
+///////////////////////////
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
+fn we_can_group_by_without_using_aggregate_functions() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let query_text =
-        "select 2 * salary + sum(salary) - tax from sxt.employees group by salary, tax";
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, true as is_remote from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["salary", "tax"], &accessor),
+            vec![col_expr_plan(&t, "department", &accessor)],
             tab(&t),
             const_bool(true),
         ),
         vec![
             group_by_postprocessing(
-                &["salary", "tax"],
-                &[aliased_expr(
-                    psub(
-                        padd(pmul(lit(2), col("salary")), sum(col("salary"))),
-                        col("tax"),
-                    ),
-                    "__expr__",
-                )],
+                &["department"],
+                &[
+                    aliased_expr(col("department"), "department"),
+                    aliased_expr(lit(true), "is_remote"),
+                ],
             ),
-            select_expr(&[aliased_expr(
-                psub(
-                    padd(pmul(lit(2), col("salary")), col("__col_agg_0")),
-                    col("tax"),
-                ),
-                "__expr__",
-            )]),
+            select_expr(&[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(lit(true), "is_remote"),
+            ]),
         ],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
+fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
+    let query_text =
+        "select max(salary) max_sal, department_budget d, count(department_budget) from sxt.employees group by department_budget, tax order by max_sal";
+
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
+            "department_budget".into() => ColumnType::BigInt,
             "salary".into() => ColumnType::BigInt,
-            "bonus".into() => ColumnType::Int128,
+            "tax".into() => ColumnType::BigInt,
         },
     );
-    let query_text = "select 7 + max(salary) as max_i, min(salary + 777 * bonus) * -5 as min_d from sxt.employees";
 
     let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast =
+    let query =
         QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
 
-    let expected_ast = QueryExpr::new(
+    let expected_query = QueryExpr::new(
         filter(
-            cols_expr_plan(&t, &["bonus", "salary"], &accessor),
+            vec![
+                col_expr_plan(&t, "department_budget", &accessor),
+                col_expr_plan(&t, "salary", &accessor),
+                col_expr_plan(&t, "tax", &accessor),
+            ],
             tab(&t),
             const_bool(true),
         ),
         vec![
             group_by_postprocessing(
-                &[],
+                &["department_budget", "tax"],
                 &[
-                    aliased_expr(padd(lit(7), max(col("salary"))), "max_i"),
-                    aliased_expr(
-                        pmul(
-                            min(padd(col("salary"), pmul(lit(777), col("bonus")))),
-                            lit(-5),
-                        ),
-                        "min_d",
-                    ),
+                    aliased_expr(max(col("salary")), "max_sal"),
+                    aliased_expr(col("department_budget"), "d"),
+                    aliased_expr(count(col("department_budget")), "__count__"),
                 ],
             ),
-            select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "max_i"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "min_d"),
-            ]),
+            orders(&[0_usize], &[true]),
         ],
     );
-    assert_eq!(ast, expected_ast);
+    assert_eq!(query, expected_query);
 }
 
 #[test]
-fn count_aggregation_always_have_integer_type() {
+fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
             "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::Int128,
+            "department".into() => ColumnType::BigInt,
         },
     );
-    let query_text =
-        "select 7 + count(name) as cs, count(salary) * -5 as ci, count(tax) from sxt.employees";
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, salary from sxt.employees group by department",
+        &accessor,
+    );
+}
 
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let ast =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+#[test]
+fn alias_references_are_not_allowed_in_the_group_by() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, min(salary) as min_salary from employees group by min_salary",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select salary as min_salary from employees group by min_salary",
+        &accessor,
+    );
+}
 
-    let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["name", "salary", "tax"], &accessor),
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &[],
-                &[
-                    aliased_expr(padd(lit(7), count(col("name"))), "cs"),
-                    aliased_expr(pmul(count(col("salary")), lit(-5)), "ci"),
-                    aliased_expr(count(col("tax")), "__count__"),
-                ],
-            ),
-            select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "cs"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "ci"),
-                aliased_expr(col("__col_agg_2"), "__count__"),
-            ]),
-        ],
+#[test]
+fn order_by_cannot_reference_an_invalid_group_by_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department as d from sxt.employees group by department order by department",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, min(salary) from sxt.employees group by department order by salary",
+        &accessor,
     );
-    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn select_wildcard_is_valid_with_group_by_exprs() {
-    let columns = [
-        "employee_name",
-        "base_salary",
-        "annual_bonus",
-        "manager_name",
-        "manager_salary",
-        "manager_bonus",
-        "department_name",
-        "department_budget",
-        "department_headcount",
-    ];
-    let sorted_columns = columns.iter().sorted().collect::<Vec<_>>();
-    let aliased_exprs = columns
-        .iter()
-        .map(|c| aliased_expr(col(c), c))
-        .collect::<Vec<_>>();
+fn group_by_column_cannot_be_a_column_result_alias() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "employee_name".into() => ColumnType::VarChar,
-            "base_salary".into() => ColumnType::BigInt,
-            "annual_bonus".into() => ColumnType::Int128,
-            "manager_name".into() => ColumnType::VarChar,
-            "manager_salary".into() => ColumnType::BigInt,
-            "manager_bonus".into() => ColumnType::Int128,
-            "department_name".into() => ColumnType::VarChar,
-            "department_budget".into() => ColumnType::BigInt,
-            "department_headcount".into() => ColumnType::Int128,
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
+    invalid_query_to_provable_ast(
+        &t,
+        "select min(salary) as min_sal from sxt.employees group by min_sal",
+        &accessor,
+    );
+}
 
-    let query_text = format!(
-        "SELECT * FROM {} GROUP BY {}",
-        "sxt.employees",
-        columns.join(", ")
+#[test]
+fn we_can_have_aggregate_functions_without_a_group_by_clause() {
+    let query_text = "select count(name) from sxt.employees";
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
     );
 
-    let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
     let ast =
         QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
 
     let expected_ast = QueryExpr::new(
-        filter(
-            sorted_columns
-                .iter()
-                .map(|c| col_expr_plan(&t, c, &accessor))
-                .collect(),
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![group_by_postprocessing(&columns, &aliased_exprs)],
+        group_by(vec![], vec![], "__count__", tab(&t), const_bool(true)),
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn nested_aggregations_are_not_supported() {
-    let supported_agg = ["max", "min", "sum", "count"];
+fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
-
-    for perm_aggs in supported_agg.iter().permutations(2) {
-        let query_text = format!(
-            "SELECT {}({}(salary)) FROM sxt.employees",
-            perm_aggs[0], perm_aggs[1]
-        );
-
-        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
-        let result =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
-
-        assert_eq!(
-            result,
-            Err(ConversionError::InvalidExpression {
-                expression: "nested aggregations are not supported".to_string()
-            })
-        );
-    }
+    let ast = query_to_provable_ast(
+        &t,
+        "select count(bonus) department from sxt.employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["bonus", "department"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[aliased_expr(count(col("bonus")), "department")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn select_group_and_order_by_preserve_the_column_order_reference() {
-    const N: usize = 4;
+fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
             "department".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
-            "name".into() => ColumnType::VarChar,
+            "bonus".into() => ColumnType::VarChar,
         },
     );
-    let base_cols: [&str; N] = ["salary", "department", "tax", "name"]; // sorted because of `select: [cols = ... ]`
-    let base_ordering = [true, false, true, false];
-    for (idx, perm_cols) in base_cols
-        .into_iter()
-        .permutations(N)
-        .collect::<IndexSet<_>>()
-        .into_iter()
-        .enumerate()
-    {
-        let perm_col_plans = perm_cols
-            .iter()
-            .sorted()
-            .map(|c| col_expr_plan(&t, c, &accessor))
-            .collect();
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
+        &accessor,
+    );
+    // Counts of non-nullable expressions are proven like sums of ones
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![count_expr(None, "__count__")],
+            "dep",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select count(*) from sxt.employees where salary = 4 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["department", "salary"], &accessor),
+            tab(&t),
+            equal(column(&t, "salary", &accessor), const_bigint(4)),
+        ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[aliased_expr(count_all(), "__count__")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn aggregate_result_columns_cannot_reference_invalid_columns() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, max(non_existent) from sxt.employees group by department",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "SELECT department as d1, department as d2 from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["department"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[
+                aliased_expr(col("department"), "d1"),
+                aliased_expr(col("department"), "d2"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "bonus".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text = "select salary d1, max(tax), salary d2, sum(bonus) sum_bonus, count(name) count_s from sxt.employees group by salary, bonus, salary";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let ast =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["bonus", "name", "salary", "tax"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["salary", "bonus", "salary"],
+            &[
+                aliased_expr(col("salary"), "d1"),
+                aliased_expr(max(col("tax")), "__max__"),
+                aliased_expr(col("salary"), "d2"),
+                aliased_expr(sum(col("bonus")), "sum_bonus"),
+                aliased_expr(count(col("name")), "count_s"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::Int128,
+            "b".into() => ColumnType::BigInt,
+            "h".into() => ColumnType::Int128,
+        },
+    );
+    // TODO: add `a / b as a_div_b` result expr once polars properly
+    // supports decimal division without panicking in production
+    let ast = query_to_provable_ast(
+        &t,
+        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "__expr__",
+                ),
+                aliased_plan(multiply(const_bigint(2), column(&t, "f", &accessor)), "f2"),
+                aliased_plan(
+                    subtract(const_bigint(-77), column(&t, "h", &accessor)),
+                    "col",
+                ),
+                aliased_plan(
+                    add(column(&t, "a", &accessor), column(&t, "f", &accessor)),
+                    "af",
+                ),
+            ],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_provable_division_and_modulo_in_the_result_expr_and_where_clause() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a / b as q, a % b as r from employees where a % 2 = 0",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "q",
+                ),
+                aliased_plan(
+                    modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "r",
+                ),
+            ],
+            tab(&t),
+            equal(
+                modulo(column(&t, "a", &accessor), const_bigint(2)),
+                const_bigint(0),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_provable_date_time_functions_in_the_result_expr_and_where_clause() {
+    let t = TableRef::new("sxt", "sales");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::new(3_600)),
+            "amount".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select extract(year from ts) as y, date_trunc('day', ts) as d, ts - interval '2 hours' as earlier from sales where extract(hour from ts) = 9",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(extract(Year, column(&t, "ts", &accessor)), "y"),
+                aliased_plan(date_trunc(Day, column(&t, "ts", &accessor)), "d"),
+                aliased_plan(
+                    add_interval(column(&t, "ts", &accessor), -2, Hour),
+                    "earlier",
+                ),
+            ],
+            tab(&t),
+            equal(extract(Hour, column(&t, "ts", &accessor)), const_bigint(9)),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_do_provable_group_by_with_a_date_trunc_key() {
+    let t = TableRef::new("sxt", "sales");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "amount".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select date_trunc('day', ts) as day, sum(amount) as total, count(*) as num_sales from sales group by date_trunc('day', ts)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![aliased_plan(
+                date_trunc(Day, column(&t, "ts", &accessor)),
+                "day",
+            )],
+            vec![sum_expr(column(&t, "amount", &accessor), "total")],
+            "num_sales",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_unprovable_date_time_functions() {
+    let t = TableRef::new("sxt", "sales");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "amount".into() => ColumnType::BigInt,
+        },
+    );
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // Date/time functions only apply to timestamps
+    assert!(matches!(
+        try_new("select extract(year from amount) as y from sales"),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        try_new("select amount from sales where date_trunc('day', amount) = 1"),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_date_time_functions_of_months_and_years_using_postprocessing() {
+    let t = TableRef::new("sxt", "sales");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "ts".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "amount".into() => ColumnType::BigInt,
+        },
+    );
+    // Months and years do not have a fixed length, so they can not be proven
+    let ast = query_to_provable_ast(
+        &t,
+        "select date_trunc('month', ts) as m, ts + interval '1 year' as later from sales",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["ts"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![select_expr(&[
+            aliased_expr(pdate_trunc(Month, col("ts")), "m"),
+            aliased_expr(padd_interval(col("ts"), 1, Year), "later"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
+) {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "c".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::BigInt,
+            "g".into() => ColumnType::BigInt,
+            "h".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    multiply(
+                        add(const_bigint(2), column(&t, "f", &accessor)),
+                        add(
+                            add(column(&t, "c", &accessor), column(&t, "g", &accessor)),
+                            multiply(const_bigint(2), column(&t, "h", &accessor)),
+                        ),
+                    ),
+                    "__expr__",
+                ),
+                aliased_plan(
+                    multiply(
+                        add(
+                            add(
+                                multiply(
+                                    subtract(
+                                        column(&t, "h", &accessor),
+                                        column(&t, "g", &accessor),
+                                    ),
+                                    const_bigint(2),
+                                ),
+                                column(&t, "c", &accessor),
+                            ),
+                            column(&t, "g", &accessor),
+                        ),
+                        add(column(&t, "f", &accessor), const_bigint(2)),
+                    ),
+                    "d",
+                ),
+            ],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "c".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::BigInt,
+            "g".into() => ColumnType::BigInt,
+            "k".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["c", "f"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["c"],
+            &[
+                aliased_expr(col("c"), "c"),
+                aliased_expr(
+                    sum(psub(padd(pmul(lit(2), col("f")), col("c")), lit(-7))),
+                    "d",
+                ),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_non_grouped_columns_outside_agg() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let identifier_not_in_agg_queries = vec![
+        "select salary from sxt.employees group by name",
+        "select sum(salary), salary from sxt.employees group by name",
+        "select min(salary) + salary from sxt.employees group by name",
+        "select 2 * salary, min(salary) from sxt.employees group by name",
+    ];
+
+    for query_text in &identifier_not_in_agg_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+        assert!(matches!(
+            result,
+            Err(ConversionError::PostprocessingError {
+                source: PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. }
+            })
+        ));
+    }
+
+    let invalid_group_by_queries = vec![
+        "select 2 * salary, min(salary) from sxt.employees",
+        "select sum(salary), salary from sxt.employees",
+        "select max(salary) + 2 * salary from sxt.employees",
+    ];
+
+    for query_text in &invalid_group_by_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+        assert!(matches!(
+            result,
+            Err(ConversionError::InvalidGroupByColumnRef { .. })
+        ));
+    }
+}
+
+#[test]
+fn varchar_column_is_not_compatible_with_integer_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+
+    let bigint_to_varchar_queries = vec![
+        "select -123 * name from sxt.employees",
+        "select salary - name from sxt.employees",
+    ];
+
+    let varchar_to_bigint_queries = vec![
+        "select name from sxt.employees where 'abc' = salary",
+        "select name from sxt.employees where 'abc' != salary",
+    ];
+
+    for query_text in &bigint_to_varchar_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::DataTypeMismatch {
+                left_type: ColumnType::BigInt.to_string(),
+                right_type: ColumnType::VarChar.to_string(),
+            })
+        );
+    }
+
+    for query_text in &varchar_to_bigint_queries {
+        let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+        let result =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::DataTypeMismatch {
+                left_type: ColumnType::VarChar.to_string(),
+                right_type: ColumnType::BigInt.to_string(),
+            })
+        );
+    }
+}
+
+#[test]
+fn arithmetic_operations_are_not_allowed_with_varchar_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+            "position".into() => ColumnType::VarChar,
+        },
+    );
+
+    let query_text = "select name - position from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+    assert_eq!(
+        result,
+        Err(ConversionError::DataTypeMismatch {
+            left_type: ColumnType::VarChar.to_string(),
+            right_type: ColumnType::VarChar.to_string(),
+        })
+    );
+}
+
+#[test]
+fn varchar_column_is_not_allowed_within_numeric_aggregations() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let sum_query = "select sum(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(sum_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'sum'"
+    ));
+
+    let max_query = "select max(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(max_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'max'"
+    ));
+
+    let min_query = "select min(name) from sxt.employees";
+    let intermediate_ast = SelectStatementParser::new().parse(min_query).unwrap();
+    let result = QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidExpression { expression })
+            if expression == "cannot use expression of type 'varchar' with numeric aggregation function 'min'"
+    ));
+}
+
+#[test]
+fn group_by_with_bigint_column_is_valid() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text = "select salary from sxt.employees group by salary";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["salary"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["salary"],
+            &[aliased_expr(col("salary"), "salary")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_decimal_column_is_valid() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::Int128,
+        },
+    );
+    let query_text = "select salary from sxt.employees group by salary";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["salary"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["salary"],
+            &[aliased_expr(col("salary"), "salary")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_varchar_column_is_valid() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let query_text = "select name from sxt.employees group by name";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["name"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["name"],
+            &[aliased_expr(col("name"), "name")],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text =
+        "select 2 * salary + sum(salary) - tax from sxt.employees group by salary, tax";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["salary", "tax"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["salary", "tax"],
+                &[aliased_expr(
+                    psub(
+                        padd(pmul(lit(2), col("salary")), sum(col("salary"))),
+                        col("tax"),
+                    ),
+                    "__expr__",
+                )],
+            ),
+            select_expr(&[aliased_expr(
+                psub(
+                    padd(pmul(lit(2), col("salary")), col("__col_agg_0")),
+                    col("tax"),
+                ),
+                "__expr__",
+            )]),
+        ],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::Int128,
+        },
+    );
+    let query_text = "select 7 + max(salary) as max_i, min(salary + 777 * bonus) * -5 as min_d from sxt.employees";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let ast =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["bonus", "salary"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &[],
+                &[
+                    aliased_expr(padd(lit(7), max(col("salary"))), "max_i"),
+                    aliased_expr(
+                        pmul(
+                            min(padd(col("salary"), pmul(lit(777), col("bonus")))),
+                            lit(-5),
+                        ),
+                        "min_d",
+                    ),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(padd(lit(7), col("__col_agg_0")), "max_i"),
+                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "min_d"),
+            ]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn count_aggregation_always_have_integer_type() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::Int128,
+        },
+    );
+    let query_text =
+        "select 7 + count(name) as cs, count(salary) * -5 as ci, count(tax) from sxt.employees";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let ast =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["name", "salary", "tax"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &[],
+                &[
+                    aliased_expr(padd(lit(7), count(col("name"))), "cs"),
+                    aliased_expr(pmul(count(col("salary")), lit(-5)), "ci"),
+                    aliased_expr(count(col("tax")), "__count__"),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(padd(lit(7), col("__col_agg_0")), "cs"),
+                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "ci"),
+                aliased_expr(col("__col_agg_2"), "__count__"),
+            ]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn select_wildcard_is_valid_with_group_by_exprs() {
+    let columns = [
+        "employee_name",
+        "base_salary",
+        "annual_bonus",
+        "manager_name",
+        "manager_salary",
+        "manager_bonus",
+        "department_name",
+        "department_budget",
+        "department_headcount",
+    ];
+    let sorted_columns = columns.iter().sorted().collect::<Vec<_>>();
+    let aliased_exprs = columns
+        .iter()
+        .map(|c| aliased_expr(col(c), c))
+        .collect::<Vec<_>>();
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "employee_name".into() => ColumnType::VarChar,
+            "base_salary".into() => ColumnType::BigInt,
+            "annual_bonus".into() => ColumnType::Int128,
+            "manager_name".into() => ColumnType::VarChar,
+            "manager_salary".into() => ColumnType::BigInt,
+            "manager_bonus".into() => ColumnType::Int128,
+            "department_name".into() => ColumnType::VarChar,
+            "department_budget".into() => ColumnType::BigInt,
+            "department_headcount".into() => ColumnType::Int128,
+        },
+    );
+
+    let query_text = format!(
+        "SELECT * FROM {} GROUP BY {}",
+        "sxt.employees",
+        columns.join(", ")
+    );
+
+    let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+    let ast =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_ast = QueryExpr::new(
+        filter(
+            sorted_columns
+                .iter()
+                .map(|c| col_expr_plan(&t, c, &accessor))
+                .collect(),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(&columns, &aliased_exprs)],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn nested_aggregations_are_not_supported() {
+    let supported_agg = ["max", "min", "sum", "count"];
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+        },
+    );
+
+    for perm_aggs in supported_agg.iter().permutations(2) {
+        let query_text = format!(
+            "SELECT {}({}(salary)) FROM sxt.employees",
+            perm_aggs[0], perm_aggs[1]
+        );
+
+        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+        let result =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor);
+
+        assert_eq!(
+            result,
+            Err(ConversionError::InvalidExpression {
+                expression: "nested aggregations are not supported".to_string()
+            })
+        );
+    }
+}
+
+#[test]
+fn select_group_and_order_by_preserve_the_column_order_reference() {
+    const N: usize = 4;
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let base_cols: [&str; N] = ["salary", "department", "tax", "name"]; // sorted because of `select: [cols = ... ]`
+    let base_ordering = [true, false, true, false];
+    for (idx, perm_cols) in base_cols
+        .into_iter()
+        .permutations(N)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .enumerate()
+    {
+        let perm_col_plans = perm_cols
+            .iter()
+            .sorted()
+            .map(|c| col_expr_plan(&t, c, &accessor))
+            .collect();
         let aliased_perm_cols = perm_cols
             .iter()
             .map(|c| aliased_expr(col(c), c))
@@ -2091,38 +3387,1413 @@ fn select_group_and_order_by_preserve_the_column_order_reference() {
         let ordering_vec = ordering.clone().collect::<Vec<_>>();
         let ordering_query_vec = ordering_vec
             .iter()
-            .map(|b| match b {
-                true => "ASC",
-                false => "DESC",
+            .map(|b| match b {
+                true => "ASC",
+                false => "DESC",
+            })
+            .collect::<Vec<_>>();
+        let query_text = format!(
+            "SELECT {} FROM {} GROUP BY {} ORDER BY {}",
+            perm_cols.join(", "),
+            t,
+            group_cols_vec.join(", "),
+            order_cols_vec
+                .iter()
+                .zip(ordering_query_vec.iter())
+                .map(|(c, o)| format!("{c} {o}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
+        let query =
+            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor)
+                .unwrap();
+
+        let expected_query = QueryExpr::new(
+            filter(perm_col_plans, tab(&t), const_bool(true)),
+            vec![
+                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
+                orders(&[2_usize, 3, 0, 1], &ordering_vec),
+            ],
+        );
+        assert_eq!(query, expected_query);
+    }
+}
+
+fn join_schema_accessor(left: &TableRef, right: &TableRef) -> TestSchemaAccessor {
+    TestSchemaAccessor::new(indexmap! {
+        left.clone() => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "salary".into() => ColumnType::BigInt,
+        },
+        right.clone() => indexmap! {
+            "employee_id".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    })
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_inner_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select salary, bonus from employees join bonuses on employees.id = bonuses.employee_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                vec![
+                    aliased_plan(column(&left, "salary", &accessor), "salary"),
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                ],
+                tab(&left),
+                const_bool(true),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                ],
+                tab(&right),
+                const_bool(true),
+            ),
+            vec![1],
+            vec![1],
+            vec!["id".into(), "salary".into(), "bonus".into()],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("salary"), "salary"),
+            aliased_expr(col("bonus"), "bonus"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_inner_join_and_filters_on_both_sides() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select id, salary + bonus as total from employees inner join bonuses on id = employee_id where salary >= 5 and bonus = 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                vec![
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                    aliased_plan(column(&left, "salary", &accessor), "salary"),
+                ],
+                tab(&left),
+                gte(column(&left, "salary", &accessor), const_bigint(5)),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                ],
+                tab(&right),
+                equal(column(&right, "bonus", &accessor), const_bigint(3)),
+            ),
+            vec![0],
+            vec![1],
+            vec!["id".into(), "salary".into(), "bonus".into()],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("id"), "id"),
+            aliased_expr(padd(col("salary"), col("bonus")), "total"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_inner_join_on_multiple_columns() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select id, salary from employees join bonuses on employees.id = bonuses.employee_id and salary = bonus",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                vec![
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                    aliased_plan(column(&left, "salary", &accessor), "salary"),
+                ],
+                tab(&left),
+                const_bool(true),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                    aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                ],
+                tab(&right),
+                const_bool(true),
+            ),
+            vec![0, 1],
+            vec![0, 1],
+            vec!["id".into(), "salary".into()],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("id"), "id"),
+            aliased_expr(col("salary"), "salary"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_reference_the_right_join_column_of_an_inner_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select employee_id, bonus from employees join bonuses on id = employee_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                vec![aliased_plan(column(&left, "id", &accessor), "employee_id")],
+                tab(&left),
+                const_bool(true),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                ],
+                tab(&right),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![1],
+            vec!["employee_id".into(), "bonus".into()],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("employee_id"), "employee_id"),
+            aliased_expr(col("bonus"), "bonus"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_select_columns_of_the_same_name_from_both_sides_of_a_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select employees.name as employee_name, bonuses.name as bonus_name from employees join bonuses on id = employee_id where bonuses.name = 'a' order by bonuses.name desc",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                vec![
+                    aliased_plan(column(&left, "name", &accessor), "employee_name"),
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                ],
+                tab(&left),
+                const_bool(true),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "name", &accessor), "bonus_name"),
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                ],
+                tab(&right),
+                equal(column(&right, "name", &accessor), const_varchar("a")),
+            ),
+            vec![1],
+            vec![1],
+            vec!["id".into(), "employee_name".into(), "bonus_name".into()],
+        ),
+        vec![
+            select_expr(&[
+                aliased_expr(col("employee_name"), "employee_name"),
+                aliased_expr(col("bonus_name"), "bonus_name"),
+            ]),
+            orders(&[1], &[false]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_qualify_columns_that_are_not_ambiguous() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select employees.salary, bonuses.employee_id, count(*) as c from employees join bonuses on id = employee_id group by employees.salary, bonuses.employee_id",
+        &accessor,
+    );
+    let unqualified_ast = query_to_provable_ast(
+        &left,
+        "select salary, employee_id, count(*) as c from employees join bonuses on id = employee_id group by salary, employee_id",
+        &accessor,
+    );
+    assert_eq!(ast, unqualified_ast);
+    let ast = query_to_provable_ast(&left, "select employees.salary from employees", &accessor);
+    let unqualified_ast = query_to_provable_ast(&left, "select salary from employees", &accessor);
+    assert_eq!(ast, unqualified_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_invalid_qualified_columns() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    // table that is not part of the query
+    invalid_query_to_provable_ast(&left, "select missing.salary from employees", &accessor);
+    // column that the table does not have
+    invalid_query_to_provable_ast(
+        &left,
+        "select bonuses.salary from employees join bonuses on id = employee_id",
+        &accessor,
+    );
+    // ordering by a qualified column that is not a result column
+    invalid_query_to_provable_ast(
+        &left,
+        "select employees.name from employees join bonuses on id = employee_id order by bonuses.name",
+        &accessor,
+    );
+    // grouping by a different column than the one of the same name that is selected
+    invalid_query_to_provable_ast(
+        &left,
+        "select employees.name, count(*) from employees join bonuses on id = employee_id group by bonuses.name",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_inner_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    // ambiguous column
+    invalid_query_to_provable_ast(
+        &left,
+        "select name from employees join bonuses on id = employee_id",
+        &accessor,
+    );
+    // join columns from the same table
+    invalid_query_to_provable_ast(
+        &left,
+        "select bonus from employees join bonuses on id = salary",
+        &accessor,
+    );
+    // join columns of different types
+    invalid_query_to_provable_ast(
+        &left,
+        "select bonus from employees join bonuses on employees.name = employee_id",
+        &accessor,
+    );
+    // join on multiple columns that can not be combined into a single key
+    let ast = select(
+        query_all(
+            cols_res(&["bonus"]),
+            inner_join(
+                ptab(None, "employees"),
+                ptab(None, "bonuses"),
+                &[("id", "employee_id"), ("employees.name", "bonuses.name")],
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        QueryExpr::try_new(ast, "sxt".into(), &accessor),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // where clause involving both tables
+    invalid_query_to_provable_ast(
+        &left,
+        "select bonus from employees join bonuses on id = employee_id where salary = bonus",
+        &accessor,
+    );
+    // missing table
+    invalid_query_to_provable_ast(
+        &left,
+        "select bonus from employees join missing on id = employee_id",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_left_outer_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select id, bonus from employees left join bonuses on id = employee_id where salary >= 5",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_outer_join(
+            filter(
+                vec![
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                    aliased_plan(column(&left, "salary", &accessor), "salary"),
+                ],
+                tab(&left),
+                gte(column(&left, "salary", &accessor), const_bigint(5)),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                    aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+                ],
+                tab(&right),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![1],
+            vec![
+                "id".into(),
+                "salary".into(),
+                "bonus".into(),
+                "__is_matched".into(),
+            ],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("id"), "id"),
+            aliased_expr(col("bonus"), "bonus"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_right_outer_join_by_swapping_its_sides() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select salary, employee_id from employees right outer join bonuses on employees.id = bonuses.employee_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_outer_join(
+            filter(
+                vec![aliased_plan(
+                    column(&right, "employee_id", &accessor),
+                    "employee_id",
+                )],
+                tab(&right),
+                const_bool(true),
+            ),
+            filter(
+                vec![
+                    aliased_plan(column(&left, "salary", &accessor), "salary"),
+                    aliased_plan(column(&left, "id", &accessor), "id"),
+                ],
+                tab(&left),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![1],
+            vec!["employee_id".into(), "salary".into(), "__is_matched".into()],
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("salary"), "salary"),
+            aliased_expr(col("employee_id"), "employee_id"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_outer_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    // join column of the side whose rows are not preserved
+    invalid_query_to_provable_ast(
+        &left,
+        "select employee_id, bonus from employees left join bonuses on id = employee_id",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &left,
+        "select id, salary from employees right join bonuses on id = employee_id",
+        &accessor,
+    );
+    // where clause on the side whose rows are not preserved
+    invalid_query_to_provable_ast(
+        &left,
+        "select id, bonus from employees left join bonuses on id = employee_id where bonus = 3",
+        &accessor,
+    );
+}
+
+///////////////////////////
+// Union All
+///////////////////////////
+fn union_schema_accessor(tables: &[&TableRef]) -> TestSchemaAccessor {
+    TestSchemaAccessor::new(
+        tables
+            .iter()
+            .map(|&table| {
+                (
+                    table.clone(),
+                    indexmap! {
+                        "id".into() => ColumnType::BigInt,
+                        "amount".into() => ColumnType::Int,
+                        "note".into() => ColumnType::VarChar,
+                    },
+                )
             })
-            .collect::<Vec<_>>();
-        let query_text = format!(
-            "SELECT {} FROM {} GROUP BY {} ORDER BY {}",
-            perm_cols.join(", "),
-            t,
-            group_cols_vec.join(", "),
-            order_cols_vec
-                .iter()
-                .zip(ordering_query_vec.iter())
-                .map(|(c, o)| format!("{c} {o}"))
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
+            .collect(),
+    )
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_all() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let feb = TableRef::new("sxt", "sales_feb");
+    let accessor = union_schema_accessor(&[&jan, &feb]);
+    let ast = query_to_provable_ast(
+        &jan,
+        "select id, amount from sales_jan where amount = 5 union all select id, amount as total from sales_feb",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        union_exec(
+            vec![
+                filter(
+                    cols_expr_plan(&jan, &["id", "amount"], &accessor),
+                    tab(&jan),
+                    equal(column(&jan, "amount", &accessor), const_bigint(5)),
+                ),
+                filter(
+                    vec![
+                        col_expr_plan(&feb, "id", &accessor),
+                        aliased_plan(column(&feb, "amount", &accessor), "total"),
+                    ],
+                    tab(&feb),
+                    const_bool(true),
+                ),
+            ],
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("amount", ColumnType::Int),
+            ],
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_order_and_slice_the_result_of_a_union_all() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let feb = TableRef::new("sxt", "sales_feb");
+    let accessor = union_schema_accessor(&[&jan, &feb]);
+    let inputs = vec![
+        filter(
+            cols_expr_plan(&jan, &["note", "amount"], &accessor),
+            tab(&jan),
+            const_bool(true),
+        ),
+        filter(
+            cols_expr_plan(&feb, &["note", "amount"], &accessor),
+            tab(&feb),
+            const_bool(true),
+        ),
+    ];
+    let schema = vec![
+        column_field("note", ColumnType::VarChar),
+        column_field("amount", ColumnType::Int),
+    ];
+
+    // A provable sort key followed by a limit is proven
+    let ast = query_to_provable_ast(
+        &jan,
+        "select note, amount from sales_jan union all select note, amount from sales_feb order by amount desc limit 10",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(union_exec(inputs.clone(), schema.clone()), vec![(1, false)]),
+            0,
+            Some(10),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Otherwise the combined result is ordered and sliced in postprocessing
+    let ast = query_to_provable_ast(
+        &jan,
+        "select note, amount from sales_jan union all select note, amount from sales_feb order by note limit 10",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        union_exec(inputs, schema),
+        vec![orders(&[0_usize], &[true]), slice(Some(10), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_union_all() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let feb = TableRef::new("sxt", "sales_feb");
+    let accessor = union_schema_accessor(&[&jan, &feb]);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // The inputs have different column types
+    assert!(matches!(
+        try_new("select id, amount from sales_jan union all select id, note from sales_feb"),
+        Err(ConversionError::UnionSchemaMismatch { .. })
+    ));
+    assert!(matches!(
+        try_new("select id, amount from sales_jan union all select id from sales_feb"),
+        Err(ConversionError::UnionSchemaMismatch { .. })
+    ));
+    // An input needs postprocessing
+    assert!(matches!(
+        try_new(
+            "select id, amount from sales_jan union all select id, avg(amount) as amount from sales_feb group by id"
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The result is ordered by a column that is not in it
+    assert!(matches!(
+        try_new("select id from sales_jan union all select id from sales_feb order by amount"),
+        Err(ConversionError::MissingColumnWithoutTable { .. })
+    ));
+}
+
+///////////////////////////
+// Distinct
+///////////////////////////
+#[test]
+fn we_can_convert_an_ast_with_a_select_distinct() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let accessor = union_schema_accessor(&[&jan]);
+    let input = filter(
+        cols_expr_plan(&jan, &["id", "amount"], &accessor),
+        tab(&jan),
+        equal(column(&jan, "amount", &accessor), const_bigint(5)),
+    );
+    let ast = query_to_provable_ast(
+        &jan,
+        "select distinct id, amount from sales_jan where amount = 5",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(distinct_exec(input.clone()), vec![]);
+    assert_eq!(ast, expected_ast);
+
+    // The distinct rows can be ordered and sliced
+    let ast = query_to_provable_ast(
+        &jan,
+        "select distinct id, amount from sales_jan where amount = 5 order by id desc limit 3",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(distinct_exec(input), vec![(0, false)]),
+            0,
+            Some(3),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_as_the_distinct_rows_of_a_union_all() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let feb = TableRef::new("sxt", "sales_feb");
+    let accessor = union_schema_accessor(&[&jan, &feb]);
+    let ast = query_to_provable_ast(
+        &jan,
+        "select id from sales_jan union select id from sales_feb",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        distinct_exec(union_exec(
+            vec![
+                filter(
+                    cols_expr_plan(&jan, &["id"], &accessor),
+                    tab(&jan),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(&feb, &["id"], &accessor),
+                    tab(&feb),
+                    const_bool(true),
+                ),
+            ],
+            vec![column_field("id", ColumnType::BigInt)],
+        )),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_count_of_distinct_values() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let accessor = union_schema_accessor(&[&jan]);
+    let ast = query_to_provable_ast(
+        &jan,
+        "select amount, count(distinct id) as num_ids from sales_jan where id = 3 group by amount",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&jan, &["amount"], &accessor),
+            vec![],
+            "num_ids",
+            tab_with_input(
+                &jan,
+                distinct_exec(filter(
+                    cols_expr_plan(&jan, &["amount", "id"], &accessor),
+                    tab(&jan),
+                    equal(column(&jan, "id", &accessor), const_bigint(3)),
+                )),
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_distinct() {
+    let jan = TableRef::new("sxt", "sales_jan");
+    let feb = TableRef::new("sxt", "sales_feb");
+    let accessor = union_schema_accessor(&[&jan, &feb]);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // Distinct strings are not supported
+    assert!(matches!(
+        try_new("select distinct note from sales_jan"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_new("select note from sales_jan union select note from sales_feb"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_new("select amount, count(distinct note) as c from sales_jan group by amount"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The input needs postprocessing
+    assert!(matches!(
+        try_new("select distinct id, avg(amount) as amount from sales_jan group by id"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The count of distinct values is not the only aggregation
+    assert!(matches!(
+        try_new(
+            "select amount, sum(id) as s, count(distinct id) as c from sales_jan group by amount"
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_new("select count(distinct id) as c, count(*) as n from sales_jan"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::UnsupportedAggregation { .. }
+        })
+    ));
+}
+
+///////////////////////////
+// Derived tables
+///////////////////////////
+fn sales_schema_accessor(sales: &TableRef) -> TestSchemaAccessor {
+    schema_accessor_from_table_ref_with_schema(
+        sales,
+        indexmap! {
+            "store".into() => ColumnType::BigInt,
+            "amount".into() => ColumnType::BigInt,
+        },
+    )
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_filter_over_the_groups_of_a_derived_table() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "select store, total from (select store, sum(amount) as total, count(*) as num from sales group by store) as totals where total >= 100",
+        &accessor,
+    );
+    // The columns of the derived table are columns of a table named after its alias
+    let totals = TableRef::from_names(None, "totals");
+    let totals_accessor = schema_accessor_from_table_ref_with_schema(
+        &totals,
+        indexmap! {
+            "store".into() => ColumnType::BigInt,
+            "total".into() => ColumnType::BigInt,
+            "num".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&totals, &["store", "total"], &totals_accessor),
+            tab_with_input(
+                &totals,
+                group_by(
+                    cols_expr_plan(&sales, &["store"], &accessor),
+                    vec![sum_expr(column(&sales, "amount", &accessor), "total")],
+                    "num",
+                    tab(&sales),
+                    const_bool(true),
+                ),
+            ),
+            gte(
+                column(&totals, "total", &totals_accessor),
+                const_bigint(100),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_group_by_over_a_derived_table_of_a_join() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let ast = query_to_provable_ast(
+        &left,
+        "select salary, count(*) as num from (select salary, bonus from employees join bonuses on employees.id = bonuses.employee_id) as j group by salary",
+        &accessor,
+    );
+    // The result expressions of the join are proven as a projection of it
+    let j = TableRef::from_names(None, "j");
+    let j_accessor = schema_accessor_from_table_ref_with_schema(
+        &j,
+        indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "bonus".into() => ColumnType::BigInt,
+        },
+    );
+    let join = sort_merge_join(
+        filter(
+            vec![
+                aliased_plan(column(&left, "salary", &accessor), "salary"),
+                aliased_plan(column(&left, "id", &accessor), "id"),
+            ],
+            tab(&left),
+            const_bool(true),
+        ),
+        filter(
+            vec![
+                aliased_plan(column(&right, "bonus", &accessor), "bonus"),
+                aliased_plan(column(&right, "employee_id", &accessor), "employee_id"),
+            ],
+            tab(&right),
+            const_bool(true),
+        ),
+        vec![1],
+        vec![1],
+        vec!["id".into(), "salary".into(), "bonus".into()],
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&j, &["salary"], &j_accessor),
+            vec![],
+            "num",
+            tab_with_input(
+                &j,
+                projection(
+                    cols_expr_plan(&j, &["salary", "bonus"], &j_accessor),
+                    tab_with_input(&j, join),
+                ),
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
 
-        let intermediate_ast = SelectStatementParser::new().parse(&query_text).unwrap();
-        let query =
-            QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor)
-                .unwrap();
+#[test]
+fn we_can_convert_an_ast_with_nested_derived_tables_whose_order_is_dropped() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "select * from (select amount from (select store, amount from sales) as s order by amount) as t",
+        &accessor,
+    );
+    let s = TableRef::from_names(None, "s");
+    let t = TableRef::from_names(None, "t");
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["amount"], &sales_schema_accessor(&t)),
+            tab_with_input(
+                &t,
+                filter(
+                    cols_expr_plan(&s, &["amount"], &sales_schema_accessor(&s)),
+                    tab_with_input(
+                        &s,
+                        filter(
+                            cols_expr_plan(&sales, &["store", "amount"], &accessor),
+                            tab(&sales),
+                            const_bool(true),
+                        ),
+                    ),
+                    const_bool(true),
+                ),
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
 
-        let expected_query = QueryExpr::new(
-            filter(perm_col_plans, tab(&t), const_bool(true)),
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_derived_table() {
+    let left = TableRef::new("sxt", "employees");
+    let right = TableRef::new("sxt", "bonuses");
+    let accessor = join_schema_accessor(&left, &right);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // The query of the derived table needs postprocessing
+    assert!(matches!(
+        try_new("select * from (select id, avg(salary) as s from employees group by id) as t"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_new(
+            "select * from (select id, bonus from employees left join bonuses on id = employee_id) as t"
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The derived table does not have the column
+    assert!(matches!(
+        try_new("select salary from (select id from employees) as t"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+}
+
+///////////////////////////
+// Subqueries
+///////////////////////////
+fn allow_list_schema_accessor(accounts: &TableRef, allow_list: &TableRef) -> TestSchemaAccessor {
+    TestSchemaAccessor::new(indexmap! {
+        accounts.clone() => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+            "balance".into() => ColumnType::BigInt,
+        },
+        allow_list.clone() => indexmap! {
+            "account_id".into() => ColumnType::BigInt,
+            "owner".into() => ColumnType::VarChar,
+            "limit_amount".into() => ColumnType::BigInt,
+            "note".into() => ColumnType::BigInt,
+        },
+    })
+    .with_nullable_column(allow_list.clone(), "note".into())
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_in_subquery() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let ast = query_to_provable_ast(
+        &accounts,
+        "select name from accounts where id in (select account_id from allow_list where owner = 'bank')",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&accounts, &["name"], &accessor),
+            tab(&accounts),
+            in_subquery(
+                column(&accounts, "id", &accessor),
+                filter(
+                    cols_expr_plan(&allow_list, &["account_id"], &accessor),
+                    tab(&allow_list),
+                    equal(
+                        column(&allow_list, "owner", &accessor),
+                        const_varchar("bank"),
+                    ),
+                ),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_scalar_subquery_that_may_be_null() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let ast = query_to_provable_ast(
+        &accounts,
+        "select id from accounts where balance > (select max(limit_amount) as m from allow_list)",
+        &accessor,
+    );
+    // The aggregation is projected from a group by that also counts the rows
+    let subquery = TableRef::from_names(None, "__subquery__");
+    let subquery_accessor = schema_accessor_from_table_ref_with_schema(
+        &subquery,
+        indexmap! {
+            "m".into() => ColumnType::BigInt,
+        },
+    );
+    let max_plan = projection(
+        cols_expr_plan(&subquery, &["m"], &subquery_accessor),
+        tab_with_input(
+            &subquery,
+            group_by(
+                vec![],
+                vec![max_expr(
+                    column(&allow_list, "limit_amount", &accessor),
+                    "m",
+                )],
+                "__count__",
+                tab(&allow_list),
+                const_bool(true),
+            ),
+        ),
+    );
+    // The maximum of no rows is NULL, so the comparison only holds where the subquery has a row
+    let presence = projection(
+        vec![aliased_plan(const_bool(true), "__present__")],
+        tab_with_input(&subquery, max_plan.clone()),
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&accounts, &["id"], &accessor),
+            tab(&accounts),
+            nullable_expr(
+                DynProofExpr::try_new_inequality(
+                    column(&accounts, "balance", &accessor),
+                    scalar_subquery(max_plan),
+                    false,
+                )
+                .unwrap(),
+                scalar_subquery(presence),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_scalar_subquery_of_a_count() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let ast = query_to_provable_ast(
+        &accounts,
+        "select id from accounts where balance < (select count(*) as n from allow_list)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&accounts, &["id"], &accessor),
+            tab(&accounts),
+            DynProofExpr::try_new_inequality(
+                column(&accounts, "balance", &accessor),
+                scalar_subquery(group_by(
+                    vec![],
+                    vec![],
+                    "n",
+                    tab(&allow_list),
+                    const_bool(true),
+                )),
+                true,
+            )
+            .unwrap(),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_subquery() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // Subqueries are only supported in WHERE clauses
+    assert!(matches!(
+        try_new("select id, (select count(*) as n from allow_list) as n from accounts"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The subquery has more than one column
+    assert!(matches!(
+        try_new(
+            "select id from accounts where id in (select account_id, limit_amount from allow_list)"
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    // The subquery references a nullable column
+    assert!(matches!(
+        try_new("select id from accounts where id in (select note from allow_list)"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // Strings can not be looked up in a subquery
+    assert!(matches!(
+        try_new("select id from accounts where name in (select owner from allow_list)"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // Correlated subqueries are not supported
+    assert!(matches!(
+        try_new("select id from accounts where id in (select account_id from allow_list where limit_amount = balance)"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+}
+
+///////////////////////////
+// Window functions
+///////////////////////////
+#[test]
+fn we_can_convert_an_ast_with_window_functions() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "select store, amount, row_number() over (partition by store order by amount desc) as rn, lag(amount) over (partition by store order by amount desc) as prev from sales where amount >= 1",
+        &accessor,
+    );
+    // The window functions are columns of a derived table of the window
+    let w = TableRef::from_names(None, "__window__");
+    let w_accessor = schema_accessor_from_table_ref_with_schema(
+        &w,
+        indexmap! {
+            "store".into() => ColumnType::BigInt,
+            "amount".into() => ColumnType::BigInt,
+            "__window_0__".into() => ColumnType::BigInt,
+            "__window_1__".into() => ColumnType::BigInt,
+        },
+    )
+    .with_nullable_column(w.clone(), "__window_1__".into());
+    let expected_ast = QueryExpr::new(
+        filter(
             vec![
-                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
-                orders(&[2_usize, 3, 0, 1], &ordering_vec),
+                aliased_plan(column(&w, "store", &w_accessor), "store"),
+                aliased_plan(column(&w, "amount", &w_accessor), "amount"),
+                aliased_plan(column(&w, "__window_0__", &w_accessor), "rn"),
+                aliased_plan(nullable_column(&w, "__window_1__", &w_accessor), "prev"),
             ],
-        );
-        assert_eq!(query, expected_query);
-    }
+            tab_with_input(
+                &w,
+                window_exec(
+                    filter(
+                        cols_expr_plan(&sales, &["store", "amount"], &accessor),
+                        tab(&sales),
+                        gte(column(&sales, "amount", &accessor), const_bigint(1)),
+                    ),
+                    vec![0],
+                    vec![(1, false)],
+                    vec![
+                        (WindowFunction::RowNumber, "__window_0__"),
+                        (WindowFunction::Lag(1), "__window_1__"),
+                    ],
+                ),
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_window_functions_over_different_windows_and_expressions() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "select store, rank() over (order by store) as r, sum(amount * 2) over (partition by store) as total from sales",
+        &accessor,
+    );
+    // The arguments that are not columns are computed by the input
+    let w = TableRef::from_names(None, "__window__");
+    let w_accessor = schema_accessor_from_table_ref_with_schema(
+        &w,
+        indexmap! {
+            "store".into() => ColumnType::BigInt,
+            "__window_argument_1__".into() => ColumnType::BigInt,
+            "__window_0__".into() => ColumnType::BigInt,
+            "__window_1__".into() => ColumnType::BigInt,
+        },
+    );
+    let input = filter(
+        vec![
+            aliased_plan(column(&sales, "store", &accessor), "store"),
+            aliased_plan(
+                multiply(column(&sales, "amount", &accessor), const_bigint(2)),
+                "__window_argument_1__",
+            ),
+        ],
+        tab(&sales),
+        const_bool(true),
+    );
+    // Each window is evaluated by its own `WindowExec`
+    let windows = window_exec(
+        window_exec(
+            input,
+            vec![],
+            vec![(0, true)],
+            vec![(WindowFunction::Rank, "__window_0__")],
+        ),
+        vec![0],
+        vec![],
+        vec![(WindowFunction::Sum(1), "__window_1__")],
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(column(&w, "store", &w_accessor), "store"),
+                aliased_plan(column(&w, "__window_0__", &w_accessor), "r"),
+                aliased_plan(column(&w, "__window_1__", &w_accessor), "total"),
+            ],
+            tab_with_input(&w, windows),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_order_and_slice_the_result_of_window_functions_over_all_columns() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "select *, sum(amount) over (order by amount) as running from sales order by running desc limit 2",
+        &accessor,
+    );
+    let w = TableRef::from_names(None, "__window__");
+    let w_accessor = schema_accessor_from_table_ref_with_schema(
+        &w,
+        indexmap! {
+            "store".into() => ColumnType::BigInt,
+            "amount".into() => ColumnType::BigInt,
+            "__window_0__".into() => ColumnType::BigInt,
+        },
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                filter(
+                    vec![
+                        aliased_plan(column(&w, "store", &w_accessor), "store"),
+                        aliased_plan(column(&w, "amount", &w_accessor), "amount"),
+                        aliased_plan(column(&w, "__window_0__", &w_accessor), "running"),
+                    ],
+                    tab_with_input(
+                        &w,
+                        window_exec(
+                            filter(
+                                cols_expr_plan(&sales, &["store", "amount"], &accessor),
+                                tab(&sales),
+                                const_bool(true),
+                            ),
+                            vec![],
+                            vec![(1, true)],
+                            vec![(WindowFunction::Sum(1), "__window_0__")],
+                        ),
+                    ),
+                    const_bool(true),
+                ),
+                vec![(2, false)],
+            ),
+            0,
+            Some(2),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_unprovable_window_functions() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // Window functions are only supported in the SELECT list of queries without GROUP BY
+    assert!(matches!(
+        try_new("select id, row_number() over (order by id) as rn from accounts group by id"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_new("select id from accounts where row_number() over (order by id) = 1"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // Strings can not partition the rows
+    assert!(matches!(
+        try_new("select id, rank() over (partition by name order by id) as r from accounts"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // Strings can not be summed
+    assert!(matches!(
+        try_new("select id, sum(name) over (order by id) as s from accounts"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The window references a nullable column
+    assert!(matches!(
+        try_new("select account_id, lag(note) over (order by account_id) as prev from allow_list"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+}
+
+///////////////////////////
+// Common table expressions
+///////////////////////////
+#[test]
+fn we_can_convert_an_ast_with_a_common_table_expression_like_a_derived_table() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let ast = query_to_provable_ast(
+        &sales,
+        "with totals as (select store, sum(amount) as total, count(*) as num from sales group by store) select store, total from totals where total >= 100",
+        &accessor,
+    );
+    // Each reference to a common table expression is planned as a derived table named after it
+    let expected_ast = query_to_provable_ast(
+        &sales,
+        "select store, total from (select store, sum(amount) as total, count(*) as num from sales group by store) as totals where total >= 100",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+    assert_eq!(
+        ast.proof_expr().get_table_references(),
+        IndexSet::from_iter([sales])
+    );
+}
+
+#[test]
+fn we_can_convert_an_ast_with_common_table_expressions_that_refer_to_each_other() {
+    let accounts = TableRef::new("sxt", "accounts");
+    let allow_list = TableRef::new("sxt", "allow_list");
+    let accessor = allow_list_schema_accessor(&accounts, &allow_list);
+    let ast = query_to_provable_ast(
+        &accounts,
+        "with allowed as (select account_id from allow_list), rich as (select id, balance from accounts where id in (select account_id from allowed)) select id from rich where balance > (select min(balance) from rich) order by id",
+        &accessor,
+    );
+    let expected_ast = query_to_provable_ast(
+        &accounts,
+        "select id from (select id, balance from accounts where id in (select account_id from (select account_id from allow_list) as allowed)) as rich where balance > (select min(balance) from (select id, balance from accounts where id in (select account_id from (select account_id from allow_list) as allowed)) as rich) order by id",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+    assert_eq!(
+        ast.proof_expr().get_table_references(),
+        IndexSet::from_iter([accounts, allow_list])
+    );
+}
+
+#[test]
+fn we_can_convert_an_ast_whose_common_table_expressions_are_shadowed_or_refer_to_real_tables() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    // A common table expression refers to the real table of its own name,
+    // a nested one shadows it, and a table with a schema always is a real table
+    let ast = query_to_provable_ast(
+        &sales,
+        "with sales as (select store, amount from sales where amount > 0) select store from (with sales as (select store from sales) select store from sales) as s where store in (select store from sxt.sales)",
+        &accessor,
+    );
+    let expected_ast = query_to_provable_ast(
+        &sales,
+        "select store from (select store from (select store from (select store, amount from sxt.sales where amount > 0) as sales) as sales) as s where store in (select store from sxt.sales)",
+        &accessor,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unprovable_common_table_expression() {
+    let sales = TableRef::new("sxt", "sales");
+    let accessor = sales_schema_accessor(&sales);
+    let try_new = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    // The query of the common table expression needs postprocessing
+    assert!(matches!(
+        try_new(
+            "with t as (select store, avg(amount) as a from sales group by store) select * from t"
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    // The common table expression does not have the column
+    assert!(matches!(
+        try_new("with t as (select store from sales) select amount from t"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    // A common table expression can not refer to the later ones
+    assert!(try_new(
+        "with t as (select store from u), u as (select store from sales) select * from t"
+    )
+    .is_err());
 }
 
 /// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
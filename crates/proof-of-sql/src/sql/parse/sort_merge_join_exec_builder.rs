@@ -0,0 +1,320 @@
+use super::{
+    where_expr_builder::WhereExprBuilder, ConversionError, ConversionResult, QueryContext,
+};
+use crate::{
+    base::{
+        database::{join_util::is_combinable_join_key, ColumnRef, LiteralValue, TableRef},
+        map::{IndexMap, IndexSet},
+    },
+    sql::{
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{DynProofPlan, FilterExec, SortMergeJoinExec, SortMergeOuterJoinExec},
+    },
+};
+use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec, vec::Vec};
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, Expression};
+use sqlparser::ast::Ident;
+
+/// Name of the result column of an outer join which indicates whether a row has a match
+const MATCH_INDICATOR_IDENT: &str = "__is_matched";
+
+/// Builder that plans an inner join between two tables as a [`SortMergeJoinExec`]
+/// and an outer join as a [`SortMergeOuterJoinExec`].
+///
+/// Each side of the join is a [`FilterExec`] over its table which returns
+/// the columns of the table referenced by the query and which applies
+/// the conjuncts of the WHERE clause that only reference that table.
+pub struct SortMergeJoinExecBuilder<'a> {
+    context: &'a QueryContext,
+}
+
+// Public interface
+impl<'a> SortMergeJoinExecBuilder<'a> {
+    pub fn new(context: &'a QueryContext) -> Self {
+        Self { context }
+    }
+
+    pub fn build(self) -> ConversionResult<DynProofPlan> {
+        let left_table_ref = self.context.get_table_ref();
+        let right_table_ref =
+            self.context
+                .get_join_table_ref()
+                .ok_or(ConversionError::InvalidExpression {
+                    expression: "QueryContext has no join".to_owned(),
+                })?;
+        let (left_join_columns, right_join_columns): (Vec<_>, Vec<_>) =
+            self.context.get_join_conditions().iter().cloned().unzip();
+        let join_column_types = left_join_columns
+            .iter()
+            .map(|column_ref| *column_ref.column_type())
+            .collect::<Vec<_>>();
+        if !is_combinable_join_key(&join_column_types) {
+            return Err(ConversionError::Unprovable {
+                error: "Joins on more than one column are only supported on boolean, integer and timestamp columns that fit in 248 bits".to_string(),
+            });
+        }
+        // The sides of a join return the values of their columns only
+        if !self.context.get_nullable_columns().is_empty() {
+            return Err(ConversionError::Unprovable {
+                error: "joins on tables with nullable columns are not supported".to_string(),
+            });
+        }
+        let column_mapping = self.context.get_column_mapping();
+        let (left_where_expr, right_where_expr) = self.split_where_expr(&column_mapping)?;
+        let (left, left_join_column_indexes, left_idents) = build_join_input(
+            left_table_ref,
+            &column_mapping,
+            &left_join_columns,
+            left_where_expr,
+            self.context.get_character_counts(),
+        )?;
+        let (right, right_join_column_indexes, right_idents) = build_join_input(
+            right_table_ref,
+            &column_mapping,
+            &right_join_columns,
+            right_where_expr,
+            self.context.get_character_counts(),
+        )?;
+        // The join columns come first, followed by the remaining left and right columns
+        let mut result_idents: Vec<Ident> = left_join_column_indexes
+            .iter()
+            .map(|&index| left_idents[index].clone())
+            .chain(
+                left_idents
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(index, _)| !left_join_column_indexes.contains(index))
+                    .map(|(_, ident)| ident),
+            )
+            .chain(
+                right_idents
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(index, _)| !right_join_column_indexes.contains(index))
+                    .map(|(_, ident)| ident),
+            )
+            .collect();
+        if self.context.is_outer_join() {
+            result_idents.push(MATCH_INDICATOR_IDENT.into());
+            Ok(DynProofPlan::SortMergeOuterJoin(
+                SortMergeOuterJoinExec::new(
+                    Box::new(left),
+                    Box::new(right),
+                    left_join_column_indexes,
+                    right_join_column_indexes,
+                    result_idents,
+                ),
+            ))
+        } else {
+            Ok(DynProofPlan::SortMergeJoin(SortMergeJoinExec::new(
+                Box::new(left),
+                Box::new(right),
+                left_join_column_indexes,
+                right_join_column_indexes,
+                result_idents,
+            )))
+        }
+    }
+}
+
+// Private interface
+impl SortMergeJoinExecBuilder<'_> {
+    /// Splits the WHERE clause into the conjuncts that apply to the left and right table respectively.
+    ///
+    /// Conjuncts without any column references are applied to the left table.
+    /// In an outer join, conjuncts on the right table are rejected,
+    /// since applying them before the join would not remove the rows of the left table they exclude.
+    #[allow(clippy::type_complexity)]
+    fn split_where_expr(
+        &self,
+        column_mapping: &IndexMap<Ident, ColumnRef>,
+    ) -> ConversionResult<(Option<Box<Expression>>, Option<Box<Expression>>)> {
+        let left_table_ref = self.context.get_table_ref();
+        let mut conjuncts = Vec::new();
+        if let Some(where_expr) = self.context.get_where_expr() {
+            push_conjuncts(*where_expr.clone(), &mut conjuncts);
+        }
+        let mut left_where_expr = None;
+        let mut right_where_expr = None;
+        for conjunct in conjuncts {
+            let mut identifiers = IndexSet::default();
+            push_column_identifiers(&conjunct, &mut identifiers);
+            let is_left = identifiers
+                .iter()
+                .map(|ident| {
+                    column_mapping
+                        .get(ident)
+                        .map(|column_ref| column_ref.table_ref() == *left_table_ref)
+                        .ok_or_else(|| ConversionError::MissingColumnWithoutTable {
+                            identifier: Box::new(ident.clone()),
+                        })
+                })
+                .collect::<ConversionResult<IndexSet<bool>>>()?;
+            let side_where_expr =
+                match (is_left.contains(&true), is_left.contains(&false)) {
+                    (_, false) => &mut left_where_expr,
+                    (false, true) if self.context.is_outer_join() => return Err(ConversionError::Unprovable {
+                        error:
+                            "WHERE conditions on the side of an outer join whose rows are not preserved are not supported yet"
+                                .to_string(),
+                    }),
+                    (false, true) => &mut right_where_expr,
+                    (true, true) => return Err(ConversionError::Unprovable {
+                        error:
+                            "WHERE conditions involving both sides of a join are not supported yet"
+                                .to_string(),
+                    }),
+                };
+            *side_where_expr = Some(match side_where_expr.take() {
+                Some(expr) => Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: expr,
+                    right: Box::new(conjunct),
+                }),
+                None => Box::new(conjunct),
+            });
+        }
+        Ok((left_where_expr, right_where_expr))
+    }
+}
+
+/// Builds the [`FilterExec`] for one side of the join.
+///
+/// Returns the plan, the indexes of the join columns in its result and the idents of its result columns.
+fn build_join_input(
+    table_ref: &TableRef,
+    column_mapping: &IndexMap<Ident, ColumnRef>,
+    join_columns: &[ColumnRef],
+    where_expr: Option<Box<Expression>>,
+    character_counts: &IndexMap<ColumnRef, usize>,
+) -> ConversionResult<(DynProofPlan, Vec<usize>, Vec<Ident>)> {
+    let table_column_mapping = column_mapping
+        .iter()
+        .filter(|(_, column_ref)| column_ref.table_ref() == *table_ref)
+        .map(|(ident, column_ref)| (ident.clone(), column_ref.clone()))
+        .collect::<IndexMap<_, _>>();
+    let mut aliased_results = table_column_mapping
+        .iter()
+        .map(|(ident, column_ref)| AliasedDynProofExpr {
+            expr: DynProofExpr::new_column(column_ref.clone()),
+            alias: ident.clone(),
+        })
+        .collect::<Vec<_>>();
+    let join_column_indexes = join_columns
+        .iter()
+        .map(|join_column| {
+            let join_column_expr = DynProofExpr::new_column(join_column.clone());
+            aliased_results
+                .iter()
+                .position(|aliased_result| aliased_result.expr == join_column_expr)
+                .unwrap_or_else(|| {
+                    aliased_results.push(AliasedDynProofExpr {
+                        expr: join_column_expr,
+                        alias: join_column.column_id(),
+                    });
+                    aliased_results.len() - 1
+                })
+        })
+        .collect();
+    let where_clause = WhereExprBuilder::new(&table_column_mapping)
+        .with_character_counts(character_counts)
+        .build(where_expr)?
+        .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+    let idents = aliased_results
+        .iter()
+        .map(|aliased_result| aliased_result.alias.clone())
+        .collect();
+    Ok((
+        DynProofPlan::Filter(FilterExec::new(
+            aliased_results,
+            TableExpr::new(table_ref.clone()),
+            where_clause,
+        )),
+        join_column_indexes,
+        idents,
+    ))
+}
+
+/// Flattens a conjunction into its conjuncts
+fn push_conjuncts(expr: Expression, conjuncts: &mut Vec<Expression>) {
+    match expr {
+        Expression::Binary {
+            op: BinaryOperator::And,
+            left,
+            right,
+        } => {
+            push_conjuncts(*left, conjuncts);
+            push_conjuncts(*right, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
+
+/// Collects the column identifiers referenced by an expression
+fn push_column_identifiers(expr: &Expression, identifiers: &mut IndexSet<Ident>) {
+    match expr {
+        Expression::Column(identifier) => {
+            identifiers.insert((*identifier).into());
+        }
+        // Qualified columns are resolved to columns by `QueryContextBuilder`
+        Expression::QualifiedColumn { .. } => {}
+        Expression::Unary { expr, .. }
+        | Expression::Aggregation {
+            expr, filter: None, ..
+        }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Like { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::InSubquery { expr, .. } => {
+            push_column_identifiers(expr, identifiers);
+        }
+        Expression::Binary { left, right, .. }
+        | Expression::Aggregation {
+            expr: left,
+            filter: Some(right),
+            ..
+        } => {
+            push_column_identifiers(left, identifiers);
+            push_column_identifiers(right, identifiers);
+        }
+        Expression::InList { expr, list } => {
+            push_column_identifiers(expr, identifiers);
+            for candidate in list {
+                push_column_identifiers(candidate, identifiers);
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            push_column_identifiers(expr, identifiers);
+            push_column_identifiers(low, identifiers);
+            push_column_identifiers(high, identifiers);
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            for (condition, then) in when_then {
+                push_column_identifiers(condition, identifiers);
+                push_column_identifiers(then, identifiers);
+            }
+            if let Some(else_expr) = else_expr {
+                push_column_identifiers(else_expr, identifiers);
+            }
+        }
+        Expression::Window {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            if let Some(argument) = function.argument() {
+                push_column_identifiers(argument, identifiers);
+            }
+            identifiers.extend(partition_by.iter().map(|&identifier| identifier.into()));
+            identifiers.extend(order_by.iter().map(|order_by| order_by.expr.into()));
+        }
+        // The columns of a subquery are resolved against its own tables
+        Expression::Literal(_) | Expression::Wildcard | Expression::ScalarSubquery(_) => {}
+    }
+}
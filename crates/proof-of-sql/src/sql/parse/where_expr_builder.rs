@@ -2,12 +2,15 @@ use super::{ConversionError, DynProofExprBuilder};
 use crate::{
     base::{
         database::{ColumnRef, ColumnType},
-        map::IndexMap,
+        map::{IndexMap, IndexSet},
+    },
+    sql::{
+        proof_exprs::{DynProofExpr, ProofExpr},
+        proof_plans::DynProofPlan,
     },
-    sql::proof_exprs::{DynProofExpr, ProofExpr},
 };
 use alloc::boxed::Box;
-use proof_of_sql_parser::intermediate_ast::Expression;
+use proof_of_sql_parser::{intermediate_ast::Expression, SelectStatement};
 use sqlparser::ast::Ident;
 
 /// Builder that enables building a `proof_of_sql::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
@@ -22,6 +25,30 @@ impl<'a> WhereExprBuilder<'a> {
             builder: DynProofExprBuilder::new(column_mapping),
         }
     }
+    /// Sets the columns of the mapping that are nullable, see [`DynProofExprBuilder::with_nullable_columns`].
+    #[must_use]
+    pub fn with_nullable_columns(mut self, nullable_columns: &'a IndexSet<ColumnRef>) -> Self {
+        self.builder = self.builder.with_nullable_columns(nullable_columns);
+        self
+    }
+    /// Sets the committed characters of the columns of the mapping, see [`DynProofExprBuilder::with_character_counts`].
+    #[must_use]
+    pub fn with_character_counts(
+        mut self,
+        character_counts: &'a IndexMap<ColumnRef, usize>,
+    ) -> Self {
+        self.builder = self.builder.with_character_counts(character_counts);
+        self
+    }
+    /// Sets the plans of the subqueries that the where clause may contain.
+    #[must_use]
+    pub fn with_subquery_plans(
+        mut self,
+        subquery_plans: &'a IndexMap<SelectStatement, DynProofPlan>,
+    ) -> Self {
+        self.builder = self.builder.with_subquery_plans(subquery_plans);
+        self
+    }
     /// Builds a `proof_of_sql::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
     /// intended to be used as the where clause in a filter expression or group by expression.
     pub fn build(
@@ -0,0 +1,67 @@
+use super::{PostprocessingError, PostprocessingResult, PostprocessingStep};
+use crate::base::{
+    database::{OwnedColumn, OwnedTable},
+    map::IndexMap,
+    scalar::Scalar,
+};
+use alloc::{string::ToString, vec::Vec};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// The average expression used to turn columns of sums into columns of averages
+///
+/// Each of the columns is divided by the count column, and the results are rounded
+/// half away from zero. This allows averages to be derived from the sums and counts of a proven `GROUP BY`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AveragePostprocessing {
+    /// The identifiers of the columns of sums which are replaced by their averages
+    sum_identifiers: Vec<Ident>,
+    /// The identifier of the column with the number of values of each sum
+    count_identifier: Ident,
+}
+
+impl AveragePostprocessing {
+    /// Create a new `AveragePostprocessing` node.
+    #[must_use]
+    pub fn new(sum_identifiers: Vec<Ident>, count_identifier: Ident) -> Self {
+        Self {
+            sum_identifiers,
+            count_identifier,
+        }
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for AveragePostprocessing {
+    /// Apply the average transformation to the given `OwnedTable`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let counts = match owned_table.inner_table().get(&self.count_identifier) {
+            Some(OwnedColumn::BigInt(counts)) => counts.clone(),
+            _ => {
+                return Err(PostprocessingError::ColumnNotFound {
+                    column: self.count_identifier.to_string(),
+                })
+            }
+        };
+        for sum_identifier in &self.sum_identifiers {
+            if !owned_table.inner_table().contains_key(sum_identifier) {
+                return Err(PostprocessingError::ColumnNotFound {
+                    column: sum_identifier.to_string(),
+                });
+            }
+        }
+        let cols: IndexMap<Ident, OwnedColumn<S>> = owned_table
+            .into_inner()
+            .into_iter()
+            .map(
+                |(identifier, column)| -> PostprocessingResult<(Ident, OwnedColumn<S>)> {
+                    if self.sum_identifiers.contains(&identifier) {
+                        Ok((identifier, column.try_average(&counts)?))
+                    } else {
+                        Ok((identifier, column))
+                    }
+                },
+            )
+            .collect::<PostprocessingResult<_>>()?;
+        Ok(OwnedTable::try_new(cols)?)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{
+        apply_postprocessing_steps, test_utility::*, OwnedTablePostprocessing, PostprocessingError,
+    },
+};
+
+#[test]
+fn we_can_average_owned_table_columns_of_sums() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        varchar("a", ["x", "y", "z"]),
+        bigint("sum_b", [7_i64, -7, 0]),
+        decimal75("sum_c", 10, 2, [15, 1, -3]),
+        bigint("cnt", [2_i64, 3, 4]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [average(&["sum_b", "sum_c"], "cnt")];
+    let expected_table = owned_table([
+        varchar("a", ["x", "y", "z"]),
+        decimal75("sum_b", 25, 6, [3_500_000, -2_333_333, 0]),
+        decimal75("sum_c", 14, 6, [75_000, 3_333, -7_500]),
+        bigint("cnt", [2_i64, 3, 4]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_average_an_empty_owned_table() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([int("sum_a", [0_i32; 0]), bigint("cnt", [0_i64; 0])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [average(&["sum_a"], "cnt")];
+    let expected_table = owned_table([
+        decimal75("sum_a", 16, 6, [0_i64; 0]),
+        bigint("cnt", [0_i64; 0]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_cannot_average_missing_or_invalid_owned_table_columns() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        varchar("a", ["x"]),
+        bigint("sum_b", [7_i64]),
+        bigint("cnt", [2_i64]),
+    ]);
+    assert!(matches!(
+        apply_postprocessing_steps(table.clone(), &[average(&["sum_c"], "cnt")]),
+        Err(PostprocessingError::ColumnNotFound { .. })
+    ));
+    assert!(matches!(
+        apply_postprocessing_steps(table.clone(), &[average(&["sum_b"], "count")]),
+        Err(PostprocessingError::ColumnNotFound { .. })
+    ));
+    assert!(matches!(
+        apply_postprocessing_steps(table, &[average(&["a"], "cnt")]),
+        Err(PostprocessingError::ColumnOperationError { .. })
+    ));
+}
@@ -1,4 +1,6 @@
+use crate::base::database::ColumnType;
 use alloc::string::String;
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
 use snafu::Snafu;
 use sqlparser::ast::Ident;
 
@@ -59,12 +61,30 @@ pub enum PostprocessingError {
         /// The underlying source error
         source: crate::base::database::OwnedColumnError,
     },
+    /// Errors in operations on columns
+    #[snafu(transparent)]
+    ColumnOperationError {
+        /// The underlying source error
+        source: crate::base::database::ColumnOperationError,
+    },
     /// Nested aggregation in `GROUP BY` clause
     #[snafu(display("Nested aggregation in `GROUP BY` clause: {error}"))]
     NestedAggregationInGroupByClause {
         /// The nested aggregation error
         error: String,
     },
+    /// `HAVING` clause that is not a predicate
+    #[snafu(display("HAVING clause must be a boolean expression, but is of type {datatype}"))]
+    NonbooleanHavingClause {
+        /// The type of the `HAVING` clause
+        datatype: ColumnType,
+    },
+    /// Aggregation that is only supported by provable queries
+    #[snafu(display("Aggregation operator {op} is not supported in postprocessing"))]
+    UnsupportedAggregation {
+        /// The unsupported aggregation operator
+        op: AggregationOperator,
+    },
 }
 
 /// Result type for postprocessing
@@ -1,6 +1,9 @@
 use super::{PostprocessingError, PostprocessingResult, PostprocessingStep};
 use crate::base::{
-    database::{group_by_util::aggregate_columns, Column, OwnedColumn, OwnedTable},
+    database::{
+        filter_util::filter_columns, group_by_util::aggregate_columns, try_average_decimal_column,
+        Column, ColumnType, OwnedColumn, OwnedTable,
+    },
     map::{indexmap, IndexMap, IndexSet},
     scalar::Scalar,
 };
@@ -8,7 +11,7 @@ use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
 use bumpalo::Bump;
 use itertools::{izip, Itertools};
 use proof_of_sql_parser::{
-    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression},
+    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression, Literal},
     Identifier,
 };
 use serde::{Deserialize, Serialize};
@@ -25,6 +28,9 @@ pub struct GroupByPostprocessing {
 
     /// A list of aggregation expressions
     aggregation_exprs: Vec<(AggregationOperator, Expression, Ident)>,
+
+    /// The `HAVING` clause, which exclusively uses identifiers in the group by clause or results of aggregation expressions
+    having_expr: Option<Expression>,
 }
 
 /// Check whether multiple layers of aggregation exist within the same GROUP BY clause
@@ -34,29 +40,120 @@ pub struct GroupByPostprocessing {
 /// Otherwise we need two layers of aggregation functions to be nested.
 fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => false,
-        Expression::Aggregation { expr, .. } => is_agg || contains_nested_aggregation(expr, true),
+        // Aggregations inside a subquery belong to the subquery,
+        // and window functions are evaluated before the rows are grouped
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::ScalarSubquery(_)
+        | Expression::Window { .. } => false,
+        Expression::Aggregation { expr, filter, .. } => {
+            is_agg
+                || contains_nested_aggregation(expr, true)
+                || filter
+                    .as_ref()
+                    .is_some_and(|filter| contains_nested_aggregation(filter, true))
+        }
         Expression::Binary { left, right, .. } => {
             contains_nested_aggregation(left, is_agg) || contains_nested_aggregation(right, is_agg)
         }
-        Expression::Unary { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Like { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::InSubquery { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::InList { expr, list } => {
+            contains_nested_aggregation(expr, is_agg)
+                || list
+                    .iter()
+                    .any(|candidate| contains_nested_aggregation(candidate, is_agg))
+        }
+        Expression::Between { expr, low, high } => [expr, low, high]
+            .into_iter()
+            .any(|expr| contains_nested_aggregation(expr, is_agg)),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            when_then.iter().any(|(condition, then)| {
+                contains_nested_aggregation(condition, is_agg)
+                    || contains_nested_aggregation(then, is_agg)
+            }) || else_expr
+                .as_ref()
+                .is_some_and(|else_expr| contains_nested_aggregation(else_expr, is_agg))
+        }
     }
 }
 
 /// Get identifiers NOT in aggregate functions
 fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
     match expr {
-        Expression::Column(identifier) => IndexSet::from_iter([(*identifier).into()]),
-        Expression::Literal(_) | Expression::Aggregation { .. } | Expression::Wildcard => {
-            IndexSet::default()
-        }
+        Expression::Column(identifier)
+        | Expression::QualifiedColumn {
+            column: identifier, ..
+        } => IndexSet::from_iter([(*identifier).into()]),
+        Expression::Literal(_)
+        | Expression::Aggregation { .. }
+        | Expression::Wildcard
+        | Expression::ScalarSubquery(_) => IndexSet::default(),
         Expression::Binary { left, right, .. } => {
             let mut left_identifiers = get_free_identifiers_from_expr(left);
             let right_identifiers = get_free_identifiers_from_expr(right);
             left_identifiers.extend(right_identifiers);
             left_identifiers
         }
-        Expression::Unary { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Like { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::InSubquery { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::InList { expr, list } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            for candidate in list {
+                identifiers.extend(get_free_identifiers_from_expr(candidate));
+            }
+            identifiers
+        }
+        Expression::Between { expr, low, high } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            identifiers.extend(get_free_identifiers_from_expr(low));
+            identifiers.extend(get_free_identifiers_from_expr(high));
+            identifiers
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            let mut identifiers = IndexSet::default();
+            for (condition, then) in when_then {
+                identifiers.extend(get_free_identifiers_from_expr(condition));
+                identifiers.extend(get_free_identifiers_from_expr(then));
+            }
+            if let Some(else_expr) = else_expr {
+                identifiers.extend(get_free_identifiers_from_expr(else_expr));
+            }
+            identifiers
+        }
+        Expression::Window {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            let mut identifiers = function
+                .argument()
+                .map(get_free_identifiers_from_expr)
+                .unwrap_or_default();
+            identifiers.extend(partition_by.iter().map(|&identifier| identifier.into()));
+            identifiers.extend(order_by.iter().map(|order_by| order_by.expr.into()));
+            identifiers
+        }
     }
 }
 
@@ -69,13 +166,34 @@ fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
 ///
 /// Will panic if the key for an aggregation expression cannot be parsed as a valid identifier
 /// or if there are issues retrieving an identifier from the map.
+#[allow(clippy::too_many_lines)]
 fn get_aggregate_and_remainder_expressions(
     expr: Expression,
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Ident>,
 ) -> Result<Expression, PostprocessingError> {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => Ok(expr),
-        Expression::Aggregation { op, expr } => {
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::ScalarSubquery(_)
+        | Expression::Window { .. } => Ok(expr),
+        // Distinct values are only counted by provable queries
+        Expression::Aggregation {
+            op: op @ AggregationOperator::CountDistinct,
+            ..
+        } => Err(PostprocessingError::UnsupportedAggregation { op }),
+        // Filtered aggregations are only supported by provable queries
+        Expression::Aggregation {
+            op,
+            filter: Some(_),
+            ..
+        } => Err(PostprocessingError::UnsupportedAggregation { op }),
+        Expression::Aggregation {
+            op,
+            expr,
+            filter: None,
+        } => {
             let key = (op, (*expr));
             if let Some(ident) = aggregation_expr_map.get(&key) {
                 let identifier = Identifier::try_from(ident.clone()).map_err(|e| {
@@ -118,34 +236,117 @@ fn get_aggregate_and_remainder_expressions(
                 expr: Box::new(remainder?),
             })
         }
+        Expression::IsNull(expr) => Ok(Expression::IsNull(Box::new(
+            get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map)?,
+        ))),
+        Expression::IsNotNull(expr) => Ok(Expression::IsNotNull(Box::new(
+            get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map)?,
+        ))),
+        Expression::Like { expr, pattern } => Ok(Expression::Like {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            pattern,
+        }),
+        Expression::Extract { field, expr } => Ok(Expression::Extract {
+            field,
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+        }),
+        Expression::DateTrunc { field, expr } => Ok(Expression::DateTrunc {
+            field,
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+        }),
+        Expression::AddInterval { expr, interval } => Ok(Expression::AddInterval {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            interval,
+        }),
+        Expression::InList { expr, list } => Ok(Expression::InList {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            list: list
+                .into_iter()
+                .map(|candidate| {
+                    get_aggregate_and_remainder_expressions(candidate, aggregation_expr_map)
+                })
+                .collect::<Result<_, _>>()?,
+        }),
+        Expression::InSubquery { expr, subquery } => Ok(Expression::InSubquery {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            subquery,
+        }),
+        Expression::Between { expr, low, high } => Ok(Expression::Between {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            low: Box::new(get_aggregate_and_remainder_expressions(
+                *low,
+                aggregation_expr_map,
+            )?),
+            high: Box::new(get_aggregate_and_remainder_expressions(
+                *high,
+                aggregation_expr_map,
+            )?),
+        }),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => Ok(Expression::Case {
+            when_then: when_then
+                .into_iter()
+                .map(|(condition, then)| {
+                    Ok((
+                        get_aggregate_and_remainder_expressions(condition, aggregation_expr_map)?,
+                        get_aggregate_and_remainder_expressions(then, aggregation_expr_map)?,
+                    ))
+                })
+                .collect::<Result<_, PostprocessingError>>()?,
+            else_expr: else_expr
+                .map(|else_expr| {
+                    get_aggregate_and_remainder_expressions(*else_expr, aggregation_expr_map)
+                        .map(Box::new)
+                })
+                .transpose()?,
+        }),
     }
 }
 
-/// Given an `AliasedResultExpr`, check if it is legitimate and if so grab the relevant aggregation expression
+/// Given an expression, check if it is legitimate and if so grab the relevant aggregation expression
 /// # Panics
 ///
 /// Will panic if there is an issue retrieving the first element from the difference of free identifiers and group-by identifiers, indicating a logical inconsistency in the identifiers.
 fn check_and_get_aggregation_and_remainder(
-    expr: AliasedResultExpr,
+    expr: Expression,
     group_by_identifiers: &[Ident],
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Ident>,
-) -> PostprocessingResult<AliasedResultExpr> {
-    let free_identifiers = get_free_identifiers_from_expr(&expr.expr);
+) -> PostprocessingResult<Expression> {
+    let free_identifiers = get_free_identifiers_from_expr(&expr);
     let group_by_identifier_set = group_by_identifiers
         .iter()
         .cloned()
         .collect::<IndexSet<_>>();
-    if contains_nested_aggregation(&expr.expr, false) {
+    if contains_nested_aggregation(&expr, false) {
         return Err(PostprocessingError::NestedAggregationInGroupByClause {
-            error: format!("Nested aggregations found {:?}", expr.expr),
+            error: format!("Nested aggregations found {expr:?}"),
         });
     }
     if free_identifiers.is_subset(&group_by_identifier_set) {
-        let remainder = get_aggregate_and_remainder_expressions(*expr.expr, aggregation_expr_map);
-        Ok(AliasedResultExpr {
-            alias: expr.alias,
-            expr: Box::new(remainder?),
-        })
+        get_aggregate_and_remainder_expressions(expr, aggregation_expr_map)
     } else {
         let diff = free_identifiers
             .difference(&group_by_identifier_set)
@@ -164,6 +365,15 @@ impl GroupByPostprocessing {
     pub fn try_new(
         by_ids: Vec<Ident>,
         aliased_exprs: Vec<AliasedResultExpr>,
+    ) -> PostprocessingResult<Self> {
+        Self::try_new_with_having(by_ids, aliased_exprs, None)
+    }
+
+    /// Create a new group by expression whose groups are filtered by a `HAVING` clause
+    pub fn try_new_with_having(
+        by_ids: Vec<Ident>,
+        aliased_exprs: Vec<AliasedResultExpr>,
+        having_expr: Option<Expression>,
     ) -> PostprocessingResult<Self> {
         let mut aggregation_expr_map: IndexMap<(AggregationOperator, Expression), Ident> =
             IndexMap::default();
@@ -171,13 +381,25 @@ impl GroupByPostprocessing {
         let remainder_exprs: Vec<AliasedResultExpr> = aliased_exprs
             .into_iter()
             .map(|aliased_expr| -> PostprocessingResult<_> {
+                Ok(AliasedResultExpr {
+                    alias: aliased_expr.alias,
+                    expr: Box::new(check_and_get_aggregation_and_remainder(
+                        *aliased_expr.expr,
+                        &by_ids,
+                        &mut aggregation_expr_map,
+                    )?),
+                })
+            })
+            .collect::<PostprocessingResult<Vec<AliasedResultExpr>>>()?;
+        let having_expr = having_expr
+            .map(|having_expr| {
                 check_and_get_aggregation_and_remainder(
-                    aliased_expr,
+                    having_expr,
                     &by_ids,
                     &mut aggregation_expr_map,
                 )
             })
-            .collect::<PostprocessingResult<Vec<AliasedResultExpr>>>()?;
+            .transpose()?;
         let group_by_identifiers = Vec::from_iter(IndexSet::from_iter(by_ids));
         Ok(Self {
             remainder_exprs,
@@ -186,6 +408,7 @@ impl GroupByPostprocessing {
                 .into_iter()
                 .map(|((op, expr), id)| (op, expr, id))
                 .collect(),
+            having_expr,
         })
     }
 
@@ -206,6 +429,12 @@ impl GroupByPostprocessing {
     pub fn aggregation_exprs(&self) -> &[(AggregationOperator, Expression, Ident)] {
         &self.aggregation_exprs
     }
+
+    /// Get the `HAVING` clause over the group by identifiers and the aggregation results
+    #[must_use]
+    pub fn having_expr(&self) -> Option<&Expression> {
+        self.having_expr.as_ref()
+    }
 }
 
 impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
@@ -214,24 +443,30 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
     fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
         // First evaluate all the aggregated columns
         let alloc = Bump::new();
-        let evaluated_columns = self
-            .aggregation_exprs
-            .iter()
-            .map(|(agg_op, expr, id)| -> PostprocessingResult<_> {
-                let evaluated_owned_column = owned_table.evaluate(expr)?;
-                Ok((*agg_op, (id.clone(), evaluated_owned_column)))
-            })
-            .process_results(|iter| {
-                iter.fold(
-                    IndexMap::<_, Vec<_>>::default(),
-                    |mut lookup, (key, val)| {
-                        lookup.entry(key).or_default().push(val);
-                        lookup
-                    },
-                )
-            })?;
+        let evaluated_columns =
+            self.aggregation_exprs
+                .iter()
+                .map(|(agg_op, expr, id)| -> PostprocessingResult<_> {
+                    // COUNT(*) counts all rows, just like COUNT(1)
+                    let evaluated_owned_column = match expr {
+                        Expression::Wildcard => owned_table
+                            .evaluate_nullable(&Expression::Literal(Literal::BigInt(1)))?,
+                        _ => owned_table.evaluate_nullable(expr)?,
+                    };
+                    Ok((*agg_op, (id.clone(), evaluated_owned_column)))
+                })
+                .process_results(|iter| {
+                    iter.fold(
+                        IndexMap::<_, Vec<_>>::default(),
+                        |mut lookup, (key, val)| {
+                            lookup.entry(key).or_default().push(val);
+                            lookup
+                        },
+                    )
+                })?;
         // Next actually do the GROUP BY
-        let group_by_ins = self
+        // NULLs are zero, so they are told apart by grouping by the presence of nullable columns as well
+        let group_by_column_components = self
             .group_by_identifiers
             .iter()
             .map(|id| {
@@ -240,95 +475,192 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
                         column: id.to_string(),
                     },
                 )?;
-                Ok(Column::<S>::from_owned_column(column, &alloc))
+                Ok(Column::<S>::from_owned_column(column, &alloc).components())
             })
             .collect::<PostprocessingResult<Vec<_>>>()?;
+        let group_by_ins = group_by_column_components
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
         // TODO: Allow a filter
         let selection_in = vec![true; owned_table.num_rows()];
+        // NULLs are zero, so they do not change sums.
+        // Extrema skip NULLs by replacing them with values that can not be the extremum.
+        let extremum_ins = |agg_op, nulls_as_lowest: bool| {
+            evaluated_columns.get(&agg_op).map_or(vec![], |tuple| {
+                tuple
+                    .iter()
+                    .map(|(id, c)| {
+                        let values = if nulls_as_lowest {
+                            c.values_with_nulls_as_lowest()
+                        } else {
+                            c.values_with_nulls_as_highest()
+                        };
+                        (id.clone(), values)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+        let max_ins = extremum_ins(AggregationOperator::Max, true);
+        let min_ins = extremum_ins(AggregationOperator::Min, false);
+        // The number of non-NULL values in each group is the sum of the presence column
+        let presence_ins = evaluated_columns
+            .values()
+            .flatten()
+            .filter_map(|(id, c)| {
+                c.presence().map(|presence| {
+                    let ones = presence.iter().map(|&present| i64::from(present)).collect();
+                    (id.clone(), OwnedColumn::<S>::BigInt(ones))
+                })
+            })
+            .collect::<Vec<_>>();
         let (sum_identifiers, sum_columns): (Vec<_>, Vec<_>) = evaluated_columns
             .get(&AggregationOperator::Sum)
             .map_or((vec![], vec![]), |tuple| {
                 tuple
                     .iter()
-                    .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
+                    .map(|(id, c)| {
+                        (
+                            id.clone(),
+                            Column::<S>::from_owned_column(c.values(), &alloc),
+                        )
+                    })
                     .unzip()
             });
-        let (max_identifiers, max_columns): (Vec<_>, Vec<_>) = evaluated_columns
-            .get(&AggregationOperator::Max)
+        let (max_identifiers, max_columns): (Vec<_>, Vec<_>) = max_ins
+            .iter()
+            .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
+            .unzip();
+        let (min_identifiers, min_columns): (Vec<_>, Vec<_>) = min_ins
+            .iter()
+            .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
+            .unzip();
+        // Averages are computed from sums so they are aggregated along with them
+        let (avg_identifiers, avg_columns): (Vec<_>, Vec<_>) = evaluated_columns
+            .get(&AggregationOperator::Avg)
             .map_or((vec![], vec![]), |tuple| {
                 tuple
                     .iter()
-                    .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
+                    .map(|(id, c)| {
+                        (
+                            id.clone(),
+                            Column::<S>::from_owned_column(c.values(), &alloc),
+                        )
+                    })
                     .unzip()
             });
-        let (min_identifiers, min_columns): (Vec<_>, Vec<_>) = evaluated_columns
-            .get(&AggregationOperator::Min)
-            .map_or((vec![], vec![]), |tuple| {
-                tuple
+        let sum_and_avg_columns = sum_columns
+            .iter()
+            .chain(&avg_columns)
+            .copied()
+            .chain(
+                presence_ins
                     .iter()
-                    .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
-                    .unzip()
-            });
+                    .map(|(_, c)| Column::<S>::from_owned_column(c, &alloc)),
+            )
+            .collect::<Vec<_>>();
         let aggregation_results = aggregate_columns(
             &alloc,
             &group_by_ins,
-            &sum_columns,
+            &sum_and_avg_columns,
             &max_columns,
             &min_columns,
             &selection_in,
         )?;
+        let (sum_results, other_sum_results) =
+            aggregation_results.sum_columns.split_at(sum_columns.len());
+        let (avg_sum_results, presence_sum_results) = other_sum_results.split_at(avg_columns.len());
+        let non_null_counts = presence_ins
+            .iter()
+            .zip(presence_sum_results)
+            .map(|((id, _), c_out)| -> PostprocessingResult<_> {
+                match OwnedColumn::try_from_scalars(c_out, ColumnType::BigInt)? {
+                    OwnedColumn::BigInt(counts) => Ok((id.clone(), counts)),
+                    _ => unreachable!("scalars are converted to the requested type"),
+                }
+            })
+            .collect::<PostprocessingResult<IndexMap<_, _>>>()?;
+        let counts_of = |id: &Ident| {
+            non_null_counts
+                .get(id)
+                .map_or(aggregation_results.count_column, Vec::as_slice)
+        };
         // Finally do another round of evaluation to get the final result
         // Gather the results into a new OwnedTable
-        let group_by_outs = aggregation_results
-            .group_by_columns
+        let mut group_by_results = aggregation_results.group_by_columns.iter();
+        let group_by_outs = group_by_column_components
             .iter()
             .zip(self.group_by_identifiers.iter())
-            .map(|(column, id)| Ok((id.clone(), OwnedColumn::from(column))));
-        let sum_outs = izip!(
-            aggregation_results.sum_columns,
-            sum_identifiers,
-            sum_columns,
-        )
-        .map(|(c_out, id, c_in)| {
+            .map(|(components, id)| -> PostprocessingResult<_> {
+                let mut column_components = group_by_results
+                    .by_ref()
+                    .take(components.len())
+                    .map(OwnedColumn::from);
+                let values = column_components.next().expect("every column has values");
+                let presence = column_components.next().map(|presence| match presence {
+                    OwnedColumn::Boolean(presence) => presence,
+                    _ => unreachable!("the presence of a column is boolean"),
+                });
+                Ok((
+                    id.clone(),
+                    NullableOwnedColumn::try_new(values, presence)?.into(),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let sum_outs = izip!(sum_results, sum_identifiers, sum_columns).map(|(c_out, id, c_in)| {
             Ok((
                 id,
                 OwnedColumn::try_from_scalars(c_out, c_in.column_type())?,
             ))
         });
+        // Groups without any non-NULL values have no extrema
+        let extremum_out = |c_out: &[Option<S>], id: Ident, c_in: Column<S>| {
+            let c_out = c_out
+                .iter()
+                .zip(counts_of(&id))
+                .map(|(value, &count)| value.filter(|_| count > 0))
+                .collect::<Vec<_>>();
+            Ok((
+                id,
+                OwnedColumn::try_from_option_scalars(&c_out, c_in.column_type())?,
+            ))
+        };
         let max_outs = izip!(
             aggregation_results.max_columns,
             max_identifiers,
             max_columns,
         )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_option_scalars(c_out, c_in.column_type())?,
-            ))
-        });
+        .map(|(c_out, id, c_in)| extremum_out(c_out, id, c_in));
         let min_outs = izip!(
             aggregation_results.min_columns,
             min_identifiers,
             min_columns,
         )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_option_scalars(c_out, c_in.column_type())?,
-            ))
-        });
-        //TODO: When we have NULLs we need to differentiate between count(1) and count(expression)
+        .map(|(c_out, id, c_in)| extremum_out(c_out, id, c_in));
         let count_column = OwnedColumn::BigInt(aggregation_results.count_column.to_vec());
+        // The sums are averaged directly so that they can not overflow the type of the column
+        let avg_outs = izip!(avg_sum_results, avg_identifiers, avg_columns).map(
+            |(c_out, id, c_in)| -> PostprocessingResult<_> {
+                let (precision, scale, values) =
+                    try_average_decimal_column(c_out, counts_of(&id), c_in.column_type())?;
+                Ok((id, OwnedColumn::Decimal75(precision, scale, values)))
+            },
+        );
+        // Counts of nullable expressions only count the non-NULL values
         let count_outs = evaluated_columns
             .get(&AggregationOperator::Count)
             .into_iter()
             .flatten()
-            .map(|(id, _)| -> PostprocessingResult<_> { Ok((id.clone(), count_column.clone())) });
+            .map(|(id, _)| -> PostprocessingResult<_> {
+                Ok((id.clone(), OwnedColumn::BigInt(counts_of(id).to_vec())))
+            });
         let new_owned_table: OwnedTable<S> = group_by_outs
             .into_iter()
             .chain(sum_outs)
             .chain(max_outs)
             .chain(min_outs)
+            .chain(avg_outs)
             .chain(count_outs)
             .process_results(|iter| OwnedTable::try_from_iter(iter))??;
         // If there are no columns at all we need to have the count column so that we can handle
@@ -338,6 +670,10 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
         } else {
             new_owned_table
         };
+        let target_table = match &self.having_expr {
+            Some(having_expr) => filter_groups(&target_table, having_expr, &alloc)?,
+            None => target_table,
+        };
         let result = self
             .remainder_exprs
             .iter()
@@ -351,6 +687,35 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
     }
 }
 
+/// Keeps the groups of `table` for which `having_expr` is `true`
+fn filter_groups<S: Scalar>(
+    table: &OwnedTable<S>,
+    having_expr: &Expression,
+    alloc: &Bump,
+) -> PostprocessingResult<OwnedTable<S>> {
+    let selection = match table.evaluate(having_expr)? {
+        OwnedColumn::Boolean(selection) => selection,
+        column => {
+            return Err(PostprocessingError::NonbooleanHavingClause {
+                datatype: column.column_type(),
+            })
+        }
+    };
+    let columns = table
+        .inner_table()
+        .values()
+        .map(|column| Column::<S>::from_owned_column(column, alloc))
+        .collect::<Vec<_>>();
+    let (filtered_columns, _) = filter_columns(alloc, &columns, &selection);
+    Ok(OwnedTable::try_from_iter(
+        table
+            .inner_table()
+            .keys()
+            .cloned()
+            .zip(filtered_columns.iter().map(OwnedColumn::from)),
+    )?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,6 +27,26 @@ fn we_cannot_have_invalid_group_bys() {
         res,
         Err(PostprocessingError::NestedAggregationInGroupByClause { .. })
     ));
+
+    // Distinct values are only counted by provable queries
+    let expr = count_distinct(col("b"));
+    let res = GroupByPostprocessing::try_new(vec!["a".into()], vec![aliased_expr(expr, "res")]);
+    assert_eq!(
+        res,
+        Err(PostprocessingError::UnsupportedAggregation {
+            op: AggregationOperator::CountDistinct
+        })
+    );
+
+    // Filtered aggregations are only supported by provable queries
+    let expr = filtered(sum(col("b")), gt(col("b"), lit(0)));
+    let res = GroupByPostprocessing::try_new(vec!["a".into()], vec![aliased_expr(expr, "res")]);
+    assert_eq!(
+        res,
+        Err(PostprocessingError::UnsupportedAggregation {
+            op: AggregationOperator::Sum
+        })
+    );
 }
 
 #[test]
@@ -297,3 +317,102 @@ fn we_can_do_complex_group_bys() {
     let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
     assert_eq!(actual_table, expected_table);
 }
+
+#[test]
+fn we_can_do_group_bys_with_averages() {
+    // SELECT a, AVG(b) as avg_b, AVG(c) as avg_c, COUNT(b) as cnt FROM tab GROUP BY a
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        int128("a", [1_i128, 1, 2]),
+        tinyint("b", [100_i8, 101, -7]),
+        decimal75("c", 3, 1, [15, 10, 3]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing(
+        &["a"],
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(avg(col("b")), "avg_b"),
+            aliased_expr(avg(col("c")), "avg_c"),
+            aliased_expr(count(col("b")), "cnt"),
+        ],
+    )];
+    // The sum of `b` in the first group does not fit into a `TinyInt`
+    let expected_table = owned_table([
+        int128("a", [1_i128, 2]),
+        decimal75("avg_b", 9, 6, [100_500_000, -7_000_000]),
+        decimal75("avg_c", 8, 6, [1_250_000, 300_000]),
+        bigint("cnt", [2_i64, 1]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_make_group_by_postprocessing_with_a_having_clause() {
+    // SELECT a, SUM(b) as s FROM tab GROUP BY a HAVING SUM(b) > 10 AND MAX(c) < 5
+    let res = GroupByPostprocessing::try_new_with_having(
+        vec!["a".into()],
+        vec![
+            aliased_expr(col("a"), "a"),
+            aliased_expr(sum(col("b")), "s"),
+        ],
+        Some(*and(gt(sum(col("b")), lit(10)), lt(max(col("c")), lit(5)))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.having_expr(),
+        Some(&*and(
+            gt(col("__col_agg_0"), lit(10)),
+            lt(col("__col_agg_1"), lit(5))
+        ))
+    );
+    assert_eq!(
+        res.aggregation_exprs(),
+        &[
+            (AggregationOperator::Sum, *col("b"), "__col_agg_0".into()),
+            (AggregationOperator::Max, *col("c"), "__col_agg_1".into()),
+        ]
+    );
+
+    // Columns of the HAVING clause must be aggregated or in the group by
+    let res = GroupByPostprocessing::try_new_with_having(
+        vec!["a".into()],
+        vec![aliased_expr(col("a"), "a")],
+        Some(*gt(col("b"), lit(10))),
+    );
+    assert!(matches!(
+        res,
+        Err(PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. })
+    ));
+}
+
+#[test]
+fn we_can_filter_groups_with_a_having_clause() {
+    // SELECT a, SUM(b) as s FROM tab GROUP BY a HAVING COUNT(*) > 1 AND MAX(b) < 8
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        varchar("a", ["x", "y", "x", "z", "y", "z"]),
+        bigint("b", [1_i64, 5, 2, 9, 6, 3]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &["a"],
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(sum(col("b")), "s"),
+        ],
+        &and(gt(count_all(), lit(1)), lt(max(col("b")), lit(8))),
+    )];
+    let expected_table = owned_table([varchar("a", ["x", "y"]), bigint("s", [3_i64, 11])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+
+    // SELECT a FROM tab GROUP BY a HAVING SUM(b)
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1_i64, 2])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &["a"],
+        &[aliased_expr(col("a"), "a")],
+        &sum(col("a")),
+    )];
+    assert!(matches!(
+        apply_postprocessing_steps(table, &postprocessing),
+        Err(PostprocessingError::NonbooleanHavingClause { .. })
+    ));
+}
@@ -11,6 +11,11 @@ pub use postprocessing_step::PostprocessingStep;
 /// Utility functions for testing postprocessing steps.
 pub mod test_utility;
 
+mod average_postprocessing;
+pub use average_postprocessing::AveragePostprocessing;
+#[cfg(test)]
+mod average_postprocessing_test;
+
 mod group_by_postprocessing;
 pub use group_by_postprocessing::GroupByPostprocessing;
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use super::{
-    GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult, PostprocessingStep,
-    SelectPostprocessing, SlicePostprocessing,
+    AveragePostprocessing, GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult,
+    PostprocessingStep, SelectPostprocessing, SlicePostprocessing,
 };
 use crate::base::{database::OwnedTable, scalar::Scalar};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,8 @@ pub enum OwnedTablePostprocessing {
     Select(SelectPostprocessing),
     /// Aggregate the `OwnedTable` with the given `GroupByPostprocessing`.
     GroupBy(GroupByPostprocessing),
+    /// Average the sums of the `OwnedTable` with the given `AveragePostprocessing`.
+    Average(AveragePostprocessing),
 }
 
 impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
@@ -26,6 +28,7 @@ impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
             OwnedTablePostprocessing::OrderBy(order_by_expr) => order_by_expr.apply(owned_table),
             OwnedTablePostprocessing::Select(select_expr) => select_expr.apply(owned_table),
             OwnedTablePostprocessing::GroupBy(group_by_expr) => group_by_expr.apply(owned_table),
+            OwnedTablePostprocessing::Average(average_expr) => average_expr.apply(owned_table),
         }
     }
 }
@@ -51,6 +54,11 @@ impl OwnedTablePostprocessing {
     pub fn new_group_by(group_by_postprocessing: GroupByPostprocessing) -> Self {
         Self::GroupBy(group_by_postprocessing)
     }
+    /// Create a new `OwnedTablePostprocessing` with the given `AveragePostprocessing`.
+    #[must_use]
+    pub fn new_average(average_postprocessing: AveragePostprocessing) -> Self {
+        Self::Average(average_postprocessing)
+    }
 }
 
 /// Apply a list of postprocessing steps to an `OwnedTable`.
@@ -24,6 +24,12 @@ impl SelectPostprocessing {
             aliased_result_exprs,
         }
     }
+
+    /// Get the aliased result expressions
+    #[must_use]
+    pub fn aliased_result_exprs(&self) -> &[AliasedResultExpr] {
+        &self.aliased_result_exprs
+    }
 }
 
 impl<S: Scalar> PostprocessingStep<S> for SelectPostprocessing {
@@ -1,5 +1,5 @@
 use super::*;
-use proof_of_sql_parser::intermediate_ast::AliasedResultExpr;
+use proof_of_sql_parser::intermediate_ast::{AliasedResultExpr, Expression};
 use sqlparser::ast::Ident;
 
 #[must_use]
@@ -14,6 +14,31 @@ pub fn group_by_postprocessing(
     )
 }
 
+#[must_use]
+/// Producing a postprocessing object that represents a group by operation with a `HAVING` clause.
+pub fn group_by_postprocessing_with_having(
+    cols: &[&str],
+    result_exprs: &[AliasedResultExpr],
+    having_expr: &Expression,
+) -> OwnedTablePostprocessing {
+    let ids: Vec<Ident> = cols.iter().map(|col| (*col).into()).collect();
+    OwnedTablePostprocessing::new_group_by(
+        GroupByPostprocessing::try_new_with_having(
+            ids,
+            result_exprs.to_vec(),
+            Some(having_expr.clone()),
+        )
+        .unwrap(),
+    )
+}
+
+/// Producing a postprocessing object that represents an average operation.
+#[must_use]
+pub fn average(sum_cols: &[&str], count_col: &str) -> OwnedTablePostprocessing {
+    let ids: Vec<Ident> = sum_cols.iter().map(|col| (*col).into()).collect();
+    OwnedTablePostprocessing::new_average(AveragePostprocessing::new(ids, count_col.into()))
+}
+
 /// Producing a postprocessing object that represents a select operation.
 /// # Panics
 ///
@@ -0,0 +1,347 @@
+use super::{ProofPlan, QueryData, QueryResult, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof, VecCommitmentExt},
+        database::{
+            ChunkedDataAccessor, Column, ColumnRef, CommitmentAccessor, DataAccessor,
+            MetadataAccessor, OwnedColumn, OwnedTable, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::{Keccak256Transcript, ProofError, Transcript},
+        scalar::Scalar,
+    },
+    sql::proof_plans::DynProofPlan,
+    utils::log,
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use core::ops::Range;
+use serde::{Deserialize, Serialize};
+
+/// The result of an sql query along with a proof that the query is valid, where the query is
+/// proven one chunk of rows at a time.
+///
+/// The data span of every table that the query references is split into chunks of `chunk_size`
+/// rows, and the query over each chunk is proven by a [`VerifiableQueryResult`] of its own, exactly
+/// as if the chunk were the whole data span. Only one chunk of each column is loaded at a time, so
+/// the memory needed to create the proof is bounded by the chunk size rather than by the size of
+/// the tables.
+///
+/// Along with the proofs, the commitments to the referenced columns over each chunk are sent. The
+/// verifier checks that they add up to the commitments to the whole columns, so the chunks are
+/// verified against the same commitments as a [`VerifiableQueryResult`] of the whole query.
+///
+/// This is not a streaming version of [`VerifiableQueryResult`]: there is one proof per chunk, so
+/// the size of the proof and the time to verify it grow with the number of chunks, and it can not
+/// be verified in place of a [`VerifiableQueryResult`] of the whole query. Only queries whose result
+/// over the whole data span is the concatenation of their results over the chunks can be proven
+/// this way, i.e. filters and projections of a table whose expressions are evaluated row by row.
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct ChunkedVerifiableQueryResult<CP: CommitmentEvaluationProof> {
+    /// The result and proof of the query over each chunk, in order.
+    pub(super) chunks: Vec<VerifiableQueryResult<CP>>,
+    /// The commitments to the columns that the query references over each chunk, in order.
+    pub(super) chunk_commitments: Vec<Vec<CP::Commitment>>,
+}
+
+impl<CP: CommitmentEvaluationProof> ChunkedVerifiableQueryResult<CP> {
+    /// Form a `ChunkedVerifiableQueryResult` from a query expression, proving it over chunks of
+    /// `chunk_size` rows that are streamed from the accessor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProofError::UnsupportedQueryPlan`] if the query is not a filter or projection of a
+    /// table whose expressions are evaluated row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[tracing::instrument(name = "ChunkedVerifiableQueryResult::new", level = "info", skip_all)]
+    pub fn new(
+        expr: &DynProofPlan,
+        accessor: &impl ChunkedDataAccessor<CP::Scalar>,
+        chunk_size: usize,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, ProofError> {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        check_row_wise(expr)?;
+        log::log_memory_usage("Start");
+
+        let column_refs = expr.get_column_references();
+        let (chunks, chunk_commitments) =
+            chunk_ranges(&expr.get_table_references(), accessor, chunk_size)
+                .iter()
+                .map(|ranges| {
+                    let chunk = LoadedChunk::new(Chunk { accessor, ranges }, &column_refs);
+                    let commitments = column_refs
+                        .iter()
+                        .map(|column_ref| chunk.commit::<CP::Commitment>(column_ref, setup))
+                        .collect::<Vec<_>>();
+                    (VerifiableQueryResult::new(expr, &chunk, setup), commitments)
+                })
+                .unzip();
+
+        log::log_memory_usage("End");
+
+        Ok(Self {
+            chunks,
+            chunk_commitments,
+        })
+    }
+
+    /// Verify a `ChunkedVerifiableQueryResult` that was proven with the same `chunk_size`. Upon
+    /// success, this function returns the finalized form of the query result over the whole data
+    /// span, which is the concatenation of the results over the chunks.
+    ///
+    /// The commitments to the chunks have to add up to the commitments of `accessor` to the whole
+    /// columns. The verification hash of the result is derived from the verification hashes of the
+    /// chunks.
+    ///
+    /// Note: This does NOT transform the result!
+    #[tracing::instrument(
+        name = "ChunkedVerifiableQueryResult::verify",
+        level = "info",
+        skip_all
+    )]
+    pub fn verify(
+        self,
+        expr: &DynProofPlan,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        chunk_size: usize,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> QueryResult<CP::Scalar> {
+        log::log_memory_usage("Start");
+
+        check_row_wise(expr)?;
+        if chunk_size == 0 {
+            Err(ProofError::VerificationError {
+                error: "chunk size must be positive",
+            })?;
+        }
+        let all_ranges = chunk_ranges(&expr.get_table_references(), accessor, chunk_size);
+        if all_ranges.len() != self.chunks.len() || all_ranges.len() != self.chunk_commitments.len()
+        {
+            Err(ProofError::VerificationError {
+                error: "number of chunks does not match the data",
+            })?;
+        }
+        let column_refs = expr.get_column_references();
+        check_chunk_commitments(&column_refs, &self.chunk_commitments, accessor)?;
+        let mut verification_hashes = Vec::with_capacity(all_ranges.len());
+        let mut columns: Option<IndexMap<_, OwnedColumn<CP::Scalar>>> = None;
+        for ((ranges, chunk_result), commitments) in all_ranges
+            .iter()
+            .zip(self.chunks)
+            .zip(&self.chunk_commitments)
+        {
+            let chunk = CommittedChunk {
+                chunk: Chunk { accessor, ranges },
+                column_refs: &column_refs,
+                commitments,
+            };
+            let chunk_data = chunk_result.verify(expr, &chunk, setup)?;
+            verification_hashes.push(chunk_data.verification_hash);
+            let chunk_columns = chunk_data.table.into_inner();
+            match &mut columns {
+                None => columns = Some(chunk_columns),
+                Some(columns) => {
+                    for (column, chunk_column) in
+                        columns.values_mut().zip(chunk_columns.into_values())
+                    {
+                        column.try_append(chunk_column).map_err(|_| {
+                            ProofError::VerificationError {
+                                error: "column types of the chunks do not match",
+                            }
+                        })?;
+                    }
+                }
+            }
+        }
+        let table = OwnedTable::try_new(columns.unwrap_or_default())?;
+        let mut transcript: Keccak256Transcript = Transcript::new();
+        transcript.extend_serialize_as_le(&verification_hashes);
+        let verification_hash = transcript.challenge_as_le();
+
+        log::log_memory_usage("End");
+
+        Ok(QueryData {
+            table,
+            verification_hash,
+        })
+    }
+}
+
+/// Check that the query is one whose result over the whole data span is the concatenation of its
+/// results over consecutive chunks, see [`DynProofPlan::is_row_wise`].
+fn check_row_wise(expr: &DynProofPlan) -> Result<(), ProofError> {
+    if expr.is_row_wise() {
+        Ok(())
+    } else {
+        Err(ProofError::UnsupportedQueryPlan {
+            error: "only filters and projections of a table can be proven one chunk at a time",
+        })
+    }
+}
+
+/// Check that the commitments to the columns over the chunks add up to the commitments to the whole
+/// columns, which is what binds the proofs of the chunks to the data.
+fn check_chunk_commitments<C: Commitment>(
+    column_refs: &IndexSet<ColumnRef>,
+    chunk_commitments: &[Vec<C>],
+    accessor: &impl CommitmentAccessor<C>,
+) -> Result<(), ProofError> {
+    if chunk_commitments
+        .iter()
+        .any(|commitments| commitments.len() != column_refs.len())
+    {
+        Err(ProofError::VerificationError {
+            error: "number of chunk commitments does not match the columns",
+        })?;
+    }
+    for (index, column_ref) in column_refs.iter().enumerate() {
+        let mut commitment = C::default();
+        for commitments in chunk_commitments {
+            commitment += commitments[index].clone();
+        }
+        if commitment != accessor.get_commitment(column_ref.clone()) {
+            Err(ProofError::VerificationError {
+                error: "chunk commitments do not add up to the commitment of the column",
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Split the data spans of the tables into chunks of `chunk_size` rows.
+///
+/// Every table is split at the same rows, so shorter tables have empty chunks at the end.
+/// There is always at least one chunk, which is empty if all tables are empty.
+fn chunk_ranges(
+    table_refs: &IndexSet<TableRef>,
+    accessor: &impl MetadataAccessor,
+    chunk_size: usize,
+) -> Vec<IndexMap<TableRef, Range<usize>>> {
+    let lengths: IndexMap<TableRef, usize> = table_refs
+        .iter()
+        .map(|table_ref| (table_ref.clone(), accessor.get_length(table_ref)))
+        .collect();
+    let num_chunks = lengths
+        .values()
+        .map(|length| length.div_ceil(chunk_size))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    (0..num_chunks)
+        .map(|i| {
+            lengths
+                .iter()
+                .map(|(table_ref, &length)| {
+                    let start = (i * chunk_size).min(length);
+                    let end = (start + chunk_size).min(length);
+                    (table_ref.clone(), start..end)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A chunk of the data spans of an accessor, which is a data span in its own right.
+struct Chunk<'a, A> {
+    accessor: &'a A,
+    ranges: &'a IndexMap<TableRef, Range<usize>>,
+}
+
+impl<A: MetadataAccessor> MetadataAccessor for Chunk<'_, A> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.ranges[table_ref].len()
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.accessor.get_offset(table_ref) + self.ranges[table_ref].start
+    }
+}
+
+/// A chunk along with the commitments to the columns over it that the prover sent.
+struct CommittedChunk<'a, C, A> {
+    chunk: Chunk<'a, A>,
+    column_refs: &'a IndexSet<ColumnRef>,
+    commitments: &'a [C],
+}
+
+impl<C, A: MetadataAccessor> MetadataAccessor for CommittedChunk<'_, C, A> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.chunk.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.chunk.get_offset(table_ref)
+    }
+}
+
+/// # Panics
+///
+/// Panics if the query does not reference the column.
+impl<C: Commitment, A: MetadataAccessor> CommitmentAccessor<C> for CommittedChunk<'_, C, A> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        let index = self
+            .column_refs
+            .get_index_of(&column)
+            .expect("the query references the column");
+        self.commitments[index].clone()
+    }
+}
+
+/// A chunk whose columns have been loaded into memory.
+struct LoadedChunk<'a, S: Scalar, A> {
+    chunk: Chunk<'a, A>,
+    columns: IndexMap<ColumnRef, OwnedColumn<S>>,
+    alloc: Bump,
+}
+
+impl<'a, S: Scalar, A: ChunkedDataAccessor<S>> LoadedChunk<'a, S, A> {
+    fn new(chunk: Chunk<'a, A>, column_refs: &IndexSet<ColumnRef>) -> Self {
+        let columns = column_refs
+            .iter()
+            .map(|column_ref| {
+                let range = chunk.ranges[&column_ref.table_ref()].clone();
+                let column = chunk.accessor.get_column_chunk(column_ref.clone(), range);
+                (column_ref.clone(), column)
+            })
+            .collect();
+        Self {
+            chunk,
+            columns,
+            alloc: Bump::new(),
+        }
+    }
+
+    /// Commit to a column over the chunk.
+    fn commit<C: Commitment>(&self, column_ref: &ColumnRef, setup: &C::PublicSetup<'_>) -> C {
+        Vec::<C>::from_columns_with_offset(
+            [&self.columns[column_ref]],
+            self.get_offset(&column_ref.table_ref()),
+            setup,
+        )[0]
+        .clone()
+    }
+}
+
+impl<S: Scalar, A: MetadataAccessor> MetadataAccessor for LoadedChunk<'_, S, A> {
+    fn get_length(&self, table_ref: &TableRef) -> usize {
+        self.chunk.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: &TableRef) -> usize {
+        self.chunk.get_offset(table_ref)
+    }
+}
+
+impl<S: Scalar, A: MetadataAccessor> DataAccessor<S> for LoadedChunk<'_, S, A> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        Column::from_owned_column(&self.columns[&column], &self.alloc)
+    }
+}
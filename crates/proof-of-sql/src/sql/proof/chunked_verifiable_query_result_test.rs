@@ -0,0 +1,217 @@
+use super::{ChunkedVerifiableQueryResult, QueryError, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        proof::ProofError,
+        scalar::Curve25519Scalar,
+    },
+    sql::{proof_exprs::test_utility::*, proof_plans::test_utility::*},
+};
+
+fn accessor_with_table(
+    data: OwnedTable<Curve25519Scalar>,
+) -> (TableRef, OwnedTableTestAccessor<'static, InnerProductProof>) {
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 5, ());
+    (t, accessor)
+}
+
+#[test]
+fn we_can_prove_a_filter_one_chunk_at_a_time() {
+    let (t, accessor) = accessor_with_table(owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+        varchar("b", ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]),
+    ]));
+    let expr = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        gte(column(&t, "a", &accessor), const_bigint(4)),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 4, &()).unwrap();
+    assert_eq!(res.chunks.len(), 3);
+    let table = res.verify(&expr, &accessor, 4, &()).unwrap().table;
+    assert_eq!(
+        table,
+        owned_table([
+            bigint("a", [4_i64, 5, 6, 7, 8, 9, 10]),
+            varchar("b", ["d", "e", "f", "g", "h", "i", "j"])
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_a_projection_one_chunk_at_a_time() {
+    let (t, accessor) = accessor_with_table(owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5]),
+        bigint("b", [5_i64, 4, 3, 2, 1]),
+    ]));
+    let expr = projection(
+        vec![aliased_plan(
+            multiply(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "c",
+        )],
+        tab(&t),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 2, &()).unwrap();
+    let table = res.verify(&expr, &accessor, 2, &()).unwrap().table;
+    assert_eq!(table, owned_table([bigint("c", [5_i64, 8, 9, 8, 5])]));
+}
+
+#[test]
+fn we_can_verify_each_chunk_as_an_ordinary_proof_of_its_rows() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7]),
+        bigint("b", [7_i64, 6, 5, 4, 3, 2, 1]),
+    ]);
+    let (t, accessor) = accessor_with_table(data.clone());
+    let expr = filter(
+        vec![aliased_plan(
+            add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "c",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 3, &()).unwrap();
+    assert_eq!(res.chunks.len(), 3);
+    for (i, chunk) in res.chunks.into_iter().enumerate() {
+        let start = 3 * i;
+        let end = (start + 3).min(7);
+        let chunk_data = OwnedTable::try_from_iter(
+            data.inner_table()
+                .iter()
+                .map(|(id, column)| (id.clone(), column.slice(start, end))),
+        )
+        .unwrap();
+        let chunk_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            chunk_data,
+            5 + start,
+            (),
+        );
+        let expected = VerifiableQueryResult::new(&expr, &chunk_accessor, &())
+            .verify(&expr, &chunk_accessor, &())
+            .unwrap();
+        let actual = chunk.verify(&expr, &chunk_accessor, &()).unwrap();
+        assert_eq!(actual.table, expected.table);
+        assert_eq!(actual.table.num_rows(), end - start);
+    }
+}
+
+#[test]
+fn we_cannot_prove_or_verify_a_group_by_one_chunk_at_a_time() {
+    let (t, accessor) = accessor_with_table(owned_table([
+        bigint("a", [1_i64, 2, 1, 2, 1]),
+        bigint("b", [10_i64, 20, 30, 40, 50]),
+    ]));
+    let expr = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    assert!(matches!(
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 2, &()),
+        Err(ProofError::UnsupportedQueryPlan { .. })
+    ));
+    let res = ChunkedVerifiableQueryResult::<InnerProductProof>::new(
+        &filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        &accessor,
+        2,
+        &(),
+    )
+    .unwrap();
+    assert!(matches!(
+        res.verify(&expr, &accessor, 2, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_prove_a_query_against_an_empty_table_one_chunk_at_a_time() {
+    let (t, accessor) = accessor_with_table(owned_table([bigint("a", [0_i64; 0])]));
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 4, &()).unwrap();
+    assert_eq!(res.chunks.len(), 1);
+    let table = res.verify(&expr, &accessor, 4, &()).unwrap().table;
+    assert_eq!(table, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_cannot_verify_chunks_with_a_different_chunk_size() {
+    let (t, accessor) =
+        accessor_with_table(owned_table([bigint("a", [1_i64, 2, 3, 4, 5, 6, 7, 8, 9])]));
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 3, &()).unwrap();
+    assert!(res.clone().verify(&expr, &accessor, 3, &()).is_ok());
+    assert!(res.clone().verify(&expr, &accessor, 2, &()).is_err());
+    assert!(res.clone().verify(&expr, &accessor, 0, &()).is_err());
+    // Both chunk sizes split the table into three chunks, but not at the same rows
+    assert!(res.verify(&expr, &accessor, 4, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_chunks_that_are_missing_or_out_of_order() {
+    let (t, accessor) = accessor_with_table(owned_table([bigint("a", [1_i64, 2, 3, 4, 5])]));
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 2, &()).unwrap();
+
+    let mut missing = res.clone();
+    missing.chunks.pop();
+    assert!(missing.verify(&expr, &accessor, 2, &()).is_err());
+
+    let mut swapped = res;
+    swapped.chunks.swap(0, 1);
+    assert!(swapped.verify(&expr, &accessor, 2, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_chunks_whose_commitments_do_not_match_the_table() {
+    let (t, accessor) = accessor_with_table(owned_table([bigint("a", [1_i64, 2, 3, 4, 5, 6])]));
+    let expr = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        const_bool(true),
+    );
+    let res =
+        ChunkedVerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, 2, &()).unwrap();
+    assert_eq!(res.chunk_commitments.len(), 3);
+    assert!(res.clone().verify(&expr, &accessor, 2, &()).is_ok());
+
+    // The commitments no longer add up to the commitment of the column
+    let mut tampered = res.clone();
+    tampered.chunk_commitments[0][0] = Default::default();
+    assert!(tampered.verify(&expr, &accessor, 2, &()).is_err());
+
+    // The commitments add up, but not to the chunks that were proven
+    let mut swapped = res;
+    swapped.chunk_commitments.swap(0, 1);
+    assert!(swapped.verify(&expr, &accessor, 2, &()).is_err());
+}
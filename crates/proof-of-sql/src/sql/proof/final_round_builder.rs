@@ -3,6 +3,8 @@ use crate::{
     base::{
         bit::BitDistribution,
         commitment::{Commitment, CommittableColumn, VecCommitmentExt},
+        database::{Table, TableRef},
+        map::IndexMap,
         polynomial::MultilinearExtension,
         scalar::Scalar,
     },
@@ -25,6 +27,9 @@ pub struct FinalRoundBuilder<'a, S: Scalar> {
     /// Note: this vector is treated as a stack and the first
     /// challenge is the last entry in the vector.
     post_result_challenges: VecDeque<S>,
+    /// The tables of the database that the query is evaluated on,
+    /// which subqueries of expressions are evaluated on as well.
+    table_map: IndexMap<TableRef, Table<'a, S>>,
 }
 
 impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
@@ -36,9 +41,20 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
             pcs_proof_mles: Vec::new(),
             sumcheck_subpolynomials: Vec::new(),
             post_result_challenges,
+            table_map: IndexMap::default(),
         }
     }
 
+    /// Set the tables of the database that the query is evaluated on.
+    pub(crate) fn set_table_map(&mut self, table_map: IndexMap<TableRef, Table<'a, S>>) {
+        self.table_map = table_map;
+    }
+
+    /// Get the tables of the database that the query is evaluated on.
+    pub(crate) fn table_map(&self) -> &IndexMap<TableRef, Table<'a, S>> {
+        &self.table_map
+    }
+
     pub fn num_sumcheck_variables(&self) -> usize {
         self.num_sumcheck_variables
     }
@@ -1,6 +1,8 @@
 use crate::{
     base::{
         commitment::{Commitment, CommittableColumn, VecCommitmentExt},
+        database::{Table, TableRef},
+        map::IndexMap,
         polynomial::MultilinearExtension,
         scalar::Scalar,
     },
@@ -8,7 +10,7 @@ use crate::{
 };
 use alloc::{boxed::Box, vec::Vec};
 /// Track the result created by a query
-pub struct FirstRoundBuilder<'a, S> {
+pub struct FirstRoundBuilder<'a, S: Scalar> {
     commitment_descriptor: Vec<CommittableColumn<'a>>,
     pcs_proof_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
     /// The number of challenges used in the proof.
@@ -22,6 +24,9 @@ pub struct FirstRoundBuilder<'a, S> {
     rho_evaluation_lengths: Vec<usize>,
     // The range_length used in sumcheck which is max of all possible ones.
     range_length: usize,
+    /// The tables of the database that the query is evaluated on,
+    /// which subqueries of expressions are evaluated on as well.
+    table_map: IndexMap<TableRef, Table<'a, S>>,
 }
 
 impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
@@ -33,9 +38,20 @@ impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
             chi_evaluation_lengths: Vec::new(),
             rho_evaluation_lengths: Vec::new(),
             range_length: initial_range_length,
+            table_map: IndexMap::default(),
         }
     }
 
+    /// Set the tables of the database that the query is evaluated on.
+    pub(crate) fn set_table_map(&mut self, table_map: IndexMap<TableRef, Table<'a, S>>) {
+        self.table_map = table_map;
+    }
+
+    /// Get the tables of the database that the query is evaluated on.
+    pub(crate) fn table_map(&self) -> &IndexMap<TableRef, Table<'a, S>> {
+        &self.table_map
+    }
+
     /// Get the range length used in the proof.
     pub(crate) fn range_length(&self) -> usize {
         self.range_length
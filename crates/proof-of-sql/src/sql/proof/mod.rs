@@ -51,6 +51,16 @@ pub use verifiable_query_result::VerifiableQueryResult;
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test;
 
+mod verifiable_multi_query_result;
+pub use verifiable_multi_query_result::VerifiableMultiQueryResult;
+#[cfg(all(test, feature = "blitzar"))]
+mod verifiable_multi_query_result_test;
+
+mod chunked_verifiable_query_result;
+pub use chunked_verifiable_query_result::ChunkedVerifiableQueryResult;
+#[cfg(all(test, feature = "blitzar"))]
+mod chunked_verifiable_query_result_test;
+
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test_utility;
 #[cfg(all(test, feature = "blitzar"))]
@@ -1,12 +1,13 @@
 use super::{decode_and_convert, decode_multiple_elements, ProvableResultColumn, QueryError};
 use crate::base::{
-    database::{Column, ColumnField, ColumnType, OwnedColumn, OwnedTable, Table},
+    database::{Column, ColumnComponent, ColumnField, ColumnType, OwnedColumn, OwnedTable, Table},
     polynomial::compute_evaluation_vector,
     scalar::{Scalar, ScalarExt},
 };
-use alloc::{vec, vec::Vec};
+use alloc::{boxed::Box, vec, vec::Vec};
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
 
 /// An intermediate form of a query result that can be transformed
 /// to either the finalized query result form or a query error
@@ -75,7 +76,11 @@ impl ProvableQueryResult {
             sz += col.write(&mut data[sz..], table_length);
         }
         ProvableQueryResult {
-            num_columns: columns.len() as u64,
+            // The presence of a nullable column is a column of its own
+            num_columns: columns
+                .iter()
+                .map(|col| if col.presence().is_some() { 2 } else { 1 })
+                .sum(),
             table_length,
             data,
         }
@@ -162,82 +167,97 @@ impl ProvableQueryResult {
         let n = self.table_length();
         let mut offset: usize = 0;
 
-        let owned_table = OwnedTable::try_new(
-            column_result_fields
-                .iter()
-                .map(|field| match field.data_type() {
-                    ColumnType::Boolean => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Boolean(col)))
-                    }
-                    ColumnType::Uint8 => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Uint8(col)))
-                    }
-                    ColumnType::TinyInt => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::TinyInt(col)))
-                    }
-                    ColumnType::SmallInt => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::SmallInt(col)))
-                    }
-                    ColumnType::Int => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Int(col)))
-                    }
-                    ColumnType::BigInt => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::BigInt(col)))
-                    }
-                    ColumnType::Int128 => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Int128(col)))
-                    }
-                    ColumnType::VarChar => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::VarChar(col)))
-                    }
-                    ColumnType::VarBinary => {
-                        // Manually specify the item type: `&[u8]`
-                        let (decoded_slices, num_read) =
-                            decode_multiple_elements::<&[u8]>(&self.data[offset..], n)?;
-                        offset += num_read;
+        let columns = column_result_fields
+            .iter()
+            .map(|field| match field.data_type() {
+                ColumnType::Boolean => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Boolean(col)))
+                }
+                ColumnType::Uint8 => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Uint8(col)))
+                }
+                ColumnType::TinyInt => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::TinyInt(col)))
+                }
+                ColumnType::SmallInt => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::SmallInt(col)))
+                }
+                ColumnType::Int => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Int(col)))
+                }
+                ColumnType::BigInt => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::BigInt(col)))
+                }
+                ColumnType::Int128 => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Int128(col)))
+                }
+                ColumnType::VarChar => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::VarChar(col)))
+                }
+                ColumnType::VarBinary => {
+                    // Manually specify the item type: `&[u8]`
+                    let (decoded_slices, num_read) =
+                        decode_multiple_elements::<&[u8]>(&self.data[offset..], n)?;
+                    offset += num_read;
 
-                        // Convert those slices to owned `Vec<u8>`
-                        let col_vec = decoded_slices.into_iter().map(<[u8]>::to_vec).collect();
+                    // Convert those slices to owned `Vec<u8>`
+                    let col_vec = decoded_slices.into_iter().map(<[u8]>::to_vec).collect();
 
-                        Ok((field.name(), OwnedColumn::VarBinary(col_vec)))
-                    }
-                    ColumnType::Scalar => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Scalar(col)))
-                    }
-                    ColumnType::Decimal75(precision, scale) => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::Decimal75(precision, scale, col)))
-                    }
-                    ColumnType::TimestampTZ(tu, tz) => {
-                        let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
-                        offset += num_read;
-                        Ok((field.name(), OwnedColumn::TimestampTZ(tu, tz, col)))
-                    }
-                })
-                .collect::<Result<_, QueryError>>()?,
-        )?;
+                    Ok((field.name(), OwnedColumn::VarBinary(col_vec)))
+                }
+                ColumnType::Scalar => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Scalar(col)))
+                }
+                ColumnType::Decimal75(precision, scale) => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::Decimal75(precision, scale, col)))
+                }
+                ColumnType::TimestampTZ(tu, tz) => {
+                    let (col, num_read) = decode_multiple_elements(&self.data[offset..], n)?;
+                    offset += num_read;
+                    Ok((field.name(), OwnedColumn::TimestampTZ(tu, tz, col)))
+                }
+            })
+            .collect::<Result<Vec<_>, QueryError>>()?;
+        // The presence of a nullable column follows its values
+        let mut owned_columns: Vec<(Ident, OwnedColumn<S>)> = Vec::with_capacity(columns.len());
+        for (field, (name, column)) in column_result_fields.iter().zip(columns) {
+            match (field.component(), owned_columns.last_mut(), column) {
+                (
+                    ColumnComponent::Presence,
+                    Some((values_name, values)),
+                    OwnedColumn::Boolean(presence),
+                ) if *values_name == name && values.presence().is_none() => {
+                    let values_column =
+                        core::mem::replace(values, OwnedColumn::Boolean(Vec::new()));
+                    *values = OwnedColumn::Nullable(Box::new(values_column), presence);
+                }
+                (ColumnComponent::Values, _, column) => owned_columns.push((name, column)),
+                _ => Err(QueryError::InvalidColumnCount)?,
+            }
+        }
+        let owned_table = OwnedTable::try_new(owned_columns.into_iter().collect())?;
 
         assert_eq!(offset, self.data.len());
-        assert_eq!(owned_table.num_columns(), self.num_columns());
 
         Ok(owned_table)
     }
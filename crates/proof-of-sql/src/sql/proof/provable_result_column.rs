@@ -28,6 +28,7 @@ impl<'a, T: ProvableResultElement<'a>> ProvableResultColumn for &[T] {
     }
 }
 
+/// A nullable column is serialized as its values followed by its presence.
 impl<S: Scalar> ProvableResultColumn for Column<'_, S> {
     fn num_bytes(&self, length: u64) -> usize {
         match self {
@@ -41,6 +42,9 @@ impl<S: Scalar> ProvableResultColumn for Column<'_, S> {
             Column::Decimal75(_, _, col) | Column::Scalar(col) => col.num_bytes(length),
             Column::VarChar((col, _)) => col.num_bytes(length),
             Column::VarBinary((col, _)) => col.num_bytes(length),
+            Column::Nullable(values, presence) => {
+                values.num_bytes(length) + presence.num_bytes(length)
+            }
         }
     }
 
@@ -56,6 +60,10 @@ impl<S: Scalar> ProvableResultColumn for Column<'_, S> {
             Column::Decimal75(_, _, col) | Column::Scalar(col) => col.write(out, length),
             Column::VarChar((col, _)) => col.write(out, length),
             Column::VarBinary((col, _)) => col.write(out, length),
+            Column::Nullable(values, presence) => {
+                let num_bytes = values.write(out, length);
+                num_bytes + presence.write(&mut out[num_bytes..], length)
+            }
         }
     }
 }
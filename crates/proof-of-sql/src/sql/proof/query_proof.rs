@@ -6,7 +6,7 @@ use super::{
 use crate::{
     base::{
         bit::BitDistribution,
-        commitment::CommitmentEvaluationProof,
+        commitment::{BatchedProofVerification, CommitmentEvaluationProof},
         database::{
             ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable, Table,
             TableRef,
@@ -15,6 +15,7 @@ use crate::{
         math::log2_up,
         polynomial::{compute_evaluation_vector, MultilinearExtension},
         proof::{Keccak256Transcript, ProofError, Transcript},
+        scalar::Scalar,
     },
     proof_primitive::sumcheck::SumcheckProof,
     utils::log,
@@ -45,6 +46,38 @@ fn get_index_range<'a>(
         .unwrap_or((0, 1))
 }
 
+/// Return the tables referenced in any of the queries
+fn get_table_references(exprs: &[impl ProofPlan]) -> IndexSet<TableRef> {
+    exprs
+        .iter()
+        .flat_map(ProofPlan::get_table_references)
+        .collect()
+}
+
+/// Return the columns referenced in any of the queries
+fn get_column_references(exprs: &[impl ProofPlan]) -> IndexSet<ColumnRef> {
+    exprs
+        .iter()
+        .flat_map(ProofPlan::get_column_references)
+        .collect()
+}
+
+/// Add the queries and their results to the transcript.
+///
+/// Note: a single query is added just as it was before queries could be proven together.
+fn extend_transcript_with_queries<S: Scalar>(
+    transcript: &mut impl Transcript,
+    exprs: &[impl ProofPlan + Serialize],
+    results: &[OwnedTable<S>],
+) {
+    for expr in exprs {
+        transcript.extend_serialize_as_le(expr);
+    }
+    for result in results {
+        transcript.extend_serialize_as_le(result);
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct FirstRoundMessage<C> {
     /// Length of the range of generators we use
@@ -94,21 +127,32 @@ pub(super) struct QueryProof<CP: CommitmentEvaluationProof> {
 
 impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     /// Create a new `QueryProof`.
-    #[tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)]
     pub fn new(
         expr: &(impl ProofPlan + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup<'_>,
     ) -> (Self, OwnedTable<CP::Scalar>) {
+        let (proof, mut results) = Self::new_multi(core::slice::from_ref(expr), accessor, setup);
+        let result = results.pop().expect("there is a result for each query");
+        (proof, result)
+    }
+
+    /// Create a new `QueryProof` of many queries, which share a single sumcheck and evaluation proof.
+    #[tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)]
+    pub fn new_multi(
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> (Self, Vec<OwnedTable<CP::Scalar>>) {
         log::log_memory_usage("Start");
 
-        let (min_row_num, max_row_num) = get_index_range(accessor, &expr.get_table_references());
+        let table_refs = get_table_references(exprs);
+        let (min_row_num, max_row_num) = get_index_range(accessor, &table_refs);
         let initial_range_length = max_row_num - min_row_num;
         let alloc = Bump::new();
 
-        let total_col_refs = expr.get_column_references();
-        let table_map: IndexMap<TableRef, Table<CP::Scalar>> = expr
-            .get_table_references()
+        let total_col_refs = get_column_references(exprs);
+        let table_map: IndexMap<TableRef, Table<CP::Scalar>> = table_refs
             .into_iter()
             .map(|table_ref| {
                 let col_refs: IndexSet<ColumnRef> = total_col_refs
@@ -120,11 +164,19 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             })
             .collect();
 
-        // Prover First Round: Evaluate the query && get the right number of post result challenges
+        // Prover First Round: Evaluate the queries && get the right number of post result challenges
         let mut first_round_builder = FirstRoundBuilder::new(initial_range_length);
-        let query_result = expr.first_round_evaluate(&mut first_round_builder, &alloc, &table_map);
-        let owned_table_result = OwnedTable::from(&query_result);
-        let provable_result = query_result.into();
+        first_round_builder.set_table_map(table_map.clone());
+        let owned_table_results: Vec<OwnedTable<CP::Scalar>> = exprs
+            .iter()
+            .map(|expr| {
+                OwnedTable::from(&expr.first_round_evaluate(
+                    &mut first_round_builder,
+                    &alloc,
+                    &table_map,
+                ))
+            })
+            .collect();
         let chi_evaluation_lengths = first_round_builder.chi_evaluation_lengths();
         let rho_evaluation_lengths = first_round_builder.rho_evaluation_lengths();
 
@@ -140,8 +192,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript = Transcript::new();
-        transcript.extend_serialize_as_le(expr);
-        transcript.extend_serialize_as_le(&owned_table_result);
+        extend_transcript_with_queries(&mut transcript, exprs, &owned_table_results);
         transcript.extend_serialize_as_le(&min_row_num);
         transcript.challenge_as_le();
 
@@ -166,8 +217,11 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         let mut final_round_builder =
             FinalRoundBuilder::new(num_sumcheck_variables, post_result_challenges);
+        final_round_builder.set_table_map(table_map.clone());
 
-        expr.final_round_evaluate(&mut final_round_builder, &alloc, &table_map);
+        for expr in exprs {
+            expr.final_round_evaluate(&mut final_round_builder, &alloc, &table_map);
+        }
 
         let num_sumcheck_variables = final_round_builder.num_sumcheck_variables();
 
@@ -272,7 +326,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         log::log_memory_usage("End");
 
-        (proof, provable_result)
+        (proof, owned_table_results)
     }
 
     #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
@@ -284,9 +338,102 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         result: OwnedTable<CP::Scalar>,
         setup: &CP::VerifierPublicSetup<'_>,
     ) -> QueryResult<CP::Scalar> {
+        let query_data = self
+            .verify_multi(core::slice::from_ref(expr), accessor, vec![result], setup)?
+            .pop()
+            .expect("there is a result for each query");
+        Ok(query_data)
+    }
+
+    #[tracing::instrument(name = "QueryProof::verify_multi", level = "debug", skip_all, err)]
+    /// Verify a `QueryProof` of many queries, returning the result of each of them.
+    /// Note: This does NOT transform the results!
+    pub fn verify_multi(
+        self,
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: Vec<OwnedTable<CP::Scalar>>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, ProofError> {
         log::log_memory_usage("Start");
 
-        let table_refs = expr.get_table_references();
+        let mut pending_check = self.verify_without_evaluation_proof(exprs, accessor, results)?;
+        pending_check
+            .verification()
+            .verify(setup)
+            .map_err(|_e| ProofError::VerificationError {
+                error: "Inner product proof of MLE evaluations failed",
+            })?;
+
+        log::log_memory_usage("End");
+
+        Ok(pending_check.finish())
+    }
+
+    #[tracing::instrument(name = "QueryProof::verify_batch", level = "debug", skip_all)]
+    /// Verify many `QueryProof`s, whose evaluation proofs are checked together.
+    /// Returns the outcome of each of them. Note: This does NOT transform the results!
+    pub fn verify_batch<'a, P: ProofPlan + Serialize + 'a>(
+        proofs: impl IntoIterator<Item = (Self, &'a P, OwnedTable<CP::Scalar>)>,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Vec<QueryResult<CP::Scalar>> {
+        log::log_memory_usage("Start");
+
+        let mut pending_checks: Vec<_> = proofs
+            .into_iter()
+            .map(|(proof, expr, result)| {
+                proof.verify_without_evaluation_proof(
+                    core::slice::from_ref(expr),
+                    accessor,
+                    vec![result],
+                )
+            })
+            .collect();
+        let mut evaluation_results = CP::verify_batched_proofs(
+            pending_checks
+                .iter_mut()
+                .filter_map(|pending_check| pending_check.as_mut().ok())
+                .map(PendingEvaluationCheck::verification)
+                .collect(),
+            setup,
+        )
+        .into_iter();
+        let results: Vec<QueryResult<CP::Scalar>> = pending_checks
+            .into_iter()
+            .map(|pending_check| {
+                let pending_check = pending_check?;
+                evaluation_results
+                    .next()
+                    .expect("there is an evaluation result for each pending check")
+                    .map_err(|_e| ProofError::VerificationError {
+                        error: "Inner product proof of MLE evaluations failed",
+                    })?;
+                Ok(pending_check
+                    .finish()
+                    .pop()
+                    .expect("there is a result for each query"))
+            })
+            .collect();
+
+        log::log_memory_usage("End");
+
+        results
+    }
+
+    /// Verify a `QueryProof` except for its evaluation proof, which is returned to be checked.
+    fn verify_without_evaluation_proof(
+        self,
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: Vec<OwnedTable<CP::Scalar>>,
+    ) -> Result<PendingEvaluationCheck<CP>, ProofError> {
+        if exprs.len() != results.len() {
+            Err(ProofError::VerificationError {
+                error: "the number of results does not match the number of queries",
+            })?;
+        }
+        let table_refs = get_table_references(exprs);
         let (min_row_num, _) = get_index_range(accessor, &table_refs);
         let num_sumcheck_variables = cmp::max(log2_up(self.first_round_message.range_length), 1);
         assert!(num_sumcheck_variables > 0);
@@ -304,12 +451,11 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             }
         }
 
-        let column_references = expr.get_column_references();
+        let column_references = get_column_references(exprs);
 
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript = Transcript::new();
-        transcript.extend_serialize_as_le(expr);
-        transcript.extend_serialize_as_le(&result);
+        extend_transcript_with_queries(&mut transcript, exprs, &results);
         transcript.extend_serialize_as_le(&min_row_num);
         transcript.challenge_as_le();
 
@@ -418,20 +564,23 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             .into_iter()
             .zip(self.pcs_proof_evaluations.column_ref.iter().copied())
             .collect();
-
-        let verifier_evaluations = expr.verifier_evaluate(
-            &mut builder,
-            &evaluation_accessor,
-            Some(&result),
-            &chi_eval_map,
-        )?;
-        // compute the evaluation of the result MLEs
-        let result_evaluations = result.mle_evaluations(&subclaim.evaluation_point);
-        // check the evaluation of the result MLEs
-        if verifier_evaluations.column_evals() != result_evaluations {
-            Err(ProofError::VerificationError {
-                error: "result evaluation check failed",
-            })?;
+        builder.set_database_evaluations(evaluation_accessor.clone(), chi_eval_map.clone());
+
+        for (expr, result) in exprs.iter().zip(&results) {
+            let verifier_evaluations = expr.verifier_evaluate(
+                &mut builder,
+                &evaluation_accessor,
+                Some(result),
+                &chi_eval_map,
+            )?;
+            // compute the evaluation of the result MLEs
+            let result_evaluations = result.mle_evaluations(&subclaim.evaluation_point);
+            // check the evaluation of the result MLEs
+            if verifier_evaluations.column_evals() != result_evaluations {
+                Err(ProofError::VerificationError {
+                    error: "result evaluation check failed",
+                })?;
+            }
         }
 
         // perform the evaluation check of the sumcheck polynomial
@@ -450,29 +599,58 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             .copied()
             .collect();
 
-        // finally, check the MLE evaluations with the inner product proof
-        self.evaluation_proof
-            .verify_batched_proof(
-                &mut transcript,
-                &pcs_proof_commitments,
-                &evaluation_random_scalars,
-                &pcs_proof_evaluations,
-                &subclaim.evaluation_point,
-                min_row_num as u64,
-                self.first_round_message.range_length,
-                setup,
-            )
-            .map_err(|_e| ProofError::VerificationError {
-                error: "Inner product proof of MLE evaluations failed",
-            })?;
+        // finally, the MLE evaluations are left to be checked with the inner product proof
+        Ok(PendingEvaluationCheck {
+            evaluation_proof: self.evaluation_proof,
+            transcript,
+            commitments: pcs_proof_commitments,
+            batching_factors: evaluation_random_scalars,
+            evaluations: pcs_proof_evaluations,
+            evaluation_point: subclaim.evaluation_point,
+            generators_offset: min_row_num as u64,
+            table_length: self.first_round_message.range_length,
+            results,
+        })
+    }
+}
 
-        let verification_hash = transcript.challenge_as_le();
+/// A `QueryProof` that is verified except for the inner product proof of its MLE evaluations.
+struct PendingEvaluationCheck<CP: CommitmentEvaluationProof> {
+    evaluation_proof: CP,
+    transcript: Keccak256Transcript,
+    commitments: Vec<CP::Commitment>,
+    batching_factors: Vec<CP::Scalar>,
+    evaluations: Vec<CP::Scalar>,
+    evaluation_point: Vec<CP::Scalar>,
+    generators_offset: u64,
+    table_length: usize,
+    results: Vec<OwnedTable<CP::Scalar>>,
+}
 
-        log::log_memory_usage("End");
+impl<CP: CommitmentEvaluationProof> PendingEvaluationCheck<CP> {
+    /// The check of the inner product proof.
+    fn verification(&mut self) -> BatchedProofVerification<'_, CP, Keccak256Transcript> {
+        BatchedProofVerification {
+            proof: &self.evaluation_proof,
+            transcript: &mut self.transcript,
+            commit_batch: &self.commitments,
+            batching_factors: &self.batching_factors,
+            evaluations: &self.evaluations,
+            b_point: &self.evaluation_point,
+            generators_offset: self.generators_offset,
+            table_length: self.table_length,
+        }
+    }
 
-        Ok(QueryData {
-            table: result,
-            verification_hash,
-        })
+    /// Finishes the verification once the inner product proof is checked.
+    fn finish(mut self) -> Vec<QueryData<CP::Scalar>> {
+        let verification_hash = self.transcript.challenge_as_le();
+        self.results
+            .into_iter()
+            .map(|table| QueryData {
+                table,
+                verification_hash,
+            })
+            .collect()
     }
 }
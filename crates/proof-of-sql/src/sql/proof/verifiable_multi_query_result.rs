@@ -0,0 +1,108 @@
+use super::{
+    verifiable_query_result::{coerce_query_data, make_empty_query_result},
+    ProofPlan, QueryProof, QueryResult,
+};
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable},
+        proof::ProofError,
+    },
+    utils::log,
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The results of many sql queries along with a single proof that they are all valid.
+///
+/// This is like a [`VerifiableQueryResult`](super::VerifiableQueryResult) for each of the queries,
+/// except that the queries share a transcript, a sumcheck and an evaluation proof, so that the
+/// proof is smaller and faster to create and verify. This is useful when the same tables are queried
+/// many times at once, e.g. by a dashboard.
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct VerifiableMultiQueryResult<CP: CommitmentEvaluationProof> {
+    /// The results of the queries in intermediate form.
+    pub(super) results: Option<Vec<OwnedTable<CP::Scalar>>>,
+    /// The proof that the query results are valid.
+    pub(super) proof: Option<QueryProof<CP>>,
+}
+
+impl<CP: CommitmentEvaluationProof> VerifiableMultiQueryResult<CP> {
+    /// Form a `VerifiableMultiQueryResult` from many query expressions.
+    ///
+    /// This function both computes the results of the queries and constructs a single proof of
+    /// their validity.
+    #[tracing::instrument(name = "VerifiableMultiQueryResult::new", level = "info", skip_all)]
+    pub fn new(
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        log::log_memory_usage("Start");
+
+        // handle the empty case
+        if all_tables_are_empty(exprs, accessor) {
+            return Self {
+                results: None,
+                proof: None,
+            };
+        }
+
+        let (proof, results) = QueryProof::new_multi(exprs, accessor, setup);
+
+        log::log_memory_usage("End");
+
+        Self {
+            results: Some(results),
+            proof: Some(proof),
+        }
+    }
+
+    /// Verify a `VerifiableMultiQueryResult`, where `exprs` must be the queries it was created from in
+    /// the same order. Upon success, this function returns the finalized form of the result of each query.
+    ///
+    /// Note: a verified result can still respresent an error (e.g. overflow), but it is a verified
+    /// error. Such errors are returned for the affected queries only.
+    ///
+    /// Note: This does NOT transform the results!
+    #[tracing::instrument(name = "VerifiableMultiQueryResult::verify", level = "info", skip_all)]
+    pub fn verify(
+        self,
+        exprs: &[impl ProofPlan + Serialize],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryResult<CP::Scalar>>, ProofError> {
+        log::log_memory_usage("Start");
+
+        let results = match (self.results, self.proof) {
+            (Some(results), Some(proof)) => proof
+                .verify_multi(exprs, accessor, results, setup)?
+                .into_iter()
+                .zip(exprs)
+                .map(|(query_data, expr)| coerce_query_data(expr, query_data))
+                .collect(),
+            (None, None) if all_tables_are_empty(exprs, accessor) => exprs
+                .iter()
+                .map(|expr| make_empty_query_result(&expr.get_column_result_fields()))
+                .collect(),
+            _ => Err(ProofError::VerificationError {
+                error: "Proof does not match results: at least one is missing",
+            })?,
+        };
+
+        log::log_memory_usage("End");
+
+        Ok(results)
+    }
+}
+
+fn all_tables_are_empty(exprs: &[impl ProofPlan], accessor: &impl MetadataAccessor) -> bool {
+    exprs
+        .iter()
+        .flat_map(ProofPlan::get_table_references)
+        .all(|table_ref| accessor.get_length(&table_ref) == 0)
+}
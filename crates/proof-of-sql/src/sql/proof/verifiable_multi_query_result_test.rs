@@ -0,0 +1,194 @@
+use super::{VerifiableMultiQueryResult, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+    },
+    sql::{
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+
+fn accessor_with_two_tables() -> (
+    TableRef,
+    TableRef,
+    OwnedTableTestAccessor<InnerProductProof>,
+) {
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 2, 1, 2, 3]),
+            bigint("b", [10_i64, 20, 30, 40, 50]),
+        ]),
+        0,
+        (),
+    );
+    accessor.add_table(
+        u.clone(),
+        owned_table([bigint("c", [5_i64, 6, 7]), varchar("d", ["x", "y", "z"])]),
+        3,
+    );
+    (t, u, accessor)
+}
+
+fn plans(
+    t: &TableRef,
+    u: &TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Vec<DynProofPlan> {
+    vec![
+        filter(
+            cols_expr_plan(t, &["b"], accessor),
+            tab(t),
+            equal(column(t, "a", accessor), const_bigint(2)),
+        ),
+        group_by(
+            cols_expr_plan(t, &["a"], accessor),
+            vec![sum_expr(column(t, "b", accessor), "sum_b")],
+            "__count__",
+            tab(t),
+            const_bool(true),
+        ),
+        filter(
+            cols_expr_plan(u, &["d"], accessor),
+            tab(u),
+            gte(column(u, "c", accessor), const_bigint(6)),
+        ),
+    ]
+}
+
+#[test]
+fn we_can_prove_and_verify_many_queries_at_once() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor);
+    let res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    let tables: Vec<OwnedTable<_>> = res
+        .verify(&exprs, &accessor, &())
+        .unwrap()
+        .into_iter()
+        .map(|query_result| query_result.unwrap().table)
+        .collect();
+    let expected = vec![
+        owned_table([bigint("b", [20_i64, 40])]),
+        owned_table([
+            bigint("a", [1_i64, 2, 3]),
+            bigint("sum_b", [40_i64, 60, 50]),
+            bigint("__count__", [2_i64, 2, 1]),
+        ]),
+        owned_table([varchar("d", ["y", "z"])]),
+    ];
+    assert_eq!(tables, expected);
+
+    // The results agree with proving each query on its own
+    for (expr, table) in exprs.iter().zip(&tables) {
+        let individual = VerifiableQueryResult::new(expr, &accessor, &())
+            .verify(expr, &accessor, &())
+            .unwrap()
+            .table;
+        assert_eq!(&individual, table);
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_a_single_query_as_a_multi_query() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor)[..1].to_vec();
+    let res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    let tables = res.verify(&exprs, &accessor, &()).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(
+        tables[0].as_ref().unwrap().table,
+        owned_table([bigint("b", [20_i64, 40])])
+    );
+}
+
+#[test]
+fn we_can_verify_many_queries_against_empty_tables() {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]),
+        0,
+        (),
+    );
+    let exprs = vec![
+        filter(
+            cols_expr_plan(&t, &["b"], &accessor),
+            tab(&t),
+            equal(column(&t, "a", &accessor), const_bigint(2)),
+        ),
+        filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+    ];
+    let res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    assert!(res.proof.is_none());
+    let tables: Vec<_> = res
+        .verify(&exprs, &accessor, &())
+        .unwrap()
+        .into_iter()
+        .map(|query_result| query_result.unwrap().table)
+        .collect();
+    assert_eq!(
+        tables,
+        vec![
+            owned_table([bigint("b", [0_i64; 0])]),
+            owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]),
+        ]
+    );
+}
+
+#[test]
+fn we_cannot_verify_many_queries_with_tampered_results() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor);
+    let mut res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    res.results.as_mut().unwrap()[2] = owned_table([varchar("d", ["y", "y"])]);
+    assert!(res.verify(&exprs, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_many_queries_with_a_missing_or_extra_result() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor);
+    let res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+
+    let mut missing = res.clone();
+    missing.results.as_mut().unwrap().pop();
+    assert!(missing.verify(&exprs, &accessor, &()).is_err());
+
+    let mut extra = res.clone();
+    extra
+        .results
+        .as_mut()
+        .unwrap()
+        .push(owned_table([bigint("b", [20_i64, 40])]));
+    assert!(extra.verify(&exprs, &accessor, &()).is_err());
+
+    assert!(res.verify(&exprs[..2], &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_many_queries_in_a_different_order() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor);
+    let res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    let reordered = vec![exprs[2].clone(), exprs[1].clone(), exprs[0].clone()];
+    assert!(res.verify(&reordered, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_verify_many_queries_with_a_missing_proof() {
+    let (t, u, accessor) = accessor_with_two_tables();
+    let exprs = plans(&t, &u, &accessor);
+    let mut res = VerifiableMultiQueryResult::new(&exprs, &accessor, &());
+    res.proof = None;
+    assert!(res.verify(&exprs, &accessor, &()).is_err());
+}
@@ -3,14 +3,15 @@ use crate::{
     base::{
         commitment::CommitmentEvaluationProof,
         database::{
-            ColumnField, ColumnType, CommitmentAccessor, DataAccessor, OwnedColumn, OwnedTable,
+            ColumnComponent, ColumnField, ColumnType, CommitmentAccessor, DataAccessor,
+            OwnedColumn, OwnedTable,
         },
         proof::ProofError,
         scalar::Scalar,
     },
     utils::log,
 };
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 /// The result of an sql query along with a proof that the query is valid. The
@@ -133,14 +134,7 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
 
         match (self.result, self.proof) {
             (Some(result), Some(proof)) => {
-                let QueryData {
-                    table,
-                    verification_hash,
-                } = proof.verify(expr, accessor, result, setup)?;
-                Ok(QueryData {
-                    table: table.try_coerce_with_fields(expr.get_column_result_fields())?,
-                    verification_hash,
-                })
+                coerce_query_data(expr, proof.verify(expr, accessor, result, setup)?)
             }
             (None, None)
                 if expr
@@ -156,13 +150,81 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
             })?,
         }
     }
+
+    /// Verify many `VerifiableQueryResult`s, each with the query expression it is the result of.
+    /// Returns the outcome of verifying each of them, in the same order, just as [`Self::verify`] would.
+    ///
+    /// Where the commitment scheme supports it, which are the Dory and `HyperKZG` schemes,
+    /// the final checks of the evaluation proofs are folded together with a random linear combination,
+    /// which is much cheaper than checking each of them.
+    /// The proofs are only checked individually if the folded check fails.
+    /// See [`CommitmentEvaluationProof::verify_batched_proofs`].
+    #[tracing::instrument(name = "VerifiableQueryResult::verify_batch", level = "info", skip_all)]
+    pub fn verify_batch<'a, P: ProofPlan + Serialize + 'a>(
+        verifiable_results: impl IntoIterator<Item = (Self, &'a P)>,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Vec<QueryResult<CP::Scalar>> {
+        log::log_memory_usage("Start");
+
+        let mut proofs = Vec::new();
+        let individual_results: Vec<_> = verifiable_results
+            .into_iter()
+            .map(|(verifiable_result, expr)| match verifiable_result {
+                Self {
+                    result: Some(result),
+                    proof: Some(proof),
+                } => {
+                    proofs.push((proof, expr, result));
+                    None
+                }
+                // There is no evaluation proof to check
+                _ => Some(verifiable_result.verify(expr, accessor, setup)),
+            })
+            .collect();
+        let exprs: Vec<_> = proofs.iter().map(|(_, expr, _)| *expr).collect();
+        let mut batch_results = QueryProof::verify_batch(proofs, accessor, setup)
+            .into_iter()
+            .zip(exprs)
+            .map(|(result, expr)| coerce_query_data(expr, result?));
+        let results = individual_results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    batch_results
+                        .next()
+                        .expect("there is a batch result for each proof")
+                })
+            })
+            .collect();
+
+        log::log_memory_usage("End");
+
+        results
+    }
+}
+
+pub(super) fn coerce_query_data<S: Scalar>(
+    expr: &impl ProofPlan,
+    QueryData {
+        table,
+        verification_hash,
+    }: QueryData<S>,
+) -> QueryResult<S> {
+    Ok(QueryData {
+        table: table.try_coerce_with_fields(expr.get_column_result_fields())?,
+        verification_hash,
+    })
 }
 
-fn make_empty_query_result<S: Scalar>(result_fields: &[ColumnField]) -> QueryResult<S> {
+pub(super) fn make_empty_query_result<S: Scalar>(result_fields: &[ColumnField]) -> QueryResult<S> {
     let table = OwnedTable::try_new(
         result_fields
             .iter()
+            // The presence of a nullable column follows its values
+            .filter(|field| field.component() == ColumnComponent::Values)
             .map(|field| {
+                let presence = result_fields.contains(&field.presence()).then(Vec::new);
                 (
                     field.name(),
                     match field.data_type() {
@@ -180,7 +242,9 @@ fn make_empty_query_result<S: Scalar>(result_fields: &[ColumnField]) -> QueryRes
                         ColumnType::VarChar => OwnedColumn::VarChar(vec![]),
                         ColumnType::VarBinary => OwnedColumn::VarBinary(vec![]),
                         ColumnType::TimestampTZ(tu, tz) => OwnedColumn::TimestampTZ(tu, tz, vec![]),
-                    },
+                    }
+                    .try_new_nullable(presence)
+                    .expect("an empty presence has the length of empty values"),
                 )
             })
             .collect(),
@@ -127,6 +127,10 @@ fn append_single_row_to_column<S: Scalar>(column: &OwnedColumn<S>) -> OwnedColum
         OwnedColumn::VarBinary(col) => col.push(vec![0u8]),
         OwnedColumn::Int128(col) => col.push(0),
         OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => col.push(S::ZERO),
+        OwnedColumn::Nullable(values, presence) => {
+            **values = append_single_row_to_column(values);
+            presence.push(true);
+        }
     }
     column
 }
@@ -164,6 +168,7 @@ pub fn tamper_first_row_of_column<S: Scalar>(column: &OwnedColumn<S>) -> OwnedCo
         OwnedColumn::VarBinary(col) => col[0].push(1u8),
         OwnedColumn::Int128(col) => col[0] = col[0].wrapping_add(1),
         OwnedColumn::Decimal75(_, _, col) | OwnedColumn::Scalar(col) => col[0] += S::ONE,
+        OwnedColumn::Nullable(_, presence) => presence[0] ^= true,
     }
     column
 }
@@ -1,5 +1,11 @@
 use super::{SumcheckMleEvaluations, SumcheckSubpolynomialType};
-use crate::base::{bit::BitDistribution, proof::ProofSizeMismatch, scalar::Scalar};
+use crate::base::{
+    bit::BitDistribution,
+    database::{ColumnRef, TableRef},
+    map::IndexMap,
+    proof::ProofSizeMismatch,
+    scalar::Scalar,
+};
 use alloc::{collections::VecDeque, vec::Vec};
 use core::iter;
 
@@ -47,6 +53,13 @@ pub trait VerificationBuilder<S: Scalar> {
 
     /// Retrieves the `rho_256_evaluation` from the `mle_evaluations`
     fn rho_256_evaluation(&self) -> Option<S>;
+
+    /// The evaluations of the columns of the database that the query references,
+    /// which subqueries of expressions are verified against as well
+    fn column_evaluations(&self) -> &IndexMap<ColumnRef, S>;
+
+    /// The chi evaluations of the tables of the database that the query references
+    fn table_chi_evaluations(&self) -> &IndexMap<TableRef, S>;
 }
 
 /// Track components used to verify a query's proof
@@ -71,6 +84,8 @@ pub struct VerificationBuilderImpl<'a, S: Scalar> {
     chi_evaluation_length_queue: Vec<usize>,
     rho_evaluation_length_queue: Vec<usize>,
     subpolynomial_max_multiplicands: usize,
+    column_evaluations: IndexMap<ColumnRef, S>,
+    table_chi_evaluations: IndexMap<TableRef, S>,
 }
 
 impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
@@ -102,9 +117,22 @@ impl<'a, S: Scalar> VerificationBuilderImpl<'a, S> {
             chi_evaluation_length_queue,
             rho_evaluation_length_queue,
             subpolynomial_max_multiplicands,
+            column_evaluations: IndexMap::default(),
+            table_chi_evaluations: IndexMap::default(),
         }
     }
 
+    /// Set the evaluations of the columns and the chi evaluations of the tables
+    /// of the database that the query references.
+    pub(crate) fn set_database_evaluations(
+        &mut self,
+        column_evaluations: IndexMap<ColumnRef, S>,
+        table_chi_evaluations: IndexMap<TableRef, S>,
+    ) {
+        self.column_evaluations = column_evaluations;
+        self.table_chi_evaluations = table_chi_evaluations;
+    }
+
     #[allow(
         clippy::missing_panics_doc,
         reason = "The panic condition is clear due to the assertion that checks if the computation is completed."
@@ -245,4 +273,12 @@ impl<S: Scalar> VerificationBuilder<S> for VerificationBuilderImpl<'_, S> {
     fn rho_256_evaluation(&self) -> Option<S> {
         self.mle_evaluations.rho_256_evaluation
     }
+
+    fn column_evaluations(&self) -> &IndexMap<ColumnRef, S> {
+        &self.column_evaluations
+    }
+
+    fn table_chi_evaluations(&self) -> &IndexMap<TableRef, S> {
+        &self.table_chi_evaluations
+    }
 }
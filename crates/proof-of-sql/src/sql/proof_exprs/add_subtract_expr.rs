@@ -6,7 +6,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
     utils::log,
 };
 use alloc::boxed::Box;
@@ -55,6 +55,24 @@ impl ProofExpr for AddSubtractExpr {
         ))
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.first_round_evaluate(builder, alloc, table);
+        Column::Scalar(add_subtract_columns(
+            lhs_column,
+            rhs_column,
+            self.lhs.data_type().scale().unwrap_or(0),
+            self.rhs.data_type().scale().unwrap_or(0),
+            alloc,
+            self.is_subtract,
+        ))
+    }
+
     #[tracing::instrument(
         name = "proofs.sql.ast.add_subtract_expr.prover_evaluate",
         level = "info",
@@ -103,4 +121,8 @@ impl ProofExpr for AddSubtractExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
 }
@@ -6,7 +6,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
     utils::log,
 };
 use alloc::boxed::Box;
@@ -16,17 +16,34 @@ use serde::{Deserialize, Serialize};
 
 /// Provable aggregate expression
 ///
-/// Currently it doesn't do much since aggregation logic is implemented elsewhere
+/// Currently it doesn't do much since aggregation logic is implemented elsewhere.
+/// In particular, evaluating it evaluates the aggregated expression only,
+/// and the rows where its filter does not hold are left out by the aggregation.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AggregateExpr {
     op: AggregationOperator,
     expr: Box<DynProofExpr>,
+    filter: Option<Box<DynProofExpr>>,
 }
 
 impl AggregateExpr {
     /// Create a new aggregate expression
-    pub fn new(op: AggregationOperator, expr: Box<DynProofExpr>) -> Self {
-        Self { op, expr }
+    pub fn new(
+        op: AggregationOperator,
+        expr: Box<DynProofExpr>,
+        filter: Option<Box<DynProofExpr>>,
+    ) -> Self {
+        Self { op, expr, filter }
+    }
+
+    /// The aggregation operator of the expression
+    pub fn op(&self) -> AggregationOperator {
+        self.op
+    }
+
+    /// The boolean expression of the `FILTER` clause, if any, which is `true` on the aggregated rows
+    pub fn filter(&self) -> Option<&DynProofExpr> {
+        self.filter.as_deref()
     }
 }
 
@@ -35,7 +52,9 @@ impl ProofExpr for AggregateExpr {
     fn data_type(&self) -> ColumnType {
         match self.op {
             AggregationOperator::Count => ColumnType::BigInt,
-            AggregationOperator::Sum => self.expr.data_type(),
+            AggregationOperator::Sum | AggregationOperator::Max | AggregationOperator::Min => {
+                self.expr.data_type()
+            }
             _ => todo!("Aggregation operator not supported here yet"),
         }
     }
@@ -55,6 +74,21 @@ impl ProofExpr for AggregateExpr {
         res
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let res = self.expr.first_round_evaluate(builder, alloc, table);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
     #[tracing::instrument(name = "AggregateExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a, S: Scalar>(
         &self,
@@ -82,5 +116,12 @@ impl ProofExpr for AggregateExpr {
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
+        if let Some(filter) = &self.filter {
+            filter.get_column_references(columns);
+        }
+    }
+
+    fn is_row_wise(&self) -> bool {
+        false
     }
 }
@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{nullable_column, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -6,7 +6,9 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
     utils::log,
 };
 use alloc::{boxed::Box, vec};
@@ -14,6 +16,9 @@ use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
 /// Provable logical AND expression
+///
+/// If either side is nullable, it follows three-valued logic: the result is FALSE if either side is FALSE,
+/// and NULL otherwise if either side is NULL.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AndExpr {
     lhs: Box<DynProofExpr>,
@@ -42,10 +47,24 @@ impl ProofExpr for AndExpr {
 
         let lhs_column: Column<'a, S> = self.lhs.result_evaluate(alloc, table);
         let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
-        let lhs = lhs_column.as_boolean().expect("lhs is not boolean");
-        let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
-        let res =
-            Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| lhs[i] && rhs[i]));
+        let res = result_evaluate_and(table.num_rows(), alloc, lhs_column, rhs_column);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.first_round_evaluate(builder, alloc, table);
+        let res = result_evaluate_and(table.num_rows(), alloc, lhs_column, rhs_column);
 
         log::log_memory_usage("End");
 
@@ -67,6 +86,7 @@ impl ProofExpr for AndExpr {
         let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
         let n = lhs.len();
         assert_eq!(n, rhs.len());
+        let (lhs_presence, rhs_presence) = (lhs_column.presence(), rhs_column.presence());
 
         // lhs_and_rhs
         let lhs_and_rhs: &[bool] = alloc.alloc_slice_fill_with(n, |i| lhs[i] && rhs[i]);
@@ -80,7 +100,37 @@ impl ProofExpr for AndExpr {
                 (-S::one(), vec![Box::new(lhs), Box::new(rhs)]),
             ],
         );
-        let res = Column::Boolean(lhs_and_rhs);
+
+        // presence: lhs_presence * rhs_presence + lhs_presence * !lhs + rhs_presence * !rhs
+        let presence = (lhs_presence.is_some() || rhs_presence.is_some()).then(|| {
+            let lhs_presence = lhs_presence.unwrap_or(alloc.alloc_slice_fill_copy(n, true));
+            let rhs_presence = rhs_presence.unwrap_or(alloc.alloc_slice_fill_copy(n, true));
+            let presence: &[_] = alloc.alloc_slice_fill_with(n, |i| {
+                and_presence(lhs[i], rhs[i], lhs_presence[i], rhs_presence[i])
+            });
+            builder.produce_intermediate_mle(presence);
+
+            // subpolynomial: presence - lhs_presence - rhs_presence + lhs + rhs
+            //   + lhs_presence * rhs_presence - lhs_presence * rhs - lhs * rhs_presence
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(presence)]),
+                    (-S::one(), vec![Box::new(lhs_presence)]),
+                    (-S::one(), vec![Box::new(rhs_presence)]),
+                    (S::one(), vec![Box::new(lhs)]),
+                    (S::one(), vec![Box::new(rhs)]),
+                    (
+                        S::one(),
+                        vec![Box::new(lhs_presence), Box::new(rhs_presence)],
+                    ),
+                    (-S::one(), vec![Box::new(lhs_presence), Box::new(rhs)]),
+                    (-S::one(), vec![Box::new(lhs), Box::new(rhs_presence)]),
+                ],
+            );
+            presence
+        });
+        let res = nullable_column(alloc, Column::Boolean(lhs_and_rhs), presence);
 
         log::log_memory_usage("End");
 
@@ -93,8 +143,26 @@ impl ProofExpr for AndExpr {
         accessor: &IndexMap<ColumnRef, S>,
         chi_eval: S,
     ) -> Result<S, ProofError> {
-        let lhs = self.lhs.verifier_evaluate(builder, accessor, chi_eval)?;
-        let rhs = self.rhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        self.verifier_evaluate_nullable(builder, accessor, chi_eval)
+            .map(|(eval, _)| eval)
+    }
+
+    fn is_nullable(&self) -> bool {
+        self.lhs.is_nullable() || self.rhs.is_nullable()
+    }
+
+    fn verifier_evaluate_nullable<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<(S, Option<S>), ProofError> {
+        let (lhs, lhs_presence) = self
+            .lhs
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
+        let (rhs, rhs_presence) = self
+            .rhs
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
 
         // lhs_and_rhs
         let lhs_and_rhs = builder.try_consume_final_round_mle_evaluation()?;
@@ -106,12 +174,64 @@ impl ProofExpr for AndExpr {
             2,
         )?;
 
-        // selection
-        Ok(lhs_and_rhs)
+        if lhs_presence.is_none() && rhs_presence.is_none() {
+            return Ok((lhs_and_rhs, None));
+        }
+        let lhs_presence = lhs_presence.unwrap_or(chi_eval);
+        let rhs_presence = rhs_presence.unwrap_or(chi_eval);
+
+        // presence
+        let presence = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: presence - lhs_presence - rhs_presence + lhs + rhs
+        //   + lhs_presence * rhs_presence - lhs_presence * rhs - lhs * rhs_presence
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            presence - lhs_presence - rhs_presence + lhs + rhs + lhs_presence * rhs_presence
+                - lhs_presence * rhs
+                - lhs * rhs_presence,
+            2,
+        )?;
+
+        Ok((lhs_and_rhs, Some(presence)))
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
+}
+
+/// Whether `lhs AND rhs` is not NULL, which is when both sides are not NULL
+/// or when either side is FALSE
+fn and_presence(lhs: bool, rhs: bool, lhs_presence: bool, rhs_presence: bool) -> bool {
+    (lhs_presence && rhs_presence) || (lhs_presence && !lhs) || (rhs_presence && !rhs)
+}
+
+/// Evaluates `lhs AND rhs` with three-valued logic, where the values of NULL rows are FALSE
+fn result_evaluate_and<'a, S: Scalar>(
+    table_length: usize,
+    alloc: &'a Bump,
+    lhs_column: Column<'a, S>,
+    rhs_column: Column<'a, S>,
+) -> Column<'a, S> {
+    let lhs = lhs_column.as_boolean().expect("lhs is not boolean");
+    let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
+    let values = Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| lhs[i] && rhs[i]));
+    let presence = match (lhs_column.presence(), rhs_column.presence()) {
+        (None, None) => None,
+        (lhs_presence, rhs_presence) => Some(&*alloc.alloc_slice_fill_with(table_length, |i| {
+            and_presence(
+                lhs[i],
+                rhs[i],
+                lhs_presence.is_none_or(|presence| presence[i]),
+                rhs_presence.is_none_or(|presence| presence[i]),
+            )
+        })),
+    };
+    nullable_column(alloc, values, presence)
 }
@@ -0,0 +1,264 @@
+use super::{scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Curve25519Scalar, Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::comparison_util::scale_and_subtract_literal,
+        proof_gadgets::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+
+/// The largest combined number of decimal digits of the two differences
+/// `expr - low` and `expr - high` for which a `BETWEEN` can be proven.
+///
+/// Keeping their product below `10^74 < 2^248` guarantees that it has a unique sign representation.
+const MAX_PRODUCT_DIGITS: u8 = 74;
+
+/// Provable AST expression for a `BETWEEN` expression
+///
+/// For bounds `low <= high`, `expr BETWEEN low AND high` holds exactly where
+/// `(expr - low) * (expr - high)` is not positive, i.e. where the product minus one is negative.
+/// This checks both bounds with a single sign decomposition instead of one per inequality.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BetweenExpr {
+    expr: Box<DynProofExpr>,
+    low: Box<DynProofExpr>,
+    high: Box<DynProofExpr>,
+}
+
+impl BetweenExpr {
+    /// Create a new `BETWEEN` expression
+    pub fn new(expr: Box<DynProofExpr>, low: Box<DynProofExpr>, high: Box<DynProofExpr>) -> Self {
+        Self { expr, low, high }
+    }
+
+    /// Whether the product test decides `expr BETWEEN low AND high`.
+    ///
+    /// This requires literal bounds with `low <= high`, since the product is also non-positive
+    /// between swapped bounds, and a product that is small enough for the sign decomposition.
+    pub(crate) fn is_provable(
+        expr: &DynProofExpr,
+        low: &DynProofExpr,
+        high: &DynProofExpr,
+    ) -> bool {
+        let (DynProofExpr::Literal(low), DynProofExpr::Literal(high)) = (low, high) else {
+            return false;
+        };
+        let expr_type = expr.data_type();
+        let (low_type, high_type) = (low.value.column_type(), high.value.column_type());
+        let (Some(low_digits), Some(high_digits)) = (
+            difference_digits(expr_type, low_type),
+            difference_digits(expr_type, high_type),
+        ) else {
+            return false;
+        };
+        low_digits + high_digits <= MAX_PRODUCT_DIGITS
+            && scale_and_subtract_literal::<Curve25519Scalar>(
+                &high.value,
+                &low.value,
+                high_type.scale().unwrap_or(0),
+                low_type.scale().unwrap_or(0),
+                false,
+            )
+            .is_ok_and(|width| width.signed_cmp(&Curve25519Scalar::ZERO) != Ordering::Less)
+    }
+
+    /// Computes `expr - low` and `expr - high`
+    ///
+    /// # Panics
+    /// Panics if a bound can not be compared to the expression,
+    /// which `DynProofExpr::try_new_between` rules out.
+    fn differences<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        expr_column: Column<'a, S>,
+        low_column: Column<'a, S>,
+        high_column: Column<'a, S>,
+    ) -> (&'a [S], &'a [S]) {
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        let low_scale = self.low.data_type().scale().unwrap_or(0);
+        let high_scale = self.high.data_type().scale().unwrap_or(0);
+        let low_difference =
+            scale_and_subtract(alloc, expr_column, low_column, expr_scale, low_scale, false)
+                .expect("Failed to scale and subtract");
+        let high_difference = scale_and_subtract(
+            alloc,
+            expr_column,
+            high_column,
+            expr_scale,
+            high_scale,
+            false,
+        )
+        .expect("Failed to scale and subtract");
+        (low_difference, high_difference)
+    }
+}
+
+/// Returns the number of decimal digits of the difference of two values of the given types
+/// once they are scaled to a common scale, or `None` if they are not numeric.
+fn difference_digits(lhs_type: ColumnType, rhs_type: ColumnType) -> Option<u8> {
+    let (lhs_scale, rhs_scale) = (lhs_type.scale()?, rhs_type.scale()?);
+    let scale = lhs_scale.max(rhs_scale);
+    let lhs_digits = lhs_type.precision_value()? + scale.abs_diff(lhs_scale);
+    let rhs_digits = rhs_type.precision_value()? + scale.abs_diff(rhs_scale);
+    Some(lhs_digits.max(rhs_digits) + 1)
+}
+
+impl ProofExpr for BetweenExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.result_evaluate(alloc, table);
+        let low_column = self.low.result_evaluate(alloc, table);
+        let high_column = self.high.result_evaluate(alloc, table);
+        let (low_difference, high_difference) =
+            self.differences(alloc, expr_column, low_column, high_column);
+        let shifted_product = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            low_difference[i] * high_difference[i] - S::one()
+        });
+
+        // (sign(shifted_product) == -1)
+        let res = Column::Boolean(result_evaluate_sign(
+            table.num_rows(),
+            alloc,
+            shifted_product,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.first_round_evaluate(builder, alloc, table);
+        let low_column = self.low.first_round_evaluate(builder, alloc, table);
+        let high_column = self.high.first_round_evaluate(builder, alloc, table);
+        let (low_difference, high_difference) =
+            self.differences(alloc, expr_column, low_column, high_column);
+        let shifted_product = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            low_difference[i] * high_difference[i] - S::one()
+        });
+
+        // (sign(shifted_product) == -1)
+        let res = Column::Boolean(result_evaluate_sign(
+            table.num_rows(),
+            alloc,
+            shifted_product,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.prover_evaluate(builder, alloc, table);
+        let low_column = self.low.prover_evaluate(builder, alloc, table);
+        let high_column = self.high.prover_evaluate(builder, alloc, table);
+        let (low_difference, high_difference) =
+            self.differences(alloc, expr_column, low_column, high_column);
+
+        // product
+        let product: &[_] = alloc
+            .alloc_slice_fill_with(table.num_rows(), |i| low_difference[i] * high_difference[i]);
+        builder.produce_intermediate_mle(product);
+
+        // subpolynomial: product - low_difference * high_difference
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(product)]),
+                (
+                    -S::one(),
+                    vec![Box::new(low_difference), Box::new(high_difference)],
+                ),
+            ],
+        );
+
+        // (sign(product - 1) == -1)
+        let shifted_product =
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| product[i] - S::one());
+        let res = Column::Boolean(prover_evaluate_sign(builder, alloc, shifted_product));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let low_eval = self.low.verifier_evaluate(builder, accessor, chi_eval)?;
+        let high_eval = self.high.verifier_evaluate(builder, accessor, chi_eval)?;
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        let low_scale = self.low.data_type().scale().unwrap_or(0);
+        let high_scale = self.high.data_type().scale().unwrap_or(0);
+        let low_difference_eval =
+            scale_and_add_subtract_eval(expr_eval, low_eval, expr_scale, low_scale, true);
+        let high_difference_eval =
+            scale_and_add_subtract_eval(expr_eval, high_eval, expr_scale, high_scale, true);
+
+        // product
+        let product_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: product - low_difference * high_difference
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            product_eval - low_difference_eval * high_difference_eval,
+            2,
+        )?;
+
+        // sign(product - 1) == -1
+        verifier_evaluate_sign(builder, product_eval - chi_eval, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        self.low.get_column_references(columns);
+        self.high.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.expr.is_row_wise() && self.low.is_row_wise() && self.high.is_row_wise()
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, LiteralValue, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+#[test]
+fn we_can_prove_a_between_query_with_no_rows() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        between(column(&t, "a", &accessor), const_bigint(1), const_bigint(2)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", [""; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_including_both_bounds() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [-5, 1, 2, 3, 4, 5, 6, i64::MAX, i64::MIN]),
+        bigint("b", [1, 2, 3, 4, 5, 6, 7, 8, 9]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let between_expr = between(column(&t, "a", &accessor), const_bigint(2), const_bigint(5));
+    assert!(matches!(between_expr, DynProofExpr::Between(_)));
+    let ast = filter(cols_expr_plan(&t, &["b"], &accessor), tab(&t), between_expr);
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [3, 4, 5, 6])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_equal_bounds() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [2, 3, 4, 3]), bigint("b", [1, 2, 3, 4])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        between(column(&t, "a", &accessor), const_bigint(3), const_bigint(3)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [2, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_swapped_bounds() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [2, 3, 4, 5]), bigint("b", [1, 2, 3, 4])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        between(column(&t, "a", &accessor), const_bigint(4), const_bigint(3)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_not_between_query_with_decimals_of_differing_scales() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 10, 2, [150, 200, -125, 0, 99]),
+        bigint("b", [1, 2, 3, 4, 5]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        not(between(
+            column(&t, "a", &accessor),
+            const_decimal75(3, 1, 10),
+            const_bigint(2),
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [3, 4, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_on_timestamps() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_625_072_400, 1_625_076_000, 1_625_079_600],
+        ),
+        bigint("b", [1, 2, 3]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        between(
+            column(&t, "a", &accessor),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                1_625_076_000,
+            )),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                1_625_079_600,
+            )),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [2, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_fall_back_to_inequalities_if_the_bounds_are_not_literals() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [2, 3, 4, 5]),
+        bigint("lo", [1, 4, 4, 6]),
+        bigint("hi", [3, 3, 5, 6]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let between_expr = between(
+        column(&t, "a", &accessor),
+        column(&t, "lo", &accessor),
+        column(&t, "hi", &accessor),
+    );
+    assert_eq!(
+        between_expr,
+        and(
+            gte(column(&t, "a", &accessor), column(&t, "lo", &accessor)),
+            lte(column(&t, "a", &accessor), column(&t, "hi", &accessor)),
+        )
+    );
+    let ast = filter(cols_expr_plan(&t, &["a"], &accessor), tab(&t), between_expr);
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_fall_back_to_inequalities_if_the_product_could_overflow() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([int128("a", [i128::MIN, 0, i128::MAX])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        between(
+            column(&t, "a", &accessor),
+            const_int128(i128::MIN),
+            const_int128(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([int128("a", [i128::MIN, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            varchar(
+                "b",
+                dist.sample_iter(&mut rng).take(n).map(|v| format!("s{v}")),
+            ),
+        ]);
+
+        // Generate random bounds to filter by
+        let low = dist.sample(&mut rng);
+        let high = dist.sample(&mut rng);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            between(
+                column(&t, "a", &accessor),
+                const_bigint(low),
+                const_bigint(high),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_a, expected_b): (Vec<_>, Vec<_>) =
+            multizip((data["a"].i64_iter(), data["b"].string_iter()))
+                .filter_map(|(a, b)| {
+                    if (low..=high).contains(a) {
+                        Some((*a, b.clone()))
+                    } else {
+                        None
+                    }
+                })
+                .multiunzip();
+        let expected_result = owned_table([bigint("a", expected_a), varchar("b", expected_b)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(5121);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_between_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [-1, 9, 1, 4], &alloc),
+        borrowed_bigint("b", [1, 2, 3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let between_expr: DynProofExpr =
+        between(column(&t, "a", &accessor), const_bigint(0), const_bigint(4));
+    let res = between_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, false, true, true]);
+    assert_eq!(res, expected_res);
+}
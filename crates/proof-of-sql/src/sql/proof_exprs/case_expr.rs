@@ -0,0 +1,214 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{try_case_column_types, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for a conditional expression
+///
+/// `CASE WHEN condition THEN a ELSE b END` is `condition * a + (1 - condition) * b`,
+/// which is checked with a single product per row.
+/// Expressions with several conditions are nested in their `ELSE` branch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr {
+    condition: Box<DynProofExpr>,
+    then_expr: Box<DynProofExpr>,
+    else_expr: Box<DynProofExpr>,
+}
+
+impl CaseExpr {
+    /// Create a new conditional expression
+    pub fn new(
+        condition: Box<DynProofExpr>,
+        then_expr: Box<DynProofExpr>,
+        else_expr: Box<DynProofExpr>,
+    ) -> Self {
+        Self {
+            condition,
+            then_expr,
+            else_expr,
+        }
+    }
+
+    /// Computes the values of both branches at the scale of the result
+    fn scaled_branches<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        then_column: Column<'a, S>,
+        else_column: Column<'a, S>,
+    ) -> (&'a [S], &'a [S]) {
+        let scale = self.data_type().scale().unwrap_or(0);
+        let then_scale = self.then_expr.data_type().scale().unwrap_or(0);
+        let else_scale = self.else_expr.data_type().scale().unwrap_or(0);
+        (
+            alloc.alloc_slice_copy(&then_column.to_scalar_with_scaling(scale - then_scale)),
+            alloc.alloc_slice_copy(&else_column.to_scalar_with_scaling(scale - else_scale)),
+        )
+    }
+
+    /// Picks the value of the branch selected by the condition in each row
+    fn select<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        condition: &[bool],
+        then_values: &[S],
+        else_values: &[S],
+    ) -> Column<'a, S> {
+        let values = alloc.alloc_slice_fill_with(condition.len(), |i| {
+            if condition[i] {
+                then_values[i]
+            } else {
+                else_values[i]
+            }
+        });
+        if self.data_type() == ColumnType::Boolean {
+            Column::Boolean(alloc.alloc_slice_fill_with(values.len(), |i| values[i] != S::ZERO))
+        } else {
+            Column::Scalar(values)
+        }
+    }
+}
+
+impl ProofExpr for CaseExpr {
+    fn data_type(&self) -> ColumnType {
+        try_case_column_types(self.then_expr.data_type(), self.else_expr.data_type())
+            .expect("Failed to determine the type of the branches")
+    }
+
+    #[tracing::instrument(name = "CaseExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let condition_column: Column<'a, S> = self.condition.result_evaluate(alloc, table);
+        let then_column: Column<'a, S> = self.then_expr.result_evaluate(alloc, table);
+        let else_column: Column<'a, S> = self.else_expr.result_evaluate(alloc, table);
+        let condition = condition_column
+            .as_boolean()
+            .expect("condition is not boolean");
+        let (then_values, else_values) = self.scaled_branches(alloc, then_column, else_column);
+        let res = self.select(alloc, condition, then_values, else_values);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let condition_column: Column<'a, S> =
+            self.condition.first_round_evaluate(builder, alloc, table);
+        let then_column: Column<'a, S> = self.then_expr.first_round_evaluate(builder, alloc, table);
+        let else_column: Column<'a, S> = self.else_expr.first_round_evaluate(builder, alloc, table);
+        let condition = condition_column
+            .as_boolean()
+            .expect("condition is not boolean");
+        let (then_values, else_values) = self.scaled_branches(alloc, then_column, else_column);
+        let res = self.select(alloc, condition, then_values, else_values);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "CaseExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let condition_column: Column<'a, S> = self.condition.prover_evaluate(builder, alloc, table);
+        let then_column: Column<'a, S> = self.then_expr.prover_evaluate(builder, alloc, table);
+        let else_column: Column<'a, S> = self.else_expr.prover_evaluate(builder, alloc, table);
+        let condition = condition_column
+            .as_boolean()
+            .expect("condition is not boolean");
+        let (then_values, else_values) = self.scaled_branches(alloc, then_column, else_column);
+
+        // res
+        let res = self.select(alloc, condition, then_values, else_values);
+        builder.produce_intermediate_mle(res);
+
+        // subpolynomial: res - else - condition * (then - else)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(res)]),
+                (-S::one(), vec![Box::new(else_values)]),
+                (-S::one(), vec![Box::new(condition), Box::new(then_values)]),
+                (S::one(), vec![Box::new(condition), Box::new(else_values)]),
+            ],
+        );
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let condition = self
+            .condition
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+        let then_eval = self
+            .then_expr
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+        let else_eval = self
+            .else_expr
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+        let scale = self.data_type().scale().unwrap_or(0);
+        let then_scale = self.then_expr.data_type().scale().unwrap_or(0);
+        let else_scale = self.else_expr.data_type().scale().unwrap_or(0);
+        let then_eval = then_eval * S::pow10(scale.abs_diff(then_scale));
+        let else_eval = else_eval * S::pow10(scale.abs_diff(else_scale));
+
+        // res
+        let res = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: res - else - condition * (then - else)
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            res - else_eval - condition * (then_eval - else_eval),
+            2,
+        )?;
+
+        Ok(res)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.condition.get_column_references(columns);
+        self.then_expr.get_column_references(columns);
+        self.else_expr.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.condition.is_row_wise() && self.then_expr.is_row_wise() && self.else_expr.is_row_wise()
+    }
+}
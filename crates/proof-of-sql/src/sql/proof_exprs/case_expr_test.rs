@@ -0,0 +1,281 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+#[test]
+fn we_can_prove_a_case_query_with_no_rows() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), bigint("b", [0; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case_when(
+                equal(column(&t, "a", &accessor), const_bigint(1)),
+                column(&t, "b", &accessor),
+                const_bigint(0),
+            ),
+            "c",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+// select case when b = 'x' then a else a * 2 end as c, b from sxt.t where case when a > 2 then false else true end
+#[test]
+fn we_can_prove_a_case_query_in_a_projection_and_a_filter() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 2]),
+        varchar("b", ["x", "y", "x", "x"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                case_when(
+                    equal(column(&t, "b", &accessor), const_varchar("x")),
+                    column(&t, "a", &accessor),
+                    multiply(column(&t, "a", &accessor), const_bigint(2)),
+                ),
+                "c",
+            ),
+            col_expr_plan(&t, "b", &accessor),
+        ],
+        tab(&t),
+        case_when(
+            not(lte(column(&t, "a", &accessor), const_bigint(2))),
+            const_bool(false),
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [1, 4, 2]), varchar("b", ["x", "y", "x"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_case_query_with_decimals_of_differing_scales() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 10, 2, [150, 200, -125, 0]),
+        int("b", [1, 2, 3, 4]),
+        boolean("c", [true, false, true, false]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case_when(
+                column(&t, "c", &accessor),
+                column(&t, "a", &accessor),
+                case_when(
+                    equal(column(&t, "b", &accessor), const_int(2)),
+                    const_decimal75(3, 1, -12),
+                    column(&t, "b", &accessor),
+                ),
+            ),
+            "d",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("d", 12, 2, [150, -120, -125, 400])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, sum(case when b = 1 then c else 0 end) as sum_c, count(*) as __count__ from sxt.t group by a
+#[test]
+fn we_can_prove_a_sum_of_a_case_expression() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [1, 1, 0, 0, 1]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![sum_expr(
+            case_when(
+                equal(column(&t, "b", &accessor), const_bigint(1)),
+                column(&t, "c", &accessor),
+                const_bigint(0),
+            ),
+            "sum_c",
+        )],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1, 2]),
+        bigint("sum_c", [101, 102 + 105]),
+        bigint("__count__", [2, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_build_a_case_expression_with_incompatible_branches() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_case(
+            const_bool(true),
+            column(&t, "a", &accessor),
+            const_bool(false),
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_case(
+            const_bool(true),
+            column(&t, "b", &accessor),
+            column(&t, "b", &accessor),
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_case(
+            column(&t, "a", &accessor),
+            column(&t, "a", &accessor),
+            column(&t, "a", &accessor),
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            bigint("b", dist.sample_iter(&mut rng).take(n)),
+            bigint("c", dist.sample_iter(&mut rng).take(n)),
+        ]);
+
+        // Generate random values to filter by
+        let filter_val = dist.sample(&mut rng);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![aliased_plan(
+                case_when(
+                    equal(column(&t, "a", &accessor), const_bigint(filter_val)),
+                    column(&t, "b", &accessor),
+                    column(&t, "c", &accessor),
+                ),
+                "d",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_d,): (Vec<_>,) = multizip((
+            data["a"].i64_iter(),
+            data["b"].i64_iter(),
+            data["c"].i64_iter(),
+        ))
+        .map(|(a, b, c)| (if *a == filter_val { *b } else { *c },))
+        .multiunzip();
+        let expected_result = owned_table([bigint("d", expected_d)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(5121);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_case_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_boolean("a", [true, false, true, false], &alloc),
+        borrowed_bigint("b", [1, 2, 3, 4], &alloc),
+        borrowed_boolean("c", [false, false, true, true], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let case_expr: DynProofExpr = case_when(
+        column(&t, "a", &accessor),
+        column(&t, "b", &accessor),
+        const_bigint(-1),
+    );
+    let res = case_expr.result_evaluate(&alloc, &data);
+    let expected_scalars = [1, -1, 3, -1].map(Curve25519Scalar::from);
+    let expected_res = Column::Scalar(&expected_scalars);
+    assert_eq!(res, expected_res);
+
+    let case_expr: DynProofExpr = case_when(
+        column(&t, "a", &accessor),
+        column(&t, "c", &accessor),
+        not(column(&t, "c", &accessor)),
+    );
+    let res = case_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, true, true, false]);
+    assert_eq!(res, expected_res);
+}
@@ -6,7 +6,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
@@ -40,16 +40,23 @@ impl ColumnExpr {
         self.column_ref.column_id()
     }
 
-    /// Get the column
+    /// Get the component of the column that is referenced, so the values of a nullable column
+    /// unless its presence is referenced.
+    /// Character components are computed from the strings of the column in `alloc`.
     /// # Panics
     ///
     /// Will panic if the column is not found. Shouldn't happen in practice since
     /// code in `sql/parse` should have already checked that the column exists.
-    pub fn fetch_column<'a, S: Scalar>(&self, table: &Table<'a, S>) -> Column<'a, S> {
-        *table
+    pub fn fetch_column<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        table
             .inner_table()
             .get(&self.column_ref.column_id())
             .expect("Column not found")
+            .component(alloc, self.column_ref.component())
     }
 }
 
@@ -63,10 +70,19 @@ impl ProofExpr for ColumnExpr {
     /// add the result to the [`FirstRoundBuilder`](crate::sql::proof::FirstRoundBuilder)
     fn result_evaluate<'a, S: Scalar>(
         &self,
-        _alloc: &'a Bump,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        self.fetch_column(alloc, table)
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
         table: &Table<'a, S>,
     ) -> Column<'a, S> {
-        self.fetch_column(table)
+        self.fetch_column(alloc, table)
     }
 
     /// Given the selected rows (as a slice of booleans), evaluate the column expression and
@@ -74,10 +90,10 @@ impl ProofExpr for ColumnExpr {
     fn prover_evaluate<'a, S: Scalar>(
         &self,
         _builder: &mut FinalRoundBuilder<'a, S>,
-        _alloc: &'a Bump,
+        alloc: &'a Bump,
         table: &Table<'a, S>,
     ) -> Column<'a, S> {
-        self.fetch_column(table)
+        self.fetch_column(alloc, table)
     }
 
     /// Evaluate the column expression at the sumcheck's random point,
@@ -101,4 +117,8 @@ impl ProofExpr for ColumnExpr {
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         columns.insert(self.column_ref.clone());
     }
+
+    fn is_row_wise(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,411 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
+        proof_gadgets::{
+            final_round_evaluate_range_check, first_round_evaluate_range_check,
+            prover_evaluate_sign, verifier_evaluate_range_check, verifier_evaluate_sign,
+        },
+    },
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{
+    PoSQLDateTimeField, PoSQLInterval, PoSQLTimeUnit, PoSQLTimeZone,
+};
+use serde::{Deserialize, Serialize};
+
+/// The number of days from 0000-03-01 to 1970-01-01 in the proleptic Gregorian calendar
+const DAYS_FROM_0000_03_01_TO_EPOCH: i128 = 719_468;
+
+/// A date/time function of a timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateTimeFunction {
+    /// `EXTRACT(field FROM timestamp)`
+    Extract(PoSQLDateTimeField),
+    /// `DATE_TRUNC('field', timestamp)`, which is only provable for fields with a fixed length
+    Truncate(PoSQLDateTimeField),
+    /// `timestamp + INTERVAL '...'`, which is only provable for intervals with a fixed length
+    AddInterval(PoSQLInterval),
+}
+
+impl DateTimeFunction {
+    /// Whether the function can be proven
+    pub fn is_provable(self) -> bool {
+        match self {
+            DateTimeFunction::Extract(_) => true,
+            DateTimeFunction::Truncate(field) => field.seconds().is_some(),
+            DateTimeFunction::AddInterval(interval) => interval.seconds().is_some(),
+        }
+    }
+}
+
+/// Provable date/time function of a `TimestampTZ` expression
+///
+/// Fields are taken in the time zone of the timestamps, so `EXTRACT(DAY FROM ts)` is the day of the month
+/// on the local calendar and `DATE_TRUNC('day', ts)` is local midnight.
+/// Every floor division by a constant is proven with a quotient witness whose remainder
+/// is range checked to lie between zero and the divisor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateTimeExpr {
+    expr: Box<DynProofExpr>,
+    function: DateTimeFunction,
+}
+
+impl DateTimeExpr {
+    /// Create a new date/time function of a timestamp expression
+    pub fn new(expr: Box<DynProofExpr>, function: DateTimeFunction) -> Self {
+        Self { expr, function }
+    }
+
+    /// # Panics
+    /// Panics if the expression is not a timestamp, which is checked when constructing it.
+    fn timestamp_type(&self) -> (PoSQLTimeUnit, PoSQLTimeZone) {
+        let ColumnType::TimestampTZ(unit, timezone) = self.expr.data_type() else {
+            panic!("Date/time functions should have been checked to apply to timestamps");
+        };
+        (unit, timezone)
+    }
+
+    /// Apply the function to timestamps counted in their unit since the Unix epoch
+    ///
+    /// # Panics
+    /// Panics if the function is not provable, which is checked when constructing it.
+    fn apply<A: DateTimeArithmetic>(
+        &self,
+        arithmetic: &mut A,
+        timestamps: A::Value,
+    ) -> Result<A::Value, ProofError> {
+        let (unit, timezone) = self.timestamp_type();
+        let units_per_second = units_per_second(unit);
+        // Intervals of a fixed length do not depend on the time zone
+        if let DateTimeFunction::AddInterval(interval) = self.function {
+            let seconds = interval
+                .seconds()
+                .expect("Intervals should have been checked to have a fixed length");
+            return Ok(
+                arithmetic.linear(&[(1, timestamps)], i128::from(seconds) * units_per_second)
+            );
+        }
+        let local_timestamps = arithmetic.linear(
+            &[(1, timestamps)],
+            i128::from(timezone.offset()) * units_per_second,
+        );
+        let field_length = |field: PoSQLDateTimeField| {
+            i128::from(
+                field
+                    .seconds()
+                    .expect("Fields should have been checked to have a fixed length"),
+            ) * units_per_second
+        };
+        match self.function {
+            DateTimeFunction::Truncate(field) => {
+                let (_, since_start) =
+                    arithmetic.floor_divide(local_timestamps, field_length(field))?;
+                Ok(arithmetic.linear(&[(1, timestamps), (-1, since_start)], 0))
+            }
+            DateTimeFunction::Extract(
+                field @ (PoSQLDateTimeField::Hour
+                | PoSQLDateTimeField::Minute
+                | PoSQLDateTimeField::Second),
+            ) => {
+                let parent = match field {
+                    PoSQLDateTimeField::Hour => PoSQLDateTimeField::Day,
+                    PoSQLDateTimeField::Minute => PoSQLDateTimeField::Hour,
+                    _ => PoSQLDateTimeField::Minute,
+                };
+                let (_, since_parent) =
+                    arithmetic.floor_divide(local_timestamps, field_length(parent))?;
+                let (value, _) = arithmetic.floor_divide(since_parent, field_length(field))?;
+                Ok(value)
+            }
+            DateTimeFunction::Extract(field) => {
+                let (days, _) = arithmetic
+                    .floor_divide(local_timestamps, field_length(PoSQLDateTimeField::Day))?;
+                civil_from_days(arithmetic, days, field)
+            }
+            DateTimeFunction::AddInterval(_) => unreachable!(),
+        }
+    }
+
+    /// Evaluate the expression on the prover side in the given round
+    ///
+    /// # Panics
+    /// Panics if a result does not fit into an `i64`.
+    fn prover_apply<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        column: Column<'a, S>,
+        round: ProverRound<'a, '_, S>,
+    ) -> Column<'a, S> {
+        let Column::TimestampTZ(unit, timezone, timestamps) = column else {
+            panic!("Date/time functions should have been checked to apply to timestamps");
+        };
+        let mut arithmetic = ProverArithmetic {
+            alloc,
+            length: timestamps.len(),
+            round,
+        };
+        let timestamps: &[i128] =
+            alloc.alloc_slice_fill_iter(timestamps.iter().map(|&timestamp| i128::from(timestamp)));
+        let values = self
+            .apply(&mut arithmetic, timestamps)
+            .expect("The prover should always be able to evaluate date/time functions");
+        let values = alloc.alloc_slice_fill_iter(values.iter().map(|&value| {
+            i64::try_from(value).expect("Date/time function result is out of range")
+        }));
+        match self.function {
+            DateTimeFunction::Extract(_) => Column::BigInt(values),
+            DateTimeFunction::Truncate(_) | DateTimeFunction::AddInterval(_) => {
+                Column::TimestampTZ(unit, timezone, values)
+            }
+        }
+    }
+}
+
+impl ProofExpr for DateTimeExpr {
+    fn data_type(&self) -> ColumnType {
+        match self.function {
+            DateTimeFunction::Extract(_) => ColumnType::BigInt,
+            DateTimeFunction::Truncate(_) | DateTimeFunction::AddInterval(_) => {
+                self.expr.data_type()
+            }
+        }
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column = self.expr.result_evaluate(alloc, table);
+        self.prover_apply(alloc, column, ProverRound::Result)
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column = self.expr.first_round_evaluate(builder, alloc, table);
+        self.prover_apply(alloc, column, ProverRound::First(builder))
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.date_time_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column = self.expr.prover_evaluate(builder, alloc, table);
+        self.prover_apply(alloc, column, ProverRound::Final(builder))
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let timestamps = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        self.apply(&mut VerifierArithmetic { builder, chi_eval }, timestamps)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.expr.is_row_wise()
+    }
+}
+
+/// The number of units of a timestamp in a second
+fn units_per_second(unit: PoSQLTimeUnit) -> i128 {
+    match unit {
+        PoSQLTimeUnit::Second => 1,
+        PoSQLTimeUnit::Millisecond => 1_000,
+        PoSQLTimeUnit::Microsecond => 1_000_000,
+        PoSQLTimeUnit::Nanosecond => 1_000_000_000,
+    }
+}
+
+/// The year, month or day of the month of dates given as days since the Unix epoch
+///
+/// This is `civil_from_days` of Howard Hinnant's
+/// [date algorithms](https://howardhinnant.github.io/date_algorithms.html#civil_from_days),
+/// where every division is a floor division by a constant.
+fn civil_from_days<A: DateTimeArithmetic>(
+    arithmetic: &mut A,
+    days: A::Value,
+    field: PoSQLDateTimeField,
+) -> Result<A::Value, ProofError> {
+    // Years start on March 1st, so that a leap day is the last day of its year
+    let days = arithmetic.linear(&[(1, days)], DAYS_FROM_0000_03_01_TO_EPOCH);
+    let (era, day_of_era) = arithmetic.floor_divide(days, 146_097)?;
+    let (every_fourth_year, _) = arithmetic.floor_divide(day_of_era, 1_460)?;
+    let (every_century, _) = arithmetic.floor_divide(day_of_era, 36_524)?;
+    let (last_day_of_era, _) = arithmetic.floor_divide(day_of_era, 146_096)?;
+    let day_of_era_without_leap_days = arithmetic.linear(
+        &[
+            (1, day_of_era),
+            (-1, every_fourth_year),
+            (1, every_century),
+            (-1, last_day_of_era),
+        ],
+        0,
+    );
+    let (year_of_era, _) = arithmetic.floor_divide(day_of_era_without_leap_days, 365)?;
+    let (leap_years, _) = arithmetic.floor_divide(year_of_era, 4)?;
+    let (non_leap_centuries, _) = arithmetic.floor_divide(year_of_era, 100)?;
+    let day_of_year = arithmetic.linear(
+        &[
+            (1, day_of_era),
+            (-365, year_of_era),
+            (-1, leap_years),
+            (1, non_leap_centuries),
+        ],
+        0,
+    );
+    // The month counted from March
+    let scaled_day_of_year = arithmetic.linear(&[(5, day_of_year)], 2);
+    let (shifted_month, _) = arithmetic.floor_divide(scaled_day_of_year, 153)?;
+    if field == PoSQLDateTimeField::Day {
+        let scaled_month = arithmetic.linear(&[(153, shifted_month)], 2);
+        let (days_before_month, _) = arithmetic.floor_divide(scaled_month, 5)?;
+        return Ok(arithmetic.linear(&[(1, day_of_year), (-1, days_before_month)], 1));
+    }
+    // January and February belong to the next calendar year
+    let shifted_month_plus_two = arithmetic.linear(&[(1, shifted_month)], 2);
+    let (is_january_or_february, _) = arithmetic.floor_divide(shifted_month_plus_two, 12)?;
+    Ok(match field {
+        PoSQLDateTimeField::Month => {
+            arithmetic.linear(&[(1, shifted_month), (-12, is_january_or_february)], 3)
+        }
+        _ => arithmetic.linear(
+            &[(400, era), (1, year_of_era), (1, is_january_or_february)],
+            0,
+        ),
+    })
+}
+
+/// The arithmetic date/time functions are computed with,
+/// so that the prover and the verifier follow the same steps
+trait DateTimeArithmetic {
+    /// A column of integers or its evaluation
+    type Value: Copy;
+
+    /// `coefficient * value + ... + constant` for every row
+    fn linear(&mut self, terms: &[(i128, Self::Value)], constant: i128) -> Self::Value;
+
+    /// The floored quotient and the non-negative remainder of a division by a positive constant
+    fn floor_divide(
+        &mut self,
+        dividend: Self::Value,
+        divisor: i128,
+    ) -> Result<(Self::Value, Self::Value), ProofError>;
+}
+
+/// The round the prover evaluates an expression in
+enum ProverRound<'a, 'b, S: Scalar> {
+    Result,
+    First(&'b mut FirstRoundBuilder<'a, S>),
+    Final(&'b mut FinalRoundBuilder<'a, S>),
+}
+
+struct ProverArithmetic<'a, 'b, S: Scalar> {
+    alloc: &'a Bump,
+    length: usize,
+    round: ProverRound<'a, 'b, S>,
+}
+
+impl<'a, S: Scalar> DateTimeArithmetic for ProverArithmetic<'a, '_, S> {
+    type Value = &'a [i128];
+
+    fn linear(&mut self, terms: &[(i128, &'a [i128])], constant: i128) -> &'a [i128] {
+        self.alloc.alloc_slice_fill_with(self.length, |i| {
+            terms
+                .iter()
+                .map(|(coefficient, values)| coefficient * values[i])
+                .sum::<i128>()
+                + constant
+        })
+    }
+
+    fn floor_divide(
+        &mut self,
+        dividend: &'a [i128],
+        divisor: i128,
+    ) -> Result<(&'a [i128], &'a [i128]), ProofError> {
+        let quotient: &[_] = self
+            .alloc
+            .alloc_slice_fill_iter(dividend.iter().map(|value| value.div_euclid(divisor)));
+        let remainder: &[_] = self
+            .alloc
+            .alloc_slice_fill_iter(dividend.iter().map(|value| value.rem_euclid(divisor)));
+        let gap = self.linear(&[(-1, remainder)], divisor - 1);
+        match &mut self.round {
+            ProverRound::Result => {}
+            ProverRound::First(builder) => {
+                // The word decompositions of the range checked columns have to be committed before alpha is drawn
+                for column in [remainder, gap] {
+                    first_round_evaluate_range_check(builder, column, self.alloc);
+                    builder.request_post_result_challenges(1);
+                    builder.produce_chi_evaluation_length(256);
+                }
+            }
+            ProverRound::Final(builder) => {
+                builder.produce_intermediate_mle(quotient);
+                // Bounding the quotient makes `dividend = divisor * quotient + remainder` hold over the integers
+                let quotient_scalars = self
+                    .alloc
+                    .alloc_slice_fill_iter(quotient.iter().map(S::from));
+                prover_evaluate_sign(builder, self.alloc, quotient_scalars);
+                // 0 <= remainder < divisor
+                final_round_evaluate_range_check(builder, remainder, self.alloc);
+                final_round_evaluate_range_check(builder, gap, self.alloc);
+            }
+        }
+        Ok((quotient, remainder))
+    }
+}
+
+struct VerifierArithmetic<'b, S: Scalar, B: VerificationBuilder<S>> {
+    builder: &'b mut B,
+    chi_eval: S,
+}
+
+impl<S: Scalar, B: VerificationBuilder<S>> DateTimeArithmetic for VerifierArithmetic<'_, S, B> {
+    type Value = S;
+
+    fn linear(&mut self, terms: &[(i128, S)], constant: i128) -> S {
+        terms.iter().fold(
+            S::from(constant) * self.chi_eval,
+            |sum, &(coefficient, value)| sum + S::from(coefficient) * value,
+        )
+    }
+
+    fn floor_divide(&mut self, dividend: S, divisor: i128) -> Result<(S, S), ProofError> {
+        let quotient = self.builder.try_consume_final_round_mle_evaluation()?;
+        // Bounding the quotient makes `dividend = divisor * quotient + remainder` hold over the integers
+        verifier_evaluate_sign(self.builder, quotient, self.chi_eval)?;
+        let remainder = dividend - S::from(divisor) * quotient;
+        let gap = self.linear(&[(-1, remainder)], divisor - 1);
+        // 0 <= remainder < divisor
+        verifier_evaluate_range_check(self.builder, remainder, self.chi_eval)?;
+        verifier_evaluate_range_check(self.builder, gap, self.chi_eval)?;
+        Ok((quotient, remainder))
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor,
+        },
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DateTimeFunction, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+use proof_of_sql_parser::posql_time::{
+    PoSQLDateTimeField::{Day, Hour, Minute, Month, Second, Year},
+    PoSQLInterval, PoSQLTimeUnit, PoSQLTimeZone,
+};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+// select extract(year from ts), ..., extract(second from ts) from sxt.t
+#[test]
+fn we_can_prove_extracting_fields_of_utc_timestamps() {
+    let data = owned_table([timestamptz(
+        "ts",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [
+            0_i64,
+            951_827_696,
+            -1,
+            1_709_251_199,
+            -2_208_988_800,
+            4_107_542_399,
+        ],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        [
+            ("year", Year),
+            ("month", Month),
+            ("day", Day),
+            ("hour", Hour),
+            ("minute", Minute),
+            ("second", Second),
+        ]
+        .into_iter()
+        .map(|(alias, field)| aliased_plan(extract(field, column(&t, "ts", &accessor)), alias))
+        .collect(),
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("year", [1970_i64, 2000, 1969, 2024, 1900, 2100]),
+        bigint("month", [1_i64, 2, 12, 2, 1, 2]),
+        bigint("day", [1_i64, 29, 31, 29, 1, 28]),
+        bigint("hour", [0_i64, 12, 23, 23, 0, 23]),
+        bigint("minute", [0_i64, 34, 59, 59, 0, 59]),
+        bigint("second", [0_i64, 56, 59, 59, 0, 59]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select date_trunc('day', ts), date_trunc('hour', ts), extract(day from ts), extract(hour from ts)
+// from sxt.t where extract(day from ts) = 15
+#[test]
+fn we_can_prove_date_time_functions_in_the_time_zone_of_the_timestamps() {
+    let timezone = PoSQLTimeZone::new(19_800);
+    let data = owned_table([timestamptz(
+        "ts",
+        PoSQLTimeUnit::Millisecond,
+        timezone,
+        [1_700_000_000_123_i64, -86_400_001, 1_699_986_600_000],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(date_trunc(Day, column(&t, "ts", &accessor)), "d"),
+            aliased_plan(date_trunc(Hour, column(&t, "ts", &accessor)), "h"),
+            aliased_plan(extract(Day, column(&t, "ts", &accessor)), "day"),
+            aliased_plan(extract(Hour, column(&t, "ts", &accessor)), "hour"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Millisecond,
+            timezone,
+            [1_699_986_600_000_i64, -106_200_000, 1_699_986_600_000],
+        ),
+        timestamptz(
+            "h",
+            PoSQLTimeUnit::Millisecond,
+            timezone,
+            [1_699_997_400_000_i64, -88_200_000, 1_699_986_600_000],
+        ),
+        bigint("day", [15_i64, 31, 15]),
+        bigint("hour", [3_i64, 5, 0]),
+    ]);
+    assert_eq!(res, expected_res);
+
+    let ast = filter(
+        cols_expr_plan(&t, &["ts"], &accessor),
+        tab(&t),
+        equal(extract(Day, column(&t, "ts", &accessor)), const_bigint(15)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "ts",
+        PoSQLTimeUnit::Millisecond,
+        timezone,
+        [1_700_000_000_123_i64, 1_699_986_600_000],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+// select ts + interval '1 day' as tomorrow from sxt.t where ts - interval '90 minutes' >= start
+#[test]
+fn we_can_prove_adding_intervals_to_timestamps() {
+    let data = owned_table([
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Microsecond,
+            PoSQLTimeZone::new(-18_000),
+            [0_i64, 5_400_000_000, -1],
+        ),
+        timestamptz(
+            "start",
+            PoSQLTimeUnit::Microsecond,
+            PoSQLTimeZone::new(-18_000),
+            [0_i64, 0, 0],
+        ),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            add_interval(column(&t, "ts", &accessor), 1, Day),
+            "tomorrow",
+        )],
+        tab(&t),
+        gte(
+            add_interval(column(&t, "ts", &accessor), -90, Minute),
+            column(&t, "start", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "tomorrow",
+        PoSQLTimeUnit::Microsecond,
+        PoSQLTimeZone::new(-18_000),
+        [91_800_000_000_i64],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_prove_unsupported_date_time_functions() {
+    let data = owned_table([
+        bigint("a", [1_i64]),
+        timestamptz("ts", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_date_time(column(&t, "a", &accessor), DateTimeFunction::Extract(Day)),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    // Months and years do not have a fixed length
+    assert!(matches!(
+        DynProofExpr::try_new_date_time(
+            column(&t, "ts", &accessor),
+            DateTimeFunction::Truncate(Month)
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_date_time(
+            column(&t, "ts", &accessor),
+            DateTimeFunction::AddInterval(PoSQLInterval::new(1, Year))
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    // From 1677 to 2262, the range of nanosecond timestamps
+    let dist = Uniform::new(-9_223_372_036_i64, 9_223_372_036);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..10 {
+        // Generate random table
+        let n = Uniform::new(1, 11).sample(&mut rng);
+        let offset_seconds = Uniform::new(-50_400, 50_401).sample(&mut rng);
+        let timezone = PoSQLTimeZone::new(offset_seconds);
+        let seconds: Vec<i64> = dist.sample_iter(&mut rng).take(n).collect();
+        let data = owned_table([timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            timezone,
+            seconds.clone(),
+        )]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data,
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![
+                aliased_plan(extract(Year, column(&t, "ts", &accessor)), "year"),
+                aliased_plan(extract(Month, column(&t, "ts", &accessor)), "month"),
+                aliased_plan(extract(Day, column(&t, "ts", &accessor)), "day"),
+                aliased_plan(extract(Minute, column(&t, "ts", &accessor)), "minute"),
+            ],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let local_times: Vec<_> = seconds
+            .iter()
+            .map(|&seconds| {
+                DateTime::from_timestamp(seconds, 0)
+                    .unwrap()
+                    .with_timezone(&FixedOffset::east_opt(offset_seconds).unwrap())
+            })
+            .collect();
+        let expected_result = owned_table([
+            bigint(
+                "year",
+                local_times.iter().map(|time| i64::from(time.year())),
+            ),
+            bigint(
+                "month",
+                local_times.iter().map(|time| i64::from(time.month())),
+            ),
+            bigint("day", local_times.iter().map(|time| i64::from(time.day()))),
+            bigint(
+                "minute",
+                local_times.iter().map(|time| i64::from(time.minute())),
+            ),
+        ]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(23);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_date_time_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let timezone = PoSQLTimeZone::new(3_600);
+    let data = table([borrowed_timestamptz(
+        "ts",
+        PoSQLTimeUnit::Nanosecond,
+        timezone,
+        [-1_i64, 82_800_000_000_000],
+        &alloc,
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let truncated = date_trunc(Day, column(&t, "ts", &accessor));
+    let res = truncated.result_evaluate(&alloc, &data);
+    let expected_res = Column::TimestampTZ(
+        PoSQLTimeUnit::Nanosecond,
+        timezone,
+        &[-3_600_000_000_000, 82_800_000_000_000],
+    );
+    assert_eq!(res, expected_res);
+    let year = extract(Year, column(&t, "ts", &accessor));
+    let res = year.result_evaluate(&alloc, &data);
+    assert_eq!(res, Column::BigInt(&[1970, 1970]));
+}
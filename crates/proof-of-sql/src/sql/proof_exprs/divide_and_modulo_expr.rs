@@ -0,0 +1,468 @@
+use super::{prover_evaluate_equals_zero, verifier_evaluate_equals_zero, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{
+            try_divide_column_types, try_modulo_column_types, Column, ColumnRef, ColumnType, Table,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_range_check, first_round_evaluate_range_check,
+            prover_evaluate_sign, verifier_evaluate_range_check, verifier_evaluate_sign,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+use serde::{Deserialize, Serialize};
+
+/// The largest combined number of decimal digits of the (scaled) dividend and divisor
+/// for which a division can be proven.
+///
+/// Keeping `|dividend| * |divisor| < 10^74 < 2^248` guarantees that every witness fits in the range
+/// accepted by `range_check` and that `quotient * divisor + remainder` can not wrap around the field.
+const MAX_PRODUCT_DIGITS: u8 = 74;
+
+/// Provable numeric `/` and `%` expression
+///
+/// Division truncates towards zero and the remainder takes the sign of the dividend.
+/// Rows where the divisor is zero evaluate to a quotient of zero and a remainder equal to the dividend,
+/// see [`DynProofExpr::try_new_divide`]. Unlike the division of the postprocessing, this does not
+/// fail, because the expression is also evaluated on the rows that a `WHERE` clause leaves out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DivideAndModuloExpr {
+    lhs: Box<DynProofExpr>,
+    rhs: Box<DynProofExpr>,
+    is_modulo: bool,
+}
+
+/// Columns the prover supplies as witnesses for a division
+struct DivisionWitnesses<'a, S: Scalar> {
+    quotient: &'a [S],
+    remainder: &'a [S],
+    quotient_magnitude: &'a [S],
+    remainder_magnitude: &'a [S],
+    quotient_gap: &'a [S],
+    remainder_gap: &'a [S],
+}
+
+impl DivideAndModuloExpr {
+    /// Create numerical `/` or `%` expression
+    pub fn new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>, is_modulo: bool) -> Self {
+        Self {
+            lhs,
+            rhs,
+            is_modulo,
+        }
+    }
+
+    /// Returns the powers of ten by which the dividend and the divisor have to be scaled
+    /// so that their integer quotient carries the scale of the result type.
+    ///
+    /// Returns `None` if the operation is not supported or can not be proven for the given types.
+    pub(crate) fn try_scaling_exponents(
+        lhs_type: ColumnType,
+        rhs_type: ColumnType,
+        is_modulo: bool,
+    ) -> Option<(u8, u8)> {
+        let result_type = if is_modulo {
+            try_modulo_column_types(lhs_type, rhs_type)
+        } else {
+            try_divide_column_types(lhs_type, rhs_type)
+        }
+        .ok()?;
+        let lhs_scale = i16::from(lhs_type.scale()?);
+        let rhs_scale = i16::from(rhs_type.scale()?);
+        let result_scale = i16::from(result_type.scale()?);
+        let scale_difference = result_scale - lhs_scale + rhs_scale;
+        let dividend_exponent = u8::try_from(scale_difference.max(0)).ok()?;
+        let divisor_exponent = u8::try_from((-scale_difference).max(0)).ok()?;
+        let digits = u16::from(lhs_type.precision_value()?)
+            + u16::from(rhs_type.precision_value()?)
+            + u16::from(dividend_exponent)
+            + u16::from(divisor_exponent);
+        (digits <= u16::from(MAX_PRODUCT_DIGITS)).then_some((dividend_exponent, divisor_exponent))
+    }
+
+    /// # Panics
+    /// Panics if the expression was constructed for types whose division can not be proven.
+    fn scaling_exponents(&self) -> (u8, u8) {
+        Self::try_scaling_exponents(self.lhs.data_type(), self.rhs.data_type(), self.is_modulo)
+            .expect("Division should have been checked to be provable")
+    }
+
+    /// Scale the columns of the operands to the dividend and divisor
+    fn dividend_and_divisor<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        lhs_column: Column<'a, S>,
+        rhs_column: Column<'a, S>,
+    ) -> (&'a [S], &'a [S]) {
+        let (dividend_exponent, divisor_exponent) = self.scaling_exponents();
+        let dividend_factor = S::pow10(dividend_exponent);
+        let divisor_factor = S::pow10(divisor_exponent);
+        let dividend = alloc.alloc_slice_fill_iter(
+            lhs_column
+                .to_scalar_with_scaling(0)
+                .into_iter()
+                .map(|value| value * dividend_factor),
+        );
+        let divisor = alloc.alloc_slice_fill_iter(
+            rhs_column
+                .to_scalar_with_scaling(0)
+                .into_iter()
+                .map(|value| value * divisor_factor),
+        );
+        (dividend, divisor)
+    }
+
+    fn result<'a, S: Scalar>(&self, witnesses: &DivisionWitnesses<'a, S>) -> Column<'a, S> {
+        if self.is_modulo {
+            Column::Scalar(witnesses.remainder)
+        } else {
+            Column::Scalar(witnesses.quotient)
+        }
+    }
+}
+
+impl ProofExpr for DivideAndModuloExpr {
+    fn data_type(&self) -> ColumnType {
+        if self.is_modulo {
+            try_modulo_column_types(self.lhs.data_type(), self.rhs.data_type())
+                .expect("Failed to modulo column types")
+        } else {
+            try_divide_column_types(self.lhs.data_type(), self.rhs.data_type())
+                .expect("Failed to divide column types")
+        }
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.result_evaluate(alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
+        let (dividend, divisor) = self.dividend_and_divisor(alloc, lhs_column, rhs_column);
+        self.result(&compute_division_witnesses(alloc, dividend, divisor))
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.first_round_evaluate(builder, alloc, table);
+        let (dividend, divisor) = self.dividend_and_divisor(alloc, lhs_column, rhs_column);
+        let witnesses = compute_division_witnesses(alloc, dividend, divisor);
+
+        // The word decompositions of the range checked columns have to be committed before alpha is drawn
+        for column in [
+            witnesses.remainder_magnitude,
+            witnesses.remainder_gap,
+            witnesses.quotient_magnitude,
+            witnesses.quotient_gap,
+        ] {
+            first_round_evaluate_range_check(builder, column, alloc);
+            builder.request_post_result_challenges(1);
+            builder.produce_chi_evaluation_length(256);
+        }
+
+        self.result(&witnesses)
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.divide_and_modulo_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    #[allow(clippy::too_many_lines)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.prover_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.prover_evaluate(builder, alloc, table);
+        let (dividend, divisor) = self.dividend_and_divisor(alloc, lhs_column, rhs_column);
+        let table_length = table.num_rows();
+
+        // sign(dividend) == -1, sign(divisor) == -1 and divisor == 0
+        let dividend_is_negative = prover_evaluate_sign(builder, alloc, dividend);
+        let divisor_is_negative = prover_evaluate_sign(builder, alloc, divisor);
+        let divisor_is_zero = prover_evaluate_equals_zero(table_length, builder, alloc, divisor);
+
+        let witnesses = compute_division_witnesses(alloc, dividend, divisor);
+        let DivisionWitnesses {
+            quotient,
+            remainder,
+            quotient_magnitude,
+            remainder_magnitude,
+            quotient_gap,
+            remainder_gap,
+        } = witnesses;
+        builder.produce_intermediate_mle(quotient);
+        builder.produce_intermediate_mle(remainder);
+        builder.produce_intermediate_mle(quotient_magnitude);
+        builder.produce_intermediate_mle(remainder_magnitude);
+        builder.produce_intermediate_mle(quotient_gap);
+        builder.produce_intermediate_mle(remainder_gap);
+
+        let two = S::TWO;
+        let chi: &'a [bool] = alloc.alloc_slice_fill_copy(table_length, true);
+
+        // subpolynomial: dividend - quotient * divisor - remainder
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(dividend)]),
+                (-S::one(), vec![Box::new(quotient), Box::new(divisor)]),
+                (-S::one(), vec![Box::new(remainder)]),
+            ],
+        );
+
+        // subpolynomial: remainder_magnitude - sign(dividend) * remainder
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(remainder_magnitude)]),
+                (-S::one(), vec![Box::new(remainder)]),
+                (
+                    two,
+                    vec![Box::new(dividend_is_negative), Box::new(remainder)],
+                ),
+            ],
+        );
+
+        // subpolynomial: quotient_magnitude - sign(dividend) * sign(divisor) * quotient
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(quotient_magnitude)]),
+                (-S::one(), vec![Box::new(quotient)]),
+                (
+                    two,
+                    vec![Box::new(dividend_is_negative), Box::new(quotient)],
+                ),
+                (two, vec![Box::new(divisor_is_negative), Box::new(quotient)]),
+                (
+                    -two * two,
+                    vec![
+                        Box::new(dividend_is_negative),
+                        Box::new(divisor_is_negative),
+                        Box::new(quotient),
+                    ],
+                ),
+            ],
+        );
+
+        // subpolynomial: quotient_gap - |dividend| + quotient_magnitude
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(quotient_gap)]),
+                (-S::one(), vec![Box::new(dividend)]),
+                (
+                    two,
+                    vec![Box::new(dividend_is_negative), Box::new(dividend)],
+                ),
+                (S::one(), vec![Box::new(quotient_magnitude)]),
+            ],
+        );
+
+        // subpolynomial: remainder_gap - (1 - divisor_is_zero) * (|divisor| - remainder_magnitude - 1)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(remainder_gap)]),
+                (-S::one(), vec![Box::new(divisor)]),
+                (two, vec![Box::new(divisor_is_negative), Box::new(divisor)]),
+                (S::one(), vec![Box::new(remainder_magnitude)]),
+                (S::one(), vec![Box::new(chi)]),
+                (
+                    -S::one(),
+                    vec![Box::new(divisor_is_zero), Box::new(remainder_magnitude)],
+                ),
+                (-S::one(), vec![Box::new(divisor_is_zero)]),
+            ],
+        );
+
+        // subpolynomial: divisor_is_zero * quotient
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(
+                S::one(),
+                vec![Box::new(divisor_is_zero), Box::new(quotient)],
+            )],
+        );
+
+        // 0 <= remainder_magnitude < |divisor| and 0 <= quotient_magnitude <= |dividend|
+        final_round_evaluate_range_check(builder, remainder_magnitude, alloc);
+        final_round_evaluate_range_check(builder, remainder_gap, alloc);
+        final_round_evaluate_range_check(builder, quotient_magnitude, alloc);
+        final_round_evaluate_range_check(builder, quotient_gap, alloc);
+
+        let res = self.result(&witnesses);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let (dividend_exponent, divisor_exponent) = self.scaling_exponents();
+        let dividend = lhs_eval * S::pow10(dividend_exponent);
+        let divisor = rhs_eval * S::pow10(divisor_exponent);
+
+        // sign(dividend) == -1, sign(divisor) == -1 and divisor == 0
+        let dividend_is_negative = verifier_evaluate_sign(builder, dividend, chi_eval)?;
+        let divisor_is_negative = verifier_evaluate_sign(builder, divisor, chi_eval)?;
+        let divisor_is_zero = verifier_evaluate_equals_zero(builder, divisor, chi_eval)?;
+
+        let quotient = builder.try_consume_final_round_mle_evaluation()?;
+        let remainder = builder.try_consume_final_round_mle_evaluation()?;
+        let quotient_magnitude = builder.try_consume_final_round_mle_evaluation()?;
+        let remainder_magnitude = builder.try_consume_final_round_mle_evaluation()?;
+        let quotient_gap = builder.try_consume_final_round_mle_evaluation()?;
+        let remainder_gap = builder.try_consume_final_round_mle_evaluation()?;
+
+        let two = S::TWO;
+
+        // subpolynomial: dividend - quotient * divisor - remainder
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            dividend - quotient * divisor - remainder,
+            2,
+        )?;
+
+        // subpolynomial: remainder_magnitude - sign(dividend) * remainder
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            remainder_magnitude - remainder + two * dividend_is_negative * remainder,
+            2,
+        )?;
+
+        // subpolynomial: quotient_magnitude - sign(dividend) * sign(divisor) * quotient
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            quotient_magnitude - quotient
+                + two * dividend_is_negative * quotient
+                + two * divisor_is_negative * quotient
+                - two * two * dividend_is_negative * divisor_is_negative * quotient,
+            3,
+        )?;
+
+        // subpolynomial: quotient_gap - |dividend| + quotient_magnitude
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            quotient_gap - dividend + two * dividend_is_negative * dividend + quotient_magnitude,
+            2,
+        )?;
+
+        // subpolynomial: remainder_gap - (1 - divisor_is_zero) * (|divisor| - remainder_magnitude - 1)
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            remainder_gap - divisor
+                + two * divisor_is_negative * divisor
+                + remainder_magnitude
+                + chi_eval
+                - divisor_is_zero * remainder_magnitude
+                - divisor_is_zero,
+            2,
+        )?;
+
+        // subpolynomial: divisor_is_zero * quotient
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            divisor_is_zero * quotient,
+            2,
+        )?;
+
+        // 0 <= remainder_magnitude < |divisor| and 0 <= quotient_magnitude <= |dividend|
+        verifier_evaluate_range_check(builder, remainder_magnitude, chi_eval)?;
+        verifier_evaluate_range_check(builder, remainder_gap, chi_eval)?;
+        verifier_evaluate_range_check(builder, quotient_magnitude, chi_eval)?;
+        verifier_evaluate_range_check(builder, quotient_gap, chi_eval)?;
+
+        Ok(if self.is_modulo { remainder } else { quotient })
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
+}
+
+/// Compute the truncated quotient and remainder of two columns along with the columns
+/// needed to prove them.
+///
+/// # Panics
+/// Panics if `dividend` and `divisor` do not have the same length
+/// or if a witness does not fit into a scalar, which can not happen for provable types.
+fn compute_division_witnesses<'a, S: Scalar>(
+    alloc: &'a Bump,
+    dividend: &[S],
+    divisor: &[S],
+) -> DivisionWitnesses<'a, S> {
+    assert_eq!(dividend.len(), divisor.len());
+    let len = dividend.len();
+    let to_scalar = |value: BigInt| S::try_from(value).expect("Witness should fit into a scalar");
+    let quotient = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    let remainder = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    let quotient_magnitude = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    let remainder_magnitude = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    let quotient_gap = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    let remainder_gap = alloc.alloc_slice_fill_copy(len, S::ZERO);
+    for i in 0..len {
+        let a: BigInt = dividend[i].into();
+        let b: BigInt = divisor[i].into();
+        let (q, r) = if b.is_zero() {
+            (BigInt::zero(), a.clone())
+        } else {
+            (&a / &b, &a % &b)
+        };
+        remainder_gap[i] = if b.is_zero() {
+            S::ZERO
+        } else {
+            to_scalar(b.abs() - r.abs() - 1)
+        };
+        quotient_gap[i] = to_scalar(a.abs() - q.abs());
+        quotient_magnitude[i] = to_scalar(q.abs());
+        remainder_magnitude[i] = to_scalar(r.abs());
+        quotient[i] = to_scalar(q);
+        remainder[i] = to_scalar(r);
+    }
+    DivisionWitnesses {
+        quotient,
+        remainder,
+        quotient_magnitude,
+        remainder_magnitude,
+        quotient_gap,
+        remainder_gap,
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+// select a / b as q, a % b as r from sxt.t
+#[test]
+fn we_can_prove_integer_division_and_modulo_with_mixed_signs() {
+    let data = owned_table([
+        bigint("a", [7_i64, -7, 7, -7, 0, 6, i64::MAX, i64::MIN]),
+        int("b", [2_i32, 2, -2, -2, 3, 3, -1, 7]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "r",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint(
+            "q",
+            [
+                3_i64,
+                -3,
+                -3,
+                3,
+                0,
+                2,
+                -i64::MAX,
+                -1_317_624_576_693_539_401,
+            ],
+        ),
+        bigint("r", [1_i64, -1, 1, -1, 0, 0, 0, -1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / 4 as c from sxt.t where a >= -2, select b from sxt.t where a % 2 = 0
+#[test]
+fn we_can_prove_a_decimal_division_and_a_filter_by_a_modulo() {
+    let data = owned_table([decimal75("a", 10, 2, [150_i64, -275, 300, -1])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), const_int(4)),
+            "c",
+        )],
+        tab(&t),
+        gte(column(&t, "a", &accessor), const_bigint(-2)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75(
+        "c",
+        21,
+        13,
+        [3_750_000_000_000_i64, 7_500_000_000_000, -25_000_000_000],
+    )]);
+    assert_eq!(res, expected_res);
+
+    let data = owned_table([
+        bigint("a", [4_i64, 5, -6, -7]),
+        bigint("b", [1_i64, 2, 3, 4]),
+    ]);
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        equal(
+            modulo(column(&t, "a", &accessor), const_smallint(2)),
+            const_bigint(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [1_i64, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q, a % b as r from sxt.t
+#[test]
+fn division_by_zero_yields_zero_quotient_and_the_dividend_as_remainder() {
+    let data = owned_table([smallint("a", [5_i16, -5]), smallint("b", [0_i16, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![
+            aliased_plan(
+                divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "r",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([smallint("q", [0_i16, 0]), smallint("r", [5_i16, -5])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t where b <> 0
+#[test]
+fn we_can_guard_a_division_against_a_zero_divisor_with_a_where_clause() {
+    let data = owned_table([bigint("a", [7_i64, 5, -9]), bigint("b", [2_i64, 0, 4])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "q",
+        )],
+        tab(&t),
+        not(equal(column(&t, "b", &accessor), const_bigint(0))),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("q", [3_i64, -2])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as c from sxt.t
+#[test]
+fn result_expr_can_overflow() {
+    let data = owned_table([smallint("a", [i16::MIN]), smallint("b", [-1_i16])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "c",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::Overflow)
+    ));
+}
+
+#[test]
+fn we_cannot_prove_divisions_of_unsupported_types() {
+    let data = owned_table([
+        int128("a", [1_i128]),
+        decimal75("b", 10, 2, [1_i64]),
+        varchar("c", ["a"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    // The quotient of two `Int128`s is too large to be proven
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(&t, "a", &accessor), column(&t, "a", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    // Modulo is only supported for integers
+    assert!(matches!(
+        DynProofExpr::try_new_modulo(column(&t, "b", &accessor), const_int(2)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(&t, "c", &accessor), const_int(2)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-30, 31);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            bigint("b", dist.sample_iter(&mut rng).take(n)),
+        ]);
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            vec![
+                aliased_plan(
+                    divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "q",
+                ),
+                aliased_plan(
+                    modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "r",
+                ),
+            ],
+            tab(&t),
+            not(equal(column(&t, "b", &accessor), const_bigint(0))),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_q, expected_r): (Vec<_>, Vec<_>) =
+            multizip((data["a"].i64_iter(), data["b"].i64_iter()))
+                .filter(|(_, b)| **b != 0)
+                .map(|(a, b)| (a / b, a % b))
+                .multiunzip();
+        let expected_result = owned_table([bigint("q", expected_q), bigint("r", expected_r)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(23);
+}
+
+// (a - 1.5) / b
+#[test]
+fn we_can_compute_the_correct_output_of_a_divide_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_smallint("a", [1_i16, 2, 3, 4], &alloc),
+        borrowed_int("b", [1_i32, -1, 5, 0], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let arithmetic_expr: DynProofExpr = divide(
+        subtract(column(&t, "a", &accessor), const_decimal75(2, 1, 15)),
+        column(&t, "b", &accessor),
+    );
+    let res = arithmetic_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [-500_000_000_000_i64, -500_000_000_000, 300_000_000_000, 0]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
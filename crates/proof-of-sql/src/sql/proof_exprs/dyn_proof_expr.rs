@@ -1,20 +1,23 @@
 use super::{
-    AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr,
-    MultiplyExpr, NotExpr, OrExpr, ProofExpr,
+    AddSubtractExpr, AggregateExpr, AndExpr, BetweenExpr, CaseExpr, ColumnExpr, DateTimeExpr,
+    DateTimeFunction, DivideAndModuloExpr, EqualsExpr, InListExpr, InSubqueryExpr, InequalityExpr,
+    IsNullExpr, LikeExpr, LiteralExpr, MultiplyExpr, NotExpr, NullableExpr, OrExpr, ProofExpr,
+    ScalarSubqueryExpr,
 };
 use crate::{
     base::{
-        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        database::{try_case_column_types, Column, ColumnRef, ColumnType, LiteralValue, Table},
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
     },
     sql::{
         parse::{type_check_binary_operation, ConversionError, ConversionResult},
-        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof::{FinalRoundBuilder, FirstRoundBuilder, ProofPlan, VerificationBuilder},
+        proof_plans::{DistinctExec, DynProofPlan},
     },
 };
-use alloc::{boxed::Box, string::ToString};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use bumpalo::Bump;
 use core::fmt::Debug;
 use proof_of_sql_parser::intermediate_ast::AggregationOperator;
@@ -33,16 +36,36 @@ pub enum DynProofExpr {
     Or(OrExpr),
     /// Provable logical NOT expression
     Not(NotExpr),
+    /// Provable expression that is NULL where its presence is FALSE
+    Nullable(NullableExpr),
+    /// Provable `IS NULL` / `IS NOT NULL` expression
+    IsNull(IsNullExpr),
     /// Provable CONST expression
     Literal(LiteralExpr),
     /// Provable AST expression for an equals expression
     Equals(EqualsExpr),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr),
+    /// Provable AST expression for an `IN` list expression
+    InList(InListExpr),
+    /// Provable AST expression for an `IN` subquery expression
+    InSubquery(InSubqueryExpr),
+    /// Provable AST expression for the value of a scalar subquery
+    ScalarSubquery(ScalarSubqueryExpr),
+    /// Provable AST expression for a `BETWEEN` expression
+    Between(BetweenExpr),
+    /// Provable AST expression for a `LIKE` expression
+    Like(LikeExpr),
     /// Provable numeric `+` / `-` expression
     AddSubtract(AddSubtractExpr),
     /// Provable numeric `*` expression
     Multiply(MultiplyExpr),
+    /// Provable numeric `/` / `%` expression
+    DivideAndModulo(DivideAndModuloExpr),
+    /// Provable `CASE WHEN` expression
+    Case(CaseExpr),
+    /// Provable date/time function of a timestamp
+    DateTime(DateTimeExpr),
     /// Provable aggregate expression
     Aggregate(AggregateExpr),
 }
@@ -68,6 +91,18 @@ impl DynProofExpr {
         expr.check_data_type(ColumnType::Boolean)?;
         Ok(Self::Not(NotExpr::new(Box::new(expr))))
     }
+    /// Create an expression that is NULL where the boolean expression `presence` is FALSE
+    pub fn try_new_nullable(value: DynProofExpr, presence: DynProofExpr) -> ConversionResult<Self> {
+        presence.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::Nullable(NullableExpr::new(
+            Box::new(value),
+            Box::new(presence),
+        )))
+    }
+    /// Create an `IS NULL` expression, or an `IS NOT NULL` expression if `negated` is true
+    pub fn new_is_null(expr: DynProofExpr, negated: bool) -> Self {
+        Self::IsNull(IsNullExpr::new(Box::new(expr), negated))
+    }
     /// Create CONST expression
     pub fn new_literal(value: LiteralValue) -> Self {
         Self::Literal(LiteralExpr::new(value))
@@ -107,6 +142,147 @@ impl DynProofExpr {
         }
     }
 
+    /// Create a new `IN` list expression
+    pub fn try_new_in_list(expr: DynProofExpr, list: Vec<DynProofExpr>) -> ConversionResult<Self> {
+        if list.is_empty() {
+            return Err(ConversionError::InvalidExpression {
+                expression: "IN lists must have at least one candidate".to_string(),
+            });
+        }
+        let expr_datatype = expr.data_type();
+        if let Some(candidate_datatype) =
+            list.iter()
+                .map(DynProofExpr::data_type)
+                .find(|&candidate_datatype| {
+                    !type_check_binary_operation(
+                        expr_datatype,
+                        candidate_datatype,
+                        &BinaryOperator::Eq,
+                    )
+                })
+        {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: expr_datatype.to_string(),
+                right_type: candidate_datatype.to_string(),
+            });
+        }
+        Ok(Self::InList(InListExpr::new(Box::new(expr), list)))
+    }
+
+    /// Create a new `IN` subquery expression, where `subquery` has a single result column
+    pub fn try_new_in_subquery(
+        expr: DynProofExpr,
+        subquery: DynProofPlan,
+    ) -> ConversionResult<Self> {
+        let subquery_datatype = Self::subquery_data_type(&subquery)?;
+        let expr_datatype = expr.data_type();
+        if !type_check_binary_operation(expr_datatype, subquery_datatype, &BinaryOperator::Eq) {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: expr_datatype.to_string(),
+                right_type: subquery_datatype.to_string(),
+            });
+        }
+        if !InSubqueryExpr::is_supported_type(expr_datatype)
+            || !InSubqueryExpr::is_supported_type(subquery_datatype)
+            || !DistinctExec::is_provable(&subquery)
+        {
+            return Err(ConversionError::Unprovable {
+                error: format!(
+                    "IN subqueries are not supported for values of types {expr_datatype} and {subquery_datatype}"
+                ),
+            });
+        }
+        Ok(Self::InSubquery(InSubqueryExpr::new(
+            Box::new(expr),
+            subquery,
+        )))
+    }
+
+    /// Create a new scalar subquery expression, where `subquery` has a single result column
+    /// and at most one row
+    pub fn try_new_scalar_subquery(subquery: DynProofPlan) -> ConversionResult<Self> {
+        let subquery_datatype = Self::subquery_data_type(&subquery)?;
+        if !ScalarSubqueryExpr::is_supported_type(subquery_datatype) {
+            return Err(ConversionError::Unprovable {
+                error: format!(
+                    "Scalar subqueries are not supported for values of type {subquery_datatype}"
+                ),
+            });
+        }
+        Ok(Self::ScalarSubquery(ScalarSubqueryExpr::new(Box::new(
+            subquery,
+        ))))
+    }
+
+    /// The type of the single result column of a subquery
+    fn subquery_data_type(subquery: &DynProofPlan) -> ConversionResult<ColumnType> {
+        match subquery.get_column_result_fields().as_slice() {
+            [field] => Ok(field.data_type()),
+            fields => Err(ConversionError::InvalidExpression {
+                expression: format!(
+                    "Subqueries must have a single result column, but this one has {}",
+                    fields.len()
+                ),
+            }),
+        }
+    }
+
+    /// Create a new `BETWEEN` expression
+    ///
+    /// Falls back to `expr >= low AND expr <= high` if a single sign decomposition can not decide it.
+    pub fn try_new_between(
+        expr: DynProofExpr,
+        low: DynProofExpr,
+        high: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        for bound in [&low, &high] {
+            if !type_check_binary_operation(
+                expr.data_type(),
+                bound.data_type(),
+                &BinaryOperator::Lt,
+            ) {
+                return Err(ConversionError::DataTypeMismatch {
+                    left_type: expr.data_type().to_string(),
+                    right_type: bound.data_type().to_string(),
+                });
+            }
+        }
+        if BetweenExpr::is_provable(&expr, &low, &high) {
+            Ok(Self::Between(BetweenExpr::new(
+                Box::new(expr),
+                Box::new(low),
+                Box::new(high),
+            )))
+        } else {
+            Self::try_new_and(
+                Self::try_new_not(Self::try_new_inequality(expr.clone(), low, true)?)?,
+                Self::try_new_not(Self::try_new_inequality(expr, high, false)?)?,
+            )
+        }
+    }
+
+    /// Create a new `LIKE` expression
+    ///
+    /// A string matches if `length_condition` holds and each character component holds its character.
+    pub fn try_new_like(
+        length_condition: DynProofExpr,
+        characters: Vec<(ColumnRef, char)>,
+    ) -> ConversionResult<Self> {
+        length_condition.check_data_type(ColumnType::Boolean)?;
+        let characters = characters
+            .into_iter()
+            .map(|(column_ref, character)| {
+                let column = ColumnExpr::new(column_ref);
+                Self::Column(column.clone()).check_data_type(ColumnType::Int)?;
+                Ok((column, character))
+            })
+            .collect::<ConversionResult<_>>()?;
+        Ok(Self::Like(LikeExpr::new(
+            Box::new(length_condition),
+            characters,
+        )))
+    }
+
     /// Create a new add expression
     pub fn try_new_add(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
         let lhs_datatype = lhs.data_type();
@@ -160,9 +336,113 @@ impl DynProofExpr {
         }
     }
 
+    /// Create a new divide expression
+    ///
+    /// Division truncates towards zero. Rows where the divisor is zero evaluate to a quotient of zero
+    /// instead of failing, since the expression is evaluated on every row of its table, including
+    /// rows that a `WHERE` clause leaves out, such as those of `WHERE b <> 0` in `SELECT a / b`.
+    /// This differs from the division of the postprocessing, which only sees the rows of the result
+    /// and fails with `ColumnOperationError::DivisionByZero`.
+    pub fn try_new_divide(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        Self::try_new_divide_or_modulo(lhs, rhs, false)
+    }
+
+    /// Create a new modulo expression
+    ///
+    /// The remainder takes the sign of the dividend. Rows where the divisor is zero evaluate to
+    /// the dividend instead of failing, for the same reason as in [`Self::try_new_divide`].
+    pub fn try_new_modulo(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        Self::try_new_divide_or_modulo(lhs, rhs, true)
+    }
+
+    fn try_new_divide_or_modulo(
+        lhs: DynProofExpr,
+        rhs: DynProofExpr,
+        is_modulo: bool,
+    ) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        let op = if is_modulo {
+            BinaryOperator::Modulo
+        } else {
+            BinaryOperator::Divide
+        };
+        if type_check_binary_operation(lhs_datatype, rhs_datatype, &op)
+            && DivideAndModuloExpr::try_scaling_exponents(lhs_datatype, rhs_datatype, is_modulo)
+                .is_some()
+        {
+            Ok(Self::DivideAndModulo(DivideAndModuloExpr::new(
+                Box::new(lhs),
+                Box::new(rhs),
+                is_modulo,
+            )))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: lhs_datatype.to_string(),
+                right_type: rhs_datatype.to_string(),
+            })
+        }
+    }
+
+    /// Create a new `CASE WHEN condition THEN then_expr ELSE else_expr END` expression
+    pub fn try_new_case(
+        condition: DynProofExpr,
+        then_expr: DynProofExpr,
+        else_expr: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        condition.check_data_type(ColumnType::Boolean)?;
+        let then_datatype = then_expr.data_type();
+        let else_datatype = else_expr.data_type();
+        if try_case_column_types(then_datatype, else_datatype).is_ok() {
+            Ok(Self::Case(CaseExpr::new(
+                Box::new(condition),
+                Box::new(then_expr),
+                Box::new(else_expr),
+            )))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: then_datatype.to_string(),
+                right_type: else_datatype.to_string(),
+            })
+        }
+    }
+
+    /// Create a new date/time function of a timestamp expression
+    pub fn try_new_date_time(
+        expr: DynProofExpr,
+        function: DateTimeFunction,
+    ) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        if !matches!(datatype, ColumnType::TimestampTZ(_, _)) {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!("date/time functions can not be applied to {datatype}"),
+            });
+        }
+        if !function.is_provable() {
+            return Err(ConversionError::Unprovable {
+                error: "months and years are only supported by EXTRACT".to_string(),
+            });
+        }
+        Ok(Self::DateTime(DateTimeExpr::new(Box::new(expr), function)))
+    }
+
     /// Create a new aggregate expression
     pub fn new_aggregate(op: AggregationOperator, expr: DynProofExpr) -> Self {
-        Self::Aggregate(AggregateExpr::new(op, Box::new(expr)))
+        Self::Aggregate(AggregateExpr::new(op, Box::new(expr), None))
+    }
+
+    /// Create a new aggregate expression of the rows where `filter` is `true`
+    pub fn try_new_filtered_aggregate(
+        op: AggregationOperator,
+        expr: DynProofExpr,
+        filter: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        filter.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::Aggregate(AggregateExpr::new(
+            op,
+            Box::new(expr),
+            Some(Box::new(filter)),
+        )))
     }
 
     /// Check that the plan has the correct data type
@@ -7,7 +7,9 @@ use crate::{
         scalar::Scalar,
         slice_ops,
     },
-    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
     utils::log,
 };
 use alloc::{boxed::Box, vec};
@@ -54,6 +56,27 @@ impl ProofExpr for EqualsExpr {
         res
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column = self.rhs.first_round_evaluate(builder, alloc, table);
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        let res = scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, true)
+            .expect("Failed to scale and subtract");
+        let res = Column::Boolean(result_evaluate_equals_zero(table.num_rows(), alloc, res));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
     #[tracing::instrument(name = "EqualsExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a, S: Scalar>(
         &self,
@@ -100,6 +123,10 @@ impl ProofExpr for EqualsExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
 }
 
 #[allow(
@@ -0,0 +1,233 @@
+use super::{
+    prover_evaluate_equals_zero, scale_and_add_subtract_eval, scale_and_subtract,
+    verifier_evaluate_equals_zero, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for an `IN` list expression
+///
+/// `expr IN (c_1, ..., c_n)` holds exactly where `(expr - c_1) * ... * (expr - c_n)` is zero.
+/// The partial products are committed one candidate at a time, so the proof needs
+/// a single zero check instead of an equality check and a disjunction per candidate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InListExpr {
+    expr: Box<DynProofExpr>,
+    list: Vec<DynProofExpr>,
+}
+
+impl InListExpr {
+    /// Create a new `IN` list expression
+    pub fn new(expr: Box<DynProofExpr>, list: Vec<DynProofExpr>) -> Self {
+        Self { expr, list }
+    }
+
+    /// Computes the differences between the expression and each of the candidates
+    ///
+    /// # Panics
+    /// Panics if a candidate can not be compared to the expression,
+    /// which `DynProofExpr::try_new_in_list` rules out.
+    fn differences<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        expr_column: Column<'a, S>,
+        candidate_columns: Vec<Column<'a, S>>,
+    ) -> Vec<&'a [S]> {
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        self.list
+            .iter()
+            .zip(candidate_columns)
+            .map(|(candidate, candidate_column)| {
+                let candidate_scale = candidate.data_type().scale().unwrap_or(0);
+                scale_and_subtract(
+                    alloc,
+                    expr_column,
+                    candidate_column,
+                    expr_scale,
+                    candidate_scale,
+                    true,
+                )
+                .expect("Failed to scale and subtract")
+            })
+            .collect()
+    }
+}
+
+impl ProofExpr for InListExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "InListExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.result_evaluate(alloc, table);
+        let candidate_columns = self
+            .list
+            .iter()
+            .map(|candidate| candidate.result_evaluate(alloc, table))
+            .collect();
+        let differences = self.differences(alloc, expr_column, candidate_columns);
+        let res = Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            differences
+                .iter()
+                .any(|difference| difference[i] == S::zero())
+        }));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.first_round_evaluate(builder, alloc, table);
+        let candidate_columns = self
+            .list
+            .iter()
+            .map(|candidate| candidate.first_round_evaluate(builder, alloc, table))
+            .collect();
+        let differences = self.differences(alloc, expr_column, candidate_columns);
+        let res = Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            differences
+                .iter()
+                .any(|difference| difference[i] == S::zero())
+        }));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "InListExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.prover_evaluate(builder, alloc, table);
+        let candidate_columns = self
+            .list
+            .iter()
+            .map(|candidate| candidate.prover_evaluate(builder, alloc, table))
+            .collect();
+        let differences = self.differences(alloc, expr_column, candidate_columns);
+        let (first_difference, other_differences) =
+            differences.split_first().expect("IN lists are never empty");
+        let product =
+            other_differences
+                .iter()
+                .fold(*first_difference, |partial_product, &difference| {
+                    let product: &[_] = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+                        partial_product[i] * difference[i]
+                    });
+                    builder.produce_intermediate_mle(product);
+
+                    // subpolynomial: product - partial_product * difference
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::one(), vec![Box::new(product)]),
+                            (
+                                -S::one(),
+                                vec![Box::new(partial_product), Box::new(difference)],
+                            ),
+                        ],
+                    );
+                    product
+                });
+        let res = Column::Boolean(prover_evaluate_equals_zero(
+            table.num_rows(),
+            builder,
+            alloc,
+            product,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let expr_scale = self.expr.data_type().scale().unwrap_or(0);
+        let difference_evals = self
+            .list
+            .iter()
+            .map(|candidate| {
+                let candidate_eval = candidate.verifier_evaluate(builder, accessor, chi_eval)?;
+                let candidate_scale = candidate.data_type().scale().unwrap_or(0);
+                Ok(scale_and_add_subtract_eval(
+                    expr_eval,
+                    candidate_eval,
+                    expr_scale,
+                    candidate_scale,
+                    true,
+                ))
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?;
+        let (first_difference_eval, other_difference_evals) = difference_evals
+            .split_first()
+            .ok_or(ProofError::VerificationError {
+                error: "IN list has no candidates",
+            })?;
+        let product_eval = other_difference_evals.iter().try_fold(
+            *first_difference_eval,
+            |partial_product_eval, &difference_eval| {
+                let product_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+                // subpolynomial: product - partial_product * difference
+                builder.try_produce_sumcheck_subpolynomial_evaluation(
+                    SumcheckSubpolynomialType::Identity,
+                    product_eval - partial_product_eval * difference_eval,
+                    2,
+                )?;
+                Ok::<_, ProofError>(product_eval)
+            },
+        )?;
+        verifier_evaluate_equals_zero(builder, product_eval, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        for candidate in &self.list {
+            candidate.get_column_references(columns);
+        }
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.expr.is_row_wise() && self.list.iter().all(ProofExpr::is_row_wise)
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use itertools::{multizip, MultiUnzip};
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+#[test]
+fn we_can_prove_an_in_list_query_with_no_rows() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        in_list(
+            column(&t, "a", &accessor),
+            vec![const_bigint(1), const_bigint(2)],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", [""; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_a_single_candidate() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 2]),
+        varchar("b", ["x", "y", "z", "w"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        in_list(column(&t, "a", &accessor), vec![const_bigint(2)]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", ["y", "w"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_on_strings_and_columns() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        varchar("a", ["abc", "de", "f", "gh", "f"]),
+        varchar("b", ["x", "gh", "y", "z", "f"]),
+        bigint("c", [1, 2, 3, 4, 5]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["c"], &accessor),
+        tab(&t),
+        in_list(
+            column(&t, "a", &accessor),
+            vec![
+                const_varchar("abc"),
+                column(&t, "b", &accessor),
+                const_varchar("gh"),
+            ],
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [1, 4, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_decimals_of_differing_scales() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 10, 2, [150, 200, -125, 0]),
+        bigint("b", [1, 2, 3, 4]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        not(in_list(
+            column(&t, "a", &accessor),
+            vec![const_bigint(2), const_decimal75(3, 1, -12), const_bigint(0)],
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [1, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_build_an_in_list_with_incomparable_candidates() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_in_list(
+            column(&t, "a", &accessor),
+            vec![const_bigint(1), const_varchar("x")],
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_in_list(column(&t, "a", &accessor), vec![]),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let dist = Uniform::new(-3, 4);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            varchar(
+                "b",
+                dist.sample_iter(&mut rng).take(n).map(|v| format!("s{v}")),
+            ),
+        ]);
+
+        // Generate random values to filter by
+        let candidates: Vec<i64> = dist.sample_iter(&mut rng).take(3).collect();
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            in_list(
+                column(&t, "a", &accessor),
+                candidates.iter().copied().map(const_bigint).collect(),
+            ),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_a, expected_b): (Vec<_>, Vec<_>) =
+            multizip((data["a"].i64_iter(), data["b"].string_iter()))
+                .filter_map(|(a, b)| {
+                    if candidates.contains(a) {
+                        Some((*a, b.clone()))
+                    } else {
+                        None
+                    }
+                })
+                .multiunzip();
+        let expected_result = owned_table([bigint("a", expected_a), varchar("b", expected_b)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(5121);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_in_list_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [-1, 9, 1, 4], &alloc),
+        borrowed_bigint("b", [1, 2, 3, 4], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let in_list_expr: DynProofExpr = in_list(
+        column(&t, "a", &accessor),
+        vec![const_bigint(1), column(&t, "b", &accessor)],
+    );
+    let res = in_list_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, false, true, true]);
+    assert_eq!(res, expected_res);
+}
@@ -0,0 +1,350 @@
+use super::{
+    prover_evaluate_equals_zero, scalar_subquery_expr::result_evaluate_subquery,
+    verifier_evaluate_equals_zero, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_shift,
+            first_round_evaluate_membership_check, first_round_evaluate_shift,
+            prover_evaluate_sign, verifier_evaluate_sign, verify_membership_check, verify_shift,
+        },
+        proof_plans::{DistinctExec, DynProofPlan},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for an `IN` predicate over the single column of the result of a subquery
+///
+/// `expr IN (SELECT ...)` is proven against the distinct values `z_1 < ... < z_m` of the subquery,
+/// see [`DistinctExec`]. Together with the sentinels `L < z_1` and `H > z_m` these split all values
+/// into the gaps `[L, z_1), [z_1, z_2), ..., [z_m, H)`, which are committed as the columns
+/// `lo = [L, z_1, ..., z_m]` and `hi = [z_1, ..., z_m, H]`. `lo` is proven to be a shift of the values
+/// of the subquery with the shift gadget and `hi` is derived from them directly.
+///
+/// For every row the prover commits the gap `[row_lo, row_hi)` that the value of `expr` lies in.
+/// It is proven to be one of the gaps with the membership check gadget and to contain the value of `expr`
+/// by the signs of `expr - row_lo` and `row_hi - expr - 1`. Since no value of the subquery lies inside a gap,
+/// the value of `expr` is a value of the subquery exactly where it equals `row_lo`.
+///
+/// Only integer types up to `BIGINT` are supported, so that the sentinels are outside the range of every value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InSubqueryExpr {
+    expr: Box<DynProofExpr>,
+    subquery: DistinctExec,
+}
+
+impl InSubqueryExpr {
+    /// Create a new `IN` subquery expression, where `subquery` has a single result column
+    pub fn new(expr: Box<DynProofExpr>, subquery: DynProofPlan) -> Self {
+        Self {
+            expr,
+            subquery: DistinctExec::new(Box::new(subquery)),
+        }
+    }
+
+    /// Whether `IN` subqueries can be proven for values of the type
+    pub fn is_supported_type(column_type: ColumnType) -> bool {
+        matches!(
+            column_type,
+            ColumnType::Uint8
+                | ColumnType::TinyInt
+                | ColumnType::SmallInt
+                | ColumnType::Int
+                | ColumnType::BigInt
+        )
+    }
+
+    /// Computes the result of the expression together with the witnesses of its proof
+    fn evaluate_with_witnesses<'a, S: Scalar>(
+        alloc: &'a Bump,
+        expr_column: Column<'a, S>,
+        subquery_result: &Table<'a, S>,
+    ) -> InSubqueryWitnesses<'a, S> {
+        let values: &[S] = alloc.alloc_slice_copy(&expr_column.to_scalar_with_scaling(0));
+        let subquery_values: &[S] = alloc.alloc_slice_copy(
+            &subquery_result
+                .columns()
+                .next()
+                .expect("IN subqueries have a single result column")
+                .to_scalar_with_scaling(0),
+        );
+        let num_gaps = subquery_values.len() + 1;
+        let lo: &[S] = alloc.alloc_slice_fill_with(num_gaps, |i| {
+            if i == 0 {
+                lower_sentinel()
+            } else {
+                subquery_values[i - 1]
+            }
+        });
+        let hi: &[S] = alloc.alloc_slice_fill_with(num_gaps, |i| {
+            subquery_values
+                .get(i)
+                .copied()
+                .unwrap_or_else(upper_sentinel)
+        });
+        // The values of the subquery are distinct and ascending,
+        // so the gap of a value is given by the number of values of the subquery up to it
+        let gap_indexes = values
+            .iter()
+            .map(|value| {
+                subquery_values
+                    .partition_point(|subquery_value| subquery_value.signed_cmp(value).is_le())
+            })
+            .collect::<Vec<_>>();
+        InSubqueryWitnesses {
+            values,
+            subquery_values,
+            lo,
+            hi,
+            row_lo: alloc.alloc_slice_fill_with(values.len(), |i| lo[gap_indexes[i]]),
+            row_hi: alloc.alloc_slice_fill_with(values.len(), |i| hi[gap_indexes[i]]),
+        }
+    }
+}
+
+/// The columns of the proof of an [`InSubqueryExpr`]
+struct InSubqueryWitnesses<'a, S: Scalar> {
+    /// The values of the expression
+    values: &'a [S],
+    /// The distinct values of the subquery in ascending order
+    subquery_values: &'a [S],
+    /// The lower bounds of the gaps between the values of the subquery
+    lo: &'a [S],
+    /// The upper bounds of the gaps between the values of the subquery
+    hi: &'a [S],
+    /// The lower bound of the gap of each row
+    row_lo: &'a [S],
+    /// The upper bound of the gap of each row
+    row_hi: &'a [S],
+}
+
+/// The lower bound of the first gap, which is smaller than every `BIGINT`
+fn lower_sentinel<S: Scalar>() -> S {
+    S::from(i128::from(i64::MIN) - 1)
+}
+
+/// The upper bound of the last gap, which is larger than every `BIGINT`
+fn upper_sentinel<S: Scalar>() -> S {
+    S::from(i128::from(i64::MAX) + 1)
+}
+
+impl ProofExpr for InSubqueryExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    /// The subquery is evaluated on `table` as well, see `result_evaluate_subquery`.
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let expr_column = self.expr.result_evaluate(alloc, table);
+        let subquery_result = result_evaluate_subquery(&self.subquery, alloc, table);
+        let witnesses = Self::evaluate_with_witnesses(alloc, expr_column, &subquery_result);
+        Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            witnesses.values[i] == witnesses.row_lo[i]
+        }))
+    }
+
+    #[tracing::instrument(
+        name = "InSubqueryExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.first_round_evaluate(builder, alloc, table);
+        let table_map = builder.table_map().clone();
+        let subquery_result = self
+            .subquery
+            .first_round_evaluate(builder, alloc, &table_map);
+        let witnesses = Self::evaluate_with_witnesses(alloc, expr_column, &subquery_result);
+        builder.produce_chi_evaluation_length(witnesses.lo.len());
+        first_round_evaluate_shift(builder, witnesses.subquery_values.len());
+        builder.produce_intermediate_mle(witnesses.row_lo);
+        builder.produce_intermediate_mle(witnesses.row_hi);
+        first_round_evaluate_membership_check(
+            builder,
+            alloc,
+            &[Column::Scalar(witnesses.lo), Column::Scalar(witnesses.hi)],
+            &[
+                Column::Scalar(witnesses.row_lo),
+                Column::Scalar(witnesses.row_hi),
+            ],
+        );
+        builder.request_post_result_challenges(2);
+        let res = Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            witnesses.values[i] == witnesses.row_lo[i]
+        }));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "InSubqueryExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column = self.expr.prover_evaluate(builder, alloc, table);
+        let table_map = builder.table_map().clone();
+        let subquery_result = self
+            .subquery
+            .final_round_evaluate(builder, alloc, &table_map);
+        let witnesses = Self::evaluate_with_witnesses(alloc, expr_column, &subquery_result);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 1. Prove that `lo` is `[L, z_1, ..., z_m]` by shifting the values of the subquery
+        let shifted_subquery_values: &[S] = alloc.alloc_slice_fill_with(witnesses.lo.len(), |i| {
+            if i == 0 {
+                S::ZERO
+            } else {
+                witnesses.subquery_values[i - 1]
+            }
+        });
+        builder.produce_intermediate_mle(shifted_subquery_values);
+        final_round_evaluate_shift(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witnesses.subquery_values,
+            shifted_subquery_values,
+        );
+        // 2. Prove that the gap of each row is one of the gaps
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            alloc.alloc_slice_fill_copy(witnesses.lo.len(), true),
+            alloc.alloc_slice_fill_copy(table.num_rows(), true),
+            &[Column::Scalar(witnesses.lo), Column::Scalar(witnesses.hi)],
+            &[
+                Column::Scalar(witnesses.row_lo),
+                Column::Scalar(witnesses.row_hi),
+            ],
+        );
+        // 3. Prove that the gap of each row contains its value
+        let lo_differences: &[S] = alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            witnesses.values[i] - witnesses.row_lo[i]
+        });
+        prover_evaluate_sign(builder, alloc, lo_differences);
+        prover_evaluate_sign(
+            builder,
+            alloc,
+            alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+                witnesses.row_hi[i] - witnesses.values[i] - S::ONE
+            }),
+        );
+        // 4. The value is a value of the subquery exactly where it is the lower bound of its gap
+        let res = Column::Boolean(prover_evaluate_equals_zero(
+            table.num_rows(),
+            builder,
+            alloc,
+            lo_differences,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let column_evals = builder.column_evaluations().clone();
+        let table_chi_evals = builder.table_chi_evaluations().clone();
+        let subquery_eval =
+            self.subquery
+                .verifier_evaluate(builder, &column_evals, None, &table_chi_evals)?;
+        let &[subquery_values_eval] = subquery_eval.column_evals() else {
+            return Err(ProofError::VerificationError {
+                error: "IN subquery does not have a single result column",
+            });
+        };
+        let subquery_chi_eval = subquery_eval.chi_eval();
+        let row_lo_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let row_hi_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        let gaps_chi_eval = builder.try_consume_chi_evaluation()?;
+
+        // 1. `lo` is the shift of the values of the subquery with `L` in its first row
+        let shifted_subquery_values_eval = builder.try_consume_final_round_mle_evaluation()?;
+        verify_shift(
+            builder,
+            alpha,
+            beta,
+            subquery_values_eval,
+            shifted_subquery_values_eval,
+            subquery_chi_eval,
+            gaps_chi_eval,
+        )?;
+        let lo_eval = shifted_subquery_values_eval
+            + lower_sentinel::<S>() * builder.singleton_chi_evaluation();
+        // `hi` is the values of the subquery with `H` in its last row
+        let hi_eval =
+            subquery_values_eval + upper_sentinel::<S>() * (gaps_chi_eval - subquery_chi_eval);
+        // 2. The gap of each row is one of the gaps
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            gaps_chi_eval,
+            chi_eval,
+            &[lo_eval, hi_eval],
+            &[row_lo_eval, row_hi_eval],
+        )?;
+        // 3. The gap of each row contains its value
+        let lo_difference_eval = expr_eval - row_lo_eval;
+        for difference_eval in [lo_difference_eval, row_hi_eval - expr_eval - chi_eval] {
+            if verifier_evaluate_sign(builder, difference_eval, chi_eval)? != S::ZERO {
+                return Err(ProofError::VerificationError {
+                    error: "IN subquery value is outside of its gap",
+                });
+            }
+        }
+        // 4. The value is a value of the subquery exactly where it is the lower bound of its gap
+        verifier_evaluate_equals_zero(builder, lo_difference_eval, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        columns.extend(self.subquery.get_column_references());
+    }
+
+    fn is_row_wise(&self) -> bool {
+        false
+    }
+}
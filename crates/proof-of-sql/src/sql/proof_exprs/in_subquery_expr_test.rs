@@ -0,0 +1,192 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+
+fn accessor_with_allow_list(
+    data: OwnedTable<Curve25519Scalar>,
+    allow_list: OwnedTable<Curve25519Scalar>,
+) -> (
+    TableRef,
+    TableRef,
+    OwnedTableTestAccessor<InnerProductProof>,
+) {
+    let t = TableRef::new("sxt", "t");
+    let allow = TableRef::new("sxt", "allow");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    accessor.add_table(allow.clone(), allow_list, 0);
+    (t, allow, accessor)
+}
+
+fn allowed_ids(
+    allow: &TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> DynProofPlan {
+    projection(cols_expr_plan(allow, &["id"], accessor), tab(allow))
+}
+
+/// `select b from sxt.t where a in (select id from sxt.allow)`
+#[test]
+fn we_can_prove_an_in_subquery_query() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([
+            bigint("a", [1, 2, 3, 2, 5, -7]),
+            varchar("b", ["x", "y", "z", "w", "v", "u"]),
+        ]),
+        owned_table([bigint("id", [5, 2, -7, 2, 9])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        in_subquery(column(&t, "a", &accessor), allowed_ids(&allow, &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", ["y", "w", "v", "u"])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select a from sxt.t where not a in (select id from sxt.allow where id > 0)`
+#[test]
+fn we_can_prove_a_not_in_subquery_query_with_a_filtered_subquery() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([int("a", [i32::MIN, -1, 0, 1, 2, i32::MAX])]),
+        owned_table([int("id", [-1, 1, i32::MAX, 0])]),
+    );
+    let subquery = filter(
+        cols_expr_plan(&allow, &["id"], &accessor),
+        tab(&allow),
+        gte(column(&allow, "id", &accessor), const_int(1)),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        not(in_subquery(column(&t, "a", &accessor), subquery)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([int("a", [i32::MIN, -1, 0, 2])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select a from sxt.t where a in (select id from sxt.allow)` with an empty allow list
+#[test]
+fn we_can_prove_an_in_subquery_query_with_an_empty_subquery() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([bigint("a", [i64::MIN, 0, i64::MAX])]),
+        owned_table([bigint("id", [0; 0])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        in_subquery(column(&t, "a", &accessor), allowed_ids(&allow, &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select a from sxt.t where a in (select id from sxt.allow)` with an empty table
+#[test]
+fn we_can_prove_an_in_subquery_query_with_no_rows() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([bigint("a", [0; 0])]),
+        owned_table([bigint("id", [1, 2])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        in_subquery(column(&t, "a", &accessor), allowed_ids(&allow, &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_an_in_subquery_expr_with_unsupported_types() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([varchar("a", ["x"]), int128("b", [1])]),
+        owned_table([varchar("id", ["x"]), int128("value", [1])]),
+    );
+    assert!(matches!(
+        DynProofExpr::try_new_in_subquery(
+            column(&t, "a", &accessor),
+            allowed_ids(&allow, &accessor)
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_in_subquery(
+            column(&t, "b", &accessor),
+            projection(cols_expr_plan(&allow, &["value"], &accessor), tab(&allow))
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_in_subquery(
+            column(&t, "b", &accessor),
+            allowed_ids(&allow, &accessor)
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_create_an_in_subquery_expr_with_more_than_one_column() {
+    let (t, allow, accessor) = accessor_with_allow_list(
+        owned_table([bigint("a", [1])]),
+        owned_table([bigint("id", [1]), bigint("other", [2])]),
+    );
+    assert!(matches!(
+        DynProofExpr::try_new_in_subquery(
+            column(&t, "a", &accessor),
+            projection(
+                cols_expr_plan(&allow, &["id", "other"], &accessor),
+                tab(&allow)
+            )
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_in_subquery_of_the_same_table_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2, 3, 4, 5], &alloc),
+        borrowed_bigint("b", [4, 1, 7, 4, 2], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let subquery = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        gte(column(&t, "b", &accessor), const_bigint(2)),
+    );
+    let in_subquery_expr = in_subquery(column(&t, "a", &accessor), subquery);
+    let res = in_subquery_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, true, false, true, false]);
+    assert_eq!(res, expected_res);
+}
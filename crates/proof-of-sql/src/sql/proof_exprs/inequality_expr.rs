@@ -7,7 +7,7 @@ use crate::{
         scalar::Scalar,
     },
     sql::{
-        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
         proof_gadgets::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign},
     },
     utils::log,
@@ -65,6 +65,35 @@ impl ProofExpr for InequalityExpr {
         res
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column = self.rhs.first_round_evaluate(builder, alloc, table);
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        let table_length = table.num_rows();
+        let diff = if self.is_lt {
+            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
+                .expect("Failed to scale and subtract")
+        } else {
+            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
+                .expect("Failed to scale and subtract")
+        };
+
+        // (sign(diff) == -1)
+        let res = Column::Boolean(result_evaluate_sign(table_length, alloc, diff));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
     #[tracing::instrument(name = "InequalityExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a, S: Scalar>(
         &self,
@@ -118,4 +147,8 @@ impl ProofExpr for InequalityExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
 }
@@ -0,0 +1,123 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable `IS NULL` / `IS NOT NULL` expression
+///
+/// The result is never NULL. It is the presence of the expression for `IS NOT NULL`
+/// and its negation for `IS NULL`, so it is constant if the expression is not nullable.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IsNullExpr {
+    expr: Box<DynProofExpr>,
+    negated: bool,
+}
+
+impl IsNullExpr {
+    /// Create an `IS NULL` expression, or an `IS NOT NULL` expression if `negated` is true
+    pub fn new(expr: Box<DynProofExpr>, negated: bool) -> Self {
+        Self { expr, negated }
+    }
+
+    /// Evaluates the result from the evaluation of the expression
+    fn evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        expr_column: Column<'a, S>,
+    ) -> Column<'a, S> {
+        let presence = expr_column.presence();
+        Column::Boolean(alloc.alloc_slice_fill_with(expr_column.len(), |i| {
+            presence.is_none_or(|presence| presence[i]) == self.negated
+        }))
+    }
+}
+
+impl ProofExpr for IsNullExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "IsNullExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let res = self.evaluate(alloc, expr_column);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column: Column<'a, S> = self.expr.first_round_evaluate(builder, alloc, table);
+        let res = self.evaluate(alloc, expr_column);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "IsNullExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let res = self.evaluate(alloc, expr_column);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let (_, presence) = self
+            .expr
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
+        let presence = presence.unwrap_or(chi_eval);
+        Ok(if self.negated {
+            presence
+        } else {
+            chi_eval - presence
+        })
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.expr.is_row_wise()
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+
+#[test]
+fn we_can_prove_an_is_null_query() {
+    let data = owned_table([
+        nullable(bigint("a", [1_i64, 7, 0, 5]), [true, false, true, false]),
+        bigint("b", [10_i64, 20, 30, 40]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        is_null(nullable_column(&t, "a", &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [20_i64, 40])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_is_not_null_on_nullable_and_non_nullable_expressions() {
+    let data = owned_table([
+        nullable(bigint("a", [1_i64, 7, 0, 5]), [true, false, true, false]),
+        bigint("b", [10_i64, 20, 30, 40]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(is_not_null(nullable_column(&t, "a", &accessor)), "a"),
+            aliased_plan(is_not_null(column(&t, "b", &accessor)), "b"),
+        ],
+        tab(&t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("a", [true, false, true, false]),
+        boolean("b", [true; 4]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_that_the_connectives_follow_three_valued_logic() {
+    let data = owned_table([nullable(
+        bigint("a", [1_i64, 7, 0, 5]),
+        [true, false, true, false],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let a_is_zero = || {
+        nullable_expr(
+            equal(column(&t, "a", &accessor), const_bigint(0)),
+            presence(&t, "a", &accessor),
+        )
+    };
+    let ast = projection(
+        vec![
+            // NULL AND FALSE is FALSE, NULL AND TRUE is NULL
+            aliased_plan(is_null(and(a_is_zero(), const_bool(false))), "and_false"),
+            aliased_plan(is_null(and(a_is_zero(), const_bool(true))), "and_true"),
+            // NULL OR TRUE is TRUE, NULL OR FALSE is NULL
+            aliased_plan(is_null(or(a_is_zero(), const_bool(true))), "or_true"),
+            aliased_plan(is_null(or(a_is_zero(), const_bool(false))), "or_false"),
+            // NOT NULL is NULL
+            aliased_plan(is_null(not(a_is_zero())), "not"),
+        ],
+        tab(&t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("and_false", [false; 4]),
+        boolean("and_true", [false, true, false, true]),
+        boolean("or_true", [false; 4]),
+        boolean("or_false", [false, true, false, true]),
+        boolean("not", [false, true, false, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_an_is_null_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([(
+        "a".into(),
+        Column::<TestScalar>::Nullable(
+            alloc.alloc(Column::BigInt(&[1, 0, 0, 0])),
+            &[true, false, true, false],
+        ),
+    )]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let is_null_expr: DynProofExpr = is_null(nullable_column(&t, "a", &accessor));
+    let res = is_null_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, true, false, true]);
+    assert_eq!(res, expected_res);
+}
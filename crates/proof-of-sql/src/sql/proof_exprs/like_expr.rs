@@ -0,0 +1,221 @@
+use super::{
+    prover_evaluate_equals_zero, verifier_evaluate_equals_zero, ColumnExpr, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// The number of characters whose differences from the pattern are packed into a single scalar
+const CHARACTERS_PER_CHUNK: usize = 7;
+
+/// The differences of `Int` character components from a code point are less than `2^32` in absolute value,
+/// so packing them in base `2^33` is injective as long as the packed value does not wrap around.
+const PACKING_BASE: u64 = 1 << 33;
+
+/// Provable AST expression for a `LIKE` expression
+///
+/// A string matches a pattern where the condition on its length holds
+/// and each of its character components holds the character of the pattern that it is checked against.
+/// The differences between the character components and the pattern are packed into a single scalar
+/// per [`CHARACTERS_PER_CHUNK`] characters, so that the characters are checked
+/// with one zero check per chunk instead of an equality check per character.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LikeExpr {
+    length_condition: Box<DynProofExpr>,
+    characters: Vec<(ColumnExpr, char)>,
+}
+
+impl LikeExpr {
+    /// Create a new `LIKE` expression
+    pub fn new(length_condition: Box<DynProofExpr>, characters: Vec<(ColumnExpr, char)>) -> Self {
+        Self {
+            length_condition,
+            characters,
+        }
+    }
+
+    /// Computes the packed differences between the character components and the pattern, one per chunk
+    fn packed_differences<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Vec<&'a [S]> {
+        self.characters
+            .chunks(CHARACTERS_PER_CHUNK)
+            .map(|chunk| {
+                let packed = alloc.alloc_slice_fill_copy(table.num_rows(), S::zero());
+                let mut weight = S::one();
+                for (column, character) in chunk {
+                    let code_point = S::from(u64::from(u32::from(*character)));
+                    let values = column.fetch_column(alloc, table).to_scalar_with_scaling(0);
+                    for (packed, value) in packed.iter_mut().zip(values) {
+                        *packed += weight * (value - code_point);
+                    }
+                    weight *= S::from(PACKING_BASE);
+                }
+                packed as &[_]
+            })
+            .collect()
+    }
+
+    /// Evaluates the expression without proving it
+    ///
+    /// # Panics
+    /// Panics if the length condition is not boolean, which `DynProofExpr::try_new_like` rules out.
+    fn evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+        length_condition: Column<'a, S>,
+    ) -> Column<'a, S> {
+        let length_condition = length_condition
+            .as_boolean()
+            .expect("length condition is not boolean");
+        let differences = self.packed_differences(alloc, table);
+        Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| {
+            length_condition[i]
+                && differences
+                    .iter()
+                    .all(|difference| difference[i] == S::zero())
+        }))
+    }
+}
+
+impl ProofExpr for LikeExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "LikeExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let length_condition = self.length_condition.result_evaluate(alloc, table);
+        let res = self.evaluate(alloc, table, length_condition);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let length_condition = self
+            .length_condition
+            .first_round_evaluate(builder, alloc, table);
+        let res = self.evaluate(alloc, table, length_condition);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "LikeExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let length_condition_column = self.length_condition.prover_evaluate(builder, alloc, table);
+        let length_condition = length_condition_column
+            .as_boolean()
+            .expect("length condition is not boolean");
+        let res = self.packed_differences(alloc, table).into_iter().fold(
+            length_condition,
+            |matches, difference| {
+                let chunk_matches =
+                    prover_evaluate_equals_zero(table.num_rows(), builder, alloc, difference);
+
+                // matches_and_chunk_matches
+                let matches_and_chunk_matches: &[_] = alloc
+                    .alloc_slice_fill_with(table.num_rows(), |i| matches[i] && chunk_matches[i]);
+                builder.produce_intermediate_mle(matches_and_chunk_matches);
+
+                // subpolynomial: matches_and_chunk_matches - matches * chunk_matches
+                builder.produce_sumcheck_subpolynomial(
+                    SumcheckSubpolynomialType::Identity,
+                    vec![
+                        (S::one(), vec![Box::new(matches_and_chunk_matches)]),
+                        (-S::one(), vec![Box::new(matches), Box::new(chunk_matches)]),
+                    ],
+                );
+                matches_and_chunk_matches
+            },
+        );
+
+        log::log_memory_usage("End");
+
+        Column::Boolean(res)
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let length_condition = self
+            .length_condition
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+        self.characters
+            .chunks(CHARACTERS_PER_CHUNK)
+            .try_fold(length_condition, |matches, chunk| {
+                let mut difference = S::zero();
+                let mut weight = S::one();
+                for (column, character) in chunk {
+                    let code_point = S::from(u64::from(u32::from(*character)));
+                    let eval = column.verifier_evaluate(builder, accessor, chi_eval)?;
+                    difference += weight * (eval - code_point * chi_eval);
+                    weight *= S::from(PACKING_BASE);
+                }
+                let chunk_matches = verifier_evaluate_equals_zero(builder, difference, chi_eval)?;
+
+                // matches_and_chunk_matches
+                let matches_and_chunk_matches = builder.try_consume_final_round_mle_evaluation()?;
+
+                // subpolynomial: matches_and_chunk_matches - matches * chunk_matches
+                builder.try_produce_sumcheck_subpolynomial_evaluation(
+                    SumcheckSubpolynomialType::Identity,
+                    matches_and_chunk_matches - matches * chunk_matches,
+                    2,
+                )?;
+                Ok(matches_and_chunk_matches)
+            })
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.length_condition.get_column_references(columns);
+        for (column, _) in &self.characters {
+            column.get_column_references(columns);
+        }
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.length_condition.is_row_wise()
+    }
+}
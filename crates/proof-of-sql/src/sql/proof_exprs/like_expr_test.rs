@@ -0,0 +1,235 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnComponent, ColumnRef,
+            ColumnType, LikePattern, OwnedTable, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use rand::{
+    distributions::{Distribution, Uniform},
+    rngs::StdRng,
+};
+use rand_core::SeedableRng;
+
+/// Creates a table with the string column `a` and the column `b`
+fn table_with_strings(a: &[&str], b: &[i64]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        varchar("a", a.iter().copied()),
+        bigint("b", b.iter().copied()),
+    ])
+}
+
+#[test]
+fn we_can_prove_a_like_query_with_no_rows() {
+    let data = table_with_strings(&[], &[]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        like(&t, "a", "ab%", &accessor),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("a", [""; 0]), bigint("b", [0; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, b from sxt.t where a like 'ab%'
+#[test]
+fn we_can_prove_a_prefix_query() {
+    let data = table_with_strings(
+        &["abc", "ab", "a", "xabc", "", "abcdef"],
+        &[1, 2, 3, 4, 5, 6],
+    );
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        like(&t, "a", "ab%", &accessor),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("a", ["abc", "ab", "abcdef"]),
+        bigint("b", [1, 2, 6]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select b, a like '%.com' as is_com, a like 'a_c' as is_axc, a like 'x%_z' as is_xz from sxt.t
+#[test]
+fn we_can_prove_suffix_exact_and_mixed_patterns() {
+    let data = table_with_strings(
+        &["a.com", "abc", "xyz", "xz", "axc.com", ".com"],
+        &[1, 2, 3, 4, 5, 6],
+    );
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            col_expr_plan(&t, "b", &accessor),
+            aliased_plan(like(&t, "a", "%.com", &accessor), "is_com"),
+            aliased_plan(like(&t, "a", "a_c", &accessor), "is_axc"),
+            aliased_plan(like(&t, "a", "x%_z", &accessor), "is_xz"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("b", [1, 2, 3, 4, 5, 6]),
+        boolean("is_com", [true, false, false, false, true, true]),
+        boolean("is_axc", [false, true, false, false, false, false]),
+        boolean("is_xz", [false, false, true, false, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// Patterns with more characters than fit in a single zero check are checked in several chunks
+#[test]
+fn we_can_prove_a_like_query_with_a_long_pattern() {
+    let data = table_with_strings(
+        &[
+            "SKU-2024-0001",
+            "SKU-2024-0002",
+            "SKU-2023-0001",
+            "SKU-2024-",
+            "sku-2024-0003",
+        ],
+        &[1, 2, 3, 4, 5],
+    );
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        like(&t, "a", "SKU-2024-_%1", &accessor),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [1])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_build_a_like_expression_with_invalid_columns() {
+    let data = table_with_strings(&["ab"], &[1]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_like(
+            DynProofExpr::new_column(
+                col_ref(&t, "a", &accessor).with_component(ColumnComponent::Length)
+            ),
+            vec![(
+                col_ref(&t, "a", &accessor).with_component(ColumnComponent::Character(0)),
+                'a'
+            )],
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_like(
+            const_bool(true),
+            vec![(
+                ColumnRef::new(t.clone(), "b".into(), ColumnType::BigInt),
+                'a'
+            )],
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+}
+
+fn test_random_tables_with_given_offset(offset: usize) {
+    let alphabet = ['a', 'b', 'é'];
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let random_string = |rng: &mut StdRng| -> String {
+        let len = Uniform::new(0, 5).sample(rng);
+        (0..len)
+            .map(|_| alphabet[Uniform::new(0, alphabet.len()).sample(rng)])
+            .collect()
+    };
+    let patterns = ["a%", "%b", "_é%", "a%a", "ab", "%", "__"];
+    for _ in 0..20 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let values: Vec<String> = (0..n).map(|_| random_string(&mut rng)).collect();
+        let data: OwnedTable<Curve25519Scalar> = owned_table([varchar("a", values.clone())]);
+
+        // Pick a random pattern to filter by
+        let pattern = patterns[Uniform::new(0, patterns.len()).sample(&mut rng)];
+
+        // Create and verify proof
+        let t = TableRef::new("sxt", "t");
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t.clone(),
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            like(&t, "a", pattern, &accessor),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let like_pattern = LikePattern::new(pattern);
+        let expected_a: Vec<String> = values
+            .into_iter()
+            .filter(|value| like_pattern.matches(value))
+            .collect();
+        let expected_result = owned_table([varchar("a", expected_a)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_zero_offset() {
+    test_random_tables_with_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_using_a_non_zero_offset() {
+    test_random_tables_with_given_offset(1001);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_like_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_varchar("a", ["abc", "xbc", "ab", "abcd"], &alloc)]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let like_expr: DynProofExpr = like(&t, "a", "a%c", &accessor);
+    let res = like_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[true, false, false, false]);
+    assert_eq!(res, expected_res);
+}
@@ -6,7 +6,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
     utils::log,
 };
 use bumpalo::Bump;
@@ -55,6 +55,15 @@ impl ProofExpr for LiteralExpr {
         res
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        self.result_evaluate(alloc, table)
+    }
+
     #[tracing::instrument(name = "LiteralExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a, S: Scalar>(
         &self,
@@ -82,4 +91,8 @@ impl ProofExpr for LiteralExpr {
     }
 
     fn get_column_references(&self, _columns: &mut IndexSet<ColumnRef>) {}
+
+    fn is_row_wise(&self) -> bool {
+        true
+    }
 }
@@ -20,6 +20,11 @@ use multiply_expr::MultiplyExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod multiply_expr_test;
 
+mod divide_and_modulo_expr;
+use divide_and_modulo_expr::DivideAndModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod divide_and_modulo_expr_test;
+
 mod dyn_proof_expr;
 pub(crate) use dyn_proof_expr::DynProofExpr;
 
@@ -48,6 +53,52 @@ use not_expr::NotExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod not_expr_test;
 
+mod nullable_expr;
+use nullable_expr::{nullable_column, NullableExpr};
+#[cfg(all(test, feature = "blitzar"))]
+mod nullable_expr_test;
+
+mod is_null_expr;
+use is_null_expr::IsNullExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod is_null_expr_test;
+
+mod in_list_expr;
+use in_list_expr::InListExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_expr_test;
+
+mod in_subquery_expr;
+use in_subquery_expr::InSubqueryExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_subquery_expr_test;
+
+mod scalar_subquery_expr;
+use scalar_subquery_expr::ScalarSubqueryExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod scalar_subquery_expr_test;
+
+mod between_expr;
+use between_expr::BetweenExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod between_expr_test;
+
+mod case_expr;
+use case_expr::CaseExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod case_expr_test;
+
+mod like_expr;
+use like_expr::LikeExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod like_expr_test;
+
+mod date_time_expr;
+use date_time_expr::DateTimeExpr;
+pub(crate) use date_time_expr::DateTimeFunction;
+#[cfg(all(test, feature = "blitzar"))]
+mod date_time_expr_test;
+
 mod comparison_util;
 pub(crate) use comparison_util::scale_and_subtract;
 
@@ -57,7 +108,9 @@ pub(crate) use numerical_util::{
 };
 
 mod equals_expr;
-pub(crate) use equals_expr::EqualsExpr;
+pub(crate) use equals_expr::{
+    prover_evaluate_equals_zero, verifier_evaluate_equals_zero, EqualsExpr,
+};
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
@@ -7,7 +7,9 @@ use crate::{
         scalar::Scalar,
     },
     sql::{
-        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+        },
         proof_exprs::multiply_columns,
     },
     utils::log,
@@ -47,6 +49,18 @@ impl ProofExpr for MultiplyExpr {
         Column::Scalar(scalars)
     }
 
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.first_round_evaluate(builder, alloc, table);
+        let scalars = multiply_columns(&lhs_column, &rhs_column, alloc);
+        Column::Scalar(scalars)
+    }
+
     #[tracing::instrument(
         name = "proofs.sql.ast.multiply_expr.prover_evaluate",
         level = "info",
@@ -109,4 +123,8 @@ impl ProofExpr for MultiplyExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
 }
@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{nullable_column, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -6,7 +6,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
     utils::log,
 };
 use alloc::boxed::Box;
@@ -14,6 +14,8 @@ use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
 /// Provable logical NOT expression
+///
+/// If the expression is nullable, the result is NULL where the expression is NULL.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NotExpr {
     expr: Box<DynProofExpr>,
@@ -40,8 +42,23 @@ impl ProofExpr for NotExpr {
         log::log_memory_usage("Start");
 
         let expr_column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
-        let expr = expr_column.as_boolean().expect("expr is not boolean");
-        let res = Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]));
+        let res = evaluate_not(alloc, expr_column);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let expr_column: Column<'a, S> = self.expr.first_round_evaluate(builder, alloc, table);
+        let res = evaluate_not(alloc, expr_column);
 
         log::log_memory_usage("End");
 
@@ -58,8 +75,7 @@ impl ProofExpr for NotExpr {
         log::log_memory_usage("Start");
 
         let expr_column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
-        let expr = expr_column.as_boolean().expect("expr is not boolean");
-        let res = Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]));
+        let res = evaluate_not(alloc, expr_column);
 
         log::log_memory_usage("End");
 
@@ -72,11 +88,41 @@ impl ProofExpr for NotExpr {
         accessor: &IndexMap<ColumnRef, S>,
         chi_eval: S,
     ) -> Result<S, ProofError> {
-        let eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
-        Ok(chi_eval - eval)
+        self.verifier_evaluate_nullable(builder, accessor, chi_eval)
+            .map(|(eval, _)| eval)
+    }
+
+    fn is_nullable(&self) -> bool {
+        self.expr.is_nullable()
+    }
+
+    fn verifier_evaluate_nullable<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<(S, Option<S>), ProofError> {
+        let (eval, presence) = self
+            .expr
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
+        Ok((presence.unwrap_or(chi_eval) - eval, presence))
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.expr.is_row_wise()
+    }
+}
+
+/// Evaluates `NOT expr`, which is NULL where `expr` is NULL and whose values are FALSE there
+fn evaluate_not<'a, S: Scalar>(alloc: &'a Bump, expr_column: Column<'a, S>) -> Column<'a, S> {
+    let expr = expr_column.as_boolean().expect("expr is not boolean");
+    let presence = expr_column.presence();
+    let values = Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| {
+        presence.is_none_or(|presence| presence[i]) && !expr[i]
+    }));
+    nullable_column(alloc, values, presence)
 }
@@ -0,0 +1,176 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable expression that is NULL where its presence is FALSE
+///
+/// The values of the result hold the default value of the type in the NULL rows,
+/// which is what the expressions that take nullable input rely on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NullableExpr {
+    value: Box<DynProofExpr>,
+    presence: Box<DynProofExpr>,
+}
+
+impl NullableExpr {
+    /// Create a nullable expression from the expression of its values and the boolean expression of its presence
+    pub fn new(value: Box<DynProofExpr>, presence: Box<DynProofExpr>) -> Self {
+        Self { value, presence }
+    }
+
+    /// Evaluates the masked values of the expression and its presence
+    fn evaluate<'a, S: Scalar>(
+        alloc: &'a Bump,
+        value_column: Column<'a, S>,
+        presence_column: Column<'a, S>,
+    ) -> (Column<'a, S>, &'a [bool]) {
+        let presence = presence_column
+            .as_boolean()
+            .expect("presence is not boolean");
+        (value_column.mask(alloc, presence), presence)
+    }
+}
+
+impl ProofExpr for NullableExpr {
+    fn data_type(&self) -> ColumnType {
+        self.value.data_type()
+    }
+
+    #[tracing::instrument(name = "NullableExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let value_column: Column<'a, S> = self.value.result_evaluate(alloc, table);
+        let presence_column: Column<'a, S> = self.presence.result_evaluate(alloc, table);
+        let (masked, presence) = Self::evaluate(alloc, value_column, presence_column);
+        let res = nullable_column(alloc, masked, Some(presence));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let value_column: Column<'a, S> = self.value.first_round_evaluate(builder, alloc, table);
+        let presence_column: Column<'a, S> =
+            self.presence.first_round_evaluate(builder, alloc, table);
+        let (masked, presence) = Self::evaluate(alloc, value_column, presence_column);
+        let res = nullable_column(alloc, masked, Some(presence));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "NullableExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let value_column: Column<'a, S> = self.value.prover_evaluate(builder, alloc, table);
+        let presence_column: Column<'a, S> = self.presence.prover_evaluate(builder, alloc, table);
+        let (masked, presence) = Self::evaluate(alloc, value_column, presence_column);
+        builder.produce_intermediate_mle(masked);
+
+        // subpolynomial: masked - value * presence
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(masked)]),
+                (-S::one(), vec![Box::new(value_column), Box::new(presence)]),
+            ],
+        );
+        let res = nullable_column(alloc, masked, Some(presence));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        self.verifier_evaluate_nullable(builder, accessor, chi_eval)
+            .map(|(eval, _)| eval)
+    }
+
+    fn is_nullable(&self) -> bool {
+        true
+    }
+
+    fn verifier_evaluate_nullable<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<(S, Option<S>), ProofError> {
+        let value = self.value.verifier_evaluate(builder, accessor, chi_eval)?;
+        let presence = self
+            .presence
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+
+        // masked
+        let masked = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: masked - value * presence
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            masked - value * presence,
+            2,
+        )?;
+
+        Ok((masked, Some(presence)))
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.value.get_column_references(columns);
+        self.presence.get_column_references(columns);
+    }
+
+    fn is_row_wise(&self) -> bool {
+        self.value.is_row_wise() && self.presence.is_row_wise()
+    }
+}
+
+/// Returns `values` as a nullable column if `presence` is given, and as it is otherwise
+pub(crate) fn nullable_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    values: Column<'a, S>,
+    presence: Option<&'a [bool]>,
+) -> Column<'a, S> {
+    match presence {
+        Some(presence) => Column::Nullable(alloc.alloc(values), presence),
+        None => values,
+    }
+}
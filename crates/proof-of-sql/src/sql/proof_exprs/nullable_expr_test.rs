@@ -0,0 +1,101 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+
+#[test]
+fn we_can_prove_a_projection_of_nullable_expressions() {
+    let data = owned_table([
+        nullable(bigint("a", [1_i64, 7, 0, 5]), [true, false, true, true]),
+        bigint("b", [10_i64, 20, 30, 40]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(nullable_column(&t, "a", &accessor), "a"),
+            aliased_plan(
+                nullable_expr(
+                    add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    presence(&t, "a", &accessor),
+                ),
+                "c",
+            ),
+        ],
+        tab(&t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        nullable(bigint("a", [1_i64, 0, 0, 5]), [true, false, true, true]),
+        nullable(bigint("c", [11_i64, 0, 30, 45]), [true, false, true, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_a_nullable_comparison() {
+    let data = owned_table([
+        nullable(bigint("a", [1_i64, 7, 0, 5]), [true, false, true, true]),
+        bigint("b", [10_i64, 20, 30, 40]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    // The NULL row has the value 0 and must not be selected
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        nullable_expr(
+            equal(column(&t, "a", &accessor), const_bigint(0)),
+            presence(&t, "a", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [30_i64])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_nullable_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        (
+            "a".into(),
+            Column::<TestScalar>::Nullable(
+                alloc.alloc(Column::BigInt(&[1, 0, 0, 5])),
+                &[true, false, true, true],
+            ),
+        ),
+        borrowed_bigint("b", [10, 20, 30, 40], &alloc),
+    ]);
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = TableRef::new("sxt", "t");
+    accessor.add_table(t.clone(), data.clone(), 0);
+    let nullable_expr: DynProofExpr = nullable_expr(
+        add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+        presence(&t, "a", &accessor),
+    );
+    let res = nullable_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Nullable(
+        &Column::BigInt(&[11, 0, 30, 45]),
+        &[true, false, true, true],
+    );
+    assert_eq!(res, expected_res);
+}
@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{nullable_column, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -6,7 +6,9 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
     utils::log,
 };
 use alloc::{boxed::Box, vec};
@@ -14,6 +16,9 @@ use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
 /// Provable logical OR expression
+///
+/// If either side is nullable, it follows three-valued logic: the result is TRUE if either side is TRUE,
+/// and NULL otherwise if either side is NULL.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct OrExpr {
     lhs: Box<DynProofExpr>,
@@ -44,7 +49,50 @@ impl ProofExpr for OrExpr {
         let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
         let lhs = lhs_column.as_boolean().expect("lhs is not boolean");
         let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
-        let res = Column::Boolean(result_evaluate_or(table.num_rows(), alloc, lhs, rhs));
+        let presence = result_evaluate_or_presence(
+            table.num_rows(),
+            alloc,
+            lhs_column.presence(),
+            rhs_column.presence(),
+            lhs,
+            rhs,
+        );
+        let res = nullable_column(
+            alloc,
+            Column::Boolean(result_evaluate_or(table.num_rows(), alloc, lhs, rhs)),
+            presence,
+        );
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.first_round_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.first_round_evaluate(builder, alloc, table);
+        let lhs = lhs_column.as_boolean().expect("lhs is not boolean");
+        let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
+        let presence = result_evaluate_or_presence(
+            table.num_rows(),
+            alloc,
+            lhs_column.presence(),
+            rhs_column.presence(),
+            lhs,
+            rhs,
+        );
+        let res = nullable_column(
+            alloc,
+            Column::Boolean(result_evaluate_or(table.num_rows(), alloc, lhs, rhs)),
+            presence,
+        );
 
         log::log_memory_usage("End");
 
@@ -64,7 +112,43 @@ impl ProofExpr for OrExpr {
         let rhs_column: Column<'a, S> = self.rhs.prover_evaluate(builder, alloc, table);
         let lhs = lhs_column.as_boolean().expect("lhs is not boolean");
         let rhs = rhs_column.as_boolean().expect("rhs is not boolean");
-        let res = Column::Boolean(prover_evaluate_or(builder, alloc, lhs, rhs));
+        let values = Column::Boolean(prover_evaluate_or(builder, alloc, lhs, rhs));
+        let presence = result_evaluate_or_presence(
+            lhs.len(),
+            alloc,
+            lhs_column.presence(),
+            rhs_column.presence(),
+            lhs,
+            rhs,
+        );
+        if let Some(presence) = presence {
+            let n = lhs.len();
+            let lhs_presence = lhs_column
+                .presence()
+                .unwrap_or(alloc.alloc_slice_fill_copy(n, true));
+            let rhs_presence = rhs_column
+                .presence()
+                .unwrap_or(alloc.alloc_slice_fill_copy(n, true));
+            builder.produce_intermediate_mle(presence);
+
+            // subpolynomial: presence - lhs - rhs - lhs_presence * rhs_presence
+            //   + lhs_presence * rhs + lhs * rhs_presence
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(presence)]),
+                    (-S::one(), vec![Box::new(lhs)]),
+                    (-S::one(), vec![Box::new(rhs)]),
+                    (
+                        -S::one(),
+                        vec![Box::new(lhs_presence), Box::new(rhs_presence)],
+                    ),
+                    (S::one(), vec![Box::new(lhs_presence), Box::new(rhs)]),
+                    (S::one(), vec![Box::new(lhs), Box::new(rhs_presence)]),
+                ],
+            );
+        }
+        let res = nullable_column(alloc, values, presence);
 
         log::log_memory_usage("End");
 
@@ -77,16 +161,78 @@ impl ProofExpr for OrExpr {
         accessor: &IndexMap<ColumnRef, S>,
         chi_eval: S,
     ) -> Result<S, ProofError> {
-        let lhs = self.lhs.verifier_evaluate(builder, accessor, chi_eval)?;
-        let rhs = self.rhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        self.verifier_evaluate_nullable(builder, accessor, chi_eval)
+            .map(|(eval, _)| eval)
+    }
 
-        verifier_evaluate_or(builder, &lhs, &rhs)
+    fn is_nullable(&self) -> bool {
+        self.lhs.is_nullable() || self.rhs.is_nullable()
+    }
+
+    fn verifier_evaluate_nullable<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<(S, Option<S>), ProofError> {
+        let (lhs, lhs_presence) = self
+            .lhs
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
+        let (rhs, rhs_presence) = self
+            .rhs
+            .verifier_evaluate_nullable(builder, accessor, chi_eval)?;
+
+        let values = verifier_evaluate_or(builder, &lhs, &rhs)?;
+        if lhs_presence.is_none() && rhs_presence.is_none() {
+            return Ok((values, None));
+        }
+        let lhs_presence = lhs_presence.unwrap_or(chi_eval);
+        let rhs_presence = rhs_presence.unwrap_or(chi_eval);
+
+        // presence
+        let presence = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: presence - lhs - rhs - lhs_presence * rhs_presence
+        //   + lhs_presence * rhs + lhs * rhs_presence
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            presence - lhs - rhs - lhs_presence * rhs_presence
+                + lhs_presence * rhs
+                + lhs * rhs_presence,
+            2,
+        )?;
+
+        Ok((values, Some(presence)))
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn is_row_wise(&self) -> bool {
+        self.lhs.is_row_wise() && self.rhs.is_row_wise()
+    }
+}
+
+/// Evaluates whether `lhs OR rhs` is not NULL, which is when both sides are not NULL
+/// or when either side is TRUE. Returns `None` if neither side is nullable.
+fn result_evaluate_or_presence<'a>(
+    table_length: usize,
+    alloc: &'a Bump,
+    lhs_presence: Option<&[bool]>,
+    rhs_presence: Option<&[bool]>,
+    lhs: &[bool],
+    rhs: &[bool],
+) -> Option<&'a [bool]> {
+    (lhs_presence.is_some() || rhs_presence.is_some()).then(|| {
+        &*alloc.alloc_slice_fill_with(table_length, |i| {
+            (lhs_presence.is_none_or(|presence| presence[i])
+                && rhs_presence.is_none_or(|presence| presence[i]))
+                || lhs[i]
+                || rhs[i]
+        })
+    })
 }
 
 #[allow(
@@ -5,7 +5,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use bumpalo::Bump;
 use core::fmt::Debug;
@@ -25,6 +25,15 @@ pub trait ProofExpr: Debug + Send + Sync {
         table: &Table<'a, S>,
     ) -> Column<'a, S>;
 
+    /// Evaluate the expression, add components needed to prove it that have to be committed
+    /// before the post-result challenges are drawn, and return the resulting column of values
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S>;
+
     /// Evaluate the expression, add components needed to prove it, and return thet resulting column
     /// of values
     fn prover_evaluate<'a, S: Scalar>(
@@ -44,8 +53,31 @@ pub trait ProofExpr: Debug + Send + Sync {
         chi_eval: S,
     ) -> Result<S, ProofError>;
 
+    /// Whether the expression can evaluate to NULL, in which case the evaluated column is
+    /// [`Column::Nullable`] and its values hold the default value of the type in the NULL rows
+    fn is_nullable(&self) -> bool {
+        false
+    }
+
+    /// Same as [`ProofExpr::verifier_evaluate`], but also returns the evaluation of the presence
+    /// of the expression if it is nullable
+    fn verifier_evaluate_nullable<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<(S, Option<S>), ProofError> {
+        Ok((self.verifier_evaluate(builder, accessor, chi_eval)?, None))
+    }
+
     /// Insert in the [`IndexSet`] `columns` all the column
     /// references in the `BoolExpr` or forwards the call to some
     /// subsequent `bool_expr`
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>);
+
+    /// Whether the value of the expression in each row only depends on that row,
+    /// so that evaluating it over a subset of the rows gives the same values for those rows.
+    ///
+    /// Expressions that aggregate or look at other tables, such as subqueries, are not row-wise.
+    fn is_row_wise(&self) -> bool;
 }
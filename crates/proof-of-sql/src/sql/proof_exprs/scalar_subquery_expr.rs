@@ -0,0 +1,236 @@
+use super::ProofExpr;
+use crate::{
+    base::{
+        database::{filter_util::filter_column_by_index, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_plans::DynProofPlan,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use num_traits::Inv;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for the value of a subquery whose result has a single column
+/// and at most one row
+///
+/// The value is broadcast to every row of the table, like a literal.
+/// The result of the subquery is proven to have at most one row by its chi evaluation,
+/// which is either zero or the chi evaluation of a single row.
+/// If the result is empty, the value is zero in every row.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalarSubqueryExpr {
+    subquery: Box<DynProofPlan>,
+}
+
+impl ScalarSubqueryExpr {
+    /// Create a new scalar subquery expression, where `subquery` has a single result column
+    pub fn new(subquery: Box<DynProofPlan>) -> Self {
+        Self { subquery }
+    }
+
+    /// Whether scalar subqueries can be proven for values of the type,
+    /// which is the case for the types whose zero is the default value of a column
+    pub fn is_supported_type(column_type: ColumnType) -> bool {
+        matches!(
+            column_type,
+            ColumnType::Boolean
+                | ColumnType::Uint8
+                | ColumnType::TinyInt
+                | ColumnType::SmallInt
+                | ColumnType::Int
+                | ColumnType::BigInt
+                | ColumnType::Int128
+                | ColumnType::Decimal75(_, _)
+                | ColumnType::TimestampTZ(_, _)
+        )
+    }
+
+    /// Broadcasts the value of the first row of the result of the subquery to `num_rows` rows
+    ///
+    /// If the result has more rows, the verifier rejects the proof.
+    ///
+    /// # Panics
+    /// Panics if the result of the subquery is empty and has a type that is not supported.
+    fn broadcast<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        subquery_result: &Table<'a, S>,
+        num_rows: usize,
+    ) -> Column<'a, S> {
+        let column = subquery_result
+            .columns()
+            .next()
+            .expect("Scalar subqueries have a single result column");
+        if column.is_empty() {
+            zero_column(alloc, self.data_type(), num_rows)
+        } else {
+            filter_column_by_index(alloc, column, &vec![0; num_rows])
+        }
+    }
+}
+
+/// A column of `num_rows` zeros of the type
+///
+/// # Panics
+/// Panics if the type is not supported by [`ScalarSubqueryExpr`].
+fn zero_column<S: Scalar>(alloc: &Bump, column_type: ColumnType, num_rows: usize) -> Column<'_, S> {
+    match column_type {
+        ColumnType::Boolean => Column::Boolean(alloc.alloc_slice_fill_copy(num_rows, false)),
+        ColumnType::Uint8 => Column::Uint8(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::TinyInt => Column::TinyInt(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::SmallInt => Column::SmallInt(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::Int => Column::Int(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::BigInt => Column::BigInt(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::Int128 => Column::Int128(alloc.alloc_slice_fill_copy(num_rows, 0)),
+        ColumnType::Decimal75(precision, scale) => Column::Decimal75(
+            precision,
+            scale,
+            alloc.alloc_slice_fill_copy(num_rows, S::ZERO),
+        ),
+        ColumnType::TimestampTZ(time_unit, timezone) => Column::TimestampTZ(
+            time_unit,
+            timezone,
+            alloc.alloc_slice_fill_copy(num_rows, 0),
+        ),
+        _ => panic!("Scalar subqueries of type {column_type} are not supported"),
+    }
+}
+
+/// Evaluates a subquery outside of a proof, where the only table available is the table
+/// of the expression that contains the subquery
+///
+/// Every table that the subquery reads is taken to be `table`, so `table` has to hold
+/// the columns of the subquery as well, as is the case when the subquery reads the same table.
+///
+/// # Panics
+/// Panics if `table` does not hold a column that the subquery reads.
+pub(super) fn result_evaluate_subquery<'a, S: Scalar>(
+    subquery: &(impl ProofPlan + ProverEvaluate),
+    alloc: &'a Bump,
+    table: &Table<'a, S>,
+) -> Table<'a, S> {
+    let table_map: IndexMap<_, _> = subquery
+        .get_table_references()
+        .into_iter()
+        .map(|table_ref| (table_ref, table.clone()))
+        .collect();
+    let mut builder = FirstRoundBuilder::new(table.num_rows());
+    builder.set_table_map(table_map.clone());
+    subquery.first_round_evaluate(&mut builder, alloc, &table_map)
+}
+
+impl ProofExpr for ScalarSubqueryExpr {
+    fn data_type(&self) -> ColumnType {
+        self.subquery.get_column_result_fields()[0].data_type()
+    }
+
+    /// The subquery is evaluated on `table` as well, see `result_evaluate_subquery`.
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let subquery_result = result_evaluate_subquery(self.subquery.as_ref(), alloc, table);
+        self.broadcast(alloc, &subquery_result, table.num_rows())
+    }
+
+    #[tracing::instrument(
+        name = "ScalarSubqueryExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let table_map = builder.table_map().clone();
+        let subquery_result = self
+            .subquery
+            .first_round_evaluate(builder, alloc, &table_map);
+        let res = self.broadcast(alloc, &subquery_result, table.num_rows());
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(
+        name = "ScalarSubqueryExpr::prover_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let table_map = builder.table_map().clone();
+        let subquery_result = self
+            .subquery
+            .final_round_evaluate(builder, alloc, &table_map);
+        let res = self.broadcast(alloc, &subquery_result, table.num_rows());
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        _accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let column_evals = builder.column_evaluations().clone();
+        let table_chi_evals = builder.table_chi_evaluations().clone();
+        let subquery_eval =
+            self.subquery
+                .verifier_evaluate(builder, &column_evals, None, &table_chi_evals)?;
+        let &[value_eval] = subquery_eval.column_evals() else {
+            return Err(ProofError::VerificationError {
+                error: "Scalar subquery does not have a single result column",
+            });
+        };
+        // The evaluation of a single row is the value times the chi evaluation of a single row
+        let singleton_chi_eval = builder.singleton_chi_evaluation();
+        match subquery_eval.chi_eval() {
+            subquery_chi_eval if subquery_chi_eval == S::ZERO => Ok(S::ZERO),
+            subquery_chi_eval if subquery_chi_eval == singleton_chi_eval => {
+                let singleton_chi_inv =
+                    singleton_chi_eval
+                        .inv()
+                        .ok_or(ProofError::VerificationError {
+                            error: "Chi evaluation of a single row is zero",
+                        })?;
+                Ok(value_eval * singleton_chi_inv * chi_eval)
+            }
+            _ => Err(ProofError::VerificationError {
+                error: "Scalar subquery has more than one row",
+            }),
+        }
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        columns.extend(self.subquery.get_column_references());
+    }
+
+    fn is_row_wise(&self) -> bool {
+        false
+    }
+}
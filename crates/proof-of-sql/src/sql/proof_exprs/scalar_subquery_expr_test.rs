@@ -0,0 +1,197 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnRef, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+
+fn accessor_with_other_table(
+    data: OwnedTable<Curve25519Scalar>,
+    other: OwnedTable<Curve25519Scalar>,
+) -> (
+    TableRef,
+    TableRef,
+    OwnedTableTestAccessor<InnerProductProof>,
+) {
+    let t = TableRef::new("sxt", "t");
+    let o = TableRef::new("sxt", "other");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    accessor.add_table(o.clone(), other, 0);
+    (t, o, accessor)
+}
+
+/// `select max(y) from sxt.other`, planned as a projection of the groups of a group by without group by columns
+fn max_of_other(
+    o: &TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> DynProofPlan {
+    let subquery = TableRef::new("", "__subquery__");
+    projection(
+        vec![aliased_plan(
+            DynProofExpr::new_column(ColumnRef::new(
+                subquery.clone(),
+                "max_y".into(),
+                ColumnType::BigInt,
+            )),
+            "max_y",
+        )],
+        tab_with_input(
+            &subquery,
+            group_by(
+                vec![],
+                vec![max_expr(column(o, "y", accessor), "max_y")],
+                "__count__",
+                tab(o),
+                const_bool(true),
+            ),
+        ),
+    )
+}
+
+/// `select b from sxt.t where a > (select max(y) from sxt.other)`
+#[test]
+fn we_can_prove_a_comparison_with_a_scalar_subquery() {
+    let (t, o, accessor) = accessor_with_other_table(
+        owned_table([
+            bigint("a", [1, 5, 3, 7, 4, 6]),
+            varchar("b", ["x", "y", "z", "w", "v", "u"]),
+        ]),
+        owned_table([bigint("y", [2, 4, -3])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        not(lte(
+            column(&t, "a", &accessor),
+            scalar_subquery(max_of_other(&o, &accessor)),
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("b", ["y", "w", "u"])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select a, (select y from sxt.other where id = 2) as y from sxt.t`
+#[test]
+fn we_can_prove_a_scalar_subquery_of_a_filter_with_a_single_row() {
+    let (t, o, accessor) = accessor_with_other_table(
+        owned_table([bigint("a", [1, 2, 3])]),
+        owned_table([bigint("id", [1, 2, 3]), int("y", [10, 20, 30])]),
+    );
+    let subquery = filter(
+        cols_expr_plan(&o, &["y"], &accessor),
+        tab(&o),
+        equal(column(&o, "id", &accessor), const_bigint(2)),
+    );
+    let ast = projection(
+        vec![
+            col_expr_plan(&t, "a", &accessor),
+            aliased_plan(scalar_subquery(subquery), "y"),
+        ],
+        tab(&t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1, 2, 3]), int("y", [20, 20, 20])]);
+    assert_eq!(res, expected_res);
+}
+
+/// `select a from sxt.t where (select max(y) from sxt.other) = 0` with an empty `sxt.other`,
+/// where the empty result of the subquery is zero
+#[test]
+fn we_can_prove_a_scalar_subquery_with_an_empty_result() {
+    let (t, o, accessor) = accessor_with_other_table(
+        owned_table([bigint("a", [1, 2, 3])]),
+        owned_table([bigint("y", [0; 0])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            scalar_subquery(max_of_other(&o, &accessor)),
+            const_bigint(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1, 2, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_scalar_subquery_with_more_than_one_row() {
+    let (t, o, accessor) = accessor_with_other_table(
+        owned_table([bigint("a", [1, 2, 3])]),
+        owned_table([bigint("y", [1, 2])]),
+    );
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            column(&t, "a", &accessor),
+            scalar_subquery(projection(cols_expr_plan(&o, &["y"], &accessor), tab(&o))),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_cannot_create_a_scalar_subquery_expr_with_unsupported_types_or_columns() {
+    let (_, o, accessor) = accessor_with_other_table(
+        owned_table([bigint("a", [1])]),
+        owned_table([varchar("s", ["x"]), bigint("y", [1])]),
+    );
+    assert!(matches!(
+        DynProofExpr::try_new_scalar_subquery(projection(
+            cols_expr_plan(&o, &["s"], &accessor),
+            tab(&o)
+        )),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_scalar_subquery(projection(
+            cols_expr_plan(&o, &["s", "y"], &accessor),
+            tab(&o)
+        )),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_the_correct_output_of_a_scalar_subquery_of_the_same_table_using_result_evaluate()
+{
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1, 2, 3], &alloc),
+        borrowed_bigint("b", [10, 20, 30], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let subquery = filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(2)),
+    );
+    let scalar_subquery_expr = scalar_subquery(subquery);
+    let res = scalar_subquery_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::BigInt(&[20, 20, 20]);
+    assert_eq!(res, expected_res);
+}
@@ -1,8 +1,151 @@
-use crate::base::database::TableRef;
+use crate::{
+    base::{
+        database::{ColumnRef, Table, TableRef},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_plans::DynProofPlan,
+    },
+};
+use alloc::{borrow::Cow, boxed::Box};
+use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
 /// Expression for an SQL table
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+///
+/// The table is either a table of the database or the result of an input plan,
+/// such as the groups of a `GROUP BY` that a `HAVING` clause filters.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct TableExpr {
     pub table_ref: TableRef,
+    /// The plan whose result is the table, if it is not a table of the database.
+    ///
+    /// Expressions refer to the columns of its result as columns of `table_ref`.
+    pub input: Option<Box<DynProofPlan>>,
+}
+
+impl TableExpr {
+    /// Creates an expression for a table of the database
+    #[must_use]
+    pub fn new(table_ref: TableRef) -> Self {
+        Self {
+            table_ref,
+            input: None,
+        }
+    }
+
+    /// Creates an expression for the result of `input`, whose columns are referred to as columns of `table_ref`
+    #[must_use]
+    pub fn new_with_input(table_ref: TableRef, input: DynProofPlan) -> Self {
+        Self {
+            table_ref,
+            input: Some(Box::new(input)),
+        }
+    }
+
+    /// Evaluates the table in the first round of the proof
+    ///
+    /// # Panics
+    /// Panics if the table is a table of the database that is not in `table_map`.
+    pub fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        match &self.input {
+            Some(input) => input.first_round_evaluate(builder, alloc, table_map),
+            None => table_map
+                .get(&self.table_ref)
+                .expect("Table not found")
+                .clone(),
+        }
+    }
+
+    /// Evaluates the table in the final round of the proof
+    ///
+    /// # Panics
+    /// Panics if the table is a table of the database that is not in `table_map`.
+    pub fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        match &self.input {
+            Some(input) => input.final_round_evaluate(builder, alloc, table_map),
+            None => table_map
+                .get(&self.table_ref)
+                .expect("Table not found")
+                .clone(),
+        }
+    }
+
+    /// Verifies the input plan, if there is one, and returns the evaluations
+    /// that expressions over the table are verified against, together with the chi evaluation of the table.
+    ///
+    /// # Panics
+    /// Panics if the table is a table of the database that is not in `chi_eval_map`.
+    pub fn verifier_evaluate<'b, S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &'b IndexMap<ColumnRef, S>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<(Cow<'b, IndexMap<ColumnRef, S>>, S), ProofError> {
+        if let Some(input) = &self.input {
+            let input_eval = input.verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+            let input_accessor = input
+                .get_column_result_fields()
+                .into_iter()
+                .map(|field| field.column_ref(self.table_ref.clone()))
+                .zip(input_eval.column_evals().iter().copied())
+                .collect();
+            Ok((Cow::Owned(input_accessor), input_eval.chi_eval()))
+        } else {
+            let chi_eval = *chi_eval_map
+                .get(&self.table_ref)
+                .expect("Chi eval not found");
+            Ok((Cow::Borrowed(accessor), chi_eval))
+        }
+    }
+
+    /// The columns of the database that a plan over the table needs,
+    /// given the columns `expr_columns` that its expressions reference.
+    ///
+    /// Expressions over the result of an input plan only reference columns of the database
+    /// through subqueries, whose columns belong to other tables.
+    #[must_use]
+    pub fn get_column_references(&self, expr_columns: IndexSet<ColumnRef>) -> IndexSet<ColumnRef> {
+        match &self.input {
+            Some(input) => {
+                let mut columns = input.get_column_references();
+                columns.extend(
+                    expr_columns
+                        .into_iter()
+                        .filter(|column| column.table_ref() != self.table_ref),
+                );
+                columns
+            }
+            None => expr_columns,
+        }
+    }
+
+    /// The tables of the database that a plan over the table needs,
+    /// given the columns of the database that the plan references.
+    ///
+    /// These include the tables of subqueries of its expressions.
+    #[must_use]
+    pub fn get_table_references(&self, columns: &IndexSet<ColumnRef>) -> IndexSet<TableRef> {
+        let mut tables = match &self.input {
+            Some(input) => input.get_table_references(),
+            None => IndexSet::from_iter([self.table_ref.clone()]),
+        };
+        tables.extend(columns.iter().map(ColumnRef::table_ref));
+        tables
+    }
 }
@@ -1,10 +1,18 @@
-use super::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr};
-use crate::base::{
-    database::{ColumnRef, LiteralValue, SchemaAccessor, TableRef},
-    math::{decimal::Precision, i256::I256},
-    scalar::Scalar,
+use super::{AliasedDynProofExpr, ColumnExpr, DateTimeFunction, DynProofExpr, TableExpr};
+use crate::{
+    base::{
+        database::{
+            ColumnComponent, ColumnRef, LikePattern, LiteralValue, SchemaAccessor, TableRef,
+        },
+        math::{decimal::Precision, i256::I256},
+        scalar::Scalar,
+    },
+    sql::proof_plans::DynProofPlan,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::AggregationOperator,
+    posql_time::{PoSQLDateTimeField, PoSQLInterval},
 };
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
 use sqlparser::ast::Ident;
 
 pub fn col_ref(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> ColumnRef {
@@ -22,6 +30,37 @@ pub fn column(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> Dyn
     DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(tab.clone(), name, type_col)))
 }
 
+/// # Panics
+/// Panics if:
+/// - `accessor.lookup_column()` returns `None`, indicating the column is not found.
+pub fn presence(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> DynProofExpr {
+    DynProofExpr::new_column(col_ref(tab, name, accessor).presence())
+}
+
+/// The expression of a nullable column, i.e. its values where its presence is true
+///
+/// # Panics
+/// Panics if:
+/// - `accessor.lookup_column()` returns `None`, indicating the column is not found.
+pub fn nullable_column(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> DynProofExpr {
+    nullable_expr(column(tab, name, accessor), presence(tab, name, accessor))
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_nullable()` returns an error.
+pub fn nullable_expr(value: DynProofExpr, presence: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_nullable(value, presence).unwrap()
+}
+
+pub fn is_null(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::new_is_null(expr, false)
+}
+
+pub fn is_not_null(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::new_is_null(expr, true)
+}
+
 /// # Panics
 /// Panics if:
 /// - `DynProofExpr::try_new_equals()` returns an error.
@@ -43,6 +82,74 @@ pub fn gte(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
     not(DynProofExpr::try_new_inequality(left, right, true).unwrap())
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in_list()` returns an error.
+pub fn in_list(expr: DynProofExpr, list: Vec<DynProofExpr>) -> DynProofExpr {
+    DynProofExpr::try_new_in_list(expr, list).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in_subquery()` returns an error.
+pub fn in_subquery(expr: DynProofExpr, subquery: DynProofPlan) -> DynProofExpr {
+    DynProofExpr::try_new_in_subquery(expr, subquery).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_scalar_subquery()` returns an error.
+pub fn scalar_subquery(subquery: DynProofPlan) -> DynProofExpr {
+    DynProofExpr::try_new_scalar_subquery(subquery).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_between()` returns an error.
+pub fn between(expr: DynProofExpr, low: DynProofExpr, high: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_between(expr, low, high).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_case()` returns an error.
+pub fn case_when(
+    condition: DynProofExpr,
+    then_expr: DynProofExpr,
+    else_expr: DynProofExpr,
+) -> DynProofExpr {
+    DynProofExpr::try_new_case(condition, then_expr, else_expr).unwrap()
+}
+
+/// Builds `name LIKE pattern` from the character components of `name`.
+///
+/// # Panics
+/// Panics if:
+/// - `name` is not found.
+/// - `DynProofExpr::try_new_like()` returns an error.
+pub fn like(
+    tab: &TableRef,
+    name: &str,
+    pattern: &str,
+    accessor: &impl SchemaAccessor,
+) -> DynProofExpr {
+    let column_ref = col_ref(tab, name, accessor);
+    let pattern = LikePattern::new(pattern);
+    let length = DynProofExpr::new_column(column_ref.with_component(ColumnComponent::Length));
+    let min_length = const_bigint(pattern.min_length().try_into().unwrap());
+    let length_condition = if pattern.is_exact() {
+        equal(length, min_length)
+    } else {
+        gte(length, min_length)
+    };
+    let characters = pattern
+        .character_constraints()
+        .into_iter()
+        .map(|(component, character)| (column_ref.with_component(component), character))
+        .collect();
+    DynProofExpr::try_new_like(length_condition, characters).unwrap()
+}
+
 /// # Panics
 /// Panics if:
 /// - `DynProofExpr::try_new_not()` returns an error.
@@ -85,6 +192,45 @@ pub fn multiply(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
     DynProofExpr::try_new_multiply(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_divide()` returns an error.
+pub fn divide(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_divide(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_modulo()` returns an error.
+pub fn modulo(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_modulo(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_date_time()` returns an error.
+pub fn extract(field: PoSQLDateTimeField, expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_date_time(expr, DateTimeFunction::Extract(field)).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_date_time()` returns an error.
+pub fn date_trunc(field: PoSQLDateTimeField, expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_date_time(expr, DateTimeFunction::Truncate(field)).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_date_time()` returns an error.
+pub fn add_interval(expr: DynProofExpr, count: i64, field: PoSQLDateTimeField) -> DynProofExpr {
+    DynProofExpr::try_new_date_time(
+        expr,
+        DateTimeFunction::AddInterval(PoSQLInterval::new(count, field)),
+    )
+    .unwrap()
+}
+
 pub fn const_bool(val: bool) -> DynProofExpr {
     DynProofExpr::new_literal(LiteralValue::Boolean(val))
 }
@@ -126,9 +272,11 @@ pub fn const_decimal75<T: Into<I256>>(precision: u8, scale: i8, val: T) -> DynPr
 }
 
 pub fn tab(tab: &TableRef) -> TableExpr {
-    TableExpr {
-        table_ref: tab.clone(),
-    }
+    TableExpr::new(tab.clone())
+}
+
+pub fn tab_with_input(tab: &TableRef, input: DynProofPlan) -> TableExpr {
+    TableExpr::new_with_input(tab.clone(), input)
 }
 
 /// # Panics
@@ -194,27 +342,66 @@ pub fn cols_expr_plan(
         .collect()
 }
 
-pub fn col_expr(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> ColumnExpr {
-    ColumnExpr::new(col_ref(tab, name, accessor))
+/// # Panics
+/// Panics if:
+/// - `alias.parse()` fails to parse the provided alias string.
+pub fn sum_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Sum, expr),
+        alias: alias.into(),
+    }
 }
 
-pub fn cols_expr(
-    tab: &TableRef,
-    names: &[&str],
-    accessor: &impl SchemaAccessor,
-) -> Vec<ColumnExpr> {
-    names
-        .iter()
-        .map(|name| col_expr(tab, name, accessor))
-        .collect()
+/// # Panics
+/// Panics if:
+/// - `alias.parse()` fails to parse the provided alias string.
+pub fn filtered_sum_expr(
+    expr: DynProofExpr,
+    filter: DynProofExpr,
+    alias: &str,
+) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::try_new_filtered_aggregate(AggregationOperator::Sum, expr, filter)
+            .unwrap(),
+        alias: alias.into(),
+    }
 }
 
+/// A count of the rows where `filter` holds, or of all rows, which counts ones like `COUNT(1)`
+///
 /// # Panics
 /// Panics if:
 /// - `alias.parse()` fails to parse the provided alias string.
-pub fn sum_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+pub fn count_expr(filter: Option<DynProofExpr>, alias: &str) -> AliasedDynProofExpr {
+    let one = DynProofExpr::new_literal(LiteralValue::BigInt(1));
     AliasedDynProofExpr {
-        expr: DynProofExpr::new_aggregate(AggregationOperator::Sum, expr),
+        expr: match filter {
+            Some(filter) => {
+                DynProofExpr::try_new_filtered_aggregate(AggregationOperator::Count, one, filter)
+                    .unwrap()
+            }
+            None => DynProofExpr::new_aggregate(AggregationOperator::Count, one),
+        },
+        alias: alias.into(),
+    }
+}
+
+/// # Panics
+/// Panics if:
+/// - `alias.parse()` fails to parse the provided alias string.
+pub fn max_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Max, expr),
+        alias: alias.into(),
+    }
+}
+
+/// # Panics
+/// Panics if:
+/// - `alias.parse()` fails to parse the provided alias string.
+pub fn min_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Min, expr),
         alias: alias.into(),
     }
 }
@@ -9,18 +9,21 @@ pub(crate) use membership_check::{
 };
 #[cfg(test)]
 mod membership_check_test;
-#[allow(unused_imports, dead_code)]
-use permutation_check::{final_round_evaluate_permutation_check, verify_permutation_check};
+pub(crate) use permutation_check::{
+    final_round_evaluate_permutation_check, verify_permutation_check,
+};
 #[cfg(test)]
 mod permutation_check_test;
-use shift::{final_round_evaluate_shift, first_round_evaluate_shift, verify_shift};
+pub(crate) use shift::{final_round_evaluate_shift, first_round_evaluate_shift, verify_shift};
 #[cfg(test)]
 mod shift_test;
 mod sign_expr;
 pub(crate) use sign_expr::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign};
-#[cfg(feature = "blitzar")]
-#[allow(unused_imports, dead_code)] // remove this when we use it
 mod range_check;
+pub(crate) use range_check::{
+    final_round_evaluate_range_check, first_round_evaluate_range_check,
+    verifier_evaluate_range_check,
+};
 #[cfg(all(test, feature = "blitzar"))]
 mod range_check_test;
 #[cfg(all(test, feature = "blitzar"))]
@@ -21,7 +21,11 @@
 //! * Batch Inversion: Inversions of large vectors are computationally expensive
 //! * Parallelization: Single-threaded execution of these operations is a performance bottleneck
 use crate::{
-    base::{proof::ProofSizeMismatch, scalar::Scalar, slice_ops},
+    base::{
+        proof::{ProofError, ProofSizeMismatch},
+        scalar::Scalar,
+        slice_ops,
+    },
     sql::proof::{
         FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder,
     },
@@ -383,14 +387,14 @@ fn prove_row_zero_sum<'a, S: Scalar + 'a>(
 
 /// Verify that the prover claim is correct.
 ///
-/// # Panics
+/// # Errors
 ///
 /// if a column contains values outside of the selected range.
 pub(crate) fn verifier_evaluate_range_check<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     input_column_eval: S,
     chi_n_eval: S,
-) -> Result<(), ProofSizeMismatch> {
+) -> Result<(), ProofError> {
     // Retrieve the post-result challenge α
     let alpha = builder.try_consume_post_result_challenge()?;
     let chi_ones_256_eval = builder.try_consume_chi_evaluation()?;
@@ -437,10 +441,11 @@ pub(crate) fn verifier_evaluate_range_check<S: Scalar>(
 
     // Ensure the sum of the scalars (interpreted in base 256) matches
     // the claimed input_column_eval. If not, the column is out of range.
-    assert_eq!(
-        sum, input_column_eval,
-        "Range check failed, column contains values outside of the selected range"
-    );
+    if sum != input_column_eval {
+        return Err(ProofError::VerificationError {
+            error: "Range check failed, column contains values outside of the selected range",
+        });
+    }
 
     // Retrieve word_vals_eval (evaluation for w-values)
     // from the builder’s MLE evaluations
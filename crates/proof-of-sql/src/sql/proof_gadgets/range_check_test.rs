@@ -185,7 +185,7 @@ mod tests {
             math::decimal::Precision,
             scalar::Curve25519Scalar,
         },
-        sql::proof::VerifiableQueryResult,
+        sql::proof::{QueryError, VerifiableQueryResult},
     };
     use blitzar::proof::InnerProductProof;
     use num_bigint::BigUint;
@@ -261,9 +261,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(
-        expected = "Range check failed, column contains values outside of the selected range"
-    )]
     fn we_cannot_successfully_verify_invalid_range() {
         let data = owned_table([scalar("a", -2..254)]);
         let t = TableRef::new("sxt", "t");
@@ -273,7 +270,15 @@ mod tests {
             column: ColumnRef::new(t.clone(), "a".into(), ColumnType::Scalar),
         };
         let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
-        let _ = verifiable_res.verify(&ast, &accessor, &());
+        assert!(matches!(
+            verifiable_res.verify(&ast, &accessor, &()),
+            Err(QueryError::ProofError {
+                source: ProofError::VerificationError {
+                    error:
+                        "Range check failed, column contains values outside of the selected range"
+                }
+            })
+        ));
     }
 
     #[test]
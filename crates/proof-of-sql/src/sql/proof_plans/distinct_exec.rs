@@ -0,0 +1,256 @@
+use super::{
+    sort_exec::{compute_sort_key, sort_indexes, sort_key_bit_widths, sort_key_weights},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_column_by_index, order_by_util::compare_indexes_by_columns, Column,
+            ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_monotonic,
+            first_round_evaluate_membership_check, first_round_evaluate_monotonic,
+            prover_evaluate_sign, verifier_evaluate_sign, verify_membership_check,
+            verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT DISTINCT * FROM (<ProofPlan>)
+/// ```
+///
+/// The result consists of the unique rows of the input, ordered ascendingly by all of its columns.
+/// It is proven to be the set of rows of the input by
+/// 1. a membership check of the rows of the input in the result, whose multiplicities
+///    are proven to be positive so that every row of the result is a row of the input, and
+/// 2. a check that the sort key of the result, see [`super::SortExec`], is strictly increasing,
+///    so that the rows of the result are unique.
+///
+/// This is the grouping argument of [`super::GroupByExec`] without any aggregates.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct DistinctExec {
+    pub(super) input: Box<DynProofPlan>,
+}
+
+impl DistinctExec {
+    /// Creates a new distinct execution plan.
+    pub fn new(input: Box<DynProofPlan>) -> Self {
+        Self { input }
+    }
+
+    /// Whether the rows of the result of `input` can be proven to be unique.
+    ///
+    /// This is the case if all of its columns are of integer, boolean or timestamp types
+    /// and the sort key of a row is small enough.
+    #[must_use]
+    pub fn is_provable(input: &DynProofPlan) -> bool {
+        let fields = input.get_column_result_fields();
+        sort_key_bit_widths(&fields, &order_by_all_columns(fields.len())).is_some()
+    }
+
+    /// The columns that the result is ordered by, which are all of them
+    fn order_by(&self) -> Vec<(usize, bool)> {
+        order_by_all_columns(self.input.get_column_result_fields().len())
+    }
+}
+
+/// Orders ascendingly by each of `num_columns` columns
+fn order_by_all_columns(num_columns: usize) -> Vec<(usize, bool)> {
+    (0..num_columns).map(|index| (index, true)).collect()
+}
+
+/// The indexes of the first occurrences of the unique rows of `columns`,
+/// which are the components of the columns so that NULLs are equal to each other but not to any value,
+/// ordered ascendingly by the columns.
+fn distinct_indexes<S: Scalar>(columns: &[Column<S>]) -> Vec<usize> {
+    let mut indexes = sort_indexes(columns, &order_by_all_columns(columns.len()));
+    indexes.dedup_by(|&mut i, &mut j| compare_indexes_by_columns(columns, i, j) == Ordering::Equal);
+    indexes
+}
+
+impl ProofPlan for DistinctExec
+where
+    DistinctExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let input_table_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let input_chi_eval = input_table_eval.chi_eval();
+        let columns_evals = input_table_eval.column_evals();
+        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        // 2. distinct columns
+        let distinct_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(columns_evals.len())?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 3. Every row of the input is a row of the result
+        let multiplicity_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            output_chi_eval,
+            input_chi_eval,
+            &distinct_columns_evals,
+            columns_evals,
+        )?;
+        // 4. Every row of the result is a row of the input, i.e. `multiplicity - 1` is not negative
+        if verifier_evaluate_sign(
+            builder,
+            multiplicity_eval - output_chi_eval,
+            output_chi_eval,
+        )? != S::ZERO
+        {
+            Err(ProofError::VerificationError {
+                error: "Result of distinct has a row that is not in its input.",
+            })?;
+        }
+        // 5. The rows of the result are unique
+        let weights = sort_key_weights::<S>(&self.get_column_result_fields(), &self.order_by())
+            .ok_or(ProofError::UnsupportedQueryPlan {
+                error: "DistinctExec only supports integer, boolean and timestamp columns",
+            })?;
+        let sort_key_eval = weights
+            .iter()
+            .zip(&distinct_columns_evals)
+            .map(|(&weight, &eval)| weight * eval)
+            .sum();
+        verify_monotonic::<S, true, true>(builder, alpha, beta, sort_key_eval, output_chi_eval)?;
+        Ok(TableEvaluation::new(
+            distinct_columns_evals,
+            output_chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for DistinctExec {
+    #[tracing::instrument(name = "DistinctExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let columns = input.column_components();
+        // 2. distinct columns
+        let indexes = distinct_indexes(&columns);
+        let distinct_columns = columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect::<Vec<_>>();
+        builder.produce_chi_evaluation_length(indexes.len());
+        first_round_evaluate_membership_check(builder, alloc, &distinct_columns, &columns);
+        builder.request_post_result_challenges(2);
+        first_round_evaluate_monotonic(builder, indexes.len());
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            distinct_columns,
+            TableOptions::new(Some(indexes.len())),
+            alloc,
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "DistinctExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let columns = input.column_components();
+        // 2. distinct columns
+        let indexes = distinct_indexes(&columns);
+        let num_distinct_rows = indexes.len();
+        let distinct_columns = columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect::<Vec<_>>();
+        distinct_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. Every row of the input is a row of the result
+        let chi_n = alloc.alloc_slice_fill_copy(input.num_rows(), true);
+        let chi_m = alloc.alloc_slice_fill_copy(num_distinct_rows, true);
+        let multiplicities = final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m,
+            chi_n,
+            &distinct_columns,
+            &columns,
+        );
+        // 4. Every row of the result is a row of the input, i.e. `multiplicity - 1` is not negative
+        let multiplicities_minus_one =
+            alloc.alloc_slice_fill_with(num_distinct_rows, |i| S::from(multiplicities[i]) - S::ONE);
+        prover_evaluate_sign(builder, alloc, multiplicities_minus_one);
+        // 5. The rows of the result are unique
+        let order_by = self.order_by();
+        let weights = sort_key_weights::<S>(&self.get_column_result_fields(), &order_by)
+            .expect("DistinctExec only supports integer, boolean and timestamp columns");
+        let sort_key = compute_sort_key(alloc, &distinct_columns, &order_by, &weights);
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, sort_key);
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            distinct_columns,
+            TableOptions::new(Some(num_distinct_rows)),
+            alloc,
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
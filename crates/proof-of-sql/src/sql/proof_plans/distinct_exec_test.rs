@@ -0,0 +1,140 @@
+use super::{test_utility::*, DistinctExec};
+use crate::{
+    base::database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef},
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_distinct_exec() {
+    let data = owned_table([
+        bigint("a", [3_i64, -1, 3, 1, -1, 3]),
+        bigint("b", [7_i64, 8, 9, 10, 11, 12]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = distinct_exec(projection(cols_expr_plan(&t, &["a"], &accessor), tab(&t)));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [-1_i64, 1, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_distinct_exec_with_multiple_columns_of_a_filter() {
+    let data = owned_table([
+        boolean("a", [true, false, true, false, true, true, false]),
+        int("b", [2, 7, -3, 7, 2, 2, 7]),
+        bigint("c", [1_i64, 2, 3, 4, 5, 6, 7]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let where_clause: DynProofExpr = gte(column(&t, "c", &accessor), const_bigint(2));
+    let ast = distinct_exec(filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        where_clause,
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([boolean("a", [false, true, true]), int("b", [7, -3, 2])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_union_of_tables_as_a_distinct_exec_of_a_union_exec() {
+    let data0 = owned_table([bigint("a0", [1_i64, 2, 2, 5])]);
+    let t0 = TableRef::new("sxt", "t0");
+    let data1 = owned_table([bigint("a1", [5_i64, 3, 1])]);
+    let t1 = TableRef::new("sxt", "t1");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t0.clone(), data0, 0);
+    accessor.add_table(t1.clone(), data1, 0);
+    let ast = distinct_exec(union_exec(
+        vec![
+            projection(cols_expr_plan(&t0, &["a0"], &accessor), tab(&t0)),
+            projection(cols_expr_plan(&t1, &["a1"], &accessor), tab(&t1)),
+        ],
+        vec![column_field("a", ColumnType::BigInt)],
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t0);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 3, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_distinct_exec() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let where_clause: DynProofExpr = equal(column(&t, "a", &accessor), const_bigint(999));
+    let ast = distinct_exec(filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        where_clause,
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_count_of_distinct_values_as_a_group_by_over_a_distinct_exec() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 1, 2, 1, 3]),
+        bigint("user_id", [10_i64, 20, 10, 30, 40, 30]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![],
+        "unique_users",
+        tab_with_input(
+            &t,
+            distinct_exec(projection(
+                cols_expr_plan(&t, &["a", "user_id"], &accessor),
+                tab(&t),
+            )),
+        ),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 2, 3]),
+        bigint("unique_users", [2_i64, 2, 1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_prove_a_distinct_exec_of_unsupported_or_too_wide_columns() {
+    let data = owned_table([
+        varchar("a", ["1", "2"]),
+        int128("b", [1_i128, 2]),
+        int128("c", [1_i128, 2]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let input = |columns: &[&str]| projection(cols_expr_plan(&t, columns, &accessor), tab(&t));
+    assert!(DistinctExec::is_provable(&input(&["b"])));
+    assert!(!DistinctExec::is_provable(&input(&["a"])));
+    assert!(!DistinctExec::is_provable(&input(&["b", "c"])));
+    assert!(!DistinctExec::is_provable(&input(&[])));
+}
@@ -1,6 +1,6 @@
 use super::{
-    EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec, TableExec,
-    UnionExec,
+    DistinctExec, EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortExec,
+    SortMergeJoinExec, SortMergeOuterJoinExec, TableExec, UnionExec, WindowExec,
 };
 use crate::{
     base::{
@@ -9,8 +9,11 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{
-        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_exprs::ProofExpr,
     },
 };
 use alloc::vec::Vec;
@@ -33,12 +36,13 @@ pub enum DynProofPlan {
     /// Provable expressions for queries of the form
     /// ```ignore
     ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
-    ///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
+    ///         <op1>(<aggregate_expr1>.0) as <aggregate_expr1>.1, ..., <opN>(<aggregate_exprN>.0) as <aggregate_exprN>.1,
     ///         COUNT(*) as count_alias
     ///     FROM <table>
     ///     WHERE <where_clause>
     ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
     /// ```
+    /// where each of `<op1>`, ..., `<opN>` is one of `SUM`, `MAX` or `MIN`.
     GroupBy(GroupByExec),
     /// Provable expressions for queries of the form, where the result is sent in a dense form
     /// ```ignore
@@ -52,6 +56,11 @@ pub enum DynProofPlan {
     Slice(SliceExec),
     /// `ProofPlan` for queries of the form
     /// ```ignore
+    ///     <ProofPlan> ORDER BY <order_by_expr1> [ASC | DESC], ..., <order_by_exprN> [ASC | DESC]
+    /// ```
+    Sort(SortExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
     ///     <ProofPlan>
     ///     UNION ALL
     ///     <ProofPlan>
@@ -62,8 +71,61 @@ pub enum DynProofPlan {
     Union(UnionExec),
     /// `ProofPlan` for queries of the form
     /// ```ignore
+    ///     SELECT DISTINCT * FROM (<ProofPlan>)
+    /// ```
+    Distinct(DistinctExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT *,
+    ///         <function1> OVER (PARTITION BY <partition_by> ORDER BY <order_by>) AS <alias1>, ...
+    ///     FROM (<ProofPlan>)
+    /// ```
+    /// where each of `<function1>`, ... is one of `ROW_NUMBER()`, `RANK()`, `LAG(<column>)`,
+    /// `LEAD(<column>)` or `SUM(<column>)`.
+    Window(WindowExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
     ///     <ProofPlan> INNER JOIN <ProofPlan>
     ///     ON col1 = col2
     /// ```
     SortMergeJoin(SortMergeJoinExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> LEFT OUTER JOIN <ProofPlan>
+    ///     ON col1 = col2
+    /// ```
+    SortMergeOuterJoin(SortMergeOuterJoinExec),
+}
+
+impl DynProofPlan {
+    /// Whether each row of the result only depends on the corresponding row of a table of the database,
+    /// as is the case for projections and filters of a table whose expressions are all row-wise,
+    /// see [`ProofExpr::is_row_wise`](crate::sql::proof_exprs::ProofExpr::is_row_wise).
+    ///
+    /// The result of such a plan over the whole table is the concatenation
+    /// of its results over consecutive chunks of rows of the table,
+    /// which is what [`ChunkedVerifiableQueryResult`](crate::sql::proof::ChunkedVerifiableQueryResult) relies on.
+    /// This is a conservative check of the shape of the plan: any other plan is rejected,
+    /// even if its result happens to be the concatenation of its results over the chunks.
+    pub(crate) fn is_row_wise(&self) -> bool {
+        match self {
+            DynProofPlan::Table(_) => true,
+            DynProofPlan::Projection(projection) => {
+                projection.table.input.is_none()
+                    && projection
+                        .aliased_results
+                        .iter()
+                        .all(|aliased_expr| aliased_expr.expr.is_row_wise())
+            }
+            DynProofPlan::Filter(filter) => {
+                filter.table.input.is_none()
+                    && filter.where_clause.is_row_wise()
+                    && filter
+                        .aliased_results
+                        .iter()
+                        .all(|aliased_expr| aliased_expr.expr.is_row_wise())
+            }
+            _ => false,
+        }
+    }
 }
@@ -21,7 +21,7 @@ use crate::{
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
-use core::marker::PhantomData;
+use core::{iter, marker::PhantomData};
 use num_traits::{One, Zero};
 use serde::{Deserialize, Serialize};
 
@@ -68,28 +68,31 @@ where
         _result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
     ) -> Result<TableEvaluation<S>, ProofError> {
-        let input_chi_eval = *chi_eval_map
-            .get(&self.table.table_ref)
-            .expect("Chi eval not found");
+        let (accessor, input_chi_eval) =
+            self.table
+                .verifier_evaluate(builder, accessor, chi_eval_map)?;
+        let accessor = &*accessor;
         // 1. selection
         let selection_eval =
             self.where_clause
                 .verifier_evaluate(builder, accessor, input_chi_eval)?;
         // 2. columns
-        let columns_evals = Vec::from_iter(
-            self.aliased_results
-                .iter()
-                .map(|aliased_expr| {
-                    aliased_expr
-                        .expr
-                        .verifier_evaluate(builder, accessor, input_chi_eval)
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-        );
+        let columns_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate_nullable(builder, accessor, input_chi_eval)
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flat_map(|(eval, presence_eval)| iter::once(eval).chain(presence_eval))
+            .collect::<Vec<_>>();
         // 3. filtered_columns
         let filtered_columns_evals =
-            builder.try_consume_final_round_mle_evaluations(self.aliased_results.len())?;
-        assert!(filtered_columns_evals.len() == self.aliased_results.len());
+            builder.try_consume_final_round_mle_evaluations(columns_evals.len())?;
+        assert!(filtered_columns_evals.len() == columns_evals.len());
 
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
@@ -115,8 +118,11 @@ where
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.aliased_results
             .iter()
-            .map(|aliased_expr| {
-                ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+            .flat_map(|aliased_expr| {
+                let field =
+                    ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type());
+                let presence = aliased_expr.expr.is_nullable().then(|| field.presence());
+                iter::once(field).chain(presence)
             })
             .collect()
     }
@@ -130,11 +136,12 @@ where
 
         self.where_clause.get_column_references(&mut columns);
 
-        columns
+        self.table.get_column_references(columns)
     }
 
     fn get_table_references(&self) -> IndexSet<TableRef> {
-        IndexSet::from_iter([self.table.table_ref.clone()])
+        self.table
+            .get_table_references(&self.get_column_references())
     }
 }
 
@@ -151,11 +158,11 @@ impl ProverEvaluate for FilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.first_round_evaluate(builder, alloc, table_map);
         // 1. selection
-        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
+        let selection_column: Column<'a, S> = self
+            .where_clause
+            .first_round_evaluate(builder, alloc, table);
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
@@ -165,7 +172,11 @@ impl ProverEvaluate for FilterExec {
         let columns: Vec<_> = self
             .aliased_results
             .iter()
-            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .first_round_evaluate(builder, alloc, table)
+            })
             .collect();
 
         // Compute filtered_columns and indexes
@@ -196,9 +207,7 @@ impl ProverEvaluate for FilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.final_round_evaluate(builder, alloc, table_map);
         // 1. selection
         let selection_column: Column<'a, S> =
             self.where_clause.prover_evaluate(builder, alloc, table);
@@ -215,10 +224,19 @@ impl ProverEvaluate for FilterExec {
             .collect();
         // Compute filtered_columns
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
+        // The presence of nullable columns is filtered and committed to like a column of its own
+        let column_components: Vec<_> = columns.iter().flat_map(Column::components).collect();
+        let filtered_column_components: Vec<_> = filtered_columns
+            .iter()
+            .flat_map(Column::components)
+            .collect();
         // 3. Produce MLEs
-        filtered_columns.iter().copied().for_each(|column| {
-            builder.produce_intermediate_mle(column);
-        });
+        filtered_column_components
+            .iter()
+            .copied()
+            .for_each(|column| {
+                builder.produce_intermediate_mle(column);
+            });
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
@@ -228,9 +246,9 @@ impl ProverEvaluate for FilterExec {
             alloc,
             alpha,
             beta,
-            &columns,
+            &column_components,
             selection,
-            &filtered_columns,
+            &filtered_column_components,
             table.num_rows(),
             result_len,
         );
@@ -48,9 +48,7 @@ fn we_can_correctly_fetch_the_query_result_schema() {
                 "b",
             ),
         ],
-        TableExpr {
-            table_ref: table_ref.clone(),
-        },
+        TableExpr::new(table_ref.clone()),
         DynProofExpr::try_new_equals(
             DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
                 table_ref.clone(),
@@ -99,9 +97,7 @@ fn we_can_correctly_fetch_all_the_referenced_columns() {
                 "f",
             ),
         ],
-        TableExpr {
-            table_ref: table_ref.clone(),
-        },
+        TableExpr::new(table_ref.clone()),
         not(and(
             or(
                 DynProofExpr::try_new_equals(
@@ -449,3 +445,50 @@ fn we_can_prove_a_filter() {
     ]);
     assert_eq!(res, expected);
 }
+
+/// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a having sum(c) >= 200 and max(b) <= 3`
+#[test]
+fn we_can_prove_a_filter_on_the_groups_of_a_group_by() {
+    let data = owned_table([
+        bigint("a", [-1, 2, 2, -1, 3, 3]),
+        bigint("b", [1, 2, 3, 4, 1, 1]),
+        bigint("c", [101, 102, 103, 104, 50, 60]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let groups = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![
+            sum_expr(column(&t, "c", &accessor), "sum_c"),
+            max_expr(column(&t, "b", &accessor), "__having_0__"),
+        ],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let group_column = |name: &str| {
+        DynProofExpr::new_column(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt))
+    };
+    let expr = filter(
+        vec![
+            aliased_plan(group_column("a"), "a"),
+            aliased_plan(group_column("sum_c"), "sum_c"),
+            aliased_plan(group_column("__count__"), "__count__"),
+        ],
+        tab_with_input(&t, groups),
+        and(
+            gte(group_column("sum_c"), const_bigint(200)),
+            lte(group_column("__having_0__"), const_bigint(3)),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [2]),
+        bigint("sum_c", [205]),
+        bigint("__count__", [2]),
+    ]);
+    assert_eq!(res, expected);
+}
@@ -47,7 +47,9 @@ impl ProverEvaluate for DishonestFilterExec {
             .get(&self.table.table_ref)
             .expect("Table not found");
         // 1. selection
-        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
+        let selection_column: Column<'a, S> = self
+            .where_clause
+            .first_round_evaluate(builder, alloc, table);
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
@@ -56,7 +58,11 @@ impl ProverEvaluate for DishonestFilterExec {
         let columns: Vec<_> = self
             .aliased_results
             .iter()
-            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .first_round_evaluate(builder, alloc, table)
+            })
             .collect();
         // Compute filtered_columns
         let (filtered_columns, _) = filter_columns(alloc, &columns, selection);
@@ -2,8 +2,9 @@ use super::{fold_columns, fold_vals};
 use crate::{
     base::{
         database::{
+            filter_util::filter_column_by_index,
             group_by_util::{aggregate_columns, AggregatedColumns},
-            order_by_util::compare_indexes_by_owned_columns,
+            order_by_util::{compare_indexes_by_columns, compare_indexes_by_owned_columns},
             Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
             TableRef,
         },
@@ -17,7 +18,11 @@ use crate::{
             FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
             SumcheckSubpolynomialType, VerificationBuilder,
         },
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, prover_evaluate_sign,
+            verifier_evaluate_sign, verify_monotonic,
+        },
     },
     utils::log,
 };
@@ -25,24 +30,36 @@ use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
 use core::iter;
 use num_traits::{One, Zero};
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
 /// Provable expressions for queries of the form
 /// ```ignore
-///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
-///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
+///     SELECT <group_by_expr1>.expr as <group_by_expr1>.alias, ..., <group_by_exprM>.expr as <group_by_exprM>.alias,
+///         <op1>(<aggregate_expr1>.expr) as <aggregate_expr1>.alias, ..., <opN>(<aggregate_exprN>.expr) as <aggregate_exprN>.alias,
 ///         COUNT(*) as count_alias
 ///     FROM <table>
 ///     WHERE <where_clause>
-///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
+///     GROUP BY <group_by_expr1>.expr, ..., <group_by_exprM>.expr
 /// ```
+/// where each of `<op1>`, ..., `<opN>` is one of `SUM`, `MAX` or `MIN`.
+///
+/// A `SUM` may carry the condition of a `FILTER (WHERE ...)` clause, in which case only the rows
+/// satisfying the condition are summed. This is also how `COUNT(expr)` is proven, namely as the sum of `1`
+/// over the rows where `expr` is not `NULL`.
+///
+/// The group by expressions are not restricted to columns, e.g. `GROUP BY a + b` or `GROUP BY CASE ... END`
+/// are proven the same way, since the verifier evaluates them like any other expression.
 ///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+///
+/// The groups are checked to be distinct against the order of the query result,
+/// so the plan can only be the input of another plan if it proves this itself, see [`GroupByExec::proves_distinct_groups`].
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GroupByExec {
-    pub(super) group_by_exprs: Vec<ColumnExpr>,
-    pub(super) sum_expr: Vec<AliasedDynProofExpr>,
+    pub(super) group_by_exprs: Vec<AliasedDynProofExpr>,
+    pub(super) aggregate_exprs: Vec<AliasedDynProofExpr>,
     pub(super) count_alias: Ident,
     pub(super) table: TableExpr,
     pub(super) where_clause: DynProofExpr,
@@ -50,25 +67,87 @@ pub struct GroupByExec {
 
 impl GroupByExec {
     /// Creates a new `group_by` expression.
+    ///
+    /// Aggregate expressions with the `MAX` or `MIN` operator are aggregated accordingly,
+    /// all other aggregate expressions are summed.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr>,
-        sum_expr: Vec<AliasedDynProofExpr>,
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        aggregate_exprs: Vec<AliasedDynProofExpr>,
         count_alias: Ident,
         table: TableExpr,
         where_clause: DynProofExpr,
     ) -> Self {
         Self {
             group_by_exprs,
-            sum_expr,
+            aggregate_exprs,
             count_alias,
             table,
             where_clause,
         }
     }
+
+    /// The aggregation operator of each of the aggregate expressions, which is one of
+    /// `SUM`, `MAX` or `MIN`.
+    fn aggregation_operators(&self) -> Vec<AggregationOperator> {
+        self.aggregate_exprs
+            .iter()
+            .map(|aliased_expr| match &aliased_expr.expr {
+                DynProofExpr::Aggregate(aggregate_expr)
+                    if matches!(
+                        aggregate_expr.op(),
+                        AggregationOperator::Max | AggregationOperator::Min
+                    ) =>
+                {
+                    aggregate_expr.op()
+                }
+                _ => AggregationOperator::Sum,
+            })
+            .collect()
+    }
+
+    /// The condition of the `FILTER` clause of each of the aggregate expressions, if any
+    fn aggregate_filters(&self) -> Vec<Option<&DynProofExpr>> {
+        self.aggregate_exprs
+            .iter()
+            .map(|aliased_expr| match &aliased_expr.expr {
+                DynProofExpr::Aggregate(aggregate_expr) => aggregate_expr.filter(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the plan proves that its groups are distinct, so that it does not rely on the
+    /// verifier checking the order of the query result and can be the input of another plan.
+    ///
+    /// This is the case when there is no `GROUP BY`, where the result is proven to have at most one row,
+    /// and when the groups are given by a single column whose order is the order of its scalars,
+    /// which is then proven to be strictly increasing.
+    #[must_use]
+    pub fn proves_distinct_groups(&self) -> bool {
+        self.group_by_exprs.is_empty() || self.proves_increasing_groups()
+    }
+
+    /// Whether the groups are given by a single column that is proven to be strictly increasing
+    fn proves_increasing_groups(&self) -> bool {
+        matches!(
+            self.group_by_exprs.as_slice(),
+            [aliased_expr] if matches!(
+                aliased_expr.expr.data_type(),
+                ColumnType::Boolean
+                    | ColumnType::Uint8
+                    | ColumnType::TinyInt
+                    | ColumnType::SmallInt
+                    | ColumnType::Int
+                    | ColumnType::BigInt
+                    | ColumnType::Int128
+                    | ColumnType::TimestampTZ(_, _)
+            )
+        )
+    }
 }
 
 impl ProofPlan for GroupByExec {
-    #[allow(unused_variables)]
+    #[allow(unused_variables, clippy::too_many_lines)]
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
@@ -76,9 +155,11 @@ impl ProofPlan for GroupByExec {
         result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
     ) -> Result<TableEvaluation<S>, ProofError> {
-        let input_chi_eval = *chi_eval_map
-            .get(&self.table.table_ref)
-            .expect("Chi eval not found");
+        let (accessor, input_chi_eval) =
+            self.table
+                .verifier_evaluate(builder, accessor, chi_eval_map)?;
+        let accessor = &*accessor;
+        let operators = self.aggregation_operators();
         // 1. selection
         let where_eval = self
             .where_clause
@@ -87,10 +168,14 @@ impl ProofPlan for GroupByExec {
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor, input_chi_eval))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, accessor, input_chi_eval)
+            })
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
-            .sum_expr
+            .aggregate_exprs
             .iter()
             .map(|aliased_expr| {
                 aliased_expr
@@ -98,48 +183,155 @@ impl ProofPlan for GroupByExec {
                     .verifier_evaluate(builder, accessor, input_chi_eval)
             })
             .collect::<Result<Vec<_>, _>>()?;
+        let filters = self.aggregate_filters();
+        if operators
+            .iter()
+            .zip(&filters)
+            .any(|(op, filter)| *op != AggregationOperator::Sum && filter.is_some())
+        {
+            Err(ProofError::UnsupportedQueryPlan {
+                error: "GroupByExec only supports FILTER clauses of sums and counts.",
+            })?;
+        }
+        let filter_evals = filters
+            .iter()
+            .map(|filter| {
+                filter
+                    .map(|filter| filter.verifier_evaluate(builder, accessor, input_chi_eval))
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // The filtered aggregates are summed over the masked columns `v * filter`
+        let aggregate_evals = aggregate_evals
+            .into_iter()
+            .zip(filter_evals)
+            .map(|(eval, filter_eval)| match filter_eval {
+                Some(filter_eval) => {
+                    let masked_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    // masked - v * filter = 0
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        masked_eval - eval * filter_eval,
+                        2,
+                    )?;
+                    Ok(masked_eval)
+                }
+                None => Ok(eval),
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?;
         // 3. filtered_columns
         let group_by_result_columns_evals =
             builder.try_consume_final_round_mle_evaluations(self.group_by_exprs.len())?;
-        let sum_result_columns_evals =
-            builder.try_consume_final_round_mle_evaluations(self.sum_expr.len())?;
+        let aggregate_result_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(self.aggregate_exprs.len())?;
         let count_column_eval = builder.try_consume_final_round_mle_evaluation()?;
+        // 4. witnesses of the extrema
+        let (extremum_in_evals, is_attained_evals): (Vec<_>, Vec<_>) = operators
+            .iter()
+            .filter(|op| **op != AggregationOperator::Sum)
+            .map(|_| {
+                Ok((
+                    builder.try_consume_first_round_mle_evaluation()?,
+                    builder.try_consume_first_round_mle_evaluation()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, ProofError>>()?
+            .into_iter()
+            .unzip();
 
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
         let output_chi_eval = builder.try_consume_chi_evaluation()?;
 
-        verify_group_by(
+        let evals_with = |evals: &[S], is_sum: bool| {
+            operators
+                .iter()
+                .zip(evals)
+                .filter(|(op, _)| (**op == AggregationOperator::Sum) == is_sum)
+                .map(|(_, eval)| *eval)
+                .collect::<Vec<_>>()
+        };
+        let (g_in_star_eval, g_out_star_eval) = verify_group_by(
             builder,
             alpha,
             beta,
             input_chi_eval,
             output_chi_eval,
-            (group_by_evals, aggregate_evals, where_eval),
+            (
+                group_by_evals.clone(),
+                evals_with(&aggregate_evals, true),
+                where_eval,
+            ),
             (
                 group_by_result_columns_evals.clone(),
-                sum_result_columns_evals.clone(),
+                evals_with(&aggregate_result_columns_evals, true),
                 count_column_eval,
             ),
         )?;
+        let extremum_operators = operators
+            .iter()
+            .copied()
+            .filter(|op| *op != AggregationOperator::Sum)
+            .collect::<Vec<_>>();
+        if !extremum_operators.is_empty() {
+            verify_group_by_extrema(
+                builder,
+                alpha,
+                beta,
+                input_chi_eval,
+                output_chi_eval,
+                &extremum_operators,
+                (&group_by_evals, g_in_star_eval, where_eval),
+                (
+                    &group_by_result_columns_evals,
+                    g_out_star_eval,
+                    count_column_eval,
+                ),
+                (
+                    &evals_with(&aggregate_evals, false),
+                    &extremum_in_evals,
+                    &is_attained_evals,
+                ),
+                &evals_with(&aggregate_result_columns_evals, false),
+            )?;
+        }
+        let proves_distinct_groups = self.proves_distinct_groups();
+        if self.proves_increasing_groups() {
+            verify_monotonic::<S, true, true>(
+                builder,
+                alpha,
+                beta,
+                group_by_result_columns_evals[0],
+                output_chi_eval,
+            )?;
+        } else if self.group_by_exprs.is_empty()
+            && output_chi_eval != S::ZERO
+            && output_chi_eval != builder.singleton_chi_evaluation()
+        {
+            // Without a `GROUP BY` all rows are in the same group
+            Err(ProofError::VerificationError {
+                error: "GroupByExec without group by columns has more than one group",
+            })?;
+        }
         match result {
             Some(table) => {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError {
                         error: "Result does not all correct group by columns.",
                     })?;
-                if (0..table.num_rows() - 1)
-                    .any(|i| compare_indexes_by_owned_columns(&cols, i, i + 1).is_ge())
+                if (1..table.num_rows())
+                    .any(|i| compare_indexes_by_owned_columns(&cols, i - 1, i).is_ge())
                 {
                     Err(ProofError::VerificationError {
                         error: "Result of group by not ordered as expected.",
                     })?;
                 }
             }
+            None if proves_distinct_groups => {}
             None => {
                 Err(ProofError::UnsupportedQueryPlan {
                     error: "GroupByExec currently only supported at top level of query plan.",
@@ -149,20 +341,19 @@ impl ProofPlan for GroupByExec {
 
         let column_evals = group_by_result_columns_evals
             .into_iter()
-            .chain(sum_result_columns_evals)
+            .chain(aggregate_result_columns_evals)
             .chain(iter::once(count_column_eval))
             .collect::<Vec<_>>();
         Ok(TableEvaluation::new(column_evals, output_chi_eval))
     }
 
-    #[allow(clippy::redundant_closure_for_method_calls)]
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
+            .chain(&self.aggregate_exprs)
+            .map(|aliased_expr| {
                 ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
-            }))
+            })
             .chain(iter::once(ColumnField::new(
                 self.count_alias.clone(),
                 ColumnType::BigInt,
@@ -173,20 +364,18 @@ impl ProofPlan for GroupByExec {
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         let mut columns = IndexSet::default();
 
-        for col in &self.group_by_exprs {
-            columns.insert(col.get_column_reference());
-        }
-        for aliased_expr in &self.sum_expr {
+        for aliased_expr in self.group_by_exprs.iter().chain(&self.aggregate_exprs) {
             aliased_expr.expr.get_column_references(&mut columns);
         }
 
         self.where_clause.get_column_references(&mut columns);
 
-        columns
+        self.table.get_column_references(columns)
     }
 
     fn get_table_references(&self) -> IndexSet<TableRef> {
-        IndexSet::from_iter([self.table.table_ref.clone()])
+        self.table
+            .get_table_references(&self.get_column_references())
     }
 }
 
@@ -200,11 +389,12 @@ impl ProverEvaluate for GroupByExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.first_round_evaluate(builder, alloc, table_map);
+        let operators = self.aggregation_operators();
         // 1. selection
-        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
+        let selection_column: Column<'a, S> = self
+            .where_clause
+            .first_round_evaluate(builder, alloc, table);
 
         let selection = selection_column
             .as_boolean()
@@ -214,22 +404,47 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .first_round_evaluate(builder, alloc, table)
+            })
             .collect::<Vec<_>>();
-        let sum_columns = self
-            .sum_expr
+        let aggregate_columns = self
+            .aggregate_exprs
             .iter()
-            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .first_round_evaluate(builder, alloc, table)
+            })
             .collect::<Vec<_>>();
+        let filter_columns = self
+            .aggregate_filters()
+            .into_iter()
+            .map(|filter| {
+                filter.map(|filter| {
+                    filter
+                        .first_round_evaluate(builder, alloc, table)
+                        .as_boolean()
+                        .expect("filter is not boolean")
+                })
+            })
+            .collect::<Vec<_>>();
+        let aggregate_columns = mask_aggregate_columns(alloc, &aggregate_columns, &filter_columns);
         // Compute filtered_columns
-        let AggregatedColumns {
+        let GroupByAggregation {
             group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
+            aggregate_columns: aggregate_result_columns,
             count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
-        let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
+            extremum_witnesses,
+        } = aggregate_group_by(
+            alloc,
+            &operators,
+            &group_by_columns,
+            &aggregate_columns,
+            selection,
+        );
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
                 .into_iter()
@@ -237,13 +452,20 @@ impl ProverEvaluate for GroupByExec {
                 .zip(
                     group_by_result_columns
                         .into_iter()
-                        .chain(sum_result_columns_iter)
+                        .chain(aggregate_result_columns)
                         .chain(iter::once(Column::BigInt(count_column))),
                 ),
         )
         .expect("Failed to create table from column references");
+        for (extremum_in, is_attained) in extremum_witnesses {
+            builder.produce_intermediate_mle(extremum_in);
+            builder.produce_intermediate_mle(is_attained);
+        }
         builder.request_post_result_challenges(2);
         builder.produce_chi_evaluation_length(count_column.len());
+        if self.proves_increasing_groups() {
+            first_round_evaluate_monotonic(builder, count_column.len());
+        }
 
         log::log_memory_usage("End");
 
@@ -251,7 +473,7 @@ impl ProverEvaluate for GroupByExec {
     }
 
     #[tracing::instrument(name = "GroupByExec::final_round_evaluate", level = "debug", skip_all)]
-    #[allow(unused_variables)]
+    #[allow(unused_variables, clippy::too_many_lines)]
     fn final_round_evaluate<'a, S: Scalar>(
         &self,
         builder: &mut FinalRoundBuilder<'a, S>,
@@ -260,9 +482,8 @@ impl ProverEvaluate for GroupByExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.final_round_evaluate(builder, alloc, table_map);
+        let operators = self.aggregation_operators();
         // 1. selection
         let selection_column: Column<'a, S> =
             self.where_clause.prover_evaluate(builder, alloc, table);
@@ -274,31 +495,66 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.prover_evaluate(builder, alloc, table))
+            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect::<Vec<_>>();
-        let sum_columns = self
-            .sum_expr
+        let aggregate_columns = self
+            .aggregate_exprs
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect::<Vec<_>>();
+        let filter_columns = self
+            .aggregate_filters()
+            .into_iter()
+            .map(|filter| {
+                filter.map(|filter| {
+                    filter
+                        .prover_evaluate(builder, alloc, table)
+                        .as_boolean()
+                        .expect("filter is not boolean")
+                })
+            })
+            .collect::<Vec<_>>();
+        let masked_columns = mask_aggregate_columns(alloc, &aggregate_columns, &filter_columns);
+        for ((column, filter), masked_column) in aggregate_columns
+            .iter()
+            .zip(&filter_columns)
+            .zip(&masked_columns)
+        {
+            if let Some(filter) = filter {
+                builder.produce_intermediate_mle(*masked_column);
+                // masked - v * filter = 0
+                builder.produce_sumcheck_subpolynomial(
+                    SumcheckSubpolynomialType::Identity,
+                    vec![
+                        (S::one(), vec![Box::new(*masked_column)]),
+                        (-S::one(), vec![Box::new(*column), Box::new(*filter)]),
+                    ],
+                );
+            }
+        }
+        let aggregate_columns = masked_columns;
         // 3. Compute filtered_columns
-        let AggregatedColumns {
+        let GroupByAggregation {
             group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
+            aggregate_columns: aggregate_result_columns,
             count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+            extremum_witnesses,
+        } = aggregate_group_by(
+            alloc,
+            &operators,
+            &group_by_columns,
+            &aggregate_columns,
+            selection,
+        );
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
         // 4. Tally results
-        let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
         let columns = group_by_result_columns
             .clone()
             .into_iter()
-            .chain(sum_result_columns_iter)
+            .chain(aggregate_result_columns.clone())
             .chain(iter::once(Column::BigInt(count_column)));
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
@@ -312,7 +568,23 @@ impl ProverEvaluate for GroupByExec {
             builder.produce_intermediate_mle(column);
         }
         // 6. Prove group by
-        prove_group_by(
+        let (sum_columns, extremum_columns): (Vec<_>, Vec<_>) = operators
+            .iter()
+            .zip(aggregate_columns)
+            .partition(|(op, _)| **op == AggregationOperator::Sum);
+        let (sum_result_columns, extremum_result_columns): (Vec<_>, Vec<_>) = operators
+            .iter()
+            .zip(aggregate_result_columns)
+            .partition(|(op, _)| **op == AggregationOperator::Sum);
+        let sum_columns = sum_columns
+            .into_iter()
+            .map(|(_, col)| col)
+            .collect::<Vec<_>>();
+        let sum_result_columns = sum_result_columns
+            .into_iter()
+            .map(|(_, col)| col)
+            .collect::<Vec<_>>();
+        let (g_in_star, g_out_star) = prove_group_by(
             builder,
             alloc,
             alpha,
@@ -321,6 +593,43 @@ impl ProverEvaluate for GroupByExec {
             (&group_by_result_columns, &sum_result_columns, count_column),
             table.num_rows(),
         );
+        // 7. Prove extrema
+        if !extremum_columns.is_empty() {
+            let (extremum_operators, extremum_columns): (Vec<_>, Vec<_>) = extremum_columns
+                .into_iter()
+                .map(|(op, col)| (*op, col))
+                .unzip();
+            let (extremum_in, is_attained): (Vec<_>, Vec<_>) =
+                extremum_witnesses.into_iter().unzip();
+            let extremum_result_columns = extremum_result_columns
+                .into_iter()
+                .map(|(_, col)| col)
+                .collect::<Vec<_>>();
+            prove_group_by_extrema(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                &extremum_operators,
+                (&group_by_columns, g_in_star, selection),
+                (&group_by_result_columns, g_out_star, count_column),
+                &extremum_columns,
+                (&extremum_in, &is_attained),
+                &extremum_result_columns,
+            );
+        }
+        // 8. Prove that the groups are distinct
+        if self.proves_increasing_groups() {
+            let group_by_result_column =
+                alloc.alloc_slice_copy(&group_by_result_columns[0].to_scalar_with_scaling(0));
+            final_round_evaluate_monotonic::<S, true, true>(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                group_by_result_column,
+            );
+        }
 
         log::log_memory_usage("End");
 
@@ -328,7 +637,167 @@ impl ProverEvaluate for GroupByExec {
     }
 }
 
-#[allow(clippy::unnecessary_wraps)]
+/// The result of aggregating the columns of a [`GroupByExec`]
+struct GroupByAggregation<'a, S: Scalar> {
+    group_by_columns: Vec<Column<'a, S>>,
+    /// The aggregated columns, in the same order as the aggregate expressions
+    aggregate_columns: Vec<Column<'a, S>>,
+    count_column: &'a [i64],
+    /// The witnesses of the `MAX` and `MIN` aggregates, see [`compute_extremum_witnesses`]
+    extremum_witnesses: Vec<(&'a [S], &'a [bool])>,
+}
+
+/// Aggregates the columns of a [`GroupByExec`] and computes the witnesses of its extrema.
+///
+/// # Panics
+/// Panics if the columns can not be aggregated, which should never happen for a valid [`GroupByExec`].
+fn aggregate_group_by<'a, S: Scalar>(
+    alloc: &'a Bump,
+    operators: &[AggregationOperator],
+    group_by_columns: &[Column<'a, S>],
+    aggregate_columns_in: &[Column<'a, S>],
+    selection: &'a [bool],
+) -> GroupByAggregation<'a, S> {
+    let columns_with = |operator: AggregationOperator| {
+        operators
+            .iter()
+            .zip(aggregate_columns_in)
+            .filter(|(op, _)| **op == operator)
+            .map(|(_, col)| *col)
+            .collect::<Vec<_>>()
+    };
+    let AggregatedColumns {
+        group_by_columns: group_by_result_columns,
+        sum_columns,
+        max_columns,
+        min_columns,
+        count_column,
+    } = aggregate_columns(
+        alloc,
+        group_by_columns,
+        &columns_with(AggregationOperator::Sum),
+        &columns_with(AggregationOperator::Max),
+        &columns_with(AggregationOperator::Min),
+        selection,
+    )
+    .expect("columns should be aggregatable");
+    let mut maxima = max_columns.into_iter();
+    let mut minima = min_columns.into_iter();
+    let extrema = operators
+        .iter()
+        .zip(aggregate_columns_in)
+        .filter_map(|(op, column)| match op {
+            AggregationOperator::Max => maxima.next().map(|extrema| (*column, extrema)),
+            AggregationOperator::Min => minima.next().map(|extrema| (*column, extrema)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let (extremum_witnesses, extremum_result_columns): (Vec<_>, Vec<_>) =
+        compute_extremum_witnesses(alloc, group_by_columns, selection, count_column, &extrema)
+            .into_iter()
+            .map(|(extremum_in, is_attained, column)| ((extremum_in, is_attained), column))
+            .unzip();
+    let mut sums = sum_columns.into_iter().map(|col| Column::Scalar(col));
+    let mut extremum_result_columns = extremum_result_columns.into_iter();
+    let aggregate_columns = operators
+        .iter()
+        .map(|op| match op {
+            AggregationOperator::Sum => sums.next(),
+            _ => extremum_result_columns.next(),
+        })
+        .collect::<Option<Vec<_>>>()
+        .expect("there should be an aggregated column for each aggregate expression");
+    GroupByAggregation {
+        group_by_columns: group_by_result_columns,
+        aggregate_columns,
+        count_column,
+        extremum_witnesses,
+    }
+}
+
+/// Replaces each aggregate column that has a filter by the column which agrees with it on the rows
+/// satisfying the filter and is zero on the other rows, so that summing it sums the filtered rows only.
+fn mask_aggregate_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    aggregate_columns: &[Column<'a, S>],
+    filter_columns: &[Option<&'a [bool]>],
+) -> Vec<Column<'a, S>> {
+    aggregate_columns
+        .iter()
+        .zip(filter_columns)
+        .map(|(column, filter)| match filter {
+            Some(filter) => Column::Scalar(alloc.alloc_slice_fill_with(column.len(), |i| {
+                if filter[i] {
+                    column.scalar_at(i).expect("index should be in bounds")
+                } else {
+                    S::ZERO
+                }
+            })),
+            None => *column,
+        })
+        .collect()
+}
+
+/// Compute the witnesses needed to prove the `MAX` and `MIN` aggregates.
+///
+/// For each pair of an input column and its aggregated extrema this returns
+/// 1. the extremum of the group of each selected row, and the value of the row itself for the other rows,
+/// 2. an indicator of a single row per group at which the extremum of the group is attained, and
+/// 3. the extrema as a column of the same type as the input column.
+///
+/// # Panics
+/// Panics if `counts` and `extrema` do not describe the aggregation of the selected rows.
+fn compute_extremum_witnesses<'a, S: Scalar>(
+    alloc: &'a Bump,
+    group_by_columns: &[Column<'a, S>],
+    selection: &[bool],
+    counts: &[i64],
+    extrema: &[(Column<'a, S>, &'a [Option<S>])],
+) -> Vec<(&'a [S], &'a [bool], Column<'a, S>)> {
+    // The rows are grouped the same way as in `aggregate_columns`
+    let mut filtered_indexes: Vec<_> = selection
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(i, _)| i)
+        .collect();
+    filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(group_by_columns, a, b));
+    extrema
+        .iter()
+        .map(|(column, extremum_out)| {
+            let extremum_in = alloc.alloc_slice_fill_with(column.len(), |i| {
+                column.scalar_at(i).expect("index should be in bounds")
+            });
+            let is_attained = alloc.alloc_slice_fill_copy(column.len(), false);
+            let mut attained_indexes = Vec::with_capacity(counts.len());
+            let mut group_start = 0;
+            for (&extremum, &count) in extremum_out.iter().zip(counts) {
+                // Groups only consist of selected rows, so they are never empty
+                let extremum = extremum.expect("groups should not be empty");
+                let group_end =
+                    group_start + usize::try_from(count).expect("counts should be non-negative");
+                let group = &filtered_indexes[group_start..group_end];
+                let attained_index = *group
+                    .iter()
+                    .find(|&&i| extremum_in[i] == extremum)
+                    .expect("the extremum of a group should be attained in the group");
+                is_attained[attained_index] = true;
+                attained_indexes.push(attained_index);
+                for &i in group {
+                    extremum_in[i] = extremum;
+                }
+                group_start = group_end;
+            }
+            (
+                &*extremum_in,
+                &*is_attained,
+                filter_column_by_index(alloc, column, &attained_indexes),
+            )
+        })
+        .collect()
+}
+
+/// Verifies the constraints of [`prove_group_by`] and returns the evaluations of `g_in_star` and `g_out_star`.
 fn verify_group_by<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     alpha: S,
@@ -337,7 +806,7 @@ fn verify_group_by<S: Scalar>(
     output_chi_eval: S,
     (g_in_evals, sum_in_evals, sel_in_eval): (Vec<S>, Vec<S>, S),
     (g_out_evals, sum_out_evals, count_out_eval): (Vec<S>, Vec<S>, S),
-) -> Result<(), ProofError> {
+) -> Result<(S, S), ProofError> {
     // g_in_fold = alpha * sum beta^j * g_in[j]
     let g_in_fold_eval = alpha * fold_vals(beta, &g_in_evals);
     // g_out_fold = alpha * sum beta^j * g_out[j]
@@ -371,9 +840,10 @@ fn verify_group_by<S: Scalar>(
         2,
     )?;
 
-    Ok(())
+    Ok((g_in_star_eval, g_out_star_eval))
 }
 
+/// Proves that the sums and counts of a group by are correct and returns `g_in_star` and `g_out_star`.
 #[allow(
     clippy::missing_panics_doc,
     reason = "alpha is guaranteed to not be zero in this context"
@@ -384,9 +854,9 @@ pub fn prove_group_by<'a, S: Scalar>(
     alpha: S,
     beta: S,
     (g_in, sum_in, sel_in): (&[Column<S>], &[Column<S>], &'a [bool]),
-    (g_out, sum_out, count_out): (&[Column<S>], &[&'a [S]], &'a [i64]),
+    (g_out, sum_out, count_out): (&[Column<S>], &[Column<S>], &'a [i64]),
     n: usize,
-) {
+) -> (&'a [S], &'a [S]) {
     let m = count_out.len();
     let chi_n = alloc.alloc_slice_fill_copy(n, true);
     let chi_m = alloc.alloc_slice_fill_copy(m, true);
@@ -465,4 +935,237 @@ pub fn prove_group_by<'a, S: Scalar>(
             (-S::one(), vec![Box::new(chi_m as &[_])]),
         ],
     );
+
+    (g_in_star, g_out_star)
+}
+
+/// Verifies the constraints of [`prove_group_by_extrema`].
+#[allow(clippy::too_many_arguments)]
+fn verify_group_by_extrema<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    input_chi_eval: S,
+    output_chi_eval: S,
+    operators: &[AggregationOperator],
+    (g_in_evals, g_in_star_eval, sel_in_eval): (&[S], S, S),
+    (g_out_evals, g_out_star_eval, count_out_eval): (&[S], S, S),
+    (v_in_evals, ext_in_evals, is_attained_evals): (&[S], &[S], &[S]),
+    ext_out_evals: &[S],
+) -> Result<(), ProofError> {
+    // h_in_fold = alpha * sum beta^j * (g_in || ext_in)[j]
+    let h_in_fold_eval = alpha * fold_vals(beta, &[g_in_evals, ext_in_evals].concat());
+    // h_out_fold = alpha * sum beta^j * (g_out || ext_out)[j]
+    let h_out_fold_eval = alpha * fold_vals(beta, &[g_out_evals, ext_out_evals].concat());
+
+    let h_in_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let h_out_star_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // sum h_in_star * sel_in - h_out_star * count_out = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        h_in_star_eval * sel_in_eval - h_out_star_eval * count_out_eval,
+        2,
+    )?;
+
+    // h_in_star + h_in_star * h_in_fold - chi_n = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        h_in_star_eval + h_in_star_eval * h_in_fold_eval - input_chi_eval,
+        2,
+    )?;
+
+    // h_out_star + h_out_star * h_out_fold - chi_m = 0
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        h_out_star_eval + h_out_star_eval * h_out_fold_eval - output_chi_eval,
+        2,
+    )?;
+
+    for (((op, &v_in_eval), &ext_in_eval), &is_attained_eval) in operators
+        .iter()
+        .zip(v_in_evals)
+        .zip(ext_in_evals)
+        .zip(is_attained_evals)
+    {
+        // diff = ext_in - v_in for MAX and v_in - ext_in for MIN is never negative
+        let diff_eval = if *op == AggregationOperator::Max {
+            ext_in_eval - v_in_eval
+        } else {
+            v_in_eval - ext_in_eval
+        };
+        if verifier_evaluate_sign(builder, diff_eval, input_chi_eval)? != S::ZERO {
+            Err(ProofError::VerificationError {
+                error: "Extremum of group by is not a bound of its group.",
+            })?;
+        }
+
+        // sum g_in_star * is_attained - g_out_star = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::ZeroSum,
+            g_in_star_eval * is_attained_eval - g_out_star_eval,
+            2,
+        )?;
+
+        // is_attained - is_attained * sel_in = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            is_attained_eval - is_attained_eval * sel_in_eval,
+            2,
+        )?;
+
+        // is_attained * v_in - is_attained * ext_in = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            is_attained_eval * v_in_eval - is_attained_eval * ext_in_eval,
+            2,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Proves that the `MAX` and `MIN` aggregates of a group by are correct.
+///
+/// `ext_in` assigns each row the extremum of its group, which is established by
+/// - a lookup of `(g_in, ext_in)` over the selected rows in `(g_out, ext_out)` weighted by `count_out`,
+/// - a sign check that every value of `v_in` is on the correct side of `ext_in`, and
+/// - a lookup of `g_in` over the rows marked by `is_attained` in `g_out`, where each marked row
+///   is selected and has `v_in == ext_in`, so that every extremum is attained in its group.
+///
+/// `g_in_star` and `g_out_star` are the columns returned by [`prove_group_by`].
+///
+/// # Panics
+/// Panics if the columns of `v_in` are shorter than `sel_in`.
+#[allow(clippy::too_many_arguments)]
+fn prove_group_by_extrema<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    operators: &[AggregationOperator],
+    (g_in, g_in_star, sel_in): (&[Column<'a, S>], &'a [S], &'a [bool]),
+    (g_out, g_out_star, count_out): (&[Column<'a, S>], &'a [S], &'a [i64]),
+    v_in: &[Column<'a, S>],
+    (ext_in, is_attained): (&[&'a [S]], &[&'a [bool]]),
+    ext_out: &[Column<'a, S>],
+) {
+    let n = sel_in.len();
+    let m = count_out.len();
+    let chi_n = alloc.alloc_slice_fill_copy(n, true);
+    let chi_m = alloc.alloc_slice_fill_copy(m, true);
+
+    // h_in_fold = alpha * sum beta^j * (g_in || ext_in)[j]
+    let h_in_columns = g_in
+        .iter()
+        .copied()
+        .chain(ext_in.iter().map(|col| Column::Scalar(col)))
+        .collect::<Vec<_>>();
+    let h_in_fold = alloc.alloc_slice_fill_copy(n, Zero::zero());
+    fold_columns(h_in_fold, alpha, beta, &h_in_columns);
+
+    // h_out_fold = alpha * sum beta^j * (g_out || ext_out)[j]
+    let h_out_columns = g_out
+        .iter()
+        .copied()
+        .chain(ext_out.iter().copied())
+        .collect::<Vec<_>>();
+    let h_out_fold = alloc.alloc_slice_fill_copy(m, Zero::zero());
+    fold_columns(h_out_fold, alpha, beta, &h_out_columns);
+
+    // h_in_star = (1 + h_in_fold)^(-1)
+    let h_in_star = alloc.alloc_slice_copy(h_in_fold);
+    slice_ops::add_const::<S, S>(h_in_star, One::one());
+    slice_ops::batch_inversion(h_in_star);
+
+    // h_out_star = (1 + h_out_fold)^(-1)
+    let h_out_star = alloc.alloc_slice_copy(h_out_fold);
+    slice_ops::add_const::<S, S>(h_out_star, One::one());
+    slice_ops::batch_inversion(h_out_star);
+
+    builder.produce_intermediate_mle(h_in_star as &[_]);
+    builder.produce_intermediate_mle(h_out_star as &[_]);
+
+    // sum h_in_star * sel_in - h_out_star * count_out = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(h_in_star as &[_]), Box::new(sel_in)],
+            ),
+            (
+                -S::one(),
+                vec![Box::new(h_out_star as &[_]), Box::new(count_out)],
+            ),
+        ],
+    );
+
+    // h_in_star + h_in_star * h_in_fold - chi_n = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(h_in_star as &[_])]),
+            (
+                S::one(),
+                vec![Box::new(h_in_star as &[_]), Box::new(h_in_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi_n as &[_])]),
+        ],
+    );
+
+    // h_out_star + h_out_star * h_out_fold - chi_m = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(h_out_star as &[_])]),
+            (
+                S::one(),
+                vec![Box::new(h_out_star as &[_]), Box::new(h_out_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(chi_m as &[_])]),
+        ],
+    );
+
+    for (((op, v_in), &ext_in), &is_attained) in
+        operators.iter().zip(v_in).zip(ext_in).zip(is_attained)
+    {
+        // diff = ext_in - v_in for MAX and v_in - ext_in for MIN is never negative
+        let diff = alloc.alloc_slice_fill_with(n, |i| {
+            let diff = ext_in[i] - v_in.scalar_at(i).expect("index should be in bounds");
+            if *op == AggregationOperator::Max {
+                diff
+            } else {
+                -diff
+            }
+        });
+        prover_evaluate_sign(builder, alloc, diff);
+
+        // sum g_in_star * is_attained - g_out_star = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(g_in_star), Box::new(is_attained)]),
+                (-S::one(), vec![Box::new(g_out_star)]),
+            ],
+        );
+
+        // is_attained - is_attained * sel_in = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(is_attained)]),
+                (-S::one(), vec![Box::new(is_attained), Box::new(sel_in)]),
+            ],
+        );
+
+        // is_attained * v_in - is_attained * ext_in = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(is_attained), Box::new(*v_in)]),
+                (-S::one(), vec![Box::new(is_attained), Box::new(ext_in)]),
+            ],
+        );
+    }
 }
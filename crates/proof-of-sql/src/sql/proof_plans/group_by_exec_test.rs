@@ -10,6 +10,7 @@ use crate::{
         proof_exprs::test_utility::*,
     },
 };
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
 /// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
 #[test]
@@ -23,7 +24,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t.clone(), data, 0);
     let expr = group_by(
-        cols_expr(&t, &["a"], &accessor),
+        cols_expr_plan(&t, &["a"], &accessor),
         vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
         "__count__",
         tab(&t),
@@ -52,7 +53,7 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t.clone(), data, 0);
     let expr = group_by(
-        cols_expr(&t, &["a"], &accessor),
+        cols_expr_plan(&t, &["a"], &accessor),
         vec![sum_expr(
             add(
                 multiply(column(&t, "c", &accessor), const_bigint(2)),
@@ -75,6 +76,43 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+/// `select a + b as ab, c, sum(d) as sum_d, count(*) as __count__ from sxt.t group by a + b, c`
+#[test]
+fn we_can_prove_a_group_by_with_an_expression_key() {
+    let data = owned_table([
+        bigint("a", [1, 2, 0, 3, 1]),
+        bigint("b", [2, 1, 3, 0, 5]),
+        bigint("c", [1, 1, 1, 2, 1]),
+        bigint("d", [10, 20, 30, 40, 50]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        vec![
+            aliased_plan(
+                add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "ab",
+            ),
+            col_expr_plan(&t, "c", &accessor),
+        ],
+        vec![sum_expr(column(&t, "d", &accessor), "sum_d")],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("ab", [3, 3, 6]),
+        bigint("c", [1, 2, 1]),
+        bigint("sum_d", [10 + 20 + 30, 40, 50]),
+        bigint("__count__", [3, 1, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 #[allow(clippy::too_many_lines)]
 #[test]
 fn we_can_prove_a_complex_group_by_query_with_many_columns() {
@@ -160,7 +198,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
     //  GROUP BY scalar_group, int128_group, bigint_group
     let expr = group_by(
-        cols_expr(
+        cols_expr_plan(
             &t,
             &["scalar_group", "int128_group", "bigint_group"],
             &accessor,
@@ -230,3 +268,162 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+/// `select a, max(c) as max_c, min(c - b) as min_diff, sum(c) as sum_c, count(*) as __count__ from sxt.t where b <> 0 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_max_and_min() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3, 1]),
+        bigint("b", [99, 98, 99, 99, 0, 97, 99]),
+        bigint("c", [-101, 102, 103, -104, 105, -7, 101]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![
+            max_expr(column(&t, "c", &accessor), "max_c"),
+            min_expr(
+                subtract(column(&t, "c", &accessor), column(&t, "b", &accessor)),
+                "min_diff",
+            ),
+            sum_expr(column(&t, "c", &accessor), "sum_c"),
+        ],
+        "__count__",
+        tab(&t),
+        not(equal(column(&t, "b", &accessor), const_bigint(0))),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("max_c", [101, 103, -7]),
+        bigint("min_diff", [-104 - 99, 102 - 98, -7 - 97]),
+        bigint("sum_c", [-101 - 104 + 101, 102 + 103, -7]),
+        bigint("__count__", [3, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, sum(c) filter (where c >= 0) as sum_c, count(c) filter (where b = 99) as count_c, count(*) as __count__ from sxt.t where b <> 0 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_filtered_sums_and_counts() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3, 1]),
+        bigint("b", [99, 98, 99, 99, 0, 97, 99]),
+        bigint("c", [-101, 102, 103, -104, 105, -7, 101]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![
+            filtered_sum_expr(
+                column(&t, "c", &accessor),
+                gte(column(&t, "c", &accessor), const_bigint(0)),
+                "sum_c",
+            ),
+            count_expr(
+                Some(equal(column(&t, "b", &accessor), const_bigint(99))),
+                "count_c",
+            ),
+            sum_expr(column(&t, "c", &accessor), "sum_all_c"),
+        ],
+        "__count__",
+        tab(&t),
+        not(equal(column(&t, "b", &accessor), const_bigint(0))),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("sum_c", [101, 102 + 103, 0]),
+        bigint("count_c", [3, 1, 0]),
+        bigint("sum_all_c", [-101 - 104 + 101, 102 + 103, -7]),
+        bigint("__count__", [3, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select max(d) as max_d, min(d) as min_d, max(ts) as max_ts, count(*) as __count__ from sxt.t where a = 1`
+#[test]
+fn we_can_prove_extrema_of_decimals_and_timestamps_without_group_by_columns() {
+    let data = owned_table([
+        bigint("a", [1, 2, 1, 1, 2]),
+        decimal75("d", 10, 2, [-150, 299, 1_000, 0, -2_000]),
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [
+                1_625_072_400,
+                1_625_076_000,
+                1_625_065_000,
+                1_625_079_600,
+                0,
+            ],
+        ),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        vec![],
+        vec![
+            max_expr(column(&t, "d", &accessor), "max_d"),
+            min_expr(column(&t, "d", &accessor), "min_d"),
+            max_expr(column(&t, "ts", &accessor), "max_ts"),
+        ],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "a", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        decimal75("max_d", 10, 2, [1_000]),
+        decimal75("min_d", 10, 2, [-150]),
+        timestamptz(
+            "max_ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_625_079_600],
+        ),
+        bigint("__count__", [3]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, max(c) as max_c, count(*) as __count__ from sxt.t where b = 1 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_extrema_and_no_selected_rows() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2]),
+        bigint("b", [0, 0, 0]),
+        bigint("c", [101, 102, 103]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![max_expr(column(&t, "c", &accessor), "max_c")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_bigint(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0; 0]),
+        bigint("max_c", [0; 0]),
+        bigint("__count__", [0; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
@@ -40,16 +40,36 @@ pub(crate) use slice_exec::SliceExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod slice_exec_test;
 
+mod sort_exec;
+pub(crate) use sort_exec::SortExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod sort_exec_test;
+
+mod distinct_exec;
+pub(crate) use distinct_exec::DistinctExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod distinct_exec_test;
+
 mod union_exec;
 pub(crate) use union_exec::UnionExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod union_exec_test;
 
+mod window_exec;
+pub(crate) use window_exec::{AliasedWindowFunction, WindowExec, WindowFunction};
+#[cfg(all(test, feature = "blitzar"))]
+mod window_exec_test;
+
 mod sort_merge_join_exec;
 pub(crate) use sort_merge_join_exec::SortMergeJoinExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod sort_merge_join_exec_test;
 
+mod sort_merge_outer_join_exec;
+pub(crate) use sort_merge_outer_join_exec::SortMergeOuterJoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod sort_merge_outer_join_exec_test;
+
 mod dyn_proof_plan;
 pub use dyn_proof_plan::DynProofPlan;
 
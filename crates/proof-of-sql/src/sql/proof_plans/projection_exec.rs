@@ -1,7 +1,8 @@
 use crate::{
     base::{
         database::{
-            ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+            Column, ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions,
+            TableRef,
         },
         map::{IndexMap, IndexSet},
         proof::ProofError,
@@ -17,6 +18,7 @@ use crate::{
 };
 use alloc::vec::Vec;
 use bumpalo::Bump;
+use core::iter;
 use serde::{Deserialize, Serialize};
 
 /// Provable expressions for queries of the form
@@ -49,9 +51,10 @@ impl ProofPlan for ProjectionExec {
         chi_eval_map: &IndexMap<TableRef, S>,
     ) -> Result<TableEvaluation<S>, ProofError> {
         // For projections input and output have the same length and hence the same chi eval
-        let chi_eval = *chi_eval_map
-            .get(&self.table.table_ref)
-            .expect("Chi eval not found");
+        let (accessor, chi_eval) = self
+            .table
+            .verifier_evaluate(builder, accessor, chi_eval_map)?;
+        let accessor = &*accessor;
         self.aliased_results
             .iter()
             .map(|aliased_expr| {
@@ -60,16 +63,19 @@ impl ProofPlan for ProjectionExec {
                     .verifier_evaluate(builder, accessor, chi_eval)
             })
             .collect::<Result<Vec<_>, _>>()?;
-        let column_evals =
-            builder.try_consume_final_round_mle_evaluations(self.aliased_results.len())?;
+        let column_evals = builder
+            .try_consume_final_round_mle_evaluations(self.get_column_result_fields().len())?;
         Ok(TableEvaluation::new(column_evals, chi_eval))
     }
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.aliased_results
             .iter()
-            .map(|aliased_expr| {
-                ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
+            .flat_map(|aliased_expr| {
+                let field =
+                    ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type());
+                let presence = aliased_expr.expr.is_nullable().then(|| field.presence());
+                iter::once(field).chain(presence)
             })
             .collect()
     }
@@ -79,11 +85,12 @@ impl ProofPlan for ProjectionExec {
         self.aliased_results.iter().for_each(|aliased_expr| {
             aliased_expr.expr.get_column_references(&mut columns);
         });
-        columns
+        self.table.get_column_references(columns)
     }
 
     fn get_table_references(&self) -> IndexSet<TableRef> {
-        IndexSet::from_iter([self.table.table_ref.clone()])
+        self.table
+            .get_table_references(&self.get_column_references())
     }
 }
 
@@ -95,20 +102,20 @@ impl ProverEvaluate for ProjectionExec {
     )]
     fn first_round_evaluate<'a, S: Scalar>(
         &self,
-        _builder: &mut FirstRoundBuilder<'a, S>,
+        builder: &mut FirstRoundBuilder<'a, S>,
         alloc: &'a Bump,
         table_map: &IndexMap<TableRef, Table<'a, S>>,
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.first_round_evaluate(builder, alloc, table_map);
         let res = Table::<'a, S>::try_from_iter_with_options(
             self.aliased_results.iter().map(|aliased_expr| {
                 (
                     aliased_expr.alias.clone(),
-                    aliased_expr.expr.result_evaluate(alloc, table),
+                    aliased_expr
+                        .expr
+                        .first_round_evaluate(builder, alloc, table),
                 )
             }),
             TableOptions::new(Some(table.num_rows())),
@@ -134,9 +141,7 @@ impl ProverEvaluate for ProjectionExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.table.final_round_evaluate(builder, alloc, table_map);
         // 1. Evaluate result expressions
         let res = Table::<'a, S>::try_from_iter_with_options(
             self.aliased_results.iter().map(|aliased_expr| {
@@ -149,7 +154,7 @@ impl ProverEvaluate for ProjectionExec {
         )
         .expect("Failed to create table from iterator");
         // 2. Produce MLEs
-        for column in res.columns().copied() {
+        for column in res.columns().flat_map(Column::components) {
             builder.produce_intermediate_mle(column);
         }
 
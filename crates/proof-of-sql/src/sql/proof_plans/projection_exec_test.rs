@@ -45,7 +45,7 @@ fn we_can_correctly_fetch_the_query_result_schema() {
                 "b",
             ),
         ],
-        TableExpr { table_ref },
+        TableExpr::new(table_ref),
     );
     let column_fields: Vec<ColumnField> = provable_ast.get_column_result_fields();
     assert_eq!(
@@ -81,9 +81,7 @@ fn we_can_correctly_fetch_all_the_referenced_columns() {
                 "f",
             ),
         ],
-        TableExpr {
-            table_ref: table_ref.clone(),
-        },
+        TableExpr::new(table_ref.clone()),
     );
 
     let ref_columns = provable_ast.get_column_references();
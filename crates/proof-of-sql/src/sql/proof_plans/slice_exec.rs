@@ -45,7 +45,6 @@ fn get_slice_select(num_rows: usize, skip: usize, fetch: Option<usize>) -> Vec<b
 
 impl SliceExec {
     /// Creates a new slice execution plan.
-    #[allow(dead_code)]
     pub fn new(input: Box<DynProofPlan>, skip: usize, fetch: Option<usize>) -> Self {
         Self { input, skip, fetch }
     }
@@ -122,7 +121,7 @@ impl ProverEvaluate for SliceExec {
         // 1. columns
         let input = self.input.first_round_evaluate(builder, alloc, table_map);
         let input_length = input.num_rows();
-        let columns = input.columns().copied().collect::<Vec<_>>();
+        let columns = input.column_components();
         // 2. select
         let select = get_slice_select(input_length, self.skip, self.fetch);
         // The selected range is (offset_index, max_index]
@@ -135,12 +134,11 @@ impl ProverEvaluate for SliceExec {
         let output_length = max_index - offset_index;
         // Compute filtered_columns
         let (filtered_columns, _) = filter_columns(alloc, &columns, &select);
-        let res = Table::<'a, S>::try_from_iter_with_options(
-            self.get_column_result_fields()
-                .into_iter()
-                .map(|expr| expr.name())
-                .zip(filtered_columns),
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            filtered_columns,
             TableOptions::new(Some(output_length)),
+            alloc,
         )
         .expect("Failed to create table from iterator");
         builder.request_post_result_challenges(2);
@@ -165,7 +163,7 @@ impl ProverEvaluate for SliceExec {
 
         // 1. columns
         let input = self.input.final_round_evaluate(builder, alloc, table_map);
-        let columns = input.columns().copied().collect::<Vec<_>>();
+        let columns = input.column_components();
         // 2. select
         let select = get_slice_select(input.num_rows(), self.skip, self.fetch);
         let select_ref: &'a [_] = alloc.alloc_slice_copy(&select);
@@ -190,12 +188,11 @@ impl ProverEvaluate for SliceExec {
             input.num_rows(),
             result_len,
         );
-        let res = Table::<'a, S>::try_from_iter_with_options(
-            self.get_column_result_fields()
-                .into_iter()
-                .map(|expr| expr.name())
-                .zip(filtered_columns),
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            filtered_columns,
             TableOptions::new(Some(output_length)),
+            alloc,
         )
         .expect("Failed to create table from iterator");
 
@@ -525,7 +525,39 @@ fn we_can_create_and_prove_a_slice_exec_on_top_of_an_empty_exec() {
 }
 
 #[test]
-fn we_cannot_prove_a_slice_exec_if_it_has_groupby_as_input_for_now() {
+fn we_can_prove_a_slice_exec_with_a_groupby_with_distinct_groups_as_input() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3]),
+        bigint("b", [99, 99, 99, 99, 0, 99]),
+        bigint("c", [101, 102, 103, 104, 105, 106]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = slice_exec(
+        group_by(
+            cols_expr_plan(&t, &["a"], &accessor),
+            vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+            "__count__",
+            tab(&t),
+            equal(column(&t, "b", &accessor), const_int128(99)),
+        ),
+        1,
+        None,
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [2, 3]),
+        bigint("sum_c", [205, 106]),
+        bigint("__count__", [2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_a_slice_exec_if_it_has_a_groupby_with_multiple_columns_as_input_for_now() {
     let data = owned_table([
         bigint("a", [1, 2, 2, 1, 2]),
         bigint("b", [99, 99, 99, 99, 0]),
@@ -536,7 +568,7 @@ fn we_cannot_prove_a_slice_exec_if_it_has_groupby_as_input_for_now() {
     accessor.add_table(t.clone(), data, 0);
     let expr = slice_exec(
         group_by(
-            cols_expr(&t, &["a"], &accessor),
+            cols_expr_plan(&t, &["a", "b"], &accessor),
             vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
             "__count__",
             tab(&t),
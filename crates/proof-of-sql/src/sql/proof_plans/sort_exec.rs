@@ -0,0 +1,324 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_column_by_index,
+            order_by_util::{compare_indexes_by_columns, OrderIndexDirectionPairs},
+            Column, ColumnComponent, ColumnField, ColumnRef, ColumnType, OwnedTable, Table,
+            TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, final_round_evaluate_permutation_check,
+            first_round_evaluate_monotonic, verify_monotonic, verify_permutation_check,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use itertools::repeat_n;
+use serde::{Deserialize, Serialize};
+
+/// The largest number of bits that the sort key of a [`SortExec`] can take.
+///
+/// The differences of consecutive keys have to be small enough for their signs to be proven.
+const MAX_SORT_KEY_BITS: usize = 248;
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> ORDER BY <order_by_index1> [ASC | DESC], ..., <order_by_indexN> [ASC | DESC]
+/// ```
+///
+/// The result is proven to be a permutation of the input that is ordered by the sort key,
+/// a single scalar per row that orders the rows lexicographically by the columns that they are ordered by.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SortExec {
+    pub(super) input: Box<DynProofPlan>,
+    pub(super) order_by: OrderIndexDirectionPairs,
+}
+
+/// The number of bits that the values of a column type fit in,
+/// if the type can be ordered by in a proof.
+fn sort_key_bits(column_type: ColumnType) -> Option<usize> {
+    match column_type {
+        ColumnType::Boolean => Some(1),
+        ColumnType::Uint8 | ColumnType::TinyInt => Some(8),
+        ColumnType::SmallInt => Some(16),
+        ColumnType::Int => Some(32),
+        ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => Some(64),
+        ColumnType::Int128 => Some(128),
+        _ => None,
+    }
+}
+
+impl SortExec {
+    /// Creates a new sort execution plan.
+    pub fn new(input: Box<DynProofPlan>, order_by: OrderIndexDirectionPairs) -> Self {
+        Self { input, order_by }
+    }
+
+    /// Whether the result of `input` can be proven to be ordered by `order_by`.
+    ///
+    /// This is the case if the columns that it is ordered by are of integer, boolean or timestamp types
+    /// and their sort key is small enough.
+    /// The NULLs of a nullable column hold the default value of its type,
+    /// so the result can not be proven to be ordered by a nullable column.
+    #[must_use]
+    pub fn is_provable(input: &DynProofPlan, order_by: &[(usize, bool)]) -> bool {
+        let fields = input.get_column_result_fields();
+        let is_nullable = |field: &ColumnField| {
+            fields.iter().any(|other| {
+                other.component() == ColumnComponent::Presence && other.name() == field.name()
+            })
+        };
+        order_by
+            .iter()
+            .all(|&(index, _)| fields.get(index).is_some_and(|field| !is_nullable(field)))
+            && sort_key_bit_widths(&fields, order_by).is_some()
+    }
+
+    /// The weights of the columns that the result is ordered by in the sort key,
+    /// which are negative for descending orders.
+    fn sort_key_weights<S: Scalar>(&self) -> Option<Vec<S>> {
+        sort_key_weights(&self.input.get_column_result_fields(), &self.order_by)
+    }
+}
+
+/// The weights of the columns that rows with the fields `fields` are ordered by in the sort key,
+/// which are negative for descending orders, if the rows can be proven to be ordered by them.
+pub(super) fn sort_key_weights<S: Scalar>(
+    fields: &[ColumnField],
+    order_by: &[(usize, bool)],
+) -> Option<Vec<S>> {
+    let bit_widths = sort_key_bit_widths(fields, order_by)?;
+    // The weight of a column exceeds the range of the sort key of the columns after it,
+    // so that the sort key orders the rows lexicographically.
+    let mut weight = S::ONE;
+    let mut weights = order_by
+        .iter()
+        .zip(bit_widths)
+        .rev()
+        .map(|(&(_, asc), bits)| {
+            let signed_weight = if asc { weight } else { -weight };
+            weight *= repeat_n(S::TWO, bits).product::<S>();
+            signed_weight
+        })
+        .collect::<Vec<_>>();
+    weights.reverse();
+    Some(weights)
+}
+
+/// The number of bits of each column that the result is ordered by,
+/// if the result can be proven to be ordered by them.
+pub(super) fn sort_key_bit_widths(
+    fields: &[ColumnField],
+    order_by: &[(usize, bool)],
+) -> Option<Vec<usize>> {
+    let bit_widths = order_by
+        .iter()
+        .map(|&(index, _)| sort_key_bits(fields.get(index)?.data_type()))
+        .collect::<Option<Vec<_>>>()?;
+    (!bit_widths.is_empty() && bit_widths.iter().sum::<usize>() <= MAX_SORT_KEY_BITS)
+        .then_some(bit_widths)
+}
+
+/// The permutation of the rows of `columns` that orders them by `order_by`
+pub(super) fn sort_indexes<S: Scalar>(
+    columns: &[Column<S>],
+    order_by: &[(usize, bool)],
+) -> Vec<usize> {
+    let num_rows = columns.first().map_or(0, Column::len);
+    let mut indexes = (0..num_rows).collect::<Vec<_>>();
+    indexes.sort_by(|&i, &j| {
+        order_by
+            .iter()
+            .map(|&(index, asc)| {
+                let ordering = compare_indexes_by_columns(&columns[index..=index], i, j);
+                if asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            })
+            .find(|&ordering| ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    indexes
+}
+
+/// The sort key of each row of `columns` given the weights returned by [`sort_key_weights`]
+pub(super) fn compute_sort_key<'a, S: Scalar>(
+    alloc: &'a Bump,
+    columns: &[Column<'a, S>],
+    order_by: &[(usize, bool)],
+    weights: &[S],
+) -> &'a [S] {
+    let num_rows = columns.first().map_or(0, Column::len);
+    let sort_key = alloc.alloc_slice_fill_copy(num_rows, S::ZERO);
+    for (&(index, _), &weight) in order_by.iter().zip(weights) {
+        for (key, value) in sort_key
+            .iter_mut()
+            .zip(columns[index].to_scalar_with_scaling(0))
+        {
+            *key += weight * value;
+        }
+    }
+    sort_key
+}
+
+impl ProofPlan for SortExec
+where
+    SortExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let input_table_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let chi_eval = input_table_eval.chi_eval();
+        let columns_evals = input_table_eval.column_evals();
+        // 2. sorted columns
+        let sorted_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(columns_evals.len())?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 3. The sorted columns are a permutation of the columns
+        verify_permutation_check(
+            builder,
+            alpha,
+            beta,
+            chi_eval,
+            columns_evals,
+            &sorted_columns_evals,
+        )?;
+        // 4. The sorted columns are ordered
+        let weights = self
+            .sort_key_weights::<S>()
+            .ok_or(ProofError::UnsupportedQueryPlan {
+                error: "SortExec can only order by integer, boolean and timestamp columns",
+            })?;
+        let sort_key_eval = self
+            .order_by
+            .iter()
+            .zip(weights)
+            .map(|(&(index, _), weight)| weight * sorted_columns_evals[index])
+            .sum();
+        verify_monotonic::<S, false, true>(builder, alpha, beta, sort_key_eval, chi_eval)?;
+        Ok(TableEvaluation::new(sorted_columns_evals, chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for SortExec {
+    #[tracing::instrument(name = "SortExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let columns = input.column_components();
+        // 2. sorted columns
+        let indexes = sort_indexes(&columns, &self.order_by);
+        let sorted_columns = columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes));
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            sorted_columns,
+            TableOptions::new(Some(input.num_rows())),
+            alloc,
+        )
+        .expect("Failed to create table from iterator");
+        builder.request_post_result_challenges(2);
+        first_round_evaluate_monotonic(builder, input.num_rows());
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "SortExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.column_components();
+        // 2. sorted columns
+        let indexes = sort_indexes(&columns, &self.order_by);
+        let sorted_columns = columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect::<Vec<_>>();
+        sorted_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. The sorted columns are a permutation of the columns
+        let chi = alloc.alloc_slice_fill_copy(num_rows, true);
+        final_round_evaluate_permutation_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi,
+            &columns,
+            &sorted_columns,
+        );
+        // 4. The sorted columns are ordered
+        let weights = self
+            .sort_key_weights::<S>()
+            .expect("SortExec can only order by integer, boolean and timestamp columns");
+        let sort_key = compute_sort_key(alloc, &sorted_columns, &self.order_by, &weights);
+        final_round_evaluate_monotonic::<S, false, true>(builder, alloc, alpha, beta, sort_key);
+        let res = Table::<'a, S>::try_from_components_with_options(
+            &self.get_column_result_fields(),
+            sorted_columns,
+            TableOptions::new(Some(num_rows)),
+            alloc,
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
@@ -0,0 +1,132 @@
+use super::{test_utility::*, SortExec};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_exec() {
+    let data = owned_table([
+        bigint("a", [3_i64, -1, 4, 1, 5, -9]),
+        varchar("b", ["3", "-1", "4", "1", "5", "-9"]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = sort_exec(
+        projection(cols_expr_plan(&t, &["a", "b"], &accessor), tab(&t)),
+        vec![(0, true)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-9_i64, -1, 1, 3, 4, 5]),
+        varchar("b", ["-9", "-1", "1", "3", "4", "5"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_sort_exec_with_a_multi_column_key_and_mixed_directions() {
+    let data = owned_table([
+        boolean("a", [true, false, true, false, true, false]),
+        int("b", [2, 7, -3, 7, 2, -1]),
+        bigint("c", [1_i64, 2, 3, 4, 5, 6]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = sort_exec(
+        projection(cols_expr_plan(&t, &["a", "b", "c"], &accessor), tab(&t)),
+        vec![(0, false), (1, true), (2, false)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("a", [true, true, true, false, false, false]),
+        int("b", [-3, 2, 2, -1, 7, 7]),
+        bigint("c", [3_i64, 5, 1, 6, 4, 2]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_the_top_rows_of_a_filter_with_a_sort_exec_and_a_slice_exec() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7]),
+        int128("revenue", [50_i128, -20, 70, 10, 70, 30, 40]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let where_clause: DynProofExpr = gte(column(&t, "a", &accessor), const_bigint(2));
+    let ast = slice_exec(
+        sort_exec(
+            filter(
+                cols_expr_plan(&t, &["a", "revenue"], &accessor),
+                tab(&t),
+                where_clause,
+            ),
+            vec![(1, false), (0, true)],
+        ),
+        0,
+        Some(3),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [3_i64, 5, 7]),
+        int128("revenue", [70_i128, 70, 40]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_exec() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5]),
+        varchar("b", ["1", "2", "3", "4", "5"]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let where_clause: DynProofExpr = equal(column(&t, "a", &accessor), const_int128(999));
+    let ast = sort_exec(
+        filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            where_clause,
+        ),
+        vec![(0, false)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), varchar("b", [""; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_prove_a_sort_exec_by_unsupported_or_too_wide_keys() {
+    let data = owned_table([
+        varchar("a", ["1", "2"]),
+        int128("b", [1_i128, 2]),
+        int128("c", [1_i128, 2]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let input = projection(cols_expr_plan(&t, &["a", "b", "c"], &accessor), tab(&t));
+    assert!(SortExec::is_provable(&input, &[(1, true)]));
+    assert!(!SortExec::is_provable(&input, &[(0, true)]));
+    assert!(!SortExec::is_provable(&input, &[(1, true), (2, false)]));
+    assert!(!SortExec::is_provable(&input, &[(3, true)]));
+    assert!(!SortExec::is_provable(&input, &[]));
+}
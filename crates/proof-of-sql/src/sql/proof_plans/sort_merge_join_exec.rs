@@ -3,8 +3,8 @@ use crate::{
     base::{
         database::{
             join_util::{
-                apply_sort_merge_join_indexes, get_columns_of_table, get_sort_merge_join_indexes,
-                ordered_set_union,
+                apply_sort_merge_join_indexes, combine_join_key, get_columns_of_table,
+                get_sort_merge_join_indexes, join_key_weights, ordered_set_union,
             },
             slice_operation::apply_slice_to_indexes,
             ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
@@ -37,8 +37,11 @@ use sqlparser::ast::Ident;
 /// `ProofPlan` for queries of the form
 /// ```ignore
 ///     <ProofPlan> INNER JOIN <ProofPlan>
-///     ON col1 = col2
+///     ON col1 = col2 [AND col3 = col4 ...]
 /// ```
+///
+/// A join on multiple columns is proven on a single strictly increasing key that combines them,
+/// which requires them to be booleans, integers or timestamps.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SortMergeJoinExec {
     pub(super) left: Box<DynProofPlan>,
@@ -91,6 +94,19 @@ impl SortMergeJoinExec {
             result_idents,
         }
     }
+
+    /// Get the weights and the offset that combine the columns of `U` into a single strictly increasing column
+    ///
+    /// Returns `None` if the join columns can not be combined, see [`join_key_weights`].
+    fn u_key_weights<S: Scalar>(&self) -> Option<(Vec<S>, S)> {
+        let left_column_fields = self.left.get_column_result_fields();
+        let column_types = self
+            .left_join_column_indexes
+            .iter()
+            .map(|&index| left_column_fields[index].data_type())
+            .collect::<Vec<_>>();
+        join_key_weights(&column_types)
+    }
 }
 
 impl ProofPlan for SortMergeJoinExec
@@ -139,22 +155,28 @@ where
             .copied()
             .collect::<Vec<_>>();
         let num_columns_u = self.left_join_column_indexes.len();
-        if num_columns_u != 1 {
-            return Err(ProofError::VerificationError {
-                error: "Join on multiple columns not supported yet",
-            });
-        }
+        let (u_weights, u_offset) = self.u_key_weights().ok_or(ProofError::VerificationError {
+            error: "Join columns can not be combined into a single key",
+        })?;
         let num_columns_res_hat = num_columns_left + num_columns_right - num_columns_u + 2;
         // `\hat{J}` in the protocol
         let res_hat_column_evals =
             builder.try_consume_final_round_mle_evaluations(num_columns_res_hat)?;
         // 5. First round MLE evaluations: `i` and `U`
-        //TODO: Make it possible for `U` to have multiple columns
         let rho_bar_left_eval = res_hat_column_evals[num_columns_left];
         let rho_bar_right_eval = res_hat_column_evals[num_columns_res_hat - 1];
         let i_eval: S = itertools::repeat_n(S::TWO, 64_usize).product::<S>() * rho_bar_left_eval
             + rho_bar_right_eval;
-        let u_column_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let u_column_evals = (0..num_columns_u)
+            .map(|_| builder.try_consume_first_round_mle_evaluation())
+            .collect::<Result<Vec<_>, _>>()?;
+        // The strictly increasing key of `U`
+        let u_key_eval = u_offset * u_chi_eval
+            + u_column_evals
+                .iter()
+                .zip_eq(&u_weights)
+                .map(|(&u_column_eval, &weight)| weight * u_column_eval)
+                .sum::<S>();
         // 6. Membership checks
         let hat_left_column_indexes = self
             .left_join_column_indexes
@@ -208,19 +230,13 @@ where
         let right_join_column_evals =
             apply_slice_to_indexes(&right_hat_column_evals, &self.right_join_column_indexes)
                 .expect("Indexes can not be out of bounds");
-        //TODO: Relax to allow multiple columns
-        if left_join_column_evals.len() != 1 || right_join_column_evals.len() != 1 {
-            return Err(ProofError::VerificationError {
-                error: "Left and right join columns should have exactly one column",
-            });
-        }
         let w_l_eval = verify_membership_check(
             builder,
             alpha,
             beta,
             u_chi_eval,
             left_chi_eval,
-            &[u_column_eval],
+            &u_column_evals,
             &left_join_column_evals,
         )?;
         let w_r_eval = verify_membership_check(
@@ -229,12 +245,12 @@ where
             beta,
             u_chi_eval,
             right_chi_eval,
-            &[u_column_eval],
+            &u_column_evals,
             &right_join_column_evals,
         )?;
         // 7. Monotonicity checks
         verify_monotonic::<S, true, true>(builder, alpha, beta, i_eval, res_chi_eval)?;
-        verify_monotonic::<S, true, true>(builder, alpha, beta, u_column_eval, u_chi_eval)?;
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_key_eval, u_chi_eval)?;
         // 8. Prove that sum w_l * w_r = chi_m
         // sum w_l * w_r - chi_m = 0
         builder.try_produce_sumcheck_subpolynomial_evaluation(
@@ -350,14 +366,11 @@ impl ProverEvaluate for SortMergeJoinExec {
         // ordered set union `U`
         let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
         let num_columns_u = u.len();
-        assert!(
-            (num_columns_u == 1),
-            "Join on multiple columns not supported yet"
-        );
-        let u_0 = u[0].to_scalar_with_scaling(0);
         let num_rows_u = u[0].len();
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
-        builder.produce_intermediate_mle(alloc_u_0 as &[_]);
+        for u_column in &u {
+            let alloc_u_column = alloc.alloc_slice_copy(&u_column.to_scalar_with_scaling(0));
+            builder.produce_intermediate_mle(alloc_u_column as &[_]);
+        }
         // 3. Chi eval and rho eval
         builder.produce_chi_evaluation_length(num_rows_res);
         builder.produce_chi_evaluation_length(num_rows_u);
@@ -485,15 +498,13 @@ impl ProverEvaluate for SortMergeJoinExec {
         // ordered set union `U`
         let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
         let num_columns_u = u.len();
-        assert!(
-            (num_columns_u == 1),
-            "Join on multiple columns not supported yet"
-        );
-        let u_0 = u[0].to_scalar_with_scaling(0);
         let num_rows_u = u[0].len();
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
         let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true);
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
+        // The strictly increasing key of `U`
+        let (u_weights, u_offset) = self
+            .u_key_weights()
+            .expect("Join columns can not be combined into a single key");
+        let u_key = combine_join_key(&u, &u_weights, u_offset, alloc);
 
         // 3. Get post-result challenges
         let alpha = builder.consume_post_result_challenge();
@@ -561,7 +572,7 @@ impl ProverEvaluate for SortMergeJoinExec {
 
         // 6. Monotonicity checks
         final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_i);
-        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_u_0);
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, u_key);
 
         // 7. Prove that sum w_l * w_r = chi_m
         // sum w_l * w_r - chi_m = 0
@@ -68,6 +68,113 @@ fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_join() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_join_on_multiple_columns() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 1, 2, 2, 3], &alloc),
+        borrowed_smallint("kind", [1_i16, 2, 1, 2, -1], &alloc),
+        borrowed_varchar(
+            "name",
+            ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper"],
+            &alloc,
+        ),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_smallint("kind", [2_i16, 1, 1, -1, 2, 2], &alloc),
+        borrowed_bigint("id", [1_i64, 2, 1, 3, 1, 3], &alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Gretta", "Ian", "Erik", "Ian", "Zoe"],
+            &alloc,
+        ),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = sort_merge_join(
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("kind", ColumnType::SmallInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("kind", ColumnType::SmallInt),
+                column_field("id", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+            ],
+        ),
+        vec![0, 1],
+        vec![1, 0],
+        vec![
+            Ident::new("id"),
+            Ident::new("kind"),
+            Ident::new("name"),
+            Ident::new("human"),
+        ],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 1, 1, 2, 3]),
+        smallint("kind", [1_i16, 2, 2, 1, -1]),
+        varchar(
+            "name",
+            ["Chloe", "Margaret", "Margaret", "Prudence", "Pepper"],
+        ),
+        varchar("human", ["Ian", "Cassia", "Ian", "Gretta", "Erik"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+#[should_panic(expected = "Join columns can not be combined into a single key")]
+fn we_cannot_prove_a_sort_merge_join_on_multiple_columns_that_can_not_be_combined() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2], &alloc),
+        borrowed_varchar("name", ["Chloe", "Margaret"], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [1_i64, 2], &alloc),
+        borrowed_varchar("name", ["Chloe", "Lucy"], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = sort_merge_join(
+        table_exec(
+            table_left,
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        table_exec(
+            table_right,
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        vec![0, 1],
+        vec![0, 1],
+        vec![Ident::new("id"), Ident::new("name")],
+    );
+    VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+}
+
 #[test]
 fn we_can_prove_and_get_the_correct_result_from_a_complex_query_involving_sort_merge_join() {
     let alloc = Bump::new();
@@ -0,0 +1,837 @@
+use super::{
+    union_exec::{prove_union, verify_union},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes,
+            join_util::{
+                apply_sort_merge_join_indexes, combine_join_key, get_columns_of_table,
+                get_default_column, get_default_scalar, get_sort_merge_join_indexes,
+                join_key_weights, ordered_set_union,
+            },
+            slice_operation::apply_slice_to_indexes,
+            union_util::column_union,
+            Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{prover_evaluate_equals_zero, verifier_evaluate_equals_zero},
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_monotonic,
+            first_round_evaluate_membership_check, first_round_evaluate_monotonic,
+            verify_membership_check, verify_monotonic,
+        },
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::{
+    collections::{CollectIn, Vec as BumpVec},
+    Bump,
+};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> LEFT OUTER JOIN <ProofPlan>
+///     ON col1 = col2 [AND col3 = col4 ...]
+/// ```
+///
+/// A join on multiple columns is proven on a single strictly increasing key that combines them,
+/// which requires them to be booleans, integers or timestamps.
+///
+/// A `RIGHT OUTER JOIN` is a `LEFT OUTER JOIN` with the two inputs swapped.
+///
+/// The result consists of the rows of the inner join `J` followed by the rows `M` of the left input
+/// which have no match in the right input. The columns of the right input are padded with
+/// default values in `M`. The last column of the result is `true` for the rows of `J`
+/// and `false` for the rows of `M`.
+///
+/// On top of the proof of the inner join done in [`super::SortMergeJoinExec`] we prove that
+/// 1. `M` consists of distinct rows of the left input, i.e. `rho` of `M` is strictly increasing
+///    and `M` is a subset of the left input.
+/// 2. For every value `u` of the ordered set union `U` of the join columns the multiplicity
+///    `w_m` of `u` in `M` is `w_l * z` where `w_l` is the multiplicity of `u` in the left input
+///    and `z` indicates whether the multiplicity `w_r` of `u` in the right input is zero.
+/// 3. The result is the union of `J` and the padded `M`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SortMergeOuterJoinExec {
+    pub(super) left: Box<DynProofPlan>,
+    pub(super) right: Box<DynProofPlan>,
+    // `j_l` in the protocol
+    pub(super) left_join_column_indexes: Vec<usize>,
+    // `j_r` in the protocol
+    pub(super) right_join_column_indexes: Vec<usize>,
+    pub(super) result_idents: Vec<Ident>,
+}
+
+impl SortMergeOuterJoinExec {
+    /// Create a new `SortMergeOuterJoinExec` with the given left and right plans
+    ///
+    /// The left plan is the one whose unmatched rows are kept.
+    /// The last of the result idents is the one of the column indicating whether a row has a match.
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - The join column index is out of bounds
+    /// - The number of join columns is different
+    /// - The number of result idents is different from the expected number of columns
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        result_idents: Vec<Ident>,
+    ) -> Self {
+        let num_columns_left = left.get_column_result_fields().len();
+        let num_columns_right = right.get_column_result_fields().len();
+        let max_left_join_column_index = left_join_column_indexes.iter().max().unwrap_or(&0);
+        let max_right_join_column_index = right_join_column_indexes.iter().max().unwrap_or(&0);
+        if *max_left_join_column_index >= num_columns_left
+            || *max_right_join_column_index >= num_columns_right
+        {
+            panic!("Join column index out of bounds");
+        }
+        let num_join_columns = left_join_column_indexes.len();
+        assert!(
+            (num_join_columns == right_join_column_indexes.len()),
+            "Join columns should have the same number of columns"
+        );
+        assert!(
+            (result_idents.len() == num_columns_left + num_columns_right - num_join_columns + 1),
+            "The amount of result idents should be the same as the expected number of columns"
+        );
+        Self {
+            left,
+            right,
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+        }
+    }
+
+    /// The types of the columns of the right input which are not join columns
+    fn right_other_column_types(&self) -> Vec<ColumnType> {
+        self.right
+            .get_column_result_fields()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.right_join_column_indexes.contains(i))
+            .map(|(_, field)| field.data_type())
+            .collect()
+    }
+
+    /// Get the weights and the offset that combine the columns of `U` into a single strictly increasing column
+    ///
+    /// Returns `None` if the join columns can not be combined, see [`join_key_weights`].
+    fn u_key_weights<S: Scalar>(&self) -> Option<(Vec<S>, S)> {
+        let left_column_fields = self.left.get_column_result_fields();
+        let column_types = self
+            .left_join_column_indexes
+            .iter()
+            .map(|&index| left_column_fields[index].data_type())
+            .collect::<Vec<_>>();
+        join_key_weights(&column_types)
+    }
+}
+
+impl ProofPlan for SortMergeOuterJoinExec
+where
+    SortMergeOuterJoinExec: ProverEvaluate,
+{
+    #[allow(clippy::too_many_lines, clippy::similar_names)]
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let left_eval = self
+            .left
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let right_eval = self
+            .right
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        // 2. Chi evals and rho evals
+        let left_chi_eval = left_eval.chi_eval();
+        let right_chi_eval = right_eval.chi_eval();
+        let res_chi_eval = builder.try_consume_chi_evaluation()?;
+        let u_chi_eval = builder.try_consume_chi_evaluation()?;
+        let unmatched_chi_eval = builder.try_consume_chi_evaluation()?;
+        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        let left_rho_eval = builder.try_consume_rho_evaluation()?;
+        let right_rho_eval = builder.try_consume_rho_evaluation()?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. column evals
+        let left_column_evals = left_eval.column_evals();
+        let right_column_evals = right_eval.column_evals();
+        let num_columns_left = left_column_evals.len();
+        let num_columns_right = right_column_evals.len();
+        let left_hat_column_evals = left_column_evals
+            .iter()
+            .chain(core::iter::once(&left_rho_eval))
+            .copied()
+            .collect::<Vec<_>>();
+        let right_hat_column_evals = right_column_evals
+            .iter()
+            .chain(core::iter::once(&right_rho_eval))
+            .copied()
+            .collect::<Vec<_>>();
+        let num_columns_u = self.left_join_column_indexes.len();
+        let (u_weights, u_offset) = self.u_key_weights().ok_or(ProofError::VerificationError {
+            error: "Join columns can not be combined into a single key",
+        })?;
+        let num_columns_res_hat = num_columns_left + num_columns_right - num_columns_u + 2;
+        // `\hat{J}` in the protocol
+        let res_hat_column_evals =
+            builder.try_consume_final_round_mle_evaluations(num_columns_res_hat)?;
+        // 5. First round MLE evaluations: `i` and `U`
+        let rho_bar_left_eval = res_hat_column_evals[num_columns_left];
+        let rho_bar_right_eval = res_hat_column_evals[num_columns_res_hat - 1];
+        let i_eval: S = itertools::repeat_n(S::TWO, 64_usize).product::<S>() * rho_bar_left_eval
+            + rho_bar_right_eval;
+        let u_column_evals = (0..num_columns_u)
+            .map(|_| builder.try_consume_first_round_mle_evaluation())
+            .collect::<Result<Vec<_>, _>>()?;
+        // The strictly increasing key of `U`
+        let u_key_eval = u_offset * u_chi_eval
+            + u_column_evals
+                .iter()
+                .zip_eq(&u_weights)
+                .map(|(&u_column_eval, &weight)| weight * u_column_eval)
+                .sum::<S>();
+        // 6. Membership checks
+        let hat_left_column_indexes = self
+            .left_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_left).filter(|i| !self.left_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_right_column_indexes = self
+            .right_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_right).filter(|i| !self.right_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_left_column_evals =
+            apply_slice_to_indexes(&left_hat_column_evals, &hat_left_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let hat_right_column_evals =
+            apply_slice_to_indexes(&right_hat_column_evals, &hat_right_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let res_left_column_indexes = (0..=num_columns_left).collect::<Vec<_>>();
+        let res_right_column_indexes = (0..num_columns_u)
+            .chain(num_columns_left + 1..num_columns_res_hat)
+            .collect::<Vec<_>>();
+        let res_left_column_evals =
+            apply_slice_to_indexes(&res_hat_column_evals, &res_left_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let res_right_column_evals =
+            apply_slice_to_indexes(&res_hat_column_evals, &res_right_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            left_chi_eval,
+            res_chi_eval,
+            &hat_left_column_evals,
+            &res_left_column_evals,
+        )?;
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            right_chi_eval,
+            res_chi_eval,
+            &hat_right_column_evals,
+            &res_right_column_evals,
+        )?;
+        let left_join_column_evals =
+            apply_slice_to_indexes(&left_hat_column_evals, &self.left_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let right_join_column_evals =
+            apply_slice_to_indexes(&right_hat_column_evals, &self.right_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let w_l_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            left_chi_eval,
+            &u_column_evals,
+            &left_join_column_evals,
+        )?;
+        let w_r_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            right_chi_eval,
+            &u_column_evals,
+            &right_join_column_evals,
+        )?;
+        // 7. Monotonicity checks
+        verify_monotonic::<S, true, true>(builder, alpha, beta, i_eval, res_chi_eval)?;
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_key_eval, u_chi_eval)?;
+        // 8. Prove that sum w_l * w_r = chi_m
+        // sum w_l * w_r - chi_m = 0
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::ZeroSum,
+            w_l_eval * w_r_eval - res_chi_eval,
+            2,
+        )?;
+        // 9. The unmatched rows of the left input, `\hat{M}` in the protocol
+        let unmatched_hat_column_evals =
+            builder.try_consume_final_round_mle_evaluations(num_columns_left + 1)?;
+        verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            left_chi_eval,
+            unmatched_chi_eval,
+            &hat_left_column_evals,
+            &unmatched_hat_column_evals,
+        )?;
+        let w_m_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            unmatched_chi_eval,
+            &u_column_evals,
+            &unmatched_hat_column_evals[..num_columns_u],
+        )?;
+        // 10. Prove that w_m = w_l * z where z = (w_r == 0)
+        let z_eval = verifier_evaluate_equals_zero(builder, w_r_eval, u_chi_eval)?;
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            w_m_eval - w_l_eval * z_eval,
+            2,
+        )?;
+        // 11. Prove that the rows of `\hat{M}` are distinct
+        verify_monotonic::<S, true, true>(
+            builder,
+            alpha,
+            beta,
+            unmatched_hat_column_evals[num_columns_left],
+            unmatched_chi_eval,
+        )?;
+        // 12. Prove that the result is the union of `J` and the padded `M`
+        // Drop the two rho columns of `\hat{J}` to get `J`
+        let res_column_indexes = (0..num_columns_left)
+            .chain(num_columns_left + 1..num_columns_left + 1 + num_columns_right - num_columns_u)
+            .collect::<Vec<_>>();
+        let res_column_evals = apply_slice_to_indexes(&res_hat_column_evals, &res_column_indexes)
+            .expect("Indexes can not be out of bounds")
+            .into_iter()
+            .chain(core::iter::once(res_chi_eval))
+            .collect::<Vec<_>>();
+        let padded_unmatched_column_evals = unmatched_hat_column_evals[..num_columns_left]
+            .iter()
+            .copied()
+            .chain(
+                self.right_other_column_types()
+                    .into_iter()
+                    .map(|column_type| get_default_scalar::<S>(column_type) * unmatched_chi_eval),
+            )
+            .chain(core::iter::once(S::ZERO))
+            .collect::<Vec<_>>();
+        let output_column_evals =
+            builder.try_consume_final_round_mle_evaluations(self.result_idents.len())?;
+        verify_union(
+            builder,
+            alpha,
+            beta,
+            &[&res_column_evals, &padded_unmatched_column_evals],
+            &output_column_evals,
+            &[res_chi_eval, unmatched_chi_eval],
+            output_chi_eval,
+        )?;
+        Ok(TableEvaluation::new(output_column_evals, output_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let left_other_column_indexes = (0..self.left.get_column_result_fields().len())
+            .filter(|i| !self.left_join_column_indexes.contains(i))
+            .collect::<Vec<_>>();
+        let left_join_column_fields = apply_slice_to_indexes(
+            &self.left.get_column_result_fields(),
+            &self.left_join_column_indexes,
+        )
+        .expect("Indexes can not be out of bounds");
+        let left_other_column_fields = apply_slice_to_indexes(
+            &self.left.get_column_result_fields(),
+            &left_other_column_indexes,
+        )
+        .expect("Indexes can not be out of bounds");
+        let column_types = left_join_column_fields
+            .iter()
+            .chain(left_other_column_fields.iter())
+            .map(ColumnField::data_type)
+            .chain(self.right_other_column_types())
+            .chain(core::iter::once(ColumnType::Boolean))
+            .collect::<Vec<_>>();
+        self.result_idents
+            .iter()
+            .zip_eq(column_types)
+            .map(|(ident, column_type)| ColumnField::new(ident.clone(), column_type))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.left
+            .get_column_references()
+            .into_iter()
+            .chain(self.right.get_column_references())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.left
+            .get_table_references()
+            .into_iter()
+            .chain(self.right.get_table_references())
+            .collect()
+    }
+}
+
+impl ProverEvaluate for SortMergeOuterJoinExec {
+    #[tracing::instrument(
+        name = "SortMergeOuterJoinExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        let left = self.left.first_round_evaluate(builder, alloc, table_map);
+        let right = self.right.first_round_evaluate(builder, alloc, table_map);
+        let num_rows_left = left.num_rows();
+        let num_rows_right = right.num_rows();
+        let num_columns_left = left.num_columns();
+        let num_columns_right = right.num_columns();
+        let left_hat = left.add_rho_column(alloc);
+        let right_hat = right.add_rho_column(alloc);
+        let c_l = get_columns_of_table(&left_hat, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right_hat, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        // 1. Conduct the join
+        let (left_row_indexes, right_row_indexes): (Vec<usize>, Vec<usize>) =
+            get_sort_merge_join_indexes(&c_l, &c_r, num_rows_left, num_rows_right)
+                .iter()
+                .copied()
+                .unzip();
+        // `\hat{J}` in the protocol
+        let res_hat = apply_sort_merge_join_indexes(
+            &left_hat,
+            &right_hat,
+            &self.left_join_column_indexes,
+            &self.right_join_column_indexes,
+            &left_row_indexes,
+            &right_row_indexes,
+            alloc,
+        )
+        .expect("Can not do sort merge join");
+        let num_rows_res = left_row_indexes.len();
+        // 2. Get and commit the strictly increasing columns, `U`
+        // ordered set union `U`
+        let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
+        let num_columns_u = u.len();
+        let num_rows_u = u[0].len();
+        for u_column in &u {
+            let alloc_u_column = alloc.alloc_slice_copy(&u_column.to_scalar_with_scaling(0));
+            builder.produce_intermediate_mle(alloc_u_column as &[_]);
+        }
+        // 3. Get the unmatched rows of the left input, `\hat{M}`
+        let hat_left_column_indexes = self
+            .left_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_left).filter(|i| !self.left_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_right_column_indexes = self
+            .right_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_right).filter(|i| !self.right_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_left_columns = get_columns_of_table(&left_hat, &hat_left_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let hat_right_columns = get_columns_of_table(&right_hat, &hat_right_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let unmatched_row_indexes = get_unmatched_row_indexes(num_rows_left, &left_row_indexes);
+        let unmatched_hat =
+            apply_columns_to_indexes(&hat_left_columns, &unmatched_row_indexes, alloc);
+        let num_rows_unmatched = unmatched_row_indexes.len();
+        // 4. Chi eval and rho eval
+        builder.produce_chi_evaluation_length(num_rows_res);
+        builder.produce_chi_evaluation_length(num_rows_u);
+        builder.produce_chi_evaluation_length(num_rows_unmatched);
+        builder.produce_chi_evaluation_length(num_rows_res + num_rows_unmatched);
+        builder.produce_rho_evaluation_length(num_rows_left);
+        builder.produce_rho_evaluation_length(num_rows_right);
+        // 5. Membership checks
+        // `J_l` in the protocol
+        let res_left_columns = res_hat[0..=num_columns_left].to_vec();
+        // `J_r` in the protocol
+        let res_right_columns: Vec<_> = res_hat[0..num_columns_u]
+            .iter()
+            .chain(&res_hat[num_columns_left + 1..])
+            .copied()
+            .collect();
+        first_round_evaluate_membership_check(builder, alloc, &hat_left_columns, &res_left_columns);
+        first_round_evaluate_membership_check(
+            builder,
+            alloc,
+            &hat_right_columns,
+            &res_right_columns,
+        );
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_l);
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_r);
+        first_round_evaluate_membership_check(builder, alloc, &hat_left_columns, &unmatched_hat);
+        first_round_evaluate_membership_check(builder, alloc, &u, &unmatched_hat[..num_columns_u]);
+        // 6. Monotonicity checks
+        first_round_evaluate_monotonic(builder, num_rows_res);
+        first_round_evaluate_monotonic(builder, num_rows_u);
+        first_round_evaluate_monotonic(builder, num_rows_unmatched);
+        // 7. Request post-result challenges
+        builder.request_post_result_challenges(2);
+        // 8. Return the result
+        let (res_columns, padded_unmatched_columns) =
+            self.get_union_inputs(&res_hat, &unmatched_hat, num_columns_left, alloc);
+        self.get_output_table(&res_columns, &padded_unmatched_columns, alloc)
+    }
+
+    #[tracing::instrument(
+        name = "SortMergeOuterJoinExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    #[allow(clippy::too_many_lines)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        let left = self.left.final_round_evaluate(builder, alloc, table_map);
+        let right = self.right.final_round_evaluate(builder, alloc, table_map);
+        let num_rows_left = left.num_rows();
+        let num_rows_right = right.num_rows();
+        let num_columns_left = left.num_columns();
+        let num_columns_right = right.num_columns();
+
+        let chi_m_l = alloc.alloc_slice_fill_copy(num_rows_left, true);
+        let chi_m_r = alloc.alloc_slice_fill_copy(num_rows_right, true);
+
+        let left_hat = left.add_rho_column(alloc);
+        let right_hat = right.add_rho_column(alloc);
+
+        let c_l = get_columns_of_table(&left_hat, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right_hat, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+
+        // 1. Conduct the join
+        let (left_row_indexes, right_row_indexes): (Vec<usize>, Vec<usize>) =
+            get_sort_merge_join_indexes(&c_l, &c_r, num_rows_left, num_rows_right)
+                .iter()
+                .copied()
+                .unzip();
+        let raw_res_hat = apply_sort_merge_join_indexes(
+            &left_hat,
+            &right_hat,
+            &self.left_join_column_indexes,
+            &self.right_join_column_indexes,
+            &left_row_indexes,
+            &right_row_indexes,
+            alloc,
+        )
+        .expect("Can not do sort merge join");
+        // Store in bump, `\hat{J}` in the protocol
+        let res_hat = alloc.alloc_slice_copy(raw_res_hat.as_slice());
+
+        let num_rows_res = left_row_indexes.len();
+        let chi_res = alloc.alloc_slice_fill_copy(num_rows_res, true);
+
+        // 2. Get the strictly increasing columns, `i` and `u`
+        // i = left_row_index * 2^64 + right_row_index
+        // which is strictly increasing
+        let i = left_row_indexes
+            .iter()
+            .zip_eq(right_row_indexes.iter())
+            .map(|(l, r)| S::from(*l as u64) * S::TWO_POW_64 + S::from(*r as u64))
+            .collect::<Vec<_>>();
+        let alloc_i = alloc.alloc_slice_copy(i.as_slice());
+
+        // ordered set union `U`
+        let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
+        let num_columns_u = u.len();
+        let num_rows_u = u[0].len();
+        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true);
+        // The strictly increasing key of `U`
+        let (u_weights, u_offset) = self
+            .u_key_weights()
+            .expect("Join columns can not be combined into a single key");
+        let u_key = combine_join_key(&u, &u_weights, u_offset, alloc);
+
+        // 3. Get the unmatched rows of the left input, `\hat{M}`
+        let hat_left_column_indexes = self
+            .left_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_left).filter(|i| !self.left_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_right_column_indexes = self
+            .right_join_column_indexes
+            .iter()
+            .copied()
+            .chain((0..=num_columns_right).filter(|i| !self.right_join_column_indexes.contains(i)))
+            .collect::<Vec<_>>();
+        let hat_left_columns = get_columns_of_table(&left_hat, &hat_left_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let hat_right_columns = get_columns_of_table(&right_hat, &hat_right_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let unmatched_row_indexes = get_unmatched_row_indexes(num_rows_left, &left_row_indexes);
+        let unmatched_hat =
+            apply_columns_to_indexes(&hat_left_columns, &unmatched_row_indexes, alloc);
+        let num_rows_unmatched = unmatched_row_indexes.len();
+        let chi_unmatched = alloc.alloc_slice_fill_copy(num_rows_unmatched, true);
+        let unmatched_rho = alloc.alloc_slice_fill_with(num_rows_unmatched, |i| {
+            S::from(unmatched_row_indexes[i] as u64)
+        });
+
+        // 4. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 5. Produce MLEs for `res_hat`
+        let alloc_res_hat = res_hat.iter().collect_in::<BumpVec<_>>(alloc);
+        for column in &alloc_res_hat {
+            builder.produce_intermediate_mle(*column);
+        }
+
+        // 6. Membership checks
+        let res_left_columns = res_hat[0..=num_columns_left].to_vec();
+        let res_right_columns: Vec<_> = res_hat[0..num_columns_u] // rho col is right after left columns
+            .iter()
+            .chain(&res_hat[num_columns_left + 1..])
+            .copied()
+            .collect();
+
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m_l,
+            chi_res,
+            &hat_left_columns,
+            &res_left_columns,
+        );
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m_r,
+            chi_res,
+            &hat_right_columns,
+            &res_right_columns,
+        );
+        let w_l = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_m_l, &u, &c_l,
+        );
+        let w_r = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_m_r, &u, &c_r,
+        );
+
+        // 7. Monotonicity checks
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_i);
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, u_key);
+
+        // 8. Prove that sum w_l * w_r = chi_m
+        // sum w_l * w_r - chi_m = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(w_l as &[_]), Box::new(w_r as &[_])]),
+                (-S::one(), vec![Box::new(chi_res as &[_])]),
+            ],
+        );
+
+        // 9. Produce MLEs for `\hat{M}` and prove that it consists of rows of the left input
+        // whose join column values have no match in the right input
+        for column in &unmatched_hat {
+            builder.produce_intermediate_mle(*column);
+        }
+        final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m_l,
+            chi_unmatched,
+            &hat_left_columns,
+            &unmatched_hat,
+        );
+        let w_m = final_round_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_u,
+            chi_unmatched,
+            &u,
+            &unmatched_hat[..num_columns_u],
+        );
+
+        // 10. Prove that w_m = w_l * z where z = (w_r == 0)
+        let w_r_scalars = alloc.alloc_slice_fill_with(num_rows_u, |i| S::from(w_r[i]));
+        let z = prover_evaluate_equals_zero(num_rows_u, builder, alloc, w_r_scalars);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(w_m as &[_])]),
+                (-S::one(), vec![Box::new(w_l as &[_]), Box::new(z as &[_])]),
+            ],
+        );
+
+        // 11. Prove that the rows of `\hat{M}` are distinct
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, unmatched_rho);
+
+        // 12. Prove that the result is the union of `J` and the padded `M`
+        let (res_columns, padded_unmatched_columns) =
+            self.get_union_inputs(res_hat, &unmatched_hat, num_columns_left, alloc);
+        let output = self.get_output_table(&res_columns, &padded_unmatched_columns, alloc);
+        let output_columns = output.columns().copied().collect::<Vec<_>>();
+        for column in &output_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        prove_union(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &[res_columns, padded_unmatched_columns],
+            &output_columns,
+            &[num_rows_res, num_rows_unmatched],
+            output.num_rows(),
+        );
+        output
+    }
+}
+
+impl SortMergeOuterJoinExec {
+    /// Get the two parts of the result, `J` and the padded `M`, each with the match indicator column
+    fn get_union_inputs<'a, S: Scalar>(
+        &self,
+        res_hat: &[Column<'a, S>],
+        unmatched_hat: &[Column<'a, S>],
+        num_columns_left: usize,
+        alloc: &'a Bump,
+    ) -> (Vec<Column<'a, S>>, Vec<Column<'a, S>>) {
+        let num_rows_res = res_hat.first().map_or(0, Column::len);
+        let num_rows_unmatched = unmatched_hat.first().map_or(0, Column::len);
+        // Drop the two rho columns of `\hat{J}` to get `J`
+        let res_columns = res_hat[..num_columns_left]
+            .iter()
+            .chain(&res_hat[num_columns_left + 1..res_hat.len() - 1])
+            .copied()
+            .chain(core::iter::once(Column::Boolean(
+                alloc.alloc_slice_fill_copy(num_rows_res, true),
+            )))
+            .collect();
+        let padded_unmatched_columns = unmatched_hat[..num_columns_left]
+            .iter()
+            .copied()
+            .chain(
+                self.right_other_column_types()
+                    .into_iter()
+                    .map(|column_type| get_default_column(column_type, num_rows_unmatched, alloc)),
+            )
+            .chain(core::iter::once(Column::Boolean(
+                alloc.alloc_slice_fill_copy(num_rows_unmatched, false),
+            )))
+            .collect();
+        (res_columns, padded_unmatched_columns)
+    }
+
+    /// Concatenate `J` and the padded `M`
+    ///
+    /// # Panics
+    /// Panics if the columns of `J` and the padded `M` have different types
+    fn get_output_table<'a, S: Scalar>(
+        &self,
+        res_columns: &[Column<'a, S>],
+        padded_unmatched_columns: &[Column<'a, S>],
+        alloc: &'a Bump,
+    ) -> Table<'a, S> {
+        let num_rows_output = res_columns.first().map_or(0, Column::len)
+            + padded_unmatched_columns.first().map_or(0, Column::len);
+        let output_columns = res_columns
+            .iter()
+            .zip_eq(padded_unmatched_columns)
+            .map(|(res_column, unmatched_column)| {
+                column_union(
+                    &[res_column, unmatched_column],
+                    alloc,
+                    res_column.column_type(),
+                )
+                .expect("Column types should match")
+            })
+            .collect::<Vec<_>>();
+        Table::try_from_iter_with_options(
+            self.result_idents.iter().cloned().zip_eq(output_columns),
+            TableOptions::new(Some(num_rows_output)),
+        )
+        .expect("Can not create table")
+    }
+}
+
+/// Get the indexes of the rows of the left input which have no match, in increasing order
+fn get_unmatched_row_indexes(num_rows_left: usize, left_row_indexes: &[usize]) -> Vec<usize> {
+    let mut is_matched = vec![false; num_rows_left];
+    for &index in left_row_indexes {
+        is_matched[index] = true;
+    }
+    (0..num_rows_left).filter(|&i| !is_matched[i]).collect()
+}
+
+/// Apply the row indexes to each of the columns
+///
+/// # Panics
+/// Panics if any of the indexes is out of bounds
+fn apply_columns_to_indexes<'a, S: Scalar>(
+    columns: &[Column<'a, S>],
+    indexes: &[usize],
+    alloc: &'a Bump,
+) -> Vec<Column<'a, S>> {
+    columns
+        .iter()
+        .map(|column| {
+            apply_column_to_indexes(column, alloc, indexes)
+                .expect("Indexes can not be out of bounds")
+        })
+        .collect()
+}
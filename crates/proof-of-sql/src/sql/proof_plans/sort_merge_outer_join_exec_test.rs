@@ -0,0 +1,310 @@
+use super::test_utility::*;
+use crate::{
+    base::database::{
+        owned_table_utility::*, table_utility::*, ColumnField, ColumnType, TableRef,
+        TableTestAccessor, TestAccessor,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+use sqlparser::ast::Ident;
+
+fn cats_and_cat_details_outer_join(
+    table_left: &TableRef,
+    table_right: &TableRef,
+) -> super::DynProofPlan {
+    sort_merge_outer_join(
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+                column_field("age", ColumnType::Int),
+            ],
+        ),
+        vec![0],
+        vec![0],
+        vec![
+            Ident::new("id"),
+            Ident::new("name"),
+            Ident::new("human"),
+            Ident::new("age"),
+            Ident::new("is_matched"),
+        ],
+    )
+}
+
+#[test]
+fn we_can_get_the_result_fields_of_a_sort_merge_outer_join() {
+    let ast = cats_and_cat_details_outer_join(
+        &"sxt.cats".parse().unwrap(),
+        &"sxt.cat_details".parse().unwrap(),
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("id".into(), ColumnType::BigInt),
+            ColumnField::new("name".into(), ColumnType::VarChar),
+            ColumnField::new("human".into(), ColumnType::VarChar),
+            ColumnField::new("age".into(), ColumnType::Int),
+            ColumnField::new("is_matched".into(), ColumnType::Boolean),
+        ]
+    );
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_outer_join() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2, 3, 4, 5, 3], &alloc),
+        borrowed_varchar(
+            "name",
+            ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper", "Rocky"],
+            &alloc,
+        ),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [1_i64, 2, 98, 4, 1, 2, 7], &alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Ian", "Erik"],
+            &alloc,
+        ),
+        borrowed_int("age", [30, 30, 45, 45, 21, 21, 60], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = cats_and_cat_details_outer_join(&table_left, &table_right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 1, 2, 2, 4, 3, 5, 3]),
+        varchar(
+            "name",
+            [
+                "Chloe", "Chloe", "Margaret", "Margaret", "Lucy", "Prudence", "Pepper", "Rocky",
+            ],
+        ),
+        varchar(
+            "human",
+            ["Cassia", "Ian", "Cassia", "Ian", "Gretta", "", "", ""],
+        ),
+        int("age", [30, 21, 30, 21, 45, 0, 0, 0]),
+        boolean(
+            "is_matched",
+            [true, true, true, true, true, false, false, false],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_outer_join_with_filters() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let cats = table([
+        borrowed_bigint("id", [1_i64, 2, 3, 4, 5, 6], &alloc),
+        borrowed_varchar(
+            "name",
+            ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper", "Rocky"],
+            &alloc,
+        ),
+    ]);
+    let table_cats: TableRef = "sxt.cats".parse().unwrap();
+    let cat_details = table([
+        borrowed_bigint("id", [1_i64, 2, 4, 1, 6], &alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Ian", "Erik"],
+            &alloc,
+        ),
+    ]);
+    let table_cat_details: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_cats.clone(), cats, 0);
+    accessor.add_table(table_cat_details.clone(), cat_details, 0);
+    let ast = sort_merge_outer_join(
+        filter(
+            cols_expr_plan(&table_cats, &["name", "id"], &accessor),
+            tab(&table_cats),
+            lte(column(&table_cats, "id", &accessor), const_bigint(4)),
+        ),
+        filter(
+            cols_expr_plan(&table_cat_details, &["human", "id"], &accessor),
+            tab(&table_cat_details),
+            not(equal(
+                column(&table_cat_details, "human", &accessor),
+                const_varchar("Cassia"),
+            )),
+        ),
+        vec![1],
+        vec![1],
+        vec![
+            Ident::new("id"),
+            Ident::new("name"),
+            Ident::new("human"),
+            Ident::new("is_matched"),
+        ],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_cats);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 4, 2, 3]),
+        varchar("name", ["Chloe", "Lucy", "Margaret", "Prudence"]),
+        varchar("human", ["Ian", "Gretta", "", ""]),
+        boolean("is_matched", [true, true, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_outer_join_without_unmatched_rows() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [2_i64, 1], &alloc),
+        borrowed_varchar("name", ["Chloe", "Margaret"], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [1_i64, 2, 3], &alloc),
+        borrowed_varchar("human", ["Cassia", "Gretta", "Ian"], &alloc),
+        borrowed_int("age", [30, 45, 21], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = cats_and_cat_details_outer_join(&table_left, &table_right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [2_i64, 1]),
+        varchar("name", ["Chloe", "Margaret"]),
+        varchar("human", ["Gretta", "Cassia"]),
+        int("age", [45, 30]),
+        boolean("is_matched", [true, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_outer_join_if_one_or_both_tables_have_no_rows(
+) {
+    let alloc = Bump::new();
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    let ast = cats_and_cat_details_outer_join(&table_left, &table_right);
+    let empty_left = || {
+        table([
+            borrowed_bigint("id", [0_i64; 0], &alloc),
+            borrowed_varchar("name", [""; 0], &alloc),
+        ])
+    };
+    let empty_right = || {
+        table([
+            borrowed_bigint("id", [0_i64; 0], &alloc),
+            borrowed_varchar("human", [""; 0], &alloc),
+            borrowed_int("age", [0; 0], &alloc),
+        ])
+    };
+    let empty_res = owned_table([
+        bigint("id", [0_i64; 0]),
+        varchar("name", [""; 0]),
+        varchar("human", [""; 0]),
+        int("age", [0; 0]),
+        boolean("is_matched", [false; 0]),
+    ]);
+
+    // Left table has no rows but right table has rows
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let right = table([
+        borrowed_bigint("id", [10_i64, 11, 12], &alloc),
+        borrowed_varchar("human", ["Rachel", "Rachel", "Megan"], &alloc),
+        borrowed_int("age", [30, 30, 45], &alloc),
+    ]);
+    accessor.add_table(table_left.clone(), empty_left(), 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_right);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, empty_res);
+
+    // Right table has no rows but left table has rows
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2, 1], &alloc),
+        borrowed_varchar("name", ["Chloe", "Margaret", "Prudence"], &alloc),
+    ]);
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), empty_right(), 0);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2, 1]),
+        varchar("name", ["Chloe", "Margaret", "Prudence"]),
+        varchar("human", ["", "", ""]),
+        int("age", [0, 0, 0]),
+        boolean("is_matched", [false, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+
+    // Both tables have no rows
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(table_left.clone(), empty_left(), 0);
+    accessor.add_table(table_right.clone(), empty_right(), 0);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, empty_res);
+}
+
+#[test]
+#[should_panic(
+    expected = "The amount of result idents should be the same as the expected number of columns"
+)]
+fn we_cannot_create_a_sort_merge_outer_join_without_the_indicator_ident() {
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    sort_merge_outer_join(
+        table_exec(
+            table_left,
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        table_exec(
+            table_right,
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+            ],
+        ),
+        vec![0],
+        vec![0],
+        vec![Ident::new("id"), Ident::new("name"), Ident::new("human")],
+    );
+}
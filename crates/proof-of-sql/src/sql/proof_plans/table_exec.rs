@@ -46,9 +46,9 @@ impl ProofPlan for TableExec {
             .schema
             .iter()
             .map(|field| {
-                let column_ref =
-                    ColumnRef::new(self.table_ref.clone(), field.name(), field.data_type());
-                *accessor.get(&column_ref).expect("Column does not exist")
+                *accessor
+                    .get(&field.column_ref(self.table_ref.clone()))
+                    .expect("Column does not exist")
             })
             .collect::<Vec<_>>();
         let chi_eval = *chi_eval_map
@@ -64,7 +64,7 @@ impl ProofPlan for TableExec {
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         self.schema
             .iter()
-            .map(|field| ColumnRef::new(self.table_ref.clone(), field.name(), field.data_type()))
+            .map(|field| field.column_ref(self.table_ref.clone()))
             .collect()
     }
 
@@ -1,10 +1,11 @@
 use super::{
-    DynProofPlan, EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec,
-    TableExec, UnionExec,
+    AliasedWindowFunction, DistinctExec, DynProofPlan, EmptyExec, FilterExec, GroupByExec,
+    ProjectionExec, SliceExec, SortExec, SortMergeJoinExec, SortMergeOuterJoinExec, TableExec,
+    UnionExec, WindowExec, WindowFunction,
 };
 use crate::{
     base::database::{ColumnField, ColumnType, TableRef},
-    sql::proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+    sql::proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
 };
 use sqlparser::ast::Ident;
 
@@ -36,15 +37,15 @@ pub fn filter(
 ///
 /// Will panic if `count_alias` cannot be parsed as a valid identifier.
 pub fn group_by(
-    group_by_exprs: Vec<ColumnExpr>,
-    sum_expr: Vec<AliasedDynProofExpr>,
+    group_by_exprs: Vec<AliasedDynProofExpr>,
+    aggregate_exprs: Vec<AliasedDynProofExpr>,
     count_alias: &str,
     table: TableExpr,
     where_clause: DynProofExpr,
 ) -> DynProofPlan {
     DynProofPlan::GroupBy(GroupByExec::new(
         group_by_exprs,
-        sum_expr,
+        aggregate_exprs,
         count_alias.into(),
         table,
         where_clause,
@@ -55,10 +56,38 @@ pub fn slice_exec(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> Dyn
     DynProofPlan::Slice(SliceExec::new(Box::new(input), skip, fetch))
 }
 
+pub fn sort_exec(input: DynProofPlan, order_by: Vec<(usize, bool)>) -> DynProofPlan {
+    DynProofPlan::Sort(SortExec::new(Box::new(input), order_by))
+}
+
 pub fn union_exec(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> DynProofPlan {
     DynProofPlan::Union(UnionExec::new(inputs, schema))
 }
 
+pub fn distinct_exec(input: DynProofPlan) -> DynProofPlan {
+    DynProofPlan::Distinct(DistinctExec::new(Box::new(input)))
+}
+
+pub fn window_exec(
+    input: DynProofPlan,
+    partition_by: Vec<usize>,
+    order_by: Vec<(usize, bool)>,
+    functions: Vec<(WindowFunction, &str)>,
+) -> DynProofPlan {
+    DynProofPlan::Window(WindowExec::new(
+        Box::new(input),
+        partition_by,
+        order_by,
+        functions
+            .into_iter()
+            .map(|(function, alias)| AliasedWindowFunction {
+                function,
+                alias: alias.into(),
+            })
+            .collect(),
+    ))
+}
+
 pub fn sort_merge_join(
     left: DynProofPlan,
     right: DynProofPlan,
@@ -74,3 +103,19 @@ pub fn sort_merge_join(
         result_idents,
     ))
 }
+
+pub fn sort_merge_outer_join(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+    result_idents: Vec<Ident>,
+) -> DynProofPlan {
+    DynProofPlan::SortMergeOuterJoin(SortMergeOuterJoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_indexes,
+        right_join_column_indexes,
+        result_idents,
+    ))
+}
@@ -143,9 +143,9 @@ impl ProverEvaluate for UnionExec {
         let beta = builder.consume_post_result_challenge();
         let input_columns: Vec<Vec<Column<'a, S>>> = inputs
             .iter()
-            .map(|table| table.columns().copied().collect::<Vec<_>>())
+            .map(Table::column_components)
             .collect::<Vec<_>>();
-        let output_columns: Vec<Column<'a, S>> = res.columns().copied().collect::<Vec<_>>();
+        let output_columns: Vec<Column<'a, S>> = res.column_components();
         // Produce intermediate MLEs for the union
         output_columns.iter().copied().for_each(|column| {
             builder.produce_intermediate_mle(column);
@@ -170,7 +170,7 @@ impl ProverEvaluate for UnionExec {
 /// # Panics
 /// Should never panic if the code is correct.
 #[allow(clippy::too_many_arguments)]
-fn verify_union<S: Scalar>(
+pub(super) fn verify_union<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     gamma: S,
     beta: S,
@@ -224,7 +224,7 @@ fn verify_union<S: Scalar>(
 /// # Panics
 /// Should never panic if the code is correct.
 #[allow(clippy::too_many_arguments)]
-fn prove_union<'a, S: Scalar + 'a>(
+pub(super) fn prove_union<'a, S: Scalar + 'a>(
     builder: &mut FinalRoundBuilder<'a, S>,
     alloc: &'a Bump,
     gamma: S,
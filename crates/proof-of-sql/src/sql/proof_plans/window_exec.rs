@@ -0,0 +1,979 @@
+use super::{
+    sort_exec::{compute_sort_key, sort_indexes, sort_key_bit_widths, sort_key_weights},
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            filter_util::filter_column_by_index, order_by_util::OrderIndexDirectionPairs, Column,
+            ColumnField, ColumnRef, ColumnType, OwnedColumn, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{prover_evaluate_equals_zero, verifier_evaluate_equals_zero},
+        proof_gadgets::{
+            final_round_evaluate_monotonic, final_round_evaluate_permutation_check,
+            final_round_evaluate_shift, first_round_evaluate_monotonic, first_round_evaluate_shift,
+            verify_monotonic, verify_permutation_check, verify_shift,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
+
+/// A function that a [`WindowExec`] evaluates over the partition of each row.
+///
+/// The arguments are the indexes of the columns of the input that the functions are evaluated on.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum WindowFunction {
+    /// `ROW_NUMBER()`, the position of the row in its partition, starting at 1
+    RowNumber,
+    /// `RANK()`, the row number of the first of the peers of the row,
+    /// which are the rows of the partition that are not ordered differently from it
+    Rank,
+    /// `LAG(<column>)`, the value of the column at the previous row of the partition,
+    /// which is NULL for the first row of the partition
+    Lag(usize),
+    /// `LEAD(<column>)`, the value of the column at the next row of the partition,
+    /// which is NULL for the last row of the partition
+    Lead(usize),
+    /// `SUM(<column>)`, the sum of the column over the rows of the partition
+    /// up to the last peer of the row
+    Sum(usize),
+}
+
+impl WindowFunction {
+    /// The index of the input column that the function is evaluated on, if any
+    fn argument(self) -> Option<usize> {
+        match self {
+            WindowFunction::RowNumber | WindowFunction::Rank => None,
+            WindowFunction::Lag(index)
+            | WindowFunction::Lead(index)
+            | WindowFunction::Sum(index) => Some(index),
+        }
+    }
+
+    /// The type of the values of the function given the fields of the input,
+    /// if the function can be proven on them
+    fn data_type(self, input_fields: &[ColumnField]) -> Option<ColumnType> {
+        let argument_type = |index: usize| input_fields.get(index).map(ColumnField::data_type);
+        match self {
+            WindowFunction::RowNumber | WindowFunction::Rank => Some(ColumnType::BigInt),
+            // NULLs are zero, which is only the default value of types without a string representation
+            WindowFunction::Lag(index) | WindowFunction::Lead(index) => argument_type(index)
+                .filter(|data_type| {
+                    data_type.is_numeric()
+                        || matches!(
+                            data_type,
+                            ColumnType::Boolean | ColumnType::TimestampTZ(_, _)
+                        )
+                }),
+            WindowFunction::Sum(index) => argument_type(index).filter(ColumnType::is_numeric),
+        }
+    }
+
+    /// Whether the values of the function can be NULL, in which case they come with a presence
+    fn is_nullable(self) -> bool {
+        matches!(self, WindowFunction::Lag(_) | WindowFunction::Lead(_))
+    }
+
+    /// The number of shifts that proving the function takes
+    fn num_shifts(self) -> usize {
+        match self {
+            WindowFunction::RowNumber => 0,
+            WindowFunction::Rank | WindowFunction::Lag(_) => 1,
+            WindowFunction::Lead(_) | WindowFunction::Sum(_) => 2,
+        }
+    }
+}
+
+/// A [`WindowFunction`] with an alias.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+pub struct AliasedWindowFunction {
+    pub function: WindowFunction,
+    pub alias: Ident,
+}
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT *,
+///         <function1> OVER (PARTITION BY <partition_by> ORDER BY <order_by>) AS <alias1>, ...
+///     FROM (<ProofPlan>)
+/// ```
+///
+/// The rows of the result are the rows of the input ordered by the window key,
+/// a sort key, see [`super::SortExec`], of the columns that partition the rows followed by
+/// the columns that order them within a partition. Each function adds a column to the result,
+/// and `LAG` and `LEAD` add its presence after it, i.e. their columns are nullable.
+///
+/// The result is proven to be a permutation of the input that is ordered by the window key.
+/// The first rows of the partitions and of the groups of peers are then proven by comparing
+/// the keys of each row with those of the previous row, which is given by a shift,
+/// and the functions are proven by relating their values at each row to those at the previous row.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct WindowExec {
+    pub(super) input: Box<DynProofPlan>,
+    pub(super) partition_by: Vec<usize>,
+    pub(super) order_by: OrderIndexDirectionPairs,
+    pub(super) functions: Vec<AliasedWindowFunction>,
+}
+
+impl WindowExec {
+    /// Creates a new window execution plan.
+    pub fn new(
+        input: Box<DynProofPlan>,
+        partition_by: Vec<usize>,
+        order_by: OrderIndexDirectionPairs,
+        functions: Vec<AliasedWindowFunction>,
+    ) -> Self {
+        Self {
+            input,
+            partition_by,
+            order_by,
+            functions,
+        }
+    }
+
+    /// Whether the functions can be proven over the windows of the result of `input`.
+    ///
+    /// This is the case if the columns that partition and order the rows are of integer,
+    /// boolean or timestamp types, their window key is small enough,
+    /// and the arguments of the functions are of types that the functions support.
+    #[must_use]
+    pub fn is_provable(
+        input: &DynProofPlan,
+        partition_by: &[usize],
+        order_by: &[(usize, bool)],
+        functions: &[WindowFunction],
+    ) -> bool {
+        let fields = input.get_column_result_fields();
+        let window_order = window_order(partition_by, order_by);
+        (window_order.is_empty() || sort_key_bit_widths(&fields, &window_order).is_some())
+            && functions
+                .iter()
+                .all(|function| function.data_type(&fields).is_some())
+    }
+
+    /// The columns that the rows are ordered by, see [`window_order`]
+    fn window_order(&self) -> Vec<(usize, bool)> {
+        window_order(&self.partition_by, &self.order_by)
+    }
+
+    /// The weights of the columns of the window key, see [`sort_key_weights`].
+    ///
+    /// The weights of the columns that partition the rows come first,
+    /// so that they are also the weights of the partition key.
+    fn window_key_weights<S: Scalar>(&self) -> Option<Vec<S>> {
+        let window_order = self.window_order();
+        if window_order.is_empty() {
+            Some(Vec::new())
+        } else {
+            sort_key_weights(&self.input.get_column_result_fields(), &window_order)
+        }
+    }
+
+    /// Whether the row numbers are needed to prove the functions
+    fn needs_row_numbers(&self) -> bool {
+        self.functions.iter().any(|aliased_function| {
+            matches!(
+                aliased_function.function,
+                WindowFunction::RowNumber | WindowFunction::Rank
+            )
+        })
+    }
+
+    /// The number of shifts that proving the plan takes, not counting the one of the monotonicity check
+    fn num_shifts(&self) -> usize {
+        // The shifts of the partition key, of the window key and of the row numbers
+        2 + usize::from(self.needs_row_numbers())
+            + self
+                .functions
+                .iter()
+                .map(|aliased_function| aliased_function.function.num_shifts())
+                .sum::<usize>()
+    }
+}
+
+/// Orders ascendingly by the columns that partition the rows, followed by the columns that order a partition
+fn window_order(partition_by: &[usize], order_by: &[(usize, bool)]) -> Vec<(usize, bool)> {
+    partition_by
+        .iter()
+        .map(|&index| (index, true))
+        .chain(order_by.iter().copied())
+        .collect()
+}
+
+/// The values of the functions of a [`WindowExec`] over the sorted rows of its input
+enum WindowFunctionValues<'a, S: Scalar> {
+    RowNumber,
+    Rank(&'a [i64]),
+    Lag(&'a [S], &'a [bool]),
+    Lead(&'a [S], &'a [bool]),
+    Sum {
+        running_sums: &'a [S],
+        sums: &'a [S],
+    },
+}
+
+/// The result of a [`WindowExec`] together with the witnesses needed to prove it
+struct Windows<'a, S: Scalar> {
+    sorted_columns: Vec<Column<'a, S>>,
+    partition_key: &'a [S],
+    window_key: &'a [S],
+    row_numbers: &'a [i64],
+    function_values: Vec<WindowFunctionValues<'a, S>>,
+}
+
+/// Whether each row starts a new group of rows with the same key, which includes the first row
+fn new_groups<'a, S: Scalar>(alloc: &'a Bump, key: &[S]) -> &'a [bool] {
+    alloc.alloc_slice_fill_with(key.len(), |i| i == 0 || key[i] != key[i - 1])
+}
+
+/// Converts `scalars` into a column of type `column_type`, which is a column of scalars if they do not fit
+fn column_from_scalars<'a, S: Scalar>(
+    alloc: &'a Bump,
+    scalars: &'a [S],
+    column_type: ColumnType,
+) -> Column<'a, S> {
+    OwnedColumn::try_from_scalars(scalars, column_type).map_or(Column::Scalar(scalars), |column| {
+        Column::from_owned_column(alloc.alloc(column), alloc)
+    })
+}
+
+impl WindowExec {
+    /// Sorts the rows of the input by the window key and evaluates the functions over them
+    fn compute_windows<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        columns: &[Column<'a, S>],
+    ) -> Windows<'a, S> {
+        let num_rows = columns.first().map_or(0, Column::len);
+        let window_order = self.window_order();
+        let weights = self.window_key_weights::<S>().expect(
+            "WindowExec can only partition and order by integer, boolean and timestamp columns",
+        );
+        let indexes = sort_indexes(columns, &window_order);
+        let sorted_columns = columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect::<Vec<_>>();
+        let num_partition_columns = self.partition_by.len();
+        let partition_key = compute_sort_key(
+            alloc,
+            &sorted_columns,
+            &window_order[..num_partition_columns],
+            &weights[..num_partition_columns],
+        );
+        let window_key = compute_sort_key(alloc, &sorted_columns, &window_order, &weights);
+        let new_partitions = new_groups(alloc, partition_key);
+        let new_peers = new_groups(alloc, window_key);
+        let row_numbers = alloc.alloc_slice_fill_copy(num_rows, 1_i64);
+        for i in 1..num_rows {
+            if !new_partitions[i] {
+                row_numbers[i] = row_numbers[i - 1] + 1;
+            }
+        }
+        let row_numbers = &*row_numbers;
+        let values = |index: usize| -> &'a [S] {
+            alloc.alloc_slice_copy(&sorted_columns[index].to_scalar_with_scaling(0))
+        };
+        let function_values = self
+            .functions
+            .iter()
+            .map(|aliased_function| match aliased_function.function {
+                WindowFunction::RowNumber => WindowFunctionValues::RowNumber,
+                WindowFunction::Rank => {
+                    let ranks = alloc.alloc_slice_copy(row_numbers);
+                    for i in 1..num_rows {
+                        if !new_peers[i] {
+                            ranks[i] = ranks[i - 1];
+                        }
+                    }
+                    WindowFunctionValues::Rank(ranks)
+                }
+                WindowFunction::Lag(index) => {
+                    let values = values(index);
+                    let presence = alloc.alloc_slice_fill_with(num_rows, |i| !new_partitions[i]);
+                    let lags = alloc.alloc_slice_fill_with(num_rows, |i| {
+                        if presence[i] {
+                            values[i - 1]
+                        } else {
+                            S::ZERO
+                        }
+                    });
+                    WindowFunctionValues::Lag(lags, presence)
+                }
+                WindowFunction::Lead(index) => {
+                    let values = values(index);
+                    let presence = alloc.alloc_slice_fill_with(num_rows, |i| {
+                        i + 1 < num_rows && !new_partitions[i + 1]
+                    });
+                    let leads = alloc.alloc_slice_fill_with(num_rows, |i| {
+                        if presence[i] {
+                            values[i + 1]
+                        } else {
+                            S::ZERO
+                        }
+                    });
+                    WindowFunctionValues::Lead(leads, presence)
+                }
+                WindowFunction::Sum(index) => {
+                    let values = values(index);
+                    let running_sums = alloc.alloc_slice_copy(values);
+                    for i in 1..num_rows {
+                        if !new_partitions[i] {
+                            running_sums[i] += running_sums[i - 1];
+                        }
+                    }
+                    // The sum of a row is the running sum of its last peer
+                    let sums = alloc.alloc_slice_copy(running_sums);
+                    for i in (0..num_rows.saturating_sub(1)).rev() {
+                        if !new_peers[i + 1] {
+                            sums[i] = sums[i + 1];
+                        }
+                    }
+                    WindowFunctionValues::Sum { running_sums, sums }
+                }
+            })
+            .collect();
+        Windows {
+            sorted_columns,
+            partition_key,
+            window_key,
+            row_numbers,
+            function_values,
+        }
+    }
+
+    /// The result of the plan given the sorted columns and the values of the functions
+    fn result_table<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        windows: &Windows<'a, S>,
+    ) -> Table<'a, S> {
+        let num_rows = windows.row_numbers.len();
+        let fields = self.get_column_result_fields();
+        let input_fields = self.input.get_column_result_fields();
+        let function_columns = self
+            .functions
+            .iter()
+            .zip(&windows.function_values)
+            .flat_map(|(aliased_function, function_values)| {
+                let data_type = aliased_function
+                    .function
+                    .data_type(&input_fields)
+                    .expect("WindowExec functions should be provable");
+                match *function_values {
+                    WindowFunctionValues::RowNumber => {
+                        vec![Column::BigInt(windows.row_numbers)]
+                    }
+                    WindowFunctionValues::Rank(ranks) => vec![Column::BigInt(ranks)],
+                    WindowFunctionValues::Lag(values, presence)
+                    | WindowFunctionValues::Lead(values, presence) => vec![
+                        column_from_scalars(alloc, values, data_type),
+                        Column::Boolean(presence),
+                    ],
+                    WindowFunctionValues::Sum { sums, .. } => {
+                        vec![column_from_scalars(alloc, sums, data_type)]
+                    }
+                }
+            });
+        Table::<'a, S>::try_from_components_with_options(
+            &fields,
+            windows
+                .sorted_columns
+                .iter()
+                .copied()
+                .chain(function_columns),
+            TableOptions::new(Some(num_rows)),
+            alloc,
+        )
+        .expect("Failed to create table from iterator")
+    }
+}
+
+impl ProofPlan for WindowExec
+where
+    WindowExec: ProverEvaluate,
+{
+    #[allow(clippy::too_many_lines)]
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        // 1. columns
+        let input_table_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let chi_eval = input_table_eval.chi_eval();
+        let columns_evals = input_table_eval.column_evals();
+        // 2. sorted columns
+        let sorted_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(columns_evals.len())?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 3. The sorted columns are a permutation of the columns
+        verify_permutation_check(
+            builder,
+            alpha,
+            beta,
+            chi_eval,
+            columns_evals,
+            &sorted_columns_evals,
+        )?;
+        // 4. The sorted columns are ordered by the window key
+        let weights = self
+            .window_key_weights::<S>()
+            .ok_or(ProofError::UnsupportedQueryPlan {
+            error:
+                "WindowExec can only partition and order by integer, boolean and timestamp columns",
+        })?;
+        let window_order = self.window_order();
+        let key_eval = |order: &[(usize, bool)]| -> S {
+            order
+                .iter()
+                .zip(&weights)
+                .map(|(&(index, _), &weight)| weight * sorted_columns_evals[index])
+                .sum()
+        };
+        let partition_key_eval = key_eval(&window_order[..self.partition_by.len()]);
+        let window_key_eval = key_eval(&window_order);
+        verify_monotonic::<S, false, true>(builder, alpha, beta, window_key_eval, chi_eval)?;
+        let shifted_chi_eval = builder.try_consume_chi_evaluation()?;
+        // 5. The first rows of the partitions and of the groups of peers
+        let new_partitions_eval = verify_new_groups(
+            builder,
+            alpha,
+            beta,
+            partition_key_eval,
+            chi_eval,
+            shifted_chi_eval,
+        )?;
+        let new_peers_eval = verify_new_groups(
+            builder,
+            alpha,
+            beta,
+            window_key_eval,
+            chi_eval,
+            shifted_chi_eval,
+        )?;
+        // 6. The row numbers, which restart at 1 with each partition
+        let row_numbers_eval = if self.needs_row_numbers() {
+            let row_numbers_eval = builder.try_consume_final_round_mle_evaluation()?;
+            let shifted_row_numbers_eval = verify_shifted(
+                builder,
+                alpha,
+                beta,
+                row_numbers_eval,
+                chi_eval,
+                shifted_chi_eval,
+            )?;
+            // row_numbers = chi + (chi - new_partitions) * shifted_row_numbers
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                row_numbers_eval
+                    - chi_eval
+                    - (chi_eval - new_partitions_eval) * shifted_row_numbers_eval,
+                2,
+            )?;
+            row_numbers_eval
+        } else {
+            S::ZERO
+        };
+        // 7. The functions
+        let mut function_evals = Vec::new();
+        for aliased_function in &self.functions {
+            let values_eval = |index: usize| sorted_columns_evals[index];
+            match aliased_function.function {
+                WindowFunction::RowNumber => function_evals.push(row_numbers_eval),
+                WindowFunction::Rank => {
+                    let ranks_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    let shifted_ranks_eval = verify_shifted(
+                        builder,
+                        alpha,
+                        beta,
+                        ranks_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    // ranks = new_peers * row_numbers + (chi - new_peers) * shifted_ranks
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        ranks_eval
+                            - new_peers_eval * row_numbers_eval
+                            - (chi_eval - new_peers_eval) * shifted_ranks_eval,
+                        2,
+                    )?;
+                    function_evals.push(ranks_eval);
+                }
+                WindowFunction::Lag(index) => {
+                    let shifted_values_eval = verify_shifted(
+                        builder,
+                        alpha,
+                        beta,
+                        values_eval(index),
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    let lags_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    // lags = (chi - new_partitions) * shifted_values
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        lags_eval - (chi_eval - new_partitions_eval) * shifted_values_eval,
+                        2,
+                    )?;
+                    function_evals.extend([lags_eval, chi_eval - new_partitions_eval]);
+                }
+                WindowFunction::Lead(index) => {
+                    let leads_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    let presence_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    let shifted_leads_eval = verify_shifted(
+                        builder,
+                        alpha,
+                        beta,
+                        leads_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    // The shift of the presence is `chi - new_partitions`,
+                    // i.e. only the last rows of the partitions have no next row
+                    verify_shift(
+                        builder,
+                        alpha,
+                        beta,
+                        presence_eval,
+                        chi_eval - new_partitions_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    // shifted_leads = (chi - new_partitions) * values
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        shifted_leads_eval - (chi_eval - new_partitions_eval) * values_eval(index),
+                        2,
+                    )?;
+                    function_evals.extend([leads_eval, presence_eval]);
+                }
+                WindowFunction::Sum(index) => {
+                    let running_sums_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    let shifted_running_sums_eval = verify_shifted(
+                        builder,
+                        alpha,
+                        beta,
+                        running_sums_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    let sums_eval = builder.try_consume_final_round_mle_evaluation()?;
+                    let shifted_sums_eval = verify_shifted(
+                        builder,
+                        alpha,
+                        beta,
+                        sums_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                    )?;
+                    // running_sums = values + (chi - new_partitions) * shifted_running_sums
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        running_sums_eval
+                            - values_eval(index)
+                            - (chi_eval - new_partitions_eval) * shifted_running_sums_eval,
+                        2,
+                    )?;
+                    // The sum of the last peer of a group is its running sum,
+                    // and the other rows take the sum of the next row:
+                    // shifted_sums = (new_peers + shifted_chi - chi) * shifted_running_sums + (chi - new_peers) * sums
+                    builder.try_produce_sumcheck_subpolynomial_evaluation(
+                        SumcheckSubpolynomialType::Identity,
+                        shifted_sums_eval
+                            - (new_peers_eval + shifted_chi_eval - chi_eval)
+                                * shifted_running_sums_eval
+                            - (chi_eval - new_peers_eval) * sums_eval,
+                        2,
+                    )?;
+                    function_evals.push(sums_eval);
+                }
+            }
+        }
+        Ok(TableEvaluation::new(
+            sorted_columns_evals
+                .into_iter()
+                .chain(function_evals)
+                .collect(),
+            chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let input_fields = self.input.get_column_result_fields();
+        let function_fields = self
+            .functions
+            .iter()
+            .flat_map(|aliased_function| {
+                let function = aliased_function.function;
+                let data_type = function
+                    .data_type(&input_fields)
+                    .unwrap_or(ColumnType::BigInt);
+                let field = ColumnField::new(aliased_function.alias.clone(), data_type);
+                let presence_field = function.is_nullable().then(|| field.presence());
+                [Some(field), presence_field].into_iter().flatten()
+            })
+            .collect::<Vec<_>>();
+        input_fields.into_iter().chain(function_fields).collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for WindowExec {
+    #[tracing::instrument(name = "WindowExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        // 2. sorted columns and the values of the functions
+        let windows = self.compute_windows(alloc, &columns);
+        let res = self.result_table(alloc, &windows);
+        builder.request_post_result_challenges(2);
+        first_round_evaluate_monotonic(builder, num_rows);
+        builder.produce_chi_evaluation_length(num_rows + 1);
+        for _ in 0..self.num_shifts() {
+            first_round_evaluate_shift(builder, num_rows);
+        }
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "WindowExec::final_round_evaluate", level = "debug", skip_all)]
+    #[allow(clippy::too_many_lines)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        // 2. sorted columns
+        let windows = self.compute_windows(alloc, &columns);
+        let sorted_columns = &windows.sorted_columns;
+        sorted_columns.iter().copied().for_each(|column| {
+            builder.produce_intermediate_mle(column);
+        });
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. The sorted columns are a permutation of the columns
+        let chi = alloc.alloc_slice_fill_copy(num_rows, true) as &[_];
+        let shifted_chi = alloc.alloc_slice_fill_copy(num_rows + 1, true) as &[_];
+        final_round_evaluate_permutation_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi,
+            &columns,
+            sorted_columns,
+        );
+        // 4. The sorted columns are ordered by the window key
+        final_round_evaluate_monotonic::<S, false, true>(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            windows.window_key,
+        );
+        // 5. The first rows of the partitions and of the groups of peers
+        let new_partitions =
+            final_round_evaluate_new_groups(builder, alloc, alpha, beta, windows.partition_key);
+        let new_peers =
+            final_round_evaluate_new_groups(builder, alloc, alpha, beta, windows.window_key);
+        // 6. The row numbers, which restart at 1 with each partition
+        let row_numbers = windows.row_numbers;
+        if self.needs_row_numbers() {
+            builder.produce_intermediate_mle(row_numbers);
+            let shifted_row_numbers = final_round_evaluate_shifted(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                alloc.alloc_slice_fill_with(num_rows, |i| S::from(row_numbers[i])),
+            );
+            // row_numbers = chi + (chi - new_partitions) * shifted_row_numbers
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::ONE, vec![Box::new(row_numbers)]),
+                    (-S::ONE, vec![Box::new(chi)]),
+                    (-S::ONE, vec![Box::new(chi), Box::new(shifted_row_numbers)]),
+                    (
+                        S::ONE,
+                        vec![Box::new(new_partitions), Box::new(shifted_row_numbers)],
+                    ),
+                ],
+            );
+        }
+        // 7. The functions
+        for (aliased_function, function_values) in
+            self.functions.iter().zip(&windows.function_values)
+        {
+            let values = |index: usize| -> &'a [S] {
+                alloc.alloc_slice_copy(&sorted_columns[index].to_scalar_with_scaling(0))
+            };
+            match (aliased_function.function, function_values) {
+                (WindowFunction::Rank, &WindowFunctionValues::Rank(ranks)) => {
+                    builder.produce_intermediate_mle(ranks);
+                    let shifted_ranks = final_round_evaluate_shifted(
+                        builder,
+                        alloc,
+                        alpha,
+                        beta,
+                        alloc.alloc_slice_fill_with(num_rows, |i| S::from(ranks[i])),
+                    );
+                    // ranks = new_peers * row_numbers + (chi - new_peers) * shifted_ranks
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::ONE, vec![Box::new(ranks)]),
+                            (-S::ONE, vec![Box::new(new_peers), Box::new(row_numbers)]),
+                            (-S::ONE, vec![Box::new(chi), Box::new(shifted_ranks)]),
+                            (S::ONE, vec![Box::new(new_peers), Box::new(shifted_ranks)]),
+                        ],
+                    );
+                }
+                (WindowFunction::Lag(index), &WindowFunctionValues::Lag(lags, _)) => {
+                    let shifted_values =
+                        final_round_evaluate_shifted(builder, alloc, alpha, beta, values(index));
+                    builder.produce_intermediate_mle(lags);
+                    // lags = (chi - new_partitions) * shifted_values
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::ONE, vec![Box::new(lags)]),
+                            (-S::ONE, vec![Box::new(chi), Box::new(shifted_values)]),
+                            (
+                                S::ONE,
+                                vec![Box::new(new_partitions), Box::new(shifted_values)],
+                            ),
+                        ],
+                    );
+                }
+                (WindowFunction::Lead(index), &WindowFunctionValues::Lead(leads, presence)) => {
+                    builder.produce_intermediate_mle(leads);
+                    builder.produce_intermediate_mle(presence);
+                    let shifted_leads =
+                        final_round_evaluate_shifted(builder, alloc, alpha, beta, leads);
+                    // The shift of the presence is `chi - new_partitions`,
+                    // i.e. only the last rows of the partitions have no next row
+                    let not_new_partitions = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+                        S::from(i < num_rows && !new_partitions[i])
+                    });
+                    final_round_evaluate_shift(
+                        builder,
+                        alloc,
+                        alpha,
+                        beta,
+                        alloc.alloc_slice_fill_with(num_rows, |i| S::from(presence[i])),
+                        not_new_partitions,
+                    );
+                    // shifted_leads = (chi - new_partitions) * values
+                    let values = values(index);
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::ONE, vec![Box::new(shifted_leads)]),
+                            (-S::ONE, vec![Box::new(chi), Box::new(values)]),
+                            (S::ONE, vec![Box::new(new_partitions), Box::new(values)]),
+                        ],
+                    );
+                }
+                (WindowFunction::Sum(index), &WindowFunctionValues::Sum { running_sums, sums }) => {
+                    builder.produce_intermediate_mle(running_sums);
+                    let shifted_running_sums =
+                        final_round_evaluate_shifted(builder, alloc, alpha, beta, running_sums);
+                    builder.produce_intermediate_mle(sums);
+                    let shifted_sums =
+                        final_round_evaluate_shifted(builder, alloc, alpha, beta, sums);
+                    // running_sums = values + (chi - new_partitions) * shifted_running_sums
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::ONE, vec![Box::new(running_sums)]),
+                            (-S::ONE, vec![Box::new(values(index))]),
+                            (-S::ONE, vec![Box::new(chi), Box::new(shifted_running_sums)]),
+                            (
+                                S::ONE,
+                                vec![Box::new(new_partitions), Box::new(shifted_running_sums)],
+                            ),
+                        ],
+                    );
+                    // The sum of the last peer of a group is its running sum,
+                    // and the other rows take the sum of the next row:
+                    // shifted_sums = (new_peers + shifted_chi - chi) * shifted_running_sums + (chi - new_peers) * sums
+                    builder.produce_sumcheck_subpolynomial(
+                        SumcheckSubpolynomialType::Identity,
+                        vec![
+                            (S::ONE, vec![Box::new(shifted_sums)]),
+                            (
+                                -S::ONE,
+                                vec![Box::new(new_peers), Box::new(shifted_running_sums)],
+                            ),
+                            (
+                                -S::ONE,
+                                vec![Box::new(shifted_chi), Box::new(shifted_running_sums)],
+                            ),
+                            (S::ONE, vec![Box::new(chi), Box::new(shifted_running_sums)]),
+                            (-S::ONE, vec![Box::new(chi), Box::new(sums)]),
+                            (S::ONE, vec![Box::new(new_peers), Box::new(sums)]),
+                        ],
+                    );
+                }
+                _ => {}
+            }
+        }
+        let res = self.result_table(alloc, &windows);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
+
+/// Produces the shift of `column` and proves it, see [`final_round_evaluate_shift`]
+fn final_round_evaluate_shifted<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    column: &'a [S],
+) -> &'a [S] {
+    let shifted_column = alloc.alloc_slice_fill_with(column.len() + 1, |i| {
+        if i == 0 {
+            S::ZERO
+        } else {
+            column[i - 1]
+        }
+    });
+    builder.produce_intermediate_mle(shifted_column as &[_]);
+    final_round_evaluate_shift(builder, alloc, alpha, beta, column, shifted_column);
+    shifted_column
+}
+
+/// Verifies the shift produced by [`final_round_evaluate_shifted`] and returns its evaluation
+fn verify_shifted<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    column_eval: S,
+    chi_eval: S,
+    shifted_chi_eval: S,
+) -> Result<S, ProofError> {
+    let shifted_column_eval = builder.try_consume_final_round_mle_evaluation()?;
+    verify_shift(
+        builder,
+        alpha,
+        beta,
+        column_eval,
+        shifted_column_eval,
+        chi_eval,
+        shifted_chi_eval,
+    )?;
+    Ok(shifted_column_eval)
+}
+
+/// Produces and proves the indicator of the rows whose `key` differs from that of the previous row,
+/// which always includes the first row.
+///
+/// A row has the same key as the previous row if the difference of `key` and its shift is zero.
+fn final_round_evaluate_new_groups<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    key: &'a [S],
+) -> &'a [bool] {
+    let num_rows = key.len();
+    let shifted_key = final_round_evaluate_shifted(builder, alloc, alpha, beta, key);
+    let diff = alloc.alloc_slice_fill_with(num_rows + 1, |i| {
+        key.get(i).copied().unwrap_or(S::ZERO) - shifted_key[i]
+    });
+    let is_same = prover_evaluate_equals_zero(num_rows + 1, builder, alloc, diff);
+    let is_new = new_groups(alloc, key);
+    builder.produce_intermediate_mle(is_new);
+    let chi = alloc.alloc_slice_fill_copy(num_rows, true) as &[_];
+    let singleton_chi = alloc.alloc_slice_fill_copy(1, true) as &[_];
+    // new_groups = chi - chi * is_same + singleton_chi * chi * is_same,
+    // where the last term makes the first row new even if its key is zero
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::ONE, vec![Box::new(is_new)]),
+            (-S::ONE, vec![Box::new(chi)]),
+            (S::ONE, vec![Box::new(chi), Box::new(is_same)]),
+            (
+                -S::ONE,
+                vec![Box::new(singleton_chi), Box::new(chi), Box::new(is_same)],
+            ),
+        ],
+    );
+    is_new
+}
+
+/// Verifies the indicator produced by [`final_round_evaluate_new_groups`] and returns its evaluation
+fn verify_new_groups<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    key_eval: S,
+    chi_eval: S,
+    shifted_chi_eval: S,
+) -> Result<S, ProofError> {
+    let shifted_key_eval =
+        verify_shifted(builder, alpha, beta, key_eval, chi_eval, shifted_chi_eval)?;
+    let is_same_eval =
+        verifier_evaluate_equals_zero(builder, key_eval - shifted_key_eval, shifted_chi_eval)?;
+    let new_groups_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let singleton_chi_eval = builder.singleton_chi_evaluation();
+    // new_groups = chi - chi * is_same + singleton_chi * chi * is_same
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        new_groups_eval - chi_eval + chi_eval * is_same_eval
+            - singleton_chi_eval * chi_eval * is_same_eval,
+        3,
+    )?;
+    Ok(new_groups_eval)
+}
@@ -0,0 +1,196 @@
+use super::{test_utility::*, WindowExec, WindowFunction};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_window_exec() {
+    let data = owned_table([
+        bigint("account", [2_i64, 1, 2, 1, 1, 2]),
+        bigint("ts", [20_i64, 10, 10, 30, 20, 20]),
+        bigint("amount", [5_i64, 10, -3, 7, 4, 8]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = window_exec(
+        projection(
+            cols_expr_plan(&t, &["account", "ts", "amount"], &accessor),
+            tab(&t),
+        ),
+        vec![0],
+        vec![(1, true)],
+        vec![
+            (WindowFunction::RowNumber, "rn"),
+            (WindowFunction::Rank, "rnk"),
+            (WindowFunction::Lag(2), "prev"),
+            (WindowFunction::Lead(2), "next"),
+            (WindowFunction::Sum(2), "balance"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("account", [1_i64, 1, 1, 2, 2, 2]),
+        bigint("ts", [10_i64, 20, 30, 10, 20, 20]),
+        bigint("amount", [10_i64, 4, 7, -3, 5, 8]),
+        bigint("rn", [1_i64, 2, 3, 1, 2, 3]),
+        bigint("rnk", [1_i64, 2, 3, 1, 2, 2]),
+        nullable(
+            bigint("prev", [0_i64, 10, 4, 0, -3, 5]),
+            [false, true, true, false, true, true],
+        ),
+        nullable(
+            bigint("next", [4_i64, 7, 0, 5, 8, 0]),
+            [true, true, false, true, true, false],
+        ),
+        bigint("balance", [10_i64, 14, 21, -3, 10, 10]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_running_balance_with_a_window_exec_ordered_descendingly_without_partitions() {
+    let data = owned_table([
+        int("ts", [2, 4, 1, 3]),
+        int128("amount", [100_i128, -20, 50, 30]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = window_exec(
+        projection(cols_expr_plan(&t, &["ts", "amount"], &accessor), tab(&t)),
+        vec![],
+        vec![(0, false)],
+        vec![
+            (WindowFunction::Sum(1), "balance"),
+            (WindowFunction::Lag(0), "prev_ts"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("ts", [4, 3, 2, 1]),
+        int128("amount", [-20_i128, 30, 100, 50]),
+        int128("balance", [-20_i128, 10, 110, 160]),
+        nullable(int("prev_ts", [0, 4, 3, 2]), [false, true, true, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_window_exec_over_a_single_window_of_peers() {
+    let data = owned_table([bigint("a", [3_i64, 1, 2]), varchar("b", ["x", "y", "z"])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = window_exec(
+        projection(cols_expr_plan(&t, &["a", "b"], &accessor), tab(&t)),
+        vec![],
+        vec![],
+        vec![
+            (WindowFunction::RowNumber, "rn"),
+            (WindowFunction::Rank, "rnk"),
+            (WindowFunction::Sum(0), "total"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [3_i64, 1, 2]),
+        varchar("b", ["x", "y", "z"]),
+        bigint("rn", [1_i64, 2, 3]),
+        bigint("rnk", [1_i64, 1, 1]),
+        bigint("total", [6_i64, 6, 6]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_window_exec() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3]), bigint("b", [4_i64, 5, 6])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let where_clause: DynProofExpr = equal(column(&t, "a", &accessor), const_bigint(999));
+    let ast = window_exec(
+        filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            where_clause,
+        ),
+        vec![0],
+        vec![(1, true)],
+        vec![
+            (WindowFunction::RowNumber, "rn"),
+            (WindowFunction::Lead(1), "next"),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [0_i64; 0]),
+        bigint("b", [0_i64; 0]),
+        bigint("rn", [0_i64; 0]),
+        nullable(bigint("next", [0_i64; 0]), [false; 0]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_prove_a_window_exec_with_unsupported_keys_or_arguments() {
+    let data = owned_table([
+        varchar("a", ["1", "2"]),
+        bigint("b", [1_i64, 2]),
+        boolean("c", [true, false]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let input = projection(cols_expr_plan(&t, &["a", "b", "c"], &accessor), tab(&t));
+    assert!(WindowExec::is_provable(
+        &input,
+        &[2],
+        &[(1, false)],
+        &[WindowFunction::Sum(1), WindowFunction::Lag(2)]
+    ));
+    assert!(WindowExec::is_provable(
+        &input,
+        &[],
+        &[],
+        &[WindowFunction::RowNumber]
+    ));
+    assert!(!WindowExec::is_provable(
+        &input,
+        &[0],
+        &[],
+        &[WindowFunction::RowNumber]
+    ));
+    assert!(!WindowExec::is_provable(
+        &input,
+        &[],
+        &[(1, true)],
+        &[WindowFunction::Sum(2)]
+    ));
+    assert!(!WindowExec::is_provable(
+        &input,
+        &[],
+        &[(1, true)],
+        &[WindowFunction::Lead(0)]
+    ));
+    assert!(!WindowExec::is_provable(
+        &input,
+        &[],
+        &[(1, true)],
+        &[WindowFunction::Lag(3)]
+    ));
+}
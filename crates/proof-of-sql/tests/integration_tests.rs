@@ -8,19 +8,23 @@ use proof_of_sql::base::commitment::InnerProductProof;
 use proof_of_sql::proof_primitive::hyperkzg::HyperKZGCommitmentEvaluationProof;
 use proof_of_sql::{
     base::{
+        commitment::{
+            Bounds, ColumnBounds, PartitionedTableCommitment, QueryCommitments, TableCommitment,
+        },
         database::{
-            owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+            owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, RowRangeAccessor, TableRef,
+            TestAccessor,
         },
         scalar::Curve25519Scalar,
     },
     proof_primitive::dory::{
-        DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+        DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup, DynamicDoryCommitment,
         DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
     },
     sql::{
         parse::{ConversionError, QueryExpr},
         postprocessing::apply_postprocessing_steps,
-        proof::{QueryError, VerifiableQueryResult},
+        proof::{QueryError, VerifiableMultiQueryResult, VerifiableQueryResult},
     },
 };
 
@@ -793,6 +797,495 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_group_by_query_with_averages_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            decimal75("c", 4, 2, [-2, 2, 1, 0, 2]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, avg(b) as avg_b, avg(c) as avg_c, count(*) as e FROM table group by a"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let transformed_result =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        decimal75("avg_b", 25, 6, [500_000, 3_500_000, 2_000_000]),
+        decimal75("avg_c", 8, 6, [0, 15_000, 0]),
+        bigint("e", [2, 2, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_group_by_query_with_extrema_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            bigint("b", [1, -5, 4, 2, 3, 7]),
+            decimal75("c", 4, 2, [-2, 2, 1, 0, 2, 9]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, max(b) as max_b, min(c) as min_c, count(*) as e FROM table WHERE c < 0.05 group by a"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("max_b", [1, 4, 2]),
+        decimal75("min_c", 4, 2, [-2, 1, 0]),
+        bigint("e", [2, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+    let transformed_result =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_group_by_query_with_a_having_clause_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            bigint("b", [1, -5, 4, 2, 3, 7]),
+            bigint("c", [4, 2, 1, 0, 2, 9]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(b) as sum_b, count(*) as e FROM table group by a having count(*) > 1 and max(c) < 5"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [2]), bigint("sum_b", [7]), bigint("e", [2])]);
+    assert_eq!(owned_table_result, expected_result);
+    let transformed_result =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_the_top_rows_of_an_ordered_query_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            int("b", [1, -5, 4, 2, 3, 7]),
+            varchar("c", ["x", "y", "z", "w", "v", "u"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT c, a, b FROM table WHERE b > -5 ORDER BY a DESC, b LIMIT 3 OFFSET 1"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        varchar("c", ["v", "z", "x"]),
+        bigint("a", [2, 2, 1]),
+        int("b", [3, 4, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_union_all_of_tables_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "sales_jan"),
+        owned_table([bigint("id", [1, 2, 3]), bigint("amount", [10, 40, 25])]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "sales_feb"),
+        owned_table([
+            bigint("id", [4, 5, 6, 7]),
+            bigint("amount", [30, 5, 50, 20]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT id, amount FROM sales_jan UNION ALL SELECT id, amount FROM sales_feb WHERE amount > 10 ORDER BY amount DESC LIMIT 3"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("id", [6, 2, 4]), bigint("amount", [50, 40, 30])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_union_and_a_count_of_distinct_values_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "sales_jan"),
+        owned_table([
+            bigint("customer", [1, 2, 1, 3]),
+            bigint("store", [10, 10, 10, 20]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "sales_feb"),
+        owned_table([bigint("customer", [4, 2, 5]), bigint("store", [20, 10, 10])]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        assert!(query.postprocessing().is_empty());
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table
+    };
+    assert_eq!(
+        prove_and_verify("SELECT customer FROM sales_jan UNION SELECT customer FROM sales_feb"),
+        owned_table([bigint("customer", [1, 2, 3, 4, 5])])
+    );
+    assert_eq!(
+        prove_and_verify(
+            "SELECT store, COUNT(DISTINCT customer) AS customers FROM sales_jan GROUP BY store"
+        ),
+        owned_table([bigint("store", [10, 20]), bigint("customers", [2, 1])])
+    );
+}
+
+#[test]
+fn we_can_prove_queries_over_derived_tables_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "sales"),
+        owned_table([
+            bigint("store", [1, 2, 1, 3]),
+            bigint("amount", [60, 30, 50, 200]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "employees"),
+        owned_table([bigint("id", [1, 2, 3]), bigint("salary", [10, 20, 10])]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "bonuses"),
+        owned_table([
+            bigint("employee_id", [1, 2, 3, 3]),
+            bigint("bonus", [5, 6, 7, 8]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        assert!(query.postprocessing().is_empty());
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table
+    };
+    // A filter over the groups of a group by
+    assert_eq!(
+        prove_and_verify(
+            "SELECT store, total FROM (SELECT store, SUM(amount) AS total, COUNT(*) AS num FROM sales GROUP BY store) AS totals WHERE total >= 100"
+        ),
+        owned_table([bigint("store", [1, 3]), bigint("total", [110, 200])])
+    );
+    // A group by over the rows of a join
+    assert_eq!(
+        prove_and_verify(
+            "SELECT salary, COUNT(*) AS num FROM (SELECT salary, bonus FROM employees JOIN bonuses ON employees.id = bonuses.employee_id) AS j GROUP BY salary"
+        ),
+        owned_table([bigint("salary", [10, 20]), bigint("num", [3, 1])])
+    );
+}
+
+#[test]
+fn we_can_prove_queries_with_subqueries_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "accounts"),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            bigint("balance", [60, 30, 50, 200]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "allow_list"),
+        owned_table([
+            bigint("account_id", [4, 1, 4]),
+            bigint("limit_amount", [40, 70, 100]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query = QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap();
+        assert!(query.postprocessing().is_empty());
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table
+    };
+    // A semi-join against an allow list
+    assert_eq!(
+        prove_and_verify("SELECT id FROM accounts WHERE id IN (SELECT account_id FROM allow_list)"),
+        owned_table([bigint("id", [1, 4])])
+    );
+    // A comparison with a scalar aggregate
+    assert_eq!(
+        prove_and_verify(
+            "SELECT id FROM accounts WHERE balance > (SELECT MAX(limit_amount) FROM allow_list)"
+        ),
+        owned_table([bigint("id", [4])])
+    );
+}
+
+#[test]
+fn we_can_prove_queries_with_window_functions_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "ledger"),
+        owned_table([
+            bigint("account", [1, 2, 1, 2, 1]),
+            bigint("ts", [3, 1, 1, 2, 2]),
+            bigint("amount", [10, 5, 20, -5, 30]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT account, ts, SUM(amount) OVER (PARTITION BY account ORDER BY ts) AS balance, \
+            LAG(amount) OVER (PARTITION BY account ORDER BY ts) AS prev, \
+            ROW_NUMBER() OVER (ORDER BY ts DESC, account) AS recency FROM ledger"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    // The rows are ordered by the last window
+    let expected_result = owned_table([
+        bigint("account", [1, 1, 2, 1, 2]),
+        bigint("ts", [3, 2, 2, 1, 1]),
+        bigint("balance", [60, 50, 0, 20, 5]),
+        bigint("prev", [30, 20, 5, 0, 0]),
+        bigint("recency", [1, 2, 3, 4, 5]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_with_common_table_expressions_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "accounts"),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            bigint("balance", [60, 30, 50, 200]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "allow_list"),
+        owned_table([
+            bigint("account_id", [4, 1, 4]),
+            bigint("limit_amount", [40, 70, 100]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "WITH allowed AS (SELECT account_id FROM allow_list WHERE limit_amount > 50), \
+            rich AS (SELECT id, balance FROM accounts WHERE id IN (SELECT account_id FROM allowed)) \
+            SELECT id, balance FROM rich WHERE balance >= 100"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("id", [4]), bigint("balance", [200])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 // Overflow checks
 #[test]
 #[cfg(feature = "blitzar")]
@@ -849,6 +1342,148 @@ fn we_can_prove_a_query_with_overflow_with_dory() {
     ));
 }
 
+#[test]
+fn we_can_verify_a_batch_of_queries_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([bigint("a", [1_i64, 2, 3]), bigint("b", [4_i64, 5, 6])]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "other"),
+        owned_table([varchar("c", ["x", "y"])]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "empty"),
+        owned_table([bigint("d", [0_i64; 0])]),
+        0,
+    );
+    let queries: Vec<_> = [
+        "SELECT a, b FROM table WHERE a > 1",
+        "SELECT c FROM other WHERE c = 'y'",
+        "SELECT d FROM empty",
+        "SELECT a + b AS s FROM table",
+    ]
+    .into_iter()
+    .map(|sql| QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor).unwrap())
+    .collect();
+    let verifiable_results: Vec<_> = queries
+        .iter()
+        .map(|query| {
+            VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+                query.proof_expr(),
+                &accessor,
+                &&prover_setup,
+            )
+        })
+        .collect();
+
+    let batch_results = VerifiableQueryResult::verify_batch(
+        verifiable_results
+            .iter()
+            .cloned()
+            .zip(queries.iter().map(QueryExpr::proof_expr)),
+        &accessor,
+        &&verifier_setup,
+    );
+    assert_eq!(batch_results.len(), 4);
+    for ((batch_result, verifiable_result), query) in batch_results
+        .into_iter()
+        .zip(&verifiable_results)
+        .zip(&queries)
+    {
+        let batch_result = batch_result.unwrap();
+        let result = verifiable_result
+            .clone()
+            .verify(query.proof_expr(), &accessor, &&verifier_setup)
+            .unwrap();
+        assert_eq!(batch_result.table, result.table);
+        assert_eq!(batch_result.verification_hash, result.verification_hash);
+    }
+
+    // Only the result that does not match its query is rejected
+    let batch_results = VerifiableQueryResult::verify_batch(
+        [
+            (verifiable_results[0].clone(), queries[0].proof_expr()),
+            (verifiable_results[1].clone(), queries[3].proof_expr()),
+            (verifiable_results[3].clone(), queries[3].proof_expr()),
+        ],
+        &accessor,
+        &&verifier_setup,
+    );
+    assert_eq!(
+        batch_results[0].as_ref().unwrap().table,
+        owned_table([bigint("a", [2_i64, 3]), bigint("b", [5_i64, 6])])
+    );
+    assert!(matches!(
+        batch_results[1],
+        Err(QueryError::ProofError { .. })
+    ));
+    assert_eq!(
+        batch_results[2].as_ref().unwrap().table,
+        owned_table([bigint("s", [5_i64, 7, 9])])
+    );
+}
+
+#[test]
+fn we_can_prove_many_queries_at_once_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_empty_with_setup(&prover_setup);
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        owned_table([bigint("a", [1_i64, 2, 3]), bigint("b", [4_i64, 5, 6])]),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "other"),
+        owned_table([varchar("c", ["x", "y"])]),
+        0,
+    );
+    let proof_exprs: Vec<_> = [
+        "SELECT a, b FROM table WHERE a > 1",
+        "SELECT c FROM other WHERE c = 'y'",
+        "SELECT a + b AS s FROM table",
+    ]
+    .into_iter()
+    .map(|sql| {
+        QueryExpr::try_new(sql.parse().unwrap(), "sxt".into(), &accessor)
+            .unwrap()
+            .proof_expr()
+            .clone()
+    })
+    .collect();
+    let verifiable_result = VerifiableMultiQueryResult::<DynamicDoryEvaluationProof>::new(
+        &proof_exprs,
+        &accessor,
+        &&prover_setup,
+    );
+    let tables: Vec<_> = verifiable_result
+        .verify(&proof_exprs, &accessor, &&verifier_setup)
+        .unwrap()
+        .into_iter()
+        .map(|result| result.unwrap().table)
+        .collect();
+    assert_eq!(
+        tables,
+        vec![
+            owned_table([bigint("a", [2_i64, 3]), bigint("b", [5_i64, 6])]),
+            owned_table([varchar("c", ["y"])]),
+            owned_table([bigint("s", [5_i64, 7, 9])]),
+        ]
+    );
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_perform_arithmetic_and_conditional_operations_on_tinyint() {
@@ -879,3 +1514,89 @@ fn we_can_perform_arithmetic_and_conditional_operations_on_tinyint() {
     let expected_result = owned_table([tinyint("result", [9_i8, 10])]);
     assert_eq!(owned_table_result, expected_result);
 }
+
+#[test]
+fn we_can_prove_a_range_restricted_query_against_committed_partitions_with_dynamic_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+
+    let table_ref = TableRef::new("sxt", "blocks");
+    let data = owned_table([
+        bigint("block_number", [1_i64, 1, 2, 3, 3, 4, 5, 5, 6]),
+        bigint("value", [10_i64, 11, 20, 30, 31, 40, 50, 51, 60]),
+    ]);
+    let accessor = OwnedTableTestAccessor::<DynamicDoryEvaluationProof>::new_from_table(
+        table_ref.clone(),
+        data.clone(),
+        0,
+        &prover_setup,
+    );
+
+    // The database commits to the table three rows at a time
+    let partitioned_commitment = PartitionedTableCommitment::try_new(
+        (0..3)
+            .map(|i| {
+                let partition = OwnedTable::try_from_iter(
+                    data.inner_table()
+                        .iter()
+                        .map(|(id, column)| (id.clone(), column.slice(3 * i, 3 * i + 3))),
+                )
+                .unwrap();
+                TableCommitment::<DynamicDoryCommitment>::from_owned_table_with_offset(
+                    &partition,
+                    3 * i,
+                    &&prover_setup,
+                )
+            })
+            .collect(),
+    )
+    .unwrap();
+
+    // Only the partitions that can contain blocks 3 through 4 are needed
+    let partitions = partitioned_commitment
+        .try_partitions_overlapping_bounds(
+            &"block_number".into(),
+            &ColumnBounds::BigInt(Bounds::sharp(3, 4).unwrap()),
+        )
+        .unwrap();
+    assert_eq!(partitions, 1..2);
+    let commitment = partitioned_commitment
+        .try_combine_partitions(partitions)
+        .unwrap();
+    let rows = commitment.range().clone();
+    let mut query_commitments = QueryCommitments::default();
+    query_commitments.insert(table_ref.clone(), commitment);
+
+    let query = QueryExpr::try_new(
+        "SELECT value FROM blocks WHERE block_number >= 3 AND block_number <= 4"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &query_commitments,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
+        query.proof_expr(),
+        &RowRangeAccessor::new(&accessor, [(table_ref, rows)]),
+        &&prover_setup,
+    );
+    let owned_table_result = verifiable_result
+        .clone()
+        .verify(query.proof_expr(), &query_commitments, &&verifier_setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("value", [30_i64, 31, 40])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    // The result cannot be verified against the rest of the table
+    let full_commitments: QueryCommitments<DynamicDoryCommitment> = [(
+        TableRef::new("sxt", "blocks"),
+        partitioned_commitment.try_combine_partitions(0..3).unwrap(),
+    )]
+    .into_iter()
+    .collect();
+    assert!(verifiable_result
+        .verify(query.proof_expr(), &full_commitments, &&verifier_setup)
+        .is_err());
+}